@@ -0,0 +1,58 @@
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+
+use kenseader_core::{
+    storage::{export_archive, import_archive, Database, WriterLock},
+    AppConfig,
+};
+
+pub async fn export(config: &AppConfig, file_path: &str) -> Result<()> {
+    // Reading is safe next to a running daemon; only migrate when we own the database
+    let lock = WriterLock::try_acquire(config)?;
+    let db = if lock.is_some() {
+        Database::new(config).await?
+    } else {
+        Database::open(config).await?
+    };
+
+    let file = File::create(file_path).with_context(|| format!("Cannot create {}", file_path))?;
+    let stats = export_archive(&db, BufWriter::new(file)).await?;
+
+    println!("Exported to {}:", file_path);
+    println!("  {} feeds", stats.feeds);
+    println!("  {} articles", stats.articles);
+    println!("  {} style classifications", stats.styles);
+    println!("  {} preferences", stats.preferences);
+    println!("  {} behavior events", stats.events);
+
+    Ok(())
+}
+
+pub async fn import(config: &AppConfig, file_path: &str) -> Result<()> {
+    let path = Path::new(file_path);
+    if !path.exists() {
+        println!("File not found: {}", file_path);
+        return Ok(());
+    }
+
+    let Some(_lock) = WriterLock::try_acquire(config)? else {
+        bail!("The database is in use by another kenseader process. Stop the daemon and try again.");
+    };
+
+    let db = Database::new(config).await?;
+    let file = File::open(path)?;
+    let stats = import_archive(&db, BufReader::new(file)).await?;
+
+    println!("Imported from {}:", file_path);
+    println!("  {} new feeds", stats.feeds);
+    println!("  {} new articles", stats.articles);
+    println!("  {} style classifications", stats.styles);
+    println!("  {} preferences", stats.preferences);
+    println!("  {} new behavior events", stats.events);
+    println!("\nExisting feeds and articles were kept unchanged.");
+
+    Ok(())
+}
@@ -0,0 +1,16 @@
+use anyhow::{Context, Result};
+
+use kenseader_core::{calendar::digest_calendar, AppConfig};
+
+/// Write the calendar of upcoming digests to `output`, or print it
+pub fn run(config: &AppConfig, days: u32, output: Option<&str>) -> Result<()> {
+    let ics = digest_calendar(config, days);
+    match output {
+        Some(path) => {
+            std::fs::write(path, ics).with_context(|| format!("Cannot write {}", path))?;
+            println!("Wrote the digest calendar to {}", path);
+        }
+        None => print!("{}", ics),
+    }
+    Ok(())
+}
@@ -1,18 +1,14 @@
 use anyhow::Result;
 
-use kenseader_core::{
-    scheduler::cleanup_old_articles,
-    storage::Database,
-    AppConfig,
-};
+use kenseader_core::{ipc::DaemonClient, AppConfig};
 
-pub async fn run(db: &Database, config: &AppConfig) -> Result<()> {
+pub async fn run(client: &DaemonClient, config: &AppConfig) -> Result<()> {
     println!(
         "Cleaning up articles older than {} days...",
         config.general.article_retention_days
     );
 
-    let deleted = cleanup_old_articles(db, config).await?;
+    let deleted = client.cleanup().await?;
 
     if deleted > 0 {
         println!("Deleted {} old articles.", deleted);
@@ -4,17 +4,22 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use anyhow::Result;
-use tokio::sync::watch;
+use tokio::sync::{broadcast, watch};
+use tokio::task::JoinHandle;
 use tracing::{info, warn};
 
 use kenseader_core::{
     ai::Summarizer,
-    ipc::DaemonServer,
-    scheduler::SchedulerService,
-    storage::Database,
+    feed::bandwidth,
+    ipc::{self, DaemonClient, DaemonServer, SchedulerTask},
+    scheduler::{NextRefresh, SchedulerService},
+    storage::{Database, WriterLock},
     AppConfig,
 };
 
+/// Scheduler events buffered per subscriber before it starts skipping them
+const EVENT_CHANNEL_CAPACITY: usize = 64;
+
 /// Get the PID file path
 fn pid_file_path() -> PathBuf {
     dirs::runtime_dir()
@@ -157,7 +162,7 @@ fn migrate_data_dir(old_dir: &Path, new_dir: &Path) -> Result<bool> {
 
 /// Check and perform data migration if needed.
 /// This must be called BEFORE Database::new() to ensure proper migration.
-pub fn maybe_migrate_data(config: &AppConfig) -> Result<()> {
+fn maybe_migrate_data(config: &AppConfig) -> Result<()> {
     let current_data_dir = config.data_dir();
     let default_data_dir = dirs::data_local_dir()
         .unwrap_or_else(|| PathBuf::from("."))
@@ -216,7 +221,7 @@ pub fn maybe_migrate_data(config: &AppConfig) -> Result<()> {
 ///
 /// When `foreground` is true, the daemon runs in foreground mode (for launchd/systemd/brew services).
 /// In foreground mode, PID file management is skipped since the service manager handles process lifecycle.
-pub async fn start(db: Arc<Database>, config: Arc<AppConfig>, foreground: bool) -> Result<()> {
+pub async fn start(config: Arc<AppConfig>, foreground: bool) -> Result<()> {
     if !foreground {
         // Check if already running (only in background mode)
         if let Some(pid) = is_daemon_running() {
@@ -225,6 +230,23 @@ pub async fn start(db: Arc<Database>, config: Arc<AppConfig>, foreground: bool)
         }
     }
 
+    // Become the single database writer; wait for CLI commands still writing
+    let _lock = match WriterLock::try_acquire(&config)? {
+        Some(lock) => lock,
+        None => {
+            if ipc::is_daemon_running(&config.socket_path()).await {
+                println!("Daemon is already running.");
+                return Ok(());
+            }
+            println!("Waiting for another kenseader process to release the database...");
+            WriterLock::acquire(&config).await?
+        }
+    };
+
+    // Migrate data before database init
+    maybe_migrate_data(&config)?;
+    let db = Arc::new(Database::new(&config).await?);
+
     println!("Starting kenseader daemon{}...", if foreground { " (foreground mode)" } else { "" });
 
     // Write PID file (only in background mode)
@@ -243,9 +265,67 @@ pub async fn start(db: Arc<Database>, config: Arc<AppConfig>, foreground: bool)
         let _ = shutdown_tx_clone.send(true);
     });
 
+    let (scheduler, ipc_server) = build_services(&db, &config);
+
+    println!(
+        "Daemon started (PID: {}). Press Ctrl+C or run 'kenseader daemon stop' to stop.",
+        std::process::id()
+    );
+    println!("  Refresh interval: {} seconds", config.sync.refresh_interval_secs);
+    println!("  Cleanup interval: {} seconds", config.sync.cleanup_interval_secs);
+    println!("  Summarize interval: {} seconds", config.sync.summarize_interval_secs);
+    println!("  IPC socket: {}", config.socket_path().display());
+
+    run_services(scheduler, ipc_server, shutdown_rx).await;
+
+    // Cleanup
+    if !foreground {
+        remove_pid_file();
+    }
+    println!("Daemon stopped.");
+
+    Ok(())
+}
+
+/// Daemon services running inside the TUI process
+pub struct EmbeddedDaemon {
+    shutdown_tx: watch::Sender<bool>,
+    handle: JoinHandle<()>,
+    _lock: WriterLock,
+}
+
+impl EmbeddedDaemon {
+    /// Stop the scheduler and IPC server and wait for them to finish
+    pub async fn shutdown(self) {
+        let _ = self.shutdown_tx.send(true);
+        let _ = self.handle.await;
+    }
+}
+
+/// Start the scheduler and IPC server as a background task of the current
+/// process, which must hold the writer lock
+pub fn spawn_embedded(db: Arc<Database>, config: Arc<AppConfig>, lock: WriterLock) -> EmbeddedDaemon {
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    let (scheduler, ipc_server) = build_services(&db, &config);
+
+    info!("Starting embedded daemon");
+    let handle = tokio::spawn(run_services(scheduler, ipc_server, shutdown_rx));
+
+    EmbeddedDaemon {
+        shutdown_tx,
+        handle,
+        _lock: lock,
+    }
+}
+
+/// Build the scheduler service and IPC server
+fn build_services(db: &Arc<Database>, config: &Arc<AppConfig>) -> (SchedulerService, DaemonServer) {
+    // Until a client switches it
+    bandwidth::set_metered(config.metered.enabled);
+
     // Create summarizer if AI is enabled
     let summarizer = if config.ai.enabled {
-        match Summarizer::new(&config) {
+        match Summarizer::new(config) {
             Ok(s) => {
                 info!("AI summarization enabled (provider: {})", config.ai.provider);
                 Some(Arc::new(s))
@@ -260,28 +340,40 @@ pub async fn start(db: Arc<Database>, config: Arc<AppConfig>, foreground: bool)
         None
     };
 
+    // Scheduler events, streamed to clients subscribed over IPC
+    let (events_tx, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+
+    let next_refresh = NextRefresh::default();
+
     // Build scheduler service
     let scheduler = {
-        let mut svc = SchedulerService::new(db.clone(), config.clone());
+        let mut svc = SchedulerService::new(db.clone(), config.clone())
+            .with_event_sender(events_tx.clone())
+            .with_next_refresh(next_refresh.clone());
         if let Some(ref s) = summarizer {
             svc = svc.with_summarizer(s.clone());
         }
         svc
     };
 
-    // Create IPC server
-    let ipc_server = DaemonServer::new(db.clone(), config.clone());
+    // Create IPC server (sharing the summarizer's AI request pool)
+    let ipc_server = {
+        let server = DaemonServer::new(db.clone(), config.clone()).with_events(events_tx, next_refresh);
+        match summarizer {
+            Some(s) => server.with_summarizer(s),
+            None => server,
+        }
+    };
 
-    println!(
-        "Daemon started (PID: {}). Press Ctrl+C or run 'kenseader daemon stop' to stop.",
-        std::process::id()
-    );
-    println!("  Refresh interval: {} seconds", config.sync.refresh_interval_secs);
-    println!("  Cleanup interval: {} seconds", config.sync.cleanup_interval_secs);
-    println!("  Summarize interval: {} seconds", config.sync.summarize_interval_secs);
-    println!("  IPC socket: {}", config.socket_path().display());
+    (scheduler, ipc_server)
+}
 
-    // Run scheduler and IPC server in parallel
+/// Run scheduler and IPC server in parallel until shutdown
+async fn run_services(
+    scheduler: SchedulerService,
+    ipc_server: DaemonServer,
+    shutdown_rx: watch::Receiver<bool>,
+) {
     let scheduler_shutdown_rx = shutdown_rx.clone();
     let ipc_shutdown_rx = shutdown_rx;
 
@@ -295,14 +387,6 @@ pub async fn start(db: Arc<Database>, config: Arc<AppConfig>, foreground: bool)
             }
         }
     }
-
-    // Cleanup
-    if !foreground {
-        remove_pid_file();
-    }
-    println!("Daemon stopped.");
-
-    Ok(())
 }
 
 /// Stop the daemon
@@ -353,12 +437,64 @@ pub async fn stop() -> Result<()> {
     Ok(())
 }
 
+/// Switch the running daemon's metered mode on or off
+pub async fn metered(config: &AppConfig, enabled: bool) -> Result<()> {
+    let client = DaemonClient::new(config.socket_path());
+    if !client.ping().await? {
+        anyhow::bail!("Daemon is not running (set metered.enabled in the config to start in metered mode)");
+    }
+    client.set_metered(enabled).await?;
+    println!("Metered mode {}", if enabled { "on" } else { "off" });
+    Ok(())
+}
+
+/// Run a scheduler task immediately, in the daemon when it is running
+pub async fn run_task(config: &Arc<AppConfig>, name: &str) -> Result<()> {
+    let Some(task) = SchedulerTask::ALL.into_iter().find(|task| task.name() == name) else {
+        let names: Vec<&str> = SchedulerTask::ALL.iter().map(|task| task.name()).collect();
+        anyhow::bail!("Unknown task '{}' (expected one of: {})", name, names.join(", "));
+    };
+
+    let (client, _lock) = super::connect(config).await?;
+    println!("Running task '{}'...", name);
+    let summary = client.run_task(task).await?;
+    println!("Done: {}", summary);
+
+    Ok(())
+}
+
 /// Show daemon status
-pub async fn status() -> Result<()> {
+pub async fn status(config: &AppConfig) -> Result<()> {
     match is_daemon_running() {
         Some(pid) => {
             println!("Daemon is running (PID: {})", pid);
             println!("PID file: {}", pid_file_path().display());
+
+            let client = DaemonClient::new(config.socket_path());
+            if let Ok(status) = client.status().await {
+                println!("Uptime: {}s", status.uptime_secs);
+                if let Some(secs) = status.next_refresh_secs {
+                    println!("Next refresh in: {}s", secs);
+                }
+                println!(
+                    "Metered mode: {} ({:.1} MB fetched today)",
+                    if status.metered { "on" } else { "off" },
+                    status.bandwidth_today_bytes as f64 / (1024.0 * 1024.0)
+                );
+                if let Some(queue) = status.ai_queue {
+                    println!("AI queue:");
+                    for stats in queue {
+                        println!(
+                            "  {:<10} {} queued, {} running, {} done, avg wait {}ms",
+                            stats.task.name(),
+                            stats.queued,
+                            stats.running,
+                            stats.completed,
+                            stats.avg_wait_ms
+                        );
+                    }
+                }
+            }
         }
         None => {
             println!("Daemon is not running.");
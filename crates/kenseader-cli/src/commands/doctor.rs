@@ -0,0 +1,64 @@
+use anyhow::Result;
+
+use kenseader_core::ipc::DaemonClient;
+
+/// Check the daemon and the subscriptions for problems, switching moved
+/// feeds to their new URL when `update_moved` is set
+pub async fn run(client: &DaemonClient, update_moved: bool) -> Result<()> {
+    if client.is_local() {
+        println!("Daemon: not running (start it with `kenseader daemon start`)");
+    } else {
+        println!("Daemon: running");
+    }
+
+    let feeds = client.list_feeds().await?;
+    let paused = feeds.iter().filter(|f| f.paused).count();
+    println!("Subscriptions: {} ({} paused)", feeds.len(), paused);
+
+    let failing: Vec<_> = feeds
+        .iter()
+        .filter_map(|f| f.fetch_error.as_ref().map(|error| (f, error)))
+        .collect();
+    if !failing.is_empty() {
+        println!("\nFeeds failing to fetch ({}):", failing.len());
+        for (feed, error) in &failing {
+            println!("  {} - {}", feed.local_name, error);
+        }
+    }
+
+    let moved: Vec<_> = feeds
+        .iter()
+        .filter_map(|f| f.moved_to.as_ref().map(|new_url| (f, new_url)))
+        .collect();
+    if !moved.is_empty() {
+        println!("\nFeeds that moved permanently ({}):", moved.len());
+        for (feed, new_url) in &moved {
+            println!("  {} - {} -> {}", feed.local_name, feed.url, new_url);
+        }
+        if update_moved {
+            for (feed, new_url) in &moved {
+                let mut settings = feed.settings();
+                settings.url = new_url.to_string();
+                client.update_feed(feed.id, &settings).await?;
+            }
+            println!("Updated {} feed URLs.", moved.len());
+        } else {
+            println!("\nSwitch them to the new URLs with `kenseader doctor --update-moved`.");
+        }
+    }
+
+    let suggestions = client.prune_suggestions().await?;
+    if !suggestions.is_empty() {
+        println!("\nConsider unsubscribing ({}):", suggestions.len());
+        for suggestion in &suggestions {
+            println!("  {} - {}", suggestion.name, suggestion.reasons.join(", "));
+        }
+        println!("\nUnsubscribe with `kenseader unsubscribe <name>`, or pause with `kenseader pause <name>`.");
+    }
+
+    if failing.is_empty() && moved.is_empty() && suggestions.is_empty() {
+        println!("\nNo problems found.");
+    }
+
+    Ok(())
+}
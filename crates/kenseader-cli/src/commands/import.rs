@@ -1,15 +1,16 @@
+use std::collections::HashSet;
 use std::io::{self, Write};
 use std::path::Path;
 
 use anyhow::Result;
 
 use kenseader_core::{
-    feed::{parse_opml_file, FeedFetcher, NewFeed},
-    storage::{ArticleRepository, Database, FeedRepository},
+    feed::{parse_opml_file, FeedFetcher},
+    ipc::DaemonClient,
     AppConfig,
 };
 
-pub async fn run(db: &Database, config: &AppConfig, file_path: &str) -> Result<()> {
+pub async fn run(client: &DaemonClient, config: &AppConfig, file_path: &str) -> Result<()> {
     let path = Path::new(file_path);
 
     if !path.exists() {
@@ -25,9 +26,13 @@ pub async fn run(db: &Database, config: &AppConfig, file_path: &str) -> Result<(
         return Ok(());
     }
 
-    let feed_repo = FeedRepository::new(db);
-    let article_repo = ArticleRepository::new(db);
     let fetcher = FeedFetcher::new(config)?;
+    let mut subscribed: HashSet<String> = client
+        .list_feeds()
+        .await?
+        .into_iter()
+        .map(|f| f.url)
+        .collect();
 
     let mut imported = 0u32;
     let mut skipped = 0u32;
@@ -56,19 +61,14 @@ pub async fn run(db: &Database, config: &AppConfig, file_path: &str) -> Result<(
         };
 
         // Check if already subscribed
-        if feed_repo.find_by_url(&resolved_url).await?.is_some() {
+        if subscribed.contains(&resolved_url) {
             println!("already subscribed");
             skipped += 1;
             continue;
         }
 
         // Create feed
-        let new_feed = NewFeed {
-            url: resolved_url.clone(),
-            local_name: opml_feed.name.clone(),
-        };
-
-        let feed = match feed_repo.create(&new_feed).await {
+        let feed = match client.add_feed(&resolved_url, &opml_feed.name).await {
             Ok(f) => f,
             Err(e) => {
                 println!("failed to create: {}", e);
@@ -76,23 +76,11 @@ pub async fn run(db: &Database, config: &AppConfig, file_path: &str) -> Result<(
                 continue;
             }
         };
+        subscribed.insert(resolved_url);
 
         // Fetch articles (optional, don't fail if fetch fails)
-        match fetcher.fetch(&resolved_url, feed.id).await {
-            Ok(parsed) => {
-                // Update feed metadata
-                feed_repo
-                    .update_metadata(
-                        feed.id,
-                        parsed.title.as_deref(),
-                        parsed.description.as_deref(),
-                        parsed.site_url.as_deref(),
-                        parsed.icon_url.as_deref(),
-                    )
-                    .await
-                    .ok();
-
-                let count = article_repo.create_many(&parsed.articles).await.unwrap_or(0);
+        match client.refresh(Some(feed.id)).await {
+            Ok(count) => {
                 println!("OK ({} articles)", count);
             }
             Err(_) => {
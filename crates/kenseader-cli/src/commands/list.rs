@@ -1,10 +1,9 @@
 use anyhow::Result;
 
-use kenseader_core::storage::{Database, FeedRepository};
+use kenseader_core::ipc::DaemonClient;
 
-pub async fn run(db: &Database) -> Result<()> {
-    let feed_repo = FeedRepository::new(db);
-    let feeds = feed_repo.list_all().await?;
+pub async fn run(client: &DaemonClient) -> Result<()> {
+    let feeds = client.list_feeds().await?;
 
     if feeds.is_empty() {
         println!("No subscriptions yet.");
@@ -28,9 +27,11 @@ pub async fn run(db: &Database) -> Result<()> {
             String::new()
         };
 
+        let paused = if feed.paused { " [paused]" } else { "" };
+
         let title = feed.title.as_deref().unwrap_or("(no title)");
 
-        println!("  {} - {}{}{}", feed.local_name, title, unread, error);
+        println!("  {} - {}{}{}{}", feed.local_name, title, unread, paused, error);
         println!("    URL: {}", feed.url);
         if let Some(last) = feed.last_fetched_at {
             println!("    Last fetched: {}", last.format("%Y-%m-%d %H:%M"));
@@ -0,0 +1,40 @@
+use anyhow::{bail, Result};
+
+use kenseader_core::{
+    storage::{latest_version, Database, WriterLock},
+    AppConfig,
+};
+
+pub async fn run(config: &AppConfig, dry_run: bool) -> Result<()> {
+    let db = Database::open(config).await?;
+
+    let current = db.schema_version().await?;
+    let pending = db.pending_migrations().await?;
+
+    println!("Database: {}", config.database_path().display());
+    println!("Schema version: {} (latest: {})", current, latest_version());
+
+    if pending.is_empty() {
+        println!("\nDatabase schema is up to date.");
+        return Ok(());
+    }
+
+    println!("\nPending migrations ({}):", pending.len());
+    for migration in &pending {
+        println!("  {:03} {}", migration.version, migration.description);
+    }
+
+    if dry_run {
+        println!("\nDry run: no changes made.");
+        return Ok(());
+    }
+
+    let Some(_lock) = WriterLock::try_acquire(config)? else {
+        bail!("The database is in use by another kenseader process. Stop the daemon and try again.");
+    };
+
+    let applied = db.migrate().await?;
+    println!("\nApplied {} migrations.", applied.len());
+
+    Ok(())
+}
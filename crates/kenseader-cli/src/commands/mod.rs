@@ -1,8 +1,74 @@
+pub mod archive;
+pub mod calendar;
 pub mod cleanup;
 pub mod daemon;
+pub mod doctor;
 pub mod import;
 pub mod list;
+pub mod migrate;
+pub mod pause;
+pub mod profile;
 pub mod refresh;
 pub mod run;
+pub mod secret;
 pub mod subscribe;
 pub mod unsubscribe;
+
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use kenseader_core::{
+    ipc::DaemonClient,
+    storage::{Database, WriterLock},
+    AppConfig,
+};
+
+/// Connect a CLI command to the database owner.
+///
+/// Uses the daemon when it holds the writer lock; otherwise takes the lock and
+/// serves requests in-process. Keep the returned lock alive until the command
+/// finishes.
+pub async fn connect(config: &Arc<AppConfig>) -> Result<(DaemonClient, Option<WriterLock>)> {
+    let lock = match WriterLock::try_acquire(config)? {
+        Some(lock) => lock,
+        None => {
+            let daemon = DaemonClient::new(config.socket_path());
+            if daemon.ping().await? {
+                return Ok((daemon, None));
+            }
+            // Another command (or a daemon still starting up) is writing
+            println!("Waiting for another kenseader process to release the database...");
+            WriterLock::acquire(config).await?
+        }
+    };
+
+    let db = Arc::new(Database::new(config).await?);
+    Ok((DaemonClient::local(db, config.clone()), Some(lock)))
+}
+
+/// Connect a read-only CLI command to the database.
+///
+/// Uses the daemon when it is running; otherwise reads the database in-process
+/// without taking the writer lock, so it doesn't wait for (or block) a writer.
+pub async fn connect_reader(config: &Arc<AppConfig>) -> Result<DaemonClient> {
+    let daemon = DaemonClient::new(config.socket_path());
+    if daemon.ping().await? {
+        return Ok(daemon);
+    }
+
+    let db = open_migrated(config).await?;
+    Ok(DaemonClient::local(Arc::new(db), config.clone()))
+}
+
+/// Open the database without holding the writer lock, taking it only to
+/// apply pending migrations
+pub async fn open_migrated(config: &AppConfig) -> Result<Database> {
+    let db = Database::open(config).await?;
+    if !db.pending_migrations().await?.is_empty() {
+        // Migrating writes, so it needs the lock
+        let _lock = WriterLock::acquire(config).await?;
+        db.migrate().await?;
+    }
+    Ok(db)
+}
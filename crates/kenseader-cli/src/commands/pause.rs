@@ -0,0 +1,37 @@
+use anyhow::Result;
+
+use kenseader_core::ipc::DaemonClient;
+
+/// Pause (`paused = true`) or resume fetching a feed
+pub async fn run(client: &DaemonClient, name: &str, paused: bool) -> Result<()> {
+    let feeds = client.list_feeds().await?;
+
+    // Find feed by name
+    let feed = feeds.iter().find(|f| f.local_name == name);
+
+    match feed {
+        Some(f) if f.paused == paused => {
+            let state = if paused { "paused" } else { "active" };
+            println!("{} is already {}.", name, state);
+        }
+        Some(f) => {
+            let mut settings = f.settings();
+            settings.paused = paused;
+            client.update_feed(f.id, &settings).await?;
+            if paused {
+                println!("Paused: {} (articles are kept; resume with `kenseader resume`)", name);
+            } else {
+                println!("Resumed: {}", name);
+            }
+        }
+        None => {
+            println!("Feed '{}' not found.", name);
+            println!("\nAvailable subscriptions:");
+            for f in &feeds {
+                println!("  - {}", f.local_name);
+            }
+        }
+    }
+
+    Ok(())
+}
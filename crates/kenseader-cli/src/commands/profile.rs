@@ -0,0 +1,286 @@
+use anyhow::Result;
+
+use kenseader_core::{
+    feed::FetchProfile,
+    ipc::{CompareScoringParams, DaemonClient},
+    profile::{ComparedArticle, PreferenceInsight, ScoringSide, TimeWindow},
+    AppConfig,
+};
+
+/// Width of the feed name column
+const NAME_WIDTH: usize = 24;
+
+/// Width of the bars of `profile show`
+const BAR_WIDTH: usize = 30;
+
+/// Width of the article titles of `profile compare`
+const TITLE_WIDTH: usize = 60;
+
+fn format_ms(ms: f64) -> String {
+    if ms < 1000.0 {
+        format!("{:.0}ms", ms)
+    } else {
+        format!("{:.1}s", ms / 1000.0)
+    }
+}
+
+fn format_bytes(bytes: f64) -> String {
+    if bytes < 1024.0 {
+        format!("{:.0} B", bytes)
+    } else if bytes < 1024.0 * 1024.0 {
+        format!("{:.0} KB", bytes / 1024.0)
+    } else {
+        format!("{:.1} MB", bytes / (1024.0 * 1024.0))
+    }
+}
+
+fn truncate_name(name: &str, width: usize) -> String {
+    if name.chars().count() <= width {
+        name.to_string()
+    } else {
+        let mut short: String = name.chars().take(width - 1).collect();
+        short.push('…');
+        short
+    }
+}
+
+/// Bar of `width` scaled so that `max` fills it, at least one block for
+/// any positive value
+fn bar(value: f64, max: f64, width: usize) -> String {
+    if value <= 0.0 || max <= 0.0 {
+        return String::new();
+    }
+    let blocks = ((value / max) * width as f64).round() as usize;
+    "█".repeat(blocks.clamp(1, width))
+}
+
+/// Print a section of the profile: each preference with a bar relative to
+/// the heaviest, and its weight
+fn print_preferences(title: &str, prefs: &[PreferenceInsight]) {
+    if prefs.is_empty() {
+        return;
+    }
+    println!("\n{}", title);
+    let max = prefs.iter().map(|p| p.weight.abs()).fold(0.0, f64::max);
+    for pref in prefs {
+        println!(
+            "  {:<width$} {:<bar_width$} {:>7.1}",
+            truncate_name(&pref.label, NAME_WIDTH),
+            bar(pref.weight.abs(), max, BAR_WIDTH),
+            pref.weight,
+            width = NAME_WIDTH,
+            bar_width = BAR_WIDTH
+        );
+    }
+}
+
+/// Show the interest profile learned in a time window
+pub async fn show(client: &DaemonClient, window: TimeWindow, limit: usize) -> Result<()> {
+    let insights = client.profile_insights(window, limit).await?;
+    if insights.is_empty() {
+        println!("Nothing learned about your interests in the {} yet.", window.label());
+        println!("Preferences are computed from your reading whenever articles are scored.");
+        return Ok(());
+    }
+
+    match insights.computed_at {
+        Some(at) => println!(
+            "Interest profile of the {} (computed {})",
+            window.label(),
+            at.format("%Y-%m-%d %H:%M UTC")
+        ),
+        None => println!("Interest profile of the {}", window.label()),
+    }
+    print_preferences("Top tags", &insights.tags);
+    print_preferences("Tags asked for less of", &insights.avoided_tags);
+    print_preferences("Top feeds", &insights.feeds);
+    print_preferences("Article styles", &insights.styles);
+
+    if !insights.times.is_empty() {
+        println!("\nReading by time of day");
+        let total: f64 = insights.times.iter().map(|t| t.weight).sum();
+        let max = insights.times.iter().map(|t| t.weight).fold(0.0, f64::max);
+        for time in &insights.times {
+            println!(
+                "  {:<10} {:<bar_width$} {:>3.0}%",
+                time.label,
+                bar(time.weight, max, BAR_WIDTH),
+                time.weight / total * 100.0,
+                bar_width = BAR_WIDTH
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Describe the settings of one side of a comparison
+fn describe_side(name: &str, side: &ScoringSide) {
+    let interests = match (side.interests.is_empty(), side.learned_interests) {
+        (true, true) => "no learned interests (nothing is filtered)".to_string(),
+        (true, false) => "no interests".to_string(),
+        (false, learned) => format!(
+            "{}interests {}",
+            if learned { "learned " } else { "" },
+            side.interests.join(", ")
+        ),
+    };
+    println!(
+        "  {}: threshold {:.2}, provider {}, {}",
+        name, side.relevance_threshold, side.provider, interests
+    );
+}
+
+fn format_score(score: Option<f64>) -> String {
+    score.map_or_else(|| "-".to_string(), |s| format!("{:.2}", s))
+}
+
+/// Print the articles whose filtering differs, with their scores
+fn print_changed(title: &str, articles: &[&ComparedArticle]) {
+    if articles.is_empty() {
+        return;
+    }
+    println!("\n{} ({}):", title, articles.len());
+    println!("  {:>5} {:>5}  Title", "A", "B");
+    for article in articles {
+        println!(
+            "  {:>5} {:>5}  {}",
+            format_score(article.score_a),
+            format_score(article.score_b),
+            truncate_name(&article.title, TITLE_WIDTH)
+        );
+    }
+}
+
+/// Score recent articles with two settings and show how filtering them
+/// would differ
+pub async fn compare(client: &DaemonClient, params: CompareScoringParams) -> Result<()> {
+    let days = params.days;
+    println!("Scoring the articles of the last {} days, this may take a while...", days);
+    let comparison = client.compare_scoring(params).await?;
+    if comparison.articles.is_empty() {
+        println!("No articles to score were fetched in the last {} days.", days);
+        return Ok(());
+    }
+
+    println!("\nCompared {} articles", comparison.articles.len());
+    describe_side("A", &comparison.a);
+    describe_side("B", &comparison.b);
+    if comparison.same_scores {
+        println!("  Both sides share one scoring, only the thresholds differ.");
+    }
+
+    let total = comparison.articles.len();
+    let (kept_a, kept_b) = comparison.kept();
+    println!("\n  {:>6} {:>9}", "Kept", "Filtered");
+    println!("A {:>6} {:>9}", kept_a, total - kept_a);
+    println!("B {:>6} {:>9}", kept_b, total - kept_b);
+
+    let filtered = comparison.filtered_only_by_b();
+    let kept = comparison.kept_only_by_b();
+    if filtered.is_empty() && kept.is_empty() {
+        println!("\nBoth settings filter the same articles.");
+    }
+    print_changed("Kept by A, filtered by B", &filtered);
+    print_changed("Filtered by A, kept by B", &kept);
+
+    let unscored = comparison
+        .articles
+        .iter()
+        .filter(|a| a.score_a.is_none() || a.score_b.is_none())
+        .count();
+    if unscored > 0 {
+        println!("\n{} articles could not be scored on a side and count as kept there.", unscored);
+    }
+
+    Ok(())
+}
+
+/// Report how long feeds took to fetch, parse and store over the last
+/// `days` days, listing the `limit` slowest
+pub async fn fetch(client: &DaemonClient, config: &AppConfig, days: u32, limit: usize) -> Result<()> {
+    let feeds = client.fetch_profile(days).await?;
+    if feeds.is_empty() {
+        println!("No feeds were fetched in the last {} days.", days);
+        return Ok(());
+    }
+
+    let fetches: u32 = feeds.iter().map(|f| f.fetches).sum();
+    let total = |part: fn(&FetchProfile) -> f64| -> f64 {
+        feeds.iter().map(|f| part(f) * f64::from(f.fetches)).sum()
+    };
+    let fetch_ms = total(|f| f.avg_fetch_ms);
+    let parse_ms = total(|f| f.avg_parse_ms);
+    let store_ms = total(|f| f.avg_store_ms);
+    let all_ms = fetch_ms + parse_ms + store_ms;
+
+    println!(
+        "Fetch timing over the last {} days: {} feeds, {} fetches, {} in total",
+        days,
+        feeds.len(),
+        fetches,
+        format_ms(all_ms)
+    );
+    if all_ms > 0.0 {
+        println!(
+            "Time spent: {:.0}% downloading, {:.0}% parsing, {:.0}% storing",
+            fetch_ms / all_ms * 100.0,
+            parse_ms / all_ms * 100.0,
+            store_ms / all_ms * 100.0
+        );
+    }
+    if config.sync.rate_limit_ms > 0 {
+        println!(
+            "Refreshes also pause {}ms between feeds (sync.rate_limit_ms)",
+            config.sync.rate_limit_ms
+        );
+    }
+
+    println!(
+        "\n{:<width$} {:>7} {:>8} {:>8} {:>8} {:>8} {:>8} {:>9} {:>5} {:>6}",
+        "Feed",
+        "Fetches",
+        "Average",
+        "Max",
+        "Fetch",
+        "Parse",
+        "Store",
+        "Size",
+        "New",
+        "Errors",
+        width = NAME_WIDTH
+    );
+    for feed in feeds.iter().take(limit) {
+        println!(
+            "{:<width$} {:>7} {:>8} {:>8} {:>8} {:>8} {:>8} {:>9} {:>5} {:>6}",
+            truncate_name(&feed.name, NAME_WIDTH),
+            feed.fetches,
+            format_ms(feed.avg_total_ms()),
+            format_ms(feed.max_total_ms as f64),
+            format_ms(feed.avg_fetch_ms),
+            format_ms(feed.avg_parse_ms),
+            format_ms(feed.avg_store_ms),
+            format_bytes(feed.avg_bytes),
+            feed.inserted,
+            feed.errors,
+            width = NAME_WIDTH
+        );
+    }
+
+    let shown = feeds.len().min(limit);
+    if shown < feeds.len() && all_ms > 0.0 {
+        let slowest: f64 = feeds
+            .iter()
+            .take(shown)
+            .map(|f| f.avg_total_ms() * f64::from(f.fetches))
+            .sum();
+        println!(
+            "\nThe {} slowest feeds take {:.0}% of the time ({} more not shown, see --limit).",
+            shown,
+            slowest / all_ms * 100.0,
+            feeds.len() - shown
+        );
+    }
+
+    Ok(())
+}
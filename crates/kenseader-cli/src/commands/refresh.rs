@@ -1,17 +1,75 @@
 use anyhow::Result;
 
-use kenseader_core::{
-    scheduler::refresh_all_feeds,
-    storage::Database,
-    AppConfig,
-};
+use kenseader_core::{feed::ArticleOutcome, ipc::DaemonClient};
 
-pub async fn run(db: &Database, config: &AppConfig) -> Result<()> {
+pub async fn run(client: &DaemonClient) -> Result<()> {
     println!("Refreshing all feeds...\n");
 
-    let new_articles = refresh_all_feeds(db, config).await?;
+    let new_articles = client.refresh(None).await?;
 
     println!("\nRefresh complete. {} new articles fetched.", new_articles);
 
     Ok(())
 }
+
+/// Report per feed what a refresh would fetch and store, listing every
+/// item with `verbose`
+pub async fn dry_run(client: &DaemonClient, verbose: bool) -> Result<()> {
+    println!("Fetching all active feeds (dry run)...\n");
+
+    let feeds = client.refresh_dry_run().await?;
+    let mut new_articles = 0;
+
+    for (idx, feed) in feeds.iter().enumerate() {
+        if verbose && idx > 0 {
+            println!();
+        }
+        let due = if feed.due { "due" } else { "not due" };
+        let result = match (&feed.error, feed.status) {
+            (Some(error), _) => format!("failed: {}", error),
+            (None, status) => {
+                let counts: Vec<String> = [
+                    ArticleOutcome::New,
+                    ArticleOutcome::Updated,
+                    ArticleOutcome::Unchanged,
+                    ArticleOutcome::Republished,
+                    ArticleOutcome::Duplicate,
+                ]
+                .into_iter()
+                .map(|outcome| (feed.count(outcome), outcome))
+                .filter(|(count, _)| *count > 0)
+                .map(|(count, outcome)| format!("{} {}", count, outcome.label()))
+                .collect();
+                let status = status.map(|s| format!("HTTP {}, ", s)).unwrap_or_default();
+                if counts.is_empty() {
+                    format!("{}no items", status)
+                } else {
+                    format!("{}{} items: {}", status, feed.articles.len(), counts.join(", "))
+                }
+            }
+        };
+        println!("{} ({}) - {}", feed.name, due, result);
+        if feed.due {
+            new_articles += feed.count(ArticleOutcome::New);
+        }
+
+        if verbose {
+            println!("  URL: {}", feed.url);
+            if let Some(ref moved_to) = feed.moved_to {
+                println!("  Moved permanently to: {}", moved_to);
+            }
+            for article in &feed.articles {
+                println!("  [{}] {} ({})", article.outcome.label(), article.title, article.guid);
+            }
+        }
+    }
+
+    println!(
+        "\nDry run: nothing was stored. A refresh now would fetch {} of {} feeds and store {} new articles.",
+        feeds.iter().filter(|f| f.due).count(),
+        feeds.len(),
+        new_articles
+    );
+
+    Ok(())
+}
@@ -1,9 +1,11 @@
-use std::io;
+use std::io::{self, Write};
 use std::path::PathBuf;
+use std::process::Stdio;
 use std::sync::Arc;
+use std::time::Instant;
 // Arc is also used for DynamicImage sharing in image cache
 
-use anyhow::{anyhow, Result};
+use anyhow::Result;
 use uuid::Uuid;
 use crossterm::{
     event::{DisableMouseCapture, EnableMouseCapture},
@@ -15,83 +17,123 @@ use ratatui::{
     layout::{Constraint, Direction, Layout},
     Terminal,
 };
-use tokio::sync::mpsc;
+use tokio::sync::mpsc::{self, error::TryRecvError};
+
+use super::daemon::spawn_embedded;
+use super::open_migrated;
 
 use kenseader_core::{
-    ipc::DaemonClient,
-    storage::{Database, ArticleRepository, FeedRepository},
+    config::write_settings,
+    feed::{github, snapshot_date, Article, ArticleListing, Feed, FeedSettings, FetchedPage, PageCursor, PruneSuggestion},
+    ipc::{ArticleArchivedResponse, ArticleDiscussionResponse, DaemonClient},
+    profile::TimeWindow,
+    scheduler::SchedulerEvent,
+    storage::WriterLock,
     AppConfig,
 };
 use kenseader_tui::{
-    app::{App, Focus, Mode, RichArticleState, ViewMode},
+    app::{
+        App, Discussion, Focus, Mark, Mode, RegisterCommand, RichArticleState, ViewMode,
+        VirtualFeed, ADVISORIES_VIEW_SIZE, ALERTS_VIEW_SIZE, ARTICLE_PAGE_SIZE, FILTERED_VIEW_SIZE, HISTORY_VIEW_SIZE, PREVIOUS_MARK,
+    },
+    appearance::ThemeSwitcher,
     event::{AppEvent, EventHandler, ImageLoadResult, RefreshResult},
+    external::{self, ForegroundCommand},
+    feed_manager::{
+        default_feed_name, swapped_feed_order, FeedForm, FeedManager, FeedManagerAction,
+        SubscribePrompt, TextField,
+    },
     input::{handle_key_event, Action},
-    keymap::Keymap,
-    load_theme,
-    rich_content::{download_image, FocusableItem},
+    keymap::{action_label, Keymap},
+    qr::QrCode,
+    reading::FinishedReading,
+    tts,
+    image_download::ImageDownloader,
+    rich_content::{FocusableItem, RichContent},
+    settings::{Setting, SettingValue, SettingsAction, SettingsScreen},
+    share::{self, ShareTarget},
+    tagging::TagPrompt,
+    theme::ColorDepth,
+    toc::TocAction,
+    triage::{Decision, TriageAction, Verdict},
     widgets::{
-        ArticleDetailWidget, ArticleListWidget, ImageViewerWidget, PopupWidget, StatusBarWidget,
-        SubscriptionsWidget,
+        ArticleDetailWidget, ArticleListWidget, FeedManagerWidget, FeedStatsWidget,
+        HelpWidget, ImageViewerWidget, PopupWidget, ProfileWidget, SettingsWidget, StatusBarWidget,
+        SubscriptionsWidget, TriageWidget, truncate_str,
     },
 };
 
 pub async fn run(config: Arc<AppConfig>, read_mode: bool) -> Result<()> {
     // Create keymap from config
     let keymap = Keymap::from_config(&config.keymap);
-
-    // Create daemon client (None in read-mode)
-    let client: Option<Arc<DaemonClient>> = if read_mode {
-        None
-    } else {
-        let client = Arc::new(DaemonClient::new(config.socket_path()));
-        if !client.ping().await? {
-            return Err(anyhow!(
-                "Daemon is not running.\nPlease start the daemon first with:\n  kenseader daemon start\n\nOr use --read-mode to read directly from data_dir without daemon."
-            ));
+    let which_key_delay = config
+        .ui
+        .which_key
+        .then(|| std::time::Duration::from_millis(config.ui.which_key_delay_ms));
+
+    // All data access goes through the client. Without a daemon (or in
+    // read-mode) it serves requests in-process from the local database.
+    let daemon = DaemonClient::new(config.socket_path());
+    let daemon_missing = !read_mode && !daemon.ping().await?;
+
+    let mut embedded = None;
+    let client = if read_mode || daemon_missing {
+        let db = Arc::new(open_migrated(&config).await?);
+
+        // Without a daemon, optionally run one inside this process, unless
+        // another process (e.g. a CLI refresh) currently owns the database
+        if daemon_missing && config.ui.embedded_daemon {
+            if let Some(lock) = WriterLock::try_acquire(&config)? {
+                embedded = Some(spawn_embedded(db.clone(), config.clone(), lock));
+            }
         }
-        Some(client)
-    };
 
-    // Create database for read-mode (direct database access)
-    let db: Option<Arc<Database>> = if read_mode {
-        Some(Arc::new(Database::new(&config).await?))
+        Arc::new(DaemonClient::local(db, config.clone()))
     } else {
-        None
+        Arc::new(daemon)
     };
+    let read_mode = read_mode || (daemon_missing && embedded.is_none());
 
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
 
-    // Set terminal title (include Read Mode indicator)
-    let title = if read_mode {
-        "Kenseader (Read Mode)"
-    } else {
-        "Kenseader"
-    };
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture, SetTitle(title))?;
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
 
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    // Load theme from config
-    let theme = load_theme(&config.ui.theme);
+    // Load theme from config (switching between light and dark if configured)
+    let color_depth = ColorDepth::from_mode(config.ui.color_mode);
+    let mut theme_switcher = ThemeSwitcher::new(&config.ui.theme, color_depth);
+    let theme = theme_switcher.initial_theme();
 
     // Create app state
-    let mut app = if read_mode {
-        App::new_read_mode(config.clone(), theme)
-    } else {
-        App::new(client.clone().unwrap(), config.clone(), theme)
-    };
+    let mut app = App::new(client, config.clone(), read_mode, theme);
+    // A metered connection holds back image downloads and daemon fetches
+    if app.metered.poll() && app.metered_override.is_none() {
+        sync_metered(&app).await;
+    }
 
     // Load initial data
-    load_feeds(&mut app, db.as_ref()).await?;
+    load_feeds(&mut app).await?;
+    if embedded.is_some() {
+        app.set_status("Daemon not running: started embedded daemon");
+    } else if daemon_missing {
+        app.set_warning("Daemon not running: read-mode (start it with `kenseader daemon start`)");
+    }
+    if config.prune.enabled {
+        if let Ok(feeds) = app.client.prune_suggestions().await {
+            suggest_pruning(&mut app, &feeds);
+        }
+    }
 
     // Create event handler with animation FPS support
     let event_handler = EventHandler::with_animation_fps(
         config.ui.tick_rate_ms,
         config.ui.scroll.animation_fps,
-    );
+    )
+    .with_idle_tick_rate(config.ui.idle_tick_rate_ms);
 
     // Get data directory for disk cache (use configured data_dir)
     let data_dir = Some(config.data_dir());
@@ -108,131 +150,341 @@ pub async fn run(config: Arc<AppConfig>, read_mode: bool) -> Result<()> {
     // Create channel for async refresh results
     let (refresh_tx, mut refresh_rx) = mpsc::unbounded_channel::<RefreshResult>();
 
-    // Track if we need high frame rate for smooth scrolling
+    // Daemon events: refreshes run in the daemon and report back here
+    let mut daemon_events = if app.client.is_local() {
+        None
+    } else {
+        app.client.subscribe_events().await.ok()
+    };
+    app.daemon_events = daemon_events.is_some();
+
+    // Track if we need high frame rate for smooth scrolling or arriving images
     // This is checked at the END of each iteration to determine NEXT iteration's tick rate
     let mut needs_fast_update = false;
 
-    // Main loop
-    loop {
-        // Process any completed image loads (non-blocking)
-        while let Ok(result) = img_rx.try_recv() {
-            handle_image_result(&mut app, result);
-        }
+    // Terminal title last set, so it is only written when it changes
+    let mut terminal_title: Option<String> = None;
 
-        // Process any completed refresh operations (non-blocking)
-        while let Ok(result) = refresh_rx.try_recv() {
-            handle_refresh_result(&mut app, result, db.as_ref(), data_dir.as_ref()).await?;
-        }
+    // Main loop. It ends here on errors too, so buffered read state is
+    // still saved and the terminal restored below.
+    let outcome: Result<()> = async {
+        loop {
+            // Process any completed image loads (non-blocking)
+            while let Ok(result) = img_rx.try_recv() {
+                handle_image_result(&mut app, result);
+            }
 
-        // Preload images for nearby articles (when in article list view)
-        if app.focus == Focus::ArticleList && app.config.ui.image_preview {
-            process_preload(&mut app, &img_tx, data_dir.as_ref());
-        }
+            // Process any completed refresh operations (non-blocking)
+            while let Ok(result) = refresh_rx.try_recv() {
+                handle_refresh_result(&mut app, result, data_dir.as_ref()).await?;
+            }
 
-        // Check if we need to load more images (visible-first strategy)
-        if let Some(ref mut rich_state) = app.rich_state {
-            let urls_to_load = rich_state.get_urls_needing_load(
-                app.detail_scroll,
-                rich_state.viewport_height,
-            );
-            for url in urls_to_load {
-                // Mark as loading
-                rich_state.image_cache.start_loading(&url);
-                // Spawn async download task
-                spawn_image_load(url, img_tx.clone(), data_dir.clone(), &rich_state.image_cache);
+            // Process daemon events (non-blocking)
+            if let Some(ref mut events) = daemon_events {
+                loop {
+                    match events.try_recv() {
+                        Ok(event) => handle_daemon_event(&mut app, event, data_dir.as_ref()).await?,
+                        Err(TryRecvError::Empty) => break,
+                        Err(TryRecvError::Disconnected) => {
+                            // The daemon went away; refresh through requests again
+                            daemon_events = None;
+                            app.daemon_events = false;
+                            if app.is_refreshing {
+                                handle_refresh_result(
+                                    &mut app,
+                                    RefreshResult::Failure { error: "daemon disconnected".to_string() },
+                                    data_dir.as_ref(),
+                                )
+                                .await?;
+                            }
+                            break;
+                        }
+                    }
+                }
             }
-        }
 
-        // Update scroll animation (when in ArticleDetail)
-        if app.focus == Focus::ArticleDetail {
-            app.update_scroll_animation();
-        }
+            // Fetch the next page of articles when scrolling near the end of the
+            // loaded window, or the previous one near its start
+            if app.needs_next_articles() {
+                load_next_articles(&mut app).await?;
+            } else if app.needs_previous_articles() {
+                load_previous_articles(&mut app).await?;
+            }
 
-        // Draw UI
-        terminal.draw(|frame| {
-            let size = frame.area();
-            // Update viewport height for adaptive scrolling
-            app.viewport_height = size.height;
+            // Style classification for the AI header of the article detail
+            if let Some(article_id) = app.current_article().map(|a| a.id) {
+                if app.article_style.as_ref().map(|(id, _)| *id) != Some(article_id) {
+                    let style = app.client.get_article_style(article_id).await.ok().flatten();
+                    app.article_style = Some((article_id, style));
+                }
+            }
 
-            // Check if we're in fullscreen image viewer mode
-            if let Mode::ImageViewer(image_index) = app.mode {
-                ImageViewerWidget::render(frame, size, &mut app, image_index);
-                return;
+            // Statistics of the selected feed, reloaded each time the
+            // subscriptions panel is focused so they follow reading
+            let feed_stats_shown = app.focus == Focus::Subscriptions && app.config.ui.feed_stats_panel;
+            if !feed_stats_shown {
+                app.feed_stats = None;
+            } else if let Some(feed_id) = app.current_feed().map(|f| f.id) {
+                if app.feed_stats.as_ref().map(|(id, _)| *id) != Some(feed_id) {
+                    let stats = app.client.feed_stats(feed_id).await.ok();
+                    app.feed_stats = Some((feed_id, stats));
+                }
             }
 
-            // Main layout: content + status bar
-            let main_layout = Layout::default()
-                .direction(Direction::Vertical)
-                .constraints([Constraint::Min(1), Constraint::Length(1)])
-                .split(size);
-
-            // Three-column layout with 1:4:5 ratio
-            let columns = Layout::default()
-                .direction(Direction::Horizontal)
-                .constraints([
-                    Constraint::Ratio(1, 10), // Subscriptions
-                    Constraint::Ratio(4, 10), // Article list
-                    Constraint::Ratio(5, 10), // Article detail
-                ])
-                .split(main_layout[0]);
-
-            // Render widgets
-            SubscriptionsWidget::render(frame, columns[0], &app);
-            ArticleListWidget::render(frame, columns[1], &app);
-            ArticleDetailWidget::render(frame, columns[2], &mut app);
-            StatusBarWidget::render(frame, main_layout[1], &app);
-
-            // Render popup dialogs on top (if in confirmation mode)
-            match &app.mode {
-                Mode::DeleteConfirm(_) => {
-                    let feed_name = app
-                        .current_feed()
-                        .map(|f| f.local_name.as_str())
-                        .unwrap_or("Unknown");
-                    PopupWidget::render_delete_confirm(frame, feed_name, &app.theme);
+            // Preload images for nearby articles (when in article list view)
+            if app.focus == Focus::ArticleList && app.images_load_automatically() {
+                process_preload(&mut app, &img_tx, data_dir.as_ref());
+            }
+
+            // Check if we need to load more images (visible-first strategy)
+            let images_enabled = app.images_enabled();
+            let max_downloads = app.max_image_downloads();
+            let downloader = app.image_downloader.clone();
+            if let Some(rich_state) = app.rich_state.as_mut().filter(|_| images_enabled) {
+                let mut urls_to_load = rich_state.get_urls_needing_load(
+                    app.detail_scroll,
+                    rich_state.viewport_height,
+                );
+                // Metered mode caps the downloads in flight
+                if let Some(max) = max_downloads {
+                    urls_to_load.truncate(max.saturating_sub(rich_state.image_cache.loading_count()));
                 }
-                Mode::BatchDeleteConfirm => {
-                    let count = app.selected_feeds.len();
-                    PopupWidget::render_batch_delete_confirm(frame, count, &app.theme);
+                for url in urls_to_load {
+                    // Mark as loading
+                    rich_state.image_cache.start_loading(&url);
+                    // Spawn async download task
+                    spawn_image_load(url, img_tx.clone(), downloader.clone(), data_dir.clone(), &rich_state.image_cache);
                 }
-                _ => {}
             }
-        })?;
 
-        // Handle events (use faster tick rate during animations or when pending scroll)
-        let event = if needs_fast_update {
-            event_handler.next_animation()?
-        } else {
-            event_handler.next()?
-        };
-        if let Some(event) = event {
-            match event {
-                AppEvent::Key(key) => {
-                    let action = handle_key_event(key, &app, &keymap);
-                    handle_action(&mut app, action, client.as_ref(), db.as_ref(), data_dir.as_ref(), refresh_tx.clone()).await?;
+            // Update scroll animation (when in ArticleDetail)
+            if app.focus == Focus::ArticleDetail {
+                app.update_scroll_animation();
+            }
+
+            // Unread counts in the terminal title
+            if let Some(title) = StatusBarWidget::terminal_title(&app).filter(|t| terminal_title.as_ref() != Some(t)) {
+                execute!(terminal.backend_mut(), SetTitle(&title))?;
+                terminal_title = Some(title);
+            }
+
+            // Draw UI
+            terminal.draw(|frame| {
+                let size = frame.area();
+                // Update viewport height for adaptive scrolling
+                app.viewport_height = size.height;
+
+                // Check if we're in fullscreen image viewer mode
+                if let Mode::ImageViewer(image_index) = app.mode {
+                    ImageViewerWidget::render(frame, size, &mut app, image_index);
+                    return;
+                }
+
+                // Main layout: content + status bar
+                let main_layout = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Min(1), Constraint::Length(1)])
+                    .split(size);
+
+                // Three-column layout with 1:4:5 ratio
+                let columns = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([
+                        Constraint::Ratio(1, 10), // Subscriptions
+                        Constraint::Ratio(4, 10), // Article list
+                        Constraint::Ratio(5, 10), // Article detail
+                    ])
+                    .split(main_layout[0]);
+
+                // Render widgets
+                SubscriptionsWidget::render(frame, columns[0], &app);
+                ArticleListWidget::render(frame, columns[1], &app);
+                if feed_stats_shown {
+                    FeedStatsWidget::render(frame, columns[2], &mut app);
+                } else {
+                    ArticleDetailWidget::render(frame, columns[2], &mut app);
                 }
-                AppEvent::Resize(_, _) => {
-                    // Recalculate heights on resize
-                    if let Some(ref mut rich_state) = app.rich_state {
-                        rich_state.element_heights.clear();
+                StatusBarWidget::render(frame, main_layout[1], &app);
+
+                // Render popup dialogs on top (if in confirmation mode)
+                match &app.mode {
+                    Mode::DeleteConfirm(_) => {
+                        let feed_name = app
+                            .current_feed()
+                            .map(|f| f.local_name.as_str())
+                            .unwrap_or("Unknown");
+                        PopupWidget::render_delete_confirm(frame, feed_name, &app.theme);
+                    }
+                    Mode::BatchDeleteConfirm => {
+                        let count = app.selected_feeds.len();
+                        PopupWidget::render_batch_delete_confirm(frame, count, &app.theme);
+                    }
+                    Mode::MarkAllReadConfirm(feed_id) => {
+                        if let Some(feed) = app.feeds.iter().find(|f| f.id == *feed_id) {
+                            PopupWidget::render_mark_all_read_confirm(frame, &feed.local_name, feed.unread_count, &app.theme);
+                        }
+                    }
+                    Mode::Share => {
+                        let title = app.current_article().map(|a| a.title.as_str()).unwrap_or("");
+                        let has_command = app.config.ui.share_command.is_some();
+                        PopupWidget::render_share_menu(frame, title, has_command, &app.theme);
+                    }
+                    Mode::QrCode => {
+                        if let Some(url) = app.current_article().and_then(|a| a.url.as_deref()) {
+                            PopupWidget::render_qr_code(frame, url, &app.theme);
+                        }
                     }
+                    Mode::Messages => PopupWidget::render_messages(frame, &app.toasts, &app.theme),
+                    Mode::Profile(window) => ProfileWidget::render(frame, &app, *window),
+                    Mode::Help(_) => HelpWidget::render(frame, &mut app, &keymap.help_entries()),
+                    Mode::FeedManager => FeedManagerWidget::render(frame, &app),
+                    Mode::Settings => SettingsWidget::render(frame, &app),
+                    Mode::Triage => TriageWidget::render(frame, &app),
+                    Mode::Toc(selected) => {
+                        PopupWidget::render_toc(frame, &app.table_of_contents(), *selected, &app.theme)
+                    }
+                    Mode::Tag => PopupWidget::render_tag_prompt(frame, &app.tag_prompt, &app.theme),
+                    Mode::Subscribe => FeedManagerWidget::render_subscribe_prompt(
+                        frame,
+                        &app.subscribe_prompt,
+                        &app.theme,
+                    ),
+                    _ => {}
+                }
+
+                PopupWidget::render_toasts(frame, main_layout[1], &app.toasts, &app.theme);
+
+                // Keys that can follow a pending prefix key
+                if let Some(prefix) = which_key_delay.and_then(|delay| app.which_key_prefix(delay)) {
+                    let entries: Vec<(String, String)> = keymap
+                        .continuations(&prefix)
+                        .iter()
+                        .map(|(key, action)| (key.label(), action_label(action)))
+                        .collect();
+                    PopupWidget::render_which_key(
+                        frame,
+                        main_layout[1],
+                        &prefix.label(),
+                        &entries,
+                        &app.theme,
+                    );
                 }
-                AppEvent::Tick => {
-                    // Tick spinner animation for loading indicator
-                    app.tick_spinner();
+            })?;
+
+            // Handle events (use faster tick rate during animations, pending scroll
+            // or image loads, and a slow one when nothing is changing)
+            let event = if needs_fast_update {
+                event_handler.next_animation()?
+            } else if app.is_idle() {
+                event_handler.next_idle()?
+            } else {
+                event_handler.next()?
+            };
+            if let Some(event) = event {
+                match event {
+                    AppEvent::Key(key) => {
+                        app.reading.input(Instant::now());
+                        // The typed key, then any keys queued by a macro replay
+                        let mut next_key = Some(key);
+                        let mut replaying = false;
+                        while let Some(key) = next_key {
+                            let action = handle_key_event(key, &app, &keymap);
+                            // Replayed keys can't record or replay macros themselves
+                            if !(replaying && action.is_macro_control()) {
+                                if !action.is_macro_control() {
+                                    app.macros.record(key);
+                                }
+                                handle_action(&mut app, action, data_dir.as_ref(), refresh_tx.clone())
+                                    .await?;
+                                if let Some(foreground) = app.foreground_command.take() {
+                                    run_foreground(&mut terminal, &mut app, foreground)?;
+                                }
+                            }
+                            if app.should_quit {
+                                app.macros.cancel_replay();
+                            }
+                            next_key = app.macros.next_replay_key();
+                            replaying = true;
+                        }
+                    }
+                    AppEvent::Resize(_, _) => {
+                        // Recalculate heights and image protocols on resize
+                        if let Some(ref mut rich_state) = app.rich_state {
+                            rich_state.element_heights.clear();
+                            rich_state.image_cache.invalidate_protocols();
+                        }
+                        app.preload_cache.invalidate_protocols();
+                    }
+                    AppEvent::Tick => {
+                        // Tick spinner animation for loading indicator
+                        app.tick_spinner();
+                        app.toasts.expire();
+                        // Listening to the article read aloud is reading it too
+                        if app.speaker.is_active() {
+                            app.reading.input(Instant::now());
+                        }
+                        match app.speaker.poll() {
+                            Ok(true) if !app.speaker.is_active() => app.set_status("Finished reading aloud"),
+                            Ok(_) => {}
+                            Err(e) => {
+                                app.speaker.stop();
+                                app.set_error(format!("Read aloud failed: {}", e));
+                            }
+                        }
+                        if let Some(theme) = theme_switcher.poll() {
+                            app.theme = theme;
+                        }
+                        let was_metered = app.is_metered();
+                        if app.metered.poll() && app.is_metered() != was_metered {
+                            sync_metered(&app).await;
+                            app.set_status(if was_metered {
+                                "Connection no longer metered"
+                            } else {
+                                "Metered connection: saving bandwidth"
+                            });
+                        }
+                        if app.read_state.is_due() {
+                            flush_read_state(&mut app).await;
+                        }
+                        if app.daemon_status_due() {
+                            let status = app.client.status().await.ok();
+                            app.daemon_status.update(status);
+                        }
+                    }
                 }
             }
-        }
 
-        // Update fast update flag for next iteration
-        // This ensures we use high frame rate immediately after a scroll action
-        needs_fast_update = app.focus == Focus::ArticleDetail && app.needs_scroll_update();
+            // Update fast update flag for next iteration
+            // This ensures we use high frame rate immediately after a scroll action
+            needs_fast_update = (app.focus == Focus::ArticleDetail && app.needs_scroll_update())
+                || app.image_loads_pending();
 
-        if app.should_quit {
-            break;
+            // Time spent on the open article, recorded once it is left
+            let reading = app.reading_article();
+            if let Some(finished) = app.reading.track(reading, app.detail_scroll_depth(), Instant::now()) {
+                record_reading(&app, finished).await;
+            }
+
+            if app.should_quit {
+                break;
+            }
         }
+        Ok(())
+    }
+    .await;
+
+    if let Some(finished) = app.reading.finish(Instant::now()) {
+        record_reading(&app, finished).await;
     }
 
+    // Store read-state changes still waiting for the next flush
+    let changes = app.read_state.take();
+    let unsaved = if changes.is_empty() {
+        None
+    } else {
+        app.client.set_read_many(&changes).await.err()
+    };
+
     // Restore terminal
     disable_raw_mode()?;
     execute!(
@@ -242,7 +494,14 @@ pub async fn run(config: Arc<AppConfig>, read_mode: bool) -> Result<()> {
     )?;
     terminal.show_cursor()?;
 
-    Ok(())
+    if let Some(embedded) = embedded {
+        embedded.shutdown().await;
+    }
+    if let Some(e) = unsaved {
+        eprintln!("Failed to save the read state of {} articles: {}", changes.len(), e);
+    }
+
+    outcome
 }
 
 /// Handle completed image load result
@@ -283,15 +542,14 @@ fn handle_image_result(app: &mut App, result: ImageLoadResult) {
 async fn handle_refresh_result(
     app: &mut App,
     result: RefreshResult,
-    db: Option<&Arc<Database>>,
     data_dir: Option<&PathBuf>,
 ) -> Result<()> {
-    app.is_refreshing = false;
-
     match result {
         RefreshResult::Success { new_count } => {
+            app.is_refreshing = false;
+            app.refresh_progress = None;
             // Reload data
-            load_feeds(app, db).await?;
+            load_feeds(app).await?;
             init_rich_article_state(app, data_dir);
             if new_count > 0 {
                 app.set_status(format!("Refreshed: {} new articles", new_count));
@@ -300,18 +558,181 @@ async fn handle_refresh_result(
             }
         }
         RefreshResult::Failure { error } => {
-            app.set_status(format!("Refresh failed: {}", error));
+            app.is_refreshing = false;
+            app.refresh_progress = None;
+            app.set_error(format!("Refresh failed: {}", error));
+        }
+        RefreshResult::Subscribed {
+            feed_id,
+            name,
+            new_count,
+        } => {
+            if matches!(app.mode, Mode::Subscribe) {
+                app.mode = Mode::Normal;
+            }
+            flush_read_state(app).await;
+            app.feeds = app.client.list_feeds().await?;
+            if let Some(idx) = app.find_feed_index(feed_id) {
+                app.selected_feed = idx;
+            }
+            ensure_valid_feed_selection(app);
+            load_articles(app).await?;
+            init_rich_article_state(app, data_dir);
+            app.set_status(format!("Subscribed to {}: {} articles", name, new_count));
+        }
+        RefreshResult::SubscribeDuplicate { feed_id, name } => {
+            let prompt = &mut app.subscribe_prompt;
+            if matches!(app.mode, Mode::Subscribe) && prompt.pending {
+                prompt.pending = false;
+                let wanted = prompt.name.value.trim();
+                if wanted.is_empty() || wanted == name {
+                    prompt.error = Some(format!("Already subscribed as '{}'", name));
+                } else {
+                    prompt.duplicate = Some((feed_id, name));
+                }
+            } else {
+                app.set_error(format!("Already subscribed as '{}'", name));
+            }
+        }
+        RefreshResult::SubscriptionRenamed { feed_id, old_name, name } => {
+            if matches!(app.mode, Mode::Subscribe) {
+                app.mode = Mode::Normal;
+            }
+            flush_read_state(app).await;
+            app.feeds = app.client.list_feeds().await?;
+            if let Some(idx) = app.find_feed_index(feed_id) {
+                app.selected_feed = idx;
+            }
+            ensure_valid_feed_selection(app);
+            load_articles(app).await?;
+            init_rich_article_state(app, data_dir);
+            app.set_status(format!("Already subscribed: renamed '{}' to '{}'", old_name, name));
+        }
+        RefreshResult::SubscribeFailure { error } => {
+            if matches!(app.mode, Mode::Subscribe) && app.subscribe_prompt.pending {
+                app.subscribe_prompt.pending = false;
+                app.subscribe_prompt.error = Some(error);
+            } else {
+                app.set_error(format!("Subscribe failed: {}", error));
+            }
+        }
+        RefreshResult::Played { error: Some(error), .. } => {
+            app.set_warning(format!("Player failed: {}", error));
+        }
+        RefreshResult::Discussion { article_id, open, result } => {
+            handle_discussion(app, article_id, open, result);
+        }
+        RefreshResult::Archived { result, .. } => {
+            handle_archived(app, result);
+        }
+        RefreshResult::PageLoaded { article_id, result } => {
+            handle_page_loaded(app, article_id, result, data_dir);
+        }
+        RefreshResult::Played { article_id, error: None } => {
+            let Some(idx) = app.find_article_index(article_id) else {
+                // Article is no longer listed; still record it as watched
+                mark_article_read(app, article_id);
+                return Ok(());
+            };
+            if !app.articles[idx].is_read {
+                mark_article_read(app, article_id);
+                app.articles[idx].is_read = true;
+                if let Some(feed) = app.article_feed_mut(idx) {
+                    feed.unread_count = feed.unread_count.saturating_sub(1);
+                }
+                app.set_status("Finished playing; marked as read");
+            }
         }
     }
 
     Ok(())
 }
 
+/// Handle an event from the daemon's scheduler
+async fn handle_daemon_event(
+    app: &mut App,
+    event: SchedulerEvent,
+    data_dir: Option<&PathBuf>,
+) -> Result<()> {
+    match event {
+        SchedulerEvent::RefreshProgress { done, total } => {
+            // Scheduled refreshes show their progress too
+            app.is_refreshing = true;
+            app.refresh_progress = Some((done, total));
+        }
+        SchedulerEvent::FeedsRefreshed { new_articles } => {
+            if app.is_refreshing {
+                handle_refresh_result(app, RefreshResult::Success { new_count: new_articles }, data_dir)
+                    .await?;
+            } else if new_articles > 0 {
+                load_feeds(app).await?;
+                init_rich_article_state(app, data_dir);
+            }
+        }
+        SchedulerEvent::Error { task, message } if task == "refresh" && app.is_refreshing => {
+            handle_refresh_result(app, RefreshResult::Failure { error: message }, data_dir).await?;
+        }
+        SchedulerEvent::ConnectivityChanged { online: false } if app.is_refreshing => {
+            let error = "network unreachable".to_string();
+            handle_refresh_result(app, RefreshResult::Failure { error }, data_dir).await?;
+        }
+        SchedulerEvent::ArticlesAutoRead { count } if count > 0 => {
+            // Keep unread counts in step with the aged articles marked read
+            load_feeds(app).await?;
+            init_rich_article_state(app, data_dir);
+        }
+        SchedulerEvent::StaleFeeds { feeds } => suggest_pruning(app, &feeds),
+        SchedulerEvent::FeedsMoved { feeds } => {
+            // Titles and icons may have changed along with the moves
+            load_feeds(app).await?;
+            init_rich_article_state(app, data_dir);
+            suggest_moving(app, &feeds);
+        }
+        SchedulerEvent::FeedsMigrated { feeds } => {
+            load_feeds(app).await?;
+            init_rich_article_state(app, data_dir);
+            let names: Vec<&str> = feeds.iter().map(|f| f.local_name.as_str()).collect();
+            app.set_status(format!("{} moved permanently; switched to the new URL", names.join(", ")));
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Warn about feeds worth unsubscribing from
+fn suggest_pruning(app: &mut App, feeds: &[PruneSuggestion]) {
+    if feeds.is_empty() {
+        return;
+    }
+    let names: Vec<&str> = feeds.iter().map(|f| f.name.as_str()).collect();
+    app.set_warning(format!(
+        "Consider unsubscribing from {} (see `kenseader doctor`)",
+        names.join(", ")
+    ));
+}
+
+/// Tell the daemon whether metered mode is on
+async fn sync_metered(app: &App) {
+    if let Err(e) = app.client.set_metered(app.is_metered()).await {
+        tracing::debug!("Failed to switch metered mode: {}", e);
+    }
+}
+
+/// Offer to switch moved feeds to their new URL
+fn suggest_moving(app: &mut App, feeds: &[Feed]) {
+    let names: Vec<&str> = feeds.iter().map(|f| f.local_name.as_str()).collect();
+    app.set_warning(format!(
+        "{} moved permanently (switch to the new URLs with `kenseader doctor --update-moved`)",
+        names.join(", ")
+    ));
+}
+
 /// Spawn an async task to load an image (from disk cache or download)
 /// Disk cache check is synchronous for fast cache hits; only decoding/download is async
 fn spawn_image_load(
     url: String,
     tx: mpsc::UnboundedSender<ImageLoadResult>,
+    downloader: ImageDownloader,
     data_dir: Option<PathBuf>,
     _cache: &kenseader_tui::rich_content::ArticleImageCache,
 ) {
@@ -349,7 +770,7 @@ fn spawn_image_load(
     // Cache miss - spawn async task for network download
     let data_dir_clone = data_dir.clone();
     tokio::spawn(async move {
-        match download_image(&url).await {
+        match downloader.download(&url).await {
             Ok((bytes, image)) => {
                 let cache_path = data_dir_clone.and_then(|dir| {
                     kenseader_tui::rich_content::ImageDiskCache::new(&dir)
@@ -412,7 +833,7 @@ fn process_preload(
 
                 // Mark as loading and spawn download
                 app.preload_cache.start_loading(&url);
-                spawn_preload_image(url, tx.clone(), data_dir.cloned());
+                spawn_preload_image(url, tx.clone(), app.image_downloader.clone(), data_dir.cloned());
                 count += 1;
             }
         }
@@ -424,6 +845,7 @@ fn process_preload(
 fn spawn_preload_image(
     url: String,
     tx: mpsc::UnboundedSender<ImageLoadResult>,
+    downloader: ImageDownloader,
     data_dir: Option<PathBuf>,
 ) {
     // First, check if image exists in disk cache (fast synchronous check)
@@ -460,7 +882,7 @@ fn spawn_preload_image(
     // Cache miss - spawn async task for network download
     let data_dir_clone = data_dir.clone();
     tokio::spawn(async move {
-        match download_image(&url).await {
+        match downloader.download(&url).await {
             Ok((bytes, image)) => {
                 let cache_path = data_dir_clone.and_then(|dir| {
                     kenseader_tui::rich_content::ImageDiskCache::new(&dir)
@@ -481,25 +903,32 @@ fn spawn_preload_image(
     });
 }
 
-async fn load_feeds(app: &mut App, db: Option<&Arc<Database>>) -> Result<()> {
-    // Load feeds from database (read-mode) or daemon client
-    app.feeds = if app.read_mode {
-        let db = db.expect("Database required in read-mode");
-        FeedRepository::new(db).list_all().await?
-    } else {
-        let client = app.client.as_ref().expect("Client required in normal mode");
-        client.list_feeds().await?
-    };
+async fn load_feeds(app: &mut App) -> Result<()> {
+    flush_read_state(app).await;
+    app.feeds = app.client.list_feeds().await?;
+    app.feed_stats = None;
 
     if !app.feeds.is_empty() {
         // Ensure selected feed is valid for current view mode
         ensure_valid_feed_selection(app);
-        load_articles(app, db).await?;
+        load_articles(app).await?;
     }
 
     Ok(())
 }
 
+/// Move the selection onto a feed matching the filter and show its articles
+async fn apply_feed_filter(app: &mut App, data_dir: Option<&PathBuf>) -> Result<()> {
+    let prev_feed = app.selected_feed;
+    ensure_valid_feed_selection(app);
+    if app.selected_feed != prev_feed {
+        app.preload_cache.clear();
+        load_articles(app).await?;
+        init_rich_article_state(app, data_dir);
+    }
+    Ok(())
+}
+
 /// Ensure selected feed is valid for current view mode
 fn ensure_valid_feed_selection(app: &mut App) {
     let visible_feeds = app.visible_feeds();
@@ -520,38 +949,54 @@ fn ensure_valid_feed_selection(app: &mut App) {
     }
 }
 
-async fn load_articles(app: &mut App, db: Option<&Arc<Database>>) -> Result<()> {
-    load_articles_preserve_selection(app, db, false).await
+async fn load_articles(app: &mut App) -> Result<()> {
+    load_articles_preserve_selection(app, false).await
 }
 
-async fn load_articles_preserve_selection(app: &mut App, db: Option<&Arc<Database>>, preserve: bool) -> Result<()> {
+async fn load_articles_preserve_selection(app: &mut App, preserve: bool) -> Result<()> {
+    flush_read_state(app).await;
+    if let Some(virtual_feed) = app.virtual_feed {
+        return load_virtual_feed_articles(app, virtual_feed, preserve).await;
+    }
     if let Some(feed) = app.current_feed() {
         let feed_idx = app.selected_feed;
         let feed_id = feed.id;
-        let unread_only = matches!(app.view_mode, ViewMode::UnreadOnly);
+        let listing = app.article_listing();
         let prev_selected = app.selected_article;
 
-        // Load articles from database (read-mode) or daemon client
-        app.articles = if app.read_mode {
-            let db = db.expect("Database required in read-mode");
-            ArticleRepository::new(db).list_by_feed(feed_id, unread_only).await?
-        } else {
-            let client = app.client.as_ref().expect("Client required in normal mode");
-            client.list_articles(Some(feed_id), unread_only).await?
-        };
-
-        // Sync unread_count with actual article data
-        let actual_unread_count = if unread_only {
-            // In unread-only mode, all articles in the list are unread
-            app.articles.len() as u32
+        // Reload a preserved window from where it started, enough to keep
+        // the selection in range; otherwise start at the top
+        let cursor = preserve.then(|| app.articles.first().map(|a| PageCursor::From(a.id))).flatten();
+        let limit = if cursor.is_some() {
+            ARTICLE_PAGE_SIZE.max(app.articles.len() as u32)
         } else {
-            // In all mode, count articles where is_read = false
-            app.articles.iter().filter(|a| !a.is_read).count() as u32
+            ARTICLE_PAGE_SIZE
         };
+        app.articles = fetch_article_page(app, feed_id, listing, limit, cursor).await?;
+        if cursor.is_some() && app.articles.is_empty() {
+            // The window's first article is gone (e.g. cleaned up): start over
+            app.articles = fetch_article_page(app, feed_id, listing, limit, None).await?;
+            app.articles_offset = 0;
+        } else if cursor.is_none() {
+            app.articles_offset = 0;
+        }
+        app.articles_exhausted = app.articles.len() < limit as usize;
+
+        // Sync unread_count with actual article data (only known once the
+        // whole feed is loaded, and not while low-relevance articles are hidden)
+        if app.articles_offset == 0 && app.articles_exhausted && !app.hide_low_relevance {
+            let actual_unread_count = if listing.unread_only {
+                // In unread-only mode, all articles in the list are unread
+                app.articles.len() as u32
+            } else {
+                // In all mode, count articles where is_read = false
+                app.articles.iter().filter(|a| !a.is_read).count() as u32
+            };
 
-        // Update the feed's unread_count to match reality
-        if let Some(feed) = app.feeds.get_mut(feed_idx) {
-            feed.unread_count = actual_unread_count;
+            // Update the feed's unread_count to match reality
+            if let Some(feed) = app.feeds.get_mut(feed_idx) {
+                feed.unread_count = actual_unread_count;
+            }
         }
 
         if preserve && prev_selected < app.articles.len() {
@@ -568,151 +1013,1257 @@ async fn load_articles_preserve_selection(app: &mut App, db: Option<&Arc<Databas
     Ok(())
 }
 
-/// Load articles for history navigation - ignores unread-only filter to find the target article
-async fn load_articles_for_history(app: &mut App, db: Option<&Arc<Database>>, target_article_id: Uuid) -> Result<bool> {
+/// Load a virtual feed: recently read articles of all feeds, newest first,
+/// filtered or alerted articles, most recent first, or articles mentioning
+/// CVEs, most severe first
+async fn load_virtual_feed_articles(app: &mut App, virtual_feed: VirtualFeed, preserve: bool) -> Result<()> {
+    let prev_selected = app.selected_article;
+    match virtual_feed {
+        VirtualFeed::History => {
+            app.articles = app.client.list_recently_read(HISTORY_VIEW_SIZE).await?;
+        }
+        VirtualFeed::Filtered => {
+            let filtered = app.client.list_filtered(FILTERED_VIEW_SIZE).await?;
+            app.filter_reasons = filtered.iter().map(|f| (f.article.id, f.reason)).collect();
+            app.articles = filtered.into_iter().map(|f| f.article).collect();
+        }
+        VirtualFeed::Alerts => {
+            let alerted = app.client.list_alerted(ALERTS_VIEW_SIZE).await?;
+            app.article_alerts = alerted.iter().map(|a| (a.article.id, a.alert.clone())).collect();
+            app.articles = alerted.into_iter().map(|a| a.article).collect();
+        }
+        VirtualFeed::Advisories => {
+            let listed = app.client.list_advisories(0.0, ADVISORIES_VIEW_SIZE).await?;
+            app.article_advisories = listed.iter().map(|a| (a.article.id, a.advisories.clone())).collect();
+            app.articles = listed.into_iter().map(|a| a.article).collect();
+        }
+    }
+    app.articles_offset = 0;
+    app.articles_exhausted = true;
+
+    if preserve && prev_selected < app.articles.len() {
+        app.selected_article = prev_selected;
+    } else {
+        app.selected_article = 0;
+        app.reset_detail_scroll();
+    }
+    app.clear_rich_state();
+
+    Ok(())
+}
+
+/// Show a virtual feed in the article list, or leave it if shown
+async fn toggle_virtual_feed(app: &mut App, virtual_feed: VirtualFeed, data_dir: Option<&PathBuf>) -> Result<()> {
+    app.virtual_feed = if app.virtual_feed == Some(virtual_feed) { None } else { Some(virtual_feed) };
+    app.clear_article_selection();
+    load_articles(app).await?;
+    if app.virtual_feed.is_some() {
+        app.focus = Focus::ArticleList;
+        if app.articles.is_empty() {
+            app.set_status(match virtual_feed {
+                VirtualFeed::History => "No recently read articles",
+                VirtualFeed::Filtered => "No articles were filtered",
+                VirtualFeed::Alerts => "No keyword alerts (set alerts.keywords to watch for some)",
+                VirtualFeed::Advisories => "No articles mention CVEs (set advisories.enabled to look for them)",
+            });
+        }
+    }
+    init_rich_article_state(app, data_dir);
+    Ok(())
+}
+
+/// Fetch one page of a feed's article listing, starting at `cursor`
+async fn fetch_article_page(
+    app: &App,
+    feed_id: Uuid,
+    listing: ArticleListing,
+    limit: u32,
+    cursor: Option<PageCursor>,
+) -> Result<Vec<Article>> {
+    let articles = app
+        .client
+        .list_articles_page(feed_id, listing, limit, cursor)
+        .await?;
+    Ok(articles)
+}
+
+/// Append the next page of articles when the cursor nears the end of the loaded window
+async fn load_next_articles(app: &mut App) -> Result<()> {
+    let Some(feed_id) = app.current_feed().map(|f| f.id) else {
+        return Ok(());
+    };
+    // Continue after the last loaded article: read marks since the last
+    // page don't shift the next one
+    let cursor = app.articles.last().map(|a| PageCursor::After(a.id));
+
+    let page = fetch_article_page(app, feed_id, app.article_listing(), ARTICLE_PAGE_SIZE, cursor).await?;
+    let exhausted = page.len() < ARTICLE_PAGE_SIZE as usize;
+    app.append_articles(page, exhausted);
+
+    Ok(())
+}
+
+/// Fetch the page before the loaded window again when the cursor nears its start
+async fn load_previous_articles(app: &mut App) -> Result<()> {
+    let (Some(feed_id), Some(first)) = (app.current_feed().map(|f| f.id), app.articles.first()) else {
+        return Ok(());
+    };
+    let cursor = Some(PageCursor::Before(first.id));
+
+    let page = fetch_article_page(app, feed_id, app.article_listing(), ARTICLE_PAGE_SIZE, cursor).await?;
+    let at_start = page.len() < ARTICLE_PAGE_SIZE as usize;
+    app.prepend_articles(page, at_start);
+
+    Ok(())
+}
+
+/// Load the part of the feed's listing around an article and select it;
+/// `position` is the number of listed articles before it
+async fn load_article_window(
+    app: &mut App,
+    feed_id: Uuid,
+    listing: ArticleListing,
+    article_id: Uuid,
+    position: u32,
+) -> Result<()> {
+    let before = Some(PageCursor::Before(article_id));
+    let mut articles = fetch_article_page(app, feed_id, listing, ARTICLE_PAGE_SIZE / 2, before).await?;
+    let from = Some(PageCursor::From(article_id));
+    let page = fetch_article_page(app, feed_id, listing, ARTICLE_PAGE_SIZE, from).await?;
+
+    app.articles_exhausted = page.len() < ARTICLE_PAGE_SIZE as usize;
+    app.articles_offset = (position as usize).saturating_sub(articles.len());
+    app.selected_article = articles.len();
+    articles.extend(page);
+    app.articles = articles;
+    app.reset_detail_scroll();
+    app.clear_rich_state();
+
+    Ok(())
+}
+
+/// Load articles for history navigation - ignores unread-only filter to find
+/// the target article. Shows the start of the feed if it isn't listed.
+async fn load_articles_for_history(app: &mut App, target_article_id: Uuid) -> Result<bool> {
     let feed_id = match app.current_feed() {
         Some(feed) => feed.id,
         None => return Ok(false),
     };
+    app.virtual_feed = None;
+    flush_read_state(app).await;
+
+    // Look the article up in the current listing, then among read articles
+    let mut listing = app.article_listing();
+    let mut position = app.client.article_position(feed_id, listing, target_article_id).await?;
+    if position.is_none() && listing.unread_only {
+        listing.unread_only = false;
+        position = app.client.article_position(feed_id, listing, target_article_id).await?;
+        if position.is_some() {
+            // Temporarily switch to All mode to show the article
+            app.view_mode = ViewMode::All;
+        }
+    }
 
-    // First try to find in current filtered list
-    let unread_only = matches!(app.view_mode, ViewMode::UnreadOnly);
+    match position {
+        Some(position) => {
+            load_article_window(app, feed_id, listing, target_article_id, position).await?;
+            Ok(true)
+        }
+        None => {
+            load_articles(app).await?;
+            Ok(false)
+        }
+    }
+}
 
-    // Load articles from database (read-mode) or daemon client
-    app.articles = if app.read_mode {
-        let db = db.expect("Database required in read-mode");
-        ArticleRepository::new(db).list_by_feed(feed_id, unread_only).await?
-    } else {
-        let client = app.client.as_ref().expect("Client required in normal mode");
-        client.list_articles(Some(feed_id), unread_only).await?
+/// Reload the article list after the relevance sort or filter changed,
+/// keeping the cursor on the same article when it is still listed
+async fn reload_relevance_view(app: &mut App, data_dir: Option<&PathBuf>) -> Result<()> {
+    let selected_id = app.current_article().map(|a| a.id);
+    app.clear_article_selection();
+    load_articles(app).await?;
+    if let Some(idx) = selected_id.and_then(|id| app.find_article_index(id)) {
+        app.selected_article = idx;
+    }
+    init_rich_article_state(app, data_dir);
+    Ok(())
+}
+
+/// Select the feed and article of a mark and restore its focus.
+/// Returns false if the feed or article is gone.
+async fn jump_to_mark(app: &mut App, mark: Mark, data_dir: Option<&PathBuf>) -> Result<bool> {
+    let Some(feed_idx) = app.find_feed_index(mark.feed_id) else {
+        return Ok(false);
     };
+    // Show the feed even if the unread-only view hides it
+    if app.actual_to_visible_feed_index(feed_idx).is_none() {
+        app.view_mode = ViewMode::All;
+    }
+    app.selected_feed = feed_idx;
+    app.virtual_feed = None;
+    app.clear_article_selection();
+
+    match mark.article_id {
+        Some(article_id) => {
+            if !load_articles_for_history(app, article_id).await? {
+                app.focus = Focus::ArticleList;
+                return Ok(false);
+            }
+        }
+        None => load_articles(app).await?,
+    }
+    app.focus = mark.focus;
+    init_rich_article_state(app, data_dir);
+    Ok(true)
+}
 
-    if let Some(idx) = app.find_article_index(target_article_id) {
-        app.selected_article = idx;
-        app.reset_detail_scroll();
-        app.clear_rich_state();
-        return Ok(true);
+/// Initialize rich content state for the current article. Images are
+/// loaded by the main loop only while `App::images_enabled`; otherwise they
+/// stay placeholders (and screen-reader mode never shows them).
+fn init_rich_article_state(app: &mut App, data_dir: Option<&PathBuf>) {
+    if let Some(article) = app.current_article() {
+        // Check if we already have state for this article
+        // (Simple check: if rich_state exists and has content, keep it)
+        if app.rich_state.is_some() {
+            return;
+        }
+
+        // Parse HTML content or fall back to text
+        let mut rich_state = if let Some(ref html) = article.content {
+            RichArticleState::from_html(html, data_dir)
+        } else if let Some(ref text) = article.content_text {
+            RichArticleState::from_text(text, data_dir)
+        } else {
+            return; // No content to display
+        };
+        rich_state.paragraph_spacing = app.config.ui.reader.paragraph_spacing;
+
+        // Pre-fill images from preload cache (makes images appear instantly)
+        // Uses Arc::clone() for cheap reference counting instead of deep cloning
+        for url in &rich_state.content.image_urls {
+            if let Some(cached) = app.preload_cache.get(url) {
+                rich_state.image_cache.set_loaded_arc(
+                    url,
+                    Arc::clone(&cached.image),
+                    cached.cache_path.clone(),
+                );
+            }
+        }
+
+        app.rich_state = Some(rich_state);
+    } else {
+        app.clear_rich_state();
+    }
+}
+
+/// Helper function to mark article as read; stored with the next flush
+fn mark_article_read(app: &mut App, article_id: Uuid) {
+    app.read_state.record(article_id, true);
+}
+
+/// Helper function to mark article as unread; stored with the next flush
+fn mark_article_unread(app: &mut App, article_id: Uuid) {
+    app.read_state.record(article_id, false);
+}
+
+/// Mark all of a feed's unread articles read, storing the pending read
+/// marks first so that none of them undoes it
+async fn mark_all_read(app: &mut App, feed_id: Uuid) {
+    flush_read_state(app).await;
+    let marked = match app.client.mark_all_read(feed_id).await {
+        Ok(marked) => marked,
+        Err(e) => {
+            app.set_error(format!("Failed to mark all read: {}", e));
+            return;
+        }
+    };
+    for article in app.articles.iter_mut().filter(|a| a.feed_id == feed_id) {
+        article.is_read = true;
+    }
+    let Some(feed) = app.feeds.iter_mut().find(|f| f.id == feed_id) else {
+        return;
+    };
+    feed.unread_count = 0;
+    let status = format!("Marked {} articles read in {}", marked, feed.local_name);
+    app.set_status(status);
+}
+
+/// Store the read-state changes made since the last flush, in one batch
+async fn flush_read_state(app: &mut App) {
+    if app.read_state.is_empty() {
+        return;
+    }
+    let changes = app.read_state.take();
+    if let Err(e) = app.client.set_read_many(&changes).await {
+        app.read_state.restore(changes);
+        app.set_error(format!("Failed to save read state: {}", e));
+    }
+}
+
+/// Record the time spent reading an article for preference learning
+async fn record_reading(app: &App, reading: FinishedReading) {
+    if app.read_mode {
+        return;
+    }
+    let duration_ms = reading.duration.as_millis() as u64;
+    if let Err(e) = app.client.record_read_time(reading.id, duration_ms, reading.scroll_depth).await {
+        tracing::debug!("Failed to record reading time: {}", e);
+    }
+}
+
+/// Play the current article's video or enclosure in `ui.player_command`.
+/// The article is marked read when the player exits cleanly.
+fn play_article(app: &mut App, refresh_tx: mpsc::UnboundedSender<RefreshResult>) {
+    let Some(article) = app.current_article() else {
+        return;
+    };
+    let Some(url) = article.playable_url() else {
+        app.set_warning("Nothing to play in this article");
+        return;
+    };
+    let article_id = article.id;
+    let title = article.title.clone();
+    let command = external::expand_command(&app.config.ui.player_command, url, &title);
+
+    let child = tokio::process::Command::new("sh")
+        .arg("-c")
+        .arg(&command)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    let mut child = match child {
+        Ok(child) => child,
+        Err(e) => {
+            app.set_error(format!("Failed to start player: {}", e));
+            return;
+        }
+    };
+    app.set_status(format!("Playing: {}", title));
+
+    tokio::spawn(async move {
+        let error = match child.wait().await {
+            Ok(status) if status.success() => None,
+            Ok(status) => Some(format!("player exited with {}", status)),
+            Err(e) => Some(e.to_string()),
+        };
+        let _ = refresh_tx.send(RefreshResult::Played { article_id, error });
+    });
+}
+
+/// Open the current article's discussion, looking it up on Hacker News
+/// first when the feed names none
+fn open_discussion(app: &mut App, refresh_tx: mpsc::UnboundedSender<RefreshResult>) {
+    let Some(article) = app.current_article() else {
+        return;
+    };
+    if let Some(url) = article.comments_url.clone() {
+        match open_url(app, &url) {
+            Ok(()) => app.set_status("Opening discussion"),
+            Err(e) => app.set_error(format!("Failed to open discussion: {}", e)),
+        }
+        return;
+    }
+    if article.url.is_none() {
+        app.set_warning("No discussion for this article");
+        return;
+    }
+    spawn_discussion_lookup(app, article.id, 0, true, refresh_tx);
+    app.set_status("Looking for a discussion...");
+}
+
+/// Show or collapse the top comments of the current article's discussion,
+/// fetching them the first time
+fn toggle_comments(app: &mut App, refresh_tx: mpsc::UnboundedSender<RefreshResult>) {
+    let Some(article_id) = app.current_article().map(|a| a.id) else {
+        return;
+    };
+    if let Some(discussion) = app.discussion.as_mut().filter(|d| d.article_id == article_id) {
+        discussion.collapsed = !discussion.collapsed;
+        return;
+    }
+    let limit = app.config.ui.top_comments;
+    if limit == 0 {
+        app.set_warning("Fetching comments is off (ui.top_comments = 0)");
+        return;
+    }
+    app.discussion = Some(Discussion {
+        article_id,
+        loading: true,
+        ..Discussion::default()
+    });
+    spawn_discussion_lookup(app, article_id, limit, false, refresh_tx);
+}
+
+fn spawn_discussion_lookup(
+    app: &App,
+    article_id: Uuid,
+    comments: usize,
+    open: bool,
+    refresh_tx: mpsc::UnboundedSender<RefreshResult>,
+) {
+    let client = app.client.clone();
+    tokio::spawn(async move {
+        let result = client
+            .article_discussion(article_id, comments)
+            .await
+            .map_err(|e| e.to_string());
+        let _ = refresh_tx.send(RefreshResult::Discussion { article_id, open, result });
+    });
+}
+
+/// Apply a finished discussion lookup: remember the link, then open it or
+/// show the comments
+fn handle_discussion(
+    app: &mut App,
+    article_id: Uuid,
+    open: bool,
+    result: std::result::Result<ArticleDiscussionResponse, String>,
+) {
+    let response = match result {
+        Ok(response) => response,
+        Err(error) if open => {
+            app.set_warning(format!("Discussion lookup failed: {}", error));
+            return;
+        }
+        Err(error) => {
+            if let Some(discussion) = app.discussion.as_mut().filter(|d| d.article_id == article_id) {
+                discussion.loading = false;
+                discussion.error = Some(error);
+            }
+            return;
+        }
+    };
+    if let (Some(url), Some(idx)) = (&response.url, app.find_article_index(article_id)) {
+        app.articles[idx].comments_url = Some(url.clone());
+    }
+
+    if open {
+        match response.url {
+            Some(url) => match open_url(app, &url) {
+                Ok(()) => app.set_status("Opening discussion"),
+                Err(e) => app.set_error(format!("Failed to open discussion: {}", e)),
+            },
+            None => app.set_warning("No discussion found"),
+        }
+    } else if let Some(discussion) = app.discussion.as_mut().filter(|d| d.article_id == article_id) {
+        discussion.loading = false;
+        discussion.url = response.url;
+        discussion.comments = response.comments;
+    }
+}
+
+/// Look up the current article's latest Wayback Machine snapshot to open it
+fn open_archived(app: &mut App, refresh_tx: mpsc::UnboundedSender<RefreshResult>) {
+    let Some(article) = app.current_article() else {
+        return;
+    };
+    if article.url.is_none() {
+        app.set_warning("Article has no URL");
+        return;
+    }
+    let article_id = article.id;
+    let client = app.client.clone();
+    tokio::spawn(async move {
+        let result = client.article_archived(article_id).await.map_err(|e| e.to_string());
+        let _ = refresh_tx.send(RefreshResult::Archived { article_id, result });
+    });
+    app.set_status("Looking for an archived copy...");
+}
+
+fn handle_archived(app: &mut App, result: std::result::Result<ArticleArchivedResponse, String>) {
+    let response = match result {
+        Ok(response) => response,
+        Err(error) => {
+            app.set_warning(format!("Wayback Machine lookup failed: {}", error));
+            return;
+        }
+    };
+    let Some(url) = response.snapshot_url else {
+        app.set_warning("No archived copy on the Wayback Machine");
+        return;
+    };
+    if let Err(e) = open_url(app, &url) {
+        app.set_error(format!("Failed to open archived copy: {}", e));
+        return;
+    }
+    let date = snapshot_date(&url).unwrap_or_default();
+    if response.original_gone {
+        app.set_status(format!("Page is gone; opening the archived copy from {}", date));
+    } else {
+        app.set_status(format!("Opening the archived copy from {}", date));
+    }
+}
+
+/// Open the diff between the current GitHub release and the previous one
+fn open_compare(app: &mut App) {
+    let Some(article) = app.current_article() else {
+        return;
+    };
+    if article.url.as_deref().and_then(github::release_of).is_none() {
+        app.set_warning("Not a GitHub release");
+        return;
+    }
+    let Some(url) = github::compare_url(article, &app.articles) else {
+        app.set_warning("No earlier release to compare with");
+        return;
+    };
+    if let Err(e) = open_url(app, &url) {
+        app.set_error(format!("Failed to open the release diff: {}", e));
+    } else {
+        app.set_status("Opening the release diff");
+    }
+}
+
+/// Fetch the current article's web page to read it in place of the feed
+/// content, from the Wayback Machine when the original is gone
+fn load_page(app: &mut App, refresh_tx: mpsc::UnboundedSender<RefreshResult>) {
+    let Some(article) = app.current_article() else {
+        return;
+    };
+    if article.url.is_none() {
+        app.set_warning("Article has no URL");
+        return;
+    }
+    let article_id = article.id;
+    let client = app.client.clone();
+    tokio::spawn(async move {
+        let result = client.article_page(article_id).await.map_err(|e| e.to_string());
+        let _ = refresh_tx.send(RefreshResult::PageLoaded { article_id, result });
+    });
+    app.set_status("Loading the web page...");
+}
+
+/// Show a fetched page in place of the article's feed content (for this
+/// session only; the stored content is left alone)
+fn handle_page_loaded(
+    app: &mut App,
+    article_id: Uuid,
+    result: std::result::Result<FetchedPage, String>,
+    data_dir: Option<&PathBuf>,
+) {
+    let page = match result {
+        Ok(page) => page,
+        Err(error) => {
+            app.set_warning(format!("Failed to load the page: {}", error));
+            return;
+        }
+    };
+    let Some(idx) = app.find_article_index(article_id) else {
+        return;
+    };
+    app.articles[idx].content = Some(page.html);
+    if idx == app.selected_article {
+        app.clear_rich_state();
+        app.reset_detail_scroll();
+        init_rich_article_state(app, data_dir);
+    }
+    match page.snapshot_url.as_deref().and_then(snapshot_date) {
+        Some(date) => app.set_status(format!("Page is gone; showing the archived copy from {}", date)),
+        None => app.set_status("Loaded the web page"),
+    }
+}
+
+/// Open a URL with the configured browser command, or the system default
+fn open_url(app: &mut App, url: &str) -> io::Result<()> {
+    let Some(template) = app.browser_command() else {
+        return open::that(url);
+    };
+    let title = app.current_article().map(|a| a.title.as_str()).unwrap_or("");
+    let command = external::expand_command(template, url, title);
+
+    if app.config.ui.browser_background {
+        external::spawn_background(&command)
+    } else {
+        // The main loop suspends the TUI while it runs
+        app.foreground_command = Some(ForegroundCommand { command, stdin: None });
+        Ok(())
+    }
+}
+
+/// Run a command in the foreground: leave the alternate screen, wait for the
+/// command to exit, then restore the TUI
+fn run_foreground(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    app: &mut App,
+    foreground: ForegroundCommand,
+) -> Result<()> {
+    app.image_renderer.clear_all();
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen, DisableMouseCapture)?;
+    terminal.show_cursor()?;
+
+    let status = std::process::Command::new("sh")
+        .arg("-c")
+        .arg(&foreground.command)
+        .stdin(if foreground.stdin.is_some() { Stdio::piped() } else { Stdio::inherit() })
+        .spawn()
+        .and_then(|mut child| {
+            if let (Some(input), Some(mut stdin)) = (foreground.stdin.as_ref(), child.stdin.take()) {
+                // A pager may quit before reading everything
+                let _ = stdin.write_all(input.as_bytes());
+            }
+            child.wait()
+        });
+
+    enable_raw_mode()?;
+    execute!(terminal.backend_mut(), EnterAlternateScreen, EnableMouseCapture)?;
+    terminal.clear()?;
+
+    match status {
+        Ok(status) if !status.success() => app.set_warning(format!("Command exited with {}", status)),
+        Err(e) => app.set_error(format!("Failed to run command: {}", e)),
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Show the current article URL as a QR code overlay
+fn show_qr_code(app: &mut App) {
+    match app.current_article().and_then(|a| a.url.as_deref()) {
+        Some(url) if QrCode::encode(url.as_bytes()).is_some() => app.mode = Mode::QrCode,
+        Some(_) => app.set_warning("URL is too long for a QR code"),
+        None => {
+            if app.current_article().is_some() {
+                app.set_warning("Article has no URL to share");
+            }
+        }
+    }
+}
+
+/// Start reading the current article aloud, or pause/resume it when it is
+/// the one being read
+fn toggle_read_aloud(app: &mut App) {
+    let Some(article) = app.current_article() else {
+        return;
+    };
+    let article_id = article.id;
+
+    if app.speaker.article_id() == Some(article_id) {
+        match app.speaker.toggle_pause() {
+            Ok(()) if app.speaker.is_paused() => app.set_status("Paused reading aloud"),
+            Ok(()) => app.set_status("Resumed reading aloud"),
+            Err(e) => app.set_error(format!("Read aloud failed: {}", e)),
+        }
+        return;
+    }
+
+    // Same elements as the detail view, so the spoken sentence can be highlighted
+    let sentences = match &app.rich_state {
+        Some(rich_state) => tts::article_sentences(&article.title, &rich_state.content.elements),
+        None => {
+            let content = match (&article.content, &article.content_text) {
+                (Some(html), _) => RichContent::from_html(html),
+                (None, Some(text)) => RichContent::from_text(text),
+                (None, None) => RichContent::from_text(""),
+            };
+            tts::article_sentences(&article.title, &content.elements)
+        }
+    };
+    match app.speaker.start(article_id, sentences) {
+        Ok(()) => app.set_status("Reading aloud"),
+        Err(e) => app.set_error(format!("Read aloud failed: {}", e)),
+    }
+}
+
+/// Run a share menu option for the current article
+fn share_article(app: &mut App, target: ShareTarget) {
+    let Some((title, url)) = app
+        .current_article()
+        .and_then(|a| a.url.clone().map(|url| (a.title.clone(), url)))
+    else {
+        return;
+    };
+
+    let (text, done) = match target {
+        ShareTarget::CopyUrl => (url, "Copied URL"),
+        ShareTarget::CopyMarkdown => (share::markdown_link(&title, &url), "Copied Markdown link"),
+        ShareTarget::ArchiveLink => (share::archive_url(&url), "Copied archive.org link"),
+        ShareTarget::QrCode => {
+            show_qr_code(app);
+            return;
+        }
+        ShareTarget::SendCommand => {
+            let Some(command) = app.config.ui.share_command.clone() else {
+                app.set_warning("No share_command configured in [ui]");
+                return;
+            };
+            match share::send_via_command(&command, &title, &url) {
+                Ok(()) => app.set_status("Sent via share command"),
+                Err(e) => app.set_error(format!("Share command failed: {}", e)),
+            }
+            return;
+        }
+    };
+
+    match share::copy_to_clipboard(&text) {
+        Ok(()) => app.set_status(done),
+        Err(e) => app.set_error(format!("Failed to copy: {}", e)),
+    }
+}
+
+/// Handle an action inside the feed management screen
+/// Set an article's read state in the daemon and the local list
+async fn set_article_read(app: &mut App, idx: usize, read: bool) -> Result<()> {
+    let Some(article) = app.articles.get(idx) else {
+        return Ok(());
+    };
+    if article.is_read == read {
+        return Ok(());
+    }
+    let article_id = article.id;
+    if read {
+        mark_article_read(app, article_id);
+    } else {
+        mark_article_unread(app, article_id);
+    }
+    app.articles[idx].is_read = read;
+    if let Some(feed) = app.article_feed_mut(idx) {
+        if read {
+            feed.unread_count = feed.unread_count.saturating_sub(1);
+        } else {
+            feed.unread_count += 1;
+        }
+    }
+    Ok(())
+}
+
+/// Select another article from the triage view, like moving in the list
+fn select_triage_article(app: &mut App, idx: usize, data_dir: Option<&PathBuf>) {
+    if idx != app.selected_article && idx < app.articles.len() {
+        app.selected_article = idx;
+        app.reset_detail_scroll();
+        app.clear_rich_state();
+        init_rich_article_state(app, data_dir);
+    }
+}
+
+/// Handle an action inside the triage view
+/// Move in the table of contents, or jump to the selected heading
+fn handle_toc_action(app: &mut App, action: TocAction) {
+    let Mode::Toc(selected) = app.mode else {
+        return;
+    };
+    let entries = app.table_of_contents();
+    match action {
+        TocAction::MoveDown => app.mode = Mode::Toc((selected + 1).min(entries.len().saturating_sub(1))),
+        TocAction::MoveUp => app.mode = Mode::Toc(selected.saturating_sub(1)),
+        TocAction::Jump => {
+            app.mode = Mode::Normal;
+            if let Some(entry) = entries.get(selected) {
+                app.scroll_detail_to_element(entry.element_index);
+            }
+        }
+        TocAction::Close => app.mode = Mode::Normal,
+    }
+}
+
+async fn handle_triage_action(
+    app: &mut App,
+    action: TriageAction,
+    data_dir: Option<&PathBuf>,
+) -> Result<()> {
+    let idx = app.selected_article;
+    match action {
+        TriageAction::MoveDown => select_triage_article(app, idx + 1, data_dir),
+        TriageAction::MoveUp => select_triage_article(app, idx.saturating_sub(1), data_dir),
+        TriageAction::Decide(verdict) => {
+            let Some(article) = app.articles.get(idx) else {
+                return Ok(());
+            };
+            // State from before any earlier verdict on this article
+            let decision = app.triage.record(Decision {
+                article_id: article.id,
+                verdict,
+                was_read: article.is_read,
+                was_saved: article.is_saved,
+            });
+            let read = verdict != Verdict::Keep || decision.was_read;
+            let saved = verdict == Verdict::Save || decision.was_saved;
+            set_article_read(app, idx, read).await?;
+            if app.articles[idx].is_saved != saved {
+                app.articles[idx].is_saved = app.client.toggle_saved(decision.article_id).await?;
+            }
+
+            if idx + 1 < app.articles.len() {
+                select_triage_article(app, idx + 1, data_dir);
+            } else {
+                app.set_status(format!("Triage done: {}", app.triage.summary()));
+            }
+        }
+        TriageAction::Undo => {
+            let Some(decision) = app.triage.undo() else {
+                app.set_status("Nothing to undo");
+                return Ok(());
+            };
+            let Some(idx) = app.find_article_index(decision.article_id) else {
+                return Ok(());
+            };
+            set_article_read(app, idx, decision.was_read).await?;
+            if app.articles[idx].is_saved != decision.was_saved {
+                app.articles[idx].is_saved = app.client.toggle_saved(decision.article_id).await?;
+            }
+            select_triage_article(app, idx, data_dir);
+            app.set_status(format!("Undid {}", decision.verdict.label()));
+        }
+        TriageAction::Open => {
+            app.mode = Mode::Normal;
+            app.focus = Focus::ArticleDetail;
+            app.set_status(format!("Triage: {}", app.triage.summary()));
+        }
+        TriageAction::Close => {
+            app.mode = Mode::Normal;
+            app.focus = Focus::ArticleList;
+            app.set_status(format!("Triage: {}", app.triage.summary()));
+        }
+    }
+    Ok(())
+}
+
+/// Handle an action inside the tag prompt
+async fn handle_tag_action(app: &mut App, action: FeedManagerAction) -> Result<()> {
+    let prompt = &mut app.tag_prompt;
+    match action {
+        FeedManagerAction::CancelForm => app.mode = Mode::Normal,
+        FeedManagerAction::NextField => prompt.complete(true),
+        FeedManagerAction::PrevField => prompt.complete(false),
+        FeedManagerAction::Input(c) => prompt.input(c),
+        FeedManagerAction::Backspace => prompt.backspace(),
+        FeedManagerAction::CursorLeft => prompt.cursor_left(),
+        FeedManagerAction::CursorRight => prompt.cursor_right(),
+        FeedManagerAction::Save => {
+            let tags = prompt.tags();
+            if tags.is_empty() {
+                prompt.error = Some("Type at least one tag".to_string());
+                return Ok(());
+            }
+            let targets = prompt.targets.clone();
+            match app.client.add_tags(&targets, &tags).await {
+                Ok(_) => {
+                    for article in app.articles.iter_mut().filter(|a| targets.contains(&a.id)) {
+                        for tag in &tags {
+                            if !article.tags.contains(tag) {
+                                article.tags.push(tag.clone());
+                            }
+                        }
+                    }
+                    app.mode = Mode::Normal;
+                    app.clear_article_selection();
+                    let noun = if targets.len() == 1 { "article" } else { "articles" };
+                    app.set_status(format!("Tagged {} {}: {}", targets.len(), noun, tags.join(", ")));
+                }
+                Err(e) => app.tag_prompt.error = Some(format!("Tagging failed: {}", e)),
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Handle an action inside the settings screen
+fn handle_settings_action(app: &mut App, action: SettingsAction, data_dir: Option<&PathBuf>) {
+    let setting = app.settings.selected_setting();
+    match action {
+        SettingsAction::MoveUp => app.settings.move_up(),
+        SettingsAction::MoveDown => app.settings.move_down(),
+        SettingsAction::Close => app.mode = Mode::Normal,
+        SettingsAction::Change | SettingsAction::ChangeBack => {
+            let value = setting.value(&app.config);
+            match setting.cycle(&value, action == SettingsAction::Change) {
+                Some(value) => save_setting(app, setting, value, data_dir),
+                None if action == SettingsAction::Change => {
+                    app.settings.editing = Some(TextField::new(value.to_string()));
+                }
+                None => {}
+            }
+        }
+    }
+}
+
+/// Handle an action while typing the value of a setting
+fn handle_settings_prompt(app: &mut App, action: FeedManagerAction, data_dir: Option<&PathBuf>) {
+    let setting = app.settings.selected_setting();
+    let screen = &mut app.settings;
+    let Some(ref mut text) = screen.editing else {
+        return;
+    };
+    match action {
+        FeedManagerAction::CancelForm => {
+            screen.editing = None;
+            screen.error = None;
+        }
+        FeedManagerAction::Input(c) => text.insert(c),
+        FeedManagerAction::Backspace => text.backspace(),
+        FeedManagerAction::CursorLeft => text.move_left(),
+        FeedManagerAction::CursorRight => text.move_right(),
+        FeedManagerAction::Save => match setting.parse(&text.value) {
+            Ok(value) => save_setting(app, setting, value, data_dir),
+            Err(e) => screen.error = Some(e),
+        },
+        _ => {}
+    }
+}
+
+/// Write a setting to the config file and apply it to the running TUI
+fn save_setting(app: &mut App, setting: Setting, value: SettingValue, data_dir: Option<&PathBuf>) {
+    if let Err(e) = write_settings(&AppConfig::config_path(), &[(setting.key(), value.to_toml())]) {
+        app.settings.error = Some(format!("Saving failed: {}", e));
+        return;
+    }
+    setting.apply(Arc::make_mut(&mut app.config), &value);
+    app.settings.editing = None;
+    app.settings.error = None;
+
+    match setting {
+        Setting::Theme => {
+            let depth = ColorDepth::from_mode(app.config.ui.color_mode);
+            app.theme = ThemeSwitcher::new(&app.config.ui.theme, depth).initial_theme();
+        }
+        Setting::ImagePreview => {
+            app.clear_rich_state();
+            init_rich_article_state(app, data_dir);
+        }
+        _ => {}
+    }
+    if setting.is_live() {
+        app.set_status(format!("{}: {}", setting.label(), value));
+    } else {
+        app.set_status(format!(
+            "{}: {} (applies when the daemon restarts)",
+            setting.label(),
+            value
+        ));
+    }
+}
+
+async fn handle_feed_manager_action(
+    app: &mut App,
+    action: FeedManagerAction,
+    data_dir: Option<&PathBuf>,
+) -> Result<()> {
+    let selected = app.feeds.get(app.feed_manager.selected).cloned();
+
+    match action {
+        FeedManagerAction::MoveUp => app.feed_manager.move_up(),
+        FeedManagerAction::MoveDown => app.feed_manager.move_down(app.feeds.len()),
+        FeedManagerAction::Add => app.feed_manager.form = Some(FeedForm::new()),
+        FeedManagerAction::Edit => {
+            if let Some(feed) = selected {
+                app.feed_manager.form = Some(FeedForm::edit(&feed));
+            }
+        }
+        FeedManagerAction::TogglePause => {
+            if let Some(feed) = selected {
+                toggle_feed_pause(app, &feed, data_dir).await?;
+            }
+        }
+        FeedManagerAction::Delete => {
+            app.feed_manager.confirm_delete = selected.is_some();
+        }
+        FeedManagerAction::ConfirmDelete => {
+            app.feed_manager.confirm_delete = false;
+            if let Some(feed) = selected {
+                match app.client.delete_feed(feed.id).await {
+                    Ok(_) => {
+                        reload_managed_feeds(app, None, data_dir).await?;
+                        app.set_status(format!("Deleted {}", feed.local_name));
+                    }
+                    Err(e) => app.set_error(format!("Failed to delete feed: {}", e)),
+                }
+            }
+        }
+        FeedManagerAction::CancelDelete => app.feed_manager.confirm_delete = false,
+        FeedManagerAction::Close => app.mode = Mode::Normal,
+        FeedManagerAction::Save => save_feed_form(app, data_dir).await?,
+        FeedManagerAction::CancelForm => app.feed_manager.form = None,
+        _ => {
+            let Some(form) = app.feed_manager.form.as_mut() else {
+                return Ok(());
+            };
+            match action {
+                FeedManagerAction::NextField => form.next_field(),
+                FeedManagerAction::PrevField => form.prev_field(),
+                FeedManagerAction::Input(c) => form.input(c),
+                FeedManagerAction::Backspace => form.backspace(),
+                FeedManagerAction::CursorLeft => form.cursor_left(),
+                FeedManagerAction::CursorRight => form.cursor_right(),
+                _ => {}
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Pause or resume fetching a feed
+async fn toggle_feed_pause(app: &mut App, feed: &Feed, data_dir: Option<&PathBuf>) -> Result<()> {
+    let mut settings = feed.settings();
+    settings.paused = !settings.paused;
+    match app.client.update_feed(feed.id, &settings).await {
+        Ok(_) => {
+            reload_managed_feeds(app, Some(feed.id), data_dir).await?;
+            let verb = if settings.paused { "Paused" } else { "Resumed" };
+            app.set_status(format!("{} {}", verb, feed.local_name));
+        }
+        Err(e) => app.set_error(format!("Failed to update feed: {}", e)),
+    }
+    Ok(())
+}
+
+/// Entries listed per section of the interest profile
+const PROFILE_ENTRIES: usize = 10;
+
+/// Show the interest profile learned in a time window
+async fn show_profile(app: &mut App, window: TimeWindow) {
+    match app.client.profile_insights(window, PROFILE_ENTRIES).await {
+        Ok(profile) => {
+            app.profile = Some(profile);
+            app.mode = Mode::Profile(window);
+        }
+        Err(e) => app.set_error(format!("Failed to load the interest profile: {}", e)),
+    }
+}
+
+/// Swap the selected feed with its visible neighbor and persist the new order
+async fn move_feed(app: &mut App, up: bool, data_dir: Option<&PathBuf>) -> Result<()> {
+    let Some(visible_idx) = app.actual_to_visible_feed_index(app.selected_feed) else {
+        return Ok(());
+    };
+    let neighbor = if up {
+        visible_idx.checked_sub(1)
+    } else {
+        Some(visible_idx + 1)
+    };
+    let Some(target) = neighbor.and_then(|idx| app.visible_to_actual_feed_index(idx)) else {
+        return Ok(());
+    };
+
+    match swapped_feed_order(&app.feeds, app.selected_feed, target) {
+        Some(ids) => {
+            if let Err(e) = app.client.reorder_feeds(&ids).await {
+                app.set_error(format!("Failed to reorder feeds: {}", e));
+            } else {
+                reload_managed_feeds(app, None, data_dir).await?;
+            }
+        }
+        None => app.set_status("Pinned feeds stay above unpinned ones (P to pin/unpin)"),
     }
+    Ok(())
+}
 
-    // If in unread-only mode and article not found, load all articles
-    if unread_only {
-        app.articles = if app.read_mode {
-            let db = db.expect("Database required in read-mode");
-            ArticleRepository::new(db).list_by_feed(feed_id, false).await?
-        } else {
-            let client = app.client.as_ref().expect("Client required in normal mode");
-            client.list_articles(Some(feed_id), false).await?
-        };
+/// Validate and save the feed manager form, keeping it open on errors
+async fn save_feed_form(app: &mut App, data_dir: Option<&PathBuf>) -> Result<()> {
+    let Some(form) = app.feed_manager.form.as_mut() else {
+        return Ok(());
+    };
+    let settings = match form.to_settings() {
+        Ok(settings) => settings,
+        Err(e) => {
+            form.error = Some(e);
+            return Ok(());
+        }
+    };
+    let feed_id = form.feed_id;
 
-        if let Some(idx) = app.find_article_index(target_article_id) {
-            app.selected_article = idx;
-            app.reset_detail_scroll();
-            app.clear_rich_state();
-            // Temporarily switch to All mode to show the article
-            app.view_mode = ViewMode::All;
-            return Ok(true);
+    let result = match feed_id {
+        Some(id) => app.client.update_feed(id, &settings).await,
+        None => add_feed_with_settings(app, &settings).await,
+    };
+
+    match result {
+        Ok(feed) => {
+            app.feed_manager.form = None;
+            reload_managed_feeds(app, Some(feed.id), data_dir).await?;
+            let verb = if feed_id.is_some() { "Saved" } else { "Added" };
+            app.set_status(format!("{} {}", verb, feed.local_name));
+        }
+        Err(e) => {
+            if let Some(form) = app.feed_manager.form.as_mut() {
+                form.error = Some(e.to_string());
+            }
         }
     }
 
-    Ok(false)
+    Ok(())
 }
 
-/// Initialize rich content state for the current article
-fn init_rich_article_state(app: &mut App, data_dir: Option<&PathBuf>) {
-    // Only initialize if image preview is enabled
-    if !app.config.ui.image_preview {
-        app.clear_rich_state();
-        return;
+/// Subscribe to a feed and apply the settings `feed.add` doesn't take
+async fn add_feed_with_settings(
+    app: &App,
+    settings: &FeedSettings,
+) -> kenseader_core::Result<Feed> {
+    let feed = app.client.add_feed(&settings.url, &settings.local_name).await?;
+    if feed.settings() == *settings {
+        Ok(feed)
+    } else {
+        app.client.update_feed(feed.id, settings).await
     }
+}
 
-    if let Some(article) = app.current_article() {
-        // Check if we already have state for this article
-        // (Simple check: if rich_state exists and has content, keep it)
-        if app.rich_state.is_some() {
-            return;
-        }
-
-        // Parse HTML content or fall back to text
-        let mut rich_state = if let Some(ref html) = article.content {
-            RichArticleState::from_html(html, data_dir)
-        } else if let Some(ref text) = article.content_text {
-            RichArticleState::from_text(text, data_dir)
-        } else {
-            return; // No content to display
-        };
+/// Handle an action inside the subscribe prompt
+fn handle_subscribe_action(
+    app: &mut App,
+    action: FeedManagerAction,
+    refresh_tx: mpsc::UnboundedSender<RefreshResult>,
+) {
+    let prompt = &mut app.subscribe_prompt;
+    match action {
+        FeedManagerAction::CancelForm => app.mode = Mode::Normal,
+        FeedManagerAction::NextField | FeedManagerAction::PrevField => prompt.toggle_field(),
+        FeedManagerAction::Input(c) => prompt.input(c),
+        FeedManagerAction::Backspace => prompt.backspace(),
+        FeedManagerAction::CursorLeft => prompt.cursor_left(),
+        FeedManagerAction::CursorRight => prompt.cursor_right(),
+        FeedManagerAction::Save => {
+            let url = prompt.url.value.trim().to_string();
+            if url.is_empty() {
+                prompt.error = Some("URL is required".to_string());
+                return;
+            }
+            let name = prompt.name.value.trim().to_string();
+            prompt.pending = true;
+            prompt.error = None;
+            let client = app.client.clone();
 
-        // Pre-fill images from preload cache (makes images appear instantly)
-        // Uses Arc::clone() for cheap reference counting instead of deep cloning
-        for url in &rich_state.content.image_urls {
-            if let Some(cached) = app.preload_cache.get(url) {
-                rich_state.image_cache.set_loaded_arc(
-                    url,
-                    Arc::clone(&cached.image),
-                    cached.cache_path.clone(),
-                );
+            if let Some((feed_id, old_name)) = prompt.duplicate.take() {
+                tokio::spawn(async move {
+                    let result = rename_feed(&client, feed_id, &name).await.map(|feed| {
+                        RefreshResult::SubscriptionRenamed {
+                            feed_id,
+                            old_name,
+                            name: feed.local_name,
+                        }
+                    });
+                    let _ = refresh_tx.send(result.unwrap_or_else(|e| RefreshResult::SubscribeFailure {
+                        error: e.to_string(),
+                    }));
+                });
+                return;
             }
-        }
 
-        app.rich_state = Some(rich_state);
-    } else {
-        app.clear_rich_state();
+            // Discovery and the first fetch go over the network, so keep the UI responsive
+            tokio::spawn(async move {
+                let result = subscribe_feed(&client, &url, &name).await.unwrap_or_else(|e| {
+                    RefreshResult::SubscribeFailure {
+                        error: e.to_string(),
+                    }
+                });
+                let _ = refresh_tx.send(result);
+            });
+        }
+        _ => {}
     }
 }
 
-/// Helper function to mark article as read (handles both read-mode and normal mode)
-async fn mark_article_read(
-    app: &App,
-    client: Option<&Arc<DaemonClient>>,
-    db: Option<&Arc<Database>>,
-    article_id: Uuid,
-) -> Result<()> {
-    if app.read_mode {
-        let db = db.expect("Database required in read-mode");
-        ArticleRepository::new(db).mark_read(article_id).await?;
-    } else {
-        let client = client.expect("Client required in normal mode");
-        client.mark_read(article_id).await?;
+/// Find the feed behind `url` (a feed or a page advertising one), subscribe
+/// to it and fetch its first articles, unless it is already subscribed
+async fn subscribe_feed(
+    client: &DaemonClient,
+    url: &str,
+    name: &str,
+) -> Result<RefreshResult> {
+    let discovered = client.discover_feed(url).await?;
+    if let Some(existing) = discovered.existing {
+        return Ok(RefreshResult::SubscribeDuplicate {
+            feed_id: existing.id,
+            name: existing.local_name,
+        });
     }
-    Ok(())
+    let name = if name.is_empty() {
+        default_feed_name(discovered.feed.title.as_deref(), &discovered.feed.url)
+    } else {
+        name.to_string()
+    };
+    let feed = client.add_feed(&discovered.feed.url, &name).await?;
+    // The subscription stands even if the first fetch fails; the next refresh retries it
+    let new_count = client.refresh(Some(feed.id)).await.unwrap_or(0);
+    Ok(RefreshResult::Subscribed {
+        feed_id: feed.id,
+        name: feed.local_name,
+        new_count,
+    })
 }
 
-/// Helper function to mark article as unread (handles both read-mode and normal mode)
-async fn mark_article_unread(
-    app: &App,
-    client: Option<&Arc<DaemonClient>>,
-    db: Option<&Arc<Database>>,
-    article_id: Uuid,
+/// Rename the subscription `feed_id` to `name`
+async fn rename_feed(client: &DaemonClient, feed_id: Uuid, name: &str) -> Result<Feed> {
+    let feed = client
+        .list_feeds()
+        .await?
+        .into_iter()
+        .find(|f| f.id == feed_id)
+        .ok_or_else(|| anyhow::anyhow!("The subscription is gone"))?;
+    let mut settings = feed.settings();
+    settings.local_name = name.to_string();
+    Ok(client.update_feed(feed_id, &settings).await?)
+}
+
+/// Reload feeds after a change in the feed manager, keeping the main view on
+/// the same feed and moving the manager's selection to `focus_id`
+async fn reload_managed_feeds(
+    app: &mut App,
+    focus_id: Option<Uuid>,
+    data_dir: Option<&PathBuf>,
 ) -> Result<()> {
-    if app.read_mode {
-        let db = db.expect("Database required in read-mode");
-        ArticleRepository::new(db).mark_unread(article_id).await?;
-    } else {
-        let client = client.expect("Client required in normal mode");
-        client.mark_unread(article_id).await?;
+    let current_id = app.current_feed().map(|f| f.id);
+    flush_read_state(app).await;
+    app.feeds = app.client.list_feeds().await?;
+    app.selected_feed = current_id
+        .and_then(|id| app.find_feed_index(id))
+        .unwrap_or(0);
+    ensure_valid_feed_selection(app);
+    load_articles(app).await?;
+    init_rich_article_state(app, data_dir);
+
+    if let Some(idx) = focus_id.and_then(|id| app.find_feed_index(id)) {
+        app.feed_manager.selected = idx;
     }
+    app.feed_manager.clamp(app.feeds.len());
     Ok(())
 }
 
 async fn handle_action(
     app: &mut App,
     action: Action,
-    client: Option<&Arc<DaemonClient>>,
-    db: Option<&Arc<Database>>,
     data_dir: Option<&PathBuf>,
     refresh_tx: mpsc::UnboundedSender<RefreshResult>,
 ) -> Result<()> {
-    // Clear pending key on any action except PendingG
-    if action != Action::PendingG && action != Action::JumpToTop {
+    // Clear pending key on any action except starting a sequence
+    if !matches!(action, Action::PendingKey(_)) && action != Action::JumpToTop {
         app.clear_pending_key();
     }
+    // A register command only applies to the key right after it, and a
+    // count to the command it prefixes
+    if !matches!(
+        action,
+        Action::RecordMacro | Action::PlayMacro | Action::SetMark | Action::JumpToMark
+    ) {
+        app.pending_register = None;
+    }
+    let count = match action {
+        Action::CountDigit(_) | Action::PlayMacro => None,
+        _ => app.pending_count.take(),
+    };
 
     match action {
         Action::Quit => {
             app.should_quit = true;
         }
         Action::FocusLeft => {
+            // In the subscriptions, go up the feed tree: from a feed to its
+            // category, from an expanded category to collapsing it
+            if app.focus == Focus::Subscriptions && app.virtual_feed.is_none() {
+                app.ascend_feed_tree();
+                return Ok(());
+            }
             let prev_focus = app.focus;
             app.focus_left();
+            // Leaving a virtual feed returns to the selected feed
+            if app.virtual_feed.is_some() && app.focus == Focus::Subscriptions {
+                app.virtual_feed = None;
+                app.clear_article_selection();
+                load_articles(app).await?;
+                init_rich_article_state(app, data_dir);
+            }
             // Reload articles when returning from ArticleDetail to ArticleList in unread-only mode
             // This removes the just-read article from the filtered list
             if prev_focus == Focus::ArticleDetail
@@ -721,12 +2272,16 @@ async fn handle_action(
             {
                 // Ensure feed selection is valid (feed may be hidden if all articles read)
                 ensure_valid_feed_selection(app);
-                load_articles_preserve_selection(app, db, true).await?;
+                load_articles_preserve_selection(app, true).await?;
                 // Re-initialize rich state since the article at the current index may have changed
                 init_rich_article_state(app, data_dir);
             }
         }
         Action::FocusRight => {
+            // A collapsed category in the subscriptions expands first
+            if app.focus == Focus::Subscriptions && app.expand_cursor_category() {
+                return Ok(());
+            }
             let prev_focus = app.focus;
             app.focus_right();
             // Auto mark-read when entering article detail
@@ -739,13 +2294,13 @@ async fn handle_action(
                     if !article.is_read {
                         let article_id = article.id;
                         // Mark as read
-                        mark_article_read(app, client, db, article_id).await?;
+                        mark_article_read(app, article_id);
                         // Update local state without reloading (keeps article visible in unread-only mode)
                         if let Some(article) = app.current_article_mut() {
                             article.is_read = true;
                         }
                         // Decrement feed unread count
-                        if let Some(feed) = app.current_feed_mut() {
+                        if let Some(feed) = app.article_feed_mut(app.selected_article) {
                             feed.unread_count = feed.unread_count.saturating_sub(1);
                         }
                     }
@@ -758,18 +2313,9 @@ async fn handle_action(
             let prev_feed = app.selected_feed;
             let prev_article = app.selected_article;
 
-            // For Subscriptions, use visible feed navigation
+            // For Subscriptions, move through the rows of the feed tree
             if app.focus == Focus::Subscriptions {
-                let visible_feeds = app.visible_feeds();
-                if !visible_feeds.is_empty() {
-                    let current_visible = app
-                        .actual_to_visible_feed_index(app.selected_feed)
-                        .unwrap_or(0);
-                    let next_visible = current_visible.saturating_sub(1);
-                    if let Some(actual_idx) = app.visible_to_actual_feed_index(next_visible) {
-                        app.selected_feed = actual_idx;
-                    }
-                }
+                app.move_feed_cursor(-1);
                 // Update visual selection if in visual mode
                 app.update_visual_selection_feeds();
             } else if app.focus == Focus::ArticleDetail {
@@ -784,7 +2330,7 @@ async fn handle_action(
             if app.focus == Focus::Subscriptions && prev_feed != app.selected_feed {
                 // Clear preload cache when switching feeds
                 app.preload_cache.clear();
-                load_articles(app, db).await?;
+                load_articles(app).await?;
                 // Initialize rich state for the first article in the new feed
                 init_rich_article_state(app, data_dir);
             }
@@ -799,18 +2345,9 @@ async fn handle_action(
             let prev_feed = app.selected_feed;
             let prev_article = app.selected_article;
 
-            // For Subscriptions, use visible feed navigation
+            // For Subscriptions, move through the rows of the feed tree
             if app.focus == Focus::Subscriptions {
-                let visible_feeds = app.visible_feeds();
-                if !visible_feeds.is_empty() {
-                    let current_visible = app
-                        .actual_to_visible_feed_index(app.selected_feed)
-                        .unwrap_or(0);
-                    let next_visible = (current_visible + 1).min(visible_feeds.len() - 1);
-                    if let Some(actual_idx) = app.visible_to_actual_feed_index(next_visible) {
-                        app.selected_feed = actual_idx;
-                    }
-                }
+                app.move_feed_cursor(1);
                 // Update visual selection if in visual mode
                 app.update_visual_selection_feeds();
             } else if app.focus == Focus::ArticleDetail {
@@ -825,7 +2362,7 @@ async fn handle_action(
             if app.focus == Focus::Subscriptions && prev_feed != app.selected_feed {
                 // Clear preload cache when switching feeds
                 app.preload_cache.clear();
-                load_articles(app, db).await?;
+                load_articles(app).await?;
                 // Initialize rich state for the first article in the new feed
                 init_rich_article_state(app, data_dir);
             }
@@ -865,11 +2402,11 @@ async fn handle_action(
                 if let Some(article) = app.current_article() {
                     if !article.is_read {
                         let article_id = article.id;
-                        mark_article_read(app, client, db, article_id).await?;
+                        mark_article_read(app, article_id);
                         if let Some(article) = app.current_article_mut() {
                             article.is_read = true;
                         }
-                        if let Some(feed) = app.current_feed_mut() {
+                        if let Some(feed) = app.article_feed_mut(app.selected_article) {
                             feed.unread_count = feed.unread_count.saturating_sub(1);
                         }
                     }
@@ -907,11 +2444,11 @@ async fn handle_action(
                 if let Some(article) = app.current_article() {
                     if !article.is_read {
                         let article_id = article.id;
-                        mark_article_read(app, client, db, article_id).await?;
+                        mark_article_read(app, article_id);
                         if let Some(article) = app.current_article_mut() {
                             article.is_read = true;
                         }
-                        if let Some(feed) = app.current_feed_mut() {
+                        if let Some(feed) = app.article_feed_mut(app.selected_article) {
                             feed.unread_count = feed.unread_count.saturating_sub(1);
                         }
                     }
@@ -1029,24 +2566,75 @@ async fn handle_action(
                 }
             }
         }
-        Action::PendingG => {
-            app.pending_key = Some('g');
+        Action::PendingKey(prefix) => {
+            app.set_pending_key(prefix);
+        }
+        Action::RecordMacro => {
+            if let Some((register, len)) = app.macros.stop() {
+                app.set_status(format!("Recorded macro @{} ({} keys)", register, len));
+            } else {
+                app.pending_register = Some(RegisterCommand::RecordMacro);
+            }
+        }
+        Action::PlayMacro => {
+            app.pending_register = Some(RegisterCommand::PlayMacro);
         }
+        Action::StartRecording(register) => {
+            app.macros.start(register);
+            app.clear_status();
+        }
+        Action::ReplayMacro(register) => {
+            // The main loop feeds the queued keys back through the input layer
+            if app.macros.play(register, count.unwrap_or(1) as usize).is_none() {
+                app.set_warning(format!("No macro recorded in @{}", register));
+            }
+        }
+        Action::CountDigit(digit) => {
+            let count = app.pending_count.unwrap_or(0);
+            app.pending_count = Some(count.saturating_mul(10).saturating_add(digit).min(9999));
+        }
+        Action::SetMark => {
+            app.pending_register = Some(RegisterCommand::SetMark);
+        }
+        Action::JumpToMark => {
+            app.pending_register = Some(RegisterCommand::JumpToMark);
+        }
+        Action::PlaceMark(register) => {
+            if app.set_mark(register) {
+                app.set_status(format!("Mark '{} set", register));
+            }
+        }
+        Action::GoToMark(register) => match app.marks.get(&register).copied() {
+            Some(mark) => {
+                let previous = app.current_mark();
+                if jump_to_mark(app, mark, data_dir).await? {
+                    if let Some(previous) = previous {
+                        app.marks.insert(PREVIOUS_MARK, previous);
+                    }
+                    app.clear_status();
+                } else {
+                    app.set_warning(format!("Mark '{} no longer exists", register));
+                }
+            }
+            None => app.set_warning(format!("Mark '{} not set", register)),
+        },
         Action::Select => {
-            if app.focus == Focus::ArticleList {
+            if app.focus == Focus::Subscriptions {
+                app.toggle_cursor_category();
+            } else if app.focus == Focus::ArticleList {
                 // Record history before entering article
                 app.push_history();
                 // Mark as read and switch to detail
                 if let Some(article) = app.current_article() {
                     if !article.is_read {
                         let article_id = article.id;
-                        mark_article_read(app, client, db, article_id).await?;
+                        mark_article_read(app, article_id);
                         // Update local state without reloading (keeps article visible in unread-only mode)
                         if let Some(article) = app.current_article_mut() {
                             article.is_read = true;
                         }
                         // Decrement feed unread count
-                        if let Some(feed) = app.current_feed_mut() {
+                        if let Some(feed) = app.article_feed_mut(app.selected_article) {
                             feed.unread_count = feed.unread_count.saturating_sub(1);
                         }
                     }
@@ -1058,56 +2646,54 @@ async fn handle_action(
         }
         Action::OpenInBrowser => {
             // Smart open: if a link is focused, open that link; otherwise open article URL
-            let mut opened = false;
-
-            if let Some(ref rich_state) = app.rich_state {
-                if let Some(FocusableItem::Link { url, text, .. }) = rich_state.get_focused_item() {
-                    // Open focused link in browser
-                    if let Err(e) = open::that(url) {
-                        app.set_status(format!("Failed to open link: {}", e));
-                    } else {
-                        let display = if text.len() > 30 {
-                            format!("{}...", &text[..27])
-                        } else {
-                            text.clone()
-                        };
-                        app.set_status(format!("Opening: {}", display));
-                    }
-                    opened = true;
+            let focused_link = app.rich_state.as_ref().and_then(|rich_state| {
+                match rich_state.get_focused_item() {
+                    Some(FocusableItem::Link { url, text, .. }) => Some((url.clone(), text.clone())),
+                    _ => None,
                 }
-            }
+            });
 
-            // If no link focused, open article URL
-            if !opened {
-                if let Some(article) = app.current_article() {
-                    if let Some(url) = &article.url {
-                        if let Err(e) = open::that(url) {
-                            app.set_status(format!("Failed to open browser: {}", e));
-                        }
-                    }
+            if let Some((url, text)) = focused_link {
+                // Open focused link in browser
+                if let Err(e) = open_url(app, &url) {
+                    app.set_error(format!("Failed to open link: {}", e));
+                } else {
+                    let display = truncate_str(&text, 30);
+                    app.set_status(format!("Opening: {}", display));
+                }
+            } else if let Some(url) = app.current_article().and_then(|a| a.url.clone()) {
+                // If no link focused, open article URL
+                if let Err(e) = open_url(app, &url) {
+                    app.set_error(format!("Failed to open browser: {}", e));
                 }
             }
         }
         Action::ToggleSaved => {
             if let Some(article) = app.current_article() {
                 let article_id = article.id;
-                // Toggle saved in database (read-mode) or via daemon
-                let saved = if app.read_mode {
-                    let db = db.expect("Database required in read-mode");
-                    ArticleRepository::new(db).toggle_saved(article_id).await?
-                } else {
-                    let client = client.expect("Client required in normal mode");
-                    client.toggle_saved(article_id).await?
-                };
+                let saved = app.client.toggle_saved(article_id).await?;
                 app.set_status(if saved { "Article saved" } else { "Article unsaved" });
-                load_articles_preserve_selection(app, db, true).await?;
+                load_articles_preserve_selection(app, true).await?;
                 init_rich_article_state(app, data_dir);
             }
         }
+        Action::MoreLikeThis | Action::LessLikeThis => {
+            if app.read_mode {
+                app.set_warning("Feedback disabled in read-mode");
+            } else if let Some(article_id) = app.current_article().map(|a| a.id) {
+                let more = action == Action::MoreLikeThis;
+                app.client.article_feedback(article_id, more).await?;
+                app.set_status(if more {
+                    "You'll see more articles like this"
+                } else {
+                    "You'll see fewer articles like this"
+                });
+            }
+        }
         Action::Delete => {
             // Delete is disabled in read-mode (for feeds)
             if app.read_mode {
-                app.set_status("Feed delete disabled in read-mode");
+                app.set_warning("Feed delete disabled in read-mode");
             } else {
                 // Batch delete takes priority if feeds are selected (regardless of current focus)
                 if !app.selected_feeds.is_empty() {
@@ -1120,27 +2706,44 @@ async fn handle_action(
                 }
             }
         }
+        Action::MarkAllRead => {
+            // The selected feed in the feed list, the article's feed elsewhere
+            let feed = if app.focus == Focus::Subscriptions {
+                app.current_feed()
+            } else {
+                app.current_article_feed()
+            };
+            match feed.map(|f| (f.id, f.unread_count, f.local_name.clone())) {
+                Some((_, 0, name)) => app.set_status(format!("No unread articles in {}", name)),
+                Some((feed_id, _, _)) => app.mode = Mode::MarkAllReadConfirm(feed_id),
+                None => {}
+            }
+        }
         Action::Confirm => {
             match &app.mode {
                 Mode::DeleteConfirm(feed_id) => {
                     let feed_id = *feed_id;
                     // Delete is disabled in read-mode
                     if app.read_mode {
-                        app.set_status("Feed delete disabled in read-mode");
+                        app.set_warning("Feed delete disabled in read-mode");
                         app.mode = Mode::Normal;
                     } else {
-                        let client = client.expect("Client required for delete");
-                        client.delete_feed(feed_id).await?;
+                        app.client.delete_feed(feed_id).await?;
                         app.mode = Mode::Normal;
-                        load_feeds(app, db).await?;
+                        load_feeds(app).await?;
                         init_rich_article_state(app, data_dir);
                         app.set_status("Feed deleted");
                     }
                 }
+                Mode::MarkAllReadConfirm(feed_id) => {
+                    let feed_id = *feed_id;
+                    app.mode = Mode::Normal;
+                    mark_all_read(app, feed_id).await;
+                }
                 Mode::BatchDeleteConfirm => {
                     // Batch delete is disabled in read-mode
                     if app.read_mode {
-                        app.set_status("Feed delete disabled in read-mode");
+                        app.set_warning("Feed delete disabled in read-mode");
                         app.mode = Mode::Normal;
                         app.selected_feeds.clear();
                     } else {
@@ -1148,7 +2751,7 @@ async fn handle_action(
                         let indices: Vec<usize> = app.selected_feeds.iter().cloned().collect();
                         let mut deleted_count = 0;
                         let mut errors = Vec::new();
-                        let client = client.expect("Client required for delete");
+                        let client = app.client.clone();
 
                         for &idx in &indices {
                             if let Some(feed) = app.feeds.get(idx) {
@@ -1166,16 +2769,20 @@ async fn handle_action(
 
                         app.mode = Mode::Normal;
                         app.clear_feed_selection();
-                        load_feeds(app, db).await?;
+                        load_feeds(app).await?;
                         init_rich_article_state(app, data_dir);
 
                         if errors.is_empty() {
                             app.set_status(format!("Deleted {} feeds", deleted_count));
                         } else {
-                            app.set_status(format!("Deleted {} feeds, {} errors", deleted_count, errors.len()));
+                            app.set_error(format!("Deleted {} feeds, {} errors", deleted_count, errors.len()));
                         }
                     }
                 }
+                Mode::FeedFilter => {
+                    // Keep the filter and go back to browsing
+                    app.mode = Mode::Normal;
+                }
                 Mode::SearchForward(_) | Mode::SearchBackward(_) => {
                     app.execute_search();
                     let match_count = app.search_matches.len();
@@ -1189,35 +2796,299 @@ async fn handle_action(
                 _ => {}
             }
         }
+        Action::Share => {
+            match app.current_article() {
+                Some(article) if article.url.is_some() => app.mode = Mode::Share,
+                Some(_) => app.set_warning("Article has no URL to share"),
+                None => {}
+            }
+        }
+        Action::ShareTo(target) => {
+            app.mode = Mode::Normal;
+            share_article(app, target);
+        }
+        Action::ShowQrCode => {
+            show_qr_code(app);
+        }
+        Action::OpenInPager => {
+            if let Some(article) = app.current_article() {
+                let command = external::pager_command(app.config.ui.pager_command.as_deref());
+                let stdin = Some(external::article_text(article));
+                app.foreground_command = Some(ForegroundCommand { command, stdin });
+            }
+        }
+        Action::OpenFeedManager => {
+            if app.read_mode {
+                app.set_warning("Feed management disabled in read-mode");
+            } else {
+                app.feed_manager = FeedManager::new(app.selected_feed);
+                app.mode = Mode::FeedManager;
+            }
+        }
+        Action::FeedManager(action) => {
+            handle_feed_manager_action(app, action, data_dir).await?;
+        }
+        Action::AddFeed => {
+            if app.read_mode {
+                app.set_warning("Subscribing disabled in read-mode");
+            } else {
+                app.subscribe_prompt = SubscribePrompt::default();
+                app.mode = Mode::Subscribe;
+            }
+        }
+        Action::SubscribePrompt(action) => handle_subscribe_action(app, action, refresh_tx),
+        Action::TagArticles => {
+            // Selected articles, or the current one
+            let mut indices: Vec<usize> = app.selected_articles.iter().copied().collect();
+            if indices.is_empty() {
+                indices.push(app.selected_article);
+            }
+            indices.sort_unstable();
+            let targets: Vec<Uuid> = indices
+                .iter()
+                .filter_map(|&idx| app.articles.get(idx).map(|a| a.id))
+                .collect();
+            if !targets.is_empty() {
+                let known = app.client.list_tags().await.unwrap_or_default();
+                app.tag_prompt = TagPrompt::new(targets, known);
+                app.mode = Mode::Tag;
+            }
+        }
+        Action::TagPrompt(action) => handle_tag_action(app, action).await?,
+        Action::LoadImages => {
+            if app.config.ui.screen_reader {
+                app.set_warning("Images are not shown in screen-reader mode");
+            } else if app.images_enabled() {
+                app.set_status("Images are already loaded");
+            } else if let Some(article_id) = app.current_article().map(|a| a.id) {
+                app.images_requested.insert(article_id);
+                app.set_status("Loading images");
+            }
+        }
+        Action::ToggleMetered => {
+            app.metered_override = Some(!app.is_metered());
+            sync_metered(app).await;
+            app.set_status(if app.is_metered() {
+                "Metered mode on: saving bandwidth"
+            } else {
+                "Metered mode off"
+            });
+        }
+        Action::OpenSettings => {
+            app.settings = SettingsScreen::default();
+            app.mode = Mode::Settings;
+        }
+        Action::Settings(action) => handle_settings_action(app, action, data_dir),
+        Action::SettingsPrompt(action) => handle_settings_prompt(app, action, data_dir),
+        Action::MoveFeedUp | Action::MoveFeedDown => {
+            if app.read_mode {
+                app.set_warning("Reordering feeds disabled in read-mode");
+            } else {
+                move_feed(app, action == Action::MoveFeedUp, data_dir).await?;
+            }
+        }
+        Action::TogglePin => {
+            if app.read_mode {
+                app.set_warning("Pinning feeds disabled in read-mode");
+            } else if let Some(feed) = app.current_feed().cloned() {
+                match app.client.pin_feed(feed.id, !feed.pinned).await {
+                    Ok(()) => {
+                        reload_managed_feeds(app, None, data_dir).await?;
+                        let verb = if feed.pinned { "Unpinned" } else { "Pinned" };
+                        app.set_status(format!("{} {}", verb, feed.local_name));
+                    }
+                    Err(e) => app.set_error(format!("Failed to update feed: {}", e)),
+                }
+            }
+        }
+        Action::TogglePause => {
+            if app.read_mode {
+                app.set_warning("Pausing feeds disabled in read-mode");
+            } else if let Some(feed) = app.current_feed().cloned() {
+                toggle_feed_pause(app, &feed, data_dir).await?;
+            }
+        }
+        Action::Cancel if matches!(app.mode, Mode::Share | Mode::QrCode | Mode::Messages | Mode::Help(_)) => {
+            app.mode = Mode::Normal;
+        }
+        Action::Cancel if matches!(app.mode, Mode::Profile(_)) => {
+            app.mode = Mode::Normal;
+            app.profile = None;
+        }
         Action::Cancel => {
             // Clear selected feeds when canceling batch delete
             if matches!(app.mode, Mode::BatchDeleteConfirm) {
                 app.clear_feed_selection();
                 app.set_status("Batch delete canceled");
             }
+            let was_filtering = app.mode == Mode::FeedFilter;
             app.mode = Mode::Normal;
-            app.search_query.clear();
+            app.clear_search();
+            if was_filtering {
+                app.feed_filter.clear();
+                apply_feed_filter(app, data_dir).await?;
+            }
+        }
+        Action::ToggleRelevanceSort => {
+            app.sort_by_relevance = !app.sort_by_relevance;
+            reload_relevance_view(app, data_dir).await?;
+        }
+        Action::ToggleRelevanceFilter => {
+            app.hide_low_relevance = !app.hide_low_relevance;
+            reload_relevance_view(app, data_dir).await?;
+        }
+        Action::RaiseRelevanceThreshold | Action::LowerRelevanceThreshold => {
+            let steps = count.unwrap_or(1).min(20) as i32;
+            let steps = if action == Action::RaiseRelevanceThreshold { steps } else { -steps };
+            app.adjust_relevance_threshold(steps);
+            if app.hide_low_relevance {
+                reload_relevance_view(app, data_dir).await?;
+            } else {
+                app.set_status(format!(
+                    "Relevance threshold {:.2} (low-relevance articles are not hidden)",
+                    app.relevance_threshold
+                ));
+            }
         }
         Action::ToggleUnreadOnly => {
             app.toggle_view_mode();
+            app.virtual_feed = None;
             // Ensure selected feed is valid for new view mode
             ensure_valid_feed_selection(app);
-            load_articles(app, db).await?;
+            load_articles(app).await?;
             init_rich_article_state(app, data_dir);
         }
         Action::ExitMode => {
             app.mode = Mode::Normal;
             app.view_mode = ViewMode::All;
-            load_articles(app, db).await?;
+            app.feed_filter.clear();
+            app.virtual_feed = None;
+            load_articles(app).await?;
             init_rich_article_state(app, data_dir);
         }
         Action::StartSearchForward => {
             app.mode = Mode::SearchForward(String::new());
-            app.search_query.clear();
+            app.clear_search();
         }
         Action::StartSearchBackward => {
             app.mode = Mode::SearchBackward(String::new());
-            app.search_query.clear();
+            app.clear_search();
+        }
+        Action::StartFeedFilter => {
+            app.focus = Focus::Subscriptions;
+            app.mode = Mode::FeedFilter;
+            if app.virtual_feed.is_some() {
+                app.virtual_feed = None;
+                app.clear_article_selection();
+                load_articles(app).await?;
+                init_rich_article_state(app, data_dir);
+            }
+        }
+        Action::ShowHistory => toggle_virtual_feed(app, VirtualFeed::History, data_dir).await?,
+        Action::ShowFiltered => toggle_virtual_feed(app, VirtualFeed::Filtered, data_dir).await?,
+        Action::ShowAlerts => toggle_virtual_feed(app, VirtualFeed::Alerts, data_dir).await?,
+        Action::ShowAdvisories => toggle_virtual_feed(app, VirtualFeed::Advisories, data_dir).await?,
+        Action::RescueArticle => {
+            if app.read_mode {
+                app.set_warning("Rescue disabled in read-mode");
+            } else if app.virtual_feed != Some(VirtualFeed::Filtered) {
+                app.set_warning("Only articles in the Filtered view can be rescued");
+            } else if let Some(article_id) = app.current_article().map(|a| a.id) {
+                // A pending read state must not mark the article read again
+                flush_read_state(app).await;
+                if app.client.rescue_article(article_id).await? {
+                    if let Some(feed) = app.article_feed_mut(app.selected_article) {
+                        feed.unread_count += 1;
+                    }
+                    app.set_status("Article rescued: marked unread, and you'll see more like it");
+                } else {
+                    app.set_warning("Article is no longer filtered");
+                }
+                load_articles_preserve_selection(app, true).await?;
+                init_rich_article_state(app, data_dir);
+            }
+        }
+        Action::ShowProfile => show_profile(app, TimeWindow::Last30Days).await,
+        Action::NextProfileWindow => {
+            if let Mode::Profile(window) = app.mode {
+                let next = match window {
+                    TimeWindow::Last30Days => TimeWindow::Last1Day,
+                    TimeWindow::Last1Day => TimeWindow::Recent5Min,
+                    TimeWindow::Recent5Min => TimeWindow::Last30Days,
+                };
+                show_profile(app, next).await;
+            }
+        }
+        Action::ShowMessages => {
+            app.toasts.dismiss();
+            app.mode = Mode::Messages;
+        }
+        Action::ShowHelp => app.mode = Mode::Help(0),
+        Action::ScrollHelp(lines) => {
+            if let Mode::Help(scroll) = app.mode {
+                // Scrolling past the end is clamped when rendering
+                app.mode = Mode::Help(scroll.saturating_add_signed(lines));
+            }
+        }
+        Action::Speak => toggle_read_aloud(app),
+        Action::SpeakNext | Action::SpeakPrev => {
+            let delta = if matches!(action, Action::SpeakNext) { 1 } else { -1 };
+            if let Err(e) = app.speaker.skip(delta) {
+                app.set_error(format!("Read aloud failed: {}", e));
+            }
+        }
+        Action::SpeakStop => {
+            if app.speaker.is_active() {
+                app.speaker.stop();
+                app.set_status("Stopped reading aloud");
+            }
+        }
+        Action::Play => play_article(app, refresh_tx),
+        Action::OpenDiscussion => open_discussion(app, refresh_tx),
+        Action::ToggleComments => toggle_comments(app, refresh_tx),
+        Action::OpenArchived => open_archived(app, refresh_tx),
+        Action::OpenCompare => open_compare(app),
+        Action::LoadPage => load_page(app, refresh_tx),
+        Action::ToggleAiHeader => {
+            app.ai_header_collapsed = !app.ai_header_collapsed;
+            app.set_status(if app.ai_header_collapsed { "AI header collapsed" } else { "AI header expanded" });
+        }
+        Action::StartTriage => {
+            if app.articles.is_empty() {
+                app.set_warning("No articles to triage");
+            } else {
+                app.triage.start();
+                app.mode = Mode::Triage;
+                app.focus = Focus::ArticleList;
+            }
+        }
+        Action::Triage(action) => handle_triage_action(app, action, data_dir).await?,
+        Action::ShowToc => {
+            if !app.open_toc() {
+                app.set_status("No headings in this article");
+            }
+        }
+        Action::Toc(action) => handle_toc_action(app, action),
+        Action::NextElement | Action::PrevElement => {
+            let forward = action == Action::NextElement;
+            for _ in 0..count.unwrap_or(1) {
+                if !app.scroll_detail_to_adjacent_element(forward) {
+                    break;
+                }
+            }
+        }
+        Action::ToggleSummaryOnly => {
+            app.summary_only = !app.summary_only;
+            app.reset_detail_scroll();
+            app.set_status(if app.summary_only { "Summary-only reading on" } else { "Summary-only reading off" });
+        }
+        Action::InputChar(c) if app.mode == Mode::FeedFilter => {
+            app.feed_filter.push(c);
+            apply_feed_filter(app, data_dir).await?;
+        }
+        Action::Backspace if app.mode == Mode::FeedFilter => {
+            app.feed_filter.pop();
+            apply_feed_filter(app, data_dir).await?;
         }
         Action::InputChar(c) => {
             app.search_query.push(c);
@@ -1232,7 +3103,7 @@ async fn handle_action(
         Action::Refresh => {
             // Refresh is disabled in read-mode
             if app.read_mode {
-                app.set_status("Refresh disabled in read-mode");
+                app.set_warning("Refresh disabled in read-mode");
             } else if app.is_refreshing {
                 // Don't start another refresh if one is already in progress
                 app.set_status("Refresh already in progress...");
@@ -1241,11 +3112,21 @@ async fn handle_action(
                 app.set_status("Refreshing feeds...");
 
                 // Clone what we need for the spawned task
-                let client = app.client.clone().expect("Client required for refresh");
+                let client = app.client.clone();
                 let tx = refresh_tx.clone();
+                let daemon_events = app.daemon_events;
 
                 // Spawn refresh as background task
                 tokio::spawn(async move {
+                    if daemon_events {
+                        // The daemon refreshes on its own; progress and the
+                        // result arrive as events. If another client's refresh
+                        // is running, its result is awaited instead.
+                        if let Err(e) = client.refresh_start().await {
+                            let _ = tx.send(RefreshResult::Failure { error: e.to_string() });
+                        }
+                        return;
+                    }
                     match client.refresh(None).await {
                         Ok(new_count) => {
                             let _ = tx.send(RefreshResult::Success { new_count });
@@ -1285,85 +3166,66 @@ async fn handle_action(
                 // Batch toggle read status
                 let indices: Vec<usize> = app.selected_articles.iter().cloned().collect();
                 let mut toggled_count = 0;
-                let mut errors = Vec::new();
 
                 for &idx in &indices {
                     if let Some(article) = app.articles.get(idx) {
                         let article_id = article.id;
                         let was_read = article.is_read;
 
-                        let result = if was_read {
-                            mark_article_unread(app, client, db, article_id).await
+                        if was_read {
+                            mark_article_unread(app, article_id);
                         } else {
-                            mark_article_read(app, client, db, article_id).await
-                        };
-
-                        match result {
-                            Ok(_) => {
-                                // Update local state
-                                if let Some(article) = app.articles.get_mut(idx) {
-                                    article.is_read = !was_read;
-                                }
-                                // Update feed unread count
-                                if let Some(feed) = app.current_feed_mut() {
-                                    if was_read {
-                                        feed.unread_count += 1;
-                                    } else {
-                                        feed.unread_count = feed.unread_count.saturating_sub(1);
-                                    }
-                                }
-                                toggled_count += 1;
-                            }
-                            Err(e) => {
-                                errors.push(format!("{}", e));
+                            mark_article_read(app, article_id);
+                        }
+
+                        // Update local state
+                        if let Some(article) = app.articles.get_mut(idx) {
+                            article.is_read = !was_read;
+                        }
+                        // Update feed unread count
+                        if let Some(feed) = app.article_feed_mut(idx) {
+                            if was_read {
+                                feed.unread_count += 1;
+                            } else {
+                                feed.unread_count = feed.unread_count.saturating_sub(1);
                             }
                         }
+                        toggled_count += 1;
                     }
                 }
 
                 app.clear_article_selection();
-                if errors.is_empty() {
-                    app.set_status(format!("Toggled {} articles", toggled_count));
-                } else {
-                    app.set_status(format!("Toggled {} articles, {} errors", toggled_count, errors.len()));
-                }
+                app.set_status(format!("Toggled {} articles", toggled_count));
             } else {
                 // Single article toggle (original behavior)
                 if let Some(article) = app.current_article() {
                     let article_id = article.id;
                     let was_read = article.is_read;
 
-                    let result = if was_read {
-                        mark_article_unread(app, client, db, article_id).await
+                    if was_read {
+                        mark_article_unread(app, article_id);
                     } else {
-                        mark_article_read(app, client, db, article_id).await
-                    };
+                        mark_article_read(app, article_id);
+                    }
 
-                    match result {
-                        Ok(_) => {
-                            // Update local article state
-                            if let Some(article) = app.current_article_mut() {
-                                article.is_read = !was_read;
-                            }
-                            // Update feed unread count
-                            if let Some(feed) = app.current_feed_mut() {
-                                if was_read {
-                                    feed.unread_count += 1;
-                                } else {
-                                    feed.unread_count = feed.unread_count.saturating_sub(1);
-                                }
-                            }
-                            let status = if was_read {
-                                "Marked as unread"
-                            } else {
-                                "Marked as read"
-                            };
-                            app.set_status(status);
-                        }
-                        Err(e) => {
-                            app.set_status(format!("Failed to toggle read status: {}", e));
+                    // Update local article state
+                    if let Some(article) = app.current_article_mut() {
+                        article.is_read = !was_read;
+                    }
+                    // Update feed unread count
+                    if let Some(feed) = app.article_feed_mut(app.selected_article) {
+                        if was_read {
+                            feed.unread_count += 1;
+                        } else {
+                            feed.unread_count = feed.unread_count.saturating_sub(1);
                         }
                     }
+                    let status = if was_read {
+                        "Marked as unread"
+                    } else {
+                        "Marked as read"
+                    };
+                    app.set_status(status);
                 }
             }
         }
@@ -1379,14 +3241,14 @@ async fn handle_action(
                         app.selected_feed = feed_idx;
                     }
                     // Load articles and find the target article (ignores unread-only if needed)
-                    if load_articles_for_history(app, db, article_id).await? {
+                    if load_articles_for_history(app, article_id).await? {
                         init_rich_article_state(app, data_dir);
                         app.set_status("← Back");
                     } else {
-                        app.set_status(format!("Article not found (history: {}/{})", history_pos, history_len));
+                        app.set_warning(format!("Article not found (history: {}/{})", history_pos, history_len));
                     }
                 } else {
-                    app.set_status(format!("Feed not found (history: {}/{})", history_pos, history_len));
+                    app.set_warning(format!("Feed not found (history: {}/{})", history_pos, history_len));
                 }
             } else {
                 app.set_status(format!("No history to go back (pos: {}, len: {})", history_pos, history_len));
@@ -1400,14 +3262,14 @@ async fn handle_action(
                         app.selected_feed = feed_idx;
                     }
                     // Load articles and find the target article (ignores unread-only if needed)
-                    if load_articles_for_history(app, db, article_id).await? {
+                    if load_articles_for_history(app, article_id).await? {
                         init_rich_article_state(app, data_dir);
                         app.set_status("→ Forward");
                     } else {
-                        app.set_status("Article not found in history");
+                        app.set_warning("Article not found in history");
                     }
                 } else {
-                    app.set_status("Feed not found in history");
+                    app.set_warning("Feed not found in history");
                 }
             }
         }
@@ -1424,22 +3286,15 @@ async fn handle_action(
                     }
                 }
                 Focus::Subscriptions => {
-                    // Get info before borrowing mutably
-                    let visible_len = app.visible_feeds().len();
-                    let visible_idx = app.actual_to_visible_feed_index(app.selected_feed);
                     let current_feed = app.selected_feed;
-
-                    if let Some(visible_idx) = visible_idx {
+                    if app.actual_to_visible_feed_index(current_feed).is_some() {
                         // Toggle selection using actual index
                         app.toggle_feed_selection(current_feed);
-                        // Move to next item (if not at last item)
-                        if visible_idx < visible_len.saturating_sub(1) {
-                            let next_visible = visible_idx + 1;
-                            if let Some(actual_idx) = app.visible_to_actual_feed_index(next_visible) {
-                                app.selected_feed = actual_idx;
-                                load_articles(app, db).await?;
-                                init_rich_article_state(app, data_dir);
-                            }
+                        // Move to the next row (if not at the last one)
+                        app.move_feed_cursor(1);
+                        if app.selected_feed != current_feed {
+                            load_articles(app).await?;
+                            init_rich_article_state(app, data_dir);
                         }
                     }
                 }
@@ -1480,17 +3335,23 @@ async fn handle_action(
             app.clear_feed_selection();
             app.set_status("Selection cleared");
         }
+        Action::ClearSearch => {
+            app.clear_search();
+            app.set_status("Search cleared");
+        }
         // Image navigation and viewing actions
         Action::ViewImage => {
-            // Enter fullscreen image viewer mode
-            if let Some(ref rich_state) = app.rich_state {
+            if app.follow_footnote() {
+                // A focused footnote reference jumps to its footnote and back
+            } else if let Some(ref rich_state) = app.rich_state {
+                // Enter fullscreen image viewer mode
                 let image_count = rich_state.content.image_urls.len();
                 if image_count > 0 {
                     // Use focused image index or default to first image
                     let index = rich_state.focused_image_index().unwrap_or(0);
                     app.mode = Mode::ImageViewer(index);
                 } else {
-                    app.set_status("No images in this article");
+                    app.set_warning("No images in this article");
                 }
             }
         }
@@ -1505,21 +3366,23 @@ async fn handle_action(
                             if let Some(ref path) = cached.cache_path {
                                 if path.exists() {
                                     if let Err(e) = open::that(path) {
-                                        app.set_status(format!("Failed to open image: {}", e));
+                                        app.set_error(format!("Failed to open image: {}", e));
                                     } else {
                                         app.set_status("Opening image in external viewer...");
                                     }
                                 } else {
-                                    app.set_status("Image not cached locally");
+                                    app.set_warning("Image not cached locally");
                                 }
                             } else {
-                                app.set_status("Image cache path not available");
+                                app.set_warning("Image cache path not available");
                             }
                         } else {
                             app.set_status("Image not loaded yet");
                         }
                     }
                 }
+            } else if app.follow_footnote() {
+                // Footnote reference or footnote focused
             } else if let Some(ref rich_state) = app.rich_state {
                 // Normal mode: check focused item type
                 match rich_state.get_focused_item() {
@@ -1530,15 +3393,15 @@ async fn handle_action(
                                 if let Some(ref path) = cached.cache_path {
                                     if path.exists() {
                                         if let Err(e) = open::that(path) {
-                                            app.set_status(format!("Failed to open image: {}", e));
+                                            app.set_error(format!("Failed to open image: {}", e));
                                         } else {
                                             app.set_status("Opening image in external viewer...");
                                         }
                                     } else {
-                                        app.set_status("Image not cached locally");
+                                        app.set_warning("Image not cached locally");
                                     }
                                 } else {
-                                    app.set_status("Image cache path not available");
+                                    app.set_warning("Image cache path not available");
                                 }
                             } else {
                                 app.set_status("Image not loaded yet");
@@ -1547,17 +3410,15 @@ async fn handle_action(
                     }
                     Some(FocusableItem::Link { url, text, .. }) => {
                         // Open link in browser
-                        if let Err(e) = open::that(url) {
-                            app.set_status(format!("Failed to open link: {}", e));
+                        let (url, text) = (url.clone(), text.clone());
+                        if let Err(e) = open_url(app, &url) {
+                            app.set_error(format!("Failed to open link: {}", e));
                         } else {
-                            let display = if text.len() > 30 {
-                                format!("{}...", &text[..27])
-                            } else {
-                                text.clone()
-                            };
+                            let display = truncate_str(&text, 30);
                             app.set_status(format!("Opening: {}", display));
                         }
                     }
+                    Some(FocusableItem::FootnoteRef { .. } | FocusableItem::Footnote { .. }) => {}
                     None => {
                         app.set_status("No item focused. Press Tab to focus an item.");
                     }
@@ -1594,13 +3455,13 @@ async fn handle_action(
                                     Some(format!("Image {}/{}", url_index + 1, image_count))
                                 }
                                 FocusableItem::Link { text, .. } => {
-                                    let display_text = if text.len() > 40 {
-                                        format!("{}...", &text[..37])
-                                    } else {
-                                        text.clone()
-                                    };
+                                    let display_text = truncate_str(text, 40);
                                     Some(format!("Link: {}", display_text))
                                 }
+                                FocusableItem::FootnoteRef { label, .. } => {
+                                    Some(format!("Footnote reference [{}] (Enter to read it)", label))
+                                }
+                                FocusableItem::Footnote { label, .. } => Some(format!("Footnote {}", label)),
                             }
                         } else {
                             Some("No focusable items in this article".to_string())
@@ -1644,13 +3505,13 @@ async fn handle_action(
                                     Some(format!("Image {}/{}", url_index + 1, image_count))
                                 }
                                 FocusableItem::Link { text, .. } => {
-                                    let display_text = if text.len() > 40 {
-                                        format!("{}...", &text[..37])
-                                    } else {
-                                        text.clone()
-                                    };
+                                    let display_text = truncate_str(text, 40);
                                     Some(format!("Link: {}", display_text))
                                 }
+                                FocusableItem::FootnoteRef { label, .. } => {
+                                    Some(format!("Footnote reference [{}] (Enter to read it)", label))
+                                }
+                                FocusableItem::Footnote { label, .. } => Some(format!("Footnote {}", label)),
                             }
                         } else {
                             Some("No focusable items in this article".to_string())
@@ -0,0 +1,25 @@
+use anyhow::{bail, Result};
+
+use kenseader_core::secrets::{self, KEYRING_PREFIX};
+
+/// Store a secret in the OS keyring, prompting for it without echo
+pub fn set(name: &str) -> Result<()> {
+    let secret = rpassword::prompt_password(format!("Secret for '{}': ", name))?;
+    if secret.is_empty() {
+        bail!("No secret entered");
+    }
+    secrets::set(name, &secret)?;
+    println!("Stored '{}' in the keyring.", name);
+    println!("Use it in config.toml as \"{}{}\"", KEYRING_PREFIX, name);
+    Ok(())
+}
+
+/// Remove a secret from the OS keyring
+pub fn delete(name: &str) -> Result<()> {
+    if secrets::delete(name)? {
+        println!("Deleted '{}' from the keyring.", name);
+    } else {
+        println!("No keyring entry named '{}'.", name);
+    }
+    Ok(())
+}
@@ -1,12 +1,10 @@
 use anyhow::Result;
 
-use kenseader_core::{
-    feed::{FeedFetcher, NewFeed},
-    storage::{ArticleRepository, Database, FeedRepository},
-    AppConfig,
-};
+use kenseader_core::{feed::FeedFetcher, ipc::DaemonClient, AppConfig};
 
-pub async fn run(db: &Database, config: &AppConfig, url: &str, name: &str) -> Result<()> {
+/// Subscribe to `url` as `name`. When the feed is already subscribed under
+/// an equivalent URL, that subscription is renamed with `rename` instead.
+pub async fn run(client: &DaemonClient, config: &AppConfig, url: &str, name: &str, rename: bool) -> Result<()> {
     println!("Subscribing to feed: {}", url);
 
     let fetcher = FeedFetcher::new(config)?;
@@ -15,42 +13,46 @@ pub async fn run(db: &Database, config: &AppConfig, url: &str, name: &str) -> Re
     let resolved_url = fetcher.resolve_url(url)?;
     println!("Resolved URL: {}", resolved_url);
 
-    // Check if already subscribed
-    let feed_repo = FeedRepository::new(db);
-    if feed_repo.find_by_url(&resolved_url).await?.is_some() {
-        println!("Already subscribed to this feed.");
+    // Check if already subscribed, also under another form of the URL or
+    // where it redirects to
+    let existing = match client.discover_feed(&resolved_url).await {
+        Ok(discovered) => discovered.existing,
+        // Unreachable feeds are still subscribed; compare the URL alone
+        Err(_) => client.list_feeds().await?.into_iter().find(|f| f.matches_url(&resolved_url)),
+    };
+    if let Some(existing) = existing {
+        println!("Already subscribed to this feed as '{}' ({}).", existing.local_name, existing.url);
+        if existing.local_name == name {
+            return Ok(());
+        }
+        if rename {
+            let mut settings = existing.settings();
+            settings.local_name = name.to_string();
+            client.update_feed(existing.id, &settings).await?;
+            println!("Renamed '{}' to '{}'.", existing.local_name, name);
+        } else {
+            println!("Run again with --rename to rename it to '{}' instead of subscribing twice.", name);
+        }
         return Ok(());
     }
 
     // Create the feed entry
-    let new_feed = NewFeed {
-        url: resolved_url.clone(),
-        local_name: name.to_string(),
-    };
-
-    let feed = feed_repo.create(&new_feed).await?;
+    let feed = client.add_feed(&resolved_url, name).await?;
     println!("Created subscription: {} ({})", name, feed.id);
 
-    // Fetch initial articles
+    // Fetch initial articles (also stores the feed metadata)
     println!("Fetching articles...");
-    match fetcher.fetch(&resolved_url, feed.id).await {
-        Ok(parsed) => {
-            // Update feed metadata
-            feed_repo.update_metadata(
-                feed.id,
-                parsed.title.as_deref(),
-                parsed.description.as_deref(),
-                parsed.site_url.as_deref(),
-                parsed.icon_url.as_deref(),
-            ).await?;
-
-            // Insert articles
-            let article_repo = ArticleRepository::new(db);
-            let count = article_repo.create_many(&parsed.articles).await?;
-
+    match client.refresh(Some(feed.id)).await {
+        Ok(count) => {
             println!("Successfully fetched {} articles from '{}'", count, name);
 
-            if let Some(title) = parsed.title {
+            let title = client
+                .list_feeds()
+                .await?
+                .into_iter()
+                .find(|f| f.id == feed.id)
+                .and_then(|f| f.title);
+            if let Some(title) = title {
                 println!("Feed title: {}", title);
             }
         }
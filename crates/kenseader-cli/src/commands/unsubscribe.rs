@@ -1,17 +1,16 @@
 use anyhow::Result;
 
-use kenseader_core::storage::{Database, FeedRepository};
+use kenseader_core::ipc::DaemonClient;
 
-pub async fn run(db: &Database, name: &str) -> Result<()> {
-    let feed_repo = FeedRepository::new(db);
-    let feeds = feed_repo.list_all().await?;
+pub async fn run(client: &DaemonClient, name: &str) -> Result<()> {
+    let feeds = client.list_feeds().await?;
 
     // Find feed by name
     let feed = feeds.iter().find(|f| f.local_name == name);
 
     match feed {
         Some(f) => {
-            feed_repo.delete(f.id).await?;
+            client.delete_feed(f.id).await?;
             println!("Unsubscribed from: {}", name);
         }
         None => {
@@ -4,7 +4,9 @@ use anyhow::Result;
 use clap::{Parser, Subcommand};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-use kenseader_core::{AppConfig, storage::Database};
+use kenseader_core::ipc::CompareScoringParams;
+use kenseader_core::profile::{ScoringSettings, TimeWindow};
+use kenseader_core::AppConfig;
 
 mod commands;
 
@@ -40,12 +42,25 @@ enum Commands {
         /// Local name for the subscription
         #[arg(short = 'n', long)]
         name: String,
+        /// When already subscribed to the feed, rename that subscription to NAME
+        #[arg(long)]
+        rename: bool,
     },
     /// Unsubscribe from a feed
     Unsubscribe {
         /// Name of the subscription to remove
         name: String,
     },
+    /// Stop fetching a feed, keeping its articles
+    Pause {
+        /// Name of the subscription to pause
+        name: String,
+    },
+    /// Resume fetching a paused feed
+    Resume {
+        /// Name of the subscription to resume
+        name: String,
+    },
     /// Import feeds from OPML file
     Import {
         /// Path to OPML file
@@ -55,14 +70,75 @@ enum Commands {
     /// List all subscriptions
     List,
     /// Refresh all feeds
-    Refresh,
+    Refresh {
+        /// Fetch every active feed and report what would be stored, without storing it
+        #[arg(long)]
+        dry_run: bool,
+        /// With --dry-run, also list each item and what would happen to it
+        #[arg(long, requires = "dry_run")]
+        verbose: bool,
+    },
+    /// Report fetch timing per feed, slowest first
+    ProfileFetch {
+        /// Number of days of fetches to include
+        #[arg(long, default_value_t = 7)]
+        days: u32,
+        /// Number of feeds to list
+        #[arg(short = 'n', long, default_value_t = 15)]
+        limit: usize,
+    },
+    /// Inspect the interest profile learned from your reading
+    Profile {
+        #[command(subcommand)]
+        action: ProfileAction,
+    },
     /// Clean up old articles
     Cleanup,
+    /// Check the daemon and subscriptions for problems and feeds worth unsubscribing from
+    Doctor {
+        /// Switch feeds that moved permanently to their new URL
+        #[arg(long)]
+        update_moved: bool,
+    },
+    /// Apply pending database schema migrations
+    Migrate {
+        /// Only list pending migrations without applying them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Export all feeds, articles, tags and preferences to a portable JSONL archive
+    ExportAll {
+        /// Path of the archive to write
+        file: String,
+    },
+    /// Import a JSONL archive created by `export-all`
+    ImportAll {
+        /// Path of the archive to read
+        file: String,
+    },
+    /// Print an ICS calendar of the digests scheduled by cron
+    Calendar {
+        /// Days ahead to include (at most a year)
+        #[arg(
+            long,
+            default_value_t = kenseader_core::calendar::DEFAULT_DAYS,
+            value_parser = clap::value_parser!(u32).range(1..=i64::from(kenseader_core::calendar::MAX_DAYS))
+        )]
+        days: u32,
+        /// Write the calendar to this file instead of printing it
+        #[arg(short, long)]
+        output: Option<String>,
+    },
     /// Background daemon for automatic feed refresh and summarization
     Daemon {
         #[command(subcommand)]
         action: DaemonAction,
     },
+    /// Store API keys and passwords in the OS keyring, for `keyring:<name>` config values
+    Secret {
+        #[command(subcommand)]
+        action: SecretAction,
+    },
 }
 
 #[derive(Subcommand)]
@@ -77,37 +153,100 @@ enum DaemonAction {
     Stop,
     /// Check daemon status
     Status,
+    /// Run a background task now (refresh, cleanup, summarize, filter, classify, audio_digest, prune, metadata, integrations)
+    RunTask {
+        /// Task name
+        name: String,
+    },
+    /// Switch metered mode (fewer refreshes, a daily bandwidth budget) on or off
+    Metered {
+        #[arg(value_parser = ["on", "off"])]
+        state: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum ProfileAction {
+    /// Show the top tags, feeds, styles and reading times of day
+    Show {
+        /// Time window of the preferences
+        #[arg(long, default_value = "30days", value_parser = ["5min", "1day", "30days"])]
+        window: String,
+        /// Number of entries to list per section
+        #[arg(short = 'n', long, default_value_t = 10)]
+        limit: usize,
+    },
+    /// Score recent articles with two settings (A and B) and show how filtering them would differ.
+    /// Settings left out are the configured ones; nothing is stored or marked read.
+    Compare {
+        /// Relevance threshold of A
+        #[arg(long)]
+        threshold_a: Option<f64>,
+        /// Relevance threshold of B
+        #[arg(long)]
+        threshold_b: Option<f64>,
+        /// Comma-separated interests of A, instead of the learned top tags
+        #[arg(long, value_delimiter = ',')]
+        interests_a: Option<Vec<String>>,
+        /// Comma-separated interests of B, instead of the learned top tags
+        #[arg(long, value_delimiter = ',')]
+        interests_b: Option<Vec<String>>,
+        /// AI provider of A
+        #[arg(long)]
+        provider_a: Option<String>,
+        /// AI provider of B
+        #[arg(long)]
+        provider_b: Option<String>,
+        /// Compare the articles fetched in the last this many days
+        #[arg(long, default_value_t = 7)]
+        days: u32,
+        /// Maximum number of articles to score, newest first
+        #[arg(short = 'n', long, default_value_t = 100)]
+        limit: u32,
+    },
+}
+
+#[derive(Subcommand)]
+enum SecretAction {
+    /// Store a secret (prompted for) under a name
+    Set {
+        /// Entry name
+        name: String,
+    },
+    /// Delete a stored secret
+    Delete {
+        /// Entry name
+        name: String,
+    },
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Initialize logging
-    tracing_subscriber::registry()
-        .with(tracing_subscriber::EnvFilter::new(
-            std::env::var("RUST_LOG").unwrap_or_else(|_| "info".into()),
-        ))
-        .with(tracing_subscriber::fmt::layer().with_target(false))
-        .init();
-
     let cli = Cli::parse();
 
+    // Handled before loading the config, which may name entries not stored yet
+    if let Some(Commands::Secret { ref action }) = cli.command {
+        return match action {
+            SecretAction::Set { name } => commands::secret::set(name),
+            SecretAction::Delete { name } => commands::secret::delete(name),
+        };
+    }
+
     // Load configuration
     let config = Arc::new(AppConfig::load()?);
 
-    // Check if this is a daemon start command - run migration before database init
-    if let Some(Commands::Daemon { action: DaemonAction::Start { .. } }) = &cli.command {
-        commands::daemon::maybe_migrate_data(&config)?;
-    }
-
-    // Initialize database
-    let db = Arc::new(Database::new(&config).await?);
+    // Initialize logging
+    init_logging(&cli, &config)?;
 
     // Handle shorthand subscription (-s -n flags)
     if let (Some(url), Some(name)) = (cli.subscribe_url, cli.subscribe_name) {
-        return commands::subscribe::run(&db, &config, &url, &name).await;
+        let (client, _lock) = commands::connect(&config).await?;
+        return commands::subscribe::run(&client, &config, &url, &name, false).await;
     }
 
-    // Handle commands
+    // Handle commands. Commands that touch the database go through the daemon
+    // when it owns the database, or hold the writer lock themselves; read-only
+    // commands read the database without the lock.
     match cli.command {
         Some(Commands::Run { read_mode }) => {
             commands::run::run(config, read_mode).await
@@ -116,30 +255,144 @@ async fn main() -> Result<()> {
             // Default: start TUI in normal mode
             commands::run::run(config, false).await
         }
-        Some(Commands::Subscribe { url, name }) => {
-            commands::subscribe::run(&db, &config, &url, &name).await
+        Some(Commands::Subscribe { url, name, rename }) => {
+            let (client, _lock) = commands::connect(&config).await?;
+            commands::subscribe::run(&client, &config, &url, &name, rename).await
         }
         Some(Commands::Unsubscribe { name }) => {
-            commands::unsubscribe::run(&db, &name).await
+            let (client, _lock) = commands::connect(&config).await?;
+            commands::unsubscribe::run(&client, &name).await
+        }
+        Some(Commands::Pause { name }) => {
+            let (client, _lock) = commands::connect(&config).await?;
+            commands::pause::run(&client, &name, true).await
+        }
+        Some(Commands::Resume { name }) => {
+            let (client, _lock) = commands::connect(&config).await?;
+            commands::pause::run(&client, &name, false).await
         }
         Some(Commands::Import { file }) => {
-            commands::import::run(&db, &config, &file).await
+            let (client, _lock) = commands::connect(&config).await?;
+            commands::import::run(&client, &config, &file).await
         }
         Some(Commands::List) => {
-            commands::list::run(&db).await
+            let client = commands::connect_reader(&config).await?;
+            commands::list::run(&client).await
         }
-        Some(Commands::Refresh) => {
-            commands::refresh::run(&db, &config).await
+        Some(Commands::Refresh { dry_run: true, verbose }) => {
+            let client = commands::connect_reader(&config).await?;
+            commands::refresh::dry_run(&client, verbose).await
+        }
+        Some(Commands::Refresh { dry_run: false, .. }) => {
+            let (client, _lock) = commands::connect(&config).await?;
+            commands::refresh::run(&client).await
+        }
+        Some(Commands::ProfileFetch { days, limit }) => {
+            let client = commands::connect_reader(&config).await?;
+            commands::profile::fetch(&client, &config, days, limit).await
+        }
+        Some(Commands::Profile { action: ProfileAction::Show { window, limit } }) => {
+            let client = commands::connect_reader(&config).await?;
+            let window = TimeWindow::parse(&window).unwrap_or(TimeWindow::Last30Days);
+            commands::profile::show(&client, window, limit).await
+        }
+        Some(Commands::Profile {
+            action:
+                ProfileAction::Compare {
+                    threshold_a,
+                    threshold_b,
+                    interests_a,
+                    interests_b,
+                    provider_a,
+                    provider_b,
+                    days,
+                    limit,
+                },
+        }) => {
+            let (client, _lock) = commands::connect(&config).await?;
+            let params = CompareScoringParams {
+                a: ScoringSettings {
+                    relevance_threshold: threshold_a,
+                    interests: interests_a,
+                    provider: provider_a,
+                },
+                b: ScoringSettings {
+                    relevance_threshold: threshold_b,
+                    interests: interests_b,
+                    provider: provider_b,
+                },
+                days,
+                limit,
+            };
+            commands::profile::compare(&client, params).await
         }
         Some(Commands::Cleanup) => {
-            commands::cleanup::run(&db, &config).await
+            let (client, _lock) = commands::connect(&config).await?;
+            commands::cleanup::run(&client, &config).await
+        }
+        Some(Commands::Doctor { update_moved }) => {
+            let (client, _lock) = commands::connect(&config).await?;
+            commands::doctor::run(&client, update_moved).await
+        }
+        Some(Commands::Migrate { dry_run }) => {
+            commands::migrate::run(&config, dry_run).await
+        }
+        Some(Commands::ExportAll { file }) => {
+            commands::archive::export(&config, &file).await
+        }
+        Some(Commands::ImportAll { file }) => {
+            commands::archive::import(&config, &file).await
+        }
+        Some(Commands::Calendar { days, output }) => {
+            commands::calendar::run(&config, days, output.as_deref())
         }
         Some(Commands::Daemon { action }) => {
             match action {
-                DaemonAction::Start { foreground } => commands::daemon::start(db, config, foreground).await,
+                DaemonAction::Start { foreground } => commands::daemon::start(config, foreground).await,
                 DaemonAction::Stop => commands::daemon::stop().await,
-                DaemonAction::Status => commands::daemon::status().await,
+                DaemonAction::Status => commands::daemon::status(&config).await,
+                DaemonAction::RunTask { name } => commands::daemon::run_task(&config, &name).await,
+                DaemonAction::Metered { state } => commands::daemon::metered(&config, state == "on").await,
             }
         }
+        // Handled before the config is loaded
+        Some(Commands::Secret { .. }) => Ok(()),
+    }
+}
+
+/// Initialize logging to stderr, or to a file in the data directory when the
+/// TUI may host an embedded daemon (its logs would corrupt the screen)
+fn init_logging(cli: &Cli, config: &AppConfig) -> Result<()> {
+    let filter = tracing_subscriber::EnvFilter::new(
+        std::env::var("RUST_LOG").unwrap_or_else(|_| "info".into()),
+    );
+
+    let runs_tui = cli.subscribe_url.is_none()
+        && matches!(cli.command, None | Some(Commands::Run { read_mode: false }));
+
+    if runs_tui && config.ui.embedded_daemon {
+        let data_dir = config.data_dir();
+        std::fs::create_dir_all(&data_dir)?;
+        let log_file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(data_dir.join("kenseader.log"))?;
+
+        tracing_subscriber::registry()
+            .with(filter)
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .with_target(false)
+                    .with_ansi(false)
+                    .with_writer(std::sync::Mutex::new(log_file)),
+            )
+            .init();
+    } else {
+        tracing_subscriber::registry()
+            .with(filter)
+            .with(tracing_subscriber::fmt::layer().with_target(false))
+            .init();
     }
+
+    Ok(())
 }
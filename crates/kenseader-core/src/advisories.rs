@@ -0,0 +1,302 @@
+//! CVE enrichment
+//!
+//! New articles are scanned for CVE identifiers as they are stored, and
+//! tagged with them. After each refresh the daemon looks the CVEs up in the
+//! NVD and tags the articles with their CVSS severity (`cvss-critical`,
+//! `cvss-high`, ...), so the Advisories view can list the most severe first.
+
+use std::time::Duration;
+
+use reqwest::{Client, StatusCode};
+use serde_json::Value;
+use tokio::time::sleep;
+use tracing::{debug, info, warn};
+
+use crate::config::AppConfig;
+use crate::feed::Article;
+use crate::storage::{ArticleRepository, Database};
+use crate::Result;
+
+/// Source of the tags added to articles mentioning CVEs
+pub const TAG_SOURCE: &str = "cve";
+
+/// Hours before a CVE the NVD had no score for is looked up again (fresh
+/// disclosures stay "Awaiting Analysis" for days)
+const UNSCORED_RETRY_HOURS: i64 = 24;
+
+/// Delay between lookups keeping under the NVD rate limit: 5 requests per
+/// 30 seconds without an API key, 50 with one
+fn lookup_delay(has_api_key: bool) -> Duration {
+    if has_api_key {
+        Duration::from_millis(600)
+    } else {
+        Duration::from_secs(6)
+    }
+}
+
+/// CVE identifiers ("CVE-2024-3094") in a text, uppercased, in order of
+/// first appearance
+pub fn cve_ids(text: &str) -> Vec<String> {
+    let bytes = text.as_bytes();
+    let mut ids: Vec<String> = Vec::new();
+    let mut start = 0;
+    while let Some(offset) = find_ignore_case(&bytes[start..], b"cve-") {
+        let at = start + offset;
+        start = at + 4;
+        if at > 0 && bytes[at - 1].is_ascii_alphanumeric() {
+            continue;
+        }
+        let year = &bytes[start..(start + 4).min(bytes.len())];
+        if year.len() < 4 || !year.iter().all(u8::is_ascii_digit) || bytes.get(start + 4) != Some(&b'-') {
+            continue;
+        }
+        let number_len = bytes[start + 5..].iter().take_while(|b| b.is_ascii_digit()).count();
+        if number_len < 4 || bytes.get(start + 5 + number_len).is_some_and(u8::is_ascii_alphabetic) {
+            continue;
+        }
+        let end = start + 5 + number_len;
+        let id = format!("CVE-{}", &text[start..end]);
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+        start = end;
+    }
+    ids
+}
+
+fn find_ignore_case(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|window| window.eq_ignore_ascii_case(needle))
+}
+
+/// Tag for a CVSS severity ("CRITICAL" -> "cvss-critical")
+pub fn severity_tag(severity: &str) -> String {
+    format!("cvss-{}", severity.to_lowercase())
+}
+
+/// CVSS base score and severity of an NVD CVE API response, from the newest
+/// CVSS version scored, preferring the NVD's own (primary) metric
+pub fn parse_nvd_response(json: &Value) -> Option<(f64, String)> {
+    let metrics = &json["vulnerabilities"][0]["cve"]["metrics"];
+    ["cvssMetricV40", "cvssMetricV31", "cvssMetricV30", "cvssMetricV2"]
+        .iter()
+        .filter_map(|version| metrics[version].as_array())
+        .find_map(|entries| {
+            let metric = entries
+                .iter()
+                .find(|m| m["type"] == "Primary")
+                .or_else(|| entries.first())?;
+            let score = metric["cvssData"]["baseScore"].as_f64()?;
+            // CVSS v2 keeps the severity next to the data
+            let severity = metric["cvssData"]["baseSeverity"]
+                .as_str()
+                .or_else(|| metric["baseSeverity"].as_str())?;
+            Some((score, severity.to_uppercase()))
+        })
+}
+
+/// Link a feed's newly stored articles to the CVEs they mention and tag
+/// them with the identifiers. Returns the number of articles linked.
+pub async fn link_new_articles(
+    article_repo: &ArticleRepository<'_>,
+    config: &AppConfig,
+    articles: &[Article],
+) -> Result<u32> {
+    if !config.advisories.enabled {
+        return Ok(0);
+    }
+    let mut linked = 0;
+    for article in articles {
+        let text = format!("{} {}", article.title, article.content_text.as_deref().unwrap_or(""));
+        let ids = cve_ids(&text);
+        if ids.is_empty() {
+            continue;
+        }
+        article_repo.link_cves(article.id, &ids).await?;
+        article_repo.add_tags(article.id, &ids, TAG_SOURCE).await?;
+        linked += 1;
+    }
+    Ok(linked)
+}
+
+/// Look up the severity of CVEs not looked up yet, or still unscored a day
+/// after the last lookup (up to `advisories.lookups_per_refresh`), and tag
+/// the articles mentioning them.
+/// Stops early when the NVD refuses more requests. Returns the number of
+/// CVEs looked up.
+pub async fn look_up_pending(db: &Database, config: &AppConfig) -> Result<u32> {
+    let settings = &config.advisories;
+    let article_repo = ArticleRepository::new(db);
+    let retry_unscored_before = chrono::Utc::now() - chrono::Duration::hours(UNSCORED_RETRY_HOURS);
+    let pending = article_repo
+        .list_pending_cves(settings.lookups_per_refresh, retry_unscored_before)
+        .await?;
+    if pending.is_empty() {
+        return Ok(0);
+    }
+
+    let client = Client::builder()
+        .timeout(Duration::from_secs(config.sync.request_timeout_secs))
+        .build()?;
+    let delay = lookup_delay(settings.api_key.is_some());
+    let mut looked_up = 0;
+    for (idx, cve_id) in pending.iter().enumerate() {
+        if idx > 0 {
+            sleep(delay).await;
+        }
+        let mut request = client.get(&settings.api_url).query(&[("cveId", cve_id)]);
+        if let Some(api_key) = &settings.api_key {
+            request = request.header("apiKey", api_key);
+        }
+        let response = request.send().await?;
+        let status = response.status();
+        let result = if status.is_success() {
+            parse_nvd_response(&response.json::<Value>().await?)
+        } else if status == StatusCode::NOT_FOUND {
+            None
+        } else {
+            // Rate limited or unavailable: the rest waits for the next refresh
+            warn!("CVE lookup of {} failed with {}, stopping", cve_id, status);
+            break;
+        };
+
+        let (score, severity) = result.unzip();
+        let articles = article_repo.record_cve_lookup(cve_id, score, severity.as_deref()).await?;
+        looked_up += 1;
+        match severity {
+            Some(severity) => {
+                debug!("{}: CVSS {:.1} {}", cve_id, score.unwrap_or_default(), severity);
+                let tags = [severity_tag(&severity)];
+                for id in articles {
+                    article_repo.add_tags(id, &tags, TAG_SOURCE).await?;
+                }
+            }
+            None => debug!("{}: no CVSS score yet", cve_id),
+        }
+    }
+    if looked_up > 0 {
+        info!("Looked up {} CVEs", looked_up);
+    }
+    Ok(looked_up)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feed::NewArticle;
+    use crate::storage::test_db_with_feed;
+    use serde_json::json;
+
+    #[test]
+    fn test_cve_ids_are_found_once_and_uppercased() {
+        let text = "Patch cve-2024-3094 now (see CVE-2024-3094, CVE-2021-44228). \
+                    Not XCVE-2020-1234, CVE-20-1234, CVE-2020-123 or CVE-2020-1234a.";
+        assert_eq!(cve_ids(text), ["CVE-2024-3094", "CVE-2021-44228"]);
+        assert!(cve_ids("nothing here").is_empty());
+    }
+
+    #[test]
+    fn test_nvd_response_prefers_newest_primary_metric() {
+        let json = json!({
+            "vulnerabilities": [{
+                "cve": {
+                    "id": "CVE-2021-44228",
+                    "metrics": {
+                        "cvssMetricV31": [
+                            { "type": "Secondary", "cvssData": { "baseScore": 9.0, "baseSeverity": "CRITICAL" } },
+                            { "type": "Primary", "cvssData": { "baseScore": 10.0, "baseSeverity": "CRITICAL" } }
+                        ],
+                        "cvssMetricV2": [
+                            { "type": "Primary", "cvssData": { "baseScore": 9.3 }, "baseSeverity": "HIGH" }
+                        ]
+                    }
+                }
+            }]
+        });
+        assert_eq!(parse_nvd_response(&json), Some((10.0, "CRITICAL".to_string())));
+
+        let v2_only = json!({ "vulnerabilities": [{ "cve": { "metrics": {
+            "cvssMetricV2": [{ "cvssData": { "baseScore": 5.0 }, "baseSeverity": "medium" }]
+        }}}]});
+        assert_eq!(parse_nvd_response(&v2_only), Some((5.0, "MEDIUM".to_string())));
+        assert_eq!(parse_nvd_response(&json!({ "vulnerabilities": [] })), None);
+        assert_eq!(severity_tag("CRITICAL"), "cvss-critical");
+    }
+
+    #[tokio::test]
+    async fn test_articles_are_listed_most_severe_first() {
+        let (db, feed) = test_db_with_feed().await;
+        let repo = ArticleRepository::new(&db);
+        let article = |guid: &str, title: &str| NewArticle {
+            url: Some(format!("https://example.com/{}", guid)),
+            title: title.to_string(),
+            ..NewArticle::for_test(feed.id, guid)
+        };
+        let created = repo
+            .create_many(&[
+                article("1", "Moderate bug CVE-2024-0001"),
+                article("2", "Unscored CVE-2024-0003"),
+                article("3", "Critical CVE-2024-0002 and CVE-2024-0001"),
+                article("4", "No advisory"),
+            ])
+            .await
+            .unwrap();
+
+        let mut config = AppConfig::default();
+        assert_eq!(link_new_articles(&repo, &config, &created).await.unwrap(), 0);
+        config.advisories.enabled = true;
+        assert_eq!(link_new_articles(&repo, &config, &created).await.unwrap(), 3);
+        let now = chrono::Utc::now();
+        assert_eq!(
+            repo.list_pending_cves(10, now).await.unwrap(),
+            ["CVE-2024-0003", "CVE-2024-0002", "CVE-2024-0001"]
+        );
+
+        let mentioning = repo.record_cve_lookup("CVE-2024-0001", Some(5.3), Some("MEDIUM")).await.unwrap();
+        assert_eq!(mentioning.len(), 2);
+        repo.record_cve_lookup("CVE-2024-0002", Some(9.8), Some("CRITICAL")).await.unwrap();
+        assert_eq!(repo.list_pending_cves(10, now).await.unwrap(), ["CVE-2024-0003"]);
+
+        let listed = repo.list_advisories(0.0, 10).await.unwrap();
+        let titles: Vec<_> = listed.iter().map(|a| a.article.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            ["Critical CVE-2024-0002 and CVE-2024-0001", "Moderate bug CVE-2024-0001", "Unscored CVE-2024-0003"]
+        );
+        assert_eq!(listed[0].max_score(), Some(9.8));
+        let cves: Vec<_> = listed[0].advisories.iter().map(|a| a.cve_id.as_str()).collect();
+        assert_eq!(cves, ["CVE-2024-0002", "CVE-2024-0001"]);
+        assert_eq!(listed[2].max_score(), None);
+        assert_eq!(repo.get_tags(created[1].id).await.unwrap(), ["CVE-2024-0003"]);
+
+        let critical = repo.list_advisories(9.0, 10).await.unwrap();
+        assert_eq!(critical.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_unscored_cves_are_retried_after_a_while() {
+        let (db, feed) = test_db_with_feed().await;
+        let repo = ArticleRepository::new(&db);
+        let created = repo
+            .create_many(&[NewArticle {
+                title: "Fresh CVE-2026-0002, scored CVE-2026-0001".to_string(),
+                ..NewArticle::for_test(feed.id, "1")
+            }])
+            .await
+            .unwrap();
+        let mut config = AppConfig::default();
+        config.advisories.enabled = true;
+        link_new_articles(&repo, &config, &created).await.unwrap();
+
+        // Awaiting analysis: looked up, but the NVD has no score yet
+        repo.record_cve_lookup("CVE-2026-0002", None, None).await.unwrap();
+        repo.record_cve_lookup("CVE-2026-0001", Some(7.5), Some("HIGH")).await.unwrap();
+        let now = chrono::Utc::now();
+        let hour = chrono::Duration::hours(1);
+        assert!(repo.list_pending_cves(10, now - hour).await.unwrap().is_empty());
+
+        // Once the back-off passed, only the unscored one is due again
+        assert_eq!(repo.list_pending_cves(10, now + hour).await.unwrap(), ["CVE-2026-0002"]);
+        repo.record_cve_lookup("CVE-2026-0002", Some(9.1), Some("CRITICAL")).await.unwrap();
+        assert!(repo.list_pending_cves(10, now + hour).await.unwrap().is_empty());
+    }
+}
@@ -1,4 +1,8 @@
+mod pool;
 pub mod providers;
+mod retry;
 mod summarizer;
 
+pub use pool::{AiPool, AiPermit, AiQueueStats, AiTask};
+pub use retry::RetryGuard;
 pub use summarizer::{ArticleForScoring, ArticleForSummary, ArticleStyleResult, BatchScoreResult, BatchSummaryResult, Summarizer};
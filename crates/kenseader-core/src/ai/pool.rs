@@ -0,0 +1,238 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::oneshot;
+
+/// Kind of AI work, queued separately so one kind can't starve the others
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AiTask {
+    Summarize,
+    Score,
+    Classify,
+}
+
+impl AiTask {
+    pub const ALL: [AiTask; 3] = [AiTask::Summarize, AiTask::Score, AiTask::Classify];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            AiTask::Summarize => "summarize",
+            AiTask::Score => "score",
+            AiTask::Classify => "classify",
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Queue metrics of one kind of AI work
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AiQueueStats {
+    pub task: AiTask,
+    /// Requests waiting for a slot
+    pub queued: usize,
+    /// Requests in flight
+    pub running: usize,
+    /// Requests finished since startup
+    pub completed: u64,
+    /// Average time finished requests waited for a slot
+    pub avg_wait_ms: u64,
+}
+
+/// Bounded pool of AI request slots. Waiting requests are served
+/// round-robin across task kinds, first-come-first-served within a kind.
+#[derive(Clone)]
+pub struct AiPool {
+    inner: Arc<Mutex<PoolState>>,
+}
+
+struct PoolState {
+    available: usize,
+    waiting: [VecDeque<oneshot::Sender<AiPermit>>; 3],
+    /// Kind to serve first on the next hand-over
+    next: usize,
+    stats: [KindStats; 3],
+}
+
+#[derive(Default)]
+struct KindStats {
+    running: usize,
+    completed: u64,
+    total_wait: Duration,
+}
+
+/// A slot of the pool, handed back (to the next waiter) on drop
+pub struct AiPermit {
+    pool: Option<AiPool>,
+    task: AiTask,
+}
+
+impl AiPool {
+    pub fn new(max_concurrent: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(PoolState {
+                available: max_concurrent.max(1),
+                waiting: Default::default(),
+                next: 0,
+                stats: Default::default(),
+            })),
+        }
+    }
+
+    /// Wait for a slot for `task`
+    pub async fn acquire(&self, task: AiTask) -> AiPermit {
+        let queued_at = Instant::now();
+        let rx = {
+            let mut state = self.inner.lock().unwrap();
+            let no_waiters = state.waiting.iter().all(VecDeque::is_empty);
+            if state.available > 0 && no_waiters {
+                state.available -= 1;
+                state.stats[task.index()].running += 1;
+                return AiPermit { pool: Some(self.clone()), task };
+            }
+            let (tx, rx) = oneshot::channel();
+            state.waiting[task.index()].push_back(tx);
+            rx
+        };
+        // The sender is only dropped after handing over a permit
+        let permit = rx.await.expect("AI pool dropped a waiter");
+        let mut state = self.inner.lock().unwrap();
+        state.stats[task.index()].total_wait += queued_at.elapsed();
+        permit
+    }
+
+    /// Current queue metrics per task kind
+    pub fn stats(&self) -> Vec<AiQueueStats> {
+        let state = self.inner.lock().unwrap();
+        AiTask::ALL
+            .into_iter()
+            .map(|task| {
+                let stats = &state.stats[task.index()];
+                let finished = stats.completed.max(1) as u128;
+                AiQueueStats {
+                    task,
+                    queued: state.waiting[task.index()].len(),
+                    running: stats.running,
+                    completed: stats.completed,
+                    avg_wait_ms: (stats.total_wait.as_millis() / finished) as u64,
+                }
+            })
+            .collect()
+    }
+
+    /// Hand a freed slot to the next waiter, or return it to the pool
+    fn release(&self, finished: AiTask) {
+        {
+            let mut state = self.inner.lock().unwrap();
+            let stats = &mut state.stats[finished.index()];
+            stats.running -= 1;
+            stats.completed += 1;
+        }
+        loop {
+            let (task, tx) = {
+                let mut state = self.inner.lock().unwrap();
+                let len = state.waiting.len();
+                let start = state.next;
+                let Some(index) = (0..len).map(|i| (start + i) % len).find(|&i| !state.waiting[i].is_empty())
+                else {
+                    state.available += 1;
+                    return;
+                };
+                state.next = (index + 1) % len;
+                state.stats[index].running += 1;
+                let tx = state.waiting[index].pop_front().expect("queue is not empty");
+                (AiTask::ALL[index], tx)
+            };
+            match tx.send(AiPermit { pool: Some(self.clone()), task }) {
+                Ok(()) => return,
+                // The waiter gave up; try the next one without counting a run
+                Err(mut permit) => {
+                    permit.pool = None;
+                    self.inner.lock().unwrap().stats[task.index()].running -= 1;
+                }
+            }
+        }
+    }
+}
+
+impl Drop for AiPermit {
+    fn drop(&mut self) {
+        if let Some(pool) = self.pool.take() {
+            pool.release(self.task);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_pool_round_robin() {
+        let pool = AiPool::new(1);
+        let first = pool.acquire(AiTask::Summarize).await;
+
+        // Queue two summarize requests, then one score request
+        let (order_tx, mut order_rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut handles = Vec::new();
+        for task in [AiTask::Summarize, AiTask::Summarize, AiTask::Score] {
+            let task_pool = pool.clone();
+            let order_tx = order_tx.clone();
+            handles.push(tokio::spawn(async move {
+                let _permit = task_pool.acquire(task).await;
+                order_tx.send(task).unwrap();
+            }));
+            while pool.stats().iter().map(|s| s.queued).sum::<usize>() < handles.len() {
+                tokio::task::yield_now().await;
+            }
+        }
+        let stats = pool.stats();
+        assert_eq!(stats[0].queued, 2);
+        assert_eq!(stats[0].running, 1);
+        assert_eq!(stats[1].queued, 1);
+
+        drop(first);
+        for handle in handles {
+            handle.await.unwrap();
+        }
+        drop(order_tx);
+        let mut order = Vec::new();
+        while let Some(task) = order_rx.recv().await {
+            order.push(task);
+        }
+        // The score request doesn't wait behind the second summarize request
+        assert_eq!(order, [AiTask::Summarize, AiTask::Score, AiTask::Summarize]);
+
+        let stats = pool.stats();
+        assert_eq!(stats[0].completed, 3);
+        assert_eq!(stats[1].completed, 1);
+        assert!(stats.iter().all(|s| s.running == 0 && s.queued == 0));
+    }
+
+    #[tokio::test]
+    async fn test_pool_abandoned_waiter() {
+        let pool = AiPool::new(1);
+        let first = pool.acquire(AiTask::Score).await;
+        let waiter = tokio::spawn({
+            let pool = pool.clone();
+            async move {
+                let _permit = pool.acquire(AiTask::Classify).await;
+            }
+        });
+        while pool.stats()[2].queued == 0 {
+            tokio::task::yield_now().await;
+        }
+        waiter.abort();
+        let _ = waiter.await;
+        drop(first);
+
+        // The slot went back to the pool
+        let _permit = pool.acquire(AiTask::Summarize).await;
+        assert_eq!(pool.stats()[0].running, 1);
+    }
+}
@@ -0,0 +1,189 @@
+use std::time::{Duration, Instant};
+
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use super::{check_transient_status, request_error};
+use crate::{Error, Result};
+
+const AI_REQUEST_TIMEOUT_SECS: u64 = 30;
+
+/// Scope of Azure AD tokens for Azure OpenAI
+const AZURE_AD_SCOPE: &str = "https://cognitiveservices.azure.com/.default";
+
+/// Tokens are refreshed this long before they expire
+const TOKEN_REFRESH_MARGIN_SECS: u64 = 300;
+
+/// How requests to an Azure OpenAI resource are authenticated
+pub enum AzureAuth {
+    /// Resource key, sent as the `api-key` header
+    ApiKey(String),
+    /// Azure AD (Entra ID) service principal, using the client credentials flow
+    ServicePrincipal {
+        tenant_id: String,
+        client_id: String,
+        client_secret: String,
+    },
+}
+
+#[derive(Serialize)]
+struct ChatRequest<'a> {
+    messages: [ChatMessage<'a>; 1],
+    max_tokens: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<ResponseFormat>,
+}
+
+#[derive(Serialize)]
+struct ChatMessage<'a> {
+    role: &'static str,
+    content: &'a str,
+}
+
+#[derive(Serialize)]
+struct ResponseFormat {
+    #[serde(rename = "type")]
+    kind: &'static str,
+}
+
+#[derive(Deserialize)]
+struct ChatResponse {
+    choices: Option<Vec<ChatChoice>>,
+    error: Option<ApiError>,
+}
+
+#[derive(Deserialize)]
+struct ChatChoice {
+    message: ChatChoiceMessage,
+}
+
+#[derive(Deserialize)]
+struct ChatChoiceMessage {
+    content: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ApiError {
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: Option<String>,
+    expires_in: Option<u64>,
+    error_description: Option<String>,
+}
+
+/// Chat completions client of an Azure OpenAI deployment
+pub struct AzureOpenAiClient {
+    client: Client,
+    url: String,
+    auth: AzureAuth,
+    /// Cached Azure AD token and when it expires
+    token: Mutex<Option<(String, Instant)>>,
+}
+
+impl AzureOpenAiClient {
+    /// `endpoint` is the resource URL, e.g. https://my-resource.openai.azure.com
+    pub fn new(endpoint: &str, deployment: &str, api_version: &str, auth: AzureAuth) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(AI_REQUEST_TIMEOUT_SECS))
+            .build()
+            .expect("Failed to build Azure OpenAI HTTP client");
+
+        Self {
+            client,
+            url: format!(
+                "{}/openai/deployments/{}/chat/completions?api-version={}",
+                endpoint.trim_end_matches('/'),
+                deployment,
+                api_version
+            ),
+            auth,
+            token: Mutex::new(None),
+        }
+    }
+
+    /// Send one user message, in JSON mode with `json`
+    pub async fn chat(&self, prompt: &str, max_tokens: u32, json: bool) -> Result<String> {
+        let request = ChatRequest {
+            messages: [ChatMessage { role: "user", content: prompt }],
+            max_tokens,
+            response_format: json.then_some(ResponseFormat { kind: "json_object" }),
+        };
+
+        let builder = self.client.post(&self.url).json(&request);
+        let builder = match &self.auth {
+            AzureAuth::ApiKey(key) => builder.header("api-key", key),
+            AzureAuth::ServicePrincipal { .. } => builder.bearer_auth(self.access_token().await?),
+        };
+        let response = builder
+            .send()
+            .await
+            .map_err(|e| request_error("Azure OpenAI", e))?;
+        let response = check_transient_status("Azure OpenAI", response)?;
+
+        let chat_response: ChatResponse = response
+            .json()
+            .await
+            .map_err(|e| Error::AiProvider(format!("Failed to parse Azure OpenAI response: {}", e)))?;
+
+        if let Some(error) = chat_response.error {
+            return Err(Error::AiProvider(format!("Azure OpenAI API error: {}", error.message)));
+        }
+
+        let content = chat_response
+            .choices
+            .and_then(|c| c.into_iter().next())
+            .and_then(|c| c.message.content)
+            .unwrap_or_default();
+
+        Ok(content)
+    }
+
+    /// Current Azure AD token, requesting a new one when it is about to expire
+    async fn access_token(&self) -> Result<String> {
+        let AzureAuth::ServicePrincipal { tenant_id, client_id, client_secret } = &self.auth else {
+            return Err(Error::Config("Azure OpenAI is not using Azure AD authentication".to_string()));
+        };
+
+        // Held across the request so concurrent calls share one refresh
+        let mut cached = self.token.lock().await;
+        if let Some((token, expires_at)) = cached.as_ref() {
+            if Instant::now() + Duration::from_secs(TOKEN_REFRESH_MARGIN_SECS) < *expires_at {
+                return Ok(token.clone());
+            }
+        }
+
+        let url = format!("https://login.microsoftonline.com/{}/oauth2/v2.0/token", tenant_id);
+        let response = self
+            .client
+            .post(&url)
+            .form(&[
+                ("grant_type", "client_credentials"),
+                ("client_id", client_id),
+                ("client_secret", client_secret),
+                ("scope", AZURE_AD_SCOPE),
+            ])
+            .send()
+            .await
+            .map_err(|e| request_error("Azure AD", e))?;
+        let response = check_transient_status("Azure AD", response)?;
+
+        let token_response: TokenResponse = response
+            .json()
+            .await
+            .map_err(|e| Error::AiProvider(format!("Failed to parse Azure AD token response: {}", e)))?;
+        let Some(token) = token_response.access_token else {
+            return Err(Error::AiProvider(format!(
+                "Azure AD token request failed: {}",
+                token_response.error_description.unwrap_or_default()
+            )));
+        };
+
+        let expires_in = Duration::from_secs(token_response.expires_in.unwrap_or(3600));
+        *cached = Some((token.clone(), Instant::now() + expires_in));
+        Ok(token)
+    }
+}
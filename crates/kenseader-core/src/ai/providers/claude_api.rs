@@ -2,7 +2,8 @@ use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
-use super::{AiProvider, ArticleForScoring, ArticleForSummary, ArticleStyleResult, BatchScoreResult, BatchSummaryResult};
+use super::structured::{ask_batch, ask_style, parse_scores, parse_summaries, SCORE_FORMAT, STYLE_FORMAT, SUMMARY_FORMAT};
+use super::{check_transient_status, request_error, AiProvider, ArticleForScoring, ArticleForSummary, ArticleStyleResult, BatchScoreResult, BatchSummaryResult};
 use crate::{Error, Result};
 
 fn truncate_chars(input: &str, max_chars: usize) -> &str {
@@ -69,13 +70,29 @@ impl ClaudeApiProvider {
     }
 
     async fn chat(&self, prompt: &str, max_tokens: u32) -> Result<String> {
+        self.send(prompt, max_tokens, false).await
+    }
+
+    /// Chat that answers with a JSON object (the response is prefilled with "{")
+    async fn chat_json(&self, prompt: &str, max_tokens: u32) -> Result<String> {
+        self.send(prompt, max_tokens, true).await
+    }
+
+    async fn send(&self, prompt: &str, max_tokens: u32, json: bool) -> Result<String> {
+        let mut messages = vec![ClaudeMessage {
+            role: "user".to_string(),
+            content: prompt.to_string(),
+        }];
+        if json {
+            messages.push(ClaudeMessage {
+                role: "assistant".to_string(),
+                content: "{".to_string(),
+            });
+        }
         let request = ClaudeRequest {
             model: self.model.clone(),
             max_tokens,
-            messages: vec![ClaudeMessage {
-                role: "user".to_string(),
-                content: prompt.to_string(),
-            }],
+            messages,
         };
 
         let response = self
@@ -87,7 +104,8 @@ impl ClaudeApiProvider {
             .json(&request)
             .send()
             .await
-            .map_err(|e| Error::AiProvider(format!("Claude API request failed: {}", e)))?;
+            .map_err(|e| request_error("Claude", e))?;
+        let response = check_transient_status("Claude", response)?;
 
         let claude_response: ClaudeResponse = response
             .json()
@@ -104,7 +122,7 @@ impl ClaudeApiProvider {
             .map(|c| c.text)
             .unwrap_or_default();
 
-        Ok(content)
+        Ok(if json { format!("{{{}", content) } else { content })
     }
 }
 
@@ -204,8 +222,7 @@ Respond with only a number from 0 to 100.",
         let language = &self.language;
         let mut prompt = format!(
             "Below are multiple articles. For EACH article, provide a 2-3 sentence summary in {language}.\n\
-Format your response EXACTLY as follows, with each summary on its own line:\n\
-[ARTICLE_ID]: summary text here\n\n"
+{SUMMARY_FORMAT}\n\n"
         );
 
         for article in &valid_articles {
@@ -217,26 +234,11 @@ Format your response EXACTLY as follows, with each summary on its own line:\n\
         }
 
         prompt.push_str(&format!(
-            "Now provide summaries in {language} using the format [ARTICLE_ID]: summary\n"
+            "Now provide the summaries in {language} as JSON.\n"
         ));
 
-        let result = self.chat(&prompt, 2000).await?;
-
-        // Parse results
-        let mut summaries: std::collections::HashMap<String, String> = std::collections::HashMap::new();
-
-        for line in result.lines() {
-            let line = line.trim();
-            if line.starts_with('[') {
-                if let Some(end_bracket) = line.find("]:") {
-                    let id = &line[1..end_bracket];
-                    let summary = line[end_bracket + 2..].trim();
-                    if !summary.is_empty() {
-                        summaries.insert(id.to_string(), summary.to_string());
-                    }
-                }
-            }
-        }
+        let ids: Vec<String> = valid_articles.iter().map(|a| a.id.clone()).collect();
+        let summaries = ask_batch(prompt, &ids, parse_summaries, move |prompt| async move { self.chat_json(&prompt, 2000).await }).await?;
 
         Ok(articles
             .into_iter()
@@ -290,8 +292,7 @@ Format your response EXACTLY as follows, with each summary on its own line:\n\
         let mut prompt = format!(
             "Rate how relevant each article is to someone interested in: {interests_str}.\n\
             For EACH article, respond with a score from 0 to 100.\n\
-            Format your response EXACTLY as follows, one per line:\n\
-            [ARTICLE_ID]: score\n\n"
+            {SCORE_FORMAT}\n\n"
         );
 
         for article in &articles {
@@ -302,24 +303,10 @@ Format your response EXACTLY as follows, with each summary on its own line:\n\
             ));
         }
 
-        prompt.push_str("Now provide scores using the format [ARTICLE_ID]: score\n");
-
-        let result = self.chat(&prompt, 200).await?;
+        prompt.push_str("Now provide the scores as JSON.\n");
 
-        let mut scores: std::collections::HashMap<String, f64> = std::collections::HashMap::new();
-
-        for line in result.lines() {
-            let line = line.trim();
-            if line.starts_with('[') {
-                if let Some(end_bracket) = line.find("]:") {
-                    let id = &line[1..end_bracket];
-                    let score_str = line[end_bracket + 2..].trim();
-                    if let Ok(score) = score_str.parse::<f64>() {
-                        scores.insert(id.to_string(), score / 100.0);
-                    }
-                }
-            }
-        }
+        let ids: Vec<String> = articles.iter().map(|a| a.id.clone()).collect();
+        let scores = ask_batch(prompt, &ids, parse_scores, move |prompt| async move { self.chat_json(&prompt, 1000).await }).await?;
 
         Ok(articles
             .into_iter()
@@ -353,16 +340,11 @@ Format your response EXACTLY as follows, with each summary on its own line:\n\
         let truncated = truncate_chars(content, 2000);
 
         let prompt = format!(
-            "Classify this article's style. Respond with ONLY valid JSON (no markdown, no code blocks):\n\
-            {{\"style_type\": \"tutorial|news|opinion|analysis|review\", \"tone\": \"formal|casual|technical|humorous\", \"length_category\": \"short|medium|long\"}}\n\n\
+            "Classify this article's style. {STYLE_FORMAT}\n\n\
             Choose the most appropriate value for each field based on the article content.\n\n\
             Article:\n{truncated}"
         );
 
-        let result = self.chat(&prompt, 100).await?;
-
-        // Parse JSON response
-        let cleaned = result.trim().trim_matches(|c| c == '`' || c == '\n');
-        Ok(serde_json::from_str(cleaned).unwrap_or_else(|_| ArticleStyleResult::default()))
+        ask_style(prompt, move |prompt| async move { self.chat_json(&prompt, 100).await }).await
     }
 }
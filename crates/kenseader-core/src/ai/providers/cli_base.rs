@@ -1,6 +1,7 @@
 use std::process::Command;
 use std::time::Duration;
 
+use super::structured::{ask_batch, ask_style, parse_scores, parse_summaries, SCORE_FORMAT, STYLE_FORMAT, SUMMARY_FORMAT};
 use super::{AiProvider, ArticleForScoring, ArticleForSummary, ArticleStyleResult, BatchScoreResult, BatchSummaryResult};
 use crate::{Error, Result};
 
@@ -121,6 +122,23 @@ impl CliProvider {
         let result = String::from_utf8_lossy(&output.stdout).trim().to_string();
         Ok(result)
     }
+
+    /// Run a prompt on the blocking pool, giving up after `timeout_secs`
+    async fn run_with_timeout(&self, prompt: String, timeout_secs: u64) -> Result<String> {
+        let cli_type = self.cli_type;
+        let lang = self.language.clone();
+        let max_len = self.summary_max_length;
+        let min_len = self.min_content_length;
+        let task = tokio::task::spawn_blocking(move || {
+            let provider = CliProvider::new(cli_type, &lang, max_len, min_len);
+            provider.run_cli(&prompt)
+        });
+
+        tokio::time::timeout(Duration::from_secs(timeout_secs), task)
+            .await
+            .map_err(|_| Error::AiProvider(format!("CLI timed out after {} seconds", timeout_secs)))?
+            .map_err(|e| Error::AiProvider(format!("Task join error: {}", e)))?
+    }
 }
 
 #[async_trait::async_trait]
@@ -275,8 +293,7 @@ Respond with only a number from 0 to 100, where 0 means not relevant at all and
         let mut prompt = format!(
             "Below are multiple articles. For EACH article, provide a 2-3 sentence summary (max {max_len} characters) in {language}.\n\
 Do NOT fetch any URLs. Use ONLY the text provided.\n\
-Format your response EXACTLY as follows, with each summary on its own line:\n\
-[ARTICLE_ID]: summary text here\n\n"
+{SUMMARY_FORMAT}\n\n"
         );
 
         for article in &valid_articles {
@@ -288,40 +305,11 @@ Format your response EXACTLY as follows, with each summary on its own line:\n\
         }
 
         prompt.push_str(&format!(
-            "Now provide summaries in {language} (max {max_len} chars each) using the format [ARTICLE_ID]: summary\n"
+            "Now provide the summaries in {language} (max {max_len} chars each) as JSON.\n"
         ));
 
-        let prompt_clone = prompt;
-        let cli_type = self.cli_type;
-        let lang = self.language.clone();
-        let min_len_for_spawn = self.min_content_length;
-
-        let task = tokio::task::spawn_blocking(move || {
-            let provider = CliProvider::new(cli_type, &lang, max_len, min_len_for_spawn);
-            provider.run_cli(&prompt_clone)
-        });
-
-        // Batch operations get longer timeout (3x normal)
-        let result = tokio::time::timeout(Duration::from_secs(CLI_TIMEOUT_SECS * 3), task)
-            .await
-            .map_err(|_| Error::AiProvider(format!("CLI batch timed out after {} seconds", CLI_TIMEOUT_SECS * 3)))?
-            .map_err(|e| Error::AiProvider(format!("Task join error: {}", e)))??;
-
-        // Parse results
-        let mut summaries: std::collections::HashMap<String, String> = std::collections::HashMap::new();
-
-        for line in result.lines() {
-            let line = line.trim();
-            if line.starts_with('[') {
-                if let Some(end_bracket) = line.find("]:") {
-                    let id = &line[1..end_bracket];
-                    let summary = line[end_bracket + 2..].trim();
-                    if !summary.is_empty() {
-                        summaries.insert(id.to_string(), summary.to_string());
-                    }
-                }
-            }
-        }
+        let ids: Vec<String> = valid_articles.iter().map(|a| a.id.clone()).collect();
+        let summaries = ask_batch(prompt, &ids, parse_summaries, move |prompt| self.run_with_timeout(prompt, CLI_TIMEOUT_SECS * 3)).await?;
 
         Ok(articles
             .into_iter()
@@ -375,8 +363,7 @@ Format your response EXACTLY as follows, with each summary on its own line:\n\
         let mut prompt = format!(
             "Rate how relevant each article is to someone interested in: {interests_str}.\n\
             For EACH article, respond with a score from 0 to 100.\n\
-            Format your response EXACTLY as follows, one per line:\n\
-            [ARTICLE_ID]: score\n\n"
+            {SCORE_FORMAT}\n\n"
         );
 
         for article in &articles {
@@ -387,39 +374,10 @@ Format your response EXACTLY as follows, with each summary on its own line:\n\
             ));
         }
 
-        prompt.push_str("Now provide scores using the format [ARTICLE_ID]: score\n");
+        prompt.push_str("Now provide the scores as JSON.\n");
 
-        let prompt_clone = prompt;
-        let cli_type = self.cli_type;
-        let lang = self.language.clone();
-        let max_len = self.summary_max_length;
-        let min_len = self.min_content_length;
-
-        let task = tokio::task::spawn_blocking(move || {
-            let provider = CliProvider::new(cli_type, &lang, max_len, min_len);
-            provider.run_cli(&prompt_clone)
-        });
-
-        // Batch operations get longer timeout (3x normal)
-        let result = tokio::time::timeout(Duration::from_secs(CLI_TIMEOUT_SECS * 3), task)
-            .await
-            .map_err(|_| Error::AiProvider(format!("CLI batch timed out after {} seconds", CLI_TIMEOUT_SECS * 3)))?
-            .map_err(|e| Error::AiProvider(format!("Task join error: {}", e)))??;
-
-        let mut scores: std::collections::HashMap<String, f64> = std::collections::HashMap::new();
-
-        for line in result.lines() {
-            let line = line.trim();
-            if line.starts_with('[') {
-                if let Some(end_bracket) = line.find("]:") {
-                    let id = &line[1..end_bracket];
-                    let score_str = line[end_bracket + 2..].trim();
-                    if let Ok(score) = score_str.parse::<f64>() {
-                        scores.insert(id.to_string(), score / 100.0);
-                    }
-                }
-            }
-        }
+        let ids: Vec<String> = articles.iter().map(|a| a.id.clone()).collect();
+        let scores = ask_batch(prompt, &ids, parse_scores, move |prompt| self.run_with_timeout(prompt, CLI_TIMEOUT_SECS * 3)).await?;
 
         Ok(articles
             .into_iter()
@@ -453,30 +411,11 @@ Format your response EXACTLY as follows, with each summary on its own line:\n\
         let truncated = truncate_chars(content, 2000);
 
         let prompt = format!(
-            "Classify this article's style. Respond with ONLY valid JSON (no markdown, no code blocks):\n\
-            {{\"style_type\": \"tutorial|news|opinion|analysis|review\", \"tone\": \"formal|casual|technical|humorous\", \"length_category\": \"short|medium|long\"}}\n\n\
+            "Classify this article's style. {STYLE_FORMAT}\n\n\
             Choose the most appropriate value for each field based on the article content.\n\n\
             Article:\n{truncated}"
         );
 
-        let prompt_clone = prompt;
-        let cli_type = self.cli_type;
-        let lang = self.language.clone();
-        let max_len = self.summary_max_length;
-        let min_len = self.min_content_length;
-
-        let task = tokio::task::spawn_blocking(move || {
-            let provider = CliProvider::new(cli_type, &lang, max_len, min_len);
-            provider.run_cli(&prompt_clone)
-        });
-
-        let result = tokio::time::timeout(Duration::from_secs(CLI_TIMEOUT_SECS), task)
-            .await
-            .map_err(|_| Error::AiProvider(format!("CLI timed out after {} seconds", CLI_TIMEOUT_SECS)))?
-            .map_err(|e| Error::AiProvider(format!("Task join error: {}", e)))??;
-
-        // Parse JSON response
-        let cleaned = result.trim().trim_matches(|c| c == '`' || c == '\n');
-        Ok(serde_json::from_str(cleaned).unwrap_or_else(|_| ArticleStyleResult::default()))
+        ask_style(prompt, move |prompt| self.run_with_timeout(prompt, CLI_TIMEOUT_SECS)).await
     }
 }
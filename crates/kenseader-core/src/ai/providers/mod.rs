@@ -1,16 +1,23 @@
+mod azure_openai;
 mod claude_api;
 mod claude_cli;
 mod cli_base;
 mod gemini_api;
 mod openai;
+mod structured;
 
+pub use azure_openai::AzureAuth;
 pub use claude_api::ClaudeApiProvider;
 pub use claude_cli::ClaudeCliProvider;
 pub use cli_base::{CliProvider, CliType};
 pub use gemini_api::GeminiApiProvider;
 pub use openai::OpenAiProvider;
 
-use crate::Result;
+use std::time::Duration;
+
+use reqwest::{Response, StatusCode};
+
+use crate::{Error, Result};
 
 /// Article info for batch summarization
 #[derive(Debug, Clone)]
@@ -64,6 +71,35 @@ impl Default for ArticleStyleResult {
     }
 }
 
+/// Map a failed HTTP request: connection problems and timeouts are transient
+fn request_error(provider: &str, e: reqwest::Error) -> Error {
+    let message = format!("{} API request failed: {}", provider, e);
+    if e.is_connect() || e.is_timeout() {
+        Error::AiTransient { message, retry_after: None }
+    } else {
+        Error::AiProvider(message)
+    }
+}
+
+/// Turn rate limiting (429) and server-side failures (5xx, e.g. 529
+/// overloaded) into transient errors, honoring a Retry-After in seconds
+fn check_transient_status(provider: &str, response: Response) -> Result<Response> {
+    let status = response.status();
+    if status != StatusCode::TOO_MANY_REQUESTS && !status.is_server_error() {
+        return Ok(response);
+    }
+    let retry_after = response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse().ok())
+        .map(Duration::from_secs);
+    Err(Error::AiTransient {
+        message: format!("{} API returned {}", provider, status),
+        retry_after,
+    })
+}
+
 /// Trait for AI summarization providers
 #[async_trait::async_trait]
 pub trait AiProvider: Send + Sync {
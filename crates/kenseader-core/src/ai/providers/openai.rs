@@ -1,9 +1,15 @@
 use async_openai::{
-    types::{ChatCompletionRequestMessage, ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequestArgs},
+    error::OpenAIError,
+    types::{
+        ChatCompletionRequestMessage, ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequestArgs,
+        ResponseFormat,
+    },
     Client,
 };
 
-use super::{AiProvider, ArticleForScoring, ArticleForSummary, ArticleStyleResult, BatchScoreResult, BatchSummaryResult};
+use super::azure_openai::{AzureAuth, AzureOpenAiClient};
+use super::structured::{ask_batch, ask_style, parse_scores, parse_summaries, SCORE_FORMAT, STYLE_FORMAT, SUMMARY_FORMAT};
+use super::{request_error, AiProvider, ArticleForScoring, ArticleForSummary, ArticleStyleResult, BatchScoreResult, BatchSummaryResult};
 use crate::{Error, Result};
 
 fn truncate_chars(input: &str, max_chars: usize) -> &str {
@@ -13,10 +19,31 @@ fn truncate_chars(input: &str, max_chars: usize) -> &str {
     }
 }
 
-/// OpenAI API provider
+/// Map a client error. The client already backs off on rate limits and
+/// server errors itself, so what still reaches here is retried like the
+/// other providers' transient failures.
+fn openai_error(e: OpenAIError) -> Error {
+    match e {
+        OpenAIError::Reqwest(e) => request_error("OpenAI", e),
+        OpenAIError::ApiError(ref api) if api.code.as_deref() == Some("rate_limit_exceeded") => {
+            Error::AiTransient { message: e.to_string(), retry_after: None }
+        }
+        e => Error::AiProvider(e.to_string()),
+    }
+}
+
+/// Where chat requests are sent
+enum Backend {
+    OpenAi {
+        client: Client<async_openai::config::OpenAIConfig>,
+        model: String,
+    },
+    Azure(AzureOpenAiClient),
+}
+
+/// OpenAI API provider, talking to OpenAI itself or to an Azure OpenAI deployment
 pub struct OpenAiProvider {
-    client: Client<async_openai::config::OpenAIConfig>,
-    model: String,
+    backend: Backend,
     language: String,
     summary_max_tokens: u32,
 }
@@ -27,32 +54,65 @@ impl OpenAiProvider {
         let client = Client::with_config(config);
 
         Self {
-            client,
-            model: model.to_string(),
+            backend: Backend::OpenAi {
+                client,
+                model: model.to_string(),
+            },
+            language: language.to_string(),
+            summary_max_tokens,
+        }
+    }
+
+    /// Provider for an Azure OpenAI deployment (the deployment selects the model)
+    pub fn azure(
+        endpoint: &str,
+        deployment: &str,
+        api_version: &str,
+        auth: AzureAuth,
+        language: &str,
+        summary_max_tokens: u32,
+    ) -> Self {
+        Self {
+            backend: Backend::Azure(AzureOpenAiClient::new(endpoint, deployment, api_version, auth)),
             language: language.to_string(),
             summary_max_tokens,
         }
     }
 
     async fn chat(&self, prompt: &str, max_tokens: u32) -> Result<String> {
-        let request = CreateChatCompletionRequestArgs::default()
-            .model(&self.model)
+        self.send(prompt, max_tokens, false).await
+    }
+
+    /// Chat in JSON mode (the response is a JSON object)
+    async fn chat_json(&self, prompt: &str, max_tokens: u32) -> Result<String> {
+        self.send(prompt, max_tokens, true).await
+    }
+
+    async fn send(&self, prompt: &str, max_tokens: u32, json: bool) -> Result<String> {
+        let (client, model) = match &self.backend {
+            Backend::OpenAi { client, model } => (client, model),
+            Backend::Azure(azure) => return azure.chat(prompt, max_tokens, json).await,
+        };
+
+        let mut args = CreateChatCompletionRequestArgs::default();
+        args.model(model)
             .messages(vec![ChatCompletionRequestMessage::User(
                 ChatCompletionRequestUserMessageArgs::default()
                     .content(prompt)
                     .build()
                     .map_err(|e| Error::AiProvider(e.to_string()))?,
             )])
-            .max_tokens(max_tokens)
-            .build()
-            .map_err(|e| Error::AiProvider(e.to_string()))?;
+            .max_tokens(max_tokens);
+        if json {
+            args.response_format(ResponseFormat::JsonObject);
+        }
+        let request = args.build().map_err(|e| Error::AiProvider(e.to_string()))?;
 
-        let response = self
-            .client
+        let response = client
             .chat()
             .create(request)
             .await
-            .map_err(|e| Error::AiProvider(e.to_string()))?;
+            .map_err(openai_error)?;
 
         let content = response
             .choices
@@ -154,8 +214,7 @@ impl AiProvider for OpenAiProvider {
         let language = &self.language;
         let mut prompt = format!(
             "Below are multiple articles. For EACH article, provide a 2-3 sentence summary in {language}.\n\
-            Format your response EXACTLY as follows, with each summary on its own line:\n\
-            [ARTICLE_ID]: summary text here\n\n"
+            {SUMMARY_FORMAT}\n\n"
         );
 
         for article in &valid_articles {
@@ -167,25 +226,11 @@ impl AiProvider for OpenAiProvider {
         }
 
         prompt.push_str(&format!(
-            "Now provide summaries in {language} using the format [ARTICLE_ID]: summary\n"
+            "Now provide the summaries in {language} as JSON.\n"
         ));
 
-        let result = self.chat(&prompt, 200).await?;
-
-        // Parse results
-        let mut summaries: std::collections::HashMap<String, String> = std::collections::HashMap::new();
-        for line in result.lines() {
-            let line = line.trim();
-            if line.starts_with('[') {
-                if let Some(end_bracket) = line.find("]:") {
-                    let id = &line[1..end_bracket];
-                    let summary = line[end_bracket + 2..].trim();
-                    if !summary.is_empty() {
-                        summaries.insert(id.to_string(), summary.to_string());
-                    }
-                }
-            }
-        }
+        let ids: Vec<String> = valid_articles.iter().map(|a| a.id.clone()).collect();
+        let summaries = ask_batch(prompt, &ids, parse_summaries, move |prompt| async move { self.chat_json(&prompt, 2000).await }).await?;
 
         Ok(articles
             .into_iter()
@@ -239,8 +284,7 @@ impl AiProvider for OpenAiProvider {
         let mut prompt = format!(
             "Rate how relevant each article is to someone interested in: {interests_str}.\n\
             For EACH article, respond with a score from 0 to 100.\n\
-            Format your response EXACTLY as follows, one per line:\n\
-            [ARTICLE_ID]: score\n\n"
+            {SCORE_FORMAT}\n\n"
         );
 
         for article in &articles {
@@ -251,24 +295,10 @@ impl AiProvider for OpenAiProvider {
             ));
         }
 
-        prompt.push_str("Now provide scores using the format [ARTICLE_ID]: score\n");
+        prompt.push_str("Now provide the scores as JSON.\n");
 
-        let result = self.chat(&prompt, 200).await?;
-
-        let mut scores: std::collections::HashMap<String, f64> = std::collections::HashMap::new();
-
-        for line in result.lines() {
-            let line = line.trim();
-            if line.starts_with('[') {
-                if let Some(end_bracket) = line.find("]:") {
-                    let id = &line[1..end_bracket];
-                    let score_str = line[end_bracket + 2..].trim();
-                    if let Ok(score) = score_str.parse::<f64>() {
-                        scores.insert(id.to_string(), score / 100.0);
-                    }
-                }
-            }
-        }
+        let ids: Vec<String> = articles.iter().map(|a| a.id.clone()).collect();
+        let scores = ask_batch(prompt, &ids, parse_scores, move |prompt| async move { self.chat_json(&prompt, 1000).await }).await?;
 
         Ok(articles
             .into_iter()
@@ -302,16 +332,11 @@ impl AiProvider for OpenAiProvider {
         let truncated = truncate_chars(content, 2000);
 
         let prompt = format!(
-            "Classify this article's style. Respond with ONLY valid JSON (no markdown, no code blocks):\n\
-            {{\"style_type\": \"tutorial|news|opinion|analysis|review\", \"tone\": \"formal|casual|technical|humorous\", \"length_category\": \"short|medium|long\"}}\n\n\
+            "Classify this article's style. {STYLE_FORMAT}\n\n\
             Choose the most appropriate value for each field based on the article content.\n\n\
             Article:\n{truncated}"
         );
 
-        let result = self.chat(&prompt, 100).await?;
-
-        // Parse JSON response
-        let cleaned = result.trim().trim_matches(|c| c == '`' || c == '\n');
-        Ok(serde_json::from_str(cleaned).unwrap_or_else(|_| ArticleStyleResult::default()))
+        ask_style(prompt, move |prompt| async move { self.chat_json(&prompt, 100).await }).await
     }
 }
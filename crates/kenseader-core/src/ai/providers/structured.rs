@@ -0,0 +1,250 @@
+//! JSON output for batch and classification requests: the format asked
+//! for, schema validation of the response, and one repair round that
+//! shows the model what was wrong before errors are recorded.
+
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+
+use serde_json::Value;
+
+use super::ArticleStyleResult;
+use crate::Result;
+
+/// Output format of batch summarization
+pub(super) const SUMMARY_FORMAT: &str = "Respond with ONLY a JSON object (no markdown, no code blocks) \
+with one entry per article, in this exact format:\n\
+{\"results\": [{\"id\": \"ARTICLE_ID\", \"summary\": \"summary text here\"}]}";
+
+/// Output format of batch relevance scoring
+pub(super) const SCORE_FORMAT: &str = "Respond with ONLY a JSON object (no markdown, no code blocks) \
+with one entry per article, in this exact format:\n\
+{\"results\": [{\"id\": \"ARTICLE_ID\", \"score\": 0-100}]}";
+
+/// Output format of style classification
+pub(super) const STYLE_FORMAT: &str = "Respond with ONLY valid JSON (no markdown, no code blocks):\n\
+{\"style_type\": \"tutorial|news|opinion|analysis|review\", \"tone\": \"formal|casual|technical|humorous\", \"length_category\": \"short|medium|long\"}";
+
+const STYLE_TYPES: &[&str] = &["tutorial", "news", "opinion", "analysis", "review"];
+const TONES: &[&str] = &["formal", "casual", "technical", "humorous"];
+const LENGTH_CATEGORIES: &[&str] = &["short", "medium", "long"];
+
+/// Longest previous response quoted back in a repair prompt
+const MAX_QUOTED_RESPONSE_CHARS: usize = 4000;
+
+/// Values parsed from a batch response, and what was wrong with it
+pub(super) struct BatchParse<T> {
+    pub values: HashMap<String, T>,
+    pub problems: Vec<String>,
+}
+
+/// The JSON object in a response, skipping code fences and text around it
+fn extract_json(response: &str) -> Option<&str> {
+    let start = response.find('{')?;
+    let end = response.rfind('}')?;
+    (start < end).then(|| &response[start..=end])
+}
+
+/// Parse `{"results": [{"id": .., <field>: ..}]}`, keeping the entries of
+/// the requested `ids` whose field passes `value`
+fn parse_batch<T>(response: &str, ids: &[String], field: &str, value: impl Fn(&Value) -> Option<T>) -> BatchParse<T> {
+    let mut parsed = BatchParse { values: HashMap::new(), problems: Vec::new() };
+
+    let json: Option<Value> = extract_json(response).and_then(|json| serde_json::from_str(json).ok());
+    let Some(results) = json.as_ref().and_then(|json| json.get("results")).and_then(Value::as_array) else {
+        parsed
+            .problems
+            .push("the response is not a JSON object with a \"results\" array".to_string());
+        return parsed;
+    };
+
+    let mut seen = HashSet::new();
+    for entry in results {
+        // Entries for IDs that weren't asked for are ignored
+        let Some(id) = entry.get("id").and_then(Value::as_str).filter(|id| ids.iter().any(|known| known == id))
+        else {
+            continue;
+        };
+        seen.insert(id);
+        match entry.get(field).and_then(&value) {
+            Some(v) => {
+                parsed.values.insert(id.to_string(), v);
+            }
+            None => parsed.problems.push(format!("the entry for {} has no valid \"{}\"", id, field)),
+        }
+    }
+
+    let missing: Vec<&str> = ids.iter().map(String::as_str).filter(|id| !seen.contains(id)).collect();
+    if !missing.is_empty() {
+        parsed.problems.push(format!("no entries for {}", missing.join(", ")));
+    }
+    parsed
+}
+
+/// Parse a batch summarization response (non-empty summaries)
+pub(super) fn parse_summaries(response: &str, ids: &[String]) -> BatchParse<String> {
+    parse_batch(response, ids, "summary", |v| {
+        v.as_str().map(str::trim).filter(|s| !s.is_empty()).map(str::to_string)
+    })
+}
+
+/// Parse a batch scoring response (scores 0-100, returned as 0.0-1.0)
+pub(super) fn parse_scores(response: &str, ids: &[String]) -> BatchParse<f64> {
+    parse_batch(response, ids, "score", |v| {
+        let score = v.as_f64().or_else(|| v.as_str()?.trim().parse().ok())?;
+        (0.0..=100.0).contains(&score).then_some(score / 100.0)
+    })
+}
+
+/// Parse a style classification, checking each value is an allowed one
+pub(super) fn parse_style(response: &str) -> std::result::Result<ArticleStyleResult, String> {
+    let json = extract_json(response).ok_or("the response is not a JSON object")?;
+    let style: ArticleStyleResult = serde_json::from_str(json).map_err(|e| e.to_string())?;
+    for (field, value, allowed) in [
+        ("style_type", &style.style_type, STYLE_TYPES),
+        ("tone", &style.tone, TONES),
+        ("length_category", &style.length_category, LENGTH_CATEGORIES),
+    ] {
+        if !allowed.contains(&value.as_str()) {
+            return Err(format!("\"{}\" is not a valid {} ({})", value, field, allowed.join("|")));
+        }
+    }
+    Ok(style)
+}
+
+/// The original prompt followed by what was wrong with the previous answer
+fn repair_prompt(prompt: &str, response: &str, problems: &[String]) -> String {
+    let quoted = match response.char_indices().nth(MAX_QUOTED_RESPONSE_CHARS) {
+        Some((idx, _)) => &response[..idx],
+        None => response,
+    };
+    format!(
+        "{prompt}\n\nYour previous response could not be used:\n- {}\n\nPrevious response:\n{quoted}\n\n\
+Respond again, following the required JSON format exactly.",
+        problems.join("\n- ")
+    )
+}
+
+/// Send a batch prompt through `ask` and parse the response. If it is
+/// invalid or incomplete, ask once more with the problems spelled out and
+/// merge both answers; articles still missing are left to the caller.
+pub(super) async fn ask_batch<T, F, Fut>(
+    prompt: String,
+    ids: &[String],
+    parse: fn(&str, &[String]) -> BatchParse<T>,
+    mut ask: F,
+) -> Result<HashMap<String, T>>
+where
+    F: FnMut(String) -> Fut,
+    Fut: Future<Output = Result<String>>,
+{
+    let response = ask(prompt.clone()).await?;
+    let BatchParse { mut values, problems } = parse(&response, ids);
+    if problems.is_empty() {
+        return Ok(values);
+    }
+
+    tracing::debug!("Invalid batch response ({}), asking again", problems.join("; "));
+    match ask(repair_prompt(&prompt, &response, &problems)).await {
+        Ok(repaired) => {
+            let retry = parse(&repaired, ids);
+            if !retry.problems.is_empty() {
+                tracing::warn!("Batch response still invalid after re-ask: {}", retry.problems.join("; "));
+            }
+            values.extend(retry.values);
+        }
+        Err(e) if values.is_empty() => return Err(e),
+        Err(e) => tracing::warn!("Re-asking for an invalid batch response failed: {}", e),
+    }
+    Ok(values)
+}
+
+/// Send a classification prompt through `ask`, re-asking once if the
+/// response doesn't validate. Falls back to the default style.
+pub(super) async fn ask_style<F, Fut>(prompt: String, mut ask: F) -> Result<ArticleStyleResult>
+where
+    F: FnMut(String) -> Fut,
+    Fut: Future<Output = Result<String>>,
+{
+    let response = ask(prompt.clone()).await?;
+    let problem = match parse_style(&response) {
+        Ok(style) => return Ok(style),
+        Err(problem) => problem,
+    };
+
+    tracing::debug!("Invalid style classification ({}), asking again", problem);
+    let repaired = ask(repair_prompt(&prompt, &response, &[problem])).await?;
+    Ok(parse_style(&repaired).unwrap_or_else(|problem| {
+        tracing::warn!("Style classification still invalid after re-ask ({}), using default", problem);
+        ArticleStyleResult::default()
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_batch_responses() {
+        let response = "```json\n{\"results\": [{\"id\": \"a\", \"summary\": \" First. \"}, \
+                        {\"id\": \"b\", \"summary\": \"\"}, {\"id\": \"x\", \"summary\": \"Unknown\"}]}\n```";
+        let parsed = parse_summaries(response, &ids(&["a", "b", "c"]));
+        assert_eq!(parsed.values.len(), 1);
+        assert_eq!(parsed.values["a"], "First.");
+        assert_eq!(
+            parsed.problems,
+            ["the entry for b has no valid \"summary\"", "no entries for c"]
+        );
+
+        let response = r#"{"results": [{"id": "a", "score": 85}, {"id": "b", "score": "40"}, {"id": "c", "score": 250}]}"#;
+        let parsed = parse_scores(response, &ids(&["a", "b", "c"]));
+        assert_eq!(parsed.values["a"], 0.85);
+        assert_eq!(parsed.values["b"], 0.4);
+        assert!(!parsed.values.contains_key("c"));
+
+        let parsed = parse_scores("[a]: 85", &ids(&["a"]));
+        assert!(parsed.values.is_empty());
+        assert_eq!(parsed.problems.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_style() {
+        let style = parse_style(r#"{"style_type": "tutorial", "tone": "casual", "length_category": "long"}"#).unwrap();
+        assert_eq!(style.style_type, "tutorial");
+        assert!(parse_style(r#"{"style_type": "poem", "tone": "casual", "length_category": "long"}"#).is_err());
+        assert!(parse_style("tutorial, casual, long").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_ask_batch_re_asks_once() {
+        let mut prompts = Vec::new();
+        let mut responses = vec![
+            r#"{"results": [{"id": "a", "summary": "Fixed."}, {"id": "b", "summary": "Second."}]}"#,
+            r#"{"results": [{"id": "a", "summary": "First."}]}"#,
+        ];
+        let values = ask_batch("Summarize".to_string(), &ids(&["a", "b"]), parse_summaries, |prompt| {
+            prompts.push(prompt);
+            let response = responses.pop().unwrap().to_string();
+            async move { Ok(response) }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(values["a"], "Fixed.");
+        assert_eq!(values["b"], "Second.");
+        assert_eq!(prompts.len(), 2);
+        assert!(prompts[1].starts_with("Summarize"));
+        assert!(prompts[1].contains("no entries for b"));
+
+        // A valid first response is used as is
+        let values = ask_batch("Score".to_string(), &ids(&["a"]), parse_scores, |_| async {
+            Ok(r#"{"results": [{"id": "a", "score": 10}]}"#.to_string())
+        })
+        .await
+        .unwrap();
+        assert_eq!(values["a"], 0.1);
+    }
+}
@@ -0,0 +1,193 @@
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::config::AiConfig;
+use crate::{Error, Result};
+
+/// Retries transient provider failures with exponential backoff and stops
+/// calling a provider for a while after repeated failures (circuit breaker),
+/// so an outage costs one error per cooldown instead of one per article.
+pub struct RetryGuard {
+    max_retries: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    /// Consecutive failed calls that open the circuit (0 = never)
+    threshold: u32,
+    cooldown: Duration,
+    state: Mutex<BreakerState>,
+}
+
+#[derive(Debug, Default)]
+struct BreakerState {
+    failures: u32,
+    open_until: Option<Instant>,
+}
+
+impl RetryGuard {
+    pub fn new(config: &AiConfig) -> Self {
+        Self {
+            max_retries: config.max_retries,
+            base_delay: Duration::from_millis(config.retry_base_delay_ms),
+            max_delay: Duration::from_secs(config.retry_max_delay_secs),
+            threshold: config.circuit_breaker_threshold,
+            cooldown: Duration::from_secs(config.circuit_breaker_cooldown_secs),
+            state: Mutex::new(BreakerState::default()),
+        }
+    }
+
+    /// Whether calls are currently short-circuited
+    pub fn is_open(&self) -> bool {
+        let state = self.state.lock().unwrap();
+        state.open_until.is_some_and(|until| Instant::now() < until)
+    }
+
+    /// Run `op`, retrying transient errors. Fails fast with
+    /// `Error::AiUnavailable` while the circuit is open.
+    pub async fn call<T, F, Fut>(&self, mut op: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        if self.is_open() {
+            return Err(Error::AiUnavailable(format!(
+                "too many consecutive failures, paused for up to {}s",
+                self.cooldown.as_secs()
+            )));
+        }
+
+        let mut attempt = 0;
+        loop {
+            match op().await {
+                Ok(value) => {
+                    self.record_success();
+                    return Ok(value);
+                }
+                Err(Error::AiTransient { message, retry_after }) if attempt < self.max_retries => {
+                    let delay = self.backoff(attempt, retry_after);
+                    attempt += 1;
+                    tracing::debug!(
+                        "AI request failed ({}), retry {}/{} in {:?}",
+                        message,
+                        attempt,
+                        self.max_retries,
+                        delay
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                Err(e) => {
+                    if matches!(e, Error::AiTransient { .. }) {
+                        self.record_failure();
+                    }
+                    return Err(e);
+                }
+            }
+        }
+    }
+
+    /// Delay before retry `attempt` (0-based): the provider's Retry-After if
+    /// given, otherwise base * 2^attempt, capped at the max delay
+    fn backoff(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        retry_after
+            .unwrap_or_else(|| self.base_delay.saturating_mul(1 << attempt.min(16)))
+            .min(self.max_delay)
+    }
+
+    fn record_success(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.failures = 0;
+        state.open_until = None;
+    }
+
+    fn record_failure(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.failures += 1;
+        if self.threshold > 0 && state.failures >= self.threshold {
+            // Another failure after the cooldown (half-open trial) reopens it
+            state.open_until = Some(Instant::now() + self.cooldown);
+            tracing::warn!(
+                "AI provider failed {} times in a row, pausing AI requests for {}s",
+                state.failures,
+                self.cooldown.as_secs()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn retry_guard(max_retries: u32, threshold: u32, cooldown_secs: u64) -> RetryGuard {
+        RetryGuard::new(&AiConfig {
+            max_retries,
+            retry_base_delay_ms: 1,
+            retry_max_delay_secs: 1,
+            circuit_breaker_threshold: threshold,
+            circuit_breaker_cooldown_secs: cooldown_secs,
+            ..AiConfig::default()
+        })
+    }
+
+    fn transient() -> Error {
+        Error::AiTransient { message: "429".to_string(), retry_after: None }
+    }
+
+    #[test]
+    fn test_backoff_delays() {
+        let guard = RetryGuard::new(&AiConfig::default());
+        assert_eq!(guard.backoff(0, None), Duration::from_secs(1));
+        assert_eq!(guard.backoff(2, None), Duration::from_secs(4));
+        assert_eq!(guard.backoff(10, None), Duration::from_secs(60));
+        assert_eq!(guard.backoff(0, Some(Duration::from_secs(7))), Duration::from_secs(7));
+        assert_eq!(guard.backoff(0, Some(Duration::from_secs(3600))), Duration::from_secs(60));
+    }
+
+    #[tokio::test]
+    async fn test_retry_transient_errors() {
+        let guard = retry_guard(2, 0, 60);
+        let calls = AtomicU32::new(0);
+        let result = guard
+            .call(|| async {
+                if calls.fetch_add(1, Ordering::SeqCst) < 2 {
+                    Err(transient())
+                } else {
+                    Ok("done")
+                }
+            })
+            .await;
+        assert_eq!(result.unwrap(), "done");
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        // Permanent errors are not retried
+        calls.store(0, Ordering::SeqCst);
+        let result: Result<()> = guard
+            .call(|| async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(Error::AiProvider("bad request".to_string()))
+            })
+            .await;
+        assert!(matches!(result, Err(Error::AiProvider(_))));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker() {
+        let guard = retry_guard(0, 2, 60);
+        for _ in 0..2 {
+            let result: Result<()> = guard.call(|| async { Err(transient()) }).await;
+            assert!(matches!(result, Err(Error::AiTransient { .. })));
+        }
+        assert!(guard.is_open());
+        let result = guard.call(|| async { Ok(()) }).await;
+        assert!(matches!(result, Err(Error::AiUnavailable(_))));
+
+        // After the cooldown a successful call closes the circuit
+        let guard = retry_guard(0, 1, 0);
+        guard.record_failure();
+        assert!(!guard.is_open());
+        assert!(guard.call(|| async { Ok(()) }).await.is_ok());
+        assert_eq!(guard.state.lock().unwrap().failures, 0);
+    }
+}
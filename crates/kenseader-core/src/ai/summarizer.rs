@@ -1,9 +1,9 @@
 use std::sync::Arc;
 
-use tokio::sync::Semaphore;
-
+use super::pool::{AiPool, AiQueueStats, AiTask};
+use super::retry::RetryGuard;
 use super::providers::{
-    AiProvider, ClaudeApiProvider, ClaudeCliProvider, CliProvider, CliType,
+    AiProvider, AzureAuth, ClaudeApiProvider, ClaudeCliProvider, CliProvider, CliType,
     GeminiApiProvider, OpenAiProvider,
 };
 pub use super::providers::{ArticleForScoring, ArticleForSummary, ArticleStyleResult, BatchScoreResult, BatchSummaryResult};
@@ -11,12 +11,14 @@ use crate::config::AppConfig;
 use crate::Result;
 
 /// AI Summarizer that wraps the configured provider
-/// Uses a semaphore to limit concurrent AI operations and prevent file descriptor exhaustion
+/// Uses a bounded pool to limit concurrent AI operations and prevent file descriptor exhaustion
 pub struct Summarizer {
     provider: Arc<dyn AiProvider>,
     concurrency: usize,
-    /// Semaphore to limit concurrent AI operations
-    semaphore: Arc<Semaphore>,
+    /// Slots for concurrent AI operations, shared fairly between task kinds
+    pool: AiPool,
+    /// Retry with backoff and circuit breaker around provider calls
+    guard: RetryGuard,
 }
 
 impl Summarizer {
@@ -25,7 +27,7 @@ impl Summarizer {
         let language = &config.ai.summary_language;
         let summary_max_tokens = config.ai.max_summary_tokens.max(1);
         let summary_max_length = config.ai.max_summary_length;
-        let concurrency = config.ai.concurrency.max(1);
+        let concurrency = config.ai.max_concurrent_requests.max(1);
 
         let provider: Arc<dyn AiProvider> = match config.ai.provider.as_str() {
             // API-based providers
@@ -34,6 +36,27 @@ impl Summarizer {
                     .ok_or_else(|| crate::Error::Config("OpenAI API key not configured".to_string()))?;
                 Arc::new(OpenAiProvider::new(api_key, &config.ai.openai_model, language, summary_max_tokens))
             }
+            "azure_openai" => {
+                let ai = &config.ai;
+                let endpoint = ai.azure_openai_endpoint.as_ref()
+                    .ok_or_else(|| crate::Error::Config("Azure OpenAI endpoint not configured".to_string()))?;
+                let deployment = ai.azure_openai_deployment.as_ref()
+                    .ok_or_else(|| crate::Error::Config("Azure OpenAI deployment not configured".to_string()))?;
+                let auth = match (&ai.azure_openai_api_key, &ai.azure_tenant_id, &ai.azure_client_id, &ai.azure_client_secret) {
+                    (Some(key), _, _, _) => AzureAuth::ApiKey(key.clone()),
+                    (None, Some(tenant_id), Some(client_id), Some(client_secret)) => AzureAuth::ServicePrincipal {
+                        tenant_id: tenant_id.clone(),
+                        client_id: client_id.clone(),
+                        client_secret: client_secret.clone(),
+                    },
+                    _ => {
+                        return Err(crate::Error::Config(
+                            "Azure OpenAI needs an API key or an Azure AD tenant, client ID and client secret".to_string(),
+                        ))
+                    }
+                };
+                Arc::new(OpenAiProvider::azure(endpoint, deployment, &ai.azure_openai_api_version, auth, language, summary_max_tokens))
+            }
             "gemini_api" => {
                 let api_key = config.ai.gemini_api_key.as_ref()
                     .ok_or_else(|| crate::Error::Config("Gemini API key not configured".to_string()))?;
@@ -56,40 +79,36 @@ impl Summarizer {
             }
         };
 
-        // Create semaphore to limit concurrent AI operations
+        // Limit concurrent AI operations
         // This prevents file descriptor exhaustion from too many CLI processes
-        let semaphore = Arc::new(Semaphore::new(concurrency));
+        let pool = AiPool::new(concurrency);
 
-        Ok(Self { provider, concurrency, semaphore })
+        Ok(Self { provider, concurrency, pool, guard: RetryGuard::new(&config.ai) })
     }
 
     /// Generate a summary for article content
     pub async fn summarize(&self, content: &str) -> Result<String> {
-        // Acquire semaphore permit to limit concurrent operations
-        let _permit = self.semaphore.acquire().await
-            .map_err(|_| crate::Error::Other("Semaphore closed".to_string()))?;
-        self.provider.summarize(content).await
+        // Wait for a pool slot to limit concurrent operations
+        let _permit = self.pool.acquire(AiTask::Summarize).await;
+        self.guard.call(|| self.provider.summarize(content)).await
     }
 
     /// Extract tags from article content
     pub async fn extract_tags(&self, content: &str) -> Result<Vec<String>> {
-        let _permit = self.semaphore.acquire().await
-            .map_err(|_| crate::Error::Other("Semaphore closed".to_string()))?;
-        self.provider.extract_tags(content).await
+        let _permit = self.pool.acquire(AiTask::Summarize).await;
+        self.guard.call(|| self.provider.extract_tags(content)).await
     }
 
     /// Score article relevance to user interests
     pub async fn score_relevance(&self, content: &str, interests: &[String]) -> Result<f64> {
-        let _permit = self.semaphore.acquire().await
-            .map_err(|_| crate::Error::Other("Semaphore closed".to_string()))?;
-        self.provider.score_relevance(content, interests).await
+        let _permit = self.pool.acquire(AiTask::Score).await;
+        self.guard.call(|| self.provider.score_relevance(content, interests)).await
     }
 
     /// Batch summarize multiple articles in one API call
     pub async fn batch_summarize(&self, articles: Vec<ArticleForSummary>) -> Result<Vec<BatchSummaryResult>> {
-        let _permit = self.semaphore.acquire().await
-            .map_err(|_| crate::Error::Other("Semaphore closed".to_string()))?;
-        self.provider.batch_summarize(articles).await
+        let _permit = self.pool.acquire(AiTask::Summarize).await;
+        self.guard.call(|| self.provider.batch_summarize(articles.clone())).await
     }
 
     /// Batch score multiple articles for relevance in one API call
@@ -98,9 +117,10 @@ impl Summarizer {
         articles: Vec<ArticleForScoring>,
         interests: &[String],
     ) -> Result<Vec<BatchScoreResult>> {
-        let _permit = self.semaphore.acquire().await
-            .map_err(|_| crate::Error::Other("Semaphore closed".to_string()))?;
-        self.provider.batch_score_relevance(articles, interests).await
+        let _permit = self.pool.acquire(AiTask::Score).await;
+        self.guard
+            .call(|| self.provider.batch_score_relevance(articles.clone(), interests))
+            .await
     }
 
     /// Get the character limit for batch processing
@@ -113,15 +133,24 @@ impl Summarizer {
         self.provider.min_content_length()
     }
 
-    /// Get max concurrent summarization tasks
+    /// Whether AI requests are paused after repeated provider failures
+    pub fn is_paused(&self) -> bool {
+        self.guard.is_open()
+    }
+
+    /// Queue metrics of the AI request pool per task kind
+    pub fn queue_stats(&self) -> Vec<AiQueueStats> {
+        self.pool.stats()
+    }
+
+    /// Get max concurrent AI requests
     pub fn concurrency(&self) -> usize {
         self.concurrency
     }
 
     /// Classify article style, tone, and length category
     pub async fn classify_style(&self, content: &str) -> Result<ArticleStyleResult> {
-        let _permit = self.semaphore.acquire().await
-            .map_err(|_| crate::Error::Other("Semaphore closed".to_string()))?;
-        self.provider.classify_style(content).await
+        let _permit = self.pool.acquire(AiTask::Classify).await;
+        self.guard.call(|| self.provider.classify_style(content)).await
     }
 }
@@ -0,0 +1,130 @@
+//! Keyword alerts
+//!
+//! New articles are checked against the `[alerts]` watchlist as they are
+//! stored, without waiting for summaries or relevance scoring. A match is
+//! recorded for the Alerts view and announced right away with a desktop
+//! notification and, if enabled, on every push channel.
+
+use std::time::Duration;
+
+use reqwest::Client;
+use tracing::{info, warn};
+
+use crate::config::AppConfig;
+use crate::feed::{Article, Feed};
+use crate::notify::{self, Notification};
+use crate::storage::ArticleRepository;
+use crate::Result;
+
+/// Watchlist keyword the article's title or content contains
+/// (case-insensitive), if any
+pub fn matching_keyword<'a>(keywords: &'a [String], article: &Article) -> Option<&'a str> {
+    let text = format!("{} {}", article.title, article.content_text.as_deref().unwrap_or("")).to_lowercase();
+    keywords
+        .iter()
+        .map(|keyword| keyword.trim())
+        .find(|keyword| !keyword.is_empty() && text.contains(&keyword.to_lowercase()))
+}
+
+/// Notification announcing an alert
+pub fn alert_notification(keyword: &str, feed_name: &str, article: &Article) -> Notification {
+    Notification {
+        title: format!("Alert: {}", keyword),
+        body: format!("{}: {}", feed_name, article.title),
+    }
+}
+
+/// Check a feed's newly stored articles against the watchlist, recording
+/// and announcing the ones that match. Returns the number of alerts.
+pub async fn check_new_articles(
+    article_repo: &ArticleRepository<'_>,
+    config: &AppConfig,
+    feed: &Feed,
+    articles: &[Article],
+) -> Result<u32> {
+    let settings = &config.alerts;
+    if settings.keywords.is_empty() {
+        return Ok(0);
+    }
+
+    let mut alerts = 0;
+    for article in articles {
+        let Some(keyword) = matching_keyword(&settings.keywords, article) else {
+            continue;
+        };
+        article_repo.record_alert(article.id, keyword).await?;
+        alerts += 1;
+        info!("Alert for '{}' in {}: {}", keyword, feed.local_name, article.title);
+
+        if settings.notify {
+            let notification = alert_notification(keyword, &feed.local_name, article);
+            if let Err(e) = notify::send(config, &notification).await {
+                warn!("Failed to show alert notification: {}", e);
+            }
+        }
+        if settings.push {
+            push(config, &feed.local_name, article).await?;
+        }
+    }
+    Ok(alerts)
+}
+
+/// Send an alert to every enabled push channel
+async fn push(config: &AppConfig, feed_name: &str, article: &Article) -> Result<()> {
+    let client = Client::builder()
+        .timeout(Duration::from_secs(config.sync.request_timeout_secs))
+        .build()?;
+    for channel in config.notifications.push.iter().filter(|channel| channel.enabled) {
+        if let Err(e) = notify::push(&client, channel, feed_name, article).await {
+            warn!("Failed to push alert for '{}': {}", article.title, e);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feed::NewArticle;
+    use crate::storage::test_db_with_feed;
+
+    fn new_article(feed_id: uuid::Uuid, guid: &str, title: &str, text: &str) -> NewArticle {
+        NewArticle {
+            url: Some(format!("https://example.com/{}", guid)),
+            title: title.to_string(),
+            content: Some(text.to_string()),
+            content_text: Some(text.to_string()),
+            ..NewArticle::for_test(feed_id, guid)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_new_articles_matching_keywords_raise_alerts() {
+        let (db, feed) = test_db_with_feed().await;
+        let repo = ArticleRepository::new(&db);
+        let created = repo
+            .create_many(&[
+                new_article(feed.id, "1", "Patch now", "Fixes cve-2024-3094 in xz"),
+                new_article(feed.id, "2", "Acme Corp hires", "Nothing to see"),
+                new_article(feed.id, "3", "Weather", "Sunny"),
+            ])
+            .await
+            .unwrap();
+
+        let mut config = AppConfig::default();
+        config.alerts.keywords = vec![" CVE-2024-3094".to_string(), "acme corp".to_string(), " ".to_string()];
+        config.alerts.notify = false;
+        assert_eq!(matching_keyword(&config.alerts.keywords, &created[0]), Some("CVE-2024-3094"));
+        assert_eq!(matching_keyword(&config.alerts.keywords, &created[2]), None);
+
+        assert_eq!(check_new_articles(&repo, &config, &feed, &created).await.unwrap(), 2);
+        let alerted = repo.list_alerted(10).await.unwrap();
+        let mut keywords: Vec<_> = alerted.iter().map(|a| a.alert.keyword.as_str()).collect();
+        keywords.sort();
+        assert_eq!(keywords, ["CVE-2024-3094", "acme corp"]);
+
+        let notification = alert_notification("acme corp", &feed.local_name, &created[1]);
+        assert_eq!(notification.title, "Alert: acme corp");
+        assert_eq!(notification.body, "example: Acme Corp hires");
+    }
+}
@@ -0,0 +1,446 @@
+//! Daily audio briefing
+//!
+//! The daemon reads the most relevant new articles (their titles and AI
+//! summaries) into an audio file with a text-to-speech command, and can serve
+//! the briefings to a podcast app as a small local RSS feed.
+
+use std::collections::HashMap;
+use std::io::SeekFrom;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+use chrono::{DateTime, Duration, Local, NaiveDate, Utc};
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::watch;
+use tracing::{debug, info, warn};
+
+use crate::config::{AppConfig, AudioFormat};
+use crate::feed::Article;
+use crate::http::{accept_failed, escape as xml_escape, read_request_head, respond, write_head};
+use crate::shell;
+use crate::storage::{ArticleRepository, Database, FeedRepository};
+use crate::{Error, Result};
+
+/// File names are `kenseader-briefing-YYYY-MM-DD.<ext>`, with the spoken text
+/// next to them as `.txt`
+const FILE_PREFIX: &str = "kenseader-briefing-";
+
+/// TTS command used when `audio_digest.command` is not set
+const DEFAULT_COMMAND: &str = "espeak-ng --stdout | ffmpeg -y -loglevel error -i - {output}";
+const DEFAULT_COMMAND_MACOS: &str =
+    "say -o {output}.aiff && ffmpeg -y -loglevel error -i {output}.aiff {output} && rm {output}.aiff";
+
+/// Hours of new articles covered by a briefing
+const BRIEFING_PERIOD_HOURS: i64 = 24;
+
+/// Words of the article text read when it has no summary
+const FALLBACK_WORDS: usize = 60;
+
+/// One article of a briefing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BriefingItem {
+    pub feed: String,
+    pub title: String,
+    pub text: String,
+}
+
+/// The `max` articles to read: most relevant first, then newest
+pub fn pick_articles(mut articles: Vec<Article>, max: usize) -> Vec<Article> {
+    articles.sort_by(|a, b| {
+        let score = |article: &Article| article.relevance_score.unwrap_or(-1.0);
+        score(b)
+            .total_cmp(&score(a))
+            .then_with(|| b.published_at.unwrap_or(b.created_at).cmp(&a.published_at.unwrap_or(a.created_at)))
+    });
+    articles.truncate(max);
+    articles
+}
+
+/// What is read for an article: its summary, or the start of its text
+fn article_text(article: &Article) -> String {
+    if let Some(summary) = article.summary.as_deref().filter(|s| !s.trim().is_empty()) {
+        return summary.trim().to_string();
+    }
+    let words: Vec<&str> = article
+        .content_text
+        .as_deref()
+        .unwrap_or("")
+        .split_whitespace()
+        .take(FALLBACK_WORDS)
+        .collect();
+    words.join(" ")
+}
+
+/// Text of the briefing for `date`
+pub fn briefing_script(date: NaiveDate, items: &[BriefingItem]) -> String {
+    let count = match items.len() {
+        1 => "One new article".to_string(),
+        n => format!("{} new articles", n),
+    };
+    let mut script = format!(
+        "Your kenseader briefing for {}. {}.\n\n",
+        date.format("%A, %B %-d"),
+        count
+    );
+    for (i, item) in items.iter().enumerate() {
+        script.push_str(&format!("{}. From {}: {}.\n", i + 1, item.feed, item.title.trim_end_matches('.')));
+        if !item.text.is_empty() {
+            script.push_str(&item.text);
+            script.push('\n');
+        }
+        script.push('\n');
+    }
+    script.push_str("That's all for today.\n");
+    script
+}
+
+/// Fill the `{output}` placeholder of a TTS command
+fn expand_command(template: &str, output: &Path) -> String {
+    shell::expand(template, &[("output", &output.to_string_lossy())])
+}
+
+/// Write today's briefing of the articles fetched in the last day. Returns
+/// the audio file, or None when there was nothing new.
+pub async fn generate(db: &Database, config: &AppConfig) -> Result<Option<PathBuf>> {
+    let settings = &config.audio_digest;
+    let since = Utc::now() - Duration::hours(BRIEFING_PERIOD_HOURS);
+    let articles = ArticleRepository::new(db).list_unread_created_since(since).await?;
+    let articles = pick_articles(articles, settings.max_articles.max(1));
+    if articles.is_empty() {
+        return Ok(None);
+    }
+
+    let feed_names: HashMap<_, _> = FeedRepository::new(db)
+        .list_all()
+        .await?
+        .into_iter()
+        .map(|f| (f.id, f.local_name))
+        .collect();
+    let items: Vec<BriefingItem> = articles
+        .iter()
+        .map(|article| BriefingItem {
+            feed: feed_names.get(&article.feed_id).cloned().unwrap_or_default(),
+            title: article.title.clone(),
+            text: article_text(article),
+        })
+        .collect();
+
+    let date = Local::now().date_naive();
+    let script = briefing_script(date, &items);
+    let dir = config.audio_digest_dir();
+    tokio::fs::create_dir_all(&dir).await?;
+    let stem = format!("{}{}", FILE_PREFIX, date);
+    let output = dir.join(format!("{}.{}", stem, settings.format.extension()));
+    tokio::fs::write(dir.join(format!("{}.txt", stem)), &script).await?;
+
+    let default_command = if cfg!(target_os = "macos") { DEFAULT_COMMAND_MACOS } else { DEFAULT_COMMAND };
+    let command = expand_command(settings.command.as_deref().unwrap_or(default_command), &output);
+    debug!("Generating audio briefing: {}", command);
+    let mut child = tokio::process::Command::new("sh")
+        .arg("-c")
+        .arg(&command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(script.as_bytes()).await?;
+    }
+    let result = child.wait_with_output().await?;
+    if !result.status.success() {
+        return Err(Error::Other(format!(
+            "Audio briefing command exited with {}: {}",
+            result.status,
+            String::from_utf8_lossy(&result.stderr).trim()
+        )));
+    }
+    if tokio::fs::metadata(&output).await.map(|m| m.len()).unwrap_or(0) == 0 {
+        return Err(Error::Other(format!("Audio briefing command wrote no audio to {}", output.display())));
+    }
+
+    prune(&dir, settings.keep.max(1))?;
+    info!("Audio briefing written to {} ({} articles)", output.display(), items.len());
+    Ok(Some(output))
+}
+
+/// A briefing in the folder
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Episode {
+    pub file_name: String,
+    pub date: NaiveDate,
+    pub format: AudioFormat,
+    pub length: u64,
+    pub modified: DateTime<Utc>,
+}
+
+/// Date and format of a briefing file name
+fn parse_file_name(name: &str) -> Option<(NaiveDate, AudioFormat)> {
+    let (stem, extension) = name.strip_prefix(FILE_PREFIX)?.rsplit_once('.')?;
+    let format = [AudioFormat::Mp3, AudioFormat::Ogg]
+        .into_iter()
+        .find(|format| format.extension() == extension)?;
+    Some((NaiveDate::parse_from_str(stem, "%Y-%m-%d").ok()?, format))
+}
+
+/// Briefings in `dir`, newest first
+pub fn episodes(dir: &Path) -> std::io::Result<Vec<Episode>> {
+    let mut episodes = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_name = entry.file_name().to_string_lossy().into_owned();
+        let Some((date, format)) = parse_file_name(&file_name) else {
+            continue;
+        };
+        let metadata = entry.metadata()?;
+        episodes.push(Episode {
+            file_name,
+            date,
+            format,
+            length: metadata.len(),
+            modified: metadata.modified().map(DateTime::from).unwrap_or_else(|_| Utc::now()),
+        });
+    }
+    episodes.sort_by(|a, b| b.date.cmp(&a.date).then_with(|| a.file_name.cmp(&b.file_name)));
+    Ok(episodes)
+}
+
+/// Delete all but the newest `keep` briefings (and their transcripts)
+fn prune(dir: &Path, keep: usize) -> std::io::Result<()> {
+    for episode in episodes(dir)?.into_iter().skip(keep) {
+        debug!("Deleting old audio briefing {}", episode.file_name);
+        std::fs::remove_file(dir.join(&episode.file_name))?;
+        let _ = std::fs::remove_file(dir.join(format!("{}{}.txt", FILE_PREFIX, episode.date)));
+    }
+    Ok(())
+}
+
+/// RSS feed of the briefings, with enclosures under `base_url`
+pub fn podcast_feed(episodes: &[Episode], transcripts: &HashMap<NaiveDate, String>, base_url: &str) -> String {
+    // The base URL comes from the client's Host header
+    let base_url = xml_escape(base_url);
+    let mut xml = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <rss version=\"2.0\">\n<channel>\n\
+         <title>Kenseader Briefing</title>\n\
+         <link>{base}/feed.xml</link>\n\
+         <description>Daily audio briefing of your feeds</description>\n",
+        base = base_url
+    );
+    for episode in episodes {
+        let description = transcripts.get(&episode.date).map(String::as_str).unwrap_or("");
+        xml.push_str(&format!(
+            "<item>\n\
+             <title>Briefing for {title}</title>\n\
+             <description>{description}</description>\n\
+             <enclosure url=\"{base}/{file}\" length=\"{length}\" type=\"{mime}\"/>\n\
+             <guid isPermaLink=\"false\">{file}</guid>\n\
+             <pubDate>{date}</pubDate>\n\
+             </item>\n",
+            title = episode.date.format("%A, %B %-d, %Y"),
+            description = xml_escape(description),
+            base = base_url,
+            file = xml_escape(&episode.file_name),
+            length = episode.length,
+            mime = episode.format.mime_type(),
+            date = episode.modified.to_rfc2822(),
+        ));
+    }
+    xml.push_str("</channel>\n</rss>\n");
+    xml
+}
+
+/// Byte range of a `Range: bytes=start-end` header for a file of `length` bytes
+fn parse_range(header: &str, length: u64) -> Option<(u64, u64)> {
+    let (start, end) = header.trim().strip_prefix("bytes=")?.split_once('-')?;
+    let (start, end) = match (start.trim(), end.trim()) {
+        // "bytes=-500": the last 500 bytes
+        ("", suffix) => (length.saturating_sub(suffix.parse().ok()?), length.checked_sub(1)?),
+        (start, "") => (start.parse().ok()?, length.checked_sub(1)?),
+        (start, end) => (start.parse().ok()?, end.parse::<u64>().ok()?.min(length.checked_sub(1)?)),
+    };
+    (start <= end).then_some((start, end))
+}
+
+/// Serve the podcast feed (`/feed.xml`) and the briefings in the configured
+/// folder over HTTP until shutdown
+pub async fn serve(address: String, dir: PathBuf, mut shutdown: watch::Receiver<bool>) -> Result<()> {
+    let listener = TcpListener::bind(&address).await?;
+    info!("Serving audio briefings at http://{}/feed.xml", address);
+    loop {
+        tokio::select! {
+            result = shutdown.changed() => {
+                if result.is_err() || *shutdown.borrow() {
+                    return Ok(());
+                }
+            }
+            accepted = listener.accept() => {
+                let stream = match accepted {
+                    Ok((stream, _)) => stream,
+                    Err(e) => {
+                        accept_failed("Podcast server", e).await;
+                        continue;
+                    }
+                };
+                let (dir, address) = (dir.clone(), address.clone());
+                tokio::spawn(async move {
+                    if let Err(e) = handle_request(stream, &dir, &address).await {
+                        debug!("Podcast request failed: {}", e);
+                    }
+                });
+            }
+        }
+    }
+}
+
+async fn handle_request(mut stream: TcpStream, dir: &Path, address: &str) -> std::io::Result<()> {
+    let Some(head) = read_request_head(&mut stream).await? else {
+        return Ok(());
+    };
+    let mut lines = head.lines();
+    let mut request = lines.next().unwrap_or("").split_whitespace();
+    let (method, path) = (request.next().unwrap_or(""), request.next().unwrap_or("/"));
+    let header = |name: &str| {
+        head.lines()
+            .skip(1)
+            .filter_map(|line| line.split_once(':'))
+            .find(|(key, _)| key.trim().eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.trim().to_string())
+    };
+
+    if method != "GET" && method != "HEAD" {
+        return respond(&mut stream, "405 Method Not Allowed", "text/plain", &[], b"").await;
+    }
+    let body_wanted = method == "GET";
+
+    if path == "/" || path == "/feed.xml" {
+        // Links use the host the client connected to, so other devices work too
+        let base_url = format!("http://{}", header("host").unwrap_or_else(|| address.to_string()));
+        let episodes = episodes(dir).unwrap_or_default();
+        let transcripts = episodes
+            .iter()
+            .filter_map(|e| {
+                let text = std::fs::read_to_string(dir.join(format!("{}{}.txt", FILE_PREFIX, e.date))).ok()?;
+                Some((e.date, text))
+            })
+            .collect();
+        let feed = podcast_feed(&episodes, &transcripts, &base_url);
+        let body = if body_wanted { feed.as_bytes() } else { b"" };
+        return respond(&mut stream, "200 OK", "application/rss+xml; charset=utf-8", &[], body).await;
+    }
+
+    let name = path.trim_start_matches('/');
+    let Some((_, format)) = parse_file_name(name).filter(|_| !name.contains(['/', '\\'])) else {
+        return respond(&mut stream, "404 Not Found", "text/plain", &[], b"Not found").await;
+    };
+    let Ok(mut file) = tokio::fs::File::open(dir.join(name)).await else {
+        return respond(&mut stream, "404 Not Found", "text/plain", &[], b"Not found").await;
+    };
+    let length = file.metadata().await?.len();
+    let (status, start, end) = match header("range").and_then(|range| parse_range(&range, length)) {
+        Some((start, end)) => ("206 Partial Content", start, end),
+        None => ("200 OK", 0, length.saturating_sub(1)),
+    };
+    let size = if length == 0 { 0 } else { end - start + 1 };
+    let mut headers = vec![format!("Content-Length: {}", size), "Accept-Ranges: bytes".to_string()];
+    if start > 0 || size < length {
+        headers.push(format!("Content-Range: bytes {}-{}/{}", start, end, length));
+    }
+    write_head(&mut stream, status, format.mime_type(), &headers).await?;
+    if body_wanted {
+        file.seek(SeekFrom::Start(start)).await?;
+        tokio::io::copy(&mut file.take(size), &mut stream).await?;
+    }
+    stream.shutdown().await
+}
+
+/// Start the podcast server when `audio_digest.serve_address` is set
+pub fn spawn_server(config: &AppConfig, shutdown: watch::Receiver<bool>) {
+    let Some(address) = config.audio_digest.serve_address.clone() else {
+        return;
+    };
+    let dir = config.audio_digest_dir();
+    tokio::spawn(async move {
+        if let Err(e) = serve(address, dir, shutdown).await {
+            warn!("Audio briefing server failed: {}", e);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn article(title: &str, score: Option<f64>, summary: Option<&str>) -> Article {
+        Article {
+            content_text: Some("Body text of the article".to_string()),
+            summary: summary.map(str::to_string),
+            relevance_score: score,
+            ..Article::for_test(title)
+        }
+    }
+
+    #[test]
+    fn test_briefing_script() {
+        let articles = vec![
+            article("Unscored", None, None),
+            article("Top story.", Some(0.9), Some("A summary.")),
+            article("Middle", Some(0.5), None),
+        ];
+        let picked = pick_articles(articles, 2);
+        let titles: Vec<&str> = picked.iter().map(|a| a.title.as_str()).collect();
+        assert_eq!(titles, ["Top story.", "Middle"]);
+
+        let items: Vec<BriefingItem> = picked
+            .iter()
+            .map(|a| BriefingItem { feed: "Rust Blog".to_string(), title: a.title.clone(), text: article_text(a) })
+            .collect();
+        let date = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        assert_eq!(
+            briefing_script(date, &items),
+            "Your kenseader briefing for Friday, October 16. 2 new articles.\n\n\
+             1. From Rust Blog: Top story.\nA summary.\n\n\
+             2. From Rust Blog: Middle.\nBody text of the article\n\n\
+             That's all for today.\n"
+        );
+    }
+
+    #[test]
+    fn test_podcast_feed_and_files() {
+        assert_eq!(
+            parse_file_name("kenseader-briefing-2026-10-16.ogg"),
+            Some((NaiveDate::from_ymd_opt(2026, 10, 16).unwrap(), AudioFormat::Ogg))
+        );
+        assert_eq!(parse_file_name("kenseader-briefing-2026-10-16.txt"), None);
+        assert_eq!(parse_file_name("other.mp3"), None);
+
+        let date = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        let episode = Episode {
+            file_name: "kenseader-briefing-2026-10-16.mp3".to_string(),
+            date,
+            format: AudioFormat::Mp3,
+            length: 1234,
+            modified: Utc::now(),
+        };
+        let transcripts = HashMap::from([(date, "Q&A <today>".to_string())]);
+        let feed = podcast_feed(std::slice::from_ref(&episode), &transcripts, "http://127.0.0.1:8765");
+        assert!(feed.contains(
+            "<enclosure url=\"http://127.0.0.1:8765/kenseader-briefing-2026-10-16.mp3\" length=\"1234\" type=\"audio/mpeg\"/>"
+        ));
+        assert!(feed.contains("<description>Q&amp;A &lt;today&gt;</description>"));
+
+        let feed = podcast_feed(&[episode], &transcripts, "http://a\"><x>");
+        assert!(!feed.contains("<x>"));
+        assert!(feed.contains("<link>http://a&quot;&gt;&lt;x&gt;/feed.xml</link>"));
+    }
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range("bytes=0-99", 1000), Some((0, 99)));
+        assert_eq!(parse_range("bytes=500-", 1000), Some((500, 999)));
+        assert_eq!(parse_range("bytes=-100", 1000), Some((900, 999)));
+        assert_eq!(parse_range("bytes=900-5000", 1000), Some((900, 999)));
+        assert_eq!(parse_range("bytes=1200-1300", 1000), None);
+        assert_eq!(parse_range("items=0-1", 1000), None);
+    }
+}
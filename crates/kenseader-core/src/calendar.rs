@@ -0,0 +1,166 @@
+//! Calendar (ICS) of upcoming digests
+//!
+//! Digests on a cron schedule (the notification digest and the audio
+//! briefing) run at known times, so they can be put in a calendar with a
+//! reminder. Times are written as floating local times, like the cron
+//! expressions they come from.
+
+use chrono::{DateTime, Duration, Local, NaiveDateTime, Utc};
+
+use crate::config::{AppConfig, NotificationMode};
+use crate::scheduler::CronSchedule;
+
+/// Days of digests included when none are asked for
+pub const DEFAULT_DAYS: u32 = 14;
+
+/// Most days of digests a calendar can cover
+pub const MAX_DAYS: u32 = 366;
+
+const TIME_FORMAT: &str = "%Y%m%dT%H%M%S";
+
+/// A digest due in the calendar's period
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DigestEvent {
+    /// Scheduler task making the digest
+    pub task: &'static str,
+    pub summary: &'static str,
+    /// Local time the digest is ready
+    pub start: NaiveDateTime,
+}
+
+/// Enabled digests with a valid cron schedule, as (task, event summary, schedule)
+fn scheduled_digests(config: &AppConfig) -> Vec<(&'static str, &'static str, CronSchedule)> {
+    let schedule = &config.sync.schedule;
+    let notifications = &config.notifications;
+    let digests = [
+        (
+            "digest",
+            "Kenseader news digest",
+            notifications.enabled && notifications.mode == NotificationMode::Digest,
+            &schedule.digest,
+        ),
+        (
+            "audio_digest",
+            "Kenseader audio briefing ready",
+            config.audio_digest.enabled,
+            &schedule.audio_digest,
+        ),
+    ];
+    digests
+        .into_iter()
+        .filter(|(_, _, enabled, task)| *enabled && task.enabled)
+        .filter_map(|(name, summary, _, task)| {
+            let cron = CronSchedule::parse(task.cron.as_deref()?)?;
+            Some((name, summary, cron))
+        })
+        .collect()
+}
+
+/// Digests due in the `days` after `from` (local time, at most
+/// `MAX_DAYS`), in order
+pub fn digest_events(config: &AppConfig, from: NaiveDateTime, days: u32) -> Vec<DigestEvent> {
+    let until = from
+        .checked_add_signed(Duration::days(i64::from(days.min(MAX_DAYS))))
+        .unwrap_or(NaiveDateTime::MAX);
+    let mut events = Vec::new();
+    for (task, summary, cron) in scheduled_digests(config) {
+        let mut after = from;
+        while let Some(start) = cron.next_after(after).filter(|start| *start <= until) {
+            events.push(DigestEvent { task, summary, start });
+            after = start;
+        }
+    }
+    events.sort_by_key(|event| event.start);
+    events
+}
+
+/// ICS calendar of the digests, each with a reminder when it is ready
+pub fn to_ics(events: &[DigestEvent], stamp: DateTime<Utc>) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//kenseader//digests//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "X-WR-CALNAME:Kenseader".to_string(),
+    ];
+    for event in events {
+        let start = event.start.format(TIME_FORMAT);
+        lines.extend([
+            "BEGIN:VEVENT".to_string(),
+            format!("UID:{}-{}@kenseader", event.task, start),
+            format!("DTSTAMP:{}Z", stamp.format(TIME_FORMAT)),
+            format!("DTSTART:{}", start),
+            "DURATION:PT15M".to_string(),
+            format!("SUMMARY:{}", event.summary),
+            "BEGIN:VALARM".to_string(),
+            "ACTION:DISPLAY".to_string(),
+            "TRIGGER:PT0M".to_string(),
+            format!("DESCRIPTION:{}", event.summary),
+            "END:VALARM".to_string(),
+            "END:VEVENT".to_string(),
+        ]);
+    }
+    lines.push("END:VCALENDAR".to_string());
+    // ICS lines end in CRLF
+    lines.iter().map(|line| format!("{}\r\n", line)).collect()
+}
+
+/// ICS calendar of the digests of the next `days` days
+pub fn digest_calendar(config: &AppConfig, days: u32) -> String {
+    let now = Utc::now();
+    let events = digest_events(config, now.with_timezone(&Local).naive_local(), days);
+    to_ics(&events, now)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TaskSchedule;
+    use chrono::{NaiveDate, TimeZone};
+
+    fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap().and_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_digest_events() {
+        let mut config = AppConfig::default();
+        config.audio_digest.enabled = true;
+        config.sync.schedule.audio_digest = TaskSchedule {
+            enabled: true,
+            cron: Some("0 7 * * *".to_string()),
+        };
+        config.sync.schedule.digest.cron = Some("30 12 * * *".to_string());
+
+        // The notification digest is off (notifications disabled)
+        let events = digest_events(&config, at(16, 8, 0), 2);
+        let starts: Vec<_> = events.iter().map(|e| (e.task, e.start)).collect();
+        assert_eq!(starts, vec![("audio_digest", at(17, 7, 0)), ("audio_digest", at(18, 7, 0))]);
+        // Calendars stop after a year however many days are asked for
+        assert_eq!(digest_events(&config, at(16, 8, 0), u32::MAX).len(), MAX_DAYS as usize);
+
+        config.notifications.enabled = true;
+        config.notifications.mode = NotificationMode::Digest;
+        let events = digest_events(&config, at(16, 8, 0), 1);
+        let starts: Vec<_> = events.iter().map(|e| (e.task, e.start)).collect();
+        assert_eq!(starts, vec![("digest", at(16, 12, 30)), ("audio_digest", at(17, 7, 0))]);
+
+        // Interval-based digests have no fixed time
+        config.sync.schedule.audio_digest.cron = None;
+        assert_eq!(digest_events(&config, at(16, 8, 0), 1).len(), 1);
+    }
+
+    #[test]
+    fn test_to_ics() {
+        let events = [DigestEvent {
+            task: "audio_digest",
+            summary: "Kenseader audio briefing ready",
+            start: at(17, 7, 0),
+        }];
+        let ics = to_ics(&events, Utc.with_ymd_and_hms(2026, 10, 16, 6, 0, 0).unwrap());
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.contains("\r\nUID:audio_digest-20261017T070000@kenseader\r\n"));
+        assert!(ics.contains("\r\nDTSTAMP:20261016T060000Z\r\nDTSTART:20261017T070000\r\n"));
+        assert!(ics.ends_with("END:VEVENT\r\nEND:VCALENDAR\r\n"));
+    }
+}
@@ -1,17 +1,50 @@
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Deepest chain of `include`s followed, which also stops include cycles
+const MAX_INCLUDE_DEPTH: usize = 8;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
     #[serde(default)]
     pub general: GeneralConfig,
     #[serde(default)]
+    pub database: DatabaseConfig,
+    #[serde(default)]
     pub ai: AiConfig,
     #[serde(default)]
     pub ui: UiConfig,
     #[serde(default)]
     pub sync: SyncConfig,
     #[serde(default)]
+    pub state_sync: StateSyncConfig,
+    #[serde(default)]
+    pub notifications: NotificationConfig,
+    #[serde(default)]
+    pub alerts: AlertsConfig,
+    #[serde(default)]
+    pub advisories: AdvisoriesConfig,
+    #[serde(default)]
+    pub audio_digest: AudioDigestConfig,
+    #[serde(default)]
+    pub integrations: IntegrationsConfig,
+    #[serde(default)]
+    pub mqtt: MqttConfig,
+    #[serde(default)]
+    pub web: WebConfig,
+    #[serde(default)]
+    pub prune: PruneConfig,
+    #[serde(default)]
+    pub robots: RobotsConfig,
+    #[serde(default)]
+    pub images: ImageDownloadConfig,
+    #[serde(default)]
+    pub metered: MeteredConfig,
+    /// Parsing workarounds for malformed feeds, keyed by feed name
+    #[serde(default)]
+    pub quirks: HashMap<String, FeedQuirks>,
+    #[serde(default)]
     pub rsshub: RsshubConfig,
     #[serde(default)]
     pub keymap: KeymapConfig,
@@ -21,9 +54,23 @@ impl Default for AppConfig {
     fn default() -> Self {
         Self {
             general: GeneralConfig::default(),
+            database: DatabaseConfig::default(),
             ai: AiConfig::default(),
             ui: UiConfig::default(),
             sync: SyncConfig::default(),
+            state_sync: StateSyncConfig::default(),
+            notifications: NotificationConfig::default(),
+            alerts: AlertsConfig::default(),
+            advisories: AdvisoriesConfig::default(),
+            audio_digest: AudioDigestConfig::default(),
+            integrations: IntegrationsConfig::default(),
+            mqtt: MqttConfig::default(),
+            web: WebConfig::default(),
+            prune: PruneConfig::default(),
+            robots: RobotsConfig::default(),
+            images: ImageDownloadConfig::default(),
+            metered: MeteredConfig::default(),
+            quirks: HashMap::new(),
             rsshub: RsshubConfig::default(),
             keymap: KeymapConfig::default(),
         }
@@ -41,6 +88,10 @@ pub struct GeneralConfig {
     /// Log level
     #[serde(default = "default_log_level")]
     pub log_level: String,
+    /// Mark unread articles read once they are this many days old
+    /// (0 = never); feeds can override it
+    #[serde(default)]
+    pub auto_read_days: u32,
 }
 
 impl Default for GeneralConfig {
@@ -49,6 +100,50 @@ impl Default for GeneralConfig {
             data_dir: default_data_dir(),
             article_retention_days: default_retention_days(),
             log_level: default_log_level(),
+            auto_read_days: 0,
+        }
+    }
+}
+
+/// SQLite connection tuning. The database always runs in WAL mode.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatabaseConfig {
+    /// Maximum connections in the pool
+    #[serde(default = "default_db_max_connections")]
+    pub max_connections: u32,
+    /// Milliseconds a connection waits for a lock held by another before
+    /// failing with SQLITE_BUSY
+    #[serde(default = "default_db_busy_timeout")]
+    pub busy_timeout_ms: u64,
+    /// PRAGMA synchronous: "off", "normal", "full" or "extra"
+    #[serde(default = "default_db_synchronous")]
+    pub synchronous: String,
+    /// Megabytes of the database file memory-mapped for faster reads (0 = off)
+    #[serde(default = "default_db_mmap_size")]
+    pub mmap_size_mb: u64,
+    /// WAL size in pages at which it is checkpointed into the database
+    #[serde(default = "default_db_wal_autocheckpoint")]
+    pub wal_autocheckpoint: u32,
+    /// Retries of an operation that failed with a transient error (busy,
+    /// I/O errors of cloud-synced files)
+    #[serde(default = "default_db_max_retries")]
+    pub max_retries: u32,
+    /// Delay before the first retry in milliseconds, doubled on every retry
+    /// and jittered
+    #[serde(default = "default_db_retry_base_delay")]
+    pub retry_base_delay_ms: u64,
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            max_connections: default_db_max_connections(),
+            busy_timeout_ms: default_db_busy_timeout(),
+            synchronous: default_db_synchronous(),
+            mmap_size_mb: default_db_mmap_size(),
+            wal_autocheckpoint: default_db_wal_autocheckpoint(),
+            max_retries: default_db_max_retries(),
+            retry_base_delay_ms: default_db_retry_base_delay(),
         }
     }
 }
@@ -58,7 +153,7 @@ pub struct AiConfig {
     /// Enable AI summarization
     #[serde(default = "default_true")]
     pub enabled: bool,
-    /// AI provider: "claude_cli", "gemini_cli", "codex_cli", "openai", "gemini_api", "claude_api"
+    /// AI provider: "claude_cli", "gemini_cli", "codex_cli", "openai", "azure_openai", "gemini_api", "claude_api"
     #[serde(default = "default_ai_provider")]
     pub provider: String,
     /// Summary language (e.g., "English", "Chinese", "Japanese")
@@ -70,6 +165,27 @@ pub struct AiConfig {
     /// OpenAI model name
     #[serde(default = "default_openai_model")]
     pub openai_model: String,
+    /// Azure OpenAI resource endpoint, e.g. "https://my-resource.openai.azure.com"
+    #[serde(default)]
+    pub azure_openai_endpoint: Option<String>,
+    /// Azure OpenAI deployment name (selects the model)
+    #[serde(default)]
+    pub azure_openai_deployment: Option<String>,
+    /// Azure OpenAI REST API version
+    #[serde(default = "default_azure_openai_api_version")]
+    pub azure_openai_api_version: String,
+    /// Azure OpenAI resource key (leave unset to use Azure AD instead)
+    #[serde(default)]
+    pub azure_openai_api_key: Option<String>,
+    /// Azure AD tenant of the service principal used without a resource key
+    #[serde(default)]
+    pub azure_tenant_id: Option<String>,
+    /// Azure AD application (client) ID of the service principal
+    #[serde(default)]
+    pub azure_client_id: Option<String>,
+    /// Azure AD client secret of the service principal
+    #[serde(default)]
+    pub azure_client_secret: Option<String>,
     /// Gemini API key (for gemini_api provider)
     #[serde(default)]
     pub gemini_api_key: Option<String>,
@@ -85,9 +201,10 @@ pub struct AiConfig {
     /// Max tokens for summary
     #[serde(default = "default_max_tokens")]
     pub max_summary_tokens: u32,
-    /// Concurrent summarization tasks
-    #[serde(default = "default_concurrency")]
-    pub concurrency: usize,
+    /// Maximum concurrent AI requests, shared by summarization, scoring
+    /// and classification (`concurrency` is accepted as the old name)
+    #[serde(default = "default_concurrency", alias = "concurrency")]
+    pub max_concurrent_requests: usize,
     /// Minimum content length (chars) for AI summarization
     #[serde(default = "default_min_summarize_length")]
     pub min_summarize_length: usize,
@@ -97,6 +214,25 @@ pub struct AiConfig {
     /// Relevance threshold for article filtering (0.0-1.0)
     #[serde(default = "default_relevance_threshold")]
     pub relevance_threshold: f64,
+    /// Days after which a reading event counts half as much towards the
+    /// interests used for scoring (0 = events never fade)
+    #[serde(default = "default_preference_half_life_days")]
+    pub preference_half_life_days: f64,
+    /// Retries of a request after a transient failure (rate limit, 5xx, network)
+    #[serde(default = "default_ai_max_retries")]
+    pub max_retries: u32,
+    /// Initial retry delay in milliseconds, doubled on every retry
+    #[serde(default = "default_ai_retry_base_delay_ms")]
+    pub retry_base_delay_ms: u64,
+    /// Maximum retry delay in seconds (also caps Retry-After)
+    #[serde(default = "default_ai_retry_max_delay_secs")]
+    pub retry_max_delay_secs: u64,
+    /// Consecutive failed requests that pause AI requests (0 = never pause)
+    #[serde(default = "default_circuit_breaker_threshold")]
+    pub circuit_breaker_threshold: u32,
+    /// How long AI requests stay paused after the threshold is reached
+    #[serde(default = "default_circuit_breaker_cooldown_secs")]
+    pub circuit_breaker_cooldown_secs: u64,
 }
 
 impl Default for AiConfig {
@@ -107,15 +243,28 @@ impl Default for AiConfig {
             summary_language: default_summary_language(),
             openai_api_key: None,
             openai_model: default_openai_model(),
+            azure_openai_endpoint: None,
+            azure_openai_deployment: None,
+            azure_openai_api_version: default_azure_openai_api_version(),
+            azure_openai_api_key: None,
+            azure_tenant_id: None,
+            azure_client_id: None,
+            azure_client_secret: None,
             gemini_api_key: None,
             gemini_model: default_gemini_model(),
             claude_api_key: None,
             claude_model: default_claude_model(),
             max_summary_tokens: default_max_tokens(),
-            concurrency: default_concurrency(),
+            max_concurrent_requests: default_concurrency(),
             min_summarize_length: default_min_summarize_length(),
             max_summary_length: default_max_summary_length(),
             relevance_threshold: default_relevance_threshold(),
+            preference_half_life_days: default_preference_half_life_days(),
+            max_retries: default_ai_max_retries(),
+            retry_base_delay_ms: default_ai_retry_base_delay_ms(),
+            retry_max_delay_secs: default_ai_retry_max_delay_secs(),
+            circuit_breaker_threshold: default_circuit_breaker_threshold(),
+            circuit_breaker_cooldown_secs: default_circuit_breaker_cooldown_secs(),
         }
     }
 }
@@ -125,33 +274,180 @@ pub struct UiConfig {
     /// Tick rate in milliseconds (used when not animating)
     #[serde(default = "default_tick_rate")]
     pub tick_rate_ms: u64,
+    /// Tick rate in milliseconds while nothing on screen is changing (no
+    /// refresh, notifications, pending keys or reading aloud)
+    #[serde(default = "default_idle_tick_rate")]
+    pub idle_tick_rate_ms: u64,
     /// Show article author
     #[serde(default = "default_true")]
     pub show_author: bool,
     /// Show timestamps
     #[serde(default = "default_true")]
     pub show_timestamps: bool,
-    /// Image preview enabled
+    /// strftime-style format for absolute timestamps (shown in local time)
+    #[serde(default = "default_date_format")]
+    pub date_format: String,
+    /// Show timestamps as relative times ("3h ago") instead of absolute dates
+    #[serde(default)]
+    pub relative_dates: bool,
+    /// Scrollbars and position indicators on the article list and detail
+    #[serde(default = "default_true")]
+    pub show_scrollbar: bool,
+    /// Dim unread articles in the list once they are this many days old
+    /// (0 = never)
+    #[serde(default = "default_dim_unread_after_days")]
+    pub dim_unread_after_days: u32,
+    /// Load article images automatically; when off, images are shown as
+    /// placeholders until loaded for the article with `load_images`
     #[serde(default = "default_true")]
     pub image_preview: bool,
+    /// Also load images automatically on a metered connection (as reported
+    /// by NetworkManager); otherwise they wait for `load_images` there
+    #[serde(default)]
+    pub images_on_metered: bool,
+    /// Screen-reader friendly mode: no images or animations, linear article
+    /// text with "Heading:"/"Quote:" prefixes and state shown as words
+    /// rather than colors or symbols
+    #[serde(default)]
+    pub screen_reader: bool,
+    /// Disable smooth scrolling, spinners and other animations
+    #[serde(default)]
+    pub reduce_motion: bool,
+    /// Run the scheduler and IPC server inside the TUI when no daemon is running
+    #[serde(default)]
+    pub embedded_daemon: bool,
+    /// Shell command for the share menu's "send" option. Receives the title
+    /// and URL on stdin and as `KENSEADER_TITLE` / `KENSEADER_URL`
+    #[serde(default)]
+    pub share_command: Option<String>,
+    /// Command used to open URLs instead of the system default. `{url}` and
+    /// `{title}` are replaced; without `{url}` the URL is appended
+    #[serde(default)]
+    pub browser_command: Option<String>,
+    /// Per-feed browser commands, keyed by feed name
+    #[serde(default)]
+    pub feed_browser_commands: HashMap<String, String>,
+    /// Run browser commands in the background; when false the TUI is
+    /// suspended until the command exits (for terminal browsers like w3m)
+    #[serde(default = "default_true")]
+    pub browser_background: bool,
+    /// Command that receives the article text on stdin (default: `$PAGER`,
+    /// then `less -R`), e.g. `glow -p -` or `bat -l md`
+    #[serde(default)]
+    pub pager_command: Option<String>,
+    /// Text-to-speech command for reading articles aloud. Receives one
+    /// sentence at a time on stdin (default: `say` on macOS, else `espeak-ng`
+    /// or `espeak`), e.g. `piper --model en_US-amy-medium.onnx --output-raw | aplay -r 22050 -f S16_LE -t raw -`
+    #[serde(default)]
+    pub tts_command: Option<String>,
+    /// Player for video and podcast articles, with `{url}` and `{title}`
+    /// placeholders. mpv plays YouTube and PeerTube pages through yt-dlp.
+    #[serde(default = "default_player_command")]
+    pub player_command: String,
+    /// Start with the AI header (summary, tags, relevance, style) of the
+    /// article detail collapsed to one line
+    #[serde(default)]
+    pub collapse_ai_header: bool,
+    /// Top-level comments fetched into the discussion section of the detail
+    /// (Hacker News, Lobsters, Reddit); 0 disables fetching comments
+    #[serde(default = "default_top_comments")]
+    pub top_comments: usize,
+    /// Show statistics of the selected feed in the detail panel while the
+    /// subscriptions panel is focused
+    #[serde(default = "default_true")]
+    pub feed_stats_panel: bool,
+    /// Show the keys that can follow a prefix key (g, leader) in a popup
+    #[serde(default = "default_true")]
+    pub which_key: bool,
+    /// Delay before the prefix key popup appears, in milliseconds
+    #[serde(default = "default_which_key_delay")]
+    pub which_key_delay_ms: u64,
+    /// Seconds a status message stays on screen (warnings twice, errors three times as long)
+    #[serde(default = "default_toast_timeout")]
+    pub toast_timeout_secs: u64,
+    /// Seconds without a key press after which time spent on an article no
+    /// longer counts as reading it; 0 counts all the time it is open
+    #[serde(default = "default_idle_timeout")]
+    pub idle_timeout_secs: u64,
+    /// Left side of the status bar, with `{placeholder}`s (see docs/configuration.md)
+    #[serde(default = "default_status_left")]
+    pub status_left: String,
+    /// Right side of the status bar
+    #[serde(default = "default_status_right")]
+    pub status_right: String,
+    /// Terminal window title, with the status bar's `{placeholder}`s; empty
+    /// leaves the title alone
+    #[serde(default = "default_terminal_title")]
+    pub terminal_title: String,
+    /// Colors the terminal supports: auto-detected, or forced when detection is wrong
+    #[serde(default)]
+    pub color_mode: ColorMode,
     /// Theme configuration
     #[serde(default)]
     pub theme: ThemeConfig,
     /// Smooth scrolling configuration
     #[serde(default)]
     pub scroll: ScrollConfig,
+    /// Article text layout in the detail pane
+    #[serde(default)]
+    pub reader: ReaderConfig,
 }
 
 impl Default for UiConfig {
     fn default() -> Self {
         Self {
             tick_rate_ms: default_tick_rate(),
+            idle_tick_rate_ms: default_idle_tick_rate(),
             show_author: default_true(),
             show_timestamps: default_true(),
+            date_format: default_date_format(),
+            relative_dates: false,
+            show_scrollbar: default_true(),
+            dim_unread_after_days: default_dim_unread_after_days(),
             image_preview: default_true(),
+            images_on_metered: false,
+            screen_reader: false,
+            reduce_motion: false,
+            embedded_daemon: false,
+            share_command: None,
+            browser_command: None,
+            feed_browser_commands: HashMap::new(),
+            browser_background: default_true(),
+            pager_command: None,
+            tts_command: None,
+            player_command: default_player_command(),
+            collapse_ai_header: false,
+            top_comments: default_top_comments(),
+            feed_stats_panel: default_true(),
+            which_key: default_true(),
+            which_key_delay_ms: default_which_key_delay(),
+            toast_timeout_secs: default_toast_timeout(),
+            idle_timeout_secs: default_idle_timeout(),
+            status_left: default_status_left(),
+            status_right: default_status_right(),
+            terminal_title: default_terminal_title(),
+            color_mode: ColorMode::default(),
             theme: ThemeConfig::default(),
             scroll: ScrollConfig::default(),
+            reader: ReaderConfig::default(),
+        }
+    }
+}
+
+impl UiConfig {
+    /// Whether animations are off, by `reduce_motion` or screen-reader mode
+    pub fn reduces_motion(&self) -> bool {
+        self.reduce_motion || self.screen_reader
+    }
+
+    /// Scrolling configuration with smooth scrolling turned off when motion
+    /// is reduced
+    pub fn effective_scroll(&self) -> ScrollConfig {
+        let mut scroll = self.scroll.clone();
+        if self.reduces_motion() {
+            scroll.smooth_enabled = false;
         }
+        scroll
     }
 }
 
@@ -204,6 +500,10 @@ impl Default for ScrollConfig {
     }
 }
 
+fn default_date_format() -> String {
+    "%Y-%m-%d %H:%M".to_string()
+}
+
 fn default_smooth_enabled() -> bool {
     true
 }
@@ -220,6 +520,64 @@ fn default_animation_fps() -> u32 {
     60
 }
 
+/// Typography of the article text in the detail pane ("reader mode")
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReaderConfig {
+    /// Widest the article text gets, in columns; it is centered in wider
+    /// panes (0 = use the whole pane)
+    #[serde(default)]
+    pub max_width: u16,
+    /// Blank lines between paragraphs
+    #[serde(default = "default_paragraph_spacing")]
+    pub paragraph_spacing: u16,
+    /// Justify paragraphs to both edges
+    #[serde(default)]
+    pub justify: bool,
+}
+
+impl Default for ReaderConfig {
+    fn default() -> Self {
+        Self {
+            max_width: 0,
+            paragraph_spacing: default_paragraph_spacing(),
+            justify: false,
+        }
+    }
+}
+
+fn default_paragraph_spacing() -> u16 {
+    1
+}
+
+/// Color support of the terminal; themes are mapped to the nearest
+/// 256 or 16 colors without truecolor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ColorMode {
+    /// Detect from `$COLORTERM` and `$TERM`
+    #[default]
+    #[serde(rename = "auto")]
+    Auto,
+    #[serde(rename = "truecolor")]
+    TrueColor,
+    #[serde(rename = "256")]
+    Ansi256,
+    #[serde(rename = "16")]
+    Ansi16,
+}
+
+/// How the theme is chosen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemeMode {
+    /// Always use `name`
+    #[default]
+    Fixed,
+    /// Follow the OS light/dark appearance, falling back to `light_hours`
+    Auto,
+    /// Light theme during `light_hours`, dark theme otherwise
+    Schedule,
+}
+
 /// Theme configuration
 /// Can be specified as a simple string (theme name) or as a full struct with overrides
 #[derive(Debug, Clone, Serialize)]
@@ -228,6 +586,14 @@ pub struct ThemeConfig {
     pub name: String,
     /// Optional color overrides for semantic colors
     pub colors: ThemeColorOverrides,
+    /// Fixed theme, or switching between `light` and `dark`
+    pub mode: ThemeMode,
+    /// Theme used for a light appearance (auto and schedule modes)
+    pub light: String,
+    /// Theme used for a dark appearance (auto and schedule modes)
+    pub dark: String,
+    /// Local time window of the light theme, "HH:MM-HH:MM"
+    pub light_hours: String,
 }
 
 impl Default for ThemeConfig {
@@ -235,6 +601,10 @@ impl Default for ThemeConfig {
         Self {
             name: default_theme_name(),
             colors: ThemeColorOverrides::default(),
+            mode: ThemeMode::default(),
+            light: default_light_theme_name(),
+            dark: default_theme_name(),
+            light_hours: default_light_hours(),
         }
     }
 }
@@ -264,7 +634,7 @@ impl<'de> Deserialize<'de> for ThemeConfig {
             {
                 Ok(ThemeConfig {
                     name: value.to_string(),
-                    colors: ThemeColorOverrides::default(),
+                    ..ThemeConfig::default()
                 })
             }
 
@@ -273,17 +643,16 @@ impl<'de> Deserialize<'de> for ThemeConfig {
             where
                 M: MapAccess<'de>,
             {
-                let mut name: Option<String> = None;
-                let mut colors: Option<ThemeColorOverrides> = None;
+                let mut config = ThemeConfig::default();
 
                 while let Some(key) = map.next_key::<String>()? {
                     match key.as_str() {
-                        "name" => {
-                            name = Some(map.next_value()?);
-                        }
-                        "colors" => {
-                            colors = Some(map.next_value()?);
-                        }
+                        "name" => config.name = map.next_value()?,
+                        "colors" => config.colors = map.next_value()?,
+                        "mode" => config.mode = map.next_value()?,
+                        "light" => config.light = map.next_value()?,
+                        "dark" => config.dark = map.next_value()?,
+                        "light_hours" => config.light_hours = map.next_value()?,
                         _ => {
                             // Ignore unknown fields
                             let _: serde::de::IgnoredAny = map.next_value()?;
@@ -291,10 +660,7 @@ impl<'de> Deserialize<'de> for ThemeConfig {
                     }
                 }
 
-                Ok(ThemeConfig {
-                    name: name.unwrap_or_else(default_theme_name),
-                    colors: colors.unwrap_or_default(),
-                })
+                Ok(config)
             }
         }
 
@@ -306,6 +672,14 @@ fn default_theme_name() -> String {
     "gruvbox-dark".to_string()
 }
 
+fn default_light_theme_name() -> String {
+    "gruvbox-light".to_string()
+}
+
+fn default_light_hours() -> String {
+    "07:00-19:00".to_string()
+}
+
 /// Optional color overrides for theme customization
 /// Each color is a hex string (e.g., "#ff0000" or "ff0000")
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -346,6 +720,30 @@ pub struct SyncConfig {
     /// Minimum seconds between refreshes for each feed (0 = no limit, refresh all feeds every time)
     #[serde(default = "default_feed_refresh_interval")]
     pub feed_refresh_interval_secs: u64,
+    /// Maximum random delay in seconds added to each scheduler refresh tick (0 = none)
+    #[serde(default = "default_refresh_jitter")]
+    pub refresh_jitter_secs: u64,
+    /// Stretch each feed's refresh interval by a fixed per-feed share of up to
+    /// this percentage, spreading feeds fetched together over later ticks (0 = off)
+    #[serde(default = "default_feed_stagger_percent")]
+    pub feed_stagger_percent: u32,
+    /// Local time window ("23:00-07:00") during which refresh and AI tasks pause
+    #[serde(default)]
+    pub quiet_hours: Option<String>,
+    /// During quiet hours, run those tasks only on every Nth check instead (0 = pause)
+    #[serde(default)]
+    pub quiet_hours_run_every: u32,
+    /// URL probed to detect that the network is back after going offline
+    /// (default: a well-known URL and the first few active feeds, any of
+    /// which answering is enough)
+    #[serde(default)]
+    pub connectivity_probe_url: Option<String>,
+    /// Seconds between connectivity probes while offline
+    #[serde(default = "default_connectivity_check")]
+    pub connectivity_check_secs: u64,
+    /// Per-task enable flags and cron schedules (`[sync.schedule.<task>]`)
+    #[serde(default)]
+    pub schedule: TaskSchedules,
     /// Cleanup interval in seconds (remove old articles)
     #[serde(default = "default_cleanup_interval")]
     pub cleanup_interval_secs: u64,
@@ -364,6 +762,10 @@ pub struct SyncConfig {
     /// HTTP proxy URL for feed fetching (e.g., "http://127.0.0.1:7890" or "socks5://127.0.0.1:1080")
     #[serde(default)]
     pub proxy_url: Option<String>,
+    /// Switch feeds that redirect permanently (301/308) to their new URL,
+    /// instead of only flagging them
+    #[serde(default)]
+    pub follow_moved_feeds: bool,
 }
 
 impl Default for SyncConfig {
@@ -371,12 +773,601 @@ impl Default for SyncConfig {
         Self {
             refresh_interval_secs: default_refresh_interval(),
             feed_refresh_interval_secs: default_feed_refresh_interval(),
+            refresh_jitter_secs: default_refresh_jitter(),
+            feed_stagger_percent: default_feed_stagger_percent(),
+            quiet_hours: None,
+            quiet_hours_run_every: 0,
+            connectivity_probe_url: None,
+            connectivity_check_secs: default_connectivity_check(),
+            schedule: TaskSchedules::default(),
             cleanup_interval_secs: default_cleanup_interval(),
             summarize_interval_secs: default_summarize_interval(),
             filter_interval_secs: default_filter_interval(),
             request_timeout_secs: default_timeout(),
             rate_limit_ms: default_rate_limit(),
             proxy_url: None,
+            follow_moved_feeds: false,
+        }
+    }
+}
+
+/// Scheduling of each background task
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TaskSchedules {
+    #[serde(default)]
+    pub refresh: TaskSchedule,
+    #[serde(default)]
+    pub cleanup: TaskSchedule,
+    #[serde(default)]
+    pub summarize: TaskSchedule,
+    /// Relevance scoring and style classification
+    #[serde(default)]
+    pub filter: TaskSchedule,
+    /// Notification digest (`notifications.mode = "digest"`)
+    #[serde(default)]
+    pub digest: TaskSchedule,
+    /// Daily audio briefing (`audio_digest.enabled`)
+    #[serde(default)]
+    pub audio_digest: TaskSchedule,
+    /// Daily check for feeds worth unsubscribing from (`prune.enabled`)
+    #[serde(default)]
+    pub prune: TaskSchedule,
+    /// Weekly refresh of feed titles and icons, and check for moved feeds
+    #[serde(default)]
+    pub metadata: TaskSchedule,
+    /// Export of saved articles to read-later services (`integrations.enabled`)
+    #[serde(default)]
+    pub integrations: TaskSchedule,
+    #[serde(default)]
+    pub state_sync: TaskSchedule,
+}
+
+/// Whether a background task runs, and when
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskSchedule {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Cron expression ("minute hour day month weekday", e.g. "30 7 * * *")
+    /// used instead of the task's interval
+    #[serde(default)]
+    pub cron: Option<String>,
+}
+
+impl Default for TaskSchedule {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            cron: None,
+        }
+    }
+}
+
+/// Read/saved state sync between machines through a shared folder or WebDAV
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateSyncConfig {
+    /// Enable state sync (run by the daemon)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Synced folder for journals (Syncthing, Dropbox, iCloud, ...)
+    #[serde(default)]
+    pub folder: Option<PathBuf>,
+    /// WebDAV collection URL for journals (used when `folder` is not set)
+    #[serde(default)]
+    pub webdav_url: Option<String>,
+    /// WebDAV username
+    #[serde(default)]
+    pub webdav_username: Option<String>,
+    /// WebDAV password
+    #[serde(default)]
+    pub webdav_password: Option<String>,
+    /// Sync interval in seconds
+    #[serde(default = "default_state_sync_interval")]
+    pub interval_secs: u64,
+    /// Name of this machine's journal (defaults to a generated device ID)
+    #[serde(default)]
+    pub device_name: Option<String>,
+}
+
+impl Default for StateSyncConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            folder: None,
+            webdav_url: None,
+            webdav_username: None,
+            webdav_password: None,
+            interval_secs: default_state_sync_interval(),
+            device_name: None,
+        }
+    }
+}
+
+/// Desktop notifications for new articles (sent by the daemon)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationConfig {
+    /// Enable notifications
+    #[serde(default)]
+    pub enabled: bool,
+    /// Command run for each notification, with `{title}` and `{body}`
+    /// placeholders (defaults to `notify-send {title} {body}`)
+    #[serde(default)]
+    pub command: Option<String>,
+    /// One notification per article, or a periodic digest
+    #[serde(default)]
+    pub mode: NotificationMode,
+    /// Digest interval in minutes
+    #[serde(default = "default_digest_interval_mins")]
+    pub digest_interval_mins: u64,
+    /// Relevance score from which an article counts as high relevance in digests
+    #[serde(default = "default_high_relevance_threshold")]
+    pub high_relevance_threshold: f64,
+    /// Phone push channels (ntfy, Gotify), independent of `enabled`
+    #[serde(default)]
+    pub push: Vec<PushChannel>,
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            command: None,
+            mode: NotificationMode::default(),
+            digest_interval_mins: default_digest_interval_mins(),
+            high_relevance_threshold: default_high_relevance_threshold(),
+            push: Vec::new(),
+        }
+    }
+}
+
+/// How new articles are announced
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationMode {
+    /// One notification per new article, right after each refresh
+    #[default]
+    PerArticle,
+    /// A summary of the new articles every `digest_interval_mins`
+    Digest,
+}
+
+/// Push service new articles matching keywords or scoring high are sent to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PushChannel {
+    pub service: PushService,
+    /// Server URL (defaults to https://ntfy.sh for ntfy; required for Gotify)
+    #[serde(default)]
+    pub url: Option<String>,
+    /// ntfy topic
+    #[serde(default)]
+    pub topic: Option<String>,
+    /// ntfy access token, or the Gotify application token
+    #[serde(default)]
+    pub token: Option<String>,
+    /// Message priority (ntfy 1-5, Gotify 0-10; default: the server's)
+    #[serde(default)]
+    pub priority: Option<u8>,
+    /// Push articles whose title or summary contains one of these (case-insensitive)
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    /// Push articles scored at least this relevant (default:
+    /// `high_relevance_threshold`; above 1.0 pushes keyword matches only)
+    #[serde(default)]
+    pub min_relevance: Option<f64>,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+/// Push notification service
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PushService {
+    Ntfy,
+    Gotify,
+}
+
+/// Keyword watchlist checked as new articles are stored, independent of AI
+/// scoring
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertsConfig {
+    /// Alert on new articles whose title or content contains one of these
+    /// (case-insensitive), e.g. your name, your company or a CVE identifier
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    /// Show a notification right away (with `notifications.command`), even
+    /// when `notifications.enabled` is off
+    #[serde(default = "default_true")]
+    pub notify: bool,
+    /// Also send alerts to every enabled push channel
+    #[serde(default)]
+    pub push: bool,
+}
+
+impl Default for AlertsConfig {
+    fn default() -> Self {
+        Self {
+            keywords: Vec::new(),
+            notify: true,
+            push: false,
+        }
+    }
+}
+
+/// Severity of the CVE identifiers mentioned in articles, looked up in the
+/// NVD after each refresh
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdvisoriesConfig {
+    /// Look up the CVEs of new articles and tag the articles with them
+    #[serde(default)]
+    pub enabled: bool,
+    /// NVD CVE API endpoint
+    #[serde(default = "default_advisories_api_url")]
+    pub api_url: String,
+    /// NVD API key; raises the rate limit from 5 to 50 lookups per 30 seconds
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// CVEs looked up per refresh at most; the rest wait for the next one
+    #[serde(default = "default_advisories_lookups")]
+    pub lookups_per_refresh: usize,
+}
+
+impl Default for AdvisoriesConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            api_url: default_advisories_api_url(),
+            api_key: None,
+            lookups_per_refresh: default_advisories_lookups(),
+        }
+    }
+}
+
+/// Export of saved articles to read-later services (run by the daemon)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntegrationsConfig {
+    /// Enable the export task
+    #[serde(default)]
+    pub enabled: bool,
+    /// Export interval in seconds
+    #[serde(default = "default_integrations_interval")]
+    pub interval_secs: u64,
+    /// Services saved articles are sent to
+    #[serde(default)]
+    pub destinations: Vec<IntegrationDestination>,
+}
+
+impl Default for IntegrationsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_secs: default_integrations_interval(),
+            destinations: Vec::new(),
+        }
+    }
+}
+
+/// A read-later account saved articles are exported to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntegrationDestination {
+    pub service: IntegrationService,
+    /// API token of the account
+    pub token: String,
+    /// Name the exports are tracked under (defaults to the service), so
+    /// two accounts of one service each get every article
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// API endpoint, when not the service's default
+    #[serde(default)]
+    pub api_url: Option<String>,
+    /// Tags added to exported articles (Readwise)
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl IntegrationDestination {
+    /// Name the exports are tracked under
+    pub fn name(&self) -> &str {
+        self.name.as_deref().unwrap_or(self.service.name())
+    }
+}
+
+/// Read-later service an integration talks to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IntegrationService {
+    /// Readwise Reader
+    Readwise,
+    Matter,
+}
+
+impl IntegrationService {
+    pub fn name(self) -> &'static str {
+        match self {
+            IntegrationService::Readwise => "readwise",
+            IntegrationService::Matter => "matter",
+        }
+    }
+}
+
+/// Publishing of scheduler events and new articles to an MQTT broker (by the daemon)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MqttConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Broker host name
+    #[serde(default = "default_mqtt_host")]
+    pub host: String,
+    #[serde(default = "default_mqtt_port")]
+    pub port: u16,
+    #[serde(default = "default_mqtt_client_id")]
+    pub client_id: String,
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub password: Option<String>,
+    /// Prefix of the topics published to
+    #[serde(default = "default_mqtt_topic")]
+    pub topic: String,
+    /// Articles in the retained `<topic>/top_stories` message (0 = none)
+    #[serde(default = "default_mqtt_top_stories")]
+    pub top_stories: usize,
+}
+
+impl Default for MqttConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: default_mqtt_host(),
+            port: default_mqtt_port(),
+            client_id: default_mqtt_client_id(),
+            username: None,
+            password: None,
+            topic: default_mqtt_topic(),
+            top_stories: default_mqtt_top_stories(),
+        }
+    }
+}
+
+/// Read-only web interface served by the daemon
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Address to listen on (0.0.0.0 makes it reachable from the LAN)
+    #[serde(default = "default_web_address")]
+    pub address: String,
+    /// Articles shown per list
+    #[serde(default = "default_web_page_size")]
+    pub page_size: u32,
+}
+
+impl Default for WebConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            address: default_web_address(),
+            page_size: default_web_page_size(),
+        }
+    }
+}
+
+/// Daily audio briefing of new articles, spoken by a TTS command (run by the daemon)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AudioDigestConfig {
+    /// Generate a briefing every day (or on `sync.schedule.audio_digest.cron`)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Folder for the audio files (defaults to `audio_digests` in the data directory)
+    #[serde(default)]
+    pub directory: Option<PathBuf>,
+    /// Audio file format
+    #[serde(default)]
+    pub format: AudioFormat,
+    /// Command that reads the briefing text on stdin and writes the audio
+    /// file `{output}` (defaults to espeak-ng, or `say` on macOS, piped to ffmpeg)
+    #[serde(default)]
+    pub command: Option<String>,
+    /// Articles read per briefing, most relevant first
+    #[serde(default = "default_audio_digest_max_articles")]
+    pub max_articles: usize,
+    /// Briefings kept in the folder; older ones are deleted
+    #[serde(default = "default_audio_digest_keep")]
+    pub keep: usize,
+    /// Address to serve the briefings as a podcast feed on, e.g.
+    /// "127.0.0.1:8765" (subscribe to http://127.0.0.1:8765/feed.xml)
+    #[serde(default)]
+    pub serve_address: Option<String>,
+}
+
+impl Default for AudioDigestConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            directory: None,
+            format: AudioFormat::default(),
+            command: None,
+            max_articles: default_audio_digest_max_articles(),
+            keep: default_audio_digest_keep(),
+            serve_address: None,
+        }
+    }
+}
+
+/// When a feed is suggested for unsubscribing (daemon check, feed
+/// statistics panel and `kenseader doctor`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PruneConfig {
+    /// Check the feeds daily (or on `sync.schedule.prune.cron`) and tell
+    /// the TUI about feeds worth unsubscribing from
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// A feed is stale after this many months without a post
+    #[serde(default = "default_prune_stale_months")]
+    pub stale_months: u32,
+    /// A feed is rarely read below this share of read articles
+    #[serde(default = "default_prune_min_read_ratio")]
+    pub min_read_ratio: f64,
+    /// Articles a feed needs before its read ratio counts
+    #[serde(default = "default_prune_min_articles")]
+    pub min_articles: u32,
+    /// A feed scores low below this average relevance (0.0-1.0)
+    #[serde(default = "default_prune_min_relevance")]
+    pub min_relevance: f64,
+}
+
+impl Default for PruneConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            stale_months: default_prune_stale_months(),
+            min_read_ratio: default_prune_min_read_ratio(),
+            min_articles: default_prune_min_articles(),
+            min_relevance: default_prune_min_relevance(),
+        }
+    }
+}
+
+/// Crawl etiquette for web pages fetched outside feeds (article pages,
+/// site icons)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RobotsConfig {
+    /// Space page fetches from a site by the Crawl-delay of its robots.txt
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Longest delay honored, in seconds
+    #[serde(default = "default_max_crawl_delay")]
+    pub max_crawl_delay_secs: u64,
+    /// Delay in seconds per domain (subdomains included), used instead of
+    /// robots.txt
+    #[serde(default)]
+    pub crawl_delay: HashMap<String, f64>,
+}
+
+impl Default for RobotsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_crawl_delay_secs: default_max_crawl_delay(),
+            crawl_delay: HashMap::new(),
+        }
+    }
+}
+
+/// How the TUI downloads article images
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImageDownloadConfig {
+    /// User-Agent sent with image requests
+    #[serde(default = "default_image_user_agent")]
+    pub user_agent: String,
+    /// Request timeout in seconds
+    #[serde(default = "default_image_timeout")]
+    pub timeout_secs: u64,
+    /// Extra headers sent with every image request
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// Settings per image host (subdomains included), for hosts that block
+    /// hotlinking or need a login
+    #[serde(default)]
+    pub hosts: HashMap<String, ImageHostConfig>,
+}
+
+impl Default for ImageDownloadConfig {
+    fn default() -> Self {
+        Self {
+            user_agent: default_image_user_agent(),
+            timeout_secs: default_image_timeout(),
+            headers: HashMap::new(),
+            hosts: HashMap::new(),
+        }
+    }
+}
+
+/// Image request settings for one host
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImageHostConfig {
+    /// Referer sent instead of the image's own site ("" = none)
+    #[serde(default)]
+    pub referer: Option<String>,
+    /// Cookies sent to the host ("name=value; other=value"), e.g. a login
+    #[serde(default)]
+    pub cookie: Option<String>,
+    /// Extra headers, overriding the global ones
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+}
+
+/// Saving bandwidth on metered connections. Metered mode is switched at
+/// runtime with `gM` in the TUI or `kenseader daemon metered on|off`, and the
+/// TUI also turns it on while NetworkManager reports a metered connection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeteredConfig {
+    /// Start in metered mode
+    #[serde(default)]
+    pub enabled: bool,
+    /// Seconds between scheduled feed refreshes while metered
+    #[serde(default = "default_metered_refresh_interval")]
+    pub refresh_interval_secs: u64,
+    /// Images the TUI downloads at once while metered
+    #[serde(default = "default_metered_max_downloads")]
+    pub max_concurrent_downloads: usize,
+    /// Rough daily budget for fetching while metered, in MB (0 = none);
+    /// scheduled refreshes stop once it is used up
+    #[serde(default = "default_metered_daily_budget")]
+    pub daily_budget_mb: u64,
+}
+
+impl Default for MeteredConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            refresh_interval_secs: default_metered_refresh_interval(),
+            max_concurrent_downloads: default_metered_max_downloads(),
+            daily_budget_mb: default_metered_daily_budget(),
+        }
+    }
+}
+
+/// Parsing workarounds for a feed that is chronically malformed
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeedQuirks {
+    /// Charset the feed is really encoded in, overriding its declaration
+    /// (e.g. "windows-1252", "gb18030")
+    #[serde(default)]
+    pub charset: Option<String>,
+    /// chrono format of the feed's non-standard dates (e.g. "%d/%m/%Y %H:%M")
+    #[serde(default)]
+    pub date_format: Option<String>,
+    /// Prefix removed from article titles (e.g. "[Sponsored] ")
+    #[serde(default)]
+    pub strip_title_prefix: Option<String>,
+    /// Use the description as the article content when the feed puts the
+    /// real text there and junk in `content:encoded`
+    #[serde(default)]
+    pub description_as_content: bool,
+}
+
+/// Audio format of the briefings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AudioFormat {
+    #[default]
+    Mp3,
+    Ogg,
+}
+
+impl AudioFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            AudioFormat::Mp3 => "mp3",
+            AudioFormat::Ogg => "ogg",
+        }
+    }
+
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            AudioFormat::Mp3 => "audio/mpeg",
+            AudioFormat::Ogg => "audio/ogg",
         }
     }
 }
@@ -401,9 +1392,14 @@ impl Default for RsshubConfig {
 }
 
 /// Keymap configuration using Vim-style notation
-/// Format: "j", "k", "<C-j>" (Ctrl+j), "<S-g>" (Shift+g), "<CR>" (Enter), "<Esc>", "<Tab>", "<Space>"
+/// Format: "j", "k", "<C-j>" (Ctrl+j), "<S-g>" (Shift+g), "<CR>" (Enter), "<Esc>", "<Tab>", "<Space>",
+/// and two-key sequences such as "gg" or "<leader>r"
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KeymapConfig {
+    /// Key that `<leader>` stands for in other bindings (e.g. "<leader>r")
+    #[serde(default = "default_key_leader")]
+    pub leader: String,
+
     // Application control
     /// Quit the application
     #[serde(default = "default_key_quit")]
@@ -465,12 +1461,55 @@ pub struct KeymapConfig {
     /// Toggle article saved status
     #[serde(default = "default_key_toggle_saved")]
     pub toggle_saved: String,
+    /// Ask for more articles like the current one
+    #[serde(default = "default_key_more_like_this")]
+    pub more_like_this: String,
+    /// Ask for fewer articles like the current one
+    #[serde(default = "default_key_less_like_this")]
+    pub less_like_this: String,
+    /// Show the interest profile learned from reading
+    #[serde(default = "default_key_show_profile")]
+    pub show_profile: String,
+    /// Rescue a filtered article: mark it unread and ask for more like it
+    #[serde(default = "default_key_rescue_article")]
+    pub rescue_article: String,
     /// Refresh feeds
     #[serde(default = "default_key_refresh")]
     pub refresh: String,
     /// Toggle article read/unread status (or delete feed in Subscriptions)
     #[serde(default = "default_key_toggle_read")]
     pub toggle_read: String,
+    /// Mark all unread articles of the current feed as read (with
+    /// confirmation)
+    #[serde(default = "default_key_mark_all_read")]
+    pub mark_all_read: String,
+    /// Open the share menu for the current article
+    #[serde(default = "default_key_share")]
+    pub share: String,
+    /// Show the article URL as a QR code
+    #[serde(default = "default_key_show_qr")]
+    pub show_qr: String,
+    /// Read the article in an external pager
+    #[serde(default = "default_key_pager")]
+    pub pager: String,
+    /// Open the feed management screen
+    #[serde(default = "default_key_manage_feeds")]
+    pub manage_feeds: String,
+    /// Subscribe to a feed
+    #[serde(default = "default_key_add_feed")]
+    pub add_feed: String,
+    /// Pause/resume the selected feed
+    #[serde(default = "default_key_toggle_pause")]
+    pub toggle_pause: String,
+    /// Move the selected feed up
+    #[serde(default = "default_key_move_feed_up")]
+    pub move_feed_up: String,
+    /// Move the selected feed down
+    #[serde(default = "default_key_move_feed_down")]
+    pub move_feed_down: String,
+    /// Pin/unpin the selected feed
+    #[serde(default = "default_key_toggle_pin")]
+    pub toggle_pin: String,
 
     // Search
     /// Start forward search
@@ -485,6 +1524,9 @@ pub struct KeymapConfig {
     /// Go to previous search match
     #[serde(default = "default_key_prev_match")]
     pub prev_match: String,
+    /// Filter the subscriptions list by name, title or category
+    #[serde(default = "default_key_filter_feeds")]
+    pub filter_feeds: String,
 
     // View mode
     /// Toggle between All and Unread-only view
@@ -498,6 +1540,36 @@ pub struct KeymapConfig {
     /// Navigate forward in history
     #[serde(default = "default_key_history_forward")]
     pub history_forward: String,
+    /// Show recently read articles of all feeds
+    #[serde(default = "default_key_show_history")]
+    pub show_history: String,
+    /// Show articles marked read by relevance filtering
+    #[serde(default = "default_key_show_filtered")]
+    pub show_filtered: String,
+    /// Show articles that raised a keyword alert
+    #[serde(default = "default_key_show_alerts")]
+    pub show_alerts: String,
+    /// Show articles mentioning CVEs, most severe first
+    #[serde(default = "default_key_show_advisories")]
+    pub show_advisories: String,
+    /// Show the history of status messages
+    #[serde(default = "default_key_show_messages")]
+    pub show_messages: String,
+    /// Show the key bindings in effect
+    #[serde(default = "default_key_help")]
+    pub help: String,
+    /// Toggle sorting the article list by relevance score
+    #[serde(default = "default_key_sort_by_relevance")]
+    pub sort_by_relevance: String,
+    /// Toggle hiding articles below the relevance threshold
+    #[serde(default = "default_key_hide_low_relevance")]
+    pub hide_low_relevance: String,
+    /// Raise the relevance threshold
+    #[serde(default = "default_key_raise_relevance_threshold")]
+    pub raise_relevance_threshold: String,
+    /// Lower the relevance threshold
+    #[serde(default = "default_key_lower_relevance_threshold")]
+    pub lower_relevance_threshold: String,
 
     // Selection
     /// Toggle selection and move to next
@@ -520,11 +1592,102 @@ pub struct KeymapConfig {
     /// Focus previous item (image or link)
     #[serde(default = "default_key_prev_item")]
     pub prev_item: String,
+
+    // Macros
+    /// Start recording a macro into a register (`M{a-z}`), or stop recording
+    #[serde(default = "default_key_record_macro")]
+    pub record_macro: String,
+    /// Replay a macro (`@{a-z}`, `@@` for the last one; prefix a count to repeat)
+    #[serde(default = "default_key_play_macro")]
+    pub play_macro: String,
+
+    // Marks
+    /// Mark the current feed/article position (`m{a-z}`)
+    #[serde(default = "default_key_set_mark")]
+    pub set_mark: String,
+    /// Jump to a mark (`'{a-z}`, `''` back to the position before the jump)
+    #[serde(default = "default_key_jump_to_mark")]
+    pub jump_to_mark: String,
+
+    // Read aloud
+    /// Start reading the article aloud, or pause/resume
+    #[serde(default = "default_key_speak")]
+    pub speak: String,
+    /// Skip to the next sentence while reading aloud
+    #[serde(default = "default_key_speak_next")]
+    pub speak_next: String,
+    /// Go back to the previous sentence while reading aloud
+    #[serde(default = "default_key_speak_prev")]
+    pub speak_prev: String,
+    /// Stop reading aloud
+    #[serde(default = "default_key_speak_stop")]
+    pub speak_stop: String,
+    /// Play a video or podcast article in the media player
+    #[serde(default = "default_key_play")]
+    pub play: String,
+
+    // AI header of the article detail
+    /// Collapse or expand the AI header
+    #[serde(default = "default_key_toggle_ai_header")]
+    pub toggle_ai_header: String,
+    /// Show only the summary and AI metadata, hiding the article body
+    #[serde(default = "default_key_toggle_summary_only")]
+    pub toggle_summary_only: String,
+    /// Summary-first triage of the article list
+    #[serde(default = "default_key_triage")]
+    pub triage: String,
+    /// Add tags to the selected articles (or the current one)
+    #[serde(default = "default_key_tag")]
+    pub tag: String,
+
+    // Discussion
+    /// Open the article's discussion (Hacker News, Lobsters, Reddit...)
+    #[serde(default = "default_key_open_discussion")]
+    pub open_discussion: String,
+    /// Show or collapse the top comments of the discussion
+    #[serde(default = "default_key_toggle_comments")]
+    pub toggle_comments: String,
+
+    // Wayback Machine
+    /// Open the latest Wayback Machine snapshot of the article
+    #[serde(default = "default_key_open_archived")]
+    pub open_archived: String,
+    /// Load the article's web page into the detail view (from the Wayback
+    /// Machine when the original is gone)
+    #[serde(default = "default_key_load_page")]
+    pub load_page: String,
+
+    // GitHub releases
+    /// Open the diff between a GitHub release and the previous one
+    #[serde(default = "default_key_open_compare")]
+    pub open_compare: String,
+
+    /// Open the settings screen
+    #[serde(default = "default_key_settings")]
+    pub settings: String,
+    /// Load the images of the article when they aren't loaded automatically
+    #[serde(default = "default_key_load_images")]
+    pub load_images: String,
+    /// Switch metered mode on or off
+    #[serde(default = "default_key_toggle_metered")]
+    pub toggle_metered: String,
+
+    // Article outline
+    /// Show the table of contents of the article's headings
+    #[serde(default = "default_key_toc")]
+    pub toc: String,
+    /// Scroll the article to the start of the next paragraph, heading or image
+    #[serde(default = "default_key_next_element")]
+    pub next_element: String,
+    /// Scroll the article to the start of the previous element
+    #[serde(default = "default_key_prev_element")]
+    pub prev_element: String,
 }
 
 impl Default for KeymapConfig {
     fn default() -> Self {
         Self {
+            leader: default_key_leader(),
             quit: default_key_quit(),
             focus_left: default_key_focus_left(),
             focus_right: default_key_focus_right(),
@@ -541,26 +1704,76 @@ impl Default for KeymapConfig {
             select: default_key_select(),
             open_browser: default_key_open_browser(),
             toggle_saved: default_key_toggle_saved(),
+            more_like_this: default_key_more_like_this(),
+            less_like_this: default_key_less_like_this(),
+            show_profile: default_key_show_profile(),
+            rescue_article: default_key_rescue_article(),
             refresh: default_key_refresh(),
             toggle_read: default_key_toggle_read(),
+            mark_all_read: default_key_mark_all_read(),
+            share: default_key_share(),
+            show_qr: default_key_show_qr(),
+            pager: default_key_pager(),
+            manage_feeds: default_key_manage_feeds(),
+            add_feed: default_key_add_feed(),
+            toggle_pause: default_key_toggle_pause(),
+            move_feed_up: default_key_move_feed_up(),
+            move_feed_down: default_key_move_feed_down(),
+            toggle_pin: default_key_toggle_pin(),
             search_forward: default_key_search_forward(),
             search_backward: default_key_search_backward(),
             next_match: default_key_next_match(),
             prev_match: default_key_prev_match(),
+            filter_feeds: default_key_filter_feeds(),
             toggle_unread_only: default_key_toggle_unread_only(),
             history_back: default_key_history_back(),
             history_forward: default_key_history_forward(),
+            show_history: default_key_show_history(),
+            show_filtered: default_key_show_filtered(),
+            show_alerts: default_key_show_alerts(),
+            show_advisories: default_key_show_advisories(),
+            show_messages: default_key_show_messages(),
+            help: default_key_help(),
+            sort_by_relevance: default_key_sort_by_relevance(),
+            hide_low_relevance: default_key_hide_low_relevance(),
+            raise_relevance_threshold: default_key_raise_relevance_threshold(),
+            lower_relevance_threshold: default_key_lower_relevance_threshold(),
             toggle_select: default_key_toggle_select(),
             visual_mode: default_key_visual_mode(),
             open_item: default_key_open_item(),
             view_image: default_key_view_image(),
             next_item: default_key_next_item(),
             prev_item: default_key_prev_item(),
+            record_macro: default_key_record_macro(),
+            play_macro: default_key_play_macro(),
+            set_mark: default_key_set_mark(),
+            jump_to_mark: default_key_jump_to_mark(),
+            speak: default_key_speak(),
+            speak_next: default_key_speak_next(),
+            speak_prev: default_key_speak_prev(),
+            speak_stop: default_key_speak_stop(),
+            play: default_key_play(),
+            toggle_ai_header: default_key_toggle_ai_header(),
+            toggle_summary_only: default_key_toggle_summary_only(),
+            triage: default_key_triage(),
+            tag: default_key_tag(),
+            open_discussion: default_key_open_discussion(),
+            toggle_comments: default_key_toggle_comments(),
+            open_archived: default_key_open_archived(),
+            load_page: default_key_load_page(),
+            open_compare: default_key_open_compare(),
+            settings: default_key_settings(),
+            load_images: default_key_load_images(),
+            toggle_metered: default_key_toggle_metered(),
+            toc: default_key_toc(),
+            next_element: default_key_next_element(),
+            prev_element: default_key_prev_element(),
         }
     }
 }
 
 // Default keymap values (Vim-style notation)
+fn default_key_leader() -> String { "\\".to_string() }
 fn default_key_quit() -> String { "q".to_string() }
 fn default_key_focus_left() -> String { "h".to_string() }
 fn default_key_focus_right() -> String { "l".to_string() }
@@ -579,19 +1792,68 @@ fn default_key_open_browser() -> String { "b".to_string() }
 fn default_key_toggle_saved() -> String { "s".to_string() }
 fn default_key_refresh() -> String { "r".to_string() }
 fn default_key_toggle_read() -> String { "d".to_string() }
+fn default_key_mark_all_read() -> String { "A".to_string() }
+fn default_key_share() -> String { "y".to_string() }
+fn default_key_show_qr() -> String { "Q".to_string() }
+fn default_key_pager() -> String { "|".to_string() }
+fn default_key_manage_feeds() -> String { "F".to_string() }
+fn default_key_add_feed() -> String { "a".to_string() }
+fn default_key_toggle_pause() -> String { "p".to_string() }
+fn default_key_move_feed_up() -> String { "K".to_string() }
+fn default_key_move_feed_down() -> String { "J".to_string() }
+fn default_key_toggle_pin() -> String { "P".to_string() }
 fn default_key_search_forward() -> String { "/".to_string() }
-fn default_key_search_backward() -> String { "?".to_string() }
+fn default_key_search_backward() -> String { "g/".to_string() }
 fn default_key_next_match() -> String { "n".to_string() }
 fn default_key_prev_match() -> String { "N".to_string() }
+fn default_key_filter_feeds() -> String { "f".to_string() }
 fn default_key_toggle_unread_only() -> String { "i".to_string() }
 fn default_key_history_back() -> String { "u".to_string() }
 fn default_key_history_forward() -> String { "<C-r>".to_string() }
+fn default_key_show_history() -> String { "H".to_string() }
+fn default_key_show_filtered() -> String { "gf".to_string() }
+fn default_key_show_alerts() -> String { "gA".to_string() }
+fn default_key_show_advisories() -> String { "gC".to_string() }
+fn default_key_show_messages() -> String { "gm".to_string() }
+fn default_key_help() -> String { "?".to_string() }
+fn default_key_sort_by_relevance() -> String { "S".to_string() }
+fn default_key_hide_low_relevance() -> String { "z".to_string() }
+fn default_key_raise_relevance_threshold() -> String { "+".to_string() }
+fn default_key_lower_relevance_threshold() -> String { "-".to_string() }
 fn default_key_toggle_select() -> String { "<Space>".to_string() }
 fn default_key_visual_mode() -> String { "v".to_string() }
 fn default_key_open_item() -> String { "o".to_string() }
 fn default_key_view_image() -> String { "<CR>".to_string() }
 fn default_key_next_item() -> String { "<Tab>".to_string() }
 fn default_key_prev_item() -> String { "<S-Tab>".to_string() }
+fn default_key_record_macro() -> String { "M".to_string() }
+fn default_key_play_macro() -> String { "@".to_string() }
+fn default_key_set_mark() -> String { "m".to_string() }
+fn default_key_jump_to_mark() -> String { "'".to_string() }
+fn default_key_speak() -> String { "<leader>t".to_string() }
+fn default_key_speak_next() -> String { "<leader>]".to_string() }
+fn default_key_speak_prev() -> String { "<leader>[".to_string() }
+fn default_key_speak_stop() -> String { "<leader>x".to_string() }
+fn default_key_play() -> String { "w".to_string() }
+fn default_key_toggle_ai_header() -> String { "<leader>i".to_string() }
+fn default_key_toggle_summary_only() -> String { "<leader>s".to_string() }
+fn default_key_triage() -> String { "T".to_string() }
+fn default_key_tag() -> String { "t".to_string() }
+fn default_key_open_discussion() -> String { "c".to_string() }
+fn default_key_toggle_comments() -> String { "<leader>c".to_string() }
+fn default_key_open_archived() -> String { "ga".to_string() }
+fn default_key_load_page() -> String { "gp".to_string() }
+fn default_key_open_compare() -> String { "gd".to_string() }
+fn default_key_settings() -> String { "gs".to_string() }
+fn default_key_load_images() -> String { "gi".to_string() }
+fn default_key_toggle_metered() -> String { "gM".to_string() }
+fn default_key_toc() -> String { "<leader>o".to_string() }
+fn default_key_next_element() -> String { "}".to_string() }
+fn default_key_more_like_this() -> String { "<leader>+".to_string() }
+fn default_key_less_like_this() -> String { "<leader>-".to_string() }
+fn default_key_show_profile() -> String { "<leader>p".to_string() }
+fn default_key_rescue_article() -> String { "<leader>u".to_string() }
+fn default_key_prev_element() -> String { "{".to_string() }
 
 fn default_data_dir() -> PathBuf {
     dirs::data_local_dir()
@@ -603,6 +1865,10 @@ fn default_retention_days() -> u32 {
     3
 }
 
+fn default_dim_unread_after_days() -> u32 {
+    2
+}
+
 fn default_log_level() -> String {
     "info".to_string()
 }
@@ -623,6 +1889,10 @@ fn default_openai_model() -> String {
     "gpt-4o-mini".to_string()
 }
 
+fn default_azure_openai_api_version() -> String {
+    "2024-10-21".to_string()
+}
+
 fn default_gemini_model() -> String {
     "gemini-2.0-flash".to_string()
 }
@@ -651,10 +1921,70 @@ fn default_relevance_threshold() -> f64 {
     0.3 // Articles below this score are auto-marked as read
 }
 
+fn default_preference_half_life_days() -> f64 {
+    crate::profile::DEFAULT_HALF_LIFE_DAYS
+}
+
+fn default_ai_max_retries() -> u32 {
+    3
+}
+
+fn default_ai_retry_base_delay_ms() -> u64 {
+    1000
+}
+
+fn default_ai_retry_max_delay_secs() -> u64 {
+    60
+}
+
+fn default_circuit_breaker_threshold() -> u32 {
+    5
+}
+
+fn default_circuit_breaker_cooldown_secs() -> u64 {
+    300
+}
+
 fn default_tick_rate() -> u64 {
     100
 }
 
+fn default_idle_tick_rate() -> u64 {
+    1000
+}
+
+fn default_which_key_delay() -> u64 {
+    400
+}
+
+fn default_toast_timeout() -> u64 {
+    4
+}
+
+fn default_idle_timeout() -> u64 {
+    120
+}
+
+fn default_top_comments() -> usize {
+    5
+}
+
+fn default_player_command() -> String {
+    "mpv --force-window=immediate {url}".to_string()
+}
+
+fn default_status_left() -> String {
+    " {mode} | {focus} | Feeds: {feeds} | Articles: {articles}{relevance}{search}{selection}{speech}".to_string()
+}
+
+fn default_status_right() -> String {
+    " q:quit h/l:panels j/k:move /:search ?:help ".to_string()
+}
+
+fn default_terminal_title() -> String {
+    "Kenseader ({unread})".to_string()
+}
+
 fn default_refresh_interval() -> u64 {
     3600 // 1 hour - scheduler check interval
 }
@@ -663,6 +1993,18 @@ fn default_feed_refresh_interval() -> u64 {
     43200 // 12 hours - minimum interval between refreshes for each feed
 }
 
+fn default_refresh_jitter() -> u64 {
+    120
+}
+
+fn default_feed_stagger_percent() -> u32 {
+    20
+}
+
+fn default_connectivity_check() -> u64 {
+    60
+}
+
 fn default_cleanup_interval() -> u64 {
     3600 // 1 hour
 }
@@ -683,13 +2025,141 @@ fn default_rate_limit() -> u64 {
     1000
 }
 
+fn default_state_sync_interval() -> u64 {
+    300 // 5 minutes
+}
+
+fn default_integrations_interval() -> u64 {
+    900 // 15 minutes
+}
+
+fn default_mqtt_host() -> String {
+    "localhost".to_string()
+}
+
+fn default_mqtt_port() -> u16 {
+    1883
+}
+
+fn default_mqtt_client_id() -> String {
+    "kenseader".to_string()
+}
+
+fn default_mqtt_topic() -> String {
+    "kenseader".to_string()
+}
+
+fn default_mqtt_top_stories() -> usize {
+    5
+}
+
+fn default_web_address() -> String {
+    "127.0.0.1:8480".to_string()
+}
+
+fn default_web_page_size() -> u32 {
+    100
+}
+
+fn default_digest_interval_mins() -> u64 {
+    30
+}
+
+fn default_high_relevance_threshold() -> f64 {
+    0.8
+}
+
+fn default_advisories_api_url() -> String {
+    "https://services.nvd.nist.gov/rest/json/cves/2.0".to_string()
+}
+
+fn default_advisories_lookups() -> usize {
+    20
+}
+
+fn default_audio_digest_max_articles() -> usize {
+    10
+}
+
+fn default_audio_digest_keep() -> usize {
+    14
+}
+
+fn default_prune_stale_months() -> u32 {
+    3
+}
+
+fn default_prune_min_read_ratio() -> f64 {
+    0.05
+}
+
+fn default_prune_min_articles() -> u32 {
+    20
+}
+
+fn default_prune_min_relevance() -> f64 {
+    0.3
+}
+
+fn default_max_crawl_delay() -> u64 {
+    30
+}
+
+fn default_image_user_agent() -> String {
+    "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36".to_string()
+}
+
+fn default_image_timeout() -> u64 {
+    15
+}
+
+fn default_metered_refresh_interval() -> u64 {
+    3 * 3600
+}
+
+fn default_metered_max_downloads() -> usize {
+    1
+}
+
+fn default_metered_daily_budget() -> u64 {
+    50
+}
+
+fn default_db_max_connections() -> u32 {
+    15
+}
+
+fn default_db_busy_timeout() -> u64 {
+    10_000
+}
+
+fn default_db_synchronous() -> String {
+    "normal".to_string()
+}
+
+fn default_db_mmap_size() -> u64 {
+    64
+}
+
+fn default_db_wal_autocheckpoint() -> u32 {
+    2000
+}
+
+fn default_db_max_retries() -> u32 {
+    5
+}
+
+fn default_db_retry_base_delay() -> u64 {
+    200
+}
+
 fn default_rsshub_base_url() -> String {
     // Use hub.slarker.me as default since rsshub.app is protected by Cloudflare
     "https://hub.slarker.me".to_string()
 }
 
 /// Expand tilde (~) in path to user's home directory
-fn expand_tilde(path: &std::path::Path) -> PathBuf {
+pub(crate) fn expand_tilde(path: &std::path::Path) -> PathBuf {
     if let Some(path_str) = path.to_str() {
         if let Some(stripped) = path_str.strip_prefix("~/") {
             if let Some(home) = dirs::home_dir() {
@@ -704,21 +2174,134 @@ fn expand_tilde(path: &std::path::Path) -> PathBuf {
     path.to_path_buf()
 }
 
+/// Merge `overlay` into `base`: tables are merged key by key, any other
+/// value in the overlay replaces the base's
+pub fn merge_config(base: &mut toml::Value, overlay: toml::Value) {
+    match (base, overlay) {
+        (toml::Value::Table(base), toml::Value::Table(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_config(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// Read a config file with the files it includes merged on top, in order.
+/// Include paths are relative to the including file; `{hostname}` in a path
+/// names the machine, and such includes are skipped when missing.
+pub fn load_layered(path: &Path, hostname: &str) -> crate::Result<toml::Value> {
+    load_layer(path, hostname, 0)
+}
+
+fn load_layer(path: &Path, hostname: &str, depth: usize) -> crate::Result<toml::Value> {
+    let config_error = |message: String| crate::Error::Config(format!("{}: {}", path.display(), message));
+    let content = std::fs::read_to_string(path).map_err(|e| config_error(e.to_string()))?;
+    let mut value: toml::Value = toml::from_str(&content).map_err(|e| config_error(e.to_string()))?;
+    let includes = match value.as_table_mut().and_then(|table| table.remove("include")) {
+        None => Vec::new(),
+        Some(toml::Value::String(include)) => vec![include],
+        Some(toml::Value::Array(includes)) => includes
+            .into_iter()
+            .map(|include| match include {
+                toml::Value::String(include) => Ok(include),
+                _ => Err(config_error("include must list file paths".to_string())),
+            })
+            .collect::<crate::Result<_>>()?,
+        Some(_) => return Err(config_error("include must list file paths".to_string())),
+    };
+
+    for include in includes {
+        let host_specific = include.contains("{hostname}");
+        let include_path = expand_tilde(Path::new(&include.replace("{hostname}", hostname)));
+        let include_path = path.parent().unwrap_or(Path::new(".")).join(include_path);
+        if host_specific && !include_path.exists() {
+            continue;
+        }
+        if depth + 1 >= MAX_INCLUDE_DEPTH {
+            return Err(config_error(format!("includes nested deeper than {} files", MAX_INCLUDE_DEPTH)));
+        }
+        let overlay = load_layer(&include_path, hostname, depth + 1)?;
+        merge_config(&mut value, overlay);
+    }
+    Ok(value)
+}
+
+/// Change settings in a config file, keeping its comments and layout.
+/// Keys are dotted paths (`ui.image_preview`); missing tables are created,
+/// and a plain value on the way (such as the `theme = "nord"` shorthand) is
+/// replaced by a table. Other settings, includes and secret references are
+/// left as written.
+pub fn write_settings(path: &Path, settings: &[(&str, toml::Value)]) -> crate::Result<()> {
+    let config_error = |message: String| crate::Error::Config(format!("{}: {}", path.display(), message));
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.into()),
+    };
+    let mut document: toml_edit::DocumentMut = content.parse().map_err(|e: toml_edit::TomlError| config_error(e.to_string()))?;
+
+    for (key, value) in settings {
+        let mut segments: Vec<&str> = key.split('.').collect();
+        let name = segments.pop().unwrap_or_default();
+        let mut table: &mut dyn toml_edit::TableLike = document.as_table_mut();
+        for segment in segments {
+            let item = table.entry(segment).or_insert_with(|| {
+                let mut table = toml_edit::Table::new();
+                table.set_implicit(true);
+                toml_edit::Item::Table(table)
+            });
+            if !item.is_table_like() {
+                *item = toml_edit::table();
+            }
+            table = item
+                .as_table_like_mut()
+                .ok_or_else(|| config_error(format!("{} is not a table", segment)))?;
+        }
+
+        let mut new_value: toml_edit::Value = value
+            .to_string()
+            .parse()
+            .map_err(|e: toml_edit::TomlError| config_error(format!("{}: {}", key, e)))?;
+        // Keep a comment following the old value
+        if let Some(old_value) = table.get(name).and_then(toml_edit::Item::as_value) {
+            *new_value.decor_mut() = old_value.decor().clone();
+        }
+        table.insert(name, toml_edit::Item::Value(new_value));
+    }
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, document.to_string())?;
+    Ok(())
+}
+
 impl AppConfig {
-    /// Load configuration from file or return defaults
+    /// Load configuration from file or return defaults. Included files are
+    /// merged on top of it, then `${VAR}`, `file:<path>` and `keyring:<name>`
+    /// values are expanded (see `secrets`).
     pub fn load() -> crate::Result<Self> {
         let config_path = Self::config_path();
 
         if config_path.exists() {
-            let content = std::fs::read_to_string(&config_path)?;
-            toml::from_str(&content)
+            let hostname = gethostname::gethostname().to_string_lossy().into_owned();
+            let mut value = load_layered(&config_path, &hostname)?;
+            crate::secrets::expand_config_from_system(&mut value)?;
+            value.try_into()
                 .map_err(|e| crate::Error::Config(e.to_string()))
         } else {
             Ok(Self::default())
         }
     }
 
-    /// Save configuration to file
+    /// Save configuration to file (expanded values, such as secrets read
+    /// from the keyring, are written as plain values)
     pub fn save(&self) -> crate::Result<()> {
         let config_path = Self::config_path();
 
@@ -753,8 +2336,108 @@ impl AppConfig {
         self.data_dir().join("kenseader.sock")
     }
 
+    /// Get the advisory lock file that marks the database writer
+    pub fn lock_path(&self) -> PathBuf {
+        self.data_dir().join("kenseader.lock")
+    }
+
+    /// Get the state sync folder (with tilde expansion)
+    pub fn state_sync_folder(&self) -> Option<PathBuf> {
+        self.state_sync.folder.as_deref().map(expand_tilde)
+    }
+
+    /// Get the folder of the audio briefings (with tilde expansion)
+    pub fn audio_digest_dir(&self) -> PathBuf {
+        match self.audio_digest.directory {
+            Some(ref dir) => expand_tilde(dir),
+            None => self.data_dir().join("audio_digests"),
+        }
+    }
+
     /// Get the data directory (with tilde expansion)
     pub fn data_dir(&self) -> PathBuf {
         expand_tilde(&self.general.data_dir)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_config() {
+        let mut base: toml::Value = toml::from_str(
+            "[ai]\nprovider = \"claude_cli\"\nsummary_language = \"English\"\n[ui]\nimage_preview = true\n",
+        )
+        .unwrap();
+        let overlay: toml::Value = toml::from_str("[ai]\nprovider = \"openai\"\n[sync]\nrefresh_interval_secs = 0\n").unwrap();
+        merge_config(&mut base, overlay);
+        assert_eq!(base["ai"]["provider"].as_str(), Some("openai"));
+        assert_eq!(base["ai"]["summary_language"].as_str(), Some("English"));
+        assert_eq!(base["ui"]["image_preview"].as_bool(), Some(true));
+        assert_eq!(base["sync"]["refresh_interval_secs"].as_integer(), Some(0));
+    }
+
+    #[test]
+    fn test_load_layered_includes() {
+        let dir = std::env::temp_dir().join(format!("kenseader-config-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("hosts")).unwrap();
+        std::fs::write(
+            dir.join("config.toml"),
+            "include = [\"common.toml\", \"hosts/{hostname}.toml\"]\n[general]\ndata_dir = \"~/base\"\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("common.toml"), "[ai]\nprovider = \"gemini_cli\"\nsummary_language = \"Japanese\"\n").unwrap();
+        std::fs::write(dir.join("hosts/work.toml"), "[general]\ndata_dir = \"/srv/kenseader\"\n[ai]\nprovider = \"openai\"\n").unwrap();
+
+        let config: AppConfig = load_layered(&dir.join("config.toml"), "work").unwrap().try_into().unwrap();
+        assert_eq!(config.general.data_dir, PathBuf::from("/srv/kenseader"));
+        assert_eq!(config.ai.provider, "openai");
+        assert_eq!(config.ai.summary_language, "Japanese");
+
+        // Machines without a host file use the shared settings
+        let config: AppConfig = load_layered(&dir.join("config.toml"), "laptop").unwrap().try_into().unwrap();
+        assert_eq!(config.general.data_dir, PathBuf::from("~/base"));
+        assert_eq!(config.ai.provider, "gemini_cli");
+
+        // Other missing includes and include cycles are errors
+        std::fs::write(dir.join("common.toml"), "include = \"missing.toml\"\n").unwrap();
+        assert!(load_layered(&dir.join("config.toml"), "work").is_err());
+        std::fs::write(dir.join("common.toml"), "include = \"config.toml\"\n").unwrap();
+        let error = load_layered(&dir.join("config.toml"), "work").unwrap_err().to_string();
+        assert!(error.contains("nested deeper than"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_write_settings_keeps_layout() {
+        let path = std::env::temp_dir().join(format!("kenseader-config-{}.toml", uuid::Uuid::new_v4()));
+        std::fs::write(
+            &path,
+            "include = \"hosts/{hostname}.toml\"\n\n[ui]\ntheme = \"nord\"\nimage_preview = true # Show images\n\n[ai]\nopenai_api_key = \"keyring:openai\"\n",
+        )
+        .unwrap();
+
+        write_settings(
+            &path,
+            &[
+                ("ui.image_preview", toml::Value::Boolean(false)),
+                ("ui.theme.name", toml::Value::String("dracula".to_string())),
+                ("sync.refresh_interval_secs", toml::Value::Integer(600)),
+            ],
+        )
+        .unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with("include = \"hosts/{hostname}.toml\"\n"));
+        assert!(content.contains("image_preview = false # Show images\n"));
+        assert!(content.contains("openai_api_key = \"keyring:openai\"\n"));
+
+        let config: AppConfig = toml::from_str(&content.replace("include = \"hosts/{hostname}.toml\"", "")).unwrap();
+        assert_eq!(config.ui.theme.name, "dracula");
+        assert!(!config.ui.image_preview);
+        assert_eq!(config.sync.refresh_interval_secs, 600);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -26,6 +26,17 @@ pub enum Error {
     #[error("AI provider error: {0}")]
     AiProvider(String),
 
+    /// Transient provider failure (rate limit, overload, network) worth retrying
+    #[error("AI provider temporarily unavailable: {message}")]
+    AiTransient {
+        message: String,
+        /// Delay requested by the provider (Retry-After)
+        retry_after: Option<std::time::Duration>,
+    },
+
+    #[error("AI provider unavailable: {0}")]
+    AiUnavailable(String),
+
     #[error("Article not found: {0}")]
     ArticleNotFound(String),
 
@@ -35,8 +46,19 @@ pub enum Error {
     #[error("Invalid RSSHub URL: {0}")]
     InvalidRsshubUrl(String),
 
+    #[error("Network unreachable: {0}")]
+    Offline(String),
+
     #[error("{0}")]
     Other(String),
 }
 
+impl Error {
+    /// Whether this error means the network (or DNS) could not be reached.
+    /// Timeouts don't count: a slow server says nothing about the network.
+    pub fn is_connectivity(&self) -> bool {
+        matches!(self, Error::Http(e) if e.is_connect())
+    }
+}
+
 pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,92 @@
+//! Metered mode and the bandwidth used by fetches today
+//!
+//! Both are kept per process, so the scheduler, the IPC server and every
+//! fetcher of the daemon see the same state.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+use chrono::{Local, NaiveDate};
+
+static METERED: AtomicBool = AtomicBool::new(false);
+
+/// Bytes downloaded during a day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DailyUsage {
+    pub day: NaiveDate,
+    pub bytes: u64,
+}
+
+impl DailyUsage {
+    /// Count bytes downloaded on `day`, starting over on a new day
+    pub fn add(&mut self, day: NaiveDate, bytes: u64) {
+        if day != self.day {
+            *self = DailyUsage { day, bytes: 0 };
+        }
+        self.bytes = self.bytes.saturating_add(bytes);
+    }
+
+    /// Bytes downloaded on `day`
+    pub fn on(&self, day: NaiveDate) -> u64 {
+        if day == self.day {
+            self.bytes
+        } else {
+            0
+        }
+    }
+}
+
+fn usage() -> &'static Mutex<DailyUsage> {
+    static USAGE: OnceLock<Mutex<DailyUsage>> = OnceLock::new();
+    USAGE.get_or_init(|| {
+        Mutex::new(DailyUsage {
+            day: Local::now().date_naive(),
+            bytes: 0,
+        })
+    })
+}
+
+/// Whether metered mode is on
+pub fn is_metered() -> bool {
+    METERED.load(Ordering::Relaxed)
+}
+
+/// Switch metered mode on or off
+pub fn set_metered(on: bool) {
+    METERED.store(on, Ordering::Relaxed);
+}
+
+/// Count a downloaded response body
+pub fn record_download(bytes: usize) {
+    usage().lock().unwrap().add(Local::now().date_naive(), bytes as u64);
+}
+
+/// Bytes downloaded today
+pub fn used_today() -> u64 {
+    usage().lock().unwrap().on(Local::now().date_naive())
+}
+
+/// Whether metered mode is on and today's downloads used up a budget of
+/// `budget_mb` megabytes (0 = no budget)
+pub fn budget_exhausted(budget_mb: u64) -> bool {
+    is_metered() && budget_mb > 0 && used_today() >= budget_mb.saturating_mul(1024 * 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_daily_usage_resets_on_new_day() {
+        let day = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        let next_day = day.succ_opt().unwrap();
+        let mut usage = DailyUsage { day, bytes: 0 };
+        usage.add(day, 300);
+        usage.add(day, 200);
+        assert_eq!(usage.on(day), 500);
+        assert_eq!(usage.on(next_day), 0);
+
+        usage.add(next_day, 100);
+        assert_eq!(usage, DailyUsage { day: next_day, bytes: 100 });
+    }
+}
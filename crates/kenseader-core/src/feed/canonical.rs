@@ -0,0 +1,123 @@
+use url::{form_urlencoded, Url};
+
+/// Query parameters that only track where a click came from
+const TRACKING_PARAMS: &[&str] = &[
+    "fbclid",
+    "gclid",
+    "dclid",
+    "msclkid",
+    "yclid",
+    "igshid",
+    "mc_cid",
+    "mc_eid",
+    "_hsenc",
+    "_hsmi",
+    "ref_src",
+    "ref_url",
+    "spm",
+];
+
+/// Check if a query parameter is a tracking parameter
+fn is_tracking_param(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    name.starts_with("utm_") || TRACKING_PARAMS.contains(&name.as_str())
+}
+
+/// Normalize an article URL so the same article always maps to the same string.
+///
+/// Lowercases scheme and host, drops default ports, fragments and tracking
+/// parameters (utm_*, fbclid, ...). Unparseable or non-http URLs are returned
+/// unchanged.
+pub fn canonicalize_url(url: &str) -> String {
+    let Ok(mut parsed) = Url::parse(url.trim()) else {
+        return url.to_string();
+    };
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return url.to_string();
+    }
+
+    parsed.set_fragment(None);
+
+    // Kept parameters stay exactly as written: re-encoding them would turn
+    // `%20` into `+` and `?flag` into `?flag=`
+    if let Some(query) = parsed.query() {
+        let segments: Vec<&str> = query.split('&').collect();
+        let kept: Vec<&str> = segments
+            .iter()
+            .copied()
+            .filter(|segment| {
+                form_urlencoded::parse(segment.as_bytes())
+                    .next()
+                    .is_none_or(|(name, _)| !is_tracking_param(&name))
+            })
+            .collect();
+
+        if kept.is_empty() {
+            parsed.set_query(None);
+        } else if kept.len() < segments.len() {
+            let query = kept.join("&");
+            parsed.set_query(Some(&query));
+        }
+    }
+
+    parsed.to_string()
+}
+
+/// Form of a feed URL under which equivalent subscriptions compare equal:
+/// canonicalized, and also ignoring the scheme, `www.` and a trailing slash
+pub fn feed_url_key(url: &str) -> String {
+    let canonical = canonicalize_url(url);
+    let key = canonical
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .trim_start_matches("www.");
+    key.trim_end_matches('/').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strips_tracking_params() {
+        assert_eq!(
+            canonicalize_url("https://example.com/post?utm_source=rss&utm_medium=feed"),
+            "https://example.com/post"
+        );
+        assert_eq!(
+            canonicalize_url("https://example.com/post?id=42&fbclid=abc&UTM_Campaign=x"),
+            "https://example.com/post?id=42"
+        );
+    }
+
+    #[test]
+    fn test_kept_params_are_not_reencoded() {
+        let url = "https://example.com/search?q=a%20b&flag&x=1+2";
+        assert_eq!(canonicalize_url(url), url);
+        assert_eq!(
+            canonicalize_url("https://example.com/search?q=a%20b&utm_source=rss&flag"),
+            "https://example.com/search?q=a%20b&flag"
+        );
+    }
+
+    #[test]
+    fn test_normalizes_scheme_host_and_fragment() {
+        assert_eq!(
+            canonicalize_url("HTTPS://Example.COM:443/Post#comments"),
+            "https://example.com/Post"
+        );
+    }
+
+    #[test]
+    fn test_feed_url_key() {
+        assert_eq!(feed_url_key("http://www.Example.com/feed/?utm_source=x"), "example.com/feed");
+        assert_eq!(feed_url_key("https://example.com/feed"), "example.com/feed");
+        assert_ne!(feed_url_key("https://example.com/feed?page=2"), feed_url_key("https://example.com/feed"));
+    }
+
+    #[test]
+    fn test_keeps_non_http_urls() {
+        assert_eq!(canonicalize_url("mailto:a@example.com"), "mailto:a@example.com");
+        assert_eq!(canonicalize_url("not a url"), "not a url");
+    }
+}
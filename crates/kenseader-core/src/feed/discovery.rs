@@ -0,0 +1,187 @@
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// MIME types of feeds advertised with `<link rel="alternate">`
+const FEED_TYPES: &[&str] = &[
+    "application/rss+xml",
+    "application/atom+xml",
+    "application/feed+json",
+    "application/json",
+    "application/xml",
+    "text/xml",
+];
+
+/// Feed found behind a URL
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiscoveredFeed {
+    /// URL of the feed itself
+    pub url: String,
+    pub title: Option<String>,
+    /// Where requesting the feed URL ends up after redirects, when that is
+    /// somewhere else
+    #[serde(default)]
+    pub redirected_to: Option<String>,
+}
+
+/// Find the feeds an HTML page advertises with `<link rel="alternate">`,
+/// resolving relative links against `base`
+pub fn discover_feed_links(html: &str, base: &Url) -> Vec<DiscoveredFeed> {
+    let lower = html.to_ascii_lowercase();
+    let mut feeds: Vec<DiscoveredFeed> = Vec::new();
+    let mut pos = 0;
+
+    while let Some(start) = lower[pos..].find("<link") {
+        let start = pos + start;
+        let Some(end) = lower[start..].find('>') else {
+            break;
+        };
+        let end = start + end;
+        pos = end;
+
+        let attrs = parse_attributes(&html[start + 5..end]);
+        let attr = |name: &str| {
+            attrs
+                .iter()
+                .find(|(k, _)| k == name)
+                .map(|(_, v)| v.as_str())
+        };
+
+        let is_alternate = attr("rel").is_some_and(|rel| {
+            rel.split_ascii_whitespace()
+                .any(|r| r.eq_ignore_ascii_case("alternate"))
+        });
+        let is_feed = attr("type").is_some_and(|t| {
+            FEED_TYPES.iter().any(|ft| t.trim().eq_ignore_ascii_case(ft))
+        });
+        if !is_alternate || !is_feed {
+            continue;
+        }
+
+        let Some(url) = attr("href").and_then(|href| base.join(href.trim()).ok()) else {
+            continue;
+        };
+        let url = url.to_string();
+        if feeds.iter().any(|f| f.url == url) {
+            continue;
+        }
+        feeds.push(DiscoveredFeed {
+            url,
+            title: attr("title")
+                .map(|t| t.trim().to_string())
+                .filter(|t| !t.is_empty()),
+            redirected_to: None,
+        });
+    }
+
+    feeds
+}
+
+/// Parse `name="value"` pairs of a tag (names lowercased)
+fn parse_attributes(tag: &str) -> Vec<(String, String)> {
+    let mut attrs = Vec::new();
+    let mut chars = tag.char_indices().peekable();
+
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() || c == '/' {
+            chars.next();
+            continue;
+        }
+
+        // Attribute name
+        let mut name_end = start;
+        while let Some(&(i, c)) = chars.peek() {
+            if c.is_whitespace() || c == '=' || c == '/' {
+                break;
+            }
+            name_end = i + c.len_utf8();
+            chars.next();
+        }
+        let name = tag[start..name_end].to_ascii_lowercase();
+
+        while chars.peek().is_some_and(|(_, c)| c.is_whitespace()) {
+            chars.next();
+        }
+        if chars.peek().map(|(_, c)| *c) != Some('=') {
+            attrs.push((name, String::new()));
+            continue;
+        }
+        chars.next();
+        while chars.peek().is_some_and(|(_, c)| c.is_whitespace()) {
+            chars.next();
+        }
+
+        // Quoted or bare value
+        let value = match chars.peek().map(|(_, c)| *c) {
+            Some(quote @ ('"' | '\'')) => {
+                chars.next();
+                let mut value = String::new();
+                for (_, c) in chars.by_ref() {
+                    if c == quote {
+                        break;
+                    }
+                    value.push(c);
+                }
+                value
+            }
+            _ => {
+                let mut value = String::new();
+                while let Some(&(_, c)) = chars.peek() {
+                    if c.is_whitespace() {
+                        break;
+                    }
+                    value.push(c);
+                    chars.next();
+                }
+                value
+            }
+        };
+        attrs.push((name, value.replace("&amp;", "&")));
+    }
+
+    attrs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discover_feed_links() {
+        let base = Url::parse("https://example.com/blog/").unwrap();
+        let html = r#"<html><head>
+            <link rel="stylesheet" href="/style.css">
+            <LINK REL="alternate" TYPE="application/rss+xml" TITLE="Posts" HREF="feed.xml">
+            <link type='application/atom+xml' rel='alternate' href='https://example.com/atom?a=1&amp;b=2'/>
+            <link rel=alternate type=application/rss+xml href=/blog/feed.xml>
+        </head></html>"#;
+
+        let feeds = discover_feed_links(html, &base);
+        assert_eq!(
+            feeds,
+            vec![
+                DiscoveredFeed {
+                    url: "https://example.com/blog/feed.xml".to_string(),
+                    title: Some("Posts".to_string()),
+                    redirected_to: None,
+                },
+                DiscoveredFeed {
+                    url: "https://example.com/atom?a=1&b=2".to_string(),
+                    title: None,
+                    redirected_to: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_attributes() {
+        assert_eq!(
+            parse_attributes(r#" rel="alternate" hidden href = 'a b' "#),
+            vec![
+                ("rel".to_string(), "alternate".to_string()),
+                ("hidden".to_string(), String::new()),
+                ("href".to_string(), "a b".to_string()),
+            ]
+        );
+    }
+}
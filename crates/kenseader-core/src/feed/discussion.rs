@@ -0,0 +1,229 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use url::Url;
+
+/// Top-level comment of a discussion thread
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Comment {
+    pub author: String,
+    /// Plain text, paragraphs separated by blank lines
+    pub text: String,
+    /// Votes, when the site exposes them
+    pub score: Option<i64>,
+}
+
+/// Site hosting a discussion thread
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Site {
+    HackerNews,
+    Lobsters,
+    Reddit,
+}
+
+fn site(url: &Url) -> Option<Site> {
+    let host = url.host_str()?.trim_start_matches("www.").trim_start_matches("old.");
+    match host {
+        "news.ycombinator.com" => Some(Site::HackerNews),
+        "lobste.rs" => Some(Site::Lobsters),
+        "reddit.com" => Some(Site::Reddit),
+        _ => None,
+    }
+}
+
+/// JSON API URL serving the comments of a discussion page, for the sites
+/// whose comments can be fetched (Hacker News, Lobsters, Reddit)
+pub fn comments_api_url(discussion_url: &str) -> Option<String> {
+    let mut url = Url::parse(discussion_url).ok()?;
+    url.set_fragment(None);
+    match site(&url)? {
+        Site::HackerNews => {
+            let id = url.query_pairs().find(|(k, _)| k == "id")?.1;
+            Some(format!("https://hn.algolia.com/api/v1/items/{}", id))
+        }
+        Site::Lobsters | Site::Reddit => {
+            url.set_query(None);
+            Some(format!("{}.json", url.as_str().trim_end_matches('/')))
+        }
+    }
+}
+
+/// The first `limit` top-level comments of an API response fetched from
+/// [`comments_api_url`]
+pub fn parse_comments(discussion_url: &str, json: &Value, limit: usize) -> Vec<Comment> {
+    let Some(site) = Url::parse(discussion_url).ok().as_ref().and_then(site) else {
+        return Vec::new();
+    };
+    let comments = match site {
+        Site::HackerNews => hacker_news_comments(json),
+        Site::Lobsters => lobsters_comments(json),
+        Site::Reddit => reddit_comments(json),
+    };
+    comments
+        .filter(|c| !c.text.is_empty())
+        .take(limit)
+        .collect()
+}
+
+/// Hacker News (Algolia items API): `children` of the story
+fn hacker_news_comments(json: &Value) -> Box<dyn Iterator<Item = Comment> + '_> {
+    let children = json["children"].as_array().into_iter().flatten();
+    Box::new(children.filter_map(|child| {
+        Some(Comment {
+            author: child["author"].as_str()?.to_string(),
+            text: html_to_text(child["text"].as_str()?),
+            score: child["points"].as_i64(),
+        })
+    }))
+}
+
+/// Lobsters: flattened `comments`, top-level ones at depth 0 (or indent 1)
+fn lobsters_comments(json: &Value) -> Box<dyn Iterator<Item = Comment> + '_> {
+    let comments = json["comments"].as_array().into_iter().flatten();
+    Box::new(
+        comments
+            .filter(|c| c["depth"].as_i64() == Some(0) || c["indent_level"].as_i64() == Some(1))
+            .filter_map(|c| {
+                // Older API versions nest the user in an object
+                let author = c["commenting_user"]
+                    .as_str()
+                    .or_else(|| c["commenting_user"]["username"].as_str())?;
+                let text = match c["comment_plain"].as_str() {
+                    Some(plain) => plain.trim().to_string(),
+                    None => html_to_text(c["comment"].as_str()?),
+                };
+                Some(Comment {
+                    author: author.to_string(),
+                    text,
+                    score: c["score"].as_i64(),
+                })
+            }),
+    )
+}
+
+/// Reddit: the second listing of the response holds the comments
+fn reddit_comments(json: &Value) -> Box<dyn Iterator<Item = Comment> + '_> {
+    let children = json[1]["data"]["children"].as_array().into_iter().flatten();
+    Box::new(
+        children
+            .filter(|child| child["kind"] == "t1")
+            .filter_map(|child| {
+                let data = &child["data"];
+                Some(Comment {
+                    author: data["author"].as_str()?.to_string(),
+                    text: data["body"].as_str()?.trim().to_string(),
+                    score: data["score"].as_i64(),
+                })
+            }),
+    )
+}
+
+/// Hacker News search (Algolia API) for stories linking to `article_url`
+pub fn hn_search_url(article_url: &str) -> String {
+    let mut url = Url::parse("https://hn.algolia.com/api/v1/search").expect("valid URL");
+    url.query_pairs_mut()
+        .append_pair("query", article_url)
+        .append_pair("restrictSearchableAttributes", "url")
+        .append_pair("tags", "story");
+    url.into()
+}
+
+/// Discussion page of the most commented story in a [`hn_search_url`]
+/// response that links to `article_url`
+pub fn parse_hn_search(json: &Value, article_url: &str) -> Option<String> {
+    let wanted = comparable_url(article_url);
+    json["hits"]
+        .as_array()?
+        .iter()
+        .filter(|hit| hit["url"].as_str().map(comparable_url).as_deref() == Some(wanted.as_str()))
+        .max_by_key(|hit| hit["num_comments"].as_i64().unwrap_or(0))
+        .and_then(|hit| hit["objectID"].as_str())
+        .map(|id| format!("https://news.ycombinator.com/item?id={}", id))
+}
+
+/// URL without scheme, `www.`, fragment and trailing slash, so links to the
+/// same page compare equal
+fn comparable_url(url: &str) -> String {
+    let url = url.split('#').next().unwrap_or(url);
+    let url = url
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .trim_start_matches("www.");
+    url.trim_end_matches('/').to_string()
+}
+
+fn html_to_text(html: &str) -> String {
+    html2text::from_read(html.as_bytes(), 1000)
+        .map(|text| text.trim().to_string())
+        .unwrap_or_else(|_| html.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_comments_api_url() {
+        assert_eq!(
+            comments_api_url("https://news.ycombinator.com/item?id=42").as_deref(),
+            Some("https://hn.algolia.com/api/v1/items/42")
+        );
+        assert_eq!(
+            comments_api_url("https://lobste.rs/s/abc123/title#c_x").as_deref(),
+            Some("https://lobste.rs/s/abc123/title.json")
+        );
+        assert_eq!(
+            comments_api_url("https://old.reddit.com/r/rust/comments/1/post/?ref=x").as_deref(),
+            Some("https://old.reddit.com/r/rust/comments/1/post.json")
+        );
+        assert_eq!(comments_api_url("https://example.com/blog#comments"), None);
+    }
+
+    #[test]
+    fn test_parse_comments() {
+        let hn = json!({"children": [
+            {"author": "pg", "text": "<p>First</p><p>Second &amp; last</p>", "points": null},
+            {"author": null, "text": null},
+            {"author": "dang", "text": "Reply"}
+        ]});
+        let comments = parse_comments("https://news.ycombinator.com/item?id=1", &hn, 5);
+        assert_eq!(comments.len(), 2);
+        assert_eq!(comments[0].author, "pg");
+        assert!(comments[0].text.contains("Second & last"));
+
+        let lobsters = json!({"comments": [
+            {"commenting_user": "alice", "comment_plain": "Top", "score": 3, "depth": 0},
+            {"commenting_user": "bob", "comment_plain": "Reply", "score": 1, "depth": 1},
+            {"commenting_user": {"username": "carol"}, "comment_plain": "Also top", "depth": 0}
+        ]});
+        let comments = parse_comments("https://lobste.rs/s/abc", &lobsters, 5);
+        let authors: Vec<_> = comments.iter().map(|c| c.author.as_str()).collect();
+        assert_eq!(authors, ["alice", "carol"]);
+        assert_eq!(comments[0].score, Some(3));
+
+        let reddit = json!([
+            {"data": {"children": [{"kind": "t3", "data": {}}]}},
+            {"data": {"children": [
+                {"kind": "t1", "data": {"author": "u1", "body": "Nice", "score": 10}},
+                {"kind": "t1", "data": {"author": "u2", "body": "Agreed", "score": 2}},
+                {"kind": "more", "data": {}}
+            ]}}
+        ]);
+        let comments = parse_comments("https://www.reddit.com/r/rust/comments/1/post/", &reddit, 1);
+        assert_eq!(comments, [Comment { author: "u1".into(), text: "Nice".into(), score: Some(10) }]);
+    }
+
+    #[test]
+    fn test_parse_hn_search() {
+        let response = json!({"hits": [
+            {"objectID": "1", "url": "https://example.com/other", "num_comments": 90},
+            {"objectID": "2", "url": "http://www.example.com/post/", "num_comments": 3},
+            {"objectID": "3", "url": "https://example.com/post", "num_comments": 40}
+        ]});
+        assert_eq!(
+            parse_hn_search(&response, "https://example.com/post").as_deref(),
+            Some("https://news.ycombinator.com/item?id=3")
+        );
+        assert_eq!(parse_hn_search(&response, "https://example.com/none"), None);
+    }
+}
@@ -1,19 +1,30 @@
 use bytes::Bytes;
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, ACCEPT_ENCODING, ACCEPT_LANGUAGE, REFERER, USER_AGENT};
-use reqwest::{Client, Proxy};
+use reqwest::header::LOCATION;
+use reqwest::redirect::Policy;
+use reqwest::{Client, Proxy, Response};
 use url::Url;
 use uuid::Uuid;
 
-use super::parser::{parse_feed, ParsedFeed};
-use crate::config::AppConfig;
+use super::bandwidth::record_download;
+use super::discovery::{discover_feed_links, DiscoveredFeed};
+use super::discussion::{comments_api_url, hn_search_url, parse_comments, parse_hn_search, Comment};
+use super::metadata::{default_icon_url, find_icon_link, is_permanent_redirect, FeedMetadata};
+use super::page::{
+    availability_url, is_gone_status, main_content, parse_availability, raw_snapshot_url, FetchedPage,
+};
+use super::parser::{parse_feed, parse_feed_with_quirks, resolve_relative_urls, ParsedFeed};
+use super::robots::{cache_crawl_delay, cached_crawl_delay, crawl_delay_override, parse_crawl_delay, reserve_fetch};
+use crate::config::{AppConfig, FeedQuirks, RobotsConfig};
 use crate::{Error, Result};
 
 const RSSHUB_SCHEME: &str = "rsshub";
 const MAX_FEED_BYTES: usize = 5 * 1024 * 1024;
 const MAX_RETRIES: u32 = 3;
 const INITIAL_RETRY_DELAY_MS: u64 = 500;
+const MAX_REDIRECTS: usize = 10;
 
 // Rotating User-Agent pool - mimics different browsers for better compatibility
 static USER_AGENT_INDEX: AtomicUsize = AtomicUsize::new(0);
@@ -38,33 +49,52 @@ fn next_user_agent() -> &'static str {
     USER_AGENTS[index]
 }
 
+/// Response of a fetch, after following redirects
+struct Fetched {
+    status: reqwest::StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+    /// URL the response came from
+    url: String,
+    /// URL the requested one permanently moved to (every redirect a 301/308)
+    moved_to: Option<String>,
+}
+
 /// Feed fetcher with HTTP client and RSSHub support
 pub struct FeedFetcher {
     client: Client,
+    /// Client that doesn't follow redirects, to tell permanent ones apart
+    redirectless_client: Client,
     rsshub_base_url: String,
     rsshub_access_key: Option<String>,
+    robots: RobotsConfig,
 }
 
 impl FeedFetcher {
     /// Create a new feed fetcher with configuration
     pub fn new(config: &AppConfig) -> Result<Self> {
-        let client = Self::build_client(config.sync.request_timeout_secs, &config.sync.proxy_url)?;
+        let timeout_secs = config.sync.request_timeout_secs;
+        let proxy_url = &config.sync.proxy_url;
+        let client = Self::build_client(timeout_secs, proxy_url, Policy::limited(MAX_REDIRECTS))?;
+        let redirectless_client = Self::build_client(timeout_secs, proxy_url, Policy::none())?;
 
         Ok(Self {
             client,
+            redirectless_client,
             rsshub_base_url: config.rsshub.base_url.clone(),
             rsshub_access_key: config.rsshub.access_key.clone(),
+            robots: config.robots.clone(),
         })
     }
 
     /// Build HTTP client with optional proxy
-    fn build_client(timeout_secs: u64, proxy_url: &Option<String>) -> Result<Client> {
+    fn build_client(timeout_secs: u64, proxy_url: &Option<String>, redirect: Policy) -> Result<Client> {
         let mut builder = Client::builder()
             .timeout(Duration::from_secs(timeout_secs))
             .gzip(true)
             .deflate(true)
             .brotli(true)
-            .redirect(reqwest::redirect::Policy::limited(10));
+            .redirect(redirect);
 
         // Configure proxy if provided
         if let Some(ref proxy) = proxy_url {
@@ -158,7 +188,7 @@ impl FeedFetcher {
     }
 
     /// Fetch with retry and exponential backoff
-    async fn fetch_with_retry(&self, url: &str) -> Result<(reqwest::StatusCode, HeaderMap, Bytes)> {
+    async fn fetch_with_retry(&self, url: &str) -> Result<Fetched> {
         let mut last_error = None;
         let mut delay_ms = INITIAL_RETRY_DELAY_MS;
 
@@ -173,14 +203,10 @@ impl FeedFetcher {
                 user_agent
             );
 
-            match self.client
-                .get(url)
-                .headers(headers)
-                .send()
-                .await
-            {
-                Ok(response) => {
+            match self.get_following_redirects(url, headers).await {
+                Ok((response, moved_to)) => {
                     let status = response.status();
+                    let final_url = response.url().to_string();
                     let resp_headers = response.headers().clone();
 
                     // Check if we should retry (429 Too Many Requests or 503 Service Unavailable)
@@ -212,7 +238,16 @@ impl FeedFetcher {
                     }
 
                     match response.bytes().await {
-                        Ok(bytes) => return Ok((status, resp_headers, bytes)),
+                        Ok(body) => {
+                            record_download(body.len());
+                            return Ok(Fetched {
+                                status,
+                                headers: resp_headers,
+                                body,
+                                url: final_url,
+                                moved_to,
+                            })
+                        }
                         Err(e) => {
                             tracing::warn!("Failed to read response body: {}", e);
                             last_error = Some(Error::Http(e));
@@ -226,7 +261,7 @@ impl FeedFetcher {
                         attempt + 1,
                         e
                     );
-                    last_error = Some(Error::Http(e));
+                    last_error = Some(e);
                 }
             }
 
@@ -239,13 +274,60 @@ impl FeedFetcher {
         Err(last_error.unwrap_or_else(|| Error::FeedParse(format!("Failed to fetch URL after {} retries: {}", MAX_RETRIES, url))))
     }
 
+    /// GET a URL, following redirects one at a time. Also returns where the
+    /// URL moved to when there were redirects and all of them were permanent.
+    async fn get_following_redirects(&self, url: &str, headers: HeaderMap) -> Result<(Response, Option<String>)> {
+        let mut current = Url::parse(url)?;
+        let mut permanent = None;
+        for _ in 0..=MAX_REDIRECTS {
+            let response = self.redirectless_client
+                .get(current.as_str())
+                .headers(headers.clone())
+                .send()
+                .await?;
+            let status = response.status();
+            let location = response
+                .headers()
+                .get(LOCATION)
+                .and_then(|value| value.to_str().ok())
+                .filter(|_| status.is_redirection());
+            let Some(location) = location else {
+                let moved_to = (permanent == Some(true) && current.as_str() != url).then(|| current.to_string());
+                return Ok((response, moved_to));
+            };
+            current = current.join(location)?;
+            permanent = Some(permanent.unwrap_or(true) && is_permanent_redirect(status.as_u16()));
+        }
+        Err(Error::FeedParse(format!("Too many redirects for URL: {}", url)))
+    }
+
+    /// Whether the host of `url` can be reached at all (any HTTP response,
+    /// even an error status, counts)
+    pub async fn is_reachable(&self, url: &str) -> bool {
+        let Ok(url) = self.resolve_url(url) else {
+            return true;
+        };
+        match self.client.head(&url).send().await {
+            Ok(_) => true,
+            Err(e) => !Error::Http(e).is_connectivity(),
+        }
+    }
+
     /// Fetch and parse a feed from URL
     pub async fn fetch(&self, url: &str, feed_id: Uuid) -> Result<ParsedFeed> {
+        self.fetch_with_quirks(url, feed_id, &FeedQuirks::default()).await
+    }
+
+    /// Fetch and parse a feed, working around its configured quirks
+    pub async fn fetch_with_quirks(&self, url: &str, feed_id: Uuid, quirks: &FeedQuirks) -> Result<ParsedFeed> {
         let resolved_url = self.resolve_url(url)?;
 
         tracing::info!("Fetching feed from: {}", resolved_url);
 
-        let (status, resp_headers, content) = self.fetch_with_retry(&resolved_url).await?;
+        let started = Instant::now();
+        let Fetched { status, headers: resp_headers, body: content, moved_to, .. } =
+            self.fetch_with_retry(&resolved_url).await?;
+        let fetch_ms = started.elapsed().as_millis() as u64;
 
         self.ensure_content_size(content.len(), &resolved_url)?;
 
@@ -293,7 +375,18 @@ impl FeedFetcher {
             )));
         }
 
-        parse_feed(&content, feed_id)
+        let parse_started = Instant::now();
+        let mut parsed = parse_feed_with_quirks(&content, feed_id, Some(&resolved_url), quirks)?;
+        parsed.status = Some(status.as_u16());
+        parsed.fetch_ms = fetch_ms;
+        parsed.parse_ms = parse_started.elapsed().as_millis() as u64;
+        parsed.bytes = content.len() as u64;
+        // RSSHub paths resolve to a different URL than the stored one; only
+        // plain URLs can move
+        if resolved_url == url {
+            parsed.moved_to = moved_to;
+        }
+        Ok(parsed)
     }
 
     /// Check if content is a Cloudflare challenge page
@@ -309,11 +402,13 @@ impl FeedFetcher {
             || preview.contains("challenge-platform")
     }
 
-    /// Fetch feed content as raw bytes (for testing URL validity)
-    pub async fn fetch_raw(&self, url: &str) -> Result<Vec<u8>> {
+    /// Fetch feed content as raw bytes (for testing URL validity), with the
+    /// URL it was served from after redirects
+    pub async fn fetch_raw(&self, url: &str) -> Result<(Vec<u8>, String)> {
         let resolved_url = self.resolve_url(url)?;
 
-        let (status, resp_headers, bytes) = self.fetch_with_retry(&resolved_url).await?;
+        let Fetched { status, headers: resp_headers, body: bytes, url: final_url, .. } =
+            self.fetch_with_retry(&resolved_url).await?;
 
         self.ensure_content_size(bytes.len(), &resolved_url)?;
 
@@ -361,7 +456,224 @@ impl FeedFetcher {
             )));
         }
 
-        Ok(bytes.to_vec())
+        Ok((bytes.to_vec(), final_url))
+    }
+
+    /// Find the feed behind a URL: the URL itself if it serves a feed,
+    /// otherwise the first working feed its HTML page advertises
+    pub async fn discover(&self, url: &str) -> Result<DiscoveredFeed> {
+        let resolved_url = self.resolve_url(url)?;
+        let (content, final_url) = self.fetch_raw(&resolved_url).await?;
+
+        if let Ok(parsed) = parse_feed(&content, Uuid::nil(), Some(&resolved_url)) {
+            return Ok(DiscoveredFeed {
+                redirected_to: (final_url != resolved_url).then_some(final_url),
+                url: resolved_url,
+                title: parsed.title,
+            });
+        }
+
+        let base = Url::parse(&resolved_url)?;
+        let html = String::from_utf8_lossy(&content);
+        for candidate in discover_feed_links(&html, &base) {
+            match self.fetch(&candidate.url, Uuid::nil()).await {
+                Ok(parsed) => {
+                    return Ok(DiscoveredFeed {
+                        url: candidate.url,
+                        title: parsed.title.or(candidate.title),
+                        redirected_to: parsed.moved_to,
+                    });
+                }
+                Err(e) => tracing::debug!("Discovered feed {} is unusable: {}", candidate.url, e),
+            }
+        }
+
+        Err(Error::FeedParse(format!("No feed found at URL: {}", resolved_url)))
+    }
+
+    /// Fetch the feed-level details of a feed: title, description, site and
+    /// icon (the site's favicon when the feed names none), and where the feed
+    /// moved to when every redirect on the way is permanent
+    pub async fn fetch_metadata(&self, url: &str, quirks: &FeedQuirks) -> Result<FeedMetadata> {
+        let resolved_url = self.resolve_url(url)?;
+        let fetched = self.fetch_with_retry(&resolved_url).await?;
+        if !fetched.status.is_success() {
+            return Err(Error::FeedParse(format!("HTTP {} for URL: {}", fetched.status, fetched.url)));
+        }
+        self.ensure_content_size(fetched.body.len(), &fetched.url)?;
+        let parsed = parse_feed_with_quirks(&fetched.body, Uuid::nil(), Some(&fetched.url), quirks)?;
+
+        let icon_url = match parsed.icon_url {
+            Some(icon) => Some(icon),
+            None => self.find_site_icon(parsed.site_url.as_deref().unwrap_or(&fetched.url)).await,
+        };
+        let moved_to = fetched.moved_to.filter(|_| resolved_url == url);
+        Ok(FeedMetadata {
+            title: parsed.title,
+            description: parsed.description,
+            site_url: parsed.site_url,
+            icon_url,
+            moved_to,
+        })
+    }
+
+    /// Icon of a site: the one its home page declares, else `/favicon.ico`
+    /// if it exists
+    async fn find_site_icon(&self, site_url: &str) -> Option<String> {
+        let site = Url::parse(site_url).ok()?;
+        self.wait_for_crawl_delay(&site).await;
+        if let Ok(fetched) = self.fetch_with_retry(site.as_str()).await {
+            if let Some(icon) = find_icon_link(&String::from_utf8_lossy(&fetched.body), &site) {
+                return Some(icon);
+            }
+        }
+        let favicon = default_icon_url(&site)?;
+        match self.client.head(&favicon).send().await {
+            Ok(response) if response.status().is_success() => Some(favicon),
+            _ => None,
+        }
+    }
+
+    /// Find a Hacker News discussion of an article by its URL
+    pub async fn find_discussion(&self, article_url: &str) -> Result<Option<String>> {
+        let response = self.get_json(&hn_search_url(article_url)).await?;
+        Ok(parse_hn_search(&response, article_url))
+    }
+
+    /// Fetch the first `limit` top-level comments of a discussion page
+    /// (empty for sites without a supported API)
+    pub async fn fetch_comments(&self, discussion_url: &str, limit: usize) -> Result<Vec<Comment>> {
+        let Some(api_url) = comments_api_url(discussion_url) else {
+            return Ok(Vec::new());
+        };
+        let response = self.get_json(&api_url).await?;
+        Ok(parse_comments(discussion_url, &response, limit))
+    }
+
+    /// Latest Wayback Machine snapshot of a page
+    pub async fn find_snapshot(&self, url: &str) -> Result<Option<String>> {
+        let response = self.get_json(&availability_url(url)).await?;
+        Ok(parse_availability(&response))
+    }
+
+    /// Whether a page no longer exists: it answers 404/410, or its host
+    /// can't be reached
+    pub async fn is_gone(&self, url: &str) -> bool {
+        match self.client.get(url).headers(Self::build_headers(next_user_agent())).send().await {
+            Ok(response) => is_gone_status(response.status().as_u16()),
+            Err(e) => Error::Http(e).is_connectivity(),
+        }
+    }
+
+    /// Fetch the web page of an article, falling back to its Wayback Machine
+    /// snapshot when the page is gone (404/410) or its host is unreachable
+    pub async fn fetch_page(&self, url: &str) -> Result<FetchedPage> {
+        if let Ok(page_url) = Url::parse(url) {
+            self.wait_for_crawl_delay(&page_url).await;
+        }
+        let original = self.fetch_with_retry(url).await;
+        let gone = match &original {
+            Ok(fetched) => is_gone_status(fetched.status.as_u16()),
+            Err(e) => e.is_connectivity(),
+        };
+        if !gone {
+            let fetched = original?;
+            return self.page_from_response(fetched.status, &fetched.body, url, url, None);
+        }
+
+        let Some(snapshot) = self.find_snapshot(url).await? else {
+            return Err(match original {
+                Err(e) => e,
+                Ok(fetched) => Error::FeedParse(format!(
+                    "HTTP {} for URL: {} (no archived copy)",
+                    fetched.status, url
+                )),
+            });
+        };
+        tracing::info!("{} is gone, using snapshot {}", url, snapshot);
+        let raw_url = raw_snapshot_url(&snapshot).unwrap_or_else(|| snapshot.clone());
+        let fetched = self.fetch_with_retry(&raw_url).await?;
+        self.page_from_response(fetched.status, &fetched.body, &raw_url, url, Some(snapshot))
+    }
+
+    /// Wait before fetching a page until the site's crawl delay has passed
+    /// since the previous page: the delay configured for its domain, else the
+    /// Crawl-delay of its robots.txt
+    async fn wait_for_crawl_delay(&self, url: &Url) {
+        let Some(host) = url.host_str() else {
+            return;
+        };
+        let delay = match crawl_delay_override(host, &self.robots.crawl_delay) {
+            Some(delay) => Some(delay),
+            None if self.robots.enabled => self.robots_crawl_delay(url, host).await,
+            None => None,
+        };
+        let Some(delay) = delay else {
+            return;
+        };
+        let delay = Duration::from_secs_f64(delay.clamp(0.0, self.robots.max_crawl_delay_secs as f64));
+        let wait = reserve_fetch(host, delay);
+        if !wait.is_zero() {
+            tracing::debug!("Waiting {:?} for the crawl delay of {}", wait, host);
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Crawl-delay of a site's robots.txt, fetched at most once a day
+    async fn robots_crawl_delay(&self, url: &Url, host: &str) -> Option<f64> {
+        if let Some(delay) = cached_crawl_delay(host) {
+            return delay;
+        }
+        let robots_url = url.join("/robots.txt").ok()?;
+        let delay = match self.client.get(robots_url).header(USER_AGENT, next_user_agent()).send().await {
+            Ok(response) if response.status().is_success() => response.bytes().await.ok().and_then(|body| {
+                record_download(body.len());
+                parse_crawl_delay(&String::from_utf8_lossy(&body))
+            }),
+            _ => None,
+        };
+        cache_crawl_delay(host, delay);
+        delay
+    }
+
+    /// Main content of a fetched page, with relative links resolved against
+    /// the original page URL
+    fn page_from_response(
+        &self,
+        status: reqwest::StatusCode,
+        bytes: &[u8],
+        fetched_url: &str,
+        page_url: &str,
+        snapshot_url: Option<String>,
+    ) -> Result<FetchedPage> {
+        if !status.is_success() {
+            return Err(Error::FeedParse(format!("HTTP {} for URL: {}", status, fetched_url)));
+        }
+        self.ensure_content_size(bytes.len(), fetched_url)?;
+        let html = String::from_utf8_lossy(bytes);
+        let content = main_content(&html);
+        let html = match Url::parse(page_url) {
+            Ok(base) => resolve_relative_urls(content, &base),
+            Err(_) => content.to_string(),
+        };
+        Ok(FetchedPage { html, snapshot_url })
+    }
+
+    async fn get_json(&self, url: &str) -> Result<serde_json::Value> {
+        let response = self.client
+            .get(url)
+            .header(USER_AGENT, next_user_agent())
+            .header(ACCEPT, "application/json")
+            .send()
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(Error::FeedParse(format!("HTTP {} for URL: {}", status, url)));
+        }
+        let bytes = response.bytes().await?;
+        record_download(bytes.len());
+        self.ensure_content_size(bytes.len(), url)?;
+        Ok(serde_json::from_slice(&bytes)?)
     }
 
     fn ensure_content_size(&self, size: usize, url: &str) -> Result<()> {
@@ -415,4 +727,54 @@ mod tests {
         assert!(ua2.contains("Chrome") && ua2.contains("Windows"));
         assert!(ua3.contains("Firefox") && ua3.contains("Macintosh"));
     }
+
+    /// Serve `/old` (301 to `/mid`, 308 on to `/feed.xml`), `/temp` (302 to
+    /// `/feed.xml`) and the feed itself on a local port
+    async fn serve_redirects() -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = vec![0; 4096];
+                let len = stream.read(&mut request).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&request[..len]);
+                let path = request.split_whitespace().nth(1).unwrap_or("/");
+                let response = match path {
+                    "/old" => "HTTP/1.1 301 Moved Permanently\r\nLocation: /mid\r\n".to_string(),
+                    "/mid" => "HTTP/1.1 308 Permanent Redirect\r\nLocation: /feed.xml\r\n".to_string(),
+                    "/temp" => "HTTP/1.1 302 Found\r\nLocation: /feed.xml\r\n".to_string(),
+                    _ => {
+                        let body = "<rss version=\"2.0\"><channel><title>Moved</title></channel></rss>";
+                        format!("HTTP/1.1 200 OK\r\nContent-Type: application/rss+xml\r\nContent-Length: {}\r\n\r\n{}", body.len(), body)
+                    }
+                };
+                let response = if response.ends_with("\r\n") {
+                    format!("{}Content-Length: 0\r\nConnection: close\r\n\r\n", response)
+                } else {
+                    response
+                };
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+        base
+    }
+
+    #[tokio::test]
+    async fn test_permanent_redirects_are_reported() {
+        let base = serve_redirects().await;
+        let fetcher = FeedFetcher::new(&AppConfig::default()).unwrap();
+
+        let parsed = fetcher.fetch(&format!("{}/old", base), Uuid::nil()).await.unwrap();
+        assert_eq!(parsed.title.as_deref(), Some("Moved"));
+        assert_eq!(parsed.moved_to, Some(format!("{}/feed.xml", base)));
+
+        let parsed = fetcher.fetch(&format!("{}/temp", base), Uuid::nil()).await.unwrap();
+        assert_eq!(parsed.title.as_deref(), Some("Moved"));
+        assert_eq!(parsed.moved_to, None);
+
+        let parsed = fetcher.fetch(&format!("{}/feed.xml", base), Uuid::nil()).await.unwrap();
+        assert_eq!(parsed.moved_to, None);
+    }
 }
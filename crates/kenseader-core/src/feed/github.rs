@@ -0,0 +1,317 @@
+//! GitHub release and tag feeds
+//!
+//! The release notes of `releases.atom` and `tags.atom` are GitHub's
+//! rendering of the markdown, wrapped in page chrome (permalink icons, copy
+//! buttons) that makes no sense outside the page. Feeds relaying them may
+//! also escape the HTML once more, or pass the markdown as is. Release notes
+//! are cleaned up into plain HTML when parsed, and a release links to the
+//! diff against the previous release.
+
+use std::borrow::Cow;
+
+use quick_xml::escape::{escape, unescape};
+use url::Url;
+
+use super::models::Article;
+
+/// Whether a feed URL is the release or tag feed of a GitHub repository
+/// (`https://github.com/{owner}/{repo}/releases.atom`)
+pub fn is_release_feed(feed_url: &str) -> bool {
+    let Ok(url) = Url::parse(feed_url) else {
+        return false;
+    };
+    if url.host_str() != Some("github.com") {
+        return false;
+    }
+    let segments: Vec<&str> = url.path_segments().map(|s| s.collect()).unwrap_or_default();
+    matches!(segments.as_slice(), [_, _, "releases.atom" | "tags.atom"])
+}
+
+/// Repository (`owner/repo`) and tag of a release page URL
+/// (`https://github.com/{owner}/{repo}/releases/tag/{tag}`)
+pub fn release_of(url: &str) -> Option<(String, String)> {
+    let url = Url::parse(url).ok()?;
+    if url.host_str() != Some("github.com") {
+        return None;
+    }
+    let segments: Vec<&str> = url.path_segments()?.collect();
+    match segments.as_slice() {
+        [owner, repo, "releases", "tag", tag @ ..] if !tag.is_empty() => {
+            Some((format!("{}/{}", owner, repo), tag.join("/")))
+        }
+        _ => None,
+    }
+}
+
+/// URL of the diff between a release and the one before it: the "Full
+/// Changelog" link of its notes, or else a compare of the closest earlier
+/// release of the same repository among `articles`
+pub fn compare_url(release: &Article, articles: &[Article]) -> Option<String> {
+    let (repo, tag) = release_of(release.url.as_deref()?)?;
+    let prefix = format!("https://github.com/{}/compare/", repo);
+    if let Some(link) = release.content.as_deref().and_then(|c| find_link(c, &prefix)) {
+        return Some(link);
+    }
+
+    let published = release.published_at?;
+    let (_, previous) = articles
+        .iter()
+        .filter(|a| a.id != release.id && a.published_at.is_some_and(|at| at < published))
+        .filter_map(|a| {
+            let (other_repo, other_tag) = release_of(a.url.as_deref()?)?;
+            (other_repo == repo).then_some((a.published_at, other_tag))
+        })
+        .max_by_key(|(at, _)| *at)?;
+    Some(format!("{}{}...{}", prefix, previous, tag))
+}
+
+/// First URL in `html` starting with `prefix`
+fn find_link(html: &str, prefix: &str) -> Option<String> {
+    let start = html.find(prefix)?;
+    let link = &html[start..];
+    let end = link
+        .find(|c: char| c == '"' || c == '\'' || c == '<' || c.is_whitespace())
+        .unwrap_or(link.len());
+    Some(link[..end].replace("&amp;", "&"))
+}
+
+/// Release notes as plain HTML: escaped HTML is unescaped, markdown is
+/// rendered, GitHub's permalink icons and copy buttons are dropped and the
+/// wrappers around headings and code blocks are unwrapped
+pub fn clean_release_notes(content: &str) -> String {
+    let html = if has_tags(content) {
+        Cow::Borrowed(content)
+    } else if content.contains("&lt;") {
+        unescape(content).unwrap_or(Cow::Borrowed(content))
+    } else {
+        Cow::Owned(markdown_to_html(content))
+    };
+
+    let html = strip_elements(&html, "svg", None, false);
+    let html = strip_elements(&html, "clipboard-copy", None, false);
+    let html = strip_elements(&html, "div", Some("zeroclipboard-container"), false);
+    let html = strip_elements(&html, "a", Some("anchor"), false);
+    let html = strip_elements(&html, "div", Some("markdown-heading"), true);
+    strip_elements(&html, "div", Some("highlight"), true)
+}
+
+fn has_tags(content: &str) -> bool {
+    ["p", "div", "h1", "h2", "h3", "ul", "ol", "pre", "br", "a"].iter().any(|name| {
+        content
+            .match_indices(&format!("<{}", name))
+            .any(|(at, open)| content[at + open.len()..].starts_with([' ', '>', '/']))
+    })
+}
+
+/// Remove the `name` elements (with `class` among their classes, if
+/// given), and their content unless `keep_content`. Elements nested in one
+/// of the same name aren't expected.
+fn strip_elements(html: &str, name: &str, class: Option<&str>, keep_content: bool) -> String {
+    let open = format!("<{}", name);
+    let close = format!("</{}>", name);
+    let mut result = String::with_capacity(html.len());
+    let mut remaining = html;
+
+    while let Some(start) = remaining.find(&open) {
+        let rest = &remaining[start..];
+        let Some(tag_end) = rest.find('>') else {
+            break;
+        };
+        let tag = &rest[..=tag_end];
+        let is_element = rest[open.len()..].starts_with([' ', '>', '/', '\n']);
+        let matches = class.is_none_or(|class| has_class(tag, class));
+        if !is_element || !matches {
+            result.push_str(&remaining[..start + open.len()]);
+            remaining = &remaining[start + open.len()..];
+            continue;
+        }
+        result.push_str(&remaining[..start]);
+        remaining = match rest.find(&close) {
+            _ if tag.ends_with("/>") => &rest[tag.len()..],
+            Some(end) => {
+                if keep_content {
+                    result.push_str(&rest[tag.len()..end]);
+                }
+                &rest[end + close.len()..]
+            }
+            None => "",
+        };
+    }
+    result.push_str(remaining);
+    result
+}
+
+fn has_class(tag: &str, class: &str) -> bool {
+    ["class=\"", "class='"].iter().any(|attr| {
+        tag.find(attr).is_some_and(|start| {
+            let value = &tag[start + attr.len()..];
+            let end = value.find(['"', '\'']).unwrap_or(value.len());
+            value[..end].split_whitespace().any(|c| c == class)
+        })
+    })
+}
+
+/// Render the markdown of release notes: headings, lists, fenced code
+/// blocks and paragraphs, with inline code
+fn markdown_to_html(markdown: &str) -> String {
+    let mut html = String::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut in_list = false;
+    let mut code: Option<Vec<&str>> = None;
+
+    let flush_paragraph = |html: &mut String, paragraph: &mut Vec<&str>| {
+        if !paragraph.is_empty() {
+            html.push_str(&format!("<p>{}</p>\n", inline(&paragraph.join(" "))));
+            paragraph.clear();
+        }
+    };
+
+    for line in markdown.lines() {
+        let trimmed = line.trim();
+        if let Some(lines) = code.as_mut() {
+            if trimmed.starts_with("```") {
+                html.push_str(&format!("<pre><code>{}</code></pre>\n", escape(lines.join("\n").as_str())));
+                code = None;
+            } else {
+                lines.push(line);
+            }
+            continue;
+        }
+
+        let item = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+            .or_else(|| trimmed.strip_prefix("+ "));
+        if in_list && item.is_none() {
+            html.push_str("</ul>\n");
+            in_list = false;
+        }
+
+        if trimmed.starts_with("```") {
+            flush_paragraph(&mut html, &mut paragraph);
+            code = Some(Vec::new());
+        } else if let Some(item) = item {
+            flush_paragraph(&mut html, &mut paragraph);
+            if !in_list {
+                html.push_str("<ul>\n");
+                in_list = true;
+            }
+            html.push_str(&format!("<li>{}</li>\n", inline(item)));
+        } else if let Some((level, heading)) = heading(trimmed) {
+            flush_paragraph(&mut html, &mut paragraph);
+            html.push_str(&format!("<h{0}>{1}</h{0}>\n", level, inline(heading)));
+        } else if trimmed.is_empty() {
+            flush_paragraph(&mut html, &mut paragraph);
+        } else {
+            paragraph.push(trimmed);
+        }
+    }
+    if let Some(lines) = code {
+        html.push_str(&format!("<pre><code>{}</code></pre>\n", escape(lines.join("\n").as_str())));
+    }
+    flush_paragraph(&mut html, &mut paragraph);
+    if in_list {
+        html.push_str("</ul>\n");
+    }
+    html
+}
+
+/// Level and text of an ATX heading ("## Fixes")
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    let text = line[level..].strip_prefix(' ')?;
+    (1..=6).contains(&level).then_some((level, text.trim()))
+}
+
+/// Escape a line of markdown, turning `code` spans into `<code>`
+fn inline(text: &str) -> String {
+    text.split('`')
+        .enumerate()
+        .map(|(i, part)| {
+            let part = escape(part);
+            if i % 2 == 1 {
+                format!("<code>{}</code>", part)
+            } else {
+                part.into_owned()
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use uuid::Uuid;
+
+    fn release(tag: &str, day: u32, content: Option<&str>) -> Article {
+        let at = Utc.with_ymd_and_hms(2024, 5, day, 0, 0, 0).unwrap();
+        Article {
+            feed_id: Uuid::nil(),
+            guid: tag.to_string(),
+            url: Some(format!("https://github.com/acme/tool/releases/tag/{}", tag)),
+            content: content.map(str::to_string),
+            published_at: Some(at),
+            fetched_at: at,
+            created_at: at,
+            ..Article::for_test(tag)
+        }
+    }
+
+    #[test]
+    fn test_release_feeds_are_detected() {
+        assert!(is_release_feed("https://github.com/acme/tool/releases.atom"));
+        assert!(is_release_feed("https://github.com/acme/tool/tags.atom"));
+        assert!(!is_release_feed("https://github.com/acme/tool/commits.atom"));
+        assert!(!is_release_feed("https://example.com/acme/tool/releases.atom"));
+        assert_eq!(
+            release_of("https://github.com/acme/tool/releases/tag/v1.2.0"),
+            Some(("acme/tool".to_string(), "v1.2.0".to_string()))
+        );
+        assert_eq!(release_of("https://github.com/acme/tool/pull/3"), None);
+    }
+
+    #[test]
+    fn test_release_notes_lose_page_chrome() {
+        let html = r##"<div class="markdown-heading"><h2 class="heading-element">Fixes</h2><a id="user-content-fixes" class="anchor" aria-label="Permalink: Fixes" href="#fixes"><svg class="octicon octicon-link" viewBox="0 0 16 16"><path d="m7"></path></svg></a></div>
+<div class="highlight highlight-source-shell"><pre>cargo install tool</pre><div class="zeroclipboard-container"><clipboard-copy aria-label="Copy" value="cargo install tool"><svg class="octicon"><path d="m0"></path></svg></clipboard-copy></div></div>
+<p>See <a href="https://github.com/acme/tool/pull/3">#3</a></p>"##;
+        let cleaned = clean_release_notes(html);
+        assert!(!cleaned.contains("svg"));
+        assert!(!cleaned.contains("clipboard"));
+        assert!(!cleaned.contains("Permalink"));
+        assert!(!cleaned.contains("div"));
+        assert!(cleaned.starts_with(r#"<h2 class="heading-element">Fixes</h2>"#));
+        assert!(cleaned.contains("\n<pre>cargo install tool</pre>\n"));
+        assert!(cleaned.contains(r#"<a href="https://github.com/acme/tool/pull/3">#3</a>"#));
+
+        assert_eq!(clean_release_notes("&lt;h2&gt;Fixes&lt;/h2&gt;"), "<h2>Fixes</h2>");
+    }
+
+    #[test]
+    fn test_markdown_release_notes_are_rendered() {
+        let markdown = "## What's Changed\n* Fix `--force` by @jo\n* Faster <parsing>\n\n```sh\ncargo install tool\n```\nThanks!";
+        assert_eq!(
+            clean_release_notes(markdown),
+            "<h2>What&apos;s Changed</h2>\n<ul>\n<li>Fix <code>--force</code> by @jo</li>\n\
+             <li>Faster &lt;parsing&gt;</li>\n</ul>\n<pre><code>cargo install tool</code></pre>\n<p>Thanks!</p>\n"
+        );
+    }
+
+    #[test]
+    fn test_compare_url() {
+        let changelog = r#"<p><strong>Full Changelog</strong>: <a href="https://github.com/acme/tool/compare/v1.0.0...v1.1.0">v1.0.0...v1.1.0</a></p>"#;
+        let with_link = release("v1.1.0", 10, Some(changelog));
+        assert_eq!(
+            compare_url(&with_link, &[]).as_deref(),
+            Some("https://github.com/acme/tool/compare/v1.0.0...v1.1.0")
+        );
+
+        let articles = vec![release("v1.2.0", 20, None), release("v1.1.0", 10, None), release("v1.0.0", 1, None)];
+        assert_eq!(
+            compare_url(&articles[0], &articles).as_deref(),
+            Some("https://github.com/acme/tool/compare/v1.1.0...v1.2.0")
+        );
+        assert_eq!(compare_url(&articles[2], &articles), None);
+    }
+}
@@ -0,0 +1,99 @@
+use url::Url;
+
+/// Feed-level details refreshed by the metadata task
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FeedMetadata {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub site_url: Option<String>,
+    pub icon_url: Option<String>,
+    /// URL the feed permanently redirects to (every hop a 301 or 308)
+    pub moved_to: Option<String>,
+}
+
+/// Whether a redirect status asks clients to use the new URL from now on
+pub fn is_permanent_redirect(status: u16) -> bool {
+    matches!(status, 301 | 308)
+}
+
+/// Icon declared by a page's `<link rel="icon">` (or `shortcut icon`,
+/// `apple-touch-icon`), resolved against `base`
+pub fn find_icon_link(html: &str, base: &Url) -> Option<String> {
+    // Only the head declares icons; stops before large bodies
+    let head = match html.to_ascii_lowercase().find("</head>") {
+        Some(end) => &html[..end],
+        None => html,
+    };
+    let mut fallback = None;
+    for tag in head.split('<').filter(|tag| tag.get(..5).is_some_and(|t| t.eq_ignore_ascii_case("link "))) {
+        let Some(rel) = attribute(tag, "rel") else {
+            continue;
+        };
+        let Some(href) = attribute(tag, "href") else {
+            continue;
+        };
+        let rel = rel.to_ascii_lowercase();
+        let kinds: Vec<&str> = rel.split_whitespace().collect();
+        if kinds.contains(&"icon") {
+            return base.join(href).ok().map(String::from);
+        }
+        if fallback.is_none() && kinds.contains(&"apple-touch-icon") {
+            fallback = base.join(href).ok().map(String::from);
+        }
+    }
+    fallback
+}
+
+/// Value of a (double, single or un-quoted) attribute in a tag's source
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let lower = tag.to_ascii_lowercase();
+    let mut from = 0;
+    let start = loop {
+        let pos = from + lower[from..].find(name)?;
+        let after = lower[pos + name.len()..].trim_start();
+        let preceded = pos > 0 && lower.as_bytes()[pos - 1].is_ascii_whitespace();
+        if preceded && after.starts_with('=') {
+            break tag.len() - after.len() + 1;
+        }
+        from = pos + name.len();
+    };
+    let value = tag[start..].trim_start();
+    match value.chars().next()? {
+        quote @ ('"' | '\'') => value[1..].split(quote).next(),
+        _ => value.split(|c: char| c.is_whitespace() || c == '>').next(),
+    }
+    .filter(|value| !value.is_empty())
+}
+
+/// Conventional favicon location of a site
+pub fn default_icon_url(site: &Url) -> Option<String> {
+    site.join("/favicon.ico").ok().map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_icon_link() {
+        let base = Url::parse("https://example.com/blog/").unwrap();
+        let page = r#"<html><head>
+            <link rel="apple-touch-icon" href="/touch.png">
+            <LINK REL='shortcut icon' HREF='img/fav.png' type=image/png>
+            </head><body><link rel="icon" href="/late.png"></body></html>"#;
+        assert_eq!(find_icon_link(page, &base).as_deref(), Some("https://example.com/blog/img/fav.png"));
+
+        let page = r#"<head><link rel=apple-touch-icon href=https://cdn.example.com/t.png></head>"#;
+        assert_eq!(find_icon_link(page, &base).as_deref(), Some("https://cdn.example.com/t.png"));
+        assert_eq!(find_icon_link(r#"<link rel="stylesheet" href="a.css">"#, &base), None);
+        assert_eq!(default_icon_url(&base).as_deref(), Some("https://example.com/favicon.ico"));
+    }
+
+    #[test]
+    fn test_is_permanent_redirect() {
+        assert!(is_permanent_redirect(301));
+        assert!(is_permanent_redirect(308));
+        assert!(!is_permanent_redirect(302));
+        assert!(!is_permanent_redirect(307));
+    }
+}
@@ -1,9 +1,26 @@
+pub mod bandwidth;
+mod canonical;
+mod discovery;
+mod discussion;
 mod fetcher;
+pub mod github;
+mod metadata;
 mod models;
 mod opml;
+mod page;
 mod parser;
+mod quirks;
+mod robots;
 
+pub use canonical::{canonicalize_url, feed_url_key};
+pub use discovery::{discover_feed_links, DiscoveredFeed};
+pub use discussion::Comment;
 pub use fetcher::FeedFetcher;
-pub use models::{Article, Feed, NewArticle, NewFeed};
+pub use metadata::FeedMetadata;
+pub use models::{
+    Advisory, AdvisoryArticle, AlertedArticle, Article, ArticleAlert, ArticleListing, ArticleMedia, ArticleOutcome, DryRunArticle, Feed, FeedDryRun, FeedSettings, FeedStats, FetchMetric,
+    FetchProfile, FilterReason, FilteredArticle, NewArticle, NewFeed, PageCursor, PruneSuggestion, ReadStateChange,
+};
 pub use opml::{parse_opml_file, OpmlFeed};
-pub use parser::parse_feed;
+pub use page::{snapshot_date, FetchedPage};
+pub use parser::{parse_feed, parse_feed_with_quirks};
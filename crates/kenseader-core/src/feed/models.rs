@@ -2,6 +2,9 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::canonical::feed_url_key;
+use crate::config::PruneConfig;
+
 /// Represents an RSS/Atom feed subscription
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Feed {
@@ -16,11 +19,312 @@ pub struct Feed {
     pub fetch_error: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// User-assigned category
+    #[serde(default)]
+    pub category: Option<String>,
+    /// Paused feeds are skipped by scheduled refreshes
+    #[serde(default)]
+    pub paused: bool,
+    /// Refresh interval overriding `sync.feed_refresh_interval_secs`
+    #[serde(default)]
+    pub refresh_interval_secs: Option<u64>,
+    /// Days after which unread articles are marked read, overriding
+    /// `general.auto_read_days` (0 = never)
+    #[serde(default)]
+    pub auto_read_days: Option<u32>,
+    /// Pinned feeds are listed before all others
+    #[serde(default)]
+    pub pinned: bool,
+    /// Manual sort position (ascending, within the pinned/unpinned group)
+    #[serde(default)]
+    pub position: i64,
+    /// URL the feed permanently redirects to, found by the metadata task
+    #[serde(default)]
+    pub moved_to: Option<String>,
     /// Computed field (not stored in DB)
     #[serde(default)]
     pub unread_count: u32,
 }
 
+/// User-editable settings of a feed subscription
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeedSettings {
+    pub url: String,
+    pub local_name: String,
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub paused: bool,
+    #[serde(default)]
+    pub refresh_interval_secs: Option<u64>,
+    #[serde(default)]
+    pub auto_read_days: Option<u32>,
+}
+
+/// Activity and reading statistics of a feed
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FeedStats {
+    /// Stored articles
+    pub total: u32,
+    pub unread: u32,
+    pub saved: u32,
+    /// Articles published in the last `RECENT_WEEKS` weeks
+    pub recent: u32,
+    /// Average relevance score of the scored articles
+    pub average_relevance: Option<f64>,
+    /// Publication date of the newest article
+    pub last_post_at: Option<DateTime<Utc>>,
+}
+
+impl FeedStats {
+    /// Weeks over which the posting rate is measured
+    pub const RECENT_WEEKS: i64 = 4;
+
+    /// Articles per week, over the last `RECENT_WEEKS` weeks
+    pub fn per_week(&self) -> f64 {
+        self.recent as f64 / Self::RECENT_WEEKS as f64
+    }
+
+    /// Share of the stored articles that have been read
+    pub fn read_ratio(&self) -> Option<f64> {
+        (self.total > 0).then(|| (self.total - self.unread) as f64 / self.total as f64)
+    }
+
+    /// Why a feed subscribed at `subscribed_at` may be worth unsubscribing
+    /// from. Feeds newer than the stale period aren't called stale yet.
+    pub fn prune_reasons(
+        &self,
+        subscribed_at: DateTime<Utc>,
+        config: &PruneConfig,
+        now: DateTime<Utc>,
+    ) -> Vec<String> {
+        let mut reasons = Vec::new();
+        let stale_since = now - chrono::Duration::days(30 * i64::from(config.stale_months));
+        if subscribed_at < stale_since && self.last_post_at.is_none_or(|at| at < stale_since) {
+            reasons.push(format!("no posts in {} months", config.stale_months));
+        }
+        if self.total >= config.min_articles
+            && self.read_ratio().is_some_and(|ratio| ratio < config.min_read_ratio)
+        {
+            reasons.push("rarely read".to_string());
+        }
+        if self.average_relevance.is_some_and(|score| score < config.min_relevance) {
+            reasons.push("low relevance".to_string());
+        }
+        reasons
+    }
+}
+
+/// Which articles of a feed are listed, and in what order
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ArticleListing {
+    /// Only unread articles
+    #[serde(default)]
+    pub unread_only: bool,
+    /// Most relevant first (unscored last) instead of newest first
+    #[serde(default)]
+    pub by_relevance: bool,
+    /// Leave out articles scored below this; unscored ones stay listed
+    #[serde(default)]
+    pub min_score: Option<f64>,
+}
+
+/// Where a page of a listing starts, relative to an article. The article
+/// itself no longer needs to be listed (e.g. read since in an unread-only
+/// listing).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PageCursor {
+    /// The articles listed after it
+    After(Uuid),
+    /// The articles listed before it, in list order
+    Before(Uuid),
+    /// The article itself (if listed) and the ones after it
+    From(Uuid),
+}
+
+/// Read state to store for an article
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReadStateChange {
+    pub id: Uuid,
+    pub read: bool,
+}
+
+/// Why relevance filtering marked an article read
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FilterReason {
+    pub score: f64,
+    /// Relevance threshold the score was below
+    pub threshold: f64,
+    pub filtered_at: DateTime<Utc>,
+}
+
+/// An article marked read by relevance filtering
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilteredArticle {
+    pub article: Article,
+    pub reason: FilterReason,
+}
+
+/// Why a new article raised a keyword alert
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArticleAlert {
+    /// Watchlist keyword the article contains
+    pub keyword: String,
+    pub alerted_at: DateTime<Utc>,
+}
+
+/// An article that raised a keyword alert
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertedArticle {
+    pub article: Article,
+    pub alert: ArticleAlert,
+}
+
+/// A CVE mentioned in an article, with its severity from the NVD
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Advisory {
+    pub cve_id: String,
+    /// CVSS base score; None until looked up, or when the NVD hasn't
+    /// scored the CVE yet
+    pub score: Option<f64>,
+    /// CVSS severity ("CRITICAL", "HIGH", "MEDIUM", "LOW")
+    pub severity: Option<String>,
+}
+
+/// An article mentioning CVEs, most severe first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdvisoryArticle {
+    pub article: Article,
+    pub advisories: Vec<Advisory>,
+}
+
+impl AdvisoryArticle {
+    /// Highest CVSS score of the article's CVEs
+    pub fn max_score(&self) -> Option<f64> {
+        self.advisories.iter().filter_map(|a| a.score).reduce(f64::max)
+    }
+}
+
+/// Timing of one fetch of a feed during a refresh
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FetchMetric {
+    pub feed_id: Uuid,
+    /// Time to download the feed, retries and redirects included (until
+    /// the failure for failed fetches)
+    pub fetch_ms: u64,
+    pub parse_ms: u64,
+    /// Time to store the feed's metadata and articles
+    pub store_ms: u64,
+    /// Size of the response
+    pub bytes: u64,
+    /// Items in the feed
+    pub articles: u32,
+    /// New articles stored
+    pub inserted: u32,
+    pub error: Option<String>,
+}
+
+impl FetchMetric {
+    pub fn total_ms(&self) -> u64 {
+        self.fetch_ms + self.parse_ms + self.store_ms
+    }
+}
+
+/// Fetch performance of a feed over a period, for `profile-fetch`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FetchProfile {
+    pub feed_id: Uuid,
+    pub name: String,
+    pub fetches: u32,
+    /// Fetches that failed
+    pub errors: u32,
+    pub avg_fetch_ms: f64,
+    pub avg_parse_ms: f64,
+    pub avg_store_ms: f64,
+    /// Longest fetch, parse and store together
+    pub max_total_ms: u64,
+    pub avg_bytes: f64,
+    /// New articles stored over the period
+    pub inserted: u32,
+}
+
+impl FetchProfile {
+    /// Average time of a fetch, parse and store together
+    pub fn avg_total_ms(&self) -> f64 {
+        self.avg_fetch_ms + self.avg_parse_ms + self.avg_store_ms
+    }
+}
+
+/// A feed that may be worth unsubscribing from, and why
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PruneSuggestion {
+    pub feed_id: Uuid,
+    pub name: String,
+    pub reasons: Vec<String>,
+}
+
+/// What a refresh would do with an item of a feed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArticleOutcome {
+    /// Stored as a new article
+    New,
+    /// Rewrites a stored article whose content changed
+    Updated,
+    /// Already stored as is
+    Unchanged,
+    /// Same URL and title as a stored article under another GUID, merged into it
+    Republished,
+    /// Repeats an earlier item of the same feed
+    Duplicate,
+}
+
+impl ArticleOutcome {
+    pub fn label(self) -> &'static str {
+        match self {
+            ArticleOutcome::New => "new",
+            ArticleOutcome::Updated => "updated",
+            ArticleOutcome::Unchanged => "unchanged",
+            ArticleOutcome::Republished => "republished",
+            ArticleOutcome::Duplicate => "duplicate",
+        }
+    }
+}
+
+/// An item found by a dry-run refresh
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DryRunArticle {
+    pub guid: String,
+    pub title: String,
+    pub url: Option<String>,
+    pub outcome: ArticleOutcome,
+}
+
+/// What refreshing a feed would do, without storing anything
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeedDryRun {
+    pub feed_id: Uuid,
+    pub name: String,
+    pub url: String,
+    /// Whether a scheduled refresh would fetch the feed now
+    pub due: bool,
+    /// HTTP status of a successful fetch
+    pub status: Option<u16>,
+    /// Why fetching or parsing failed
+    pub error: Option<String>,
+    /// URL the feed permanently redirects to
+    pub moved_to: Option<String>,
+    pub articles: Vec<DryRunArticle>,
+}
+
+impl FeedDryRun {
+    /// Number of items with the given outcome
+    pub fn count(&self, outcome: ArticleOutcome) -> usize {
+        self.articles.iter().filter(|a| a.outcome == outcome).count()
+    }
+}
+
 /// Data required to create a new feed
 #[derive(Debug, Clone)]
 pub struct NewFeed {
@@ -52,6 +356,27 @@ pub struct Article {
     pub relevance_score: Option<f64>,
     #[serde(default)]
     pub tags: Vec<String>,
+    /// Audio/video attachment (enclosure or Media RSS content)
+    #[serde(default)]
+    pub media: Option<ArticleMedia>,
+    /// Discussion page of the article (Hacker News, Lobsters, Reddit...)
+    #[serde(default)]
+    pub comments_url: Option<String>,
+    /// When the article content last changed after it was first fetched
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Audio/video attachment of an article, from an RSS enclosure or Media RSS
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArticleMedia {
+    pub url: String,
+    /// MIME type, e.g. `audio/mpeg`
+    pub mime_type: Option<String>,
+    /// Size in bytes
+    pub length: Option<u64>,
+    pub duration_secs: Option<u64>,
+    pub thumbnail_url: Option<String>,
 }
 
 /// Data required to create a new article
@@ -66,6 +391,8 @@ pub struct NewArticle {
     pub content_text: Option<String>,
     pub published_at: Option<DateTime<Utc>>,
     pub image_url: Option<String>,
+    pub media: Option<ArticleMedia>,
+    pub comments_url: Option<String>,
 }
 
 impl Article {
@@ -74,6 +401,31 @@ impl Article {
         self.summary.is_some()
     }
 
+    /// Check if the article is unread and more than `days` days old
+    /// (never when `days` is 0)
+    pub fn is_aging(&self, days: u32, now: DateTime<Utc>) -> bool {
+        let date = self.published_at.unwrap_or(self.fetched_at);
+        days > 0 && !self.is_read && now - date > chrono::Duration::days(i64::from(days))
+    }
+
+    /// Check if the article changed since it was last read
+    pub fn is_updated(&self) -> bool {
+        match (self.updated_at, self.read_at) {
+            (Some(updated), Some(read)) => updated > read,
+            (Some(_), None) => true,
+            _ => false,
+        }
+    }
+
+    /// URL to hand to a media player: an audio/video enclosure, or the
+    /// article URL when it points at a video
+    pub fn playable_url(&self) -> Option<&str> {
+        if let Some(media) = self.media.as_ref().filter(|m| m.is_audio() || m.is_video()) {
+            return Some(&media.url);
+        }
+        self.url.as_deref().filter(|url| is_video_url(url))
+    }
+
     /// Get a preview of the content (first N characters)
     pub fn content_preview(&self, max_len: usize) -> String {
         let text = self.content_text.as_deref()
@@ -100,9 +452,300 @@ impl Article {
     }
 }
 
+#[cfg(test)]
+impl Article {
+    /// Unread article fetched now with only a title, for tests to fill in
+    pub(crate) fn for_test(title: &str) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            feed_id: Uuid::new_v4(),
+            guid: Uuid::new_v4().to_string(),
+            url: None,
+            title: title.to_string(),
+            author: None,
+            content: None,
+            content_text: None,
+            summary: None,
+            summary_generated_at: None,
+            published_at: None,
+            fetched_at: now,
+            is_read: false,
+            read_at: None,
+            is_saved: false,
+            created_at: now,
+            image_url: None,
+            relevance_score: None,
+            tags: Vec::new(),
+            media: None,
+            comments_url: None,
+            updated_at: None,
+        }
+    }
+}
+
+#[cfg(test)]
+impl NewArticle {
+    /// Article titled "Title" with only a GUID, for tests to fill in
+    pub(crate) fn for_test(feed_id: Uuid, guid: &str) -> Self {
+        Self {
+            feed_id,
+            guid: guid.to_string(),
+            url: None,
+            title: "Title".to_string(),
+            author: None,
+            content: None,
+            content_text: None,
+            published_at: None,
+            image_url: None,
+            media: None,
+            comments_url: None,
+        }
+    }
+}
+
+/// Whether a URL is a video page (YouTube, Vimeo, PeerTube, ...) or a video file
+pub fn is_video_url(url: &str) -> bool {
+    let Ok(url) = url::Url::parse(url) else {
+        return false;
+    };
+    let host = url.host_str().unwrap_or("");
+    let host = host.strip_prefix("www.").or_else(|| host.strip_prefix("m.")).unwrap_or(host);
+    let path = url.path();
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+    let video_page = match host {
+        "youtube.com" | "music.youtube.com" => {
+            path == "/watch" || matches!(segments.first(), Some(&"shorts") | Some(&"live"))
+        }
+        "youtu.be" => !segments.is_empty(),
+        "vimeo.com" => segments.first().is_some_and(|s| s.chars().all(|c| c.is_ascii_digit())),
+        "dailymotion.com" => segments.first() == Some(&"video"),
+        "twitch.tv" => segments.first() == Some(&"videos"),
+        // PeerTube instances: /videos/watch/<uuid> and /w/<short id>
+        _ => {
+            path.starts_with("/videos/watch/")
+                || matches!(segments[..], ["w", id] if id.len() == 22 && id.chars().all(|c| c.is_ascii_alphanumeric()))
+        }
+    };
+    let video_file = path
+        .rsplit_once('.')
+        .is_some_and(|(_, ext)| matches!(ext.to_ascii_lowercase().as_str(), "mp4" | "m4v" | "webm" | "mkv" | "mov" | "m3u8"));
+    video_page || video_file
+}
+
+impl NewArticle {
+    /// Stable hash of the user-visible fields, used to detect upstream edits
+    /// without rewriting unchanged rows on every refresh
+    pub fn content_hash(&self) -> String {
+        // FNV-1a: stable across Rust versions, unlike DefaultHasher
+        const OFFSET: u64 = 0xcbf29ce484222325;
+        const PRIME: u64 = 0x100000001b3;
+
+        let fields = [
+            Some(self.title.as_str()),
+            self.url.as_deref(),
+            self.author.as_deref(),
+            self.content.as_deref(),
+            self.image_url.as_deref(),
+            self.media.as_ref().map(|m| m.url.as_str()),
+        ];
+
+        let mut hash = OFFSET;
+        for field in fields {
+            for byte in field.unwrap_or("").bytes().chain(std::iter::once(0)) {
+                hash ^= byte as u64;
+                hash = hash.wrapping_mul(PRIME);
+            }
+        }
+        format!("{:016x}", hash)
+    }
+}
+
+impl ArticleMedia {
+    /// Check if the attachment is audio (podcast episode)
+    pub fn is_audio(&self) -> bool {
+        self.mime_type.as_deref().is_some_and(|t| t.starts_with("audio/"))
+    }
+
+    /// Check if the attachment is video
+    pub fn is_video(&self) -> bool {
+        self.mime_type.as_deref().is_some_and(|t| t.starts_with("video/"))
+    }
+}
+
 impl Feed {
     /// Check if the feed has a fetch error
     pub fn has_error(&self) -> bool {
         self.fetch_error.is_some()
     }
+
+    /// Whether `url` subscribes to this feed: the same URL up to
+    /// canonicalization, or the URL the feed moved to
+    pub fn matches_url(&self, url: &str) -> bool {
+        let key = feed_url_key(url);
+        feed_url_key(&self.url) == key || self.moved_to.as_deref().is_some_and(|moved| feed_url_key(moved) == key)
+    }
+
+    /// Current user-editable settings
+    pub fn settings(&self) -> FeedSettings {
+        FeedSettings {
+            url: self.url.clone(),
+            local_name: self.local_name.clone(),
+            category: self.category.clone(),
+            paused: self.paused,
+            refresh_interval_secs: self.refresh_interval_secs,
+            auto_read_days: self.auto_read_days,
+        }
+    }
+
+    /// Check if a scheduled refresh should fetch this feed, given the
+    /// default refresh interval (0 = every time). The interval is stretched
+    /// by this feed's stagger (see `stagger_secs`).
+    pub fn is_due(&self, now: DateTime<Utc>, default_interval_secs: u64, stagger_percent: u32) -> bool {
+        if self.paused {
+            return false;
+        }
+        let interval = self.refresh_interval_secs.unwrap_or(default_interval_secs);
+        let interval = interval.saturating_add(self.stagger_secs(interval, stagger_percent));
+        let Some(fetched) = self.last_fetched_at else {
+            return true;
+        };
+        // An interval too long for a duration never falls due
+        i64::try_from(interval)
+            .ok()
+            .and_then(chrono::TimeDelta::try_seconds)
+            .is_some_and(|interval| now - fetched >= interval)
+    }
+
+    /// Days after which this feed's unread articles are marked read, given
+    /// the default (`general.auto_read_days`); None when they never are
+    pub fn auto_read_after(&self, default_days: u32) -> Option<u32> {
+        Some(self.auto_read_days.unwrap_or(default_days)).filter(|days| *days > 0)
+    }
+
+    /// Extra seconds added to this feed's refresh interval: a share of up to
+    /// `stagger_percent` of it, fixed per feed (derived from its id), so feeds
+    /// fetched together fall due on different scheduler ticks
+    pub fn stagger_secs(&self, interval_secs: u64, stagger_percent: u32) -> u64 {
+        let max = interval_secs.saturating_mul(stagger_percent.min(100) as u64) / 100;
+        (self.id.as_u128() % (max as u128 + 1)) as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feed_stats_prune_reasons() {
+        let now = Utc::now();
+        let config = PruneConfig::default();
+        let subscribed_at = now - chrono::Duration::days(365);
+        let active = FeedStats {
+            total: 40,
+            unread: 10,
+            saved: 1,
+            recent: 14,
+            average_relevance: Some(0.7),
+            last_post_at: Some(now - chrono::Duration::days(1)),
+        };
+        assert_eq!(active.per_week(), 3.5);
+        assert_eq!(active.read_ratio(), Some(0.75));
+        assert!(active.prune_reasons(subscribed_at, &config, now).is_empty());
+
+        let stale = FeedStats {
+            unread: 39,
+            average_relevance: Some(0.2),
+            last_post_at: Some(now - chrono::Duration::days(120)),
+            ..active
+        };
+        assert_eq!(
+            stale.prune_reasons(subscribed_at, &config, now),
+            ["no posts in 3 months", "rarely read", "low relevance"]
+        );
+        // A new subscription without posts isn't stale yet
+        let empty = FeedStats::default();
+        assert!(empty.prune_reasons(now - chrono::Duration::days(7), &config, now).is_empty());
+        assert_eq!(empty.prune_reasons(subscribed_at, &config, now), ["no posts in 3 months"]);
+        assert_eq!(FeedStats::default().read_ratio(), None);
+    }
+
+    fn feed(now: DateTime<Utc>, fetched_hours_ago: i64) -> Feed {
+        Feed {
+            id: Uuid::new_v4(),
+            url: "https://example.com/feed".to_string(),
+            local_name: "feed".to_string(),
+            title: None,
+            description: None,
+            site_url: None,
+            icon_url: None,
+            last_fetched_at: Some(now - chrono::Duration::hours(fetched_hours_ago)),
+            fetch_error: None,
+            created_at: now,
+            updated_at: now,
+            category: None,
+            paused: false,
+            refresh_interval_secs: None,
+            auto_read_days: None,
+            pinned: false,
+            position: 0,
+            moved_to: None,
+            unread_count: 0,
+        }
+    }
+
+    #[test]
+    fn test_stagger_spreads_due_times() {
+        let now = Utc::now();
+        let feed = |fetched_hours_ago| feed(now, fetched_hours_ago);
+        let interval = 12 * 3600;
+
+        // Stable per feed and within the configured share of the interval
+        let staggered = feed(0);
+        let offset = staggered.stagger_secs(interval, 20);
+        assert!(offset <= interval / 5);
+        assert_eq!(staggered.stagger_secs(interval, 20), offset);
+        assert_eq!(staggered.stagger_secs(interval, 0), 0);
+
+        // Due once the interval plus at most 20% has passed
+        assert!(feed(12).is_due(now, interval, 0));
+        assert!(feed(15).is_due(now, interval, 20));
+        assert!(!feed(11).is_due(now, interval, 20));
+        assert!(!feed(12).is_due(now, u64::MAX, 20));
+    }
+
+    #[test]
+    fn test_matches_url() {
+        let mut feed = feed(Utc::now(), 0);
+        assert!(feed.matches_url("http://www.example.com/feed/"));
+        assert!(!feed.matches_url("https://example.com/other"));
+        feed.moved_to = Some("https://new.example.com/rss".to_string());
+        assert!(feed.matches_url("https://new.example.com/rss"));
+    }
+
+    #[test]
+    fn test_auto_read_after() {
+        // The feed's own period wins, 0 turns it off
+        let mut feed = feed(Utc::now(), 0);
+        assert_eq!(feed.auto_read_after(0), None);
+        assert_eq!(feed.auto_read_after(14), Some(14));
+        feed.auto_read_days = Some(0);
+        assert_eq!(feed.auto_read_after(14), None);
+        feed.auto_read_days = Some(3);
+        assert_eq!(feed.auto_read_after(0), Some(3));
+    }
+
+    #[test]
+    fn test_is_video_url() {
+        assert!(is_video_url("https://www.youtube.com/watch?v=dQw4w9WgXcQ"));
+        assert!(is_video_url("https://youtu.be/dQw4w9WgXcQ"));
+        assert!(is_video_url("https://vimeo.com/76979871"));
+        assert!(is_video_url("https://framatube.org/videos/watch/9c9de5e8-0a1e-484a-b099-e80766180a6d"));
+        assert!(is_video_url("https://tube.example/w/kkGMgK9ZtnKfYAgnEtQxbv"));
+        assert!(is_video_url("https://cdn.example.com/talks/intro.MP4"));
+        assert!(!is_video_url("https://www.youtube.com/@channel"));
+        assert!(!is_video_url("https://vimeo.com/about"));
+        assert!(!is_video_url("https://blog.example.com/posts/rust-1-80"));
+    }
 }
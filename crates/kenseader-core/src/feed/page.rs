@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use url::Url;
+
+/// Web page of an article
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FetchedPage {
+    /// Main content of the page (the `<article>`, `<main>` or `<body>` element)
+    pub html: String,
+    /// Wayback Machine snapshot the page came from, when the original is gone
+    pub snapshot_url: Option<String>,
+}
+
+/// Whether a response status means the page no longer exists
+pub fn is_gone_status(status: u16) -> bool {
+    matches!(status, 404 | 410)
+}
+
+/// Wayback Machine availability API query for the latest snapshot of `url`
+pub fn availability_url(url: &str) -> String {
+    let mut api = Url::parse("https://archive.org/wayback/available").expect("valid URL");
+    api.query_pairs_mut().append_pair("url", url);
+    api.into()
+}
+
+/// Snapshot URL in an availability API response
+pub fn parse_availability(json: &Value) -> Option<String> {
+    let closest = &json["archived_snapshots"]["closest"];
+    if closest["available"].as_bool() != Some(true) {
+        return None;
+    }
+    let url = closest["url"].as_str()?;
+    Some(match url.strip_prefix("http://") {
+        Some(rest) => format!("https://{}", rest),
+        None => url.to_string(),
+    })
+}
+
+/// Timestamp and original URL of a snapshot
+/// (`https://web.archive.org/web/<timestamp>/<original>`)
+fn split_snapshot(snapshot: &str) -> Option<(&str, &str)> {
+    let rest = &snapshot[snapshot.find("/web/")? + 5..];
+    let slash = rest.find('/')?;
+    Some((&rest[..slash], &rest[slash + 1..]))
+}
+
+/// Snapshot URL serving the page as captured, without the Wayback toolbar
+pub fn raw_snapshot_url(snapshot: &str) -> Option<String> {
+    let (timestamp, original) = split_snapshot(snapshot)?;
+    let timestamp = timestamp.trim_end_matches("id_");
+    Some(format!("https://web.archive.org/web/{}id_/{}", timestamp, original))
+}
+
+/// Capture date of a snapshot, as YYYY-MM-DD
+pub fn snapshot_date(snapshot: &str) -> Option<String> {
+    let (timestamp, _) = split_snapshot(snapshot)?;
+    let digits = timestamp.get(..8).filter(|d| d.bytes().all(|b| b.is_ascii_digit()))?;
+    Some(format!("{}-{}-{}", &digits[..4], &digits[4..6], &digits[6..]))
+}
+
+/// Inner HTML of the page's `<article>`, else `<main>`, else `<body>`
+/// element, leaving out site navigation where the markup allows it
+pub fn main_content(html: &str) -> &str {
+    ["article", "main", "body"]
+        .iter()
+        .find_map(|tag| element_inner(html, tag))
+        .unwrap_or(html)
+}
+
+/// Inner HTML of the first `<tag>` element, up to its last closing tag
+fn element_inner<'a>(html: &'a str, tag: &str) -> Option<&'a str> {
+    // ASCII lowercasing keeps byte offsets identical to the original
+    let lower = html.to_ascii_lowercase();
+    let open = format!("<{}", tag);
+    let mut from = 0;
+    let start = loop {
+        let pos = from + lower[from..].find(&open)?;
+        // Skip longer tag names with the same prefix (<main> vs <mainframe>)
+        match lower.as_bytes().get(pos + open.len()) {
+            Some(b'>' | b' ' | b'\t' | b'\n' | b'\r' | b'/') => break pos,
+            _ => from = pos + open.len(),
+        }
+    };
+    let content_start = start + lower[start..].find('>')? + 1;
+    let content_end = lower.rfind(&format!("</{}>", tag))?;
+    (content_end > content_start).then(|| &html[content_start..content_end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_wayback_snapshot_urls() {
+        let response = json!({"archived_snapshots": {"closest": {
+            "available": true,
+            "status": "200",
+            "url": "http://web.archive.org/web/20230405060708/https://example.com/post",
+            "timestamp": "20230405060708"
+        }}});
+        let snapshot = parse_availability(&response).unwrap();
+        assert_eq!(snapshot, "https://web.archive.org/web/20230405060708/https://example.com/post");
+        assert_eq!(
+            raw_snapshot_url(&snapshot).as_deref(),
+            Some("https://web.archive.org/web/20230405060708id_/https://example.com/post")
+        );
+        assert_eq!(snapshot_date(&snapshot).as_deref(), Some("2023-04-05"));
+
+        assert_eq!(parse_availability(&json!({"archived_snapshots": {}})), None);
+        assert!(availability_url("https://example.com/a?b=c").ends_with("url=https%3A%2F%2Fexample.com%2Fa%3Fb%3Dc"));
+    }
+
+    #[test]
+    fn test_main_content() {
+        let page = "<html><body><nav>Menu</nav><MAIN id=m><p>Post</p></MAIN><footer>f</footer></body></html>";
+        assert_eq!(main_content(page), "<p>Post</p>");
+
+        let page = "<body class=x><mainframe>a</mainframe><p>Text</p></body>";
+        assert_eq!(main_content(page), "<mainframe>a</mainframe><p>Text</p>");
+        assert_eq!(main_content("<p>Fragment</p>"), "<p>Fragment</p>");
+    }
+}
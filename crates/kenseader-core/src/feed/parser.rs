@@ -1,8 +1,16 @@
 use chrono::{DateTime, Utc};
+use feed_rs::model::{MediaContent, MediaObject};
 use feed_rs::parser;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use url::Url;
 use uuid::Uuid;
 
-use super::models::NewArticle;
+use super::canonical::canonicalize_url;
+use super::github;
+use super::models::{ArticleMedia, NewArticle};
+use super::quirks::{decode_charset, rewrite_dates};
+use crate::config::FeedQuirks;
 use crate::{Error, Result};
 
 /// Simple regex-like pattern matching for extracting image URLs from HTML
@@ -46,6 +54,243 @@ fn find_subslice(haystack: &[u8], needle: &[u8]) -> Option<usize> {
         .position(|window| window == needle)
 }
 
+const IMAGE_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "gif", "webp", "avif"];
+
+/// Check if a media content element is an image.
+/// Falls back to the URL extension when the feed omits the MIME type.
+fn is_image_content(content: &MediaContent) -> bool {
+    match content.content_type {
+        Some(ref mime) => mime.to_string().starts_with("image/"),
+        None => content.url.as_ref().is_some_and(|url| {
+            let path = url.path().to_lowercase();
+            IMAGE_EXTENSIONS.iter().any(|ext| path.ends_with(&format!(".{}", ext)))
+        }),
+    }
+}
+
+/// Pick the best cover image from Media RSS thumbnails and image content
+fn extract_media_image_url(media: &[MediaObject]) -> Option<String> {
+    media.iter()
+        .flat_map(|m| m.thumbnails.iter())
+        .map(|t| t.image.uri.clone())
+        .next()
+        .or_else(|| {
+            media.iter()
+                .flat_map(|m| m.content.iter())
+                .filter(|c| is_image_content(c))
+                .find_map(|c| c.url.as_ref().map(|u| u.to_string()))
+        })
+}
+
+/// Extract the primary non-image attachment (podcast audio, video) of an entry
+fn extract_media(media: &[MediaObject]) -> Option<ArticleMedia> {
+    media.iter().find_map(|object| {
+        let content = object.content.iter()
+            .find(|c| c.url.is_some() && !is_image_content(c))?;
+
+        Some(ArticleMedia {
+            url: content.url.as_ref()?.to_string(),
+            mime_type: content.content_type.as_ref().map(|t| t.to_string()),
+            length: content.size.filter(|size| *size > 0),
+            duration_secs: content.duration
+                .or(object.duration)
+                .map(|d| d.as_secs()),
+            thumbnail_url: object.thumbnails.first().map(|t| t.image.uri.clone()),
+        })
+    })
+}
+
+/// The `<comments>` URL of each RSS `<item>`, in document order.
+/// feed-rs drops this element, so the raw feed is scanned for it; the
+/// result lines up with the parsed entries (and is empty for Atom feeds).
+fn extract_rss_comments(content: &[u8]) -> Vec<Option<String>> {
+    let mut reader = Reader::from_reader(content);
+    reader.config_mut().trim_text(true);
+    let mut buf = Vec::new();
+    let mut comments = Vec::new();
+    let mut in_comments = false;
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) if e.name().as_ref() == b"item" => comments.push(None),
+            Ok(Event::Start(e)) if e.name().as_ref() == b"comments" => in_comments = true,
+            Ok(Event::End(e)) if e.name().as_ref() == b"comments" => in_comments = false,
+            Ok(Event::Text(text)) if in_comments => {
+                if let (Some(slot), Ok(url)) = (comments.last_mut(), text.unescape()) {
+                    *slot = Some(url.trim().to_string());
+                }
+            }
+            Ok(Event::CData(data)) if in_comments => {
+                if let Some(slot) = comments.last_mut() {
+                    *slot = Some(String::from_utf8_lossy(&data).trim().to_string());
+                }
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    comments
+}
+
+/// Link to the comment thread in Reddit's entry content
+/// (`<a href="...">[comments]</a>`)
+fn extract_reddit_comments_url(html: &str) -> Option<String> {
+    let anchor_end = html.find(">[comments]</a>")?;
+    let tag = &html[html[..anchor_end].rfind("<a ")?..anchor_end];
+    let start = tag.find("href=\"")? + 6;
+    let end = tag[start..].find('"')?;
+    Some(tag[start..start + end].replace("&amp;", "&"))
+}
+
+/// Tag attributes whose values are URLs
+const URL_ATTRIBUTES: &[&str] = &["src", "href", "poster"];
+
+/// Resolve a possibly relative URL against a base URL.
+/// Absolute URLs, fragments and unparseable values are returned unchanged.
+fn resolve_url(value: &str, base: &Url) -> String {
+    let trimmed = value.trim();
+    if trimmed.is_empty() || trimmed.starts_with('#') || Url::parse(trimmed).is_ok() {
+        return value.to_string();
+    }
+    base.join(trimmed)
+        .map(|u| u.to_string())
+        .unwrap_or_else(|_| value.to_string())
+}
+
+/// Rewrite relative URL attribute values (`src`, `href`, `poster` and every
+/// `srcset` candidate) in HTML to absolute URLs, so images like
+/// `/img/foo.png` can be loaded outside the original site
+pub(super) fn resolve_relative_urls(html: &str, base: &Url) -> String {
+    let mut result = String::with_capacity(html.len());
+    let mut remaining = html;
+
+    while let Some(lt) = remaining.find('<') {
+        result.push_str(&remaining[..lt]);
+        remaining = &remaining[lt..];
+
+        if remaining.starts_with("<!--") {
+            // Comments may contain anything, tags included
+            let end = remaining.find("-->").map_or(remaining.len(), |i| i + 3);
+            result.push_str(&remaining[..end]);
+            remaining = &remaining[end..];
+        } else if remaining[1..].starts_with(|c: char| c.is_ascii_alphabetic()) {
+            let len = rewrite_tag(remaining, base, &mut result);
+            remaining = &remaining[len..];
+        } else {
+            result.push('<');
+            remaining = &remaining[1..];
+        }
+    }
+
+    result.push_str(remaining);
+    result
+}
+
+/// Copy the start tag at the beginning of `html` to `result`, resolving its
+/// URL attributes, and return the length of the tag
+///
+/// Attribute values may be quoted (and then contain `>`) or unquoted, with
+/// whitespace around `=`. All delimiters are ASCII, so every index is a char
+/// boundary.
+fn rewrite_tag(html: &str, base: &Url, result: &mut String) -> usize {
+    let bytes = html.as_bytes();
+    let is_space = |i: usize| bytes.get(i).is_some_and(u8::is_ascii_whitespace);
+    let mut copied = 0;
+
+    // Tag name
+    let mut i = 1;
+    while i < bytes.len() && !is_space(i) && !matches!(bytes[i], b'/' | b'>') {
+        i += 1;
+    }
+
+    loop {
+        while is_space(i) || bytes.get(i) == Some(&b'/') {
+            i += 1;
+        }
+        match bytes.get(i) {
+            None => break,
+            Some(b'>') => {
+                i += 1;
+                break;
+            }
+            _ => {}
+        }
+
+        let name_start = i;
+        while i < bytes.len() && !is_space(i) && !matches!(bytes[i], b'=' | b'>' | b'/') {
+            i += 1;
+        }
+        let name = html[name_start..i].to_ascii_lowercase();
+
+        let mut j = i;
+        while is_space(j) {
+            j += 1;
+        }
+        if bytes.get(j) != Some(&b'=') {
+            continue;
+        }
+        j += 1;
+        while is_space(j) {
+            j += 1;
+        }
+
+        let (value_start, value_end) = match bytes.get(j) {
+            Some(&quote @ (b'"' | b'\'')) => {
+                let start = j + 1;
+                let end = html[start..].find(quote as char).map_or(html.len(), |len| start + len);
+                i = (end + 1).min(html.len());
+                (start, end)
+            }
+            _ => {
+                let start = j;
+                let mut end = j;
+                while end < bytes.len() && !is_space(end) && bytes[end] != b'>' {
+                    end += 1;
+                }
+                i = end;
+                (start, end)
+            }
+        };
+
+        let value = &html[value_start..value_end];
+        let resolved = if name == "srcset" {
+            resolve_srcset(value, base)
+        } else if URL_ATTRIBUTES.contains(&name.as_str()) {
+            resolve_url(value, base)
+        } else {
+            continue;
+        };
+        result.push_str(&html[copied..value_start]);
+        result.push_str(&resolved);
+        copied = value_end;
+    }
+
+    result.push_str(&html[copied..i]);
+    i
+}
+
+/// Resolve every candidate URL of a `srcset` value, keeping the descriptors
+fn resolve_srcset(value: &str, base: &Url) -> String {
+    value
+        .split(',')
+        .map(|candidate| {
+            let url_start = candidate.len() - candidate.trim_start().len();
+            let url_end = candidate[url_start..]
+                .find(|c: char| c.is_ascii_whitespace())
+                .map_or(candidate.len(), |len| url_start + len);
+            format!(
+                "{}{}{}",
+                &candidate[..url_start],
+                resolve_url(&candidate[url_start..url_end], base),
+                &candidate[url_end..]
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
 /// Parsed feed data from RSS/Atom content
 pub struct ParsedFeed {
     pub title: Option<String>,
@@ -53,56 +298,157 @@ pub struct ParsedFeed {
     pub site_url: Option<String>,
     pub icon_url: Option<String>,
     pub articles: Vec<NewArticle>,
+    /// URL the feed permanently redirects to (set by the fetcher)
+    pub moved_to: Option<String>,
+    /// HTTP status of the response (set by the fetcher)
+    pub status: Option<u16>,
+    /// Time spent downloading and parsing, and the size of the response
+    /// (set by the fetcher)
+    pub fetch_ms: u64,
+    pub parse_ms: u64,
+    pub bytes: u64,
 }
 
 /// Parse RSS/Atom feed content into structured data
-pub fn parse_feed(content: &[u8], feed_id: Uuid) -> Result<ParsedFeed> {
+///
+/// `feed_url` is used as the last-resort base for resolving relative URLs
+/// in article links, images and content.
+pub fn parse_feed(content: &[u8], feed_id: Uuid, feed_url: Option<&str>) -> Result<ParsedFeed> {
+    parse_feed_with_quirks(content, feed_id, feed_url, &FeedQuirks::default())
+}
+
+/// Parse feed content, working around the feed's configured quirks
+pub fn parse_feed_with_quirks(
+    content: &[u8],
+    feed_id: Uuid,
+    feed_url: Option<&str>,
+    quirks: &FeedQuirks,
+) -> Result<ParsedFeed> {
+    let decoded;
+    let content = match quirks.charset {
+        Some(ref charset) => {
+            decoded = decode_charset(content, charset)?;
+            &decoded[..]
+        }
+        None => content,
+    };
+    let rewritten;
+    let content = match quirks.date_format {
+        Some(ref format) => {
+            rewritten = rewrite_dates(&String::from_utf8_lossy(content), format);
+            rewritten.as_bytes()
+        }
+        None => content,
+    };
+
     let feed = parser::parse(content)
         .map_err(|e| Error::FeedParse(e.to_string()))?;
 
     let title = feed.title.map(|t| t.content);
     let description = feed.description.map(|d| d.content);
 
-    let site_url = feed.links.first().map(|l| l.href.clone());
+    let feed_base = feed_url.and_then(|u| Url::parse(u).ok());
+    let github_releases = feed_url.is_some_and(github::is_release_feed);
+
+    let site_url = feed.links.first().map(|l| match feed_base {
+        Some(ref base) => resolve_url(&l.href, base),
+        None => l.href.clone(),
+    });
+
+    // Prefer the site URL over the feed URL as base for entries without links
+    let site_base = site_url
+        .as_deref()
+        .and_then(|u| Url::parse(u).ok())
+        .or(feed_base);
 
     let icon_url = feed.icon.map(|i| i.uri)
         .or_else(|| feed.logo.map(|l| l.uri));
 
+    let mut rss_comments = extract_rss_comments(content).into_iter();
+
     let articles = feed.entries.into_iter().map(|entry| {
+        let rss_comments_url = rss_comments.next().flatten();
+        // Atom threading extension: <link rel="replies">
+        let replies_url = entry.links.iter()
+            .find(|l| l.rel.as_deref() == Some("replies"))
+            .map(|l| l.href.clone());
+
         let guid = entry.id;
 
-        let url = entry.links.first().map(|l| l.href.clone());
+        let url = entry.links.iter()
+            .find(|l| l.rel.as_deref() != Some("replies"))
+            .map(|l| match site_base {
+                Some(ref base) => canonicalize_url(&resolve_url(&l.href, base)),
+                None => canonicalize_url(&l.href),
+            });
+
+        // Relative URLs in content are relative to the article page
+        let base = url
+            .as_deref()
+            .and_then(|u| Url::parse(u).ok())
+            .or_else(|| site_base.clone());
 
         let title = entry.title
-            .map(|t| t.content)
+            .map(|t| match quirks.strip_title_prefix {
+                Some(ref prefix) => match t.content.strip_prefix(prefix.as_str()) {
+                    Some(rest) => rest.trim_start().to_string(),
+                    None => t.content,
+                },
+                None => t.content,
+            })
             .unwrap_or_else(|| "Untitled".to_string());
 
         let author = entry.authors.first().map(|a| a.name.clone());
 
-        let content = entry.content
-            .and_then(|c| c.body)
-            .or_else(|| entry.summary.map(|s| s.content));
+        let body = entry.content.and_then(|c| c.body);
+        let summary = entry.summary.map(|s| s.content);
+        let content = if quirks.description_as_content {
+            summary.or(body)
+        } else {
+            body.or(summary)
+        };
+        // GitHub release notes come with page chrome, escaped or as markdown
+        let content = match content {
+            Some(c) if github_releases => Some(github::clean_release_notes(&c)),
+            content => content,
+        };
+        let content = content.map(|c| match base {
+            Some(ref base) => resolve_relative_urls(&c, base),
+            None => c,
+        });
 
         let content_text = content.as_ref().map(|c| html_to_text(c));
 
+        let comments_url = rss_comments_url
+            .or(replies_url)
+            .or_else(|| content.as_deref().and_then(extract_reddit_comments_url))
+            .filter(|u| !u.is_empty())
+            .map(|u| match base {
+                Some(ref base) => resolve_url(&u, base),
+                None => u,
+            });
+
         let published_at = entry.published
             .or(entry.updated)
             .map(|dt| DateTime::<Utc>::from(dt));
 
-        // Extract image URL from media content, enclosure, or HTML content
-        let image_url = entry.media.first()
-            .and_then(|m| m.thumbnails.first())
-            .map(|t| t.image.uri.clone())
-            .or_else(|| {
-                // Check media content
-                entry.media.first()
-                    .and_then(|m| m.content.first())
-                    .and_then(|c| c.url.as_ref())
-                    .map(|u| u.to_string())
-            })
+        let media = extract_media(&entry.media).map(|mut m| {
+            if let Some(ref base) = base {
+                m.url = resolve_url(&m.url, base);
+                m.thumbnail_url = m.thumbnail_url.map(|t| resolve_url(&t, base));
+            }
+            m
+        });
+
+        // Extract image URL from media thumbnails, image content, or HTML content
+        let image_url = extract_media_image_url(&entry.media)
             .or_else(|| {
                 // Extract from HTML content
                 content.as_ref().and_then(|c| extract_first_image_url(c))
+            })
+            .map(|u| match base {
+                Some(ref base) => resolve_url(&u, base),
+                None => u,
             });
 
         NewArticle {
@@ -115,6 +461,8 @@ pub fn parse_feed(content: &[u8], feed_id: Uuid) -> Result<ParsedFeed> {
             content_text,
             published_at,
             image_url,
+            media,
+            comments_url,
         }
     }).collect();
 
@@ -124,6 +472,11 @@ pub fn parse_feed(content: &[u8], feed_id: Uuid) -> Result<ParsedFeed> {
         site_url,
         icon_url,
         articles,
+        moved_to: None,
+        status: None,
+        fetch_ms: 0,
+        parse_ms: 0,
+        bytes: 0,
     })
 }
 
@@ -132,3 +485,168 @@ fn html_to_text(html: &str) -> String {
     html2text::from_read(html.as_bytes(), 80)
         .unwrap_or_else(|_| html.to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_relative_urls() {
+        let base = Url::parse("https://example.com/blog/post.html").unwrap();
+        let html = r#"<p><img src="/img/foo.png" alt="x"><a href='bar.html'>Bar</a></p>"#;
+        let resolved = resolve_relative_urls(html, &base);
+
+        assert_eq!(
+            resolved,
+            r#"<p><img src="https://example.com/img/foo.png" alt="x"><a href='https://example.com/blog/bar.html'>Bar</a></p>"#
+        );
+    }
+
+    #[test]
+    fn test_resolve_relative_urls_attribute_forms() {
+        let base = Url::parse("https://example.com/blog/post.html").unwrap();
+        let resolve = |html: &str| resolve_relative_urls(html, &base);
+
+        // Every srcset candidate, with its descriptor
+        assert_eq!(
+            resolve(r#"<img srcset="a.png 1x, /b.png 2x" src="a.png">"#),
+            r#"<img srcset="https://example.com/blog/a.png 1x, https://example.com/b.png 2x" src="https://example.com/blog/a.png">"#
+        );
+        // Unquoted values
+        assert_eq!(
+            resolve("<img src=/img/a.png alt=x>"),
+            "<img src=https://example.com/img/a.png alt=x>"
+        );
+        // Whitespace around `=`
+        assert_eq!(
+            resolve(r#"<a href = "bar.html">Bar</a>"#),
+            r#"<a href = "https://example.com/blog/bar.html">Bar</a>"#
+        );
+        // `>` inside a quoted value doesn't end the tag
+        assert_eq!(
+            resolve(r#"<img alt="a > b" src="a.png">"#),
+            r#"<img alt="a > b" src="https://example.com/blog/a.png">"#
+        );
+        // Attribute names are matched whole, and text is left alone
+        assert_eq!(
+            resolve(r#"<img data-src="a.png"> 1 < 2 <!-- <img src="c.png"> -->"#),
+            r#"<img data-src="a.png"> 1 < 2 <!-- <img src="c.png"> -->"#
+        );
+    }
+
+    #[test]
+    fn test_resolve_relative_urls_keeps_absolute() {
+        let base = Url::parse("https://example.com/").unwrap();
+        let html = r##"<img SRC="https://cdn.example.org/a.jpg"><a href="#top">Top</a><a href="mailto:a@b.c">Mail</a>"##;
+
+        assert_eq!(resolve_relative_urls(html, &base), html);
+        assert_eq!(
+            resolve_relative_urls(r#"<img src="//cdn.example.org/b.jpg">"#, &base),
+            r#"<img src="https://cdn.example.org/b.jpg">"#
+        );
+    }
+
+    #[test]
+    fn test_parse_feed_resolves_relative_content() {
+        let xml = r#"<?xml version="1.0"?>
+<rss version="2.0"><channel>
+<title>Test</title><link>https://example.com/</link>
+<item><title>Post</title><link>https://example.com/posts/1</link><guid>1</guid>
+<description><![CDATA[<img src="/img/foo.png">]]></description></item>
+</channel></rss>"#;
+        let parsed = parse_feed(xml.as_bytes(), Uuid::new_v4(), None).unwrap();
+        let article = &parsed.articles[0];
+
+        assert_eq!(article.image_url.as_deref(), Some("https://example.com/img/foo.png"));
+        assert!(article.content.as_deref().unwrap().contains("https://example.com/img/foo.png"));
+    }
+
+    #[test]
+    fn test_parse_feed_enclosure_media() {
+        let xml = r#"<?xml version="1.0"?>
+<rss version="2.0" xmlns:itunes="http://www.itunes.com/dtds/podcast-1.0.dtd" xmlns:media="http://search.yahoo.com/mrss/">
+<channel><title>Podcast</title><link>https://example.com/</link>
+<item><title>Episode 1</title><guid>ep1</guid>
+<enclosure url="https://example.com/ep1.mp3" length="12345" type="audio/mpeg"/>
+<itunes:duration>01:02:03</itunes:duration>
+<media:thumbnail url="https://example.com/ep1.jpg"/>
+</item>
+</channel></rss>"#;
+        let parsed = parse_feed(xml.as_bytes(), Uuid::new_v4(), None).unwrap();
+        let article = &parsed.articles[0];
+        let media = article.media.as_ref().unwrap();
+
+        assert_eq!(media.url, "https://example.com/ep1.mp3");
+        assert_eq!(media.mime_type.as_deref(), Some("audio/mpeg"));
+        assert_eq!(media.length, Some(12345));
+        assert_eq!(media.duration_secs, Some(3723));
+        assert!(media.is_audio());
+        // The audio enclosure must never be picked as the cover image
+        assert_eq!(article.image_url.as_deref(), Some("https://example.com/ep1.jpg"));
+    }
+
+    #[test]
+    fn test_parse_feed_comments_url() {
+        let rss = r#"<?xml version="1.0"?>
+<rss version="2.0"><channel><title>HN</title><link>https://news.ycombinator.com/</link>
+<item><title>A</title><link>https://example.com/a</link>
+<comments>https://news.ycombinator.com/item?id=1</comments></item>
+<item><title>B</title><link>https://example.com/b</link></item>
+<item><title>C</title><link>https://example.com/c</link><comments>/s/abc</comments></item>
+</channel></rss>"#;
+        let parsed = parse_feed(rss.as_bytes(), Uuid::new_v4(), None).unwrap();
+        let comments: Vec<_> = parsed.articles.iter().map(|a| a.comments_url.as_deref()).collect();
+        assert_eq!(
+            comments,
+            [Some("https://news.ycombinator.com/item?id=1"), None, Some("https://example.com/s/abc")]
+        );
+
+        let atom = r#"<?xml version="1.0"?>
+<feed xmlns="http://www.w3.org/2005/Atom"><title>Reddit</title><id>r</id><updated>2024-01-01T00:00:00Z</updated>
+<entry><id>t3_1</id><title>Post</title><updated>2024-01-01T00:00:00Z</updated>
+<link href="https://example.com/post"/>
+<content type="html">&lt;a href="https://www.reddit.com/r/rust/comments/1/post/"&gt;[comments]&lt;/a&gt;</content></entry>
+<entry><id>2</id><title>Blog</title><updated>2024-01-01T00:00:00Z</updated>
+<link rel="replies" href="https://example.com/blog#comments"/><link href="https://example.com/blog"/></entry>
+</feed>"#;
+        let parsed = parse_feed(atom.as_bytes(), Uuid::new_v4(), None).unwrap();
+        assert_eq!(
+            parsed.articles[0].comments_url.as_deref(),
+            Some("https://www.reddit.com/r/rust/comments/1/post/")
+        );
+        assert_eq!(parsed.articles[1].comments_url.as_deref(), Some("https://example.com/blog#comments"));
+        assert_eq!(parsed.articles[1].url.as_deref(), Some("https://example.com/blog"));
+    }
+
+    #[test]
+    fn test_parse_feed_with_quirks() {
+        let rss = b"<?xml version=\"1.0\" encoding=\"utf-8\"?>
+<rss version=\"2.0\" xmlns:content=\"http://purl.org/rss/1.0/modules/content/\"><channel>
+<title>Caf\xe9</title><link>https://example.com/</link>
+<item><title>[Ad] Caf\xe9 news</title><link>https://example.com/1</link>
+<pubDate>16/10/2026 08:30</pubDate>
+<description>Full text</description><content:encoded>Read more...</content:encoded></item>
+</channel></rss>";
+        let quirks = FeedQuirks {
+            charset: Some("windows-1252".to_string()),
+            date_format: Some("%d/%m/%Y %H:%M".to_string()),
+            strip_title_prefix: Some("[Ad]".to_string()),
+            description_as_content: true,
+        };
+        let parsed = parse_feed_with_quirks(rss, Uuid::new_v4(), None, &quirks).unwrap();
+        let article = &parsed.articles[0];
+
+        assert_eq!(parsed.title.as_deref(), Some("Caf\u{e9}"));
+        assert_eq!(article.title, "Caf\u{e9} news");
+        assert_eq!(article.content.as_deref(), Some("Full text"));
+        assert_eq!(
+            article.published_at.map(|d| d.to_rfc3339()).as_deref(),
+            Some("2026-10-16T08:30:00+00:00")
+        );
+
+        // Without the quirks, the same feed keeps its flaws
+        let parsed = parse_feed(rss, Uuid::new_v4(), None).unwrap();
+        assert_eq!(parsed.articles[0].content.as_deref(), Some("Read more..."));
+        assert_eq!(parsed.articles[0].published_at, None);
+    }
+}
@@ -0,0 +1,121 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use encoding_rs::Encoding;
+
+use crate::{Error, Result};
+
+/// Date elements of RSS (RFC 2822 dates) and Atom / Dublin Core (RFC 3339)
+const DATE_ELEMENTS: &[(&str, bool)] = &[
+    ("pubDate", true),
+    ("lastBuildDate", true),
+    ("published", false),
+    ("updated", false),
+    ("dc:date", false),
+];
+
+/// Decode a feed from `charset` into UTF-8, whatever its XML declaration
+/// claims. The declaration is dropped so the parser doesn't decode again.
+pub fn decode_charset(content: &[u8], charset: &str) -> Result<Vec<u8>> {
+    let encoding = Encoding::for_label(charset.trim().as_bytes())
+        .ok_or_else(|| Error::FeedParse(format!("Unknown charset: {}", charset)))?;
+    let (text, _, _) = encoding.decode(content);
+    let text = text.trim_start();
+    let text = match text.strip_prefix("<?xml").and_then(|rest| rest.split_once("?>")) {
+        Some((_, rest)) => rest,
+        None => text,
+    };
+    Ok(text.as_bytes().to_vec())
+}
+
+/// Parse a date written in a feed's own `format` (chrono syntax); formats
+/// without a time zone are read as UTC, formats without a time as midnight
+pub fn parse_date(value: &str, format: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    DateTime::parse_from_str(value, format)
+        .map(|date| date.with_timezone(&Utc))
+        .ok()
+        .or_else(|| NaiveDateTime::parse_from_str(value, format).ok().map(|date| date.and_utc()))
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, format)
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+                .map(|date| date.and_utc())
+        })
+}
+
+/// Rewrite the dates of a feed written in `format` into the standard form
+/// of their element, so the parser understands them. Dates that don't
+/// match the format are left alone.
+pub fn rewrite_dates(xml: &str, format: &str) -> String {
+    DATE_ELEMENTS.iter().fold(xml.to_string(), |xml, (element, rfc2822)| {
+        rewrite_element_dates(&xml, element, format, *rfc2822)
+    })
+}
+
+fn rewrite_element_dates(xml: &str, element: &str, format: &str, rfc2822: bool) -> String {
+    let open = format!("<{}", element);
+    let close = format!("</{}>", element);
+    let mut result = String::with_capacity(xml.len());
+    let mut remaining = xml;
+
+    while let Some(start) = remaining.find(&open) {
+        let after_name = &remaining[start + open.len()..];
+        // Skip longer element names with the same prefix (<updated> vs <updatedBy>)
+        let (Some('>' | ' ' | '\t' | '\n' | '\r'), Some(tag_end), Some(text_end)) =
+            (after_name.chars().next(), after_name.find('>'), after_name.find(&close))
+        else {
+            result.push_str(&remaining[..start + open.len()]);
+            remaining = after_name;
+            continue;
+        };
+        let text_start = tag_end + 1;
+        if text_start > text_end {
+            result.push_str(&remaining[..start + open.len()]);
+            remaining = after_name;
+            continue;
+        }
+        result.push_str(&remaining[..start + open.len() + text_start]);
+        let text = &after_name[text_start..text_end];
+        match parse_date(text, format) {
+            Some(date) if rfc2822 => result.push_str(&date.to_rfc2822()),
+            Some(date) => result.push_str(&date.to_rfc3339()),
+            None => result.push_str(text),
+        }
+        remaining = &after_name[text_end..];
+    }
+
+    result.push_str(remaining);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_charset() {
+        let content = b"<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<title>Caf\xe9</title>";
+        let decoded = decode_charset(content, "windows-1252").unwrap();
+        assert_eq!(String::from_utf8(decoded).unwrap(), "\n<title>Café</title>");
+        assert!(decode_charset(content, "klingon").is_err());
+    }
+
+    #[test]
+    fn test_rewrite_dates() {
+        let xml = "<pubDate>16/10/2026 08:30</pubDate><updated type=\"x\">01/02/2026</updated>\
+                   <updatedBy>01/02/2026</updatedBy><dc:date>not a date</dc:date>";
+        assert_eq!(
+            rewrite_dates(xml, "%d/%m/%Y %H:%M"),
+            "<pubDate>Fri, 16 Oct 2026 08:30:00 +0000</pubDate><updated type=\"x\">01/02/2026</updated>\
+             <updatedBy>01/02/2026</updatedBy><dc:date>not a date</dc:date>"
+        );
+        assert_eq!(
+            rewrite_dates(xml, "%d/%m/%Y"),
+            "<pubDate>16/10/2026 08:30</pubDate><updated type=\"x\">2026-02-01T00:00:00+00:00</updated>\
+             <updatedBy>01/02/2026</updatedBy><dc:date>not a date</dc:date>"
+        );
+        assert_eq!(
+            parse_date("2026.10.16 08:30 +0200", "%Y.%m.%d %H:%M %z").map(|d| d.to_rfc3339()).as_deref(),
+            Some("2026-10-16T06:30:00+00:00")
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// How long a site's robots.txt is trusted before it's fetched again
+const ROBOTS_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Crawl state of a host, shared by all fetchers of the process
+#[derive(Default)]
+struct HostState {
+    /// Crawl-delay of its robots.txt (seconds), and when that was fetched
+    robots: Option<(Option<f64>, Instant)>,
+    /// Earliest time the next page may be fetched
+    next_fetch: Option<Instant>,
+}
+
+fn hosts() -> &'static Mutex<HashMap<String, HostState>> {
+    static HOSTS: OnceLock<Mutex<HashMap<String, HostState>>> = OnceLock::new();
+    HOSTS.get_or_init(Default::default)
+}
+
+/// Crawl-delay in seconds that a robots.txt asks of us: from a group naming
+/// kenseader, else from the `*` group
+pub fn parse_crawl_delay(robots_txt: &str) -> Option<f64> {
+    let mut agents: Vec<String> = Vec::new();
+    let mut in_rules = false;
+    let (mut ours, mut anyone) = (None, None);
+
+    for line in robots_txt.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let (key, value) = (key.trim().to_ascii_lowercase(), value.trim());
+        if key == "user-agent" {
+            // A user-agent line after rules starts the next group
+            if in_rules {
+                agents.clear();
+                in_rules = false;
+            }
+            agents.push(value.to_ascii_lowercase());
+            continue;
+        }
+        in_rules = true;
+        if key != "crawl-delay" {
+            continue;
+        }
+        let Some(delay) = value.parse::<f64>().ok().filter(|d| d.is_finite() && *d >= 0.0) else {
+            continue;
+        };
+        if agents.iter().any(|agent| agent.contains("kenseader")) {
+            ours = ours.or(Some(delay));
+        } else if agents.iter().any(|agent| agent == "*") {
+            anyone = anyone.or(Some(delay));
+        }
+    }
+    ours.or(anyone)
+}
+
+/// Delay configured for `host`: the entry for the host itself or the most
+/// specific domain it belongs to
+pub fn crawl_delay_override(host: &str, overrides: &HashMap<String, f64>) -> Option<f64> {
+    overrides
+        .iter()
+        .filter(|(domain, _)| {
+            let domain = domain.trim_start_matches('.');
+            host == domain || host.strip_suffix(domain).is_some_and(|sub| sub.ends_with('.'))
+        })
+        .max_by_key(|(domain, _)| domain.len())
+        .map(|(_, delay)| *delay)
+}
+
+/// Crawl-delay of a host's robots.txt, if fetched recently (the outer
+/// `None` means it needs fetching)
+pub fn cached_crawl_delay(host: &str) -> Option<Option<f64>> {
+    let hosts = hosts().lock().unwrap();
+    let (delay, fetched_at) = hosts.get(host)?.robots?;
+    (fetched_at.elapsed() < ROBOTS_TTL).then_some(delay)
+}
+
+/// Remember the Crawl-delay of a host's robots.txt
+pub fn cache_crawl_delay(host: &str, delay: Option<f64>) {
+    let mut hosts = hosts().lock().unwrap();
+    hosts.entry(host.to_string()).or_default().robots = Some((delay, Instant::now()));
+}
+
+/// Take the next fetch slot of a host, `delay` after the previous one.
+/// Returns how long to wait for it.
+pub fn reserve_fetch(host: &str, delay: Duration) -> Duration {
+    let mut hosts = hosts().lock().unwrap();
+    let state = hosts.entry(host.to_string()).or_default();
+    let now = Instant::now();
+    let slot = state.next_fetch.map_or(now, |next| next.max(now));
+    state.next_fetch = Some(slot + delay);
+    slot - now
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_crawl_delay() {
+        let robots = "User-agent: Googlebot\nCrawl-delay: 1\n\nUser-agent: *\nDisallow: /admin # private\nCrawl-delay: 10\n";
+        assert_eq!(parse_crawl_delay(robots), Some(10.0));
+
+        let robots = "User-agent: *\nCrawl-delay: 10\n\nUser-agent: bingbot\nUser-agent: Kenseader\ncrawl-delay: 2.5\n";
+        assert_eq!(parse_crawl_delay(robots), Some(2.5));
+
+        assert_eq!(parse_crawl_delay("User-agent: *\nDisallow:\nCrawl-delay: soon\n"), None);
+        assert_eq!(parse_crawl_delay(""), None);
+    }
+
+    #[test]
+    fn test_crawl_delay_override() {
+        let overrides = HashMap::from([("example.com".to_string(), 5.0), ("blog.example.com".to_string(), 1.0)]);
+        assert_eq!(crawl_delay_override("example.com", &overrides), Some(5.0));
+        assert_eq!(crawl_delay_override("www.example.com", &overrides), Some(5.0));
+        assert_eq!(crawl_delay_override("blog.example.com", &overrides), Some(1.0));
+        assert_eq!(crawl_delay_override("notexample.com", &overrides), None);
+    }
+
+    #[test]
+    fn test_reserve_fetch_spaces_requests() {
+        let host = "reserve.test";
+        let delay = Duration::from_secs(10);
+        assert_eq!(reserve_fetch(host, delay), Duration::ZERO);
+        let wait = reserve_fetch(host, delay);
+        assert!(wait > Duration::from_secs(9) && wait <= delay);
+        assert!(reserve_fetch(host, delay) > Duration::from_secs(19));
+    }
+}
@@ -0,0 +1,83 @@
+//! Minimal HTTP/1.1 serving shared by the podcast and web servers
+//!
+//! Both answer a few GET routes on the local network, one request per
+//! connection, so a request head and a closing response is all they need.
+
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tracing::warn;
+
+/// Largest request head accepted
+const MAX_REQUEST_BYTES: usize = 8192;
+
+/// How long a client may take to send its request head
+const HEAD_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Pause after a failed accept before accepting again
+const ACCEPT_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+/// Escape text for HTML and XML content and attribute values
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Read the request line and headers of an HTTP request (None when the
+/// client hangs up, sends too much or is too slow)
+pub(crate) async fn read_request_head(stream: &mut TcpStream) -> std::io::Result<Option<String>> {
+    let read = async {
+        let mut head = Vec::new();
+        let mut buf = [0u8; 1024];
+        while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+            let n = stream.read(&mut buf).await?;
+            if n == 0 || head.len() > MAX_REQUEST_BYTES {
+                return Ok(None);
+            }
+            head.extend_from_slice(&buf[..n]);
+        }
+        Ok(Some(String::from_utf8_lossy(&head).into_owned()))
+    };
+    tokio::time::timeout(HEAD_TIMEOUT, read).await.unwrap_or(Ok(None))
+}
+
+/// Log a failed accept and pause briefly, so that running out of file
+/// descriptors doesn't spin the accept loop
+pub(crate) async fn accept_failed(server: &str, error: std::io::Error) {
+    warn!("{} failed to accept a connection: {}", server, error);
+    tokio::time::sleep(ACCEPT_RETRY_DELAY).await;
+}
+
+/// Write the status line and headers of a response closing the connection
+pub(crate) async fn write_head(
+    stream: &mut TcpStream,
+    status: &str,
+    content_type: &str,
+    headers: &[String],
+) -> std::io::Result<()> {
+    let mut head = format!("HTTP/1.1 {}\r\nContent-Type: {}\r\nConnection: close\r\n", status, content_type);
+    for header in headers {
+        head.push_str(header);
+        head.push_str("\r\n");
+    }
+    head.push_str("\r\n");
+    stream.write_all(head.as_bytes()).await
+}
+
+/// Send a whole response and close the connection
+pub(crate) async fn respond(
+    stream: &mut TcpStream,
+    status: &str,
+    content_type: &str,
+    headers: &[String],
+    body: &[u8],
+) -> std::io::Result<()> {
+    let mut all_headers = headers.to_vec();
+    all_headers.push(format!("Content-Length: {}", body.len()));
+    write_head(stream, status, content_type, &all_headers).await?;
+    stream.write_all(body).await?;
+    stream.shutdown().await
+}
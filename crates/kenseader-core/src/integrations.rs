@@ -0,0 +1,269 @@
+//! Export of saved articles to read-later services
+//!
+//! The daemon sends every saved article once to each enabled destination
+//! (Readwise Reader, Matter), remembering per destination what was sent so
+//! that unsaving or re-running never duplicates an export. Only articles are
+//! exported: kenseader has no highlights to send.
+
+use std::time::Duration;
+
+use reqwest::header::AUTHORIZATION;
+use reqwest::{Client, StatusCode};
+use serde_json::{json, Value};
+use tracing::{debug, info, warn};
+
+use crate::config::{AppConfig, IntegrationDestination, IntegrationService};
+use crate::feed::Article;
+use crate::storage::{ArticleRepository, Database};
+use crate::{Error, Result};
+
+/// Readwise Reader's save endpoint
+const READWISE_API_URL: &str = "https://readwise.io/api/v3/save/";
+
+/// Matter's save endpoint
+const MATTER_API_URL: &str = "https://api.getmatter.com/public/v1/items";
+
+/// Articles sent per destination and run, within the services' rate limits
+const BATCH_SIZE: u32 = 20;
+
+/// API endpoint articles are sent to
+pub fn api_url(destination: &IntegrationDestination) -> &str {
+    match (&destination.api_url, destination.service) {
+        (Some(url), _) => url,
+        (None, IntegrationService::Readwise) => READWISE_API_URL,
+        (None, IntegrationService::Matter) => MATTER_API_URL,
+    }
+}
+
+fn authorization(destination: &IntegrationDestination) -> String {
+    match destination.service {
+        IntegrationService::Readwise => format!("Token {}", destination.token),
+        IntegrationService::Matter => format!("Bearer {}", destination.token),
+    }
+}
+
+/// JSON body saving an article to the destination's service
+pub fn request_body(destination: &IntegrationDestination, article: &Article) -> Value {
+    match destination.service {
+        IntegrationService::Readwise => {
+            let mut body = json!({
+                "url": article.url,
+                "title": article.title,
+                "location": "later",
+                "saved_using": "kenseader",
+            });
+            if let Some(ref author) = article.author {
+                body["author"] = json!(author);
+            }
+            if let Some(ref summary) = article.summary {
+                body["summary"] = json!(summary);
+            }
+            if let Some(published_at) = article.published_at {
+                body["published_date"] = json!(published_at.to_rfc3339());
+            }
+            if !destination.tags.is_empty() {
+                body["tags"] = json!(destination.tags);
+            }
+            body
+        }
+        IntegrationService::Matter => json!({ "url": article.url }),
+    }
+}
+
+/// Whether a response refuses the article itself (a 4xx), rather than the
+/// token (401/403) or the rate (429), which would fail every article alike
+fn rejects_article(status: StatusCode) -> bool {
+    status.is_client_error()
+        && !matches!(
+            status,
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS
+        )
+}
+
+/// Send the saved articles not yet exported to each enabled destination.
+/// Returns how many were exported; a failing destination is retried on the
+/// next run, and only fails the task when nothing could be exported.
+pub async fn export_saved(db: &Database, config: &AppConfig) -> Result<u32> {
+    let destinations: Vec<_> = config.integrations.destinations.iter().filter(|d| d.enabled).collect();
+    if destinations.is_empty() {
+        return Ok(0);
+    }
+    let client = Client::builder()
+        .timeout(Duration::from_secs(config.sync.request_timeout_secs))
+        .build()?;
+    let repo = ArticleRepository::new(db);
+    let mut exported = 0;
+    let mut failure = None;
+
+    for destination in destinations {
+        match export_to(&client, &repo, destination).await {
+            Ok(count) => exported += count,
+            Err(e) => {
+                warn!("Export to {} failed: {}", destination.name(), e);
+                failure.get_or_insert(e);
+            }
+        }
+    }
+
+    if exported > 0 {
+        info!("Exported {} saved articles", exported);
+    }
+    match failure {
+        Some(e) if exported == 0 => Err(e),
+        _ => Ok(exported),
+    }
+}
+
+/// Send one batch of unexported saved articles to a destination. An article
+/// the service rejects is recorded as failed and skipped, so it can't hold up
+/// the ones after it.
+async fn export_to(client: &Client, repo: &ArticleRepository<'_>, destination: &IntegrationDestination) -> Result<u32> {
+    let name = destination.name();
+    if destination.token.trim().is_empty() {
+        return Err(Error::Config(format!("integration '{}' has no token", name)));
+    }
+    let mut exported = 0;
+
+    for article in repo.list_saved_unexported(name, BATCH_SIZE).await? {
+        let response = client
+            .post(api_url(destination))
+            .header(AUTHORIZATION, authorization(destination))
+            .json(&request_body(destination, &article))
+            .send()
+            .await?;
+        let status = response.status();
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            debug!("{} is rate limiting exports, continuing on the next run", name);
+            break;
+        }
+        if rejects_article(status) {
+            let error = format!("HTTP {}", status);
+            warn!("{} rejected '{}' ({}), skipping it", name, article.title, error);
+            repo.mark_export_failed(article.id, name, &error).await?;
+            continue;
+        }
+        if !status.is_success() {
+            return Err(Error::Other(format!("{} failed on '{}': HTTP {}", name, article.title, status)));
+        }
+        repo.mark_exported(article.id, name).await?;
+        exported += 1;
+    }
+
+    Ok(exported)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feed::NewArticle;
+    use crate::storage::test_db_with_feed;
+    use chrono::{TimeZone, Utc};
+
+    fn destination(service: IntegrationService) -> IntegrationDestination {
+        IntegrationDestination {
+            service,
+            token: "secret".to_string(),
+            name: None,
+            enabled: true,
+            api_url: None,
+            tags: vec!["kenseader".to_string()],
+        }
+    }
+
+    fn article() -> Article {
+        Article {
+            url: Some("https://example.com/post".to_string()),
+            author: Some("Ann".to_string()),
+            summary: Some("In short.".to_string()),
+            published_at: Some(Utc.with_ymd_and_hms(2026, 10, 16, 8, 30, 0).unwrap()),
+            is_saved: true,
+            ..Article::for_test("Post")
+        }
+    }
+
+    #[test]
+    fn test_request_body() {
+        let readwise = destination(IntegrationService::Readwise);
+        assert_eq!(
+            request_body(&readwise, &article()),
+            json!({
+                "url": "https://example.com/post",
+                "title": "Post",
+                "location": "later",
+                "saved_using": "kenseader",
+                "author": "Ann",
+                "summary": "In short.",
+                "published_date": "2026-10-16T08:30:00+00:00",
+                "tags": ["kenseader"],
+            })
+        );
+        assert_eq!(authorization(&readwise), "Token secret");
+
+        let matter = destination(IntegrationService::Matter);
+        assert_eq!(request_body(&matter, &article()), json!({ "url": "https://example.com/post" }));
+        assert_eq!(authorization(&matter), "Bearer secret");
+    }
+
+    #[test]
+    fn test_destination_endpoint_and_name() {
+        let mut readwise = destination(IntegrationService::Readwise);
+        assert_eq!(api_url(&readwise), READWISE_API_URL);
+        assert_eq!(readwise.name(), "readwise");
+
+        readwise.api_url = Some("http://localhost:8080/save".to_string());
+        readwise.name = Some("work".to_string());
+        assert_eq!(api_url(&readwise), "http://localhost:8080/save");
+        assert_eq!(readwise.name(), "work");
+    }
+
+    /// Accept every article on a local port, except the ones titled "Rejected"
+    async fn serve_destination() -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/save", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = vec![0; 8192];
+                let len = stream.read(&mut request).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&request[..len]);
+                let status = if request.contains("\"Rejected\"") { "400 Bad Request" } else { "201 Created" };
+                let response = format!("HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n", status);
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+        url
+    }
+
+    #[tokio::test]
+    async fn test_rejected_article_is_skipped() {
+        let (db, feed) = test_db_with_feed().await;
+        let repo = ArticleRepository::new(&db);
+        let article = |guid: &str, title: &str| NewArticle {
+            url: Some(format!("https://example.com/{}", guid)),
+            title: title.to_string(),
+            ..NewArticle::for_test(feed.id, guid)
+        };
+        let created = repo
+            .create_many(&[article("1", "Rejected"), article("2", "Accepted")])
+            .await
+            .unwrap();
+        for saved in &created {
+            repo.toggle_saved(saved.id).await.unwrap();
+        }
+
+        let mut readwise = destination(IntegrationService::Readwise);
+        readwise.api_url = Some(serve_destination().await);
+        let client = Client::new();
+
+        // The oldest article is refused, the one after it still goes out
+        assert_eq!(export_to(&client, &repo, &readwise).await.unwrap(), 1);
+        assert!(repo.list_saved_unexported("readwise", 10).await.unwrap().is_empty());
+        assert_eq!(export_to(&client, &repo, &readwise).await.unwrap(), 0);
+
+        assert!(rejects_article(StatusCode::UNPROCESSABLE_ENTITY));
+        assert!(!rejects_article(StatusCode::UNAUTHORIZED));
+        assert!(!rejects_article(StatusCode::TOO_MANY_REQUESTS));
+        assert!(!rejects_article(StatusCode::BAD_GATEWAY));
+    }
+}
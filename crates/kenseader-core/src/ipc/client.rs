@@ -3,25 +3,68 @@
 //! Provides a type-safe interface for communicating with the daemon.
 
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Instant;
 
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::net::UnixStream;
+use tokio::sync::mpsc;
 use uuid::Uuid;
 
 use super::protocol::*;
-use crate::feed::{Article, Feed};
+use super::server::handle_request;
+use crate::config::AppConfig;
+use crate::feed::{
+    AdvisoryArticle, AlertedArticle, Article, ArticleListing, Feed, FeedDryRun, FeedSettings, FeedStats, FetchProfile, FetchedPage,
+    FilteredArticle, PageCursor, PruneSuggestion, ReadStateChange,
+};
+use crate::profile::{ProfileInsights, ScoreComparison, TimeWindow};
+use crate::scheduler::SchedulerEvent;
+use crate::storage::{ArticleStyle, Database, WriterLock};
 use crate::{Error, Result};
 
 /// Client for communicating with the daemon
 #[derive(Clone)]
 pub struct DaemonClient {
-    socket_path: PathBuf,
+    transport: Transport,
+}
+
+/// How requests reach the request handler
+#[derive(Clone)]
+enum Transport {
+    /// Daemon listening on a Unix socket
+    Socket(PathBuf),
+    /// Handled in-process against a local database (no daemon running)
+    Local {
+        db: Arc<Database>,
+        config: Arc<AppConfig>,
+        start_time: Instant,
+    },
 }
 
 impl DaemonClient {
     /// Create a new daemon client
     pub fn new(socket_path: PathBuf) -> Self {
-        Self { socket_path }
+        Self {
+            transport: Transport::Socket(socket_path),
+        }
+    }
+
+    /// Create a client that serves requests in-process from the database,
+    /// using the same handlers as the daemon
+    pub fn local(db: Arc<Database>, config: Arc<AppConfig>) -> Self {
+        Self {
+            transport: Transport::Local {
+                db,
+                config,
+                start_time: Instant::now(),
+            },
+        }
+    }
+
+    /// Check if requests are served in-process instead of by a daemon
+    pub fn is_local(&self) -> bool {
+        matches!(self.transport, Transport::Local { .. })
     }
 
     /// Check if daemon is running by sending a ping
@@ -53,6 +96,35 @@ impl DaemonClient {
         Ok(response.articles)
     }
 
+    /// List one page of a feed's article listing, starting at `cursor`
+    /// (from the start when `None`)
+    pub async fn list_articles_page(
+        &self,
+        feed_id: Uuid,
+        listing: ArticleListing,
+        limit: u32,
+        cursor: Option<PageCursor>,
+    ) -> Result<Vec<Article>> {
+        let params = serde_json::to_value(ArticleListParams {
+            feed_id: Some(feed_id),
+            listing,
+            limit: Some(limit),
+            cursor,
+        })?;
+        let result = self.call(methods::ARTICLE_LIST, params).await?;
+        let response: ArticleListResponse = serde_json::from_value(result)?;
+        Ok(response.articles)
+    }
+
+    /// Get how many articles a feed's listing has before an article, or
+    /// `None` if the listing doesn't include it
+    pub async fn article_position(&self, feed_id: Uuid, listing: ArticleListing, id: Uuid) -> Result<Option<u32>> {
+        let params = serde_json::to_value(ArticlePositionParams { feed_id, listing, id })?;
+        let result = self.call(methods::ARTICLE_POSITION, params).await?;
+        let response: ArticlePositionResponse = serde_json::from_value(result)?;
+        Ok(response.position)
+    }
+
     /// Get a single article by ID
     pub async fn get_article(&self, id: Uuid) -> Result<Option<Article>> {
         let params = serde_json::json!({ "id": id });
@@ -61,6 +133,14 @@ impl DaemonClient {
         Ok(response.article)
     }
 
+    /// Get the AI style classification of an article
+    pub async fn get_article_style(&self, id: Uuid) -> Result<Option<ArticleStyle>> {
+        let params = serde_json::json!({ "id": id });
+        let result = self.call(methods::ARTICLE_STYLE, params).await?;
+        let response: ArticleStyleResponse = serde_json::from_value(result)?;
+        Ok(response.style)
+    }
+
     /// Mark an article as read
     pub async fn mark_read(&self, id: Uuid) -> Result<()> {
         let params = serde_json::json!({ "id": id });
@@ -75,6 +155,70 @@ impl DaemonClient {
         Ok(())
     }
 
+    /// Mark all of a feed's unread articles as read; returns how many
+    pub async fn mark_all_read(&self, feed_id: Uuid) -> Result<u32> {
+        let params = serde_json::json!({ "feed_id": feed_id });
+        let result = self.call(methods::ARTICLE_MARK_ALL_READ, params).await?;
+        let response: MarkAllReadResponse = serde_json::from_value(result)?;
+        Ok(response.marked)
+    }
+
+    /// Store several read states at once
+    pub async fn set_read_many(&self, changes: &[ReadStateChange]) -> Result<()> {
+        let params = serde_json::json!({ "changes": changes });
+        self.call(methods::ARTICLE_SET_READ_MANY, params).await?;
+        Ok(())
+    }
+
+    /// Record the time spent reading an article and how far it was scrolled
+    pub async fn record_read_time(&self, id: Uuid, duration_ms: u64, scroll_depth: u8) -> Result<()> {
+        let params = serde_json::to_value(ReadTimeParams { id, duration_ms, scroll_depth })?;
+        self.call(methods::ARTICLE_READ_TIME, params).await?;
+        Ok(())
+    }
+
+    /// Ask for more (`more` true) or fewer articles like this one
+    pub async fn article_feedback(&self, id: Uuid, more: bool) -> Result<()> {
+        let params = serde_json::to_value(ArticleFeedbackParams { id, more })?;
+        self.call(methods::ARTICLE_FEEDBACK, params).await?;
+        Ok(())
+    }
+
+    /// Articles marked read by relevance filtering, most recently filtered
+    /// first
+    pub async fn list_filtered(&self, limit: u32) -> Result<Vec<FilteredArticle>> {
+        let params = serde_json::to_value(ArticleHistoryParams { limit })?;
+        let result = self.call(methods::ARTICLE_FILTERED, params).await?;
+        let response: FilteredArticlesResponse = serde_json::from_value(result)?;
+        Ok(response.articles)
+    }
+
+    /// Articles that raised a keyword alert, most recent alert first
+    pub async fn list_alerted(&self, limit: u32) -> Result<Vec<AlertedArticle>> {
+        let params = serde_json::to_value(ArticleHistoryParams { limit })?;
+        let result = self.call(methods::ARTICLE_ALERTS, params).await?;
+        let response: AlertedArticlesResponse = serde_json::from_value(result)?;
+        Ok(response.articles)
+    }
+
+    /// Articles mentioning CVEs scoring at least `min_score`, most severe
+    /// first
+    pub async fn list_advisories(&self, min_score: f64, limit: u32) -> Result<Vec<AdvisoryArticle>> {
+        let params = serde_json::to_value(ArticleAdvisoriesParams { min_score, limit })?;
+        let result = self.call(methods::ARTICLE_ADVISORIES, params).await?;
+        let response: AdvisoryArticlesResponse = serde_json::from_value(result)?;
+        Ok(response.articles)
+    }
+
+    /// Mark a filtered article unread and ask for more articles like it.
+    /// Returns false if the article was not filtered.
+    pub async fn rescue_article(&self, id: Uuid) -> Result<bool> {
+        let params = serde_json::to_value(ArticleIdParams { id })?;
+        let result = self.call(methods::ARTICLE_RESCUE, params).await?;
+        let response: RescueResponse = serde_json::from_value(result)?;
+        Ok(response.rescued)
+    }
+
     /// Toggle article saved status
     pub async fn toggle_saved(&self, id: Uuid) -> Result<bool> {
         let params = serde_json::json!({ "id": id });
@@ -83,6 +227,46 @@ impl DaemonClient {
         Ok(response.is_saved)
     }
 
+    /// Add tags to articles, returning how many were new
+    pub async fn add_tags(&self, ids: &[Uuid], tags: &[String]) -> Result<u64> {
+        let params = serde_json::json!({ "ids": ids, "tags": tags });
+        let result = self.call(methods::ARTICLE_ADD_TAGS, params).await?;
+        let response: TagsAddResponse = serde_json::from_value(result)?;
+        Ok(response.added)
+    }
+
+    /// Discussion page of an article (looked up on Hacker News when the feed
+    /// names none) and its first `comments` top-level comments
+    pub async fn article_discussion(&self, id: Uuid, comments: usize) -> Result<ArticleDiscussionResponse> {
+        let params = serde_json::json!({ "id": id, "comments": comments });
+        let result = self.call(methods::ARTICLE_DISCUSSION, params).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    /// Latest Wayback Machine snapshot of an article page, and whether the
+    /// original page is gone
+    pub async fn article_archived(&self, id: Uuid) -> Result<ArticleArchivedResponse> {
+        let params = serde_json::json!({ "id": id });
+        let result = self.call(methods::ARTICLE_ARCHIVED, params).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    /// Web page of an article, from the Wayback Machine when the original
+    /// is gone
+    pub async fn article_page(&self, id: Uuid) -> Result<FetchedPage> {
+        let params = serde_json::json!({ "id": id });
+        let result = self.call(methods::ARTICLE_PAGE, params).await?;
+        let response: ArticlePageResponse = serde_json::from_value(result)?;
+        Ok(response.page)
+    }
+
+    /// All tags in use, most used first
+    pub async fn list_tags(&self) -> Result<Vec<String>> {
+        let result = self.call(methods::TAG_LIST, serde_json::Value::Null).await?;
+        let response: TagListResponse = serde_json::from_value(result)?;
+        Ok(response.tags)
+    }
+
     /// Search articles
     pub async fn search(&self, query: &str, feed_id: Option<Uuid>) -> Result<Vec<Article>> {
         let params = serde_json::json!({
@@ -94,6 +278,14 @@ impl DaemonClient {
         Ok(response.articles)
     }
 
+    /// List read articles across all feeds, most recently read first
+    pub async fn list_recently_read(&self, limit: u32) -> Result<Vec<Article>> {
+        let params = serde_json::json!({ "limit": limit });
+        let result = self.call(methods::ARTICLE_HISTORY, params).await?;
+        let response: ArticleListResponse = serde_json::from_value(result)?;
+        Ok(response.articles)
+    }
+
     /// List all feeds
     pub async fn list_feeds(&self) -> Result<Vec<Feed>> {
         let result = self.call(methods::FEED_LIST, serde_json::Value::Null).await?;
@@ -122,6 +314,88 @@ impl DaemonClient {
         Ok(deleted)
     }
 
+    /// Update a feed's URL, name, category, paused flag and refresh interval
+    pub async fn update_feed(&self, id: Uuid, settings: &FeedSettings) -> Result<Feed> {
+        let params = serde_json::to_value(FeedUpdateParams {
+            id,
+            settings: settings.clone(),
+        })?;
+        let result = self.call(methods::FEED_UPDATE, params).await?;
+        let response: FeedUpdateResponse = serde_json::from_value(result)?;
+        Ok(response.feed)
+    }
+
+    /// Persist a manual feed order (feed IDs in their new order)
+    pub async fn reorder_feeds(&self, ids: &[Uuid]) -> Result<()> {
+        let params = serde_json::json!({ "ids": ids });
+        self.call(methods::FEED_REORDER, params).await?;
+        Ok(())
+    }
+
+    /// Pin a feed to the top of the list, or unpin it
+    pub async fn pin_feed(&self, id: Uuid, pinned: bool) -> Result<()> {
+        let params = serde_json::json!({ "id": id, "pinned": pinned });
+        self.call(methods::FEED_PIN, params).await?;
+        Ok(())
+    }
+
+    /// Put a feed in a category, or take it out of its category (None)
+    pub async fn set_feed_category(&self, id: Uuid, category: Option<&str>) -> Result<()> {
+        let params = serde_json::to_value(FeedCategoryParams {
+            id,
+            category: category.map(str::to_string),
+        })?;
+        self.call(methods::FEED_SET_CATEGORY, params).await?;
+        Ok(())
+    }
+
+    /// Find the feed behind a URL (a feed or a page advertising one),
+    /// validating that it can be fetched and parsed
+    pub async fn discover_feed(&self, url: &str) -> Result<FeedDiscoverResponse> {
+        let params = serde_json::json!({ "url": url });
+        let result = self.call(methods::FEED_DISCOVER, params).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    /// Posting, reading and relevance statistics of a feed
+    pub async fn feed_stats(&self, id: Uuid) -> Result<FeedStats> {
+        let params = serde_json::json!({ "id": id });
+        let result = self.call(methods::FEED_STATS, params).await?;
+        let response: FeedStatsResponse = serde_json::from_value(result)?;
+        Ok(response.stats)
+    }
+
+    /// Fetch performance of each feed over the last `days` days, slowest first
+    pub async fn fetch_profile(&self, days: u32) -> Result<Vec<FetchProfile>> {
+        let params = serde_json::to_value(FetchProfileParams { days })?;
+        let result = self.call(methods::FEED_FETCH_PROFILE, params).await?;
+        let response: FetchProfileResponse = serde_json::from_value(result)?;
+        Ok(response.feeds)
+    }
+
+    /// Learned preferences of a time window, up to `limit` of each kind
+    pub async fn profile_insights(&self, window: TimeWindow, limit: usize) -> Result<ProfileInsights> {
+        let params = serde_json::to_value(ProfileInsightsParams { window, limit })?;
+        let result = self.call(methods::PROFILE_INSIGHTS, params).await?;
+        let response: ProfileInsightsResponse = serde_json::from_value(result)?;
+        Ok(response.insights)
+    }
+
+    /// Score recent articles with two settings and report how filtering
+    /// them would differ; nothing is stored
+    pub async fn compare_scoring(&self, params: CompareScoringParams) -> Result<ScoreComparison> {
+        let result = self.call(methods::PROFILE_COMPARE_SCORING, serde_json::to_value(params)?).await?;
+        let response: CompareScoringResponse = serde_json::from_value(result)?;
+        Ok(response.comparison)
+    }
+
+    /// Feeds that may be worth unsubscribing from, by `prune` settings
+    pub async fn prune_suggestions(&self) -> Result<Vec<PruneSuggestion>> {
+        let result = self.call(methods::FEED_PRUNE_SUGGESTIONS, serde_json::Value::Null).await?;
+        let response: PruneSuggestionsResponse = serde_json::from_value(result)?;
+        Ok(response.feeds)
+    }
+
     /// Refresh feeds
     pub async fn refresh(&self, feed_id: Option<Uuid>) -> Result<u32> {
         let params = serde_json::json!({ "id": feed_id });
@@ -130,43 +404,167 @@ impl DaemonClient {
         Ok(response.new_articles)
     }
 
-    /// Send a request and receive a response
-    async fn call(&self, method: &str, params: serde_json::Value) -> Result<serde_json::Value> {
-        let stream = UnixStream::connect(&self.socket_path).await.map_err(|e| {
-            Error::Other(format!(
-                "Failed to connect to daemon at {}: {}. Is the daemon running?",
-                self.socket_path.display(),
-                e
-            ))
-        })?;
+    /// What refreshing every active feed would store, without storing it
+    pub async fn refresh_dry_run(&self) -> Result<Vec<FeedDryRun>> {
+        let result = self.call(methods::FEED_REFRESH_DRY_RUN, serde_json::Value::Null).await?;
+        let response: RefreshDryRunResponse = serde_json::from_value(result)?;
+        Ok(response.feeds)
+    }
 
+    /// Start refreshing all feeds in the daemon and return right away.
+    /// Progress and the result arrive as events (see `subscribe_events`).
+    /// Returns false if a refresh started this way is already running.
+    pub async fn refresh_start(&self) -> Result<bool> {
+        let result = self.call(methods::FEED_REFRESH_START, serde_json::Value::Null).await?;
+        let response: RefreshStartResponse = serde_json::from_value(result)?;
+        Ok(response.started)
+    }
+
+    /// Receive the daemon's scheduler events until it stops
+    pub async fn subscribe_events(&self) -> Result<mpsc::UnboundedReceiver<SchedulerEvent>> {
+        let Transport::Socket(socket_path) = &self.transport else {
+            return Err(Error::Other("Events need a running daemon".to_string()));
+        };
+        let stream = connect(socket_path).await?;
         let (reader, mut writer) = stream.into_split();
         let mut reader = BufReader::new(reader);
 
-        // Build request
-        let request = Request::new(method).with_params(params);
-        let request_json = serde_json::to_string(&request)?;
-
-        // Send request
-        writer.write_all(request_json.as_bytes()).await?;
+        let request = Request::new(methods::EVENTS_SUBSCRIBE);
+        writer.write_all(serde_json::to_string(&request)?.as_bytes()).await?;
         writer.write_all(b"\n").await?;
         writer.flush().await?;
 
-        // Read response
-        let mut response_line = String::new();
-        reader.read_line(&mut response_line).await?;
-
-        let response: Response = serde_json::from_str(&response_line)?;
-
+        let mut line = String::new();
+        reader.read_line(&mut line).await?;
+        let response: Response = serde_json::from_str(&line)?;
         if let Some(error) = response.error {
-            return Err(Error::Other(format!(
-                "RPC error {}: {}",
-                error.code, error.message
-            )));
+            return Err(Error::Other(format!("RPC error {}: {}", error.code, error.message)));
         }
 
-        response.result.ok_or_else(|| Error::Other("Empty response".to_string()))
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            // Keeps the write half open: the daemon treats EOF as unsubscribing
+            let _writer = writer;
+            loop {
+                line.clear();
+                match reader.read_line(&mut line).await {
+                    Ok(0) | Err(_) => break,
+                    Ok(_) => match serde_json::from_str::<SchedulerEvent>(&line) {
+                        Ok(event) => {
+                            if tx.send(event).is_err() {
+                                break;
+                            }
+                        }
+                        Err(e) => tracing::debug!("Ignoring unknown daemon event: {}", e),
+                    },
+                }
+            }
+        });
+        Ok(rx)
+    }
+
+    /// Delete articles older than the retention period
+    pub async fn cleanup(&self) -> Result<u32> {
+        let result = self.call(methods::ARTICLE_CLEANUP, serde_json::Value::Null).await?;
+        let response: CleanupResponse = serde_json::from_value(result)?;
+        Ok(response.deleted)
+    }
+
+    /// Run a scheduler task immediately; returns what it did
+    pub async fn run_task(&self, task: SchedulerTask) -> Result<String> {
+        let params = serde_json::to_value(TaskRunParams { name: task })?;
+        let result = self.call(methods::TASK_RUN, params).await?;
+        let response: TaskRunResponse = serde_json::from_value(result)?;
+        Ok(response.summary)
+    }
+
+    /// Switch metered mode on or off
+    pub async fn set_metered(&self, enabled: bool) -> Result<()> {
+        let params = serde_json::to_value(MeteredParams { enabled })?;
+        self.call(methods::METERED_SET, params).await?;
+        Ok(())
+    }
+
+    /// Send a request and receive a response
+    async fn call(&self, method: &str, params: serde_json::Value) -> Result<serde_json::Value> {
+        let request = Request::new(method).with_params(params);
+
+        let response = match &self.transport {
+            Transport::Socket(socket_path) => send_request(socket_path, &request).await?,
+            Transport::Local { db, config, start_time } => {
+                // Writes need the writer lock, shared with anything else in
+                // this process holding it. If a daemon holds it, the write goes
+                // through the daemon instead of waiting for a lock it never
+                // releases.
+                let _lock = if methods::is_read_only(method) {
+                    None
+                } else {
+                    match WriterLock::try_acquire(config)? {
+                        Some(lock) => Some(lock),
+                        None => {
+                            let socket_path = config.socket_path();
+                            let ping = send_request(&socket_path, &Request::new(methods::PING)).await;
+                            if !ping.is_ok_and(|response| response.error.is_none()) {
+                                return Err(Error::Other(
+                                    "Another kenseader process is writing to the database, try again later"
+                                        .to_string(),
+                                ));
+                            }
+                            let response = send_request(&socket_path, &request).await?;
+                            return unwrap_response(response);
+                        }
+                    }
+                };
+                handle_request(request, db, config, *start_time, None, None).await
+            }
+        };
+
+        unwrap_response(response)
+    }
+}
+
+/// Turn a response into its result, or its error
+fn unwrap_response(response: Response) -> Result<serde_json::Value> {
+    if let Some(error) = response.error {
+        return Err(Error::Other(format!(
+            "RPC error {}: {}",
+            error.code, error.message
+        )));
     }
+
+    response.result.ok_or_else(|| Error::Other("Empty response".to_string()))
+}
+
+/// Connect to the daemon socket
+async fn connect(socket_path: &std::path::Path) -> Result<UnixStream> {
+    UnixStream::connect(socket_path).await.map_err(|e| {
+        Error::Other(format!(
+            "Failed to connect to daemon at {}: {}. Is the daemon running?",
+            socket_path.display(),
+            e
+        ))
+    })
+}
+
+/// Send a request over the daemon socket and read its response
+async fn send_request(socket_path: &std::path::Path, request: &Request) -> Result<Response> {
+    let stream = connect(socket_path).await?;
+
+    let (reader, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(reader);
+
+    let request_json = serde_json::to_string(request)?;
+
+    // Send request
+    writer.write_all(request_json.as_bytes()).await?;
+    writer.write_all(b"\n").await?;
+    writer.flush().await?;
+
+    // Read response
+    let mut response_line = String::new();
+    reader.read_line(&mut response_line).await?;
+
+    Ok(serde_json::from_str(&response_line)?)
 }
 
 /// Check if daemon is reachable
@@ -174,3 +572,138 @@ pub async fn is_daemon_running(socket_path: &std::path::Path) -> bool {
     let client = DaemonClient::new(socket_path.to_path_buf());
     client.ping().await.unwrap_or(false)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_local_client_serves_requests_in_process() {
+        let dir = std::env::temp_dir().join(format!("kenseader-local-{}", Uuid::new_v4()));
+        let mut config = AppConfig::default();
+        config.general.data_dir = dir.clone();
+        let db = Arc::new(Database::new_in_memory().await.unwrap());
+        let client = DaemonClient::local(db, Arc::new(config.clone()));
+        assert!(client.is_local());
+        assert!(client.ping().await.unwrap());
+
+        let feed = client.add_feed("https://example.com/feed", "example").await.unwrap();
+        // Writes take the writer lock only while they run
+        assert!(!WriterLock::is_held_elsewhere(&config).unwrap());
+        let feeds = client.list_feeds().await.unwrap();
+        assert_eq!(feeds.len(), 1);
+        assert_eq!(feeds[0].id, feed.id);
+
+        let mut settings = feed.settings();
+        settings.local_name = "renamed".to_string();
+        settings.paused = true;
+        let updated = client.update_feed(feed.id, &settings).await.unwrap();
+        assert_eq!(updated.local_name, "renamed");
+        assert!(updated.paused);
+
+        client.pin_feed(feed.id, true).await.unwrap();
+        assert!(client.list_feeds().await.unwrap()[0].pinned);
+        assert!(client.list_recently_read(10).await.unwrap().is_empty());
+
+        assert!(client.delete_feed(feed.id).await.unwrap());
+        assert!(client.list_feeds().await.unwrap().is_empty());
+
+        assert_eq!(client.run_task(SchedulerTask::Refresh).await.unwrap(), "0 new articles");
+        assert_eq!(
+            client.run_task(SchedulerTask::Cleanup).await.unwrap(),
+            "0 old articles deleted"
+        );
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn test_local_write_does_not_wait_for_held_lock() {
+        let dir = std::env::temp_dir().join(format!("kenseader-held-{}", Uuid::new_v4()));
+        let mut config = AppConfig::default();
+        config.general.data_dir = dir.clone();
+        std::fs::create_dir_all(&dir).unwrap();
+        let db = Arc::new(Database::new_in_memory().await.unwrap());
+        let client = DaemonClient::local(db, Arc::new(config.clone()));
+
+        // Another process (here: another handle) owns the database, no daemon answers
+        let other = std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(config.lock_path())
+            .unwrap();
+        other.lock().unwrap();
+
+        let changes = [ReadStateChange { id: Uuid::new_v4(), read: true }];
+        let write = client.set_read_many(&changes);
+        let result = tokio::time::timeout(std::time::Duration::from_secs(5), write)
+            .await
+            .expect("write should not wait for the lock");
+        assert!(result.is_err());
+        // Reads don't need the lock
+        assert!(client.list_feeds().await.unwrap().is_empty());
+
+        drop(other);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn test_overlapping_local_writes_share_the_lock() {
+        let dir = std::env::temp_dir().join(format!("kenseader-overlap-{}", Uuid::new_v4()));
+        let mut config = AppConfig::default();
+        config.general.data_dir = dir.clone();
+        let db = Arc::new(Database::new_in_memory().await.unwrap());
+        let client = DaemonClient::local(db, Arc::new(config.clone()));
+
+        // A write still running when others start and finish
+        let running = WriterLock::try_acquire(&config).unwrap().expect("lock should be free");
+        let first = [ReadStateChange { id: Uuid::new_v4(), read: true }];
+        let second = [ReadStateChange { id: Uuid::new_v4(), read: false }];
+        let (a, b) = tokio::join!(client.set_read_many(&first), client.set_read_many(&second));
+        a.unwrap();
+        b.unwrap();
+
+        // The lock stays taken until the last write is done
+        assert!(WriterLock::is_held_here(&config));
+        drop(running);
+        assert!(!WriterLock::is_held_here(&config));
+        assert!(!WriterLock::is_held_elsewhere(&config).unwrap());
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn test_background_refresh_events() {
+        use super::super::DaemonServer;
+        use tokio::sync::{broadcast, watch};
+
+        let dir = std::env::temp_dir().join(format!("kenseader-ipc-{}", Uuid::new_v4()));
+        let mut config = AppConfig::default();
+        config.general.data_dir = dir.clone();
+        let config = Arc::new(config);
+        let db = Arc::new(Database::new_in_memory().await.unwrap());
+
+        let (events_tx, _) = broadcast::channel(16);
+        let server = DaemonServer::new(db, config.clone()).with_events(events_tx, Default::default());
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let server = tokio::spawn(async move { server.run(shutdown_rx).await });
+
+        let client = DaemonClient::new(config.socket_path());
+        while !client.ping().await.unwrap_or(false) {
+            tokio::task::yield_now().await;
+        }
+        let mut events = client.subscribe_events().await.unwrap();
+        assert!(client.refresh_start().await.unwrap());
+
+        // No feeds: the refresh finishes right away
+        match events.recv().await.unwrap() {
+            SchedulerEvent::FeedsRefreshed { new_articles } => assert_eq!(new_articles, 0),
+            event => panic!("unexpected event {:?}", event),
+        }
+
+        shutdown_tx.send(true).unwrap();
+        server.await.unwrap().unwrap();
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -5,7 +5,13 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::feed::{Article, Feed};
+use crate::ai::AiQueueStats;
+use crate::feed::{
+    AdvisoryArticle, AlertedArticle, Article, ArticleListing, Comment, DiscoveredFeed, Feed, FeedDryRun, FeedSettings, FeedStats,
+    FetchProfile, FetchedPage, FilteredArticle, PageCursor, PruneSuggestion, ReadStateChange,
+};
+use crate::profile::{ProfileInsights, ScoreComparison, ScoringSettings, TimeWindow};
+use crate::storage::ArticleStyle;
 
 /// JSON-RPC style request
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -92,17 +98,85 @@ pub mod methods {
 
     // Article methods
     pub const ARTICLE_LIST: &str = "article.list";
+    pub const ARTICLE_POSITION: &str = "article.position";
     pub const ARTICLE_GET: &str = "article.get";
+    pub const ARTICLE_STYLE: &str = "article.style";
     pub const ARTICLE_MARK_READ: &str = "article.mark_read";
     pub const ARTICLE_MARK_UNREAD: &str = "article.mark_unread";
+    pub const ARTICLE_SET_READ_MANY: &str = "article.set_read_many";
+    pub const ARTICLE_MARK_ALL_READ: &str = "article.mark_all_read";
     pub const ARTICLE_TOGGLE_SAVED: &str = "article.toggle_saved";
     pub const ARTICLE_SEARCH: &str = "article.search";
+    pub const ARTICLE_HISTORY: &str = "article.history";
+    pub const ARTICLE_CLEANUP: &str = "article.cleanup";
+    pub const ARTICLE_ADD_TAGS: &str = "article.add_tags";
+    pub const ARTICLE_DISCUSSION: &str = "article.discussion";
+    pub const ARTICLE_ARCHIVED: &str = "article.archived";
+    pub const ARTICLE_PAGE: &str = "article.page";
+    pub const ARTICLE_READ_TIME: &str = "article.read_time";
+    pub const ARTICLE_FEEDBACK: &str = "article.feedback";
+    pub const ARTICLE_FILTERED: &str = "article.filtered";
+    pub const ARTICLE_RESCUE: &str = "article.rescue";
+    pub const ARTICLE_ALERTS: &str = "article.alerts";
+    pub const ARTICLE_ADVISORIES: &str = "article.advisories";
+
+    // Tag methods
+    pub const TAG_LIST: &str = "tag.list";
 
     // Feed methods
     pub const FEED_LIST: &str = "feed.list";
     pub const FEED_ADD: &str = "feed.add";
     pub const FEED_DELETE: &str = "feed.delete";
+    pub const FEED_UPDATE: &str = "feed.update";
+    pub const FEED_REORDER: &str = "feed.reorder";
+    pub const FEED_PIN: &str = "feed.pin";
+    pub const FEED_SET_CATEGORY: &str = "feed.set_category";
+    pub const FEED_DISCOVER: &str = "feed.discover";
+    pub const FEED_STATS: &str = "feed.stats";
+    pub const FEED_FETCH_PROFILE: &str = "feed.fetch_profile";
+    pub const FEED_PRUNE_SUGGESTIONS: &str = "feed.prune_suggestions";
     pub const FEED_REFRESH: &str = "feed.refresh";
+    pub const FEED_REFRESH_START: &str = "feed.refresh_start";
+    pub const FEED_REFRESH_DRY_RUN: &str = "feed.refresh_dry_run";
+
+    // Profile methods
+    pub const PROFILE_INSIGHTS: &str = "profile.insights";
+    pub const PROFILE_COMPARE_SCORING: &str = "profile.compare_scoring";
+
+    // Scheduler events, streamed on the subscribing connection
+    pub const EVENTS_SUBSCRIBE: &str = "events.subscribe";
+
+    // Scheduler tasks
+    pub const TASK_RUN: &str = "task.run";
+
+    // Metered mode
+    pub const METERED_SET: &str = "metered.set";
+
+    /// Whether a method only reads the database
+    pub fn is_read_only(method: &str) -> bool {
+        matches!(
+            method,
+            PING | STATUS
+                | ARTICLE_LIST
+                | ARTICLE_POSITION
+                | ARTICLE_GET
+                | ARTICLE_STYLE
+                | ARTICLE_SEARCH
+                | ARTICLE_HISTORY
+                | ARTICLE_PAGE
+                | ARTICLE_FILTERED
+                | ARTICLE_ALERTS
+                | ARTICLE_ADVISORIES
+                | TAG_LIST
+                | FEED_LIST
+                | FEED_DISCOVER
+                | FEED_STATS
+                | FEED_FETCH_PROFILE
+                | FEED_PRUNE_SUGGESTIONS
+                | FEED_REFRESH_DRY_RUN
+                | PROFILE_INSIGHTS
+        )
+    }
 }
 
 // Parameter structures
@@ -110,8 +184,22 @@ pub mod methods {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ArticleListParams {
     pub feed_id: Option<Uuid>,
+    #[serde(flatten)]
+    pub listing: ArticleListing,
+    /// Page size (all articles when omitted)
+    #[serde(default)]
+    pub limit: Option<u32>,
+    /// Where the page starts (the start of the listing when omitted)
     #[serde(default)]
-    pub unread_only: bool,
+    pub cursor: Option<PageCursor>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArticlePositionParams {
+    pub feed_id: Uuid,
+    #[serde(flatten)]
+    pub listing: ArticleListing,
+    pub id: Uuid,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -119,23 +207,212 @@ pub struct ArticleIdParams {
     pub id: Uuid,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadStateParams {
+    pub changes: Vec<ReadStateChange>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarkAllReadParams {
+    pub feed_id: Uuid,
+}
+
+/// Time spent reading an article, not counting time away from the keyboard
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadTimeParams {
+    pub id: Uuid,
+    pub duration_ms: u64,
+    /// How far the article was scrolled, in percent
+    pub scroll_depth: u8,
+}
+
+/// Explicit feedback on an article for preference learning
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArticleFeedbackParams {
+    pub id: Uuid,
+    /// More articles like this one (true) or fewer (false)
+    pub more: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArticleTagsParams {
+    pub ids: Vec<Uuid>,
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArticleDiscussionParams {
+    pub id: Uuid,
+    /// Number of top-level comments to fetch (0 for the link only)
+    #[serde(default)]
+    pub comments: usize,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ArticleSearchParams {
     pub query: String,
     pub feed_id: Option<Uuid>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArticleHistoryParams {
+    /// Maximum number of articles, most recently read first
+    pub limit: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArticleAdvisoriesParams {
+    /// Leave out articles whose most severe CVE scores lower (CVSS, 0-10);
+    /// 0 includes articles whose CVEs aren't scored yet
+    #[serde(default)]
+    pub min_score: f64,
+    /// Maximum number of articles, most severe first
+    pub limit: u32,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FeedAddParams {
     pub url: String,
     pub name: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedUpdateParams {
+    pub id: Uuid,
+    #[serde(flatten)]
+    pub settings: FeedSettings,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedReorderParams {
+    /// Feed IDs in their new order
+    pub ids: Vec<Uuid>,
+}
+
+/// Category of a feed; None or blank takes it out of its category
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedCategoryParams {
+    pub id: Uuid,
+    #[serde(default)]
+    pub category: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedPinParams {
+    pub id: Uuid,
+    pub pinned: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedDiscoverParams {
+    pub url: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FeedIdParams {
     pub id: Option<Uuid>,
 }
 
+/// Background task that `task.run` can start immediately
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SchedulerTask {
+    Refresh,
+    Cleanup,
+    Summarize,
+    Filter,
+    Classify,
+    #[serde(rename = "audio_digest")]
+    AudioDigest,
+    Prune,
+    Metadata,
+    Integrations,
+}
+
+impl SchedulerTask {
+    pub const ALL: [SchedulerTask; 9] = [
+        SchedulerTask::Refresh,
+        SchedulerTask::Cleanup,
+        SchedulerTask::Summarize,
+        SchedulerTask::Filter,
+        SchedulerTask::Classify,
+        SchedulerTask::AudioDigest,
+        SchedulerTask::Prune,
+        SchedulerTask::Metadata,
+        SchedulerTask::Integrations,
+    ];
+
+    /// Name used in `task.run` params and on the command line
+    pub fn name(self) -> &'static str {
+        match self {
+            SchedulerTask::Refresh => "refresh",
+            SchedulerTask::Cleanup => "cleanup",
+            SchedulerTask::Summarize => "summarize",
+            SchedulerTask::Filter => "filter",
+            SchedulerTask::Classify => "classify",
+            SchedulerTask::AudioDigest => "audio_digest",
+            SchedulerTask::Prune => "prune",
+            SchedulerTask::Metadata => "metadata",
+            SchedulerTask::Integrations => "integrations",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskRunParams {
+    pub name: SchedulerTask,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FetchProfileParams {
+    /// Days of fetches to include
+    pub days: u32,
+}
+
+/// Learned preferences of a time window, up to `limit` of each kind
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileInsightsParams {
+    #[serde(default = "default_insights_window")]
+    pub window: TimeWindow,
+    #[serde(default = "default_insights_limit")]
+    pub limit: usize,
+}
+
+fn default_insights_window() -> TimeWindow {
+    TimeWindow::Last30Days
+}
+
+fn default_insights_limit() -> usize {
+    10
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompareScoringParams {
+    #[serde(default)]
+    pub a: ScoringSettings,
+    #[serde(default)]
+    pub b: ScoringSettings,
+    /// Compare the articles fetched in the last `days` days
+    #[serde(default = "default_compare_days")]
+    pub days: u32,
+    /// At most this many articles, newest first
+    #[serde(default = "default_compare_limit")]
+    pub limit: u32,
+}
+
+fn default_compare_days() -> u32 {
+    7
+}
+
+fn default_compare_limit() -> u32 {
+    100
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeteredParams {
+    pub enabled: bool,
+}
+
 // Response structures
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -148,6 +425,18 @@ pub struct StatusResponse {
     pub running: bool,
     pub uptime_secs: u64,
     pub scheduler_running: bool,
+    /// AI request queue per task kind (None when AI is disabled)
+    #[serde(default)]
+    pub ai_queue: Option<Vec<AiQueueStats>>,
+    /// Seconds until the next scheduled feed refresh (None when it is off)
+    #[serde(default)]
+    pub next_refresh_secs: Option<u64>,
+    /// Whether metered mode is on
+    #[serde(default)]
+    pub metered: bool,
+    /// Bytes fetched today
+    #[serde(default)]
+    pub bandwidth_today_bytes: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -155,11 +444,53 @@ pub struct ArticleListResponse {
     pub articles: Vec<Article>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArticlePositionResponse {
+    /// Number of listed articles before it (none if it isn't listed)
+    pub position: Option<u32>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ArticleResponse {
     pub article: Option<Article>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArticleStyleResponse {
+    pub style: Option<ArticleStyle>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArticleDiscussionResponse {
+    /// Discussion page, from the feed or found on Hacker News
+    pub url: Option<String>,
+    #[serde(default)]
+    pub comments: Vec<Comment>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArticleArchivedResponse {
+    /// Latest Wayback Machine snapshot of the article page
+    pub snapshot_url: Option<String>,
+    /// Whether the original page is gone (404/410 or unreachable host)
+    pub original_gone: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArticlePageResponse {
+    pub page: FetchedPage,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagsAddResponse {
+    pub added: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagListResponse {
+    pub tags: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FeedListResponse {
     pub feeds: Vec<Feed>,
@@ -170,16 +501,102 @@ pub struct FeedAddResponse {
     pub feed: Feed,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedUpdateResponse {
+    pub feed: Feed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedDiscoverResponse {
+    pub feed: DiscoveredFeed,
+    /// Existing subscription to the same feed: the entered or discovered URL
+    /// up to canonicalization, its redirect target, or a URL a feed moved to
+    pub existing: Option<Feed>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedStatsResponse {
+    pub stats: FeedStats,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileInsightsResponse {
+    pub insights: ProfileInsights,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompareScoringResponse {
+    pub comparison: ScoreComparison,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FetchProfileResponse {
+    pub feeds: Vec<FetchProfile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PruneSuggestionsResponse {
+    pub feeds: Vec<PruneSuggestion>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToggleSavedResponse {
     pub is_saved: bool,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilteredArticlesResponse {
+    pub articles: Vec<FilteredArticle>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertedArticlesResponse {
+    pub articles: Vec<AlertedArticle>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdvisoryArticlesResponse {
+    pub articles: Vec<AdvisoryArticle>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarkAllReadResponse {
+    pub marked: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RescueResponse {
+    /// False if the article was not filtered
+    pub rescued: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RefreshResponse {
     pub new_articles: u32,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefreshDryRunResponse {
+    pub feeds: Vec<FeedDryRun>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefreshStartResponse {
+    /// False if a refresh started by a client is still running
+    pub started: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CleanupResponse {
+    pub deleted: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskRunResponse {
+    /// What the task did, e.g. "12 new articles"
+    pub summary: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchResponse {
     pub articles: Vec<Article>,
@@ -196,6 +613,16 @@ mod tests {
         assert!(json.contains("\"method\":\"ping\""));
     }
 
+    #[test]
+    fn test_scheduler_task_names_match_params() {
+        for task in SchedulerTask::ALL {
+            let params: TaskRunParams =
+                serde_json::from_value(serde_json::json!({ "name": task.name() })).unwrap();
+            assert_eq!(params.name, task);
+        }
+        assert!(serde_json::from_value::<TaskRunParams>(serde_json::json!({ "name": "x" })).is_err());
+    }
+
     #[test]
     fn test_response_success() {
         let id = Uuid::new_v4();
@@ -3,26 +3,50 @@
 //! Listens on Unix socket and handles client requests.
 
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Instant;
 
+use chrono::Utc;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::unix::{OwnedReadHalf, OwnedWriteHalf};
 use tokio::net::{UnixListener, UnixStream};
-use tokio::sync::{watch, Semaphore};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::{broadcast, watch, Semaphore};
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
 use super::protocol::*;
+use crate::ai::Summarizer;
+use crate::audio_digest;
+use crate::integrations;
 use crate::config::AppConfig;
-use crate::feed::NewFeed;
-use crate::profile::{BehaviorEventType, BehaviorTracker};
-use crate::scheduler::tasks;
-use crate::storage::{ArticleRepository, Database, FeedRepository};
+use crate::feed::{bandwidth, Feed, FeedFetcher, NewFeed};
+use crate::profile::{BehaviorEventType, BehaviorTracker, ProfileAnalyzer};
+use crate::scheduler::{tasks, NextRefresh, SchedulerEvent};
+use crate::storage::{ArticleRepository, ArticleStyleRepository, Database, FeedRepository, FetchMetricsRepository};
 use crate::Result;
 
 /// Maximum number of concurrent IPC requests to prevent connection pool exhaustion
 const MAX_CONCURRENT_REQUESTS: usize = 10;
 
+/// Scheduler events published to subscribed clients
+#[derive(Clone)]
+pub(super) struct EventHub {
+    tx: broadcast::Sender<SchedulerEvent>,
+    /// Whether a refresh started by a client is running
+    refreshing: Arc<AtomicBool>,
+    /// When the scheduler refreshes next, for `status`
+    next_refresh: NextRefresh,
+}
+
+impl EventHub {
+    fn send(&self, event: SchedulerEvent) {
+        // Nobody may be subscribed
+        let _ = self.tx.send(event);
+    }
+}
+
 /// IPC Server that handles client connections
 pub struct DaemonServer {
     db: Arc<Database>,
@@ -31,6 +55,10 @@ pub struct DaemonServer {
     start_time: Instant,
     /// Semaphore to limit concurrent request processing
     request_semaphore: Arc<Semaphore>,
+    /// Summarizer shared with the scheduler, for AI queue metrics and tasks
+    summarizer: Option<Arc<Summarizer>>,
+    /// Scheduler events for `events.subscribe` and background refreshes
+    events: Option<EventHub>,
 }
 
 impl DaemonServer {
@@ -42,9 +70,29 @@ impl DaemonServer {
             socket_path,
             start_time: Instant::now(),
             request_semaphore: Arc::new(Semaphore::new(MAX_CONCURRENT_REQUESTS)),
+            summarizer: None,
+            events: None,
         }
     }
 
+    /// Share the scheduler's summarizer (and its AI request pool)
+    pub fn with_summarizer(mut self, summarizer: Arc<Summarizer>) -> Self {
+        self.summarizer = Some(summarizer);
+        self
+    }
+
+    /// Publish the scheduler's events (the sender given to
+    /// `SchedulerService::with_event_sender`) to subscribed clients, and
+    /// report its next refresh (the handle given to `with_next_refresh`)
+    pub fn with_events(mut self, tx: broadcast::Sender<SchedulerEvent>, next_refresh: NextRefresh) -> Self {
+        self.events = Some(EventHub {
+            tx,
+            refreshing: Arc::new(AtomicBool::new(false)),
+            next_refresh,
+        });
+        self
+    }
+
     /// Run the IPC server
     pub async fn run(&self, mut shutdown_rx: watch::Receiver<bool>) -> Result<()> {
         // Remove old socket file if exists
@@ -69,8 +117,10 @@ impl DaemonServer {
                             let config = self.config.clone();
                             let start_time = self.start_time;
                             let semaphore = self.request_semaphore.clone();
+                            let summarizer = self.summarizer.clone();
+                            let events = self.events.clone();
                             tokio::spawn(async move {
-                                if let Err(e) = handle_connection(stream, db, config, start_time, semaphore).await {
+                                if let Err(e) = handle_connection(stream, db, config, start_time, semaphore, summarizer, events).await {
                                     warn!("Error handling connection: {}", e);
                                 }
                             });
@@ -101,6 +151,8 @@ async fn handle_connection(
     config: Arc<AppConfig>,
     start_time: Instant,
     semaphore: Arc<Semaphore>,
+    summarizer: Option<Arc<Summarizer>>,
+    events: Option<EventHub>,
 ) -> Result<()> {
     let (reader, mut writer) = stream.into_split();
     let mut reader = BufReader::new(reader);
@@ -113,6 +165,13 @@ async fn handle_connection(
             break; // Connection closed
         }
 
+        // A subscription turns the connection into an event stream
+        if let Ok(request) = serde_json::from_str::<Request>(&line) {
+            if request.method == methods::EVENTS_SUBSCRIBE {
+                return stream_events(request.id, reader, writer, events.as_ref()).await;
+            }
+        }
+
         // Acquire semaphore permit to limit concurrent request processing
         // This prevents connection pool exhaustion under high load
         let _permit = semaphore.acquire().await.map_err(|e| {
@@ -122,7 +181,7 @@ async fn handle_connection(
         let response = match serde_json::from_str::<Request>(&line) {
             Ok(request) => {
                 debug!("Received request: {} (id: {})", request.method, request.id);
-                handle_request(request, &db, &config, start_time).await
+                handle_request(request, &db, &config, start_time, summarizer.as_ref(), events.as_ref()).await
             }
             Err(e) => {
                 warn!("Failed to parse request: {}", e);
@@ -139,11 +198,90 @@ async fn handle_connection(
     Ok(())
 }
 
-async fn handle_request(
+/// Acknowledge a subscription, then write one scheduler event per line
+/// until the client disconnects or the daemon stops
+async fn stream_events(
+    id: Uuid,
+    mut reader: BufReader<OwnedReadHalf>,
+    mut writer: OwnedWriteHalf,
+    events: Option<&EventHub>,
+) -> Result<()> {
+    let Some(events) = events else {
+        let response = Response::error(id, ERR_METHOD_NOT_FOUND, "Events are not available");
+        writer.write_all(serde_json::to_string(&response)?.as_bytes()).await?;
+        writer.write_all(b"\n").await?;
+        return Ok(());
+    };
+    let mut rx = events.tx.subscribe();
+
+    let response = Response::ok(id);
+    writer.write_all(serde_json::to_string(&response)?.as_bytes()).await?;
+    writer.write_all(b"\n").await?;
+    writer.flush().await?;
+
+    let mut line = String::new();
+    loop {
+        tokio::select! {
+            event = rx.recv() => {
+                let event = match event {
+                    Ok(event) => event,
+                    Err(RecvError::Lagged(skipped)) => {
+                        debug!("Event subscriber lagged, {} events skipped", skipped);
+                        continue;
+                    }
+                    Err(RecvError::Closed) => break,
+                };
+                writer.write_all(serde_json::to_string(&event)?.as_bytes()).await?;
+                writer.write_all(b"\n").await?;
+                writer.flush().await?;
+            }
+            // Subscribers don't send anything else; EOF means they went away
+            read = reader.read_line(&mut line) => {
+                if read? == 0 {
+                    break;
+                }
+                line.clear();
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Refresh all feeds in the background, reporting progress and the result
+/// as scheduler events. Returns false if a client refresh is already running.
+fn start_refresh(db: &Database, config: &AppConfig, events: &EventHub) -> bool {
+    if events.refreshing.swap(true, Ordering::SeqCst) {
+        return false;
+    }
+    let (db, config, events) = (db.clone(), config.clone(), events.clone());
+    tokio::spawn(async move {
+        let progress = |done, total| events.send(SchedulerEvent::RefreshProgress { done, total });
+        let (event, moved) = match tasks::refresh_all_feeds_with_progress(&db, &config, progress).await {
+            Ok((new_articles, moved)) => (SchedulerEvent::FeedsRefreshed { new_articles }, moved),
+            Err(e) => {
+                let event = SchedulerEvent::Error {
+                    task: "refresh".to_string(),
+                    message: e.to_string(),
+                };
+                (event, Default::default())
+            }
+        };
+        events.refreshing.store(false, Ordering::SeqCst);
+        events.send(event);
+        for event in moved.into_events() {
+            events.send(event);
+        }
+    });
+    true
+}
+
+pub(super) async fn handle_request(
     request: Request,
     db: &Database,
     config: &AppConfig,
     start_time: Instant,
+    summarizer: Option<&Arc<Summarizer>>,
+    events: Option<&EventHub>,
 ) -> Response {
     let id = request.id;
 
@@ -157,7 +295,13 @@ async fn handle_request(
                 serde_json::json!({
                     "running": true,
                     "uptime_secs": uptime,
-                    "scheduler_running": true
+                    "scheduler_running": true,
+                    "ai_queue": summarizer.map(|s| s.queue_stats()),
+                    "next_refresh_secs": events
+                        .and_then(|events| events.next_refresh.remaining())
+                        .map(|left| left.as_secs()),
+                    "metered": bandwidth::is_metered(),
+                    "bandwidth_today_bytes": bandwidth::used_today()
                 }),
             )
         }
@@ -167,7 +311,8 @@ async fn handle_request(
                 Ok(params) => {
                     let repo = ArticleRepository::new(db);
                     let result = if let Some(feed_id) = params.feed_id {
-                        repo.list_by_feed(feed_id, params.unread_only).await
+                        repo.list_by_feed_page(feed_id, params.listing, params.limit, params.cursor)
+                            .await
                     } else {
                         // List all unread articles
                         repo.list_unread(1000).await
@@ -185,6 +330,22 @@ async fn handle_request(
             }
         }
 
+        methods::ARTICLE_POSITION => {
+            match serde_json::from_value::<ArticlePositionParams>(request.params) {
+                Ok(params) => {
+                    let repo = ArticleRepository::new(db);
+                    match repo.position_in_listing(params.feed_id, params.listing, params.id).await {
+                        Ok(position) => Response::success(
+                            id,
+                            serde_json::json!({ "position": position }),
+                        ),
+                        Err(e) => Response::error(id, ERR_INTERNAL, e.to_string()),
+                    }
+                }
+                Err(e) => Response::error(id, ERR_INVALID_PARAMS, e.to_string()),
+            }
+        }
+
         methods::ARTICLE_GET => {
             match serde_json::from_value::<ArticleIdParams>(request.params) {
                 Ok(params) => {
@@ -201,6 +362,22 @@ async fn handle_request(
             }
         }
 
+        methods::ARTICLE_STYLE => {
+            match serde_json::from_value::<ArticleIdParams>(request.params) {
+                Ok(params) => {
+                    let repo = ArticleStyleRepository::new(db);
+                    match repo.find_by_article(params.id).await {
+                        Ok(style) => Response::success(
+                            id,
+                            serde_json::json!({ "style": style }),
+                        ),
+                        Err(e) => Response::error(id, ERR_INTERNAL, e.to_string()),
+                    }
+                }
+                Err(e) => Response::error(id, ERR_INVALID_PARAMS, e.to_string()),
+            }
+        }
+
         methods::ARTICLE_MARK_READ => {
             match serde_json::from_value::<ArticleIdParams>(request.params) {
                 Ok(params) => {
@@ -250,6 +427,182 @@ async fn handle_request(
             }
         }
 
+        methods::ARTICLE_SET_READ_MANY => {
+            match serde_json::from_value::<ReadStateParams>(request.params) {
+                Ok(params) => {
+                    let repo = ArticleRepository::new(db);
+                    match repo.set_read_many(&params.changes).await {
+                        Ok(marked) => {
+                            // Record behavior events for user preference learning
+                            let tracker = BehaviorTracker::new(db);
+                            if let Err(e) = tracker.record_events(&marked, BehaviorEventType::Click).await {
+                                debug!("Failed to record behavior events: {}", e);
+                            }
+                            Response::ok(id)
+                        }
+                        Err(e) => Response::error(id, ERR_INTERNAL, e.to_string()),
+                    }
+                }
+                Err(e) => Response::error(id, ERR_INVALID_PARAMS, e.to_string()),
+            }
+        }
+
+        methods::ARTICLE_MARK_ALL_READ => {
+            match serde_json::from_value::<MarkAllReadParams>(request.params) {
+                // Catching up isn't interest: no behavior events are recorded
+                Ok(params) => match ArticleRepository::new(db).mark_all_read(params.feed_id).await {
+                    Ok(marked) => Response::success(id, serde_json::json!({ "marked": marked })),
+                    Err(e) => Response::error(id, ERR_INTERNAL, e.to_string()),
+                },
+                Err(e) => Response::error(id, ERR_INVALID_PARAMS, e.to_string()),
+            }
+        }
+
+        methods::ARTICLE_READ_TIME => {
+            match serde_json::from_value::<ReadTimeParams>(request.params) {
+                Ok(params) => match ArticleRepository::new(db).find_by_id(params.id).await {
+                    Ok(Some(article)) => {
+                        let tracker = BehaviorTracker::new(db);
+                        match tracker
+                            .record_read_complete(
+                                params.id,
+                                article.feed_id,
+                                params.duration_ms as i64,
+                                params.scroll_depth.min(100),
+                            )
+                            .await
+                        {
+                            Ok(()) => Response::ok(id),
+                            Err(e) => Response::error(id, ERR_INTERNAL, e.to_string()),
+                        }
+                    }
+                    Ok(None) => Response::error(id, ERR_INVALID_PARAMS, "Article not found"),
+                    Err(e) => Response::error(id, ERR_INTERNAL, e.to_string()),
+                },
+                Err(e) => Response::error(id, ERR_INVALID_PARAMS, e.to_string()),
+            }
+        }
+
+        methods::ARTICLE_FEEDBACK => {
+            match serde_json::from_value::<ArticleFeedbackParams>(request.params) {
+                Ok(params) => match ArticleRepository::new(db).find_by_id(params.id).await {
+                    Ok(Some(article)) => {
+                        let tracker = BehaviorTracker::new(db);
+                        match tracker.record_feedback(params.id, article.feed_id, params.more).await {
+                            Ok(()) => Response::ok(id),
+                            Err(e) => Response::error(id, ERR_INTERNAL, e.to_string()),
+                        }
+                    }
+                    Ok(None) => Response::error(id, ERR_INVALID_PARAMS, "Article not found"),
+                    Err(e) => Response::error(id, ERR_INTERNAL, e.to_string()),
+                },
+                Err(e) => Response::error(id, ERR_INVALID_PARAMS, e.to_string()),
+            }
+        }
+
+        methods::ARTICLE_FILTERED => {
+            match serde_json::from_value::<ArticleHistoryParams>(request.params) {
+                Ok(params) => match ArticleRepository::new(db).list_filtered(params.limit).await {
+                    Ok(articles) => Response::success(id, serde_json::json!({ "articles": articles })),
+                    Err(e) => Response::error(id, ERR_INTERNAL, e.to_string()),
+                },
+                Err(e) => Response::error(id, ERR_INVALID_PARAMS, e.to_string()),
+            }
+        }
+
+        methods::ARTICLE_ALERTS => {
+            match serde_json::from_value::<ArticleHistoryParams>(request.params) {
+                Ok(params) => match ArticleRepository::new(db).list_alerted(params.limit).await {
+                    Ok(articles) => Response::success(id, serde_json::json!({ "articles": articles })),
+                    Err(e) => Response::error(id, ERR_INTERNAL, e.to_string()),
+                },
+                Err(e) => Response::error(id, ERR_INVALID_PARAMS, e.to_string()),
+            }
+        }
+
+        methods::ARTICLE_ADVISORIES => {
+            match serde_json::from_value::<ArticleAdvisoriesParams>(request.params) {
+                Ok(params) => match ArticleRepository::new(db).list_advisories(params.min_score, params.limit).await {
+                    Ok(articles) => Response::success(id, serde_json::json!({ "articles": articles })),
+                    Err(e) => Response::error(id, ERR_INTERNAL, e.to_string()),
+                },
+                Err(e) => Response::error(id, ERR_INVALID_PARAMS, e.to_string()),
+            }
+        }
+
+        methods::ARTICLE_RESCUE => {
+            match serde_json::from_value::<ArticleIdParams>(request.params) {
+                Ok(params) => match rescue_article(db, params.id).await {
+                    Ok(rescued) => Response::success(id, serde_json::json!({ "rescued": rescued })),
+                    Err(e) => Response::error(id, ERR_INTERNAL, e.to_string()),
+                },
+                Err(e) => Response::error(id, ERR_INVALID_PARAMS, e.to_string()),
+            }
+        }
+
+        methods::ARTICLE_ADD_TAGS => {
+            match serde_json::from_value::<ArticleTagsParams>(request.params) {
+                Ok(params) => {
+                    let repo = ArticleRepository::new(db);
+                    match repo.add_tags_bulk(&params.ids, &params.tags, "user").await {
+                        Ok(added) => Response::success(
+                            id,
+                            serde_json::json!({ "added": added }),
+                        ),
+                        Err(e) => Response::error(id, ERR_INTERNAL, e.to_string()),
+                    }
+                }
+                Err(e) => Response::error(id, ERR_INVALID_PARAMS, e.to_string()),
+            }
+        }
+
+        methods::ARTICLE_DISCUSSION => {
+            match serde_json::from_value::<ArticleDiscussionParams>(request.params) {
+                Ok(params) => match article_discussion(db, config, &params).await {
+                    Ok(response) => Response::success(id, serde_json::json!(response)),
+                    Err(e) => Response::error(id, ERR_INTERNAL, e.to_string()),
+                },
+                Err(e) => Response::error(id, ERR_INVALID_PARAMS, e.to_string()),
+            }
+        }
+
+        methods::ARTICLE_ARCHIVED => {
+            match serde_json::from_value::<ArticleIdParams>(request.params) {
+                Ok(params) => match article_archived(db, config, params.id).await {
+                    Ok(response) => Response::success(id, serde_json::json!(response)),
+                    Err(e) => Response::error(id, ERR_INTERNAL, e.to_string()),
+                },
+                Err(e) => Response::error(id, ERR_INVALID_PARAMS, e.to_string()),
+            }
+        }
+
+        methods::ARTICLE_PAGE => {
+            match serde_json::from_value::<ArticleIdParams>(request.params) {
+                Ok(params) => {
+                    let page = match article_url(db, params.id).await {
+                        Ok(url) => match FeedFetcher::new(config) {
+                            Ok(fetcher) => fetcher.fetch_page(&url).await,
+                            Err(e) => Err(e),
+                        },
+                        Err(e) => Err(e),
+                    };
+                    match page {
+                        Ok(page) => Response::success(id, serde_json::json!({ "page": page })),
+                        Err(e) => Response::error(id, ERR_INTERNAL, e.to_string()),
+                    }
+                }
+                Err(e) => Response::error(id, ERR_INVALID_PARAMS, e.to_string()),
+            }
+        }
+
+        methods::TAG_LIST => {
+            let repo = ArticleRepository::new(db);
+            match repo.list_tags().await {
+                Ok(tags) => Response::success(id, serde_json::json!({ "tags": tags })),
+                Err(e) => Response::error(id, ERR_INTERNAL, e.to_string()),
+            }
+        }
+
         methods::ARTICLE_TOGGLE_SAVED => {
             match serde_json::from_value::<ArticleIdParams>(request.params) {
                 Ok(params) => {
@@ -307,6 +660,22 @@ async fn handle_request(
             }
         }
 
+        methods::ARTICLE_HISTORY => {
+            match serde_json::from_value::<ArticleHistoryParams>(request.params) {
+                Ok(params) => {
+                    let repo = ArticleRepository::new(db);
+                    match repo.list_recently_read(params.limit).await {
+                        Ok(articles) => Response::success(
+                            id,
+                            serde_json::json!({ "articles": articles }),
+                        ),
+                        Err(e) => Response::error(id, ERR_INTERNAL, e.to_string()),
+                    }
+                }
+                Err(e) => Response::error(id, ERR_INVALID_PARAMS, e.to_string()),
+            }
+        }
+
         methods::FEED_LIST => {
             let repo = FeedRepository::new(db);
             match repo.list_all().await {
@@ -348,12 +717,145 @@ async fn handle_request(
             }
         }
 
+        methods::FEED_UPDATE => {
+            match serde_json::from_value::<FeedUpdateParams>(request.params) {
+                Ok(params) => {
+                    let settings = params.settings;
+                    if settings.url.trim().is_empty() || settings.local_name.trim().is_empty() {
+                        return Response::error(id, ERR_INVALID_PARAMS, "Feed URL and name are required");
+                    }
+                    let repo = FeedRepository::new(db);
+                    match repo.update_settings(params.id, &settings).await {
+                        Ok(true) => match repo.find_by_id(params.id).await {
+                            Ok(Some(feed)) => Response::success(id, serde_json::json!({ "feed": feed })),
+                            Ok(None) => Response::error(id, ERR_INVALID_PARAMS, "Feed not found"),
+                            Err(e) => Response::error(id, ERR_INTERNAL, e.to_string()),
+                        },
+                        Ok(false) => Response::error(id, ERR_INVALID_PARAMS, "Feed not found"),
+                        Err(e) => Response::error(id, ERR_INTERNAL, e.to_string()),
+                    }
+                }
+                Err(e) => Response::error(id, ERR_INVALID_PARAMS, e.to_string()),
+            }
+        }
+
+        methods::FEED_REORDER => {
+            match serde_json::from_value::<FeedReorderParams>(request.params) {
+                Ok(params) => match FeedRepository::new(db).reorder(&params.ids).await {
+                    Ok(()) => Response::success(id, serde_json::json!({ "ok": true })),
+                    Err(e) => Response::error(id, ERR_INTERNAL, e.to_string()),
+                },
+                Err(e) => Response::error(id, ERR_INVALID_PARAMS, e.to_string()),
+            }
+        }
+
+        methods::FEED_PIN => {
+            match serde_json::from_value::<FeedPinParams>(request.params) {
+                Ok(params) => match FeedRepository::new(db).set_pinned(params.id, params.pinned).await {
+                    Ok(true) => Response::success(id, serde_json::json!({ "ok": true })),
+                    Ok(false) => Response::error(id, ERR_INVALID_PARAMS, "Feed not found"),
+                    Err(e) => Response::error(id, ERR_INTERNAL, e.to_string()),
+                },
+                Err(e) => Response::error(id, ERR_INVALID_PARAMS, e.to_string()),
+            }
+        }
+
+        methods::FEED_SET_CATEGORY => {
+            match serde_json::from_value::<FeedCategoryParams>(request.params) {
+                Ok(params) => match FeedRepository::new(db)
+                    .set_category(params.id, params.category.as_deref())
+                    .await
+                {
+                    Ok(true) => Response::success(id, serde_json::json!({ "ok": true })),
+                    Ok(false) => Response::error(id, ERR_INVALID_PARAMS, "Feed not found"),
+                    Err(e) => Response::error(id, ERR_INTERNAL, e.to_string()),
+                },
+                Err(e) => Response::error(id, ERR_INVALID_PARAMS, e.to_string()),
+            }
+        }
+
+        methods::FEED_STATS => {
+            match serde_json::from_value::<ArticleIdParams>(request.params) {
+                Ok(params) => match FeedRepository::new(db).stats(params.id).await {
+                    Ok(stats) => Response::success(id, serde_json::json!({ "stats": stats })),
+                    Err(e) => Response::error(id, ERR_INTERNAL, e.to_string()),
+                },
+                Err(e) => Response::error(id, ERR_INVALID_PARAMS, e.to_string()),
+            }
+        }
+
+        methods::FEED_FETCH_PROFILE => match serde_json::from_value::<FetchProfileParams>(request.params) {
+            Ok(params) => {
+                let since = Utc::now() - chrono::Duration::days(i64::from(params.days));
+                match FetchMetricsRepository::new(db).profile(since).await {
+                    Ok(feeds) => Response::success(id, serde_json::json!({ "feeds": feeds })),
+                    Err(e) => Response::error(id, ERR_INTERNAL, e.to_string()),
+                }
+            }
+            Err(e) => Response::error(id, ERR_INVALID_PARAMS, e.to_string()),
+        },
+
+        methods::PROFILE_INSIGHTS => match serde_json::from_value::<ProfileInsightsParams>(request.params) {
+            Ok(params) => match ProfileAnalyzer::new(db).insights(params.window, params.limit).await {
+                Ok(insights) => Response::success(id, serde_json::json!({ "insights": insights })),
+                Err(e) => Response::error(id, ERR_INTERNAL, e.to_string()),
+            },
+            Err(e) => Response::error(id, ERR_INVALID_PARAMS, e.to_string()),
+        },
+
+        methods::PROFILE_COMPARE_SCORING => match serde_json::from_value::<CompareScoringParams>(request.params) {
+            Ok(params) => {
+                match tasks::compare_scoring(db, config, summarizer, &params.a, &params.b, params.days, params.limit).await {
+                    Ok(comparison) => Response::success(id, serde_json::json!({ "comparison": comparison })),
+                    Err(e) => Response::error(id, ERR_INTERNAL, e.to_string()),
+                }
+            }
+            Err(e) => Response::error(id, ERR_INVALID_PARAMS, e.to_string()),
+        },
+
+        methods::FEED_PRUNE_SUGGESTIONS => match tasks::find_stale_feeds(db, config).await {
+            Ok(feeds) => Response::success(id, serde_json::json!({ "feeds": feeds })),
+            Err(e) => Response::error(id, ERR_INTERNAL, e.to_string()),
+        },
+
+        methods::FEED_DISCOVER => {
+            match serde_json::from_value::<FeedDiscoverParams>(request.params) {
+                Ok(params) => {
+                    let discovered = match FeedFetcher::new(config) {
+                        Ok(fetcher) => fetcher.discover(params.url.trim()).await,
+                        Err(e) => Err(e),
+                    };
+                    match discovered {
+                        Ok(feed) => {
+                            let mut urls = vec![params.url.trim(), feed.url.as_str()];
+                            urls.extend(feed.redirected_to.as_deref());
+                            let existing = FeedRepository::new(db).find_equivalent(&urls).await.ok().flatten();
+                            Response::success(
+                                id,
+                                serde_json::json!({
+                                    "feed": feed,
+                                    "existing": existing
+                                }),
+                            )
+                        }
+                        Err(e) => Response::error(id, ERR_INTERNAL, e.to_string()),
+                    }
+                }
+                Err(e) => Response::error(id, ERR_INVALID_PARAMS, e.to_string()),
+            }
+        }
+
         methods::FEED_REFRESH => {
             match serde_json::from_value::<FeedIdParams>(request.params) {
                 Ok(params) => {
-                    if params.id.is_some() {
-                        // Single feed refresh not implemented yet
-                        Response::error(id, ERR_INTERNAL, "Single feed refresh not implemented")
+                    if let Some(feed_id) = params.id {
+                        match tasks::refresh_feed(db, config, feed_id).await {
+                            Ok(count) => Response::success(
+                                id,
+                                serde_json::json!({ "new_articles": count }),
+                            ),
+                            Err(e) => Response::error(id, ERR_INTERNAL, e.to_string()),
+                        }
                     } else {
                         // Refresh all feeds
                         match tasks::refresh_all_feeds(db, config).await {
@@ -369,6 +871,194 @@ async fn handle_request(
             }
         }
 
+        methods::FEED_REFRESH_DRY_RUN => match tasks::dry_run_refresh(db, config).await {
+            Ok(feeds) => Response::success(id, serde_json::json!({ "feeds": feeds })),
+            Err(e) => Response::error(id, ERR_INTERNAL, e.to_string()),
+        },
+
+        methods::FEED_REFRESH_START => match events {
+            Some(events) => {
+                let started = start_refresh(db, config, events);
+                Response::success(id, serde_json::json!({ "started": started }))
+            }
+            None => Response::error(id, ERR_METHOD_NOT_FOUND, "Background refresh needs the daemon"),
+        },
+
+        methods::ARTICLE_CLEANUP => match tasks::cleanup_old_articles(db, config).await {
+            Ok(deleted) => Response::success(id, serde_json::json!({ "deleted": deleted })),
+            Err(e) => Response::error(id, ERR_INTERNAL, e.to_string()),
+        },
+
+        methods::TASK_RUN => match serde_json::from_value::<TaskRunParams>(request.params) {
+            Ok(params) => match run_task(params.name, db, config, summarizer).await {
+                Ok(summary) => Response::success(id, serde_json::json!({ "summary": summary })),
+                Err(e) => Response::error(id, ERR_INTERNAL, e.to_string()),
+            },
+            Err(e) => Response::error(id, ERR_INVALID_PARAMS, e.to_string()),
+        },
+
+        methods::METERED_SET => match serde_json::from_value::<MeteredParams>(request.params) {
+            Ok(params) => {
+                if params.enabled != bandwidth::is_metered() {
+                    info!("Metered mode {}", if params.enabled { "on" } else { "off" });
+                }
+                bandwidth::set_metered(params.enabled);
+                Response::ok(id)
+            }
+            Err(e) => Response::error(id, ERR_INVALID_PARAMS, e.to_string()),
+        },
+
         _ => Response::error(id, ERR_METHOD_NOT_FOUND, "Method not found"),
     }
 }
+
+/// Discussion page of an article and its top comments. Articles whose feed
+/// names no discussion are looked up on Hacker News, remembering the result.
+async fn article_discussion(
+    db: &Database,
+    config: &AppConfig,
+    params: &ArticleDiscussionParams,
+) -> Result<ArticleDiscussionResponse> {
+    let repo = ArticleRepository::new(db);
+    let article = repo
+        .find_by_id(params.id)
+        .await?
+        .ok_or_else(|| crate::Error::ArticleNotFound(params.id.to_string()))?;
+    let fetcher = FeedFetcher::new(config)?;
+
+    let mut url = article.comments_url;
+    if url.is_none() {
+        if let Some(article_url) = &article.url {
+            url = fetcher.find_discussion(article_url).await?;
+            if let Some(found) = &url {
+                repo.set_comments_url(article.id, found).await?;
+            }
+        }
+    }
+
+    let comments = match &url {
+        Some(url) if params.comments > 0 => fetcher.fetch_comments(url, params.comments).await?,
+        _ => Vec::new(),
+    };
+    Ok(ArticleDiscussionResponse { url, comments })
+}
+
+/// Link of an article, for requests that need its web page
+async fn article_url(db: &Database, id: Uuid) -> Result<String> {
+    ArticleRepository::new(db)
+        .find_by_id(id)
+        .await?
+        .ok_or_else(|| crate::Error::ArticleNotFound(id.to_string()))?
+        .url
+        .ok_or_else(|| crate::Error::Other("Article has no link".to_string()))
+}
+
+/// Wayback Machine snapshot of an article page, and whether the original is gone
+async fn article_archived(db: &Database, config: &AppConfig, id: Uuid) -> Result<ArticleArchivedResponse> {
+    let url = article_url(db, id).await?;
+    let fetcher = FeedFetcher::new(config)?;
+    let (snapshot_url, original_gone) = tokio::join!(fetcher.find_snapshot(&url), fetcher.is_gone(&url));
+    Ok(ArticleArchivedResponse {
+        snapshot_url: snapshot_url?,
+        original_gone,
+    })
+}
+
+/// Mark a filtered article unread and learn from it: rescuing counts as
+/// asking for more articles like it
+async fn rescue_article(db: &Database, article_id: Uuid) -> Result<bool> {
+    let repo = ArticleRepository::new(db);
+    let Some(article) = repo.find_by_id(article_id).await? else {
+        return Ok(false);
+    };
+    if !repo.rescue_filtered(article_id).await? {
+        return Ok(false);
+    }
+    BehaviorTracker::new(db).record_feedback(article_id, article.feed_id, true).await?;
+    info!("Rescued filtered article {}", article_id);
+    Ok(true)
+}
+
+/// Run one scheduler task now and describe what it did
+async fn run_task(
+    task: SchedulerTask,
+    db: &Database,
+    config: &AppConfig,
+    shared: Option<&Arc<Summarizer>>,
+) -> Result<String> {
+    info!("Running task on request: {:?}", task);
+    let summarizer = || -> Result<Arc<Summarizer>> {
+        if !config.ai.enabled {
+            return Err(crate::Error::Config("AI is disabled (ai.enabled = false)".to_string()));
+        }
+        match shared {
+            Some(summarizer) => Ok(summarizer.clone()),
+            None => Ok(Arc::new(Summarizer::new(config)?)),
+        }
+    };
+
+    Ok(match task {
+        SchedulerTask::Refresh => {
+            format!("{} new articles", tasks::refresh_all_feeds(db, config).await?)
+        }
+        SchedulerTask::Cleanup => {
+            let deleted = tasks::cleanup_old_articles(db, config).await?;
+            match tasks::auto_mark_read(db, config).await? {
+                0 => format!("{} old articles deleted", deleted),
+                marked => format!("{} old articles deleted, {} aged articles marked read", deleted, marked),
+            }
+        }
+        SchedulerTask::Summarize => {
+            let count = tasks::summarize_pending_articles(db, summarizer()?).await?;
+            format!("{} articles summarized", count)
+        }
+        SchedulerTask::Filter => {
+            let (scored, filtered) = tasks::score_and_filter_articles(
+                db,
+                summarizer()?,
+                config.ai.relevance_threshold,
+                config.ai.min_summarize_length,
+                config.ai.preference_half_life_days,
+            )
+            .await?;
+            format!("{} articles scored, {} filtered", scored, filtered)
+        }
+        SchedulerTask::Classify => {
+            let count =
+                tasks::classify_pending_articles(db, summarizer()?, tasks::CLASSIFY_BATCH_SIZE)
+                    .await?;
+            format!("{} articles classified", count)
+        }
+        SchedulerTask::AudioDigest => match audio_digest::generate(db, config).await? {
+            Some(path) => format!("audio briefing written to {}", path.display()),
+            None => "no new articles for an audio briefing".to_string(),
+        },
+        SchedulerTask::Prune => {
+            let feeds = tasks::find_stale_feeds(db, config).await?;
+            let names: Vec<&str> = feeds.iter().map(|f| f.name.as_str()).collect();
+            if names.is_empty() {
+                "no feeds worth unsubscribing from".to_string()
+            } else {
+                format!("{} feeds worth unsubscribing from: {}", names.len(), names.join(", "))
+            }
+        }
+        SchedulerTask::Metadata => {
+            let moved = tasks::refresh_feed_metadata(db, config).await?;
+            let names = |feeds: &[Feed]| feeds.iter().map(|f| f.local_name.as_str()).collect::<Vec<_>>().join(", ");
+            let mut summary = "feed metadata refreshed".to_string();
+            if !moved.flagged.is_empty() {
+                summary += &format!(", {} feeds moved: {}", moved.flagged.len(), names(&moved.flagged));
+            }
+            if !moved.migrated.is_empty() {
+                summary += &format!(", {} feeds switched to their new URL: {}", moved.migrated.len(), names(&moved.migrated));
+            }
+            summary
+        }
+        SchedulerTask::Integrations => {
+            if !config.integrations.destinations.iter().any(|d| d.enabled) {
+                return Err(crate::Error::Config("no integration destinations are configured".to_string()));
+            }
+            format!("{} saved articles exported", integrations::export_saved(db, config).await?)
+        }
+    })
+}
@@ -0,0 +1,10 @@
+//! Random delays spreading out work that would otherwise happen together
+
+use std::time::Duration;
+
+/// Random delay between zero and `max`, to the millisecond
+pub(crate) fn jitter(max: Duration) -> Duration {
+    // A v4 UUID is 122 random bits; avoids pulling in a RNG crate
+    let millis = uuid::Uuid::new_v4().as_u128() % (max.as_millis() + 1);
+    Duration::from_millis(millis as u64)
+}
@@ -5,6 +5,19 @@ pub mod storage;
 pub mod ai;
 pub mod profile;
 pub mod scheduler;
+pub mod state_sync;
+pub mod notify;
+pub mod alerts;
+pub mod advisories;
+pub mod audio_digest;
+pub mod integrations;
+pub mod mqtt;
+pub mod web;
+mod http;
+mod jitter;
+pub mod calendar;
+pub mod secrets;
+pub mod shell;
 pub mod ipc;
 
 pub use config::{AppConfig, EasingType, ScrollConfig};
@@ -0,0 +1,238 @@
+//! Publishing to an MQTT broker for home automation
+//!
+//! The daemon publishes every scheduler event to `<topic>/events/<event>`,
+//! each new article to `<topic>/articles`, and keeps the retained
+//! `<topic>/top_stories` message up to date with the most relevant unread
+//! articles of the day (e.g. for a dashboard).
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use rumqttc::{AsyncClient, Event, MqttOptions, Packet, QoS};
+use serde_json::{json, Value};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::{broadcast, watch};
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::audio_digest::pick_articles;
+use crate::config::AppConfig;
+use crate::feed::Article;
+use crate::scheduler::SchedulerEvent;
+use crate::storage::{ArticleRepository, Database, FeedRepository};
+use crate::Result;
+
+/// Messages queued while the broker is unreachable
+const QUEUE_CAPACITY: usize = 64;
+
+/// Keep-alive interval of the broker connection
+const KEEP_ALIVE: Duration = Duration::from_secs(30);
+
+/// Wait before reconnecting to the broker
+const RECONNECT_DELAY: Duration = Duration::from_secs(10);
+
+/// Top stories are picked from the articles of the last day
+const TOP_STORIES_PERIOD: chrono::Duration = chrono::Duration::hours(24);
+
+/// Topic and payload of a scheduler event
+pub fn event_message(prefix: &str, event: &SchedulerEvent) -> (String, Value) {
+    let payload = serde_json::to_value(event).unwrap_or(Value::Null);
+    let name = payload["event"].as_str().unwrap_or("unknown");
+    (format!("{}/events/{}", prefix, name), payload)
+}
+
+/// Metadata published for an article
+pub fn article_payload(feed_names: &HashMap<Uuid, String>, article: &Article) -> Value {
+    json!({
+        "id": article.id,
+        "feed": feed_names.get(&article.feed_id),
+        "title": article.title,
+        "url": article.url,
+        "author": article.author,
+        "published_at": article.published_at,
+        "relevance_score": article.relevance_score,
+        "summary": article.summary,
+        "image_url": article.image_url,
+    })
+}
+
+/// Publish to the broker from a background task (if enabled), until shutdown
+pub fn spawn(
+    db: Arc<Database>,
+    config: Arc<AppConfig>,
+    events: broadcast::Receiver<SchedulerEvent>,
+    shutdown: watch::Receiver<bool>,
+) {
+    if !config.mqtt.enabled {
+        return;
+    }
+    tokio::spawn(run(db, config, events, shutdown));
+}
+
+async fn run(
+    db: Arc<Database>,
+    config: Arc<AppConfig>,
+    mut events: broadcast::Receiver<SchedulerEvent>,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    let settings = &config.mqtt;
+    let mut options = MqttOptions::new(&settings.client_id, &settings.host, settings.port);
+    options.set_keep_alive(KEEP_ALIVE);
+    if let Some(ref username) = settings.username {
+        options.set_credentials(username, settings.password.as_deref().unwrap_or(""));
+    }
+    let (client, mut eventloop) = AsyncClient::new(options, QUEUE_CAPACITY);
+    let mut publisher = Publisher {
+        db,
+        config: config.clone(),
+        client,
+        since: Utc::now(),
+    };
+    let mut connected = false;
+
+    loop {
+        tokio::select! {
+            _ = shutdown.changed() => {
+                let _ = publisher.client.try_disconnect();
+                break;
+            }
+            notification = eventloop.poll() => match notification {
+                Ok(Event::Incoming(Packet::ConnAck(_))) => {
+                    info!("Connected to MQTT broker {}:{}", settings.host, settings.port);
+                    connected = true;
+                    publisher.publish_top_stories().await;
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    if connected {
+                        warn!("Lost connection to MQTT broker: {}", e);
+                    } else {
+                        debug!("MQTT broker unreachable: {}", e);
+                    }
+                    connected = false;
+                    tokio::time::sleep(RECONNECT_DELAY).await;
+                }
+            },
+            event = events.recv() => match event {
+                Ok(event) => publisher.publish_event(event).await,
+                Err(RecvError::Lagged(skipped)) => debug!("MQTT publisher skipped {} events", skipped),
+                Err(RecvError::Closed) => break,
+            },
+        }
+    }
+}
+
+struct Publisher {
+    db: Arc<Database>,
+    config: Arc<AppConfig>,
+    client: AsyncClient,
+    /// New articles are published once fetched after this time
+    since: DateTime<Utc>,
+}
+
+impl Publisher {
+    /// Queue a message; dropped when the broker has been away long enough
+    /// to fill the queue
+    fn publish(&self, topic: String, retain: bool, payload: &Value) {
+        if let Err(e) = self.client.try_publish(topic, QoS::AtLeastOnce, retain, payload.to_string()) {
+            debug!("MQTT message dropped: {}", e);
+        }
+    }
+
+    async fn publish_event(&mut self, event: SchedulerEvent) {
+        let (topic, payload) = event_message(&self.config.mqtt.topic, &event);
+        self.publish(topic, false, &payload);
+        match event {
+            SchedulerEvent::FeedsRefreshed { new_articles } if new_articles > 0 => {
+                if let Err(e) = self.publish_new_articles().await {
+                    warn!("Failed to publish new articles: {}", e);
+                }
+                self.publish_top_stories().await;
+            }
+            SchedulerEvent::ArticlesFiltered { .. }
+            | SchedulerEvent::ArticlesSummarized { .. }
+            | SchedulerEvent::ArticlesCleaned { .. }
+            | SchedulerEvent::ArticlesAutoRead { .. } => self.publish_top_stories().await,
+            _ => {}
+        }
+    }
+
+    async fn feed_names(&self) -> Result<HashMap<Uuid, String>> {
+        Ok(FeedRepository::new(&self.db)
+            .list_all()
+            .await?
+            .into_iter()
+            .map(|f| (f.id, f.local_name))
+            .collect())
+    }
+
+    /// Publish the unread articles fetched since the last call
+    async fn publish_new_articles(&mut self) -> Result<()> {
+        let now = Utc::now();
+        let articles = ArticleRepository::new(&self.db)
+            .list_unread_created_since(self.since)
+            .await?;
+        self.since = now;
+        let feed_names = self.feed_names().await?;
+        let topic = format!("{}/articles", self.config.mqtt.topic);
+        for article in &articles {
+            self.publish(topic.clone(), false, &article_payload(&feed_names, article));
+        }
+        Ok(())
+    }
+
+    /// The most relevant unread articles of the day
+    async fn top_stories(&self, count: usize) -> Result<Vec<Value>> {
+        let articles = ArticleRepository::new(&self.db)
+            .list_unread_created_since(Utc::now() - TOP_STORIES_PERIOD)
+            .await?;
+        let feed_names = self.feed_names().await?;
+        Ok(pick_articles(articles, count)
+            .iter()
+            .map(|article| article_payload(&feed_names, article))
+            .collect())
+    }
+
+    /// Replace the retained top stories message
+    async fn publish_top_stories(&self) {
+        let count = self.config.mqtt.top_stories;
+        if count == 0 {
+            return;
+        }
+        match self.top_stories(count).await {
+            Ok(stories) => self.publish(format!("{}/top_stories", self.config.mqtt.topic), true, &json!(stories)),
+            Err(e) => warn!("Failed to publish top stories: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_message() {
+        let (topic, payload) = event_message("home/news", &SchedulerEvent::FeedsRefreshed { new_articles: 3 });
+        assert_eq!(topic, "home/news/events/feeds_refreshed");
+        assert_eq!(payload, json!({ "event": "feeds_refreshed", "new_articles": 3 }));
+    }
+
+    #[test]
+    fn test_article_payload() {
+        let article = Article {
+            url: Some("https://example.com/post".to_string()),
+            summary: Some("In short.".to_string()),
+            relevance_score: Some(0.9),
+            ..Article::for_test("Post")
+        };
+        let feed_names = HashMap::from([(article.feed_id, "blog".to_string())]);
+        let payload = article_payload(&feed_names, &article);
+        assert_eq!(payload["feed"], "blog");
+        assert_eq!(payload["title"], "Post");
+        assert_eq!(payload["url"], "https://example.com/post");
+        assert_eq!(payload["relevance_score"], 0.9);
+        assert_eq!(payload["published_at"], Value::Null);
+    }
+}
@@ -0,0 +1,451 @@
+//! Desktop and push notifications for new articles
+//!
+//! The daemon announces articles fetched since the last notification, either
+//! one notification per article or as a periodic digest
+//! ("12 new articles in 4 feeds, 3 high relevance"). Articles matching a
+//! push channel's keywords, or scoring high once filtered, are also sent to
+//! ntfy or Gotify so they reach a phone.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use reqwest::header::AUTHORIZATION;
+use reqwest::Client;
+use serde_json::{json, Value};
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use crate::config::{AppConfig, NotificationMode, PushChannel, PushService};
+use crate::feed::Article;
+use crate::shell;
+use crate::storage::{ArticleRepository, Database, FeedRepository};
+use crate::{Error, Result};
+
+/// Command used when `notifications.command` is not set
+const DEFAULT_COMMAND: &str = "notify-send {title} {body}";
+
+/// ntfy server used when a channel names none
+const NTFY_URL: &str = "https://ntfy.sh";
+
+/// How long after being fetched an article may still be pushed, e.g. once
+/// filtering has scored it
+const PUSH_WINDOW: chrono::Duration = chrono::Duration::hours(24);
+
+/// A notification to show
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    pub title: String,
+    pub body: String,
+}
+
+/// Summary of a batch of new articles
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DigestStats {
+    pub articles: u32,
+    pub feeds: u32,
+    /// Articles scored at or above the high relevance threshold
+    pub high_relevance: u32,
+}
+
+impl DigestStats {
+    pub fn from_articles(articles: &[Article], high_relevance_threshold: f64) -> Self {
+        let feeds: HashSet<_> = articles.iter().map(|a| a.feed_id).collect();
+        let high_relevance = articles
+            .iter()
+            .filter(|a| a.relevance_score.is_some_and(|s| s >= high_relevance_threshold))
+            .count();
+        Self {
+            articles: articles.len() as u32,
+            feeds: feeds.len() as u32,
+            high_relevance: high_relevance as u32,
+        }
+    }
+
+    /// Digest notification (None when there is nothing new)
+    pub fn notification(&self) -> Option<Notification> {
+        if self.articles == 0 {
+            return None;
+        }
+        let mut body = format!(
+            "{} in {}",
+            plural(self.articles, "new article"),
+            plural(self.feeds, "feed")
+        );
+        if self.high_relevance > 0 {
+            body.push_str(&format!(", {} high relevance", self.high_relevance));
+        }
+        Some(Notification {
+            title: "kenseader".to_string(),
+            body,
+        })
+    }
+}
+
+fn plural(count: u32, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {}", noun)
+    } else {
+        format!("{} {}s", count, noun)
+    }
+}
+
+/// Fill the `{title}` and `{body}` placeholders of a notification command
+pub fn expand_command(template: &str, notification: &Notification) -> String {
+    shell::expand(template, &[("title", &notification.title), ("body", &notification.body)])
+}
+
+/// Run the configured notification command
+pub async fn send(config: &AppConfig, notification: &Notification) -> Result<()> {
+    let template = config
+        .notifications
+        .command
+        .as_deref()
+        .unwrap_or(DEFAULT_COMMAND);
+    let command = expand_command(template, notification);
+    let status = tokio::process::Command::new("sh")
+        .arg("-c")
+        .arg(&command)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .await?;
+    if !status.success() {
+        warn!("Notification command exited with {}", status);
+    }
+    Ok(())
+}
+
+/// Announces articles fetched since the previous announcement
+pub struct Notifier {
+    db: Arc<Database>,
+    config: Arc<AppConfig>,
+    since: DateTime<Utc>,
+}
+
+impl Notifier {
+    /// Start announcing articles fetched from now on
+    pub fn new(db: Arc<Database>, config: Arc<AppConfig>) -> Self {
+        Self {
+            db,
+            config,
+            since: Utc::now(),
+        }
+    }
+
+    /// Announce the unread articles fetched since the last call, according
+    /// to the configured mode. Returns the number of notifications sent.
+    pub async fn flush(&mut self) -> Result<u32> {
+        let now = Utc::now();
+        let articles = ArticleRepository::new(&self.db)
+            .list_unread_created_since(self.since)
+            .await?;
+        self.since = now;
+        if articles.is_empty() {
+            return Ok(0);
+        }
+
+        let settings = &self.config.notifications;
+        let notifications = match settings.mode {
+            NotificationMode::Digest => {
+                DigestStats::from_articles(&articles, settings.high_relevance_threshold)
+                    .notification()
+                    .into_iter()
+                    .collect()
+            }
+            NotificationMode::PerArticle => {
+                let feed_names: HashMap<_, _> = FeedRepository::new(&self.db)
+                    .list_all()
+                    .await?
+                    .into_iter()
+                    .map(|f| (f.id, f.local_name))
+                    .collect();
+                articles
+                    .iter()
+                    .map(|article| Notification {
+                        title: feed_names
+                            .get(&article.feed_id)
+                            .cloned()
+                            .unwrap_or_else(|| "kenseader".to_string()),
+                        body: article.title.clone(),
+                    })
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        for notification in &notifications {
+            send(&self.config, notification).await?;
+        }
+        Ok(notifications.len() as u32)
+    }
+}
+
+/// Whether an article should be pushed to a channel: its title or summary
+/// contains one of the keywords, or it scored at least the channel's
+/// relevance threshold
+pub fn push_matches(channel: &PushChannel, article: &Article, high_relevance_threshold: f64) -> bool {
+    let min_relevance = channel.min_relevance.unwrap_or(high_relevance_threshold);
+    if article.relevance_score.is_some_and(|score| score >= min_relevance) {
+        return true;
+    }
+    let text = format!("{} {}", article.title, article.summary.as_deref().unwrap_or("")).to_lowercase();
+    channel
+        .keywords
+        .iter()
+        .map(|keyword| keyword.trim().to_lowercase())
+        .any(|keyword| !keyword.is_empty() && text.contains(&keyword))
+}
+
+/// Endpoint and JSON body pushing an article to a channel
+pub fn push_request(channel: &PushChannel, feed_name: &str, article: &Article) -> Result<(String, Value)> {
+    match channel.service {
+        PushService::Ntfy => {
+            let topic = channel
+                .topic
+                .as_deref()
+                .ok_or_else(|| Error::Config("ntfy push channel has no topic".to_string()))?;
+            let url = channel.url.as_deref().unwrap_or(NTFY_URL).trim_end_matches('/');
+            let mut body = json!({
+                "topic": topic,
+                "title": feed_name,
+                "message": article.title,
+            });
+            if let Some(ref link) = article.url {
+                body["click"] = json!(link);
+            }
+            if let Some(priority) = channel.priority {
+                body["priority"] = json!(priority);
+            }
+            Ok((url.to_string(), body))
+        }
+        PushService::Gotify => {
+            let url = channel
+                .url
+                .as_deref()
+                .ok_or_else(|| Error::Config("Gotify push channel has no url".to_string()))?;
+            let mut body = json!({
+                "title": feed_name,
+                "message": article.title,
+            });
+            if let Some(ref link) = article.url {
+                body["extras"] = json!({ "client::notification": { "click": { "url": link } } });
+            }
+            if let Some(priority) = channel.priority {
+                body["priority"] = json!(priority);
+            }
+            Ok((format!("{}/message", url.trim_end_matches('/')), body))
+        }
+    }
+}
+
+/// Send an article to a push channel
+pub(crate) async fn push(client: &Client, channel: &PushChannel, feed_name: &str, article: &Article) -> Result<()> {
+    let (url, body) = push_request(channel, feed_name, article)?;
+    let mut request = client.post(&url).json(&body);
+    if let Some(ref token) = channel.token {
+        request = match channel.service {
+            PushService::Ntfy => request.header(AUTHORIZATION, format!("Bearer {}", token)),
+            PushService::Gotify => request.header("X-Gotify-Key", token),
+        };
+    }
+    let status = request.send().await?.status();
+    if !status.is_success() {
+        return Err(Error::Other(format!("{} returned HTTP {}", url, status)));
+    }
+    Ok(())
+}
+
+/// Pushes new articles to the push channels they match. Articles are
+/// checked again until they are a day old, so ones that only match once
+/// scored are pushed after filtering.
+pub struct Pusher {
+    db: Arc<Database>,
+    config: Arc<AppConfig>,
+    started: DateTime<Utc>,
+    /// Articles pushed per channel, with their fetch time
+    pushed: HashMap<(usize, Uuid), DateTime<Utc>>,
+}
+
+impl Pusher {
+    /// Start pushing articles fetched from now on (None without enabled channels)
+    pub fn new(db: Arc<Database>, config: Arc<AppConfig>) -> Option<Self> {
+        config.notifications.push.iter().any(|channel| channel.enabled).then(|| Self {
+            db,
+            config,
+            started: Utc::now(),
+            pushed: HashMap::new(),
+        })
+    }
+
+    /// Push the unread articles that newly match a channel. Returns the
+    /// number of pushes sent; failed ones are retried on the next call.
+    pub async fn flush(&mut self) -> Result<u32> {
+        let since = self.started.max(Utc::now() - PUSH_WINDOW);
+        self.pushed.retain(|_, created_at| *created_at > since);
+        let articles = ArticleRepository::new(&self.db)
+            .list_unread_created_since(since)
+            .await?;
+        let settings = &self.config.notifications;
+        let pending: Vec<_> = settings
+            .push
+            .iter()
+            .enumerate()
+            .filter(|(_, channel)| channel.enabled)
+            .flat_map(|(index, channel)| articles.iter().map(move |article| (index, channel, article)))
+            .filter(|(index, channel, article)| {
+                !self.pushed.contains_key(&(*index, article.id))
+                    && push_matches(channel, article, settings.high_relevance_threshold)
+            })
+            .collect();
+        if pending.is_empty() {
+            return Ok(0);
+        }
+
+        let feed_names: HashMap<_, _> = FeedRepository::new(&self.db)
+            .list_all()
+            .await?
+            .into_iter()
+            .map(|f| (f.id, f.local_name))
+            .collect();
+        let client = Client::builder()
+            .timeout(Duration::from_secs(self.config.sync.request_timeout_secs))
+            .build()?;
+        let mut sent = 0;
+        for (index, channel, article) in pending {
+            let feed_name = feed_names.get(&article.feed_id).map_or("kenseader", String::as_str);
+            match push(&client, channel, feed_name, article).await {
+                Ok(()) => {
+                    self.pushed.insert((index, article.id), article.created_at);
+                    sent += 1;
+                }
+                Err(e) => warn!("Failed to push '{}': {}", article.title, e),
+            }
+        }
+        debug!("Pushed {} articles", sent);
+        Ok(sent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn article(feed_id: Uuid, score: Option<f64>) -> Article {
+        Article {
+            feed_id,
+            relevance_score: score,
+            ..Article::for_test("Title")
+        }
+    }
+
+    #[test]
+    fn test_digest_message() {
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let articles = vec![
+            article(a, Some(0.9)),
+            article(a, Some(0.3)),
+            article(b, None),
+        ];
+        let stats = DigestStats::from_articles(&articles, 0.8);
+        assert_eq!(
+            stats,
+            DigestStats {
+                articles: 3,
+                feeds: 2,
+                high_relevance: 1
+            }
+        );
+        assert_eq!(
+            stats.notification().unwrap().body,
+            "3 new articles in 2 feeds, 1 high relevance"
+        );
+
+        let single = DigestStats::from_articles(&articles[2..], 0.8);
+        assert_eq!(single.notification().unwrap().body, "1 new article in 1 feed");
+        assert_eq!(DigestStats::default().notification(), None);
+    }
+
+    fn channel(service: PushService) -> PushChannel {
+        PushChannel {
+            service,
+            url: None,
+            topic: Some("news".to_string()),
+            token: None,
+            priority: Some(4),
+            keywords: vec!["Rust ".to_string(), " ".to_string()],
+            min_relevance: None,
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn test_push_matches_keywords_or_relevance() {
+        let ntfy = channel(PushService::Ntfy);
+        let mut matching = article(Uuid::new_v4(), None);
+        matching.title = "Announcing RUST 2.0".to_string();
+        assert!(push_matches(&ntfy, &matching, 0.8));
+
+        let mut other = article(Uuid::new_v4(), Some(0.5));
+        assert!(!push_matches(&ntfy, &other, 0.8));
+        other.summary = Some("Written in Rust again".to_string());
+        assert!(push_matches(&ntfy, &other, 0.8));
+
+        assert!(push_matches(&ntfy, &article(Uuid::new_v4(), Some(0.9)), 0.8));
+        let keywords_only = PushChannel {
+            min_relevance: Some(1.1),
+            ..ntfy
+        };
+        assert!(!push_matches(&keywords_only, &article(Uuid::new_v4(), Some(0.9)), 0.8));
+    }
+
+    #[test]
+    fn test_push_request() {
+        let mut article = article(Uuid::new_v4(), None);
+        article.title = "Post".to_string();
+        article.url = Some("https://example.com/post".to_string());
+
+        let (url, body) = push_request(&channel(PushService::Ntfy), "Blog", &article).unwrap();
+        assert_eq!(url, "https://ntfy.sh");
+        assert_eq!(
+            body,
+            json!({
+                "topic": "news",
+                "title": "Blog",
+                "message": "Post",
+                "click": "https://example.com/post",
+                "priority": 4,
+            })
+        );
+
+        let mut gotify = channel(PushService::Gotify);
+        assert!(push_request(&gotify, "Blog", &article).is_err());
+        gotify.url = Some("https://push.example.com/".to_string());
+        let (url, body) = push_request(&gotify, "Blog", &article).unwrap();
+        assert_eq!(url, "https://push.example.com/message");
+        assert_eq!(body["extras"]["client::notification"]["click"]["url"], "https://example.com/post");
+    }
+
+    #[test]
+    fn test_expand_command_quotes_values() {
+        let notification = Notification {
+            title: "Rust Blog".to_string(),
+            body: "It's out".to_string(),
+        };
+        assert_eq!(
+            expand_command(DEFAULT_COMMAND, &notification),
+            r"notify-send 'Rust Blog' 'It'\''s out'"
+        );
+
+        // A `{body}` in the title from a feed can't unquote the body
+        let notification = Notification {
+            title: "Alert: {body}".to_string(),
+            body: "feed: $(touch pwned)".to_string(),
+        };
+        assert_eq!(
+            expand_command(DEFAULT_COMMAND, &notification),
+            "notify-send 'Alert: {body}' 'feed: $(touch pwned)'"
+        );
+    }
+}
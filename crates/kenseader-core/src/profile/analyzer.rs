@@ -1,35 +1,164 @@
-use chrono::{Duration, Utc};
+use std::collections::HashMap;
 
-use super::models::{PreferenceType, TimeWindow};
+use chrono::{DateTime, Utc};
+
+use super::models::{BehaviorEventType, PreferenceInsight, PreferenceType, ProfileInsights, TimeOfDay, TimeWindow};
 use crate::storage::Database;
 use crate::Result;
 
+/// Half-life of behavior event weights when none is configured, in days
+pub const DEFAULT_HALF_LIFE_DAYS: f64 = 14.0;
+
+/// Windows of the tag and feed affinities
+const ALL_WINDOWS: [TimeWindow; 3] = [TimeWindow::Recent5Min, TimeWindow::Last1Day, TimeWindow::Last30Days];
+
+/// Windows of the time of day and style preferences
+const DAY_WINDOWS: [TimeWindow; 2] = [TimeWindow::Last1Day, TimeWindow::Last30Days];
+
+/// Most tags kept per window
+const MAX_TAGS: usize = 50;
+
+/// Behavior events counted for a preference, summed per preference key,
+/// event type and day: the key, the event type, the number of events and
+/// when the latest of them happened
+type DailyEvents = (String, String, i64, DateTime<Utc>);
+
+/// Events of each tag in a window of up to a day
+const TAG_EVENTS: &str = r#"
+    SELECT at.tag, be.event_type, COUNT(*), MAX(be.created_at)
+    FROM behavior_events be
+    JOIN article_tags at ON be.article_id = at.article_id
+    WHERE be.created_at >= ?
+    GROUP BY at.tag, be.event_type, date(be.created_at)
+"#;
+
+/// Events of each feed in a window of up to a day
+const FEED_EVENTS: &str = r#"
+    SELECT feed_id, event_type, COUNT(*), MAX(created_at)
+    FROM behavior_events
+    WHERE feed_id IS NOT NULL AND created_at >= ?
+    GROUP BY feed_id, event_type, date(created_at)
+"#;
+
+/// Reading events at each time of day in a window of up to a day
+const TIME_EVENTS: &str = r#"
+    SELECT context_time_of_day, event_type, COUNT(*), MAX(created_at)
+    FROM behavior_events
+    WHERE event_type IN ('click', 'read_complete', 'save') AND created_at >= ?
+        AND context_time_of_day IS NOT NULL
+    GROUP BY context_time_of_day, event_type, date(created_at)
+"#;
+
+/// Events of each article style in a window of up to a day
+const STYLE_EVENTS: &str = r#"
+    SELECT s.style_type, be.event_type, COUNT(*), MAX(be.created_at)
+    FROM behavior_events be
+    JOIN article_styles s ON be.article_id = s.article_id
+    WHERE be.created_at >= ? AND s.style_type IS NOT NULL
+    GROUP BY s.style_type, be.event_type, date(be.created_at)
+"#;
+
+/// Windows up to this long read the events themselves: a day's sum may
+/// hold events from before their start
+const RAW_EVENTS_WINDOW: chrono::Duration = chrono::Duration::days(1);
+
+/// Events not summed yet up to an event id, per preference key, event type
+/// and day: the key, the event type, the day, the number of events and the
+/// latest of them. `?1` is the last event id to sum, `?2` the oldest time
+/// still in a window.
+const NEW_EVENTS: [(PreferenceType, &str); 4] = [
+    (
+        PreferenceType::TagAffinity,
+        r#"
+        SELECT at.tag, be.event_type, date(be.created_at), COUNT(*), MAX(be.created_at)
+        FROM behavior_events be
+        JOIN article_tags at ON be.article_id = at.article_id
+        WHERE be.id > (SELECT last_event_id FROM preference_aggregation) AND be.id <= ?1
+            AND be.created_at >= ?2
+        GROUP BY at.tag, be.event_type, date(be.created_at)
+        "#,
+    ),
+    (
+        PreferenceType::FeedAffinity,
+        r#"
+        SELECT feed_id, event_type, date(created_at), COUNT(*), MAX(created_at)
+        FROM behavior_events
+        WHERE id > (SELECT last_event_id FROM preference_aggregation) AND id <= ?1
+            AND created_at >= ?2 AND feed_id IS NOT NULL
+        GROUP BY feed_id, event_type, date(created_at)
+        "#,
+    ),
+    (
+        PreferenceType::TimePreference,
+        r#"
+        SELECT context_time_of_day, event_type, date(created_at), COUNT(*), MAX(created_at)
+        FROM behavior_events
+        WHERE id > (SELECT last_event_id FROM preference_aggregation) AND id <= ?1
+            AND created_at >= ?2 AND event_type IN ('click', 'read_complete', 'save')
+            AND context_time_of_day IS NOT NULL
+        GROUP BY context_time_of_day, event_type, date(created_at)
+        "#,
+    ),
+    (
+        PreferenceType::StylePreference,
+        r#"
+        SELECT s.style_type, be.event_type, date(be.created_at), COUNT(*), MAX(be.created_at)
+        FROM behavior_events be
+        JOIN article_styles s ON be.article_id = s.article_id
+        WHERE be.id > (SELECT last_event_id FROM preference_aggregation) AND be.id <= ?1
+            AND be.created_at >= ?2 AND s.style_type IS NOT NULL
+        GROUP BY s.style_type, be.event_type, date(be.created_at)
+        "#,
+    ),
+];
+
+/// Daily event sums of a preference kind since a time
+const DAILY_EVENTS: &str = r#"
+    SELECT preference_key, event_type, count, latest_at
+    FROM preference_daily_events
+    WHERE preference_type = ? AND latest_at >= ?
+"#;
+
 /// Analyzes user behavior to compute preferences
 pub struct ProfileAnalyzer<'a> {
     db: &'a Database,
+    half_life_days: f64,
 }
 
 impl<'a> ProfileAnalyzer<'a> {
     pub fn new(db: &'a Database) -> Self {
-        Self { db }
+        Self {
+            db,
+            half_life_days: DEFAULT_HALF_LIFE_DAYS,
+        }
+    }
+
+    /// Set the half-life of event weights in days: an event counts half as
+    /// much that long after it happened (0 = events never fade)
+    pub fn with_half_life(mut self, days: f64) -> Self {
+        self.half_life_days = days.max(0.0);
+        self
     }
 
     /// Compute and store user preferences for all time windows
     pub async fn compute_preferences(&self) -> Result<()> {
-        self.compute_tag_affinities().await?;
-        self.compute_feed_affinities().await?;
-        self.compute_time_preferences().await?;
-        self.compute_style_preferences().await?;
+        let now = Utc::now();
+        self.sum_new_events(now).await?;
+        self.compute_tag_affinities(now).await?;
+        self.compute_feed_affinities(now).await?;
+        self.compute_time_preferences(now).await?;
+        self.compute_style_preferences(now).await?;
         Ok(())
     }
 
-    /// Get top tags by affinity for a time window
+    /// Get top tags by affinity for a time window, leaving out tags asked
+    /// for less of
     pub async fn get_top_tags(&self, window: TimeWindow, limit: u32) -> Result<Vec<String>> {
         let rows: Vec<(String,)> = sqlx::query_as(
             r#"
             SELECT preference_key
             FROM user_preferences
-            WHERE preference_type = ? AND time_window = ?
+            WHERE preference_type = ? AND time_window = ? AND weight > 0
             ORDER BY weight DESC
             LIMIT ?
             "#,
@@ -43,98 +172,172 @@ impl<'a> ProfileAnalyzer<'a> {
         Ok(rows.into_iter().map(|(k,)| k).collect())
     }
 
-    /// Compute tag affinities from behavior events
-    async fn compute_tag_affinities(&self) -> Result<()> {
-        for window in [TimeWindow::Recent5Min, TimeWindow::Last1Day, TimeWindow::Last30Days] {
-            let cutoff = match window {
-                TimeWindow::Recent5Min => Utc::now() - Duration::minutes(5),
-                TimeWindow::Last1Day => Utc::now() - Duration::days(1),
-                TimeWindow::Last30Days => Utc::now() - Duration::days(30),
-            };
+    /// The stored preferences of a time window, up to `limit` of each kind
+    pub async fn insights(&self, window: TimeWindow, limit: usize) -> Result<ProfileInsights> {
+        let tags = self.preferences(PreferenceType::TagAffinity, window).await?;
+        let mut avoided_tags: Vec<PreferenceInsight> = tags.iter().rev().filter(|p| p.weight < 0.0).cloned().collect();
+        avoided_tags.truncate(limit);
+        let liked = |prefs: Vec<PreferenceInsight>| -> Vec<PreferenceInsight> {
+            prefs.into_iter().filter(|p| p.weight > 0.0).take(limit).collect()
+        };
 
-            // Get weighted tag scores from events
-            let rows: Vec<(String, f64)> = sqlx::query_as(
-                r#"
-                SELECT at.tag, CAST(SUM(
-                    CASE be.event_type
-                        WHEN 'exposure' THEN 0.1
-                        WHEN 'click' THEN 1.0
-                        WHEN 'read_start' THEN 1.5
-                        WHEN 'read_complete' THEN 3.0
-                        WHEN 'save' THEN 5.0
-                        WHEN 'view_repeat' THEN 4.0
-                        ELSE 0.5
-                    END
-                ) AS REAL) as weight
-                FROM behavior_events be
-                JOIN article_tags at ON be.article_id = at.article_id
-                WHERE be.created_at >= ?
-                GROUP BY at.tag
-                ORDER BY weight DESC
-                LIMIT 50
-                "#,
-            )
-            .bind(cutoff)
-            .fetch_all(self.db.pool())
-            .await?;
+        let order = [TimeOfDay::Morning, TimeOfDay::Afternoon, TimeOfDay::Evening, TimeOfDay::Night];
+        let mut times = self.preferences(PreferenceType::TimePreference, window).await?;
+        times.sort_by_key(|p| order.iter().position(|t| t.as_str() == p.key).unwrap_or(order.len()));
 
-            // Update preferences
-            let now = Utc::now();
-            for (tag, weight) in rows {
-                sqlx::query(
-                    r#"
-                    INSERT OR REPLACE INTO user_preferences
-                    (preference_type, preference_key, weight, time_window, computed_at)
-                    VALUES (?, ?, ?, ?, ?)
-                    "#,
-                )
-                .bind(PreferenceType::TagAffinity.as_str())
-                .bind(&tag)
-                .bind(weight)
+        let (computed_at,): (Option<DateTime<Utc>>,) =
+            sqlx::query_as("SELECT MAX(computed_at) FROM user_preferences WHERE time_window = ?")
                 .bind(window.as_str())
-                .bind(now)
-                .execute(self.db.pool())
+                .fetch_one(self.db.pool())
                 .await?;
-            }
+
+        Ok(ProfileInsights {
+            tags: liked(tags),
+            avoided_tags,
+            feeds: liked(self.preferences(PreferenceType::FeedAffinity, window).await?),
+            times,
+            styles: liked(self.preferences(PreferenceType::StylePreference, window).await?),
+            computed_at,
+        })
+    }
+
+    /// Stored preferences of a kind and window, heaviest first; feed
+    /// affinities are labeled with the feed's name
+    async fn preferences(&self, kind: PreferenceType, window: TimeWindow) -> Result<Vec<PreferenceInsight>> {
+        let rows: Vec<(String, String, f64)> = sqlx::query_as(
+            r#"
+            SELECT p.preference_key, COALESCE(f.local_name, p.preference_key), p.weight
+            FROM user_preferences p
+            LEFT JOIN feeds f ON p.preference_type = 'feed_affinity' AND f.id = p.preference_key
+            WHERE p.preference_type = ? AND p.time_window = ?
+            ORDER BY p.weight DESC, p.preference_key
+            "#,
+        )
+        .bind(kind.as_str())
+        .bind(window.as_str())
+        .fetch_all(self.db.pool())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(key, label, weight)| PreferenceInsight { key, label, weight })
+            .collect())
+    }
+
+    /// Add the behavior events recorded since the last computation to the
+    /// daily sums, and drop the days that left every window. Events count
+    /// for the tags and style their article has when they are summed.
+    async fn sum_new_events(&self, now: DateTime<Utc>) -> Result<()> {
+        let (last_event_id,): (i64,) = sqlx::query_as("SELECT COALESCE(MAX(id), 0) FROM behavior_events")
+            .fetch_one(self.db.pool())
+            .await?;
+        let oldest = now - TimeWindow::Last30Days.duration();
+
+        let mut tx = self.db.pool().begin().await?;
+        for (kind, events) in NEW_EVENTS {
+            sqlx::query(&format!(
+                r#"
+                INSERT INTO preference_daily_events
+                (preference_type, preference_key, event_type, day, count, latest_at)
+                SELECT ?3, * FROM ({events}) WHERE true
+                ON CONFLICT (preference_type, preference_key, event_type, day) DO UPDATE SET
+                    count = count + excluded.count,
+                    latest_at = MAX(latest_at, excluded.latest_at)
+                "#
+            ))
+            .bind(last_event_id)
+            .bind(oldest)
+            .bind(kind.as_str())
+            .execute(&mut *tx)
+            .await?;
         }
+        sqlx::query("UPDATE preference_aggregation SET last_event_id = MAX(last_event_id, ?)")
+            .bind(last_event_id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query(
+            r#"
+            DELETE FROM preference_daily_events
+            WHERE latest_at < ?
+                OR (preference_type = ? AND preference_key NOT IN (SELECT id FROM feeds))
+            "#,
+        )
+        .bind(oldest)
+        .bind(PreferenceType::FeedAffinity.as_str())
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
 
         Ok(())
     }
 
+    /// Compute tag affinities from behavior events
+    async fn compute_tag_affinities(&self, now: DateTime<Utc>) -> Result<()> {
+        let limit = Some(MAX_TAGS);
+        self.store_preferences(PreferenceType::TagAffinity, &ALL_WINDOWS, TAG_EVENTS, event_weight, limit, now)
+            .await
+    }
+
     /// Compute feed affinities from behavior events
-    async fn compute_feed_affinities(&self) -> Result<()> {
-        for window in [TimeWindow::Recent5Min, TimeWindow::Last1Day, TimeWindow::Last30Days] {
-            let cutoff = match window {
-                TimeWindow::Recent5Min => Utc::now() - Duration::minutes(5),
-                TimeWindow::Last1Day => Utc::now() - Duration::days(1),
-                TimeWindow::Last30Days => Utc::now() - Duration::days(30),
-            };
+    async fn compute_feed_affinities(&self, now: DateTime<Utc>) -> Result<()> {
+        self.store_preferences(PreferenceType::FeedAffinity, &ALL_WINDOWS, FEED_EVENTS, event_weight, None, now)
+            .await
+    }
 
-            let rows: Vec<(String, f64)> = sqlx::query_as(
-                r#"
-                SELECT feed_id, CAST(SUM(
-                    CASE event_type
-                        WHEN 'exposure' THEN 0.1
-                        WHEN 'click' THEN 1.0
-                        WHEN 'read_start' THEN 1.5
-                        WHEN 'read_complete' THEN 3.0
-                        WHEN 'save' THEN 5.0
-                        WHEN 'view_repeat' THEN 4.0
-                        ELSE 0.5
-                    END
-                ) AS REAL) as weight
-                FROM behavior_events
-                WHERE feed_id IS NOT NULL AND created_at >= ?
-                GROUP BY feed_id
-                ORDER BY weight DESC
-                "#,
-            )
-            .bind(cutoff)
-            .fetch_all(self.db.pool())
-            .await?;
+    /// Compute time of day preferences: how much reading happens at each
+    /// time of day
+    async fn compute_time_preferences(&self, now: DateTime<Utc>) -> Result<()> {
+        self.store_preferences(PreferenceType::TimePreference, &DAY_WINDOWS, TIME_EVENTS, |_| 1.0, None, now)
+            .await
+    }
+
+    /// Compute style preferences from behavior events joined with article_styles
+    async fn compute_style_preferences(&self, now: DateTime<Utc>) -> Result<()> {
+        self.store_preferences(PreferenceType::StylePreference, &DAY_WINDOWS, STYLE_EVENTS, event_weight, None, now)
+            .await
+    }
+
+    /// Store the preferences of a kind in each window, from the daily event
+    /// sums since the window's start, or from the events `recent` selects
+    /// for windows of up to a day. The earlier ones are replaced in one
+    /// transaction, so ones without events in the window don't linger and
+    /// scoring never sees the kind empty.
+    async fn store_preferences(
+        &self,
+        kind: PreferenceType,
+        windows: &[TimeWindow],
+        recent: &str,
+        weigh: fn(&str) -> f64,
+        limit: Option<usize>,
+        now: DateTime<Utc>,
+    ) -> Result<()> {
+        let mut computed = Vec::with_capacity(windows.len());
+        for &window in windows {
+            let since = now - window.duration();
+            let events: Vec<DailyEvents> = if window.duration() <= RAW_EVENTS_WINDOW {
+                sqlx::query_as(recent).bind(since).fetch_all(self.db.pool()).await?
+            } else {
+                sqlx::query_as(DAILY_EVENTS)
+                    .bind(kind.as_str())
+                    .bind(since)
+                    .fetch_all(self.db.pool())
+                    .await?
+            };
+            let mut weights = window_weights(&events, now, self.half_life_days, weigh);
+            if let Some(limit) = limit {
+                weights.truncate(limit);
+            }
+            computed.push((window, weights));
+        }
 
-            let now = Utc::now();
-            for (feed_id, weight) in rows {
+        let mut tx = self.db.pool().begin().await?;
+        for (window, weights) in computed {
+            sqlx::query("DELETE FROM user_preferences WHERE preference_type = ? AND time_window = ?")
+                .bind(kind.as_str())
+                .bind(window.as_str())
+                .execute(&mut *tx)
+                .await?;
+            for (key, weight) in weights {
                 sqlx::query(
                     r#"
                     INSERT OR REPLACE INTO user_preferences
@@ -142,115 +345,203 @@ impl<'a> ProfileAnalyzer<'a> {
                     VALUES (?, ?, ?, ?, ?)
                     "#,
                 )
-                .bind(PreferenceType::FeedAffinity.as_str())
-                .bind(&feed_id)
+                .bind(kind.as_str())
+                .bind(&key)
                 .bind(weight)
                 .bind(window.as_str())
                 .bind(now)
-                .execute(self.db.pool())
+                .execute(&mut *tx)
                 .await?;
             }
         }
+        tx.commit().await?;
 
         Ok(())
     }
+}
 
-    /// Compute time of day preferences
-    async fn compute_time_preferences(&self) -> Result<()> {
-        for window in [TimeWindow::Last1Day, TimeWindow::Last30Days] {
-            let cutoff = match window {
-                TimeWindow::Recent5Min => Utc::now() - Duration::minutes(5),
-                TimeWindow::Last1Day => Utc::now() - Duration::days(1),
-                TimeWindow::Last30Days => Utc::now() - Duration::days(30),
-            };
+/// Weight of a behavior event type; events of other types count a little
+fn event_weight(event_type: &str) -> f64 {
+    BehaviorEventType::parse(event_type).map_or(0.5, |t| t.weight())
+}
 
-            let rows: Vec<(String, f64)> = sqlx::query_as(
-                r#"
-                SELECT context_time_of_day, CAST(COUNT(*) AS REAL) as count
-                FROM behavior_events
-                WHERE event_type IN ('click', 'read_complete', 'save') AND created_at >= ?
-                GROUP BY context_time_of_day
-                "#,
-            )
-            .bind(cutoff)
-            .fetch_all(self.db.pool())
-            .await?;
+/// Share of its weight an event keeps `age_days` after it happened
+fn decay(age_days: f64, half_life_days: f64) -> f64 {
+    if half_life_days > 0.0 {
+        0.5f64.powf(age_days.max(0.0) / half_life_days)
+    } else {
+        1.0
+    }
+}
 
-            let now = Utc::now();
-            for (time_of_day, count) in rows {
-                sqlx::query(
-                    r#"
-                    INSERT OR REPLACE INTO user_preferences
-                    (preference_type, preference_key, weight, time_window, computed_at)
-                    VALUES (?, ?, ?, ?, ?)
-                    "#,
-                )
-                .bind(PreferenceType::TimePreference.as_str())
-                .bind(&time_of_day)
-                .bind(count)
-                .bind(window.as_str())
-                .bind(now)
-                .execute(self.db.pool())
-                .await?;
-            }
+/// Decayed weight of each key over daily event sums, heaviest first. A
+/// day's events all decay by the age of its latest one.
+fn window_weights(
+    events: &[DailyEvents],
+    now: DateTime<Utc>,
+    half_life_days: f64,
+    weigh: fn(&str) -> f64,
+) -> Vec<(String, f64)> {
+    let mut weights: HashMap<&str, f64> = HashMap::new();
+    for (key, event_type, count, latest) in events {
+        let age_days = (now - *latest).num_seconds() as f64 / 86_400.0;
+        *weights.entry(key).or_default() += *count as f64 * weigh(event_type) * decay(age_days, half_life_days);
+    }
+    let mut weights: Vec<(String, f64)> = weights.into_iter().map(|(k, w)| (k.to_string(), w)).collect();
+    weights.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    weights
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feed::NewArticle;
+    use crate::profile::BehaviorTracker;
+    use crate::storage::{test_db_with_feed, ArticleRepository};
+
+    #[tokio::test]
+    async fn test_less_like_this_removes_interest() {
+        let (db, feed) = test_db_with_feed().await;
+        let articles = ArticleRepository::new(&db);
+        let mut ids = Vec::new();
+        for (i, tag) in ["rust", "crypto"].iter().enumerate() {
+            let id = articles
+                .create(&NewArticle {
+                    url: Some(format!("https://example.com/{}", i)),
+                    ..NewArticle::for_test(feed.id, &format!("guid-{}", i))
+                })
+                .await
+                .unwrap()
+                .unwrap()
+                .id;
+            articles.add_tags(id, &[tag.to_string()], "ai").await.unwrap();
+            ids.push(id);
         }
 
-        Ok(())
-    }
+        let tracker = BehaviorTracker::new(&db);
+        let analyzer = ProfileAnalyzer::new(&db);
+        for &id in &ids {
+            tracker.record_click(id, feed.id).await.unwrap();
+        }
+        analyzer.compute_preferences().await.unwrap();
+        let mut tags = analyzer.get_top_tags(TimeWindow::Last30Days, 10).await.unwrap();
+        tags.sort();
+        assert_eq!(tags, ["crypto", "rust"]);
 
-    /// Compute style preferences from behavior events joined with article_styles
-    async fn compute_style_preferences(&self) -> Result<()> {
-        for window in [TimeWindow::Last1Day, TimeWindow::Last30Days] {
-            let cutoff = match window {
-                TimeWindow::Recent5Min => Utc::now() - Duration::minutes(5),
-                TimeWindow::Last1Day => Utc::now() - Duration::days(1),
-                TimeWindow::Last30Days => Utc::now() - Duration::days(30),
-            };
+        tracker.record_feedback(ids[0], feed.id, true).await.unwrap();
+        tracker.record_feedback(ids[1], feed.id, false).await.unwrap();
+        analyzer.compute_preferences().await.unwrap();
+        assert_eq!(analyzer.get_top_tags(TimeWindow::Last30Days, 10).await.unwrap(), ["rust"]);
 
-            // Get weighted style type scores from events
-            let rows: Vec<(String, f64)> = sqlx::query_as(
-                r#"
-                SELECT s.style_type, CAST(SUM(
-                    CASE be.event_type
-                        WHEN 'exposure' THEN 0.1
-                        WHEN 'click' THEN 1.0
-                        WHEN 'read_start' THEN 1.5
-                        WHEN 'read_complete' THEN 3.0
-                        WHEN 'save' THEN 5.0
-                        WHEN 'view_repeat' THEN 4.0
-                        ELSE 0.5
-                    END
-                ) AS REAL) as weight
-                FROM behavior_events be
-                JOIN article_styles s ON be.article_id = s.article_id
-                WHERE be.created_at >= ? AND s.style_type IS NOT NULL
-                GROUP BY s.style_type
-                ORDER BY weight DESC
-                "#,
-            )
-            .bind(cutoff)
-            .fetch_all(self.db.pool())
-            .await?;
+        let insights = analyzer.insights(TimeWindow::Last30Days, 10).await.unwrap();
+        let keys = |prefs: &[PreferenceInsight]| prefs.iter().map(|p| p.label.clone()).collect::<Vec<_>>();
+        assert_eq!(keys(&insights.tags), ["rust"]);
+        assert_eq!(keys(&insights.avoided_tags), ["crypto"]);
+        assert_eq!(keys(&insights.feeds), ["example"]);
+        assert_eq!(insights.feeds[0].key, feed.id.to_string());
+        assert_eq!(insights.times.len(), 1);
+        assert!(insights.computed_at.is_some());
+        assert!(analyzer.insights(TimeWindow::Recent5Min, 10).await.unwrap().times.is_empty());
 
-            let now = Utc::now();
-            for (style_type, weight) in rows {
-                sqlx::query(
-                    r#"
-                    INSERT OR REPLACE INTO user_preferences
-                    (preference_type, preference_key, weight, time_window, computed_at)
-                    VALUES (?, ?, ?, ?, ?)
-                    "#,
-                )
-                .bind(PreferenceType::StylePreference.as_str())
-                .bind(&style_type)
-                .bind(weight)
-                .bind(window.as_str())
-                .bind(now)
-                .execute(self.db.pool())
-                .await?;
-            }
+        // Events older than a window drop out of it, and so do their sums
+        let long_ago = Utc::now() - chrono::Duration::days(40);
+        for update in [
+            "UPDATE behavior_events SET created_at = ?",
+            "UPDATE preference_daily_events SET latest_at = ?",
+        ] {
+            sqlx::query(update).bind(long_ago).execute(db.pool()).await.unwrap();
         }
+        analyzer.compute_preferences().await.unwrap();
+        assert!(analyzer.get_top_tags(TimeWindow::Last30Days, 10).await.unwrap().is_empty());
+        let (sums,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM preference_daily_events")
+            .fetch_one(db.pool())
+            .await
+            .unwrap();
+        assert_eq!(sums, 0);
+    }
 
-        Ok(())
+    #[tokio::test]
+    async fn test_events_are_summed_once() {
+        let (db, feed) = test_db_with_feed().await;
+        let articles = ArticleRepository::new(&db);
+        let id = articles
+            .create(&NewArticle::for_test(feed.id, "guid"))
+            .await
+            .unwrap()
+            .unwrap()
+            .id;
+        articles.add_tags(id, &["rust".to_string()], "ai").await.unwrap();
+
+        let tracker = BehaviorTracker::new(&db);
+        let analyzer = ProfileAnalyzer::new(&db).with_half_life(0.0);
+        let weight = |window| {
+            let analyzer = &analyzer;
+            async move { analyzer.insights(window, 10).await.unwrap().tags[0].weight }
+        };
+        let click = BehaviorEventType::Click.weight();
+
+        tracker.record_click(id, feed.id).await.unwrap();
+        tracker.record_click(id, feed.id).await.unwrap();
+        analyzer.compute_preferences().await.unwrap();
+        analyzer.compute_preferences().await.unwrap();
+        assert_eq!(weight(TimeWindow::Last30Days).await, 2.0 * click);
+
+        tracker.record_click(id, feed.id).await.unwrap();
+        analyzer.compute_preferences().await.unwrap();
+        assert_eq!(weight(TimeWindow::Last30Days).await, 3.0 * click);
+        assert_eq!(weight(TimeWindow::Last1Day).await, 3.0 * click);
+        assert_eq!(weight(TimeWindow::Recent5Min).await, 3.0 * click);
+    }
+
+    #[tokio::test]
+    async fn test_last_day_leaves_out_older_events() {
+        let (db, feed) = test_db_with_feed().await;
+        let articles = ArticleRepository::new(&db);
+        let id = articles
+            .create(&NewArticle::for_test(feed.id, "guid"))
+            .await
+            .unwrap()
+            .unwrap()
+            .id;
+        articles.add_tags(id, &["rust".to_string()], "ai").await.unwrap();
+
+        let tracker = BehaviorTracker::new(&db);
+        tracker.record_click(id, feed.id).await.unwrap();
+        tracker.record_click(id, feed.id).await.unwrap();
+        sqlx::query("UPDATE behavior_events SET created_at = ? WHERE id = (SELECT MIN(id) FROM behavior_events)")
+            .bind(Utc::now() - chrono::Duration::hours(30))
+            .execute(db.pool())
+            .await
+            .unwrap();
+
+        let analyzer = ProfileAnalyzer::new(&db).with_half_life(0.0);
+        analyzer.compute_preferences().await.unwrap();
+        let click = BehaviorEventType::Click.weight();
+        let day = analyzer.insights(TimeWindow::Last1Day, 10).await.unwrap();
+        assert_eq!(day.tags[0].weight, click);
+        assert_eq!(day.feeds[0].weight, click);
+        let month = analyzer.insights(TimeWindow::Last30Days, 10).await.unwrap();
+        assert_eq!(month.tags[0].weight, 2.0 * click);
+    }
+
+    #[test]
+    fn test_window_weights_decay_with_age() {
+        let now = Utc::now();
+        let events = |key: &str, event_type: &str, count: i64, days: i64| {
+            (key.to_string(), event_type.to_string(), count, now - chrono::Duration::days(days))
+        };
+        let events = [events("old", "save", 2, 28), events("new", "click", 1, 0), events("new", "unknown", 1, 0)];
+
+        // Two saves four half-lives ago weigh less than a fresh click
+        let weights = window_weights(&events, now, 7.0, event_weight);
+        assert_eq!(weights.len(), 2);
+        assert_eq!(weights[0], ("new".to_string(), 1.5));
+        assert_eq!(weights[1].0, "old");
+        assert!((weights[1].1 - 0.625).abs() < 1e-9);
+
+        // Without decay the saves still count in full
+        let weights = window_weights(&events, now, 0.0, event_weight);
+        assert_eq!(weights[0], ("old".to_string(), 10.0));
+        assert_eq!(window_weights(&events[1..], now, 7.0, |_| 1.0), [("new".to_string(), 2.0)]);
     }
 }
@@ -4,6 +4,6 @@ mod filter;
 mod models;
 
 pub use tracker::BehaviorTracker;
-pub use analyzer::ProfileAnalyzer;
+pub use analyzer::{ProfileAnalyzer, DEFAULT_HALF_LIFE_DAYS};
 pub use filter::ArticleFilter;
 pub use models::*;
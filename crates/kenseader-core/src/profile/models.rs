@@ -22,6 +22,10 @@ pub enum BehaviorEventType {
     Share,
     /// User viewed the article again
     ViewRepeat,
+    /// User asked for more articles like this one
+    MoreLikeThis,
+    /// User asked for fewer articles like this one
+    LessLikeThis,
 }
 
 impl BehaviorEventType {
@@ -35,9 +39,28 @@ impl BehaviorEventType {
             Self::Save => "save",
             Self::Share => "share",
             Self::ViewRepeat => "view_repeat",
+            Self::MoreLikeThis => "more_like_this",
+            Self::LessLikeThis => "less_like_this",
         }
     }
 
+    /// Event type stored as `s`
+    pub fn parse(s: &str) -> Option<Self> {
+        Some(match s {
+            "exposure" => Self::Exposure,
+            "click" => Self::Click,
+            "read_start" => Self::ReadStart,
+            "read_complete" => Self::ReadComplete,
+            "scroll" => Self::Scroll,
+            "save" => Self::Save,
+            "share" => Self::Share,
+            "view_repeat" => Self::ViewRepeat,
+            "more_like_this" => Self::MoreLikeThis,
+            "less_like_this" => Self::LessLikeThis,
+            _ => return None,
+        })
+    }
+
     /// Get the weight of this event type for preference calculation
     pub fn weight(&self) -> f64 {
         match self {
@@ -49,6 +72,8 @@ impl BehaviorEventType {
             Self::Save => 5.0,
             Self::Share => 5.0,
             Self::ViewRepeat => 4.0,
+            Self::MoreLikeThis => 8.0,
+            Self::LessLikeThis => -8.0,
         }
     }
 }
@@ -89,17 +114,38 @@ impl TimeOfDay {
 
 /// Time windows for preference aggregation
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
 pub enum TimeWindow {
     /// Last 5 minutes
+    #[serde(rename = "5min")]
     Recent5Min,
     /// Last 24 hours
+    #[serde(rename = "1day")]
     Last1Day,
     /// Last 30 days
+    #[serde(rename = "30days")]
     Last30Days,
 }
 
 impl TimeWindow {
+    /// Window named `s` ("5min", "1day" or "30days")
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "5min" => Some(Self::Recent5Min),
+            "1day" => Some(Self::Last1Day),
+            "30days" => Some(Self::Last30Days),
+            _ => None,
+        }
+    }
+
+    /// Description for display ("last 30 days")
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Recent5Min => "last 5 minutes",
+            Self::Last1Day => "last day",
+            Self::Last30Days => "last 30 days",
+        }
+    }
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::Recent5Min => "5min",
@@ -107,6 +153,15 @@ impl TimeWindow {
             Self::Last30Days => "30days",
         }
     }
+
+    /// How far back the window reaches
+    pub fn duration(&self) -> chrono::Duration {
+        match self {
+            Self::Recent5Min => chrono::Duration::minutes(5),
+            Self::Last1Day => chrono::Duration::days(1),
+            Self::Last30Days => chrono::Duration::days(30),
+        }
+    }
 }
 
 /// A user behavior event
@@ -150,6 +205,125 @@ impl PreferenceType {
     }
 }
 
+/// A learned preference, for showing the profile
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PreferenceInsight {
+    /// Tag, feed ID, time of day or style
+    pub key: String,
+    /// Name to show: the feed's name for a feed affinity, else the key
+    pub label: String,
+    pub weight: f64,
+}
+
+/// The learned profile in one time window, for inspection
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProfileInsights {
+    /// Tags read most, heaviest first
+    pub tags: Vec<PreferenceInsight>,
+    /// Tags asked for less of, most disliked first
+    pub avoided_tags: Vec<PreferenceInsight>,
+    /// Feeds read most, heaviest first
+    pub feeds: Vec<PreferenceInsight>,
+    /// Reading by time of day, from morning to night
+    pub times: Vec<PreferenceInsight>,
+    /// Article styles read most, heaviest first
+    pub styles: Vec<PreferenceInsight>,
+    /// When the preferences were computed; None when they never were
+    pub computed_at: Option<DateTime<Utc>>,
+}
+
+impl ProfileInsights {
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+            && self.avoided_tags.is_empty()
+            && self.feeds.is_empty()
+            && self.times.is_empty()
+            && self.styles.is_empty()
+    }
+}
+
+/// Settings to score with in a scoring comparison; unset fields keep the
+/// configured ones
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScoringSettings {
+    /// Score below which articles are filtered (`ai.relevance_threshold`)
+    #[serde(default)]
+    pub relevance_threshold: Option<f64>,
+    /// Interests to score against instead of the learned top tags
+    #[serde(default)]
+    pub interests: Option<Vec<String>>,
+    /// AI provider to score with instead of `ai.provider`
+    #[serde(default)]
+    pub provider: Option<String>,
+}
+
+/// One side of a scoring comparison, as scored
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoringSide {
+    pub relevance_threshold: f64,
+    pub interests: Vec<String>,
+    /// Whether `interests` are the learned top tags
+    pub learned_interests: bool,
+    pub provider: String,
+}
+
+/// An article scored with both settings of a comparison
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComparedArticle {
+    pub id: Uuid,
+    pub title: String,
+    /// None when the article could not be scored
+    pub score_a: Option<f64>,
+    pub score_b: Option<f64>,
+}
+
+impl ComparedArticle {
+    /// Whether the article would stay unread with the settings of side A
+    pub fn kept_a(&self, comparison: &ScoreComparison) -> bool {
+        is_kept(self.score_a, comparison.a.relevance_threshold)
+    }
+
+    /// Whether the article would stay unread with the settings of side B
+    pub fn kept_b(&self, comparison: &ScoreComparison) -> bool {
+        is_kept(self.score_b, comparison.b.relevance_threshold)
+    }
+}
+
+/// Articles that could not be scored are not filtered
+fn is_kept(score: Option<f64>, threshold: f64) -> bool {
+    score.is_none_or(|score| score >= threshold)
+}
+
+/// How filtering the same articles would differ between two scoring settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoreComparison {
+    pub a: ScoringSide,
+    pub b: ScoringSide,
+    /// Whether both sides share one scoring, differing only in threshold
+    pub same_scores: bool,
+    /// The compared articles, newest first
+    pub articles: Vec<ComparedArticle>,
+}
+
+impl ScoreComparison {
+    /// Number of articles kept by side A and by side B
+    pub fn kept(&self) -> (usize, usize) {
+        let a = self.articles.iter().filter(|article| article.kept_a(self)).count();
+        let b = self.articles.iter().filter(|article| article.kept_b(self)).count();
+        (a, b)
+    }
+
+    /// Articles kept by A that B would filter
+    pub fn filtered_only_by_b(&self) -> Vec<&ComparedArticle> {
+        self.articles.iter().filter(|a| a.kept_a(self) && !a.kept_b(self)).collect()
+    }
+
+    /// Articles filtered by A that B would keep
+    pub fn kept_only_by_b(&self) -> Vec<&ComparedArticle> {
+        self.articles.iter().filter(|a| !a.kept_a(self) && a.kept_b(self)).collect()
+    }
+}
+
 /// A computed user preference
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserPreference {
@@ -159,3 +333,47 @@ pub struct UserPreference {
     pub time_window: TimeWindow,
     pub computed_at: DateTime<Utc>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn side(relevance_threshold: f64) -> ScoringSide {
+        ScoringSide {
+            relevance_threshold,
+            interests: vec!["rust".to_string()],
+            learned_interests: true,
+            provider: "claude_cli".to_string(),
+        }
+    }
+
+    fn article(title: &str, score: Option<f64>) -> ComparedArticle {
+        ComparedArticle {
+            id: Uuid::new_v4(),
+            title: title.to_string(),
+            score_a: score,
+            score_b: score,
+        }
+    }
+
+    #[test]
+    fn test_score_comparison_reports_changed_filtering() {
+        let comparison = ScoreComparison {
+            a: side(0.3),
+            b: side(0.5),
+            same_scores: true,
+            articles: vec![
+                article("high", Some(0.8)),
+                article("middle", Some(0.4)),
+                article("low", Some(0.1)),
+                article("unscored", None),
+            ],
+        };
+        assert_eq!(comparison.kept(), (3, 2));
+        let titles = |articles: Vec<&ComparedArticle>| -> Vec<String> {
+            articles.into_iter().map(|a| a.title.clone()).collect()
+        };
+        assert_eq!(titles(comparison.filtered_only_by_b()), ["middle"]);
+        assert!(comparison.kept_only_by_b().is_empty());
+    }
+}
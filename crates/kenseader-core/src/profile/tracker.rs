@@ -51,6 +51,38 @@ impl<'a> BehaviorTracker<'a> {
         Ok(())
     }
 
+    /// Record the same event for several articles, given with their feed,
+    /// in one transaction
+    pub async fn record_events(&self, articles: &[(Uuid, Uuid)], event_type: BehaviorEventType) -> Result<()> {
+        let now = Utc::now();
+        let time_of_day = TimeOfDay::from_hour(now.hour());
+        let day_of_week = now.weekday().num_days_from_monday() as i32;
+
+        let mut tx = self.db.pool().begin().await?;
+        for (article_id, feed_id) in articles {
+            sqlx::query(
+                r#"
+                INSERT INTO behavior_events
+                (article_id, feed_id, event_type, context_time_of_day, context_day_of_week,
+                 context_network_type, created_at)
+                VALUES (?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(article_id.to_string())
+            .bind(feed_id.to_string())
+            .bind(event_type.as_str())
+            .bind(time_of_day.as_str())
+            .bind(day_of_week)
+            .bind("unknown")
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+
     /// Record article exposure (seen in list)
     pub async fn record_exposure(&self, article_id: Uuid, feed_id: Uuid) -> Result<()> {
         self.record_event(
@@ -117,6 +149,17 @@ impl<'a> BehaviorTracker<'a> {
         .await
     }
 
+    /// Record explicit feedback: more (`more` true) or fewer articles like this
+    pub async fn record_feedback(&self, article_id: Uuid, feed_id: Uuid, more: bool) -> Result<()> {
+        let event_type = if more {
+            BehaviorEventType::MoreLikeThis
+        } else {
+            BehaviorEventType::LessLikeThis
+        };
+        self.record_event(Some(article_id), Some(feed_id), event_type, None, None)
+            .await
+    }
+
     /// Record repeat view
     pub async fn record_repeat_view(&self, article_id: Uuid, feed_id: Uuid) -> Result<()> {
         self.record_event(
@@ -1,5 +1,9 @@
+mod quiet;
+mod schedule;
 mod service;
 pub mod tasks;
 
-pub use service::{SchedulerEvent, SchedulerService};
-pub use tasks::{cleanup_old_articles, refresh_all_feeds, summarize_pending_articles};
+pub use quiet::QuietHours;
+pub use schedule::CronSchedule;
+pub use service::{NextRefresh, SchedulerEvent, SchedulerService};
+pub use tasks::{auto_mark_read, MovedFeeds, check_connectivity, cleanup_old_articles, find_stale_feeds, refresh_all_feeds, refresh_feed_metadata, refresh_all_feeds_with_progress, refresh_feed, summarize_pending_articles};
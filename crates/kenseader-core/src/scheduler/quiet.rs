@@ -0,0 +1,92 @@
+use chrono::{Local, NaiveTime};
+
+/// Daily local-time window (e.g. "23:00-07:00") during which background
+/// refresh and AI tasks pause or run less often
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuietHours {
+    start: NaiveTime,
+    end: NaiveTime,
+    /// Run a task on every Nth tick inside the window (0 = never)
+    run_every: u32,
+}
+
+impl QuietHours {
+    /// Parse "HH:MM-HH:MM". The window may wrap past midnight.
+    pub fn parse(spec: &str, run_every: u32) -> Option<Self> {
+        let (start, end) = spec.split_once('-')?;
+        let start = NaiveTime::parse_from_str(start.trim(), "%H:%M").ok()?;
+        let end = NaiveTime::parse_from_str(end.trim(), "%H:%M").ok()?;
+        (start != end).then_some(Self { start, end, run_every })
+    }
+
+    /// Whether `time` falls inside the window (start inclusive, end exclusive)
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start < self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+
+    /// Whether a task should run on this tick. `skipped` counts the task's
+    /// ticks skipped so far in the current window.
+    pub fn allows(&self, time: NaiveTime, skipped: &mut u32) -> bool {
+        if !self.contains(time) {
+            *skipped = 0;
+            return true;
+        }
+        if self.run_every == 0 {
+            return false;
+        }
+        *skipped += 1;
+        if *skipped >= self.run_every {
+            *skipped = 0;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// `allows` for the current local time
+    pub fn allows_now(&self, skipped: &mut u32) -> bool {
+        self.allows(Local::now().time(), skipped)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_quiet_hours_window() {
+        let night = QuietHours::parse("23:00-07:00", 0).unwrap();
+        assert!(night.contains(at(23, 0)));
+        assert!(night.contains(at(3, 30)));
+        assert!(!night.contains(at(7, 0)));
+        assert!(!night.contains(at(12, 0)));
+
+        let lunch = QuietHours::parse(" 12:00 - 13:30 ", 0).unwrap();
+        assert!(lunch.contains(at(13, 0)));
+        assert!(!lunch.contains(at(14, 0)));
+
+        assert!(QuietHours::parse("23:00", 0).is_none());
+        assert!(QuietHours::parse("25:00-07:00", 0).is_none());
+        assert!(QuietHours::parse("07:00-07:00", 0).is_none());
+    }
+
+    #[test]
+    fn test_quiet_hours_reduced_frequency() {
+        let mut skipped = 0;
+        let paused = QuietHours::parse("23:00-07:00", 0).unwrap();
+        assert!(!paused.allows(at(1, 0), &mut skipped));
+        assert!(paused.allows(at(8, 0), &mut skipped));
+
+        let every_third = QuietHours::parse("23:00-07:00", 3).unwrap();
+        let runs: Vec<bool> = (0..6).map(|_| every_third.allows(at(1, 0), &mut skipped)).collect();
+        assert_eq!(runs, [false, false, true, false, false, true]);
+    }
+}
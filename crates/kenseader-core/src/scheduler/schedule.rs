@@ -0,0 +1,254 @@
+use std::future::pending;
+use std::pin::Pin;
+use std::time::Duration;
+
+use chrono::{Datelike, Local, NaiveDate, NaiveDateTime, Timelike};
+use tokio::time::{interval_at, sleep_until, Instant, Interval, Sleep};
+use tracing::warn;
+
+use crate::config::TaskSchedule;
+
+/// Cron expression with the five standard fields (minute hour day-of-month
+/// month day-of-week), e.g. "30 7 * * *". Fields accept `*`, numbers,
+/// lists (`1,15`), ranges (`1-5`) and steps (`*/15`). The shortcuts
+/// `@hourly`, `@daily`, `@weekly` and `@monthly` are also accepted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    /// Day-of-month and day-of-week were given; a date matching either runs
+    days_restricted: bool,
+    weekdays_restricted: bool,
+}
+
+impl CronSchedule {
+    pub fn parse(expr: &str) -> Option<Self> {
+        let expr = match expr.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            expr => expr,
+        };
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return None;
+        };
+        // Day-of-week 7 is Sunday too
+        let mut weekdays = parse_field(weekday, 0, 7)?;
+        if weekdays & (1 << 7) != 0 {
+            weekdays |= 1;
+        }
+        Some(Self {
+            minutes: parse_field(minute, 0, 59)?,
+            hours: parse_field(hour, 0, 23)?,
+            days: parse_field(day, 1, 31)?,
+            months: parse_field(month, 1, 12)?,
+            weekdays,
+            days_restricted: day != "*",
+            weekdays_restricted: weekday != "*",
+        })
+    }
+
+    fn matches_date(&self, date: NaiveDate) -> bool {
+        if self.months & (1 << date.month()) == 0 {
+            return false;
+        }
+        let day = self.days & (1 << date.day()) != 0;
+        let weekday = self.weekdays & (1 << date.weekday().num_days_from_sunday()) != 0;
+        match (self.days_restricted, self.weekdays_restricted) {
+            (true, true) => day || weekday,
+            _ => day && weekday,
+        }
+    }
+
+    /// First matching minute strictly after `after`
+    pub fn next_after(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        let start = after.with_second(0)?.with_nanosecond(0)? + chrono::Duration::minutes(1);
+        let mut date = start.date();
+        // Five years covers every valid expression (e.g. February 29)
+        for _ in 0..5 * 366 {
+            if self.matches_date(date) {
+                let first_day = date == start.date();
+                let from_hour = if first_day { start.hour() } else { 0 };
+                for hour in (from_hour..24).filter(|h| self.hours & (1 << h) != 0) {
+                    let from_minute = if first_day && hour == start.hour() { start.minute() } else { 0 };
+                    if let Some(minute) = (from_minute..60).find(|m| self.minutes & (1 << m) != 0) {
+                        return date.and_hms_opt(hour, minute, 0);
+                    }
+                }
+            }
+            date = date.succ_opt()?;
+        }
+        None
+    }
+}
+
+/// Parse one cron field into a bit set of the allowed values
+fn parse_field(field: &str, min: u32, max: u32) -> Option<u64> {
+    let mut bits = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse::<u32>().ok().filter(|&s| s > 0)?),
+            None => (part, 1),
+        };
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (start.parse().ok()?, end.parse().ok()?)
+        } else {
+            let value = range.parse().ok()?;
+            // "5/15" runs from 5 to the end of the range
+            (value, if step > 1 { max } else { value })
+        };
+        if start < min || end > max || start > end {
+            return None;
+        }
+        for value in (start..=end).step_by(step as usize) {
+            bits |= 1 << value;
+        }
+    }
+    Some(bits)
+}
+
+/// When a scheduler task runs: on a fixed interval, on a cron schedule, or never
+pub enum Ticker {
+    Interval {
+        interval: Interval,
+        /// When the next tick is due
+        next: Instant,
+    },
+    Cron {
+        schedule: CronSchedule,
+        sleep: Pin<Box<Sleep>>,
+    },
+    Disabled,
+}
+
+impl Ticker {
+    /// Build the ticker of task `name` from its schedule settings, falling
+    /// back to `interval` without a (valid) cron expression. With `immediate`
+    /// an interval ticks right away, otherwise after one period.
+    pub fn new(name: &str, schedule: &TaskSchedule, interval: Duration, immediate: bool) -> Self {
+        if !schedule.enabled {
+            return Ticker::Disabled;
+        }
+        if let Some(ref expr) = schedule.cron {
+            match CronSchedule::parse(expr) {
+                Some(schedule) => {
+                    let sleep = Box::pin(sleep_until(next_cron_instant(&schedule)));
+                    return Ticker::Cron { schedule, sleep };
+                }
+                None => warn!("Invalid cron expression for {}: '{}', using its interval", name, expr),
+            }
+        }
+        let start = if immediate { Instant::now() } else { Instant::now() + interval };
+        Ticker::Interval { interval: interval_at(start, interval), next: start }
+    }
+
+    /// Wait for the next run
+    pub async fn tick(&mut self) {
+        match self {
+            Ticker::Interval { interval, next } => {
+                interval.tick().await;
+                *next = Instant::now() + interval.period();
+            }
+            Ticker::Cron { schedule, sleep } => {
+                sleep.as_mut().await;
+                let next = next_cron_instant(schedule);
+                sleep.as_mut().reset(next);
+            }
+            Ticker::Disabled => pending().await,
+        }
+    }
+
+    /// Delay the next interval tick to `after` from now (cron runs keep their times)
+    pub fn reset_after(&mut self, after: Duration) {
+        if let Ticker::Interval { interval, next } = self {
+            interval.reset_after(after);
+            *next = Instant::now() + after;
+        }
+    }
+
+    /// Run as soon as possible
+    pub fn reset_immediately(&mut self) {
+        match self {
+            Ticker::Interval { interval, next } => {
+                interval.reset_immediately();
+                *next = Instant::now();
+            }
+            Ticker::Cron { sleep, .. } => sleep.as_mut().reset(Instant::now()),
+            Ticker::Disabled => {}
+        }
+    }
+
+    /// When the next run is due (None when disabled)
+    pub fn next_run(&self) -> Option<Instant> {
+        match self {
+            Ticker::Interval { next, .. } => Some(*next),
+            Ticker::Cron { sleep, .. } => Some(sleep.deadline()),
+            Ticker::Disabled => None,
+        }
+    }
+
+    /// Human-readable schedule for logging
+    pub fn describe(&self) -> String {
+        match self {
+            Ticker::Interval { interval, .. } => format!("{}s", interval.period().as_secs()),
+            Ticker::Cron { .. } => "cron".to_string(),
+            Ticker::Disabled => "off".to_string(),
+        }
+    }
+}
+
+/// Instant of the next cron match in local time (a day away if none is found)
+fn next_cron_instant(schedule: &CronSchedule) -> Instant {
+    let now = Local::now().naive_local();
+    let wait = schedule
+        .next_after(now)
+        .and_then(|next| (next - now).to_std().ok())
+        .unwrap_or(Duration::from_secs(24 * 3600));
+    Instant::now() + wait
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(date: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(date, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn test_cron_next_after() {
+        let daily = CronSchedule::parse("30 7 * * *").unwrap();
+        assert_eq!(daily.next_after(at("2026-10-16 06:00")), Some(at("2026-10-16 07:30")));
+        assert_eq!(daily.next_after(at("2026-10-16 07:30")), Some(at("2026-10-17 07:30")));
+
+        // 2026-10-16 is a Friday; weekly runs on Sunday
+        let weekly = CronSchedule::parse("@weekly").unwrap();
+        assert_eq!(weekly.next_after(at("2026-10-16 12:00")), Some(at("2026-10-18 00:00")));
+
+        let quarter_hours = CronSchedule::parse("*/15 9-17 * * 1-5").unwrap();
+        assert_eq!(quarter_hours.next_after(at("2026-10-16 09:07")), Some(at("2026-10-16 09:15")));
+        assert_eq!(quarter_hours.next_after(at("2026-10-16 17:50")), Some(at("2026-10-19 09:00")));
+
+        // Day-of-month or day-of-week when both are restricted
+        let either = CronSchedule::parse("0 0 1 * 0").unwrap();
+        assert_eq!(either.next_after(at("2026-10-16 00:00")), Some(at("2026-10-18 00:00")));
+
+        let leap_day = CronSchedule::parse("0 12 29 2 *").unwrap();
+        assert_eq!(leap_day.next_after(at("2026-10-16 00:00")), Some(at("2028-02-29 12:00")));
+    }
+
+    #[test]
+    fn test_cron_parse_errors() {
+        for expr in ["", "* * * *", "60 * * * *", "* 24 * * *", "* * 0 * *", "*/0 * * * *", "5-1 * * * *"] {
+            assert!(CronSchedule::parse(expr).is_none(), "{}", expr);
+        }
+        assert!(CronSchedule::parse("0 4 * * 7").is_some());
+    }
+}
@@ -1,39 +1,98 @@
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
-use tokio::sync::{mpsc, watch};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{broadcast, watch};
+use tokio::task::JoinHandle;
+use tokio::time::{Instant, MissedTickBehavior};
 use tracing::{debug, error, info, warn};
 
+use crate::advisories;
 use crate::ai::Summarizer;
-use crate::config::AppConfig;
+use crate::audio_digest;
+use crate::config::{AppConfig, NotificationMode};
+use crate::feed::bandwidth;
+use crate::feed::{Feed, PruneSuggestion};
+use crate::integrations::export_saved;
+use crate::jitter::jitter;
+use crate::mqtt;
+use crate::web;
+use crate::notify::{Notifier, Pusher};
+use crate::state_sync::sync_state;
 use crate::storage::Database;
-use crate::Result;
+use crate::{Error, Result};
 
-use super::tasks::{classify_pending_articles, cleanup_old_articles, refresh_all_feeds, score_and_filter_articles, summarize_pending_articles};
+use super::quiet::QuietHours;
+use super::schedule::Ticker;
+use super::tasks::{auto_mark_read, check_connectivity, CLASSIFY_BATCH_SIZE, classify_pending_articles, cleanup_old_articles, find_stale_feeds, refresh_all_feeds, refresh_all_feeds_with_progress, refresh_feed_metadata, score_and_filter_articles, summarize_pending_articles};
+
+/// Audio briefings are written daily unless a cron schedule is set
+const AUDIO_DIGEST_INTERVAL_SECS: u64 = 24 * 60 * 60;
+
+/// Feeds are checked for pruning daily unless a cron schedule is set
+const PRUNE_INTERVAL_SECS: u64 = 24 * 60 * 60;
+
+/// Feed metadata is refreshed weekly unless a cron schedule is set
+const METADATA_INTERVAL_SECS: u64 = 7 * 24 * 60 * 60;
 
 /// Events emitted by the scheduler to notify the UI of changes
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
 pub enum SchedulerEvent {
+    /// A feed refresh has fetched `done` of `total` due feeds
+    RefreshProgress { done: u32, total: u32 },
     /// Feeds have been refreshed, UI should reload article list
     FeedsRefreshed { new_articles: u32 },
     /// Articles have been cleaned up
     ArticlesCleaned { deleted: u32 },
+    /// Unread articles past their feed's auto-read period were marked read
+    ArticlesAutoRead { count: u32 },
     /// Articles have been summarized
     ArticlesSummarized { count: u32 },
     /// Articles have been scored and filtered
     ArticlesFiltered { scored: u32, filtered: u32 },
     /// Articles have been classified for style/tone
     ArticlesClassified { count: u32 },
+    /// Feeds found worth unsubscribing from (sent only when there are any)
+    StaleFeeds { feeds: Vec<PruneSuggestion> },
+    /// Feeds found permanently redirected, with their new URL in `moved_to`
+    /// (sent only when there are any)
+    FeedsMoved { feeds: Vec<Feed> },
+    /// Permanently redirected feeds were switched to their new URL
+    /// (`sync.follow_moved_feeds`)
+    FeedsMigrated { feeds: Vec<Feed> },
+    /// Read/saved state was merged from other machines
+    StateSynced { applied: u32 },
+    /// Saved articles were sent to read-later services (sent only when any were)
+    ArticlesExported { count: u32 },
+    /// The network was lost (fetching paused) or came back
+    ConnectivityChanged { online: bool },
     /// An error occurred during a background task
     Error { task: String, message: String },
 }
 
+/// When the scheduler refreshes feeds next, shared with the IPC server
+#[derive(Debug, Clone, Default)]
+pub struct NextRefresh(Arc<Mutex<Option<Instant>>>);
+
+impl NextRefresh {
+    fn set(&self, at: Option<Instant>) {
+        *self.0.lock().unwrap() = at;
+    }
+
+    /// Time left until the next scheduled refresh (None when refresh is off)
+    pub fn remaining(&self) -> Option<Duration> {
+        self.0.lock().unwrap().map(|at| at.saturating_duration_since(Instant::now()))
+    }
+}
+
 /// Background scheduler service that runs periodic tasks
 pub struct SchedulerService {
     db: Arc<Database>,
     config: Arc<AppConfig>,
     summarizer: Option<Arc<Summarizer>>,
-    event_tx: Option<mpsc::UnboundedSender<SchedulerEvent>>,
+    event_tx: Option<broadcast::Sender<SchedulerEvent>>,
+    next_refresh: NextRefresh,
 }
 
 impl SchedulerService {
@@ -44,6 +103,7 @@ impl SchedulerService {
             config,
             summarizer: None,
             event_tx: None,
+            next_refresh: NextRefresh::default(),
         }
     }
 
@@ -53,27 +113,70 @@ impl SchedulerService {
         self
     }
 
-    /// Set the event sender for UI notifications
-    pub fn with_event_sender(mut self, tx: mpsc::UnboundedSender<SchedulerEvent>) -> Self {
+    /// Set the event sender for UI notifications (one sender can feed many subscribers)
+    pub fn with_event_sender(mut self, tx: broadcast::Sender<SchedulerEvent>) -> Self {
         self.event_tx = Some(tx);
         self
     }
 
+    /// Publish the time of the next scheduled refresh to `next_refresh`
+    pub fn with_next_refresh(mut self, next_refresh: NextRefresh) -> Self {
+        self.next_refresh = next_refresh;
+        self
+    }
+
     /// Send an event to the UI (if event channel is configured)
     fn send_event(&self, event: SchedulerEvent) {
-        if let Some(ref tx) = self.event_tx {
-            if tx.send(event).is_err() {
-                warn!("Failed to send scheduler event: receiver dropped");
-            }
-        }
+        send_event(&self.event_tx, event);
     }
 
     /// Run background tasks in a loop until shutdown signal
     pub async fn run(self, mut shutdown: watch::Receiver<bool>) {
         let refresh_secs = self.config.sync.refresh_interval_secs;
+        let refresh_jitter_secs = self.config.sync.refresh_jitter_secs;
+        let metered_refresh = Duration::from_secs(self.config.metered.refresh_interval_secs);
+        let mut last_refresh: Option<Instant> = None;
+        let quiet_hours = self.config.sync.quiet_hours.as_deref().and_then(|spec| {
+            match QuietHours::parse(spec, self.config.sync.quiet_hours_run_every) {
+                Some(quiet_hours) => {
+                    info!("Quiet hours: {}", spec);
+                    Some(quiet_hours)
+                }
+                None => {
+                    warn!("Invalid quiet_hours '{}' (expected \"HH:MM-HH:MM\"), ignoring", spec);
+                    None
+                }
+            }
+        });
+        // Ticks skipped per task during the current quiet hours
+        let (mut refresh_skipped, mut summarize_skipped, mut filter_skipped) = (0, 0, 0);
+        let in_quiet_hours = |skipped: &mut u32| {
+            quiet_hours.is_some_and(|quiet_hours| !quiet_hours.allows_now(skipped))
+        };
         let cleanup_secs = self.config.sync.cleanup_interval_secs;
         let summarize_secs = self.config.sync.summarize_interval_secs;
         let filter_secs = self.config.sync.filter_interval_secs;
+        let state_sync_enabled = self.config.state_sync.enabled;
+        let state_sync_secs = self.config.state_sync.interval_secs.max(1);
+        let notifications = &self.config.notifications;
+        let mut notifier = notifications
+            .enabled
+            .then(|| Notifier::new(self.db.clone(), self.config.clone()));
+        let mut pusher = Pusher::new(self.db.clone(), self.config.clone());
+        let notify_after_refresh = notifications.mode == NotificationMode::PerArticle;
+        let digest_enabled = notifier.is_some() && notifications.mode == NotificationMode::Digest;
+        let digest_secs = notifications.digest_interval_mins.max(1) * 60;
+        let audio_digest_enabled = self.config.audio_digest.enabled;
+        let prune_enabled = self.config.prune.enabled;
+        let integrations_enabled = self.config.integrations.enabled;
+        let integrations_secs = self.config.integrations.interval_secs.max(1);
+        let advisories_enabled = self.config.advisories.enabled;
+        // Serves briefings to podcast apps whether or not new ones are made
+        audio_digest::spawn_server(&self.config, shutdown.clone());
+        web::spawn_server(self.db.clone(), self.config.clone(), shutdown.clone());
+        if let Some(ref event_tx) = self.event_tx {
+            mqtt::spawn(self.db.clone(), self.config.clone(), event_tx.subscribe(), shutdown.clone());
+        }
 
         // Skip if refresh is disabled (0)
         if refresh_secs == 0 {
@@ -83,25 +186,45 @@ impl SchedulerService {
             return;
         }
 
-        info!(
-            "Scheduler started: refresh={}s, cleanup={}s, summarize={}s, filter={}s",
-            refresh_secs, cleanup_secs, summarize_secs, filter_secs
-        );
-
-        let mut refresh_interval =
-            tokio::time::interval(Duration::from_secs(refresh_secs));
-        let mut cleanup_interval =
-            tokio::time::interval(Duration::from_secs(cleanup_secs));
+        // Each task runs on its interval, or its cron expression, unless disabled
+        let schedule = &self.config.sync.schedule;
+        let secs = Duration::from_secs;
+        let mut refresh_interval = Ticker::new("refresh", &schedule.refresh, secs(refresh_secs), false);
+        let mut cleanup_interval = Ticker::new("cleanup", &schedule.cleanup, secs(cleanup_secs), false);
         let mut summarize_interval =
-            tokio::time::interval(Duration::from_secs(summarize_secs));
-        let mut filter_interval =
-            tokio::time::interval(Duration::from_secs(filter_secs));
+            Ticker::new("summarize", &schedule.summarize, secs(summarize_secs), false);
+        let mut filter_interval = Ticker::new("filter", &schedule.filter, secs(filter_secs), false);
+        // State sync runs on its first tick too, to pick up changes made elsewhere
+        let mut state_sync_interval =
+            Ticker::new("state_sync", &schedule.state_sync, secs(state_sync_secs), true);
+        let mut digest_interval = Ticker::new("digest", &schedule.digest, secs(digest_secs), false);
+        let mut audio_digest_interval =
+            Ticker::new("audio_digest", &schedule.audio_digest, secs(AUDIO_DIGEST_INTERVAL_SECS), false);
+        let mut prune_interval = Ticker::new("prune", &schedule.prune, secs(PRUNE_INTERVAL_SECS), false);
+        let mut metadata_interval =
+            Ticker::new("metadata", &schedule.metadata, secs(METADATA_INTERVAL_SECS), false);
+        let mut integrations_interval =
+            Ticker::new("integrations", &schedule.integrations, secs(integrations_secs), false);
+        self.next_refresh.set(refresh_interval.next_run());
 
-        // Skip the first tick (fires immediately)
-        refresh_interval.tick().await;
-        cleanup_interval.tick().await;
-        summarize_interval.tick().await;
-        filter_interval.tick().await;
+        info!(
+            "Scheduler started: refresh={}, cleanup={}, summarize={}, filter={}",
+            refresh_interval.describe(),
+            cleanup_interval.describe(),
+            summarize_interval.describe(),
+            filter_interval.describe()
+        );
+        // Probes only run while offline; don't burst missed ticks afterwards
+        let mut connectivity_interval = tokio::time::interval(Duration::from_secs(
+            self.config.sync.connectivity_check_secs.max(1),
+        ));
+        connectivity_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let mut offline = false;
+        // Background AI runs; a tick is skipped while the previous run is busy
+        let mut summarize_task: Option<JoinHandle<()>> = None;
+        let mut filter_task: Option<JoinHandle<()>> = None;
+        let mut audio_digest_task: Option<JoinHandle<()>> = None;
+        let mut advisories_task: Option<JoinHandle<()>> = None;
 
         loop {
             tokio::select! {
@@ -115,13 +238,71 @@ impl SchedulerService {
 
                 // Refresh feeds periodically
                 _ = refresh_interval.tick() => {
+                    // Delay the next tick by a random jitter so daemons started
+                    // together don't hit the network and database in lockstep
+                    if refresh_jitter_secs > 0 {
+                        refresh_interval.reset_after(
+                            Duration::from_secs(refresh_secs) + jitter(Duration::from_secs(refresh_jitter_secs)),
+                        );
+                    }
+                    self.next_refresh.set(refresh_interval.next_run());
+                    if offline {
+                        debug!("Skipping scheduled feed refresh while offline");
+                        continue;
+                    }
+                    if in_quiet_hours(&mut refresh_skipped) {
+                        debug!("Skipping scheduled feed refresh during quiet hours");
+                        continue;
+                    }
+                    // Metered mode stretches the interval between refreshes
+                    if bandwidth::is_metered() && last_refresh.is_some_and(|at| at.elapsed() < metered_refresh) {
+                        debug!("Skipping scheduled feed refresh while metered");
+                        continue;
+                    }
+                    if bandwidth::budget_exhausted(self.config.metered.daily_budget_mb) {
+                        debug!("Skipping scheduled feed refresh: daily metered bandwidth budget used up");
+                        continue;
+                    }
+                    last_refresh = Some(Instant::now());
                     debug!("Running scheduled feed refresh");
-                    match refresh_all_feeds(&self.db, &self.config).await {
-                        Ok(new_articles) => {
+                    let progress = |done, total| self.send_event(SchedulerEvent::RefreshProgress { done, total });
+                    match refresh_all_feeds_with_progress(&self.db, &self.config, progress).await {
+                        Ok((new_articles, moved)) => {
                             if new_articles > 0 {
                                 info!("Scheduled refresh: {} new articles", new_articles);
                             }
                             self.send_event(SchedulerEvent::FeedsRefreshed { new_articles });
+                            for event in moved.into_events() {
+                                self.send_event(event);
+                            }
+
+                            if let Some(ref mut notifier) = notifier {
+                                if notify_after_refresh && new_articles > 0 {
+                                    if let Err(e) = notifier.flush().await {
+                                        warn!("Failed to send notifications: {}", e);
+                                    }
+                                }
+                            }
+                            if let Some(ref mut pusher) = pusher {
+                                if new_articles > 0 {
+                                    if let Err(e) = pusher.flush().await {
+                                        warn!("Failed to push articles: {}", e);
+                                    }
+                                }
+                            }
+                            // Lookups are rate limited, so they run in the background
+                            if advisories_enabled && advisories_task.as_ref().is_none_or(|task| task.is_finished()) {
+                                advisories_task = Some(tokio::spawn(run_advisories(
+                                    self.db.clone(),
+                                    self.config.clone(),
+                                    self.event_tx.clone(),
+                                )));
+                            }
+                        }
+                        Err(Error::Offline(reason)) => {
+                            warn!("Network unreachable ({}), pausing feed refresh", reason);
+                            offline = true;
+                            self.send_event(SchedulerEvent::ConnectivityChanged { online: false });
                         }
                         Err(e) => {
                             error!("Scheduled refresh failed: {}", e);
@@ -133,7 +314,23 @@ impl SchedulerService {
                     }
                 }
 
-                // Cleanup old articles periodically
+                // While offline, probe until the network is back, then
+                // refresh right away instead of waiting out the interval
+                _ = connectivity_interval.tick(), if offline => {
+                    match check_connectivity(&self.db, &self.config).await {
+                        Ok(true) => {
+                            info!("Network is reachable again, refreshing feeds");
+                            offline = false;
+                            self.send_event(SchedulerEvent::ConnectivityChanged { online: true });
+                            refresh_interval.reset_immediately();
+                            self.next_refresh.set(refresh_interval.next_run());
+                        }
+                        Ok(false) => debug!("Still offline"),
+                        Err(e) => warn!("Connectivity check failed: {}", e),
+                    }
+                }
+
+                // Cleanup old articles and mark aged ones read periodically
                 _ = cleanup_interval.tick() => {
                     debug!("Running scheduled article cleanup");
                     match cleanup_old_articles(&self.db, &self.config).await {
@@ -151,76 +348,183 @@ impl SchedulerService {
                             });
                         }
                     }
+                    match auto_mark_read(&self.db, &self.config).await {
+                        Ok(0) => {}
+                        Ok(count) => self.send_event(SchedulerEvent::ArticlesAutoRead { count }),
+                        Err(e) => {
+                            error!("Marking aged articles read failed: {}", e);
+                            self.send_event(SchedulerEvent::Error {
+                                task: "cleanup".to_string(),
+                                message: e.to_string(),
+                            });
+                        }
+                    }
                 }
 
                 // Summarize pending articles (if AI is enabled)
                 _ = summarize_interval.tick() => {
+                    if in_quiet_hours(&mut summarize_skipped) {
+                        continue;
+                    }
                     if let Some(ref summarizer) = self.summarizer {
+                        if summarizer.is_paused() {
+                            debug!("AI requests paused after repeated failures, skipping summarization");
+                            continue;
+                        }
+                        if summarize_task.as_ref().is_some_and(|task| !task.is_finished()) {
+                            debug!("Previous summarization still running, skipping");
+                            continue;
+                        }
                         debug!("Running scheduled summarization");
-                        // No article limit - batch size is controlled by token limit
-                        match summarize_pending_articles(&self.db, summarizer.clone()).await {
-                            Ok(count) => {
-                                if count > 0 {
-                                    info!("Scheduled summarization: {} articles", count);
-                                }
-                                self.send_event(SchedulerEvent::ArticlesSummarized { count });
+                        // Runs in the background so it shares the AI pool with
+                        // filtering instead of holding up the other tasks
+                        summarize_task = Some(tokio::spawn(run_summarize(
+                            self.db.clone(),
+                            summarizer.clone(),
+                            self.event_tx.clone(),
+                        )));
+                    }
+                }
+
+                // Send the notification digest (if enabled)
+                _ = digest_interval.tick(), if digest_enabled => {
+                    if let Some(ref mut notifier) = notifier {
+                        debug!("Sending notification digest");
+                        if let Err(e) = notifier.flush().await {
+                            warn!("Failed to send notification digest: {}", e);
+                        }
+                    }
+                }
+
+                // Write the daily audio briefing (if enabled)
+                _ = audio_digest_interval.tick(), if audio_digest_enabled => {
+                    if audio_digest_task.as_ref().is_some_and(|task| !task.is_finished()) {
+                        debug!("Previous audio briefing still being written, skipping");
+                        continue;
+                    }
+                    debug!("Writing audio briefing");
+                    audio_digest_task = Some(tokio::spawn(run_audio_digest(
+                        self.db.clone(),
+                        self.config.clone(),
+                        self.event_tx.clone(),
+                    )));
+                }
+
+                // Look for feeds worth unsubscribing from (if enabled)
+                _ = prune_interval.tick(), if prune_enabled => {
+                    debug!("Checking feeds for pruning");
+                    match find_stale_feeds(&self.db, &self.config).await {
+                        Ok(feeds) if feeds.is_empty() => {}
+                        Ok(feeds) => {
+                            info!("{} feeds may be worth unsubscribing from", feeds.len());
+                            self.send_event(SchedulerEvent::StaleFeeds { feeds });
+                        }
+                        Err(e) => {
+                            error!("Feed pruning check failed: {}", e);
+                            self.send_event(SchedulerEvent::Error {
+                                task: "prune".to_string(),
+                                message: e.to_string(),
+                            });
+                        }
+                    }
+                }
+
+                // Refresh feed titles, icons and moved URLs (low frequency)
+                _ = metadata_interval.tick() => {
+                    if offline {
+                        continue;
+                    }
+                    debug!("Refreshing feed metadata");
+                    match refresh_feed_metadata(&self.db, &self.config).await {
+                        Ok(moved) => {
+                            for event in moved.into_events() {
+                                self.send_event(event);
                             }
-                            Err(e) => {
-                                error!("Scheduled summarization failed: {}", e);
-                                self.send_event(SchedulerEvent::Error {
-                                    task: "summarize".to_string(),
-                                    message: e.to_string(),
-                                });
+                        }
+                        Err(e) => {
+                            error!("Feed metadata refresh failed: {}", e);
+                            self.send_event(SchedulerEvent::Error {
+                                task: "metadata".to_string(),
+                                message: e.to_string(),
+                            });
+                        }
+                    }
+                }
+
+                // Send saved articles to read-later services (if enabled)
+                _ = integrations_interval.tick(), if integrations_enabled => {
+                    if offline {
+                        continue;
+                    }
+                    debug!("Exporting saved articles");
+                    match export_saved(&self.db, &self.config).await {
+                        Ok(0) => {}
+                        Ok(count) => self.send_event(SchedulerEvent::ArticlesExported { count }),
+                        Err(e) => {
+                            error!("Exporting saved articles failed: {}", e);
+                            self.send_event(SchedulerEvent::Error {
+                                task: "integrations".to_string(),
+                                message: e.to_string(),
+                            });
+                        }
+                    }
+                }
+
+                // Exchange read/saved state with other machines (if enabled)
+                _ = state_sync_interval.tick(), if state_sync_enabled => {
+                    debug!("Running scheduled state sync");
+                    match sync_state(&self.db, &self.config).await {
+                        Ok(stats) => {
+                            if stats.applied > 0 {
+                                info!("State sync: {} articles updated from other machines", stats.applied);
                             }
+                            self.send_event(SchedulerEvent::StateSynced { applied: stats.applied });
+                        }
+                        Err(e) => {
+                            error!("Scheduled state sync failed: {}", e);
+                            self.send_event(SchedulerEvent::Error {
+                                task: "state_sync".to_string(),
+                                message: e.to_string(),
+                            });
                         }
                     }
                 }
 
                 // Score and filter articles (if AI is enabled)
                 _ = filter_interval.tick() => {
+                    if in_quiet_hours(&mut filter_skipped) {
+                        continue;
+                    }
+                    // Push articles the previous run scored high
+                    if let Some(ref mut pusher) = pusher {
+                        if let Err(e) = pusher.flush().await {
+                            warn!("Failed to push articles: {}", e);
+                        }
+                    }
                     if let Some(ref summarizer) = self.summarizer {
-                        // Run filtering
-                        debug!("Running scheduled filtering");
-                        let threshold = self.config.ai.relevance_threshold;
-                        let min_len = self.config.ai.min_summarize_length;
-                        match score_and_filter_articles(&self.db, summarizer.clone(), threshold, min_len).await {
-                            Ok((scored, filtered)) => {
-                                if scored > 0 {
-                                    info!("Scheduled filtering: scored {}, filtered {}", scored, filtered);
-                                }
-                                self.send_event(SchedulerEvent::ArticlesFiltered { scored, filtered });
-                            }
-                            Err(e) => {
-                                error!("Scheduled filtering failed: {}", e);
-                                self.send_event(SchedulerEvent::Error {
-                                    task: "filter".to_string(),
-                                    message: e.to_string(),
-                                });
-                            }
+                        if summarizer.is_paused() {
+                            debug!("AI requests paused after repeated failures, skipping filtering");
+                            continue;
                         }
-
-                        // Run classification after filtering
-                        debug!("Running scheduled classification");
-                        match classify_pending_articles(&self.db, summarizer.clone(), 10).await {
-                            Ok(count) => {
-                                if count > 0 {
-                                    info!("Scheduled classification: {} articles", count);
-                                }
-                                self.send_event(SchedulerEvent::ArticlesClassified { count });
-                            }
-                            Err(e) => {
-                                error!("Scheduled classification failed: {}", e);
-                                self.send_event(SchedulerEvent::Error {
-                                    task: "classify".to_string(),
-                                    message: e.to_string(),
-                                });
-                            }
+                        if filter_task.as_ref().is_some_and(|task| !task.is_finished()) {
+                            debug!("Previous filtering still running, skipping");
+                            continue;
                         }
+                        debug!("Running scheduled filtering");
+                        filter_task = Some(tokio::spawn(run_filter(
+                            self.db.clone(),
+                            summarizer.clone(),
+                            self.config.clone(),
+                            self.event_tx.clone(),
+                        )));
                     }
                 }
             }
         }
 
+        for task in [summarize_task, filter_task, audio_digest_task, advisories_task].into_iter().flatten() {
+            task.abort();
+        }
         info!("Scheduler stopped");
     }
 
@@ -232,6 +536,129 @@ impl SchedulerService {
     }
 }
 
+/// Send an event to the UI (if event channel is configured)
+fn send_event(event_tx: &Option<broadcast::Sender<SchedulerEvent>>, event: SchedulerEvent) {
+    if let Some(ref tx) = event_tx {
+        // Without subscribers (no UI connected) the event is simply dropped
+        let _ = tx.send(event);
+    }
+}
+
+/// Scheduled summarization, run as a background task
+async fn run_summarize(
+    db: Arc<Database>,
+    summarizer: Arc<Summarizer>,
+    event_tx: Option<broadcast::Sender<SchedulerEvent>>,
+) {
+    // No article limit - batch size is controlled by token limit
+    match summarize_pending_articles(&db, summarizer.clone()).await {
+        Ok(count) => {
+            if count > 0 {
+                info!("Scheduled summarization: {} articles", count);
+            }
+            send_event(&event_tx, SchedulerEvent::ArticlesSummarized { count });
+        }
+        Err(e) => {
+            error!("Scheduled summarization failed: {}", e);
+            send_event(&event_tx, SchedulerEvent::Error {
+                task: "summarize".to_string(),
+                message: e.to_string(),
+            });
+        }
+    }
+    log_queue_stats(&summarizer);
+}
+
+/// Scheduled audio briefing, run as a background task (TTS takes a while)
+async fn run_audio_digest(
+    db: Arc<Database>,
+    config: Arc<AppConfig>,
+    event_tx: Option<broadcast::Sender<SchedulerEvent>>,
+) {
+    match audio_digest::generate(&db, &config).await {
+        Ok(Some(_)) => {}
+        Ok(None) => debug!("No new articles for an audio briefing"),
+        Err(e) => {
+            error!("Audio briefing failed: {}", e);
+            send_event(&event_tx, SchedulerEvent::Error {
+                task: "audio_digest".to_string(),
+                message: e.to_string(),
+            });
+        }
+    }
+}
+
+/// CVE lookups after a refresh, run as a background task
+async fn run_advisories(
+    db: Arc<Database>,
+    config: Arc<AppConfig>,
+    event_tx: Option<broadcast::Sender<SchedulerEvent>>,
+) {
+    if let Err(e) = advisories::look_up_pending(&db, &config).await {
+        error!("CVE lookups failed: {}", e);
+        send_event(&event_tx, SchedulerEvent::Error {
+            task: "advisories".to_string(),
+            message: e.to_string(),
+        });
+    }
+}
+
+/// Scheduled filtering followed by classification, run as a background task
+async fn run_filter(
+    db: Arc<Database>,
+    summarizer: Arc<Summarizer>,
+    config: Arc<AppConfig>,
+    event_tx: Option<broadcast::Sender<SchedulerEvent>>,
+) {
+    let threshold = config.ai.relevance_threshold;
+    let min_len = config.ai.min_summarize_length;
+    let half_life = config.ai.preference_half_life_days;
+    match score_and_filter_articles(&db, summarizer.clone(), threshold, min_len, half_life).await {
+        Ok((scored, filtered)) => {
+            if scored > 0 {
+                info!("Scheduled filtering: scored {}, filtered {}", scored, filtered);
+            }
+            send_event(&event_tx, SchedulerEvent::ArticlesFiltered { scored, filtered });
+        }
+        Err(e) => {
+            error!("Scheduled filtering failed: {}", e);
+            send_event(&event_tx, SchedulerEvent::Error {
+                task: "filter".to_string(),
+                message: e.to_string(),
+            });
+        }
+    }
+
+    // Run classification after filtering
+    debug!("Running scheduled classification");
+    match classify_pending_articles(&db, summarizer.clone(), CLASSIFY_BATCH_SIZE).await {
+        Ok(count) => {
+            if count > 0 {
+                info!("Scheduled classification: {} articles", count);
+            }
+            send_event(&event_tx, SchedulerEvent::ArticlesClassified { count });
+        }
+        Err(e) => {
+            error!("Scheduled classification failed: {}", e);
+            send_event(&event_tx, SchedulerEvent::Error {
+                task: "classify".to_string(),
+                message: e.to_string(),
+            });
+        }
+    }
+    log_queue_stats(&summarizer);
+}
+
+/// Log the AI pool's queue metrics
+fn log_queue_stats(summarizer: &Summarizer) {
+    for stats in summarizer.queue_stats() {
+        debug!(
+            "AI queue {}: {} queued, {} running, {} completed, avg wait {}ms",
+            stats.task.name(), stats.queued, stats.running, stats.completed, stats.avg_wait_ms
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
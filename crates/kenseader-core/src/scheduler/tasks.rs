@@ -1,15 +1,21 @@
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
+use tokio::task::JoinSet;
 use tokio::time::sleep;
 use uuid::Uuid;
 
 use crate::ai::{ArticleForScoring, ArticleForSummary, Summarizer};
+use crate::advisories;
+use crate::alerts;
 use crate::config::AppConfig;
-use crate::feed::FeedFetcher;
-use crate::profile::{ProfileAnalyzer, TimeWindow};
-use crate::storage::{ArticleRepository, ArticleStyleRepository, Database, FeedRepository};
-use crate::Result;
+use crate::feed::{Article, ArticleOutcome, DryRunArticle, Feed, FeedDryRun, FeedFetcher, FetchMetric, PruneSuggestion};
+use crate::profile::{ComparedArticle, ProfileAnalyzer, ScoreComparison, ScoringSettings, ScoringSide, TimeWindow};
+use crate::storage::{ArticleRepository, ArticleStyleRepository, Database, FeedRepository, FetchMetricsRepository};
+use crate::{Error, Result};
+
+use super::service::SchedulerEvent;
 
 /// Truncate a string to a maximum number of characters (respecting char boundaries)
 fn truncate_chars(input: &str, max_chars: usize) -> &str {
@@ -19,69 +25,347 @@ fn truncate_chars(input: &str, max_chars: usize) -> &str {
     }
 }
 
+/// Articles classified per classification run
+pub const CLASSIFY_BATCH_SIZE: usize = 10;
+
+/// Consecutive feeds failing to connect before a refresh gives up as offline
+const OFFLINE_FAILURE_THRESHOLD: u32 = 3;
+
+/// Probed while offline when `sync.connectivity_probe_url` is unset
+const DEFAULT_CONNECTIVITY_PROBE_URL: &str = "https://1.1.1.1/";
+
+/// Active feeds probed along with the default URL, so one dead host
+/// (or a blocked default) can't keep the daemon offline
+const PROBED_FEEDS: usize = 3;
+
+/// Feeds in a row that failed to connect during a refresh
+#[derive(Debug, Default)]
+struct ConnectFailures(u32);
+
+impl ConnectFailures {
+    /// Count a fetch that failed to connect (or reset on any other outcome).
+    /// Returns true once enough feeds in a row failed to call the network down.
+    fn record(&mut self, connect_failed: bool) -> bool {
+        self.0 = if connect_failed { self.0 + 1 } else { 0 };
+        self.0 >= OFFLINE_FAILURE_THRESHOLD
+    }
+}
+
+/// Feeds found redirecting permanently (301/308) during a task
+#[derive(Debug, Clone, Default)]
+pub struct MovedFeeds {
+    /// Feeds flagged with the URL they moved to in `moved_to`
+    pub flagged: Vec<Feed>,
+    /// Feeds switched to their new URL (`sync.follow_moved_feeds`)
+    pub migrated: Vec<Feed>,
+}
+
+impl MovedFeeds {
+    /// Events telling the UI about the moves (none when there were none)
+    pub fn into_events(self) -> Vec<SchedulerEvent> {
+        let mut events = Vec::new();
+        if !self.flagged.is_empty() {
+            events.push(SchedulerEvent::FeedsMoved { feeds: self.flagged });
+        }
+        if !self.migrated.is_empty() {
+            events.push(SchedulerEvent::FeedsMigrated { feeds: self.migrated });
+        }
+        events
+    }
+}
+
 /// Refresh feeds and fetch new articles
-/// Uses smart refresh: only refreshes feeds that haven't been fetched recently
+/// Uses smart refresh: only refreshes feeds that haven't been fetched recently.
+/// Returns `Error::Offline` when several feeds in a row cannot be reached.
 pub async fn refresh_all_feeds(db: &Database, config: &AppConfig) -> Result<u32> {
+    let (new_articles, _) = refresh_all_feeds_with_progress(db, config, |_, _| {}).await?;
+    Ok(new_articles)
+}
+
+/// `refresh_all_feeds`, calling `progress(done, total)` before the first
+/// feed and after each one. Also returns the feeds newly found moved.
+pub async fn refresh_all_feeds_with_progress(
+    db: &Database,
+    config: &AppConfig,
+    mut progress: impl FnMut(u32, u32),
+) -> Result<(u32, MovedFeeds)> {
     let fetcher = FeedFetcher::new(config)?;
     let feed_repo = FeedRepository::new(db);
     let article_repo = ArticleRepository::new(db);
-
-    // Smart refresh: only get feeds that need refreshing
-    let feeds = if config.sync.feed_refresh_interval_secs > 0 {
-        let needs_refresh = feed_repo.list_needs_refresh(config.sync.feed_refresh_interval_secs).await?;
-        let total_feeds = feed_repo.count().await?;
-        if needs_refresh.is_empty() {
-            tracing::debug!("No feeds need refreshing (all {} feeds are up to date)", total_feeds);
-            return Ok(0);
-        }
-        tracing::info!(
-            "Smart refresh: {} of {} feeds need refreshing (interval: {} hours)",
-            needs_refresh.len(),
-            total_feeds,
-            config.sync.feed_refresh_interval_secs / 3600
-        );
-        needs_refresh
-    } else {
-        // feed_refresh_interval_secs = 0 means refresh all feeds every time
-        feed_repo.list_all().await?
-    };
+    let metrics = FetchMetricsRepository::new(db);
+
+    // Smart refresh: only get feeds that need refreshing (paused feeds are
+    // skipped; feed_refresh_interval_secs = 0 means every other feed is due)
+    let feeds = feed_repo
+        .list_needs_refresh(
+            config.sync.feed_refresh_interval_secs,
+            config.sync.feed_stagger_percent,
+        )
+        .await?;
+    let total_feeds = feed_repo.count().await?;
+    let mut moved = MovedFeeds::default();
+    if feeds.is_empty() {
+        tracing::debug!("No feeds need refreshing (all {} feeds are up to date)", total_feeds);
+        return Ok((0, moved));
+    }
+    tracing::info!(
+        "Smart refresh: {} of {} feeds need refreshing (interval: {} hours)",
+        feeds.len(),
+        total_feeds,
+        config.sync.feed_refresh_interval_secs / 3600
+    );
 
     let mut total_new = 0;
+    let mut connect_failures = ConnectFailures::default();
     let rate_limit = Duration::from_millis(config.sync.rate_limit_ms);
+    let due = feeds.len() as u32;
+    progress(0, due);
 
     for (idx, feed) in feeds.iter().enumerate() {
         tracing::info!("Refreshing feed: {}", feed.local_name);
 
-        match fetcher.fetch(&feed.url, feed.id).await {
-            Ok(parsed) => {
-                // Update feed metadata
-                feed_repo.update_metadata(
-                    feed.id,
-                    parsed.title.as_deref(),
-                    parsed.description.as_deref(),
-                    parsed.site_url.as_deref(),
-                    parsed.icon_url.as_deref(),
-                ).await?;
-
-                // Insert new articles
-                let new_count = article_repo.create_many(&parsed.articles).await?;
+        match fetch_feed(&fetcher, &feed_repo, &article_repo, &metrics, config, feed, &mut moved).await? {
+            Ok(new_count) => {
+                connect_failures.record(false);
                 total_new += new_count;
-
                 tracing::info!("Feed '{}': {} new articles", feed.local_name, new_count);
             }
             Err(e) => {
                 tracing::error!("Failed to fetch feed '{}': {}", feed.local_name, e);
-                feed_repo.update_fetch_error(feed.id, &e.to_string()).await?;
+                if connect_failures.record(e.is_connectivity()) {
+                    if total_new > 0 {
+                        tracing::info!("Stored {} new articles before losing the network", total_new);
+                    }
+                    return Err(Error::Offline(format!(
+                        "{} feeds in a row could not be reached",
+                        connect_failures.0
+                    )));
+                }
             }
         }
 
+        progress(idx as u32 + 1, due);
+
         // Apply rate limit between requests (skip delay after last feed)
         if rate_limit.as_millis() > 0 && idx < feeds.len() - 1 {
             sleep(rate_limit).await;
         }
     }
 
-    Ok(total_new)
+    Ok((total_new, moved))
+}
+
+/// Check whether the network is reachable: any one of `probe_urls` answering
+/// is enough
+pub async fn check_connectivity(db: &Database, config: &AppConfig) -> Result<bool> {
+    let feeds = match config.sync.connectivity_probe_url {
+        Some(_) => Vec::new(),
+        None => FeedRepository::new(db).list_all().await?,
+    };
+    let fetcher = FeedFetcher::new(config)?;
+    for url in probe_urls(config, &feeds) {
+        if fetcher.is_reachable(&url).await {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// URLs probed while offline: `sync.connectivity_probe_url` alone when set,
+/// otherwise a well-known URL followed by the first few active feeds
+fn probe_urls(config: &AppConfig, feeds: &[Feed]) -> Vec<String> {
+    if let Some(url) = &config.sync.connectivity_probe_url {
+        return vec![url.clone()];
+    }
+    std::iter::once(DEFAULT_CONNECTIVITY_PROBE_URL.to_string())
+        .chain(feeds.iter().filter(|feed| !feed.paused).take(PROBED_FEEDS).map(|feed| feed.url.clone()))
+        .collect()
+}
+
+/// Refresh a single feed regardless of when it was last fetched
+pub async fn refresh_feed(db: &Database, config: &AppConfig, feed_id: Uuid) -> Result<u32> {
+    let fetcher = FeedFetcher::new(config)?;
+    let feed_repo = FeedRepository::new(db);
+    let article_repo = ArticleRepository::new(db);
+    let metrics = FetchMetricsRepository::new(db);
+
+    let feed = feed_repo
+        .find_by_id(feed_id)
+        .await?
+        .ok_or_else(|| Error::FeedNotFound(feed_id.to_string()))?;
+
+    tracing::info!("Refreshing feed: {}", feed.local_name);
+    let mut moved = MovedFeeds::default();
+    fetch_feed(&fetcher, &feed_repo, &article_repo, &metrics, config, &feed, &mut moved).await?
+}
+
+/// Fetch a feed and store its metadata and new articles, adding it to
+/// `moved` when it newly turns out to redirect permanently, and record how
+/// long it took in `metrics`.
+/// The outer result carries database errors, the inner one the fetch error,
+/// which is also recorded on the feed.
+async fn fetch_feed(
+    fetcher: &FeedFetcher,
+    feed_repo: &FeedRepository<'_>,
+    article_repo: &ArticleRepository<'_>,
+    metrics: &FetchMetricsRepository<'_>,
+    config: &AppConfig,
+    feed: &Feed,
+    moved: &mut MovedFeeds,
+) -> Result<Result<u32>> {
+    let quirks = config.quirks.get(&feed.local_name).cloned().unwrap_or_default();
+    let started = Instant::now();
+    let mut metric = FetchMetric {
+        feed_id: feed.id,
+        ..FetchMetric::default()
+    };
+    let result = match fetcher.fetch_with_quirks(&feed.url, feed.id, &quirks).await {
+        Ok(parsed) => {
+            let storing = Instant::now();
+            metric.fetch_ms = parsed.fetch_ms;
+            metric.parse_ms = parsed.parse_ms;
+            metric.bytes = parsed.bytes;
+            metric.articles = parsed.articles.len() as u32;
+
+            // Update feed metadata
+            feed_repo.update_metadata(
+                feed.id,
+                parsed.title.as_deref(),
+                parsed.description.as_deref(),
+                parsed.site_url.as_deref(),
+                parsed.icon_url.as_deref(),
+            ).await?;
+
+            match parsed.moved_to.as_deref() {
+                // Already flagged moves are left to the weekly metadata check
+                Some(new_url)
+                    if config.sync.follow_moved_feeds || feed.moved_to.as_deref() != Some(new_url) =>
+                {
+                    record_move(feed_repo, config, feed, new_url, moved).await?;
+                }
+                None if feed.moved_to.is_some() => feed_repo.set_moved_to(feed.id, None).await?,
+                _ => {}
+            }
+
+            // Insert new articles
+            let created = article_repo.create_many(&parsed.articles).await?;
+            metric.store_ms = storing.elapsed().as_millis() as u64;
+            metric.inserted = created.len() as u32;
+
+            // Keyword alerts don't wait for summaries or scoring
+            if let Err(e) = alerts::check_new_articles(article_repo, config, feed, &created).await {
+                tracing::warn!("Failed to check '{}' for keyword alerts: {}", feed.local_name, e);
+            }
+            if let Err(e) = advisories::link_new_articles(article_repo, config, &created).await {
+                tracing::warn!("Failed to link '{}' articles to CVEs: {}", feed.local_name, e);
+            }
+            Ok(metric.inserted)
+        }
+        Err(e) => {
+            metric.fetch_ms = started.elapsed().as_millis() as u64;
+            metric.error = Some(e.to_string());
+            feed_repo.update_fetch_error(feed.id, &e.to_string()).await?;
+            Err(e)
+        }
+    };
+
+    tracing::debug!(
+        "Feed '{}' took {}ms: fetch {}ms ({} bytes), parse {}ms, store {}ms ({} of {} items new)",
+        feed.local_name,
+        metric.total_ms(),
+        metric.fetch_ms,
+        metric.bytes,
+        metric.parse_ms,
+        metric.store_ms,
+        metric.inserted,
+        metric.articles
+    );
+    if let Err(e) = metrics.record(&metric).await {
+        tracing::warn!("Failed to record fetch metrics of '{}': {}", feed.local_name, e);
+    }
+    Ok(result)
+}
+
+/// Fetch every active feed and report what a refresh would store, without
+/// writing to the database. Feeds that aren't due are fetched too, and
+/// marked as such.
+pub async fn dry_run_refresh(db: &Database, config: &AppConfig) -> Result<Vec<FeedDryRun>> {
+    let fetcher = FeedFetcher::new(config)?;
+    let feed_repo = FeedRepository::new(db);
+    let article_repo = ArticleRepository::new(db);
+    let now = chrono::Utc::now();
+    let rate_limit = Duration::from_millis(config.sync.rate_limit_ms);
+    let feeds: Vec<Feed> = feed_repo.list_all().await?.into_iter().filter(|f| !f.paused).collect();
+    let mut reports = Vec::with_capacity(feeds.len());
+
+    for (idx, feed) in feeds.iter().enumerate() {
+        let mut report = FeedDryRun {
+            feed_id: feed.id,
+            name: feed.local_name.clone(),
+            url: feed.url.clone(),
+            due: feed.is_due(now, config.sync.feed_refresh_interval_secs, config.sync.feed_stagger_percent),
+            status: None,
+            error: None,
+            moved_to: None,
+            articles: Vec::new(),
+        };
+        let quirks = config.quirks.get(&feed.local_name).cloned().unwrap_or_default();
+        match fetcher.fetch_with_quirks(&feed.url, feed.id, &quirks).await {
+            Ok(parsed) => {
+                report.status = parsed.status;
+                report.moved_to = parsed.moved_to;
+                let mut seen = HashSet::new();
+                for article in &parsed.articles {
+                    let outcome = if seen.insert(article.guid.as_str()) {
+                        article_repo.outcome(article).await?
+                    } else {
+                        ArticleOutcome::Duplicate
+                    };
+                    report.articles.push(DryRunArticle {
+                        guid: article.guid.clone(),
+                        title: article.title.clone(),
+                        url: article.url.clone(),
+                        outcome,
+                    });
+                }
+            }
+            Err(e) => report.error = Some(e.to_string()),
+        }
+        reports.push(report);
+
+        if rate_limit.as_millis() > 0 && idx < feeds.len() - 1 {
+            sleep(rate_limit).await;
+        }
+    }
+
+    Ok(reports)
+}
+
+/// Flag a feed found redirecting permanently to `new_url`, or switch it to
+/// that URL with `sync.follow_moved_feeds` (its articles and settings stay)
+async fn record_move(
+    feed_repo: &FeedRepository<'_>,
+    config: &AppConfig,
+    feed: &Feed,
+    new_url: &str,
+    moved: &mut MovedFeeds,
+) -> Result<()> {
+    if config.sync.follow_moved_feeds && feed_repo.migrate_url(feed.id, new_url).await? {
+        tracing::info!("Feed '{}' moved permanently, switched from {} to {}", feed.local_name, feed.url, new_url);
+        moved.migrated.push(Feed {
+            url: new_url.to_string(),
+            moved_to: None,
+            ..feed.clone()
+        });
+        return Ok(());
+    }
+    tracing::info!("Feed '{}' moved permanently to {}", feed.local_name, new_url);
+    feed_repo.set_moved_to(feed.id, Some(new_url)).await?;
+    moved.flagged.push(Feed {
+        moved_to: Some(new_url.to_string()),
+        ..feed.clone()
+    });
+    Ok(())
 }
 
 /// Clean up articles older than retention period
@@ -92,10 +376,91 @@ pub async fn cleanup_old_articles(db: &Database, config: &AppConfig) -> Result<u
     if deleted > 0 {
         tracing::info!("Cleaned up {} old articles", deleted);
     }
+    FetchMetricsRepository::new(db).cleanup().await?;
 
     Ok(deleted)
 }
 
+/// Mark unread articles read once they pass their feed's auto-read period
+/// (`general.auto_read_days` unless the feed sets its own)
+pub async fn auto_mark_read(db: &Database, config: &AppConfig) -> Result<u32> {
+    let feed_repo = FeedRepository::new(db);
+    let article_repo = ArticleRepository::new(db);
+    let now = chrono::Utc::now();
+    let mut marked = 0;
+
+    for feed in feed_repo.list_all().await? {
+        let Some(days) = feed.auto_read_after(config.general.auto_read_days) else {
+            continue;
+        };
+        let before = now - chrono::Duration::days(i64::from(days));
+        marked += article_repo.mark_read_before(feed.id, before).await?;
+    }
+
+    if marked > 0 {
+        tracing::info!("Marked {} aged articles as read", marked);
+    }
+
+    Ok(marked)
+}
+
+/// Feeds that may be worth unsubscribing from: stale, rarely read or low
+/// scoring by `prune` settings. Paused feeds are left out.
+pub async fn find_stale_feeds(db: &Database, config: &AppConfig) -> Result<Vec<PruneSuggestion>> {
+    let feed_repo = FeedRepository::new(db);
+    let now = chrono::Utc::now();
+    let mut suggestions = Vec::new();
+
+    for feed in feed_repo.list_all().await? {
+        if feed.paused {
+            continue;
+        }
+        let reasons = feed_repo
+            .stats(feed.id)
+            .await?
+            .prune_reasons(feed.created_at, &config.prune, now);
+        if !reasons.is_empty() {
+            suggestions.push(PruneSuggestion {
+                feed_id: feed.id,
+                name: feed.local_name,
+                reasons,
+            });
+        }
+    }
+
+    Ok(suggestions)
+}
+
+/// Refresh the feed-level details (title, description, site, icon) of the
+/// active feeds and record permanent redirects. Returns all moved feeds.
+pub async fn refresh_feed_metadata(db: &Database, config: &AppConfig) -> Result<MovedFeeds> {
+    let fetcher = FeedFetcher::new(config)?;
+    let feed_repo = FeedRepository::new(db);
+    let rate_limit = Duration::from_millis(config.sync.rate_limit_ms);
+    let mut moved = MovedFeeds::default();
+
+    for feed in feed_repo.list_all().await? {
+        if feed.paused {
+            continue;
+        }
+        let quirks = config.quirks.get(&feed.local_name).cloned().unwrap_or_default();
+        match fetcher.fetch_metadata(&feed.url, &quirks).await {
+            Ok(metadata) => {
+                feed_repo.update_details(feed.id, &metadata).await?;
+                if let Some(ref new_url) = metadata.moved_to {
+                    record_move(&feed_repo, config, &feed, new_url, &mut moved).await?;
+                }
+            }
+            Err(e) => tracing::debug!("Metadata refresh failed for '{}': {}", feed.local_name, e),
+        }
+        if rate_limit.as_millis() > 0 {
+            sleep(rate_limit).await;
+        }
+    }
+
+    Ok(moved)
+}
+
 /// Maximum content length per article (truncate if longer)
 const CONTENT_TRUNCATE_LIMIT: usize = 4000;
 
@@ -148,10 +513,8 @@ pub async fn summarize_pending_articles(
     let mut summarized = 0;
     let mut batch_idx = 0;
 
-    // Process articles in batches, re-checking status before each batch
+    // Process articles in rounds of concurrent batches, re-checking status before each round
     while !pending_articles.is_empty() {
-        batch_idx += 1;
-
         // Re-check which articles are still unread before processing
         let pending_ids: Vec<Uuid> = pending_articles
             .iter()
@@ -172,7 +535,7 @@ pub async fn summarize_pending_articles(
         if filtered_count > 0 {
             tracing::info!(
                 "Batch {}: Filtered out {} already-read articles",
-                batch_idx,
+                batch_idx + 1,
                 filtered_count
             );
         }
@@ -182,55 +545,82 @@ pub async fn summarize_pending_articles(
             break;
         }
 
-        // Create a single batch from pending articles (up to char limit)
-        let batch = create_single_batch(&mut pending_articles, batch_char_limit);
+        // Start one batch per AI slot (each up to char limit); the shared
+        // pool keeps them from crowding out scoring and classification
+        let mut running = JoinSet::new();
+        for _ in 0..summarizer.concurrency() {
+            let batch = create_single_batch(&mut pending_articles, batch_char_limit);
+            if batch.is_empty() {
+                break;
+            }
+            batch_idx += 1;
 
-        if batch.is_empty() {
-            continue;
-        }
+            tracing::debug!(
+                "Processing batch {} with {} articles ({} remaining)",
+                batch_idx,
+                batch.len(),
+                pending_articles.len()
+            );
 
-        tracing::debug!(
-            "Processing batch {} with {} articles ({} remaining)",
-            batch_idx,
-            batch.len(),
-            pending_articles.len()
-        );
+            let summarizer = summarizer.clone();
+            running.spawn(async move { (batch_idx, summarizer.batch_summarize(batch).await) });
+        }
 
-        match summarizer.batch_summarize(batch).await {
-            Ok(results) => {
-                for result in results {
-                    if let Some(summary) = result.summary {
-                        // Parse article ID back to Uuid
-                        if let Ok(article_id) = Uuid::parse_str(&result.id) {
-                            if let Err(e) = article_repo.update_summary(article_id, &summary).await {
-                                tracing::warn!("Failed to save summary for article {}: {}", article_id, e);
-                                continue;
-                            }
+        let mut unavailable = false;
+        while let Some(joined) = running.join_next().await {
+            let (batch_idx, result) = match joined {
+                Ok(joined) => joined,
+                Err(e) => {
+                    tracing::error!("Summarization batch task failed: {}", e);
+                    continue;
+                }
+            };
+            match result {
+                Ok(results) => {
+                    for result in results {
+                        if let Some(summary) = result.summary {
+                            // Parse article ID back to Uuid
+                            if let Ok(article_id) = Uuid::parse_str(&result.id) {
+                                if let Err(e) = article_repo.update_summary(article_id, &summary).await {
+                                    tracing::warn!("Failed to save summary for article {}: {}", article_id, e);
+                                    continue;
+                                }
 
-                            // Find original article to extract tags
-                            if let Some(article) = articles.iter().find(|a| a.id == article_id) {
-                                if let Some(content) = &article.content_text {
-                                    if let Ok(tags) = summarizer.extract_tags(content).await {
-                                        if let Err(e) = article_repo.add_tags(article_id, &tags, "ai").await {
-                                            tracing::warn!("Failed to add tags for article {}: {}", article_id, e);
+                                // Find original article to extract tags
+                                if let Some(article) = articles.iter().find(|a| a.id == article_id) {
+                                    if let Some(content) = &article.content_text {
+                                        if let Ok(tags) = summarizer.extract_tags(content).await {
+                                            if let Err(e) = article_repo.add_tags(article_id, &tags, "ai").await {
+                                                tracing::warn!("Failed to add tags for article {}: {}", article_id, e);
+                                            }
                                         }
                                     }
                                 }
-                            }
 
-                            summarized += 1;
-                            tracing::debug!("Summarized article ID {}", article_id);
+                                summarized += 1;
+                                tracing::debug!("Summarized article ID {}", article_id);
+                            }
+                        } else if let Some(error) = result.error {
+                            tracing::warn!("Batch result error for article {}: {}", result.id, error);
                         }
-                    } else if let Some(error) = result.error {
-                        tracing::warn!("Batch result error for article {}: {}", result.id, error);
                     }
                 }
-            }
-            Err(e) => {
-                tracing::error!("Batch {} summarization failed: {}", batch_idx, e);
-                // Continue with next batch instead of failing completely
+                Err(e @ Error::AiUnavailable(_)) => {
+                    // Remaining batches would fail the same way
+                    if !unavailable {
+                        tracing::warn!("Summarization stopped: {}", e);
+                    }
+                    unavailable = true;
+                }
+                Err(e) => {
+                    tracing::error!("Batch {} summarization failed: {}", batch_idx, e);
+                    // Continue with next batch instead of failing completely
+                }
             }
         }
+        if unavailable {
+            break;
+        }
     }
 
     if summarized > 0 {
@@ -293,9 +683,10 @@ pub async fn score_and_filter_articles(
     summarizer: Arc<Summarizer>,
     relevance_threshold: f64,
     min_summarize_length: usize,
+    preference_half_life_days: f64,
 ) -> Result<(u32, u32)> {
     let article_repo = ArticleRepository::new(db);
-    let analyzer = ProfileAnalyzer::new(db);
+    let analyzer = ProfileAnalyzer::new(db).with_half_life(preference_half_life_days);
 
     // Compute user preferences from behavior events before scoring
     if let Err(e) = analyzer.compute_preferences().await {
@@ -315,39 +706,21 @@ pub async fn score_and_filter_articles(
 
     // Get unread short articles (< min_summarize_length) that don't need summarization
     let all_unread = article_repo.list_unread(1000).await?;
-    let short_articles: Vec<_> = all_unread
+    let short_articles = all_unread.into_iter().filter(|a| a.summary.is_none());
+
+    // Articles rescued from filtering stay unread
+    let rescued: HashSet<Uuid> = article_repo.list_rescued_ids().await?.into_iter().collect();
+
+    // Build scoring candidates: summarized articles first, then short ones
+    let candidates: Vec<(Uuid, String)> = summarized_articles
         .into_iter()
-        .filter(|a| {
-            // No summary and content is short enough to skip summarization
-            a.summary.is_none()
-                && a.content_text
-                    .as_ref()
-                    .map(|c| c.trim().len() < min_summarize_length)
-                    .unwrap_or(true)
+        .chain(short_articles)
+        .filter(|article| !rescued.contains(&article.id))
+        .filter_map(|article| {
+            scoring_text(&article, min_summarize_length).map(|content| (article.id, content))
         })
         .collect();
 
-    // Build scoring candidates
-    let mut candidates: Vec<(Uuid, String)> = Vec::new();
-
-    // Summarized articles: use title + summary
-    for article in &summarized_articles {
-        if let Some(ref summary) = article.summary {
-            let content = format!("{}\n\n{}", article.title, summary);
-            candidates.push((article.id, content));
-        }
-    }
-
-    // Short articles: use title + content
-    for article in &short_articles {
-        let content = if let Some(ref text) = article.content_text {
-            format!("{}\n\n{}", article.title, text)
-        } else {
-            article.title.clone()
-        };
-        candidates.push((article.id, content));
-    }
-
     if candidates.is_empty() {
         tracing::info!("No articles to score");
         return Ok((0, 0));
@@ -375,6 +748,9 @@ pub async fn score_and_filter_articles(
     let mut scored = 0u32;
     let mut filtered = 0u32;
 
+    // Start all batches at once; the AI pool bounds how many run concurrently
+    let interests = Arc::new(interests);
+    let mut running = JoinSet::new();
     for (batch_idx, batch) in batches.into_iter().enumerate() {
         tracing::debug!(
             "Processing scoring batch {} with {} articles",
@@ -382,7 +758,20 @@ pub async fn score_and_filter_articles(
             batch.len()
         );
 
-        match summarizer.batch_score_relevance(batch, &interests).await {
+        let summarizer = summarizer.clone();
+        let interests = interests.clone();
+        running.spawn(async move { summarizer.batch_score_relevance(batch, &interests).await });
+    }
+
+    while let Some(joined) = running.join_next().await {
+        let result = match joined {
+            Ok(result) => result,
+            Err(e) => {
+                tracing::error!("Scoring batch task failed: {}", e);
+                continue;
+            }
+        };
+        match result {
             Ok(results) => {
                 for result in results {
                     if let Ok(article_id) = Uuid::parse_str(&result.id) {
@@ -403,8 +792,12 @@ pub async fn score_and_filter_articles(
                             );
 
                             if score < relevance_threshold {
-                                // Mark low-relevance articles as read (auto-filter)
-                                if let Err(e) = article_repo.mark_read(article_id).await {
+                                // Mark low-relevance articles as read (auto-filter),
+                                // to be reviewed in the Filtered view
+                                if let Err(e) = article_repo
+                                    .mark_filtered(article_id, score, relevance_threshold)
+                                    .await
+                                {
                                     tracing::warn!(
                                         "Failed to mark article {} as read: {}",
                                         article_id,
@@ -430,6 +823,11 @@ pub async fn score_and_filter_articles(
                     }
                 }
             }
+            Err(e @ Error::AiUnavailable(_)) => {
+                // Dropping the set cancels the batches still queued
+                tracing::warn!("Scoring stopped: {}", e);
+                break;
+            }
             Err(e) => {
                 tracing::error!("Batch scoring failed: {}", e);
             }
@@ -448,6 +846,134 @@ pub async fn score_and_filter_articles(
     Ok((scored, filtered))
 }
 
+/// Text an article is scored on: its title and summary, or its title and
+/// content when it is too short to summarize. None for articles still
+/// awaiting a summary.
+fn scoring_text(article: &Article, min_summarize_length: usize) -> Option<String> {
+    if let Some(ref summary) = article.summary {
+        return Some(format!("{}\n\n{}", article.title, summary));
+    }
+    match article.content_text.as_deref() {
+        Some(text) if text.trim().len() >= min_summarize_length => None,
+        Some(text) => Some(format!("{}\n\n{}", article.title, text)),
+        None => Some(article.title.clone()),
+    }
+}
+
+/// Score the articles of the last `days` days (up to `limit`, read or not)
+/// with the settings of both sides, and report how filtering them would
+/// differ. Nothing is written: scores are not stored and no article is
+/// marked read. Sides differing only in threshold share one scoring.
+pub async fn compare_scoring(
+    db: &Database,
+    config: &AppConfig,
+    shared: Option<&Arc<Summarizer>>,
+    a: &ScoringSettings,
+    b: &ScoringSettings,
+    days: u32,
+    limit: u32,
+) -> Result<ScoreComparison> {
+    if !config.ai.enabled {
+        return Err(Error::Config("AI is disabled (ai.enabled = false)".to_string()));
+    }
+
+    let learned = ProfileAnalyzer::new(db).get_top_tags(TimeWindow::Last30Days, 10).await?;
+    let side = |settings: &ScoringSettings| ScoringSide {
+        relevance_threshold: settings.relevance_threshold.unwrap_or(config.ai.relevance_threshold),
+        interests: settings.interests.clone().unwrap_or_else(|| learned.clone()),
+        learned_interests: settings.interests.is_none(),
+        provider: settings.provider.clone().unwrap_or_else(|| config.ai.provider.clone()),
+    };
+    let (a, b) = (side(a), side(b));
+    let same_scores = a.interests == b.interests && a.provider == b.provider;
+
+    let since = chrono::Utc::now() - chrono::Duration::days(i64::from(days));
+    let articles: Vec<(Article, String)> = ArticleRepository::new(db)
+        .list_created_since(since, limit)
+        .await?
+        .into_iter()
+        .filter_map(|article| {
+            let text = scoring_text(&article, config.ai.min_summarize_length)?;
+            Some((article, text))
+        })
+        .collect();
+
+    tracing::info!(
+        "Comparing scoring of {} articles ({})",
+        articles.len(),
+        if same_scores { "one scoring" } else { "two scorings" }
+    );
+    let scores_a = score_for_comparison(config, shared, &a, &articles).await?;
+    let scores_b = if same_scores {
+        scores_a.clone()
+    } else {
+        score_for_comparison(config, shared, &b, &articles).await?
+    };
+
+    let articles = articles
+        .into_iter()
+        .map(|(article, _)| ComparedArticle {
+            id: article.id,
+            score_a: scores_a.get(&article.id).copied(),
+            score_b: scores_b.get(&article.id).copied(),
+            title: article.title,
+        })
+        .collect();
+    Ok(ScoreComparison { a, b, same_scores, articles })
+}
+
+/// Scores of the articles with the provider and interests of one side
+async fn score_for_comparison(
+    config: &AppConfig,
+    shared: Option<&Arc<Summarizer>>,
+    side: &ScoringSide,
+    articles: &[(Article, String)],
+) -> Result<HashMap<Uuid, f64>> {
+    let summarizer = match shared {
+        Some(summarizer) if side.provider == config.ai.provider => summarizer.clone(),
+        _ => {
+            let mut config = config.clone();
+            config.ai.provider = side.provider.clone();
+            Arc::new(Summarizer::new(&config)?)
+        }
+    };
+
+    let candidates = articles
+        .iter()
+        .map(|(article, text)| ArticleForScoring {
+            id: article.id.to_string(),
+            content: text.clone(),
+        })
+        .collect();
+    let interests = Arc::new(side.interests.clone());
+    let mut running = JoinSet::new();
+    for batch in create_scoring_batches(candidates, summarizer.batch_char_limit()) {
+        let summarizer = summarizer.clone();
+        let interests = interests.clone();
+        running.spawn(async move { summarizer.batch_score_relevance(batch, &interests).await });
+    }
+
+    let mut scores = HashMap::new();
+    while let Some(joined) = running.join_next().await {
+        let result = match joined {
+            Ok(result) => result,
+            Err(e) => {
+                tracing::error!("Comparison scoring task failed: {}", e);
+                continue;
+            }
+        };
+        match result {
+            Ok(results) => scores.extend(results.into_iter().filter_map(|result| {
+                Some((Uuid::parse_str(&result.id).ok()?, result.score?))
+            })),
+            // Fail rather than report every article as unscored
+            Err(e @ Error::AiUnavailable(_)) => return Err(e),
+            Err(e) => tracing::warn!("Comparison scoring batch failed: {}", e),
+        }
+    }
+    Ok(scores)
+}
+
 /// Split articles into batches for scoring based on character limit
 fn create_scoring_batches(
     articles: Vec<ArticleForScoring>,
@@ -506,11 +1032,27 @@ pub async fn classify_pending_articles(
 
     let mut classified = 0u32;
 
+    // Classify concurrently; the AI pool bounds how many requests run at once
+    let mut running = JoinSet::new();
     for article in unclassified {
-        // Use content for classification
-        let content = article.content.as_deref().unwrap_or(&article.title);
+        let summarizer = summarizer.clone();
+        running.spawn(async move {
+            // Use content for classification
+            let content = article.content.as_deref().unwrap_or(&article.title);
+            let result = summarizer.classify_style(content).await;
+            (article, result)
+        });
+    }
 
-        match summarizer.classify_style(content).await {
+    while let Some(joined) = running.join_next().await {
+        let (article, result) = match joined {
+            Ok(joined) => joined,
+            Err(e) => {
+                tracing::error!("Classification task failed: {}", e);
+                continue;
+            }
+        };
+        match result {
             Ok(result) => {
                 if let Err(e) = style_repo.upsert(article.id, &result).await {
                     tracing::warn!(
@@ -529,6 +1071,11 @@ pub async fn classify_pending_articles(
                     result.length_category
                 );
             }
+            Err(e @ Error::AiUnavailable(_)) => {
+                // Dropping the set cancels the articles still queued
+                tracing::warn!("Classification stopped: {}", e);
+                break;
+            }
             Err(e) => {
                 tracing::warn!(
                     "Failed to classify article '{}': {}",
@@ -545,3 +1092,78 @@ pub async fn classify_pending_articles(
 
     Ok(classified)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feed::NewFeed;
+    use crate::storage::test_db_with_feed;
+
+    #[test]
+    fn test_connect_failures_trip_only_in_a_row() {
+        let mut failures = ConnectFailures::default();
+        assert!(!failures.record(true));
+        assert!(!failures.record(true));
+        // A feed that answers (or fails some other way) resets the count
+        assert!(!failures.record(false));
+        assert!(!failures.record(true));
+        assert!(!failures.record(true));
+        assert!(failures.record(true));
+    }
+
+    #[tokio::test]
+    async fn test_timeouts_are_not_connectivity_errors() {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(100))
+            .build()
+            .unwrap();
+
+        // Accepts the connection but never answers
+        let slow = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let slow_url = format!("http://{}/", slow.local_addr().unwrap());
+        let error = client.get(&slow_url).send().await.unwrap_err();
+        assert!(error.is_timeout());
+        assert!(!Error::Http(error).is_connectivity());
+
+        // Nothing listens on a port freed again
+        let closed = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let closed_url = format!("http://{}/", closed.local_addr().unwrap());
+        drop(closed);
+        let error = client.get(&closed_url).send().await.unwrap_err();
+        assert!(Error::Http(error).is_connectivity());
+    }
+
+    #[tokio::test]
+    async fn test_probe_urls_fall_back_to_several_feeds() {
+        let (db, first) = test_db_with_feed().await;
+        let repo = FeedRepository::new(&db);
+        let mut settings = first.settings();
+        settings.paused = true;
+        repo.update_settings(first.id, &settings).await.unwrap();
+        for name in ["a", "b", "c", "d"] {
+            repo.create(&NewFeed {
+                url: format!("https://{}.example.com/feed", name),
+                local_name: name.to_string(),
+            })
+            .await
+            .unwrap();
+        }
+        let feeds = repo.list_all().await.unwrap();
+
+        // A well-known URL first, then a few active feeds (not the paused one)
+        let mut config = AppConfig::default();
+        assert_eq!(
+            probe_urls(&config, &feeds),
+            [
+                DEFAULT_CONNECTIVITY_PROBE_URL,
+                "https://a.example.com/feed",
+                "https://b.example.com/feed",
+                "https://c.example.com/feed",
+            ]
+        );
+        assert_eq!(probe_urls(&config, &[]), [DEFAULT_CONNECTIVITY_PROBE_URL]);
+
+        config.sync.connectivity_probe_url = Some("https://probe.example.com/".to_string());
+        assert_eq!(probe_urls(&config, &feeds), ["https://probe.example.com/"]);
+    }
+}
@@ -0,0 +1,234 @@
+//! Secrets kept out of the config file
+//!
+//! Any string in the config is expanded on load:
+//! - `${NAME}` is replaced by the environment variable `NAME` (`$${` is a
+//!   literal `${`)
+//! - a value `file:<path>` is replaced by the contents of the file
+//! - a value `keyring:<name>` is replaced by the entry `<name>` of the
+//!   "kenseader" service in the OS keyring (Secret Service on Linux,
+//!   Keychain on macOS, Credential Manager on Windows)
+//!
+//! Variables are expanded first, so `file:${CREDENTIALS_DIRECTORY}/openai`
+//! works too.
+
+use std::path::Path;
+
+use keyring::Entry;
+
+use crate::config::expand_tilde;
+use crate::{Error, Result};
+
+/// Prefix of config values read from the keyring
+pub const KEYRING_PREFIX: &str = "keyring:";
+
+/// Prefix of config values read from a file
+pub const FILE_PREFIX: &str = "file:";
+
+/// Keyring service the entries are stored under
+const KEYRING_SERVICE: &str = "kenseader";
+
+fn entry(name: &str) -> Result<Entry> {
+    Entry::new(KEYRING_SERVICE, name).map_err(|e| Error::Config(format!("Keyring entry '{}': {}", name, e)))
+}
+
+/// Read a secret from the keyring
+pub fn get(name: &str) -> Result<String> {
+    entry(name)?
+        .get_password()
+        .map_err(|e| Error::Config(format!("Cannot read '{}' from the keyring: {}", name, e)))
+}
+
+/// Store a secret in the keyring, replacing any previous one
+pub fn set(name: &str, secret: &str) -> Result<()> {
+    entry(name)?
+        .set_password(secret)
+        .map_err(|e| Error::Config(format!("Cannot store '{}' in the keyring: {}", name, e)))
+}
+
+/// Remove a secret from the keyring. Returns false when there was none.
+pub fn delete(name: &str) -> Result<bool> {
+    match entry(name)?.delete_credential() {
+        Ok(()) => Ok(true),
+        Err(keyring::Error::NoEntry) => Ok(false),
+        Err(e) => Err(Error::Config(format!("Cannot delete '{}' from the keyring: {}", name, e))),
+    }
+}
+
+/// Where expanded values come from; replaced in tests
+pub struct Sources<E, K> {
+    /// Environment variable lookup
+    pub env: E,
+    /// Keyring lookup
+    pub keyring: K,
+}
+
+/// Expand `${NAME}` references to environment variables in `text`
+fn expand_env(text: &str, env: &impl Fn(&str) -> Option<String>) -> std::result::Result<String, String> {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('$') {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        if let Some(after) = after.strip_prefix("${") {
+            result.push_str("${");
+            rest = after;
+        } else if let Some(reference) = after.strip_prefix('{') {
+            let end = reference
+                .find('}')
+                .ok_or_else(|| "unclosed ${ in value".to_string())?;
+            let name = &reference[..end];
+            let value = env(name).ok_or_else(|| format!("environment variable {} is not set", name))?;
+            result.push_str(&value);
+            rest = &reference[end + 1..];
+        } else {
+            result.push('$');
+            rest = after;
+        }
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+/// Expand one config string
+fn expand_string<E, K>(text: &str, sources: &mut Sources<E, K>) -> std::result::Result<String, String>
+where
+    E: Fn(&str) -> Option<String>,
+    K: FnMut(&str) -> Result<String>,
+{
+    let text = expand_env(text, &sources.env)?;
+    if let Some(name) = text.strip_prefix(KEYRING_PREFIX) {
+        return (sources.keyring)(name.trim()).map_err(|e| match e {
+            Error::Config(message) => message,
+            e => e.to_string(),
+        });
+    }
+    if let Some(path) = text.strip_prefix(FILE_PREFIX) {
+        let path = expand_tilde(Path::new(path.trim()));
+        let content = std::fs::read_to_string(&path).map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+        // Files written by editors end in a newline that isn't part of the secret
+        return Ok(content.trim_end_matches(['\r', '\n']).to_string());
+    }
+    Ok(text)
+}
+
+/// Expand every string in a parsed config. Errors name the key whose
+/// value could not be expanded.
+pub fn expand_config<E, K>(value: &mut toml::Value, sources: &mut Sources<E, K>) -> Result<()>
+where
+    E: Fn(&str) -> Option<String>,
+    K: FnMut(&str) -> Result<String>,
+{
+    expand_value(value, "", sources)
+}
+
+fn expand_value<E, K>(value: &mut toml::Value, key: &str, sources: &mut Sources<E, K>) -> Result<()>
+where
+    E: Fn(&str) -> Option<String>,
+    K: FnMut(&str) -> Result<String>,
+{
+    match value {
+        toml::Value::String(text) => {
+            *text = expand_string(text, sources).map_err(|e| Error::Config(format!("{}: {}", key, e)))?;
+        }
+        toml::Value::Array(items) => {
+            for (index, item) in items.iter_mut().enumerate() {
+                expand_value(item, &format!("{}[{}]", key, index), sources)?;
+            }
+        }
+        toml::Value::Table(table) => {
+            for (name, item) in table.iter_mut() {
+                let key = if key.is_empty() { name.clone() } else { format!("{}.{}", key, name) };
+                expand_value(item, &key, sources)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Expand a config with the real environment and keyring
+pub fn expand_config_from_system(value: &mut toml::Value) -> Result<()> {
+    expand_config(
+        value,
+        &mut Sources {
+            env: |name: &str| std::env::var(name).ok(),
+            keyring: get,
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(name: &str) -> Option<String> {
+        match name {
+            "HOME" => Some("/home/ann".to_string()),
+            "OPENAI_KEY" => Some("sk-env".to_string()),
+            _ => None,
+        }
+    }
+
+    fn expand(toml: &str) -> Result<toml::Value> {
+        let mut config: toml::Value = toml::from_str(toml).unwrap();
+        let mut sources = Sources {
+            env,
+            keyring: |name: &str| match name {
+                "ntfy" => Ok("secret-ntfy".to_string()),
+                _ => Err(Error::Config(format!("no entry {}", name))),
+            },
+        };
+        expand_config(&mut config, &mut sources)?;
+        Ok(config)
+    }
+
+    #[test]
+    fn test_expand_env_and_keyring() {
+        let config = expand(
+            r#"
+            [general]
+            data_dir = "${HOME}/.kenseader"
+
+            [ai]
+            openai_api_key = "${OPENAI_KEY}"
+            provider = "openai"
+
+            [ui]
+            share_command = "echo $$ $${not_expanded}"
+
+            [[notifications.push]]
+            service = "ntfy"
+            token = "keyring: ntfy "
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config["general"]["data_dir"].as_str(), Some("/home/ann/.kenseader"));
+        assert_eq!(config["ai"]["openai_api_key"].as_str(), Some("sk-env"));
+        assert_eq!(config["ai"]["provider"].as_str(), Some("openai"));
+        assert_eq!(config["ui"]["share_command"].as_str(), Some("echo $$ ${not_expanded}"));
+        assert_eq!(config["notifications"]["push"][0]["token"].as_str(), Some("secret-ntfy"));
+    }
+
+    #[test]
+    fn test_expand_file() {
+        let path = std::env::temp_dir().join(format!("kenseader-secret-{}", uuid::Uuid::new_v4()));
+        std::fs::write(&path, "sk-file\n").unwrap();
+        let config = expand(&format!("key = \"file:{}\"", path.display())).unwrap();
+        assert_eq!(config["key"].as_str(), Some("sk-file"));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_expand_errors_name_the_key() {
+        let error = expand("[ai]\nopenai_api_key = \"${MISSING}\"").unwrap_err().to_string();
+        assert_eq!(error, "Configuration error: ai.openai_api_key: environment variable MISSING is not set");
+
+        let error = expand("[[notifications.push]]\ntoken = \"keyring:gone\"").unwrap_err().to_string();
+        assert_eq!(error, "Configuration error: notifications.push[0].token: no entry gone");
+
+        let error = expand("key = \"file:/nonexistent/kenseader\"").unwrap_err().to_string();
+        assert!(error.starts_with("Configuration error: key: cannot read /nonexistent/kenseader:"));
+
+        assert!(expand("key = \"${OPEN\"").is_err());
+    }
+}
@@ -0,0 +1,64 @@
+//! Shell commands built from user templates
+//!
+//! Configured commands (browsers, players, notifications, TTS) are run with
+//! `sh -c` after their `{placeholder}`s are filled with values that often
+//! come from feeds. Values are quoted, and filled in one pass over the
+//! template so placeholder text inside a value is never expanded.
+
+/// Quote a value for safe use in `sh -c`
+pub fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// Replace the `{name}` placeholders of a template with the shell-quoted
+/// values of `values`. Unknown placeholders are left as they are.
+pub fn expand(template: &str, values: &[(&str, &str)]) -> String {
+    let mut command = String::with_capacity(template.len());
+    let mut remaining = template;
+    while let Some(start) = remaining.find('{') {
+        command.push_str(&remaining[..start]);
+        let rest = &remaining[start..];
+        let value = values.iter().find_map(|(name, value)| {
+            let placeholder = rest.strip_prefix('{')?.strip_prefix(*name)?.strip_prefix('}')?;
+            Some((value, rest.len() - placeholder.len()))
+        });
+        match value {
+            Some((value, len)) => {
+                command.push_str(&quote(value));
+                remaining = &rest[len..];
+            }
+            None => {
+                command.push('{');
+                remaining = &rest[1..];
+            }
+        }
+    }
+    command.push_str(remaining);
+    command
+}
+
+/// Whether a template has the `{name}` placeholder
+pub fn has_placeholder(template: &str, name: &str) -> bool {
+    template.contains(&format!("{{{}}}", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_values_are_quoted_once() {
+        assert_eq!(quote("It's"), r"'It'\''s'");
+        assert_eq!(
+            expand("echo {url} {title} {other}", &[("url", "u"), ("title", "t")]),
+            "echo 'u' 't' {other}"
+        );
+        // Placeholders inside a value are not expanded, so the title stays quoted
+        assert_eq!(
+            expand("echo {url} {title}", &[("url", "http://evil/{title}"), ("title", "$(echo INJECTED)")]),
+            "echo 'http://evil/{title}' '$(echo INJECTED)'"
+        );
+        assert!(has_placeholder("mpv {url}", "url"));
+        assert!(!has_placeholder("mpv", "url"));
+    }
+}
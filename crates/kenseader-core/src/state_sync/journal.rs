@@ -0,0 +1,174 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+
+use crate::storage::Database;
+use crate::Result;
+
+/// Journal of one machine's state changes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateJournal {
+    pub device: String,
+    pub written_at: DateTime<Utc>,
+    pub entries: Vec<StateEntry>,
+}
+
+/// Latest read/saved state of one article
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StateEntry {
+    pub feed_url: String,
+    pub guid: String,
+    pub is_read: bool,
+    /// When the user read it; none for articles marked read in bulk, by
+    /// age or by relevance filtering, which stay out of the reading history
+    #[serde(default)]
+    pub read_at: Option<DateTime<Utc>>,
+    pub is_saved: bool,
+    pub changed_at: DateTime<Utc>,
+}
+
+/// Collect the state of every article the user changed on this machine
+pub async fn collect_changes(db: &Database) -> Result<Vec<StateEntry>> {
+    let pool = db.pool().clone();
+    let rows = db.query_with_retry(|| {
+        let pool = pool.clone();
+        async move {
+            sqlx::query(
+                r#"
+                SELECT f.url AS feed_url, a.guid, a.is_read, a.is_saved, a.state_changed_at,
+                       CASE WHEN a.id IN (SELECT article_id FROM filtered_articles WHERE rescued_at IS NULL)
+                            THEN NULL ELSE a.read_at END AS read_at
+                FROM articles a
+                JOIN feeds f ON f.id = a.feed_id
+                WHERE a.state_changed_at IS NOT NULL
+                ORDER BY a.state_changed_at
+                "#,
+            )
+            .fetch_all(&pool)
+            .await
+        }
+    })
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| StateEntry {
+            feed_url: row.get("feed_url"),
+            guid: row.get("guid"),
+            is_read: row.get::<i32, _>("is_read") != 0,
+            read_at: row.get("read_at"),
+            is_saved: row.get::<i32, _>("is_saved") != 0,
+            changed_at: row.get("state_changed_at"),
+        })
+        .collect())
+}
+
+/// Apply entries that are newer than the local state (last write wins).
+/// Returns the number of articles updated; unknown articles are skipped.
+pub async fn apply_changes(db: &Database, entries: &[StateEntry]) -> Result<u32> {
+    let mut tx = db.pool().begin().await?;
+    let mut applied = 0u32;
+
+    for entry in entries {
+        let result = sqlx::query(
+            r#"
+            UPDATE articles
+            SET is_read = ?,
+                read_at = ?,
+                is_saved = ?,
+                state_changed_at = ?
+            WHERE feed_id IN (SELECT id FROM feeds WHERE url = ?)
+              AND guid = ?
+              AND (state_changed_at IS NULL OR state_changed_at < ?)
+            "#,
+        )
+        .bind(entry.is_read as i32)
+        .bind(entry.read_at.filter(|_| entry.is_read))
+        .bind(entry.is_saved as i32)
+        .bind(entry.changed_at)
+        .bind(&entry.feed_url)
+        .bind(&entry.guid)
+        .bind(entry.changed_at)
+        .execute(&mut *tx)
+        .await?;
+        applied += result.rows_affected() as u32;
+    }
+
+    tx.commit().await?;
+    Ok(applied)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feed::NewArticle;
+    use crate::storage::{test_db_with_feed, ArticleRepository};
+    use chrono::Duration;
+
+    async fn db_with_article() -> (Database, uuid::Uuid) {
+        let (db, feed) = test_db_with_feed().await;
+        let article = ArticleRepository::new(&db)
+            .create(&NewArticle::for_test(feed.id, "guid-1"))
+            .await
+            .unwrap()
+            .unwrap();
+        (db, article.id)
+    }
+
+    #[tokio::test]
+    async fn test_changes_merge_last_write_wins() {
+        let (laptop, laptop_id) = db_with_article().await;
+        let (desktop, desktop_id) = db_with_article().await;
+
+        // Untouched articles are not journaled
+        assert!(collect_changes(&laptop).await.unwrap().is_empty());
+
+        ArticleRepository::new(&laptop).mark_read(laptop_id).await.unwrap();
+        let changes = collect_changes(&laptop).await.unwrap();
+        assert_eq!(changes.len(), 1);
+
+        assert_eq!(apply_changes(&desktop, &changes).await.unwrap(), 1);
+        let article = ArticleRepository::new(&desktop)
+            .find_by_id(desktop_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(article.is_read);
+
+        // Re-applying the same journal is a no-op
+        assert_eq!(apply_changes(&desktop, &changes).await.unwrap(), 0);
+
+        // An older change loses against the newer local state
+        let mut stale = changes[0].clone();
+        stale.is_read = false;
+        stale.changed_at -= Duration::hours(1);
+        assert_eq!(apply_changes(&desktop, &[stale]).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_read_time_is_applied_as_is() {
+        let (laptop, laptop_id) = db_with_article().await;
+        let (desktop, desktop_id) = db_with_article().await;
+        let laptop_repo = ArticleRepository::new(&laptop);
+        let desktop_repo = ArticleRepository::new(&desktop);
+
+        // Catching up in bulk leaves no read time, on either machine
+        let feed_id = laptop_repo.find_by_id(laptop_id).await.unwrap().unwrap().feed_id;
+        laptop_repo.mark_all_read(feed_id).await.unwrap();
+        let changes = collect_changes(&laptop).await.unwrap();
+        assert!(changes[0].is_read && changes[0].read_at.is_none());
+        apply_changes(&desktop, &changes).await.unwrap();
+        let article = desktop_repo.find_by_id(desktop_id).await.unwrap().unwrap();
+        assert!(article.is_read && article.read_at.is_none());
+        assert!(desktop_repo.list_recently_read(10).await.unwrap().is_empty());
+
+        // Reading it keeps the laptop's read time
+        laptop_repo.mark_unread(laptop_id).await.unwrap();
+        laptop_repo.mark_read(laptop_id).await.unwrap();
+        let changes = collect_changes(&laptop).await.unwrap();
+        apply_changes(&desktop, &changes).await.unwrap();
+        let read_at = laptop_repo.find_by_id(laptop_id).await.unwrap().unwrap().read_at;
+        assert!(read_at.is_some());
+        assert_eq!(desktop_repo.find_by_id(desktop_id).await.unwrap().unwrap().read_at, read_at);
+    }
+}
@@ -0,0 +1,146 @@
+//! Read/saved state sync between machines
+//!
+//! Each machine writes a journal of the articles whose read or saved state the
+//! user changed to a shared folder or WebDAV collection, and merges the
+//! journals of the other machines. Articles are matched by feed URL and GUID;
+//! conflicts are resolved by keeping the most recent change.
+
+mod journal;
+mod store;
+
+use std::path::PathBuf;
+
+use chrono::Utc;
+use tracing::{debug, warn};
+
+pub use journal::{apply_changes, collect_changes, StateEntry, StateJournal};
+pub use store::JournalStore;
+
+use crate::config::AppConfig;
+use crate::storage::Database;
+use crate::Result;
+
+/// Outcome of one sync run
+#[derive(Debug, Clone, Default)]
+pub struct StateSyncStats {
+    /// Entries written to this machine's journal
+    pub exported: u32,
+    /// Local articles updated from other journals
+    pub applied: u32,
+    /// Journals of other machines that were read
+    pub journals: u32,
+}
+
+/// Merge the journals of other machines, then publish this machine's journal
+pub async fn sync_state(db: &Database, config: &AppConfig) -> Result<StateSyncStats> {
+    let Some(store) = JournalStore::from_config(config)? else {
+        return Ok(StateSyncStats::default());
+    };
+    let device = device_name(config)?;
+    let own_name = JournalStore::journal_name(&device);
+    let mut stats = StateSyncStats::default();
+
+    for name in store.list().await? {
+        if name == own_name {
+            continue;
+        }
+        // Cloud clients may remove files or expose partially synced ones
+        let data = match store.read(&name).await {
+            Ok(data) => data,
+            Err(e) => {
+                warn!("Skipping unreadable state journal {}: {}", name, e);
+                continue;
+            }
+        };
+        let journal: StateJournal = match serde_json::from_slice(&data) {
+            Ok(journal) => journal,
+            Err(e) => {
+                warn!("Skipping unreadable state journal {}: {}", name, e);
+                continue;
+            }
+        };
+        stats.applied += apply_changes(db, &journal.entries).await?;
+        stats.journals += 1;
+    }
+
+    let entries = collect_changes(db).await?;
+    stats.exported = entries.len() as u32;
+    let journal = StateJournal {
+        device,
+        written_at: Utc::now(),
+        entries,
+    };
+    store.write(&own_name, &serde_json::to_vec(&journal)?).await?;
+
+    debug!(
+        "State sync: exported {}, applied {} from {} journals",
+        stats.exported, stats.applied, stats.journals
+    );
+    Ok(stats)
+}
+
+/// Name identifying this machine's journal.
+///
+/// Uses `state_sync.device_name`, or a random ID generated once and kept in
+/// the config directory (the data directory may itself be synced).
+pub fn device_name(config: &AppConfig) -> Result<String> {
+    if let Some(ref name) = config.state_sync.device_name {
+        return Ok(sanitize(name));
+    }
+
+    let path = device_id_path();
+    if let Ok(id) = std::fs::read_to_string(&path) {
+        let id = id.trim();
+        if !id.is_empty() {
+            return Ok(sanitize(id));
+        }
+    }
+
+    let id = uuid::Uuid::new_v4().to_string();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, &id)?;
+    Ok(id)
+}
+
+fn device_id_path() -> PathBuf {
+    AppConfig::config_path().with_file_name("device-id")
+}
+
+/// Keep device names usable as file names
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '-' })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_device_name() {
+        assert_eq!(sanitize("Ken's MacBook/Pro"), "Ken-s-MacBook-Pro");
+        assert_eq!(sanitize("desktop_01"), "desktop_01");
+    }
+
+    #[tokio::test]
+    async fn test_unreadable_journals_are_skipped() {
+        let dir = std::env::temp_dir().join(format!("kenseader-state-{}", uuid::Uuid::new_v4()));
+        let mut config = AppConfig::default();
+        config.state_sync.enabled = true;
+        config.state_sync.folder = Some(dir.clone());
+        config.state_sync.device_name = Some("me".to_string());
+        // A journal that can't be read, like one vanishing mid-sync
+        std::fs::create_dir_all(dir.join(JournalStore::journal_name("other"))).unwrap();
+        std::fs::write(dir.join(JournalStore::journal_name("half")), b"{\"device\":").unwrap();
+
+        let db = Database::new_in_memory().await.unwrap();
+        let stats = sync_state(&db, &config).await.unwrap();
+        assert_eq!(stats.journals, 0);
+        assert!(dir.join(JournalStore::journal_name("me")).exists());
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,207 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use reqwest::{Client, Method, StatusCode};
+
+use crate::config::AppConfig;
+use crate::{Error, Result};
+
+const JOURNAL_PREFIX: &str = "kenseader-state-";
+const JOURNAL_SUFFIX: &str = ".json";
+
+/// Where journals are exchanged
+pub enum JournalStore {
+    /// A folder kept in sync by an external tool
+    Folder(PathBuf),
+    /// A WebDAV collection
+    WebDav {
+        client: Client,
+        base_url: String,
+        username: Option<String>,
+        password: Option<String>,
+    },
+}
+
+impl JournalStore {
+    /// Build the store from config, or `None` if state sync is disabled
+    pub fn from_config(config: &AppConfig) -> Result<Option<Self>> {
+        let sync = &config.state_sync;
+        if !sync.enabled {
+            return Ok(None);
+        }
+
+        if let Some(folder) = config.state_sync_folder() {
+            return Ok(Some(Self::Folder(folder)));
+        }
+
+        let Some(ref url) = sync.webdav_url else {
+            return Err(Error::Config(
+                "state_sync is enabled but neither folder nor webdav_url is set".to_string(),
+            ));
+        };
+        let client = Client::builder()
+            .timeout(Duration::from_secs(config.sync.request_timeout_secs))
+            .build()?;
+        let base_url = if url.ends_with('/') {
+            url.clone()
+        } else {
+            format!("{}/", url)
+        };
+
+        Ok(Some(Self::WebDav {
+            client,
+            base_url,
+            username: sync.webdav_username.clone(),
+            password: sync.webdav_password.clone(),
+        }))
+    }
+
+    /// File name of a device's journal
+    pub fn journal_name(device: &str) -> String {
+        format!("{}{}{}", JOURNAL_PREFIX, device, JOURNAL_SUFFIX)
+    }
+
+    /// List all journal file names
+    pub async fn list(&self) -> Result<Vec<String>> {
+        let names = match self {
+            Self::Folder(dir) => {
+                if !dir.exists() {
+                    return Ok(Vec::new());
+                }
+                std::fs::read_dir(dir)?
+                    .filter_map(|entry| entry.ok())
+                    .filter_map(|entry| entry.file_name().into_string().ok())
+                    .collect::<Vec<_>>()
+            }
+            Self::WebDav { client, base_url, .. } => {
+                let response = self
+                    .authorize(client.request(Method::from_bytes(b"PROPFIND").unwrap(), base_url))
+                    .header("Depth", "1")
+                    .send()
+                    .await?;
+                if response.status() == StatusCode::NOT_FOUND {
+                    // First run: create the collection
+                    self.authorize(client.request(Method::from_bytes(b"MKCOL").unwrap(), base_url))
+                        .send()
+                        .await?
+                        .error_for_status()?;
+                    return Ok(Vec::new());
+                }
+                let body = response.error_for_status()?.text().await?;
+                parse_propfind_names(&body)?
+            }
+        };
+
+        Ok(names
+            .into_iter()
+            .filter(|name| name.starts_with(JOURNAL_PREFIX) && name.ends_with(JOURNAL_SUFFIX))
+            .collect())
+    }
+
+    /// Read a journal
+    pub async fn read(&self, name: &str) -> Result<Vec<u8>> {
+        match self {
+            Self::Folder(dir) => Ok(std::fs::read(dir.join(name))?),
+            Self::WebDav { client, base_url, .. } => {
+                let url = format!("{}{}", base_url, name);
+                let response = self.authorize(client.get(url)).send().await?;
+                Ok(response.error_for_status()?.bytes().await?.to_vec())
+            }
+        }
+    }
+
+    /// Replace a journal
+    pub async fn write(&self, name: &str, data: &[u8]) -> Result<()> {
+        match self {
+            Self::Folder(dir) => {
+                std::fs::create_dir_all(dir)?;
+                // Write then rename so sync tools never pick up a partial file
+                let tmp = dir.join(format!(".{}.tmp", name));
+                std::fs::write(&tmp, data)?;
+                std::fs::rename(&tmp, dir.join(name))?;
+            }
+            Self::WebDav { client, base_url, .. } => {
+                let url = format!("{}{}", base_url, name);
+                self.authorize(client.put(url).body(data.to_vec()))
+                    .send()
+                    .await?
+                    .error_for_status()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn authorize(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match self {
+            Self::WebDav {
+                username: Some(username),
+                password,
+                ..
+            } => request.basic_auth(username, password.as_ref()),
+            _ => request,
+        }
+    }
+}
+
+/// Extract the last path segment of every `href` in a PROPFIND response
+fn parse_propfind_names(body: &str) -> Result<Vec<String>> {
+    let mut reader = Reader::from_str(body);
+    let mut names = Vec::new();
+    let mut in_href = false;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) if e.local_name().as_ref() == b"href" => in_href = true,
+            Ok(Event::End(e)) if e.local_name().as_ref() == b"href" => in_href = false,
+            Ok(Event::Text(text)) if in_href => {
+                let href = text
+                    .unescape()
+                    .map_err(|e| Error::Other(format!("Invalid WebDAV response: {}", e)))?;
+                if let Some(name) = href.trim_end_matches('/').rsplit('/').next() {
+                    names.push(name.to_string());
+                }
+            }
+            Ok(Event::Eof) => break,
+            Ok(_) => {}
+            Err(e) => return Err(Error::Other(format!("Invalid WebDAV response: {}", e))),
+        }
+    }
+
+    Ok(names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_propfind_names() {
+        let body = r#"<?xml version="1.0"?>
+<d:multistatus xmlns:d="DAV:">
+  <d:response><d:href>/dav/kenseader/</d:href></d:response>
+  <d:response><d:href>/dav/kenseader/kenseader-state-laptop.json</d:href></d:response>
+</d:multistatus>"#;
+        assert_eq!(
+            parse_propfind_names(body).unwrap(),
+            vec!["kenseader".to_string(), "kenseader-state-laptop.json".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_folder_store_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("kenseader-sync-{}", uuid::Uuid::new_v4()));
+        let store = JournalStore::Folder(dir.clone());
+        assert!(store.list().await.unwrap().is_empty());
+
+        let name = JournalStore::journal_name("laptop");
+        store.write(&name, b"{}").await.unwrap();
+        std::fs::write(dir.join("unrelated.txt"), "x").unwrap();
+
+        assert_eq!(store.list().await.unwrap(), vec![name.clone()]);
+        assert_eq!(store.read(&name).await.unwrap(), b"{}");
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,756 @@
+//! Portable JSONL archive of all user data
+//!
+//! The first line is a header; every following line is one record tagged with
+//! its `type`. The format only depends on the serialized models, not on the
+//! SQLite schema, so archives can move between versions and machines.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::{BufRead, Write};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+use uuid::Uuid;
+
+use super::{latest_version, ArticleRepository, ArticleStyle, Database, FeedRepository};
+use crate::feed::{Article, Feed};
+use crate::{Error, Result};
+
+/// Archive format version, bumped when records are added or changed.
+/// Version 2 added behavior events, filtered articles, alerts, CVEs, exports
+/// to read-later services, the articles' state change times and where their
+/// tags came from.
+pub const ARCHIVE_FORMAT_VERSION: u32 = 2;
+
+/// One line of an archive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ArchiveRecord {
+    Header {
+        format_version: u32,
+        schema_version: u32,
+        exported_at: DateTime<Utc>,
+    },
+    Feed(Box<Feed>),
+    /// Article with its tags
+    Article(Box<ArchivedArticle>),
+    Style(ArticleStyle),
+    Preference(ArchivedPreference),
+    /// Behavior event the preferences are computed from
+    Event(ArchivedEvent),
+    Filtered(ArchivedFilter),
+    Alert(ArchivedAlert),
+    Cve(ArchivedCve),
+    ArticleCve(ArchivedArticleCve),
+    /// Saved article sent (or rejected) by a read-later service
+    Export(ArchivedExport),
+}
+
+/// An article with when its read/saved state last changed (for state sync)
+/// and the source of each of its tags
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedArticle {
+    #[serde(flatten)]
+    pub article: Article,
+    #[serde(default)]
+    pub state_changed_at: Option<DateTime<Utc>>,
+    /// Who added each tag ("ai" when missing)
+    #[serde(default)]
+    pub tag_sources: BTreeMap<String, String>,
+}
+
+/// A computed user preference as stored in the database
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedPreference {
+    pub preference_type: String,
+    pub preference_key: String,
+    pub weight: f64,
+    pub time_window: String,
+    pub computed_at: DateTime<Utc>,
+}
+
+/// A behavior event as stored in the database
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedEvent {
+    pub article_id: Option<Uuid>,
+    pub feed_id: Option<Uuid>,
+    pub event_type: String,
+    pub event_data: Option<String>,
+    pub reading_duration_ms: Option<i64>,
+    pub scroll_depth_percent: Option<i64>,
+    pub context_time_of_day: Option<String>,
+    pub context_day_of_week: Option<i64>,
+    pub context_network_type: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// An article relevance filtering marked read, and whether it was rescued
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedFilter {
+    pub article_id: Uuid,
+    pub score: f64,
+    pub threshold: f64,
+    pub filtered_at: DateTime<Utc>,
+    pub rescued_at: Option<DateTime<Utc>>,
+}
+
+/// A keyword alert raised by an article
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedAlert {
+    pub article_id: Uuid,
+    pub keyword: String,
+    pub alerted_at: DateTime<Utc>,
+}
+
+/// A looked up CVE
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedCve {
+    pub cve_id: String,
+    pub score: Option<f64>,
+    pub severity: Option<String>,
+    pub looked_up_at: Option<DateTime<Utc>>,
+}
+
+/// A CVE mentioned by an article
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedArticleCve {
+    pub article_id: Uuid,
+    pub cve_id: String,
+}
+
+/// A saved article sent to a read-later destination
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedExport {
+    pub article_id: Uuid,
+    pub destination: String,
+    pub exported_at: DateTime<Utc>,
+    /// Why the service rejected it
+    pub error: Option<String>,
+}
+
+/// Number of records written or imported per kind
+#[derive(Debug, Clone, Default)]
+pub struct ArchiveStats {
+    pub feeds: u32,
+    pub articles: u32,
+    pub styles: u32,
+    pub preferences: u32,
+    pub events: u32,
+}
+
+/// Write all feeds, articles (with tags), styles, preferences, behavior
+/// events, filtered articles, alerts, CVEs and exports to `writer`
+pub async fn export_archive<W: Write>(db: &Database, mut writer: W) -> Result<ArchiveStats> {
+    let mut stats = ArchiveStats::default();
+
+    write_record(
+        &mut writer,
+        &ArchiveRecord::Header {
+            format_version: ARCHIVE_FORMAT_VERSION,
+            schema_version: latest_version(),
+            exported_at: Utc::now(),
+        },
+    )?;
+
+    // Tags for all articles in one query
+    let mut tags: HashMap<String, BTreeMap<String, String>> = HashMap::new();
+    let rows = sqlx::query("SELECT article_id, tag, source FROM article_tags")
+        .fetch_all(db.pool())
+        .await?;
+    for row in rows {
+        tags.entry(row.get("article_id")).or_default().insert(row.get("tag"), row.get("source"));
+    }
+
+    let state_changes: HashMap<String, DateTime<Utc>> =
+        sqlx::query_as("SELECT id, state_changed_at FROM articles WHERE state_changed_at IS NOT NULL")
+            .fetch_all(db.pool())
+            .await?
+            .into_iter()
+            .collect();
+
+    let article_repo = ArticleRepository::new(db);
+    for feed in FeedRepository::new(db).list_all().await? {
+        let feed_id = feed.id;
+        write_record(&mut writer, &ArchiveRecord::Feed(Box::new(feed)))?;
+        stats.feeds += 1;
+
+        for mut article in article_repo.list_by_feed(feed_id, false).await? {
+            let id = article.id.to_string();
+            let tag_sources = tags.remove(&id).unwrap_or_default();
+            article.tags = tag_sources.keys().cloned().collect();
+            let state_changed_at = state_changes.get(&id).copied();
+            write_record(
+                &mut writer,
+                &ArchiveRecord::Article(Box::new(ArchivedArticle { article, state_changed_at, tag_sources })),
+            )?;
+            stats.articles += 1;
+        }
+    }
+
+    let rows = sqlx::query(
+        "SELECT article_id, style_type, tone, length_category, computed_at FROM article_styles",
+    )
+    .fetch_all(db.pool())
+    .await?;
+    for row in rows {
+        let id_str: String = row.get("article_id");
+        let style = ArticleStyle {
+            article_id: Uuid::parse_str(&id_str).unwrap_or_default(),
+            style_type: row.get("style_type"),
+            tone: row.get("tone"),
+            length_category: row.get("length_category"),
+            computed_at: row.get("computed_at"),
+        };
+        write_record(&mut writer, &ArchiveRecord::Style(style))?;
+        stats.styles += 1;
+    }
+
+    let rows = sqlx::query(
+        "SELECT preference_type, preference_key, weight, time_window, computed_at FROM user_preferences",
+    )
+    .fetch_all(db.pool())
+    .await?;
+    for row in rows {
+        let preference = ArchivedPreference {
+            preference_type: row.get("preference_type"),
+            preference_key: row.get("preference_key"),
+            weight: row.get("weight"),
+            time_window: row.get("time_window"),
+            computed_at: row.get("computed_at"),
+        };
+        write_record(&mut writer, &ArchiveRecord::Preference(preference))?;
+        stats.preferences += 1;
+    }
+
+    let rows = sqlx::query(
+        r#"
+        SELECT article_id, feed_id, event_type, event_data, reading_duration_ms, scroll_depth_percent,
+               context_time_of_day, context_day_of_week, context_network_type, created_at
+        FROM behavior_events
+        ORDER BY id
+        "#,
+    )
+    .fetch_all(db.pool())
+    .await?;
+    for row in rows {
+        let event = ArchivedEvent {
+            article_id: parse_id(row.get("article_id")),
+            feed_id: parse_id(row.get("feed_id")),
+            event_type: row.get("event_type"),
+            event_data: row.get("event_data"),
+            reading_duration_ms: row.get("reading_duration_ms"),
+            scroll_depth_percent: row.get("scroll_depth_percent"),
+            context_time_of_day: row.get("context_time_of_day"),
+            context_day_of_week: row.get("context_day_of_week"),
+            context_network_type: row.get("context_network_type"),
+            created_at: row.get("created_at"),
+        };
+        write_record(&mut writer, &ArchiveRecord::Event(event))?;
+        stats.events += 1;
+    }
+
+    let rows = sqlx::query("SELECT article_id, score, threshold, filtered_at, rescued_at FROM filtered_articles")
+        .fetch_all(db.pool())
+        .await?;
+    for row in rows {
+        let filter = ArchivedFilter {
+            article_id: parse_id(row.get("article_id")).unwrap_or_default(),
+            score: row.get("score"),
+            threshold: row.get("threshold"),
+            filtered_at: row.get("filtered_at"),
+            rescued_at: row.get("rescued_at"),
+        };
+        write_record(&mut writer, &ArchiveRecord::Filtered(filter))?;
+    }
+
+    let rows = sqlx::query("SELECT article_id, keyword, alerted_at FROM article_alerts")
+        .fetch_all(db.pool())
+        .await?;
+    for row in rows {
+        let alert = ArchivedAlert {
+            article_id: parse_id(row.get("article_id")).unwrap_or_default(),
+            keyword: row.get("keyword"),
+            alerted_at: row.get("alerted_at"),
+        };
+        write_record(&mut writer, &ArchiveRecord::Alert(alert))?;
+    }
+
+    let rows = sqlx::query("SELECT cve_id, score, severity, looked_up_at FROM cves")
+        .fetch_all(db.pool())
+        .await?;
+    for row in rows {
+        let cve = ArchivedCve {
+            cve_id: row.get("cve_id"),
+            score: row.get("score"),
+            severity: row.get("severity"),
+            looked_up_at: row.get("looked_up_at"),
+        };
+        write_record(&mut writer, &ArchiveRecord::Cve(cve))?;
+    }
+
+    let rows = sqlx::query("SELECT article_id, cve_id FROM article_cves")
+        .fetch_all(db.pool())
+        .await?;
+    for row in rows {
+        let mention = ArchivedArticleCve {
+            article_id: parse_id(row.get("article_id")).unwrap_or_default(),
+            cve_id: row.get("cve_id"),
+        };
+        write_record(&mut writer, &ArchiveRecord::ArticleCve(mention))?;
+    }
+
+    let rows = sqlx::query("SELECT article_id, destination, exported_at, error FROM article_exports")
+        .fetch_all(db.pool())
+        .await?;
+    for row in rows {
+        let export = ArchivedExport {
+            article_id: parse_id(row.get("article_id")).unwrap_or_default(),
+            destination: row.get("destination"),
+            exported_at: row.get("exported_at"),
+            error: row.get("error"),
+        };
+        write_record(&mut writer, &ArchiveRecord::Export(export))?;
+    }
+
+    writer.flush()?;
+    Ok(stats)
+}
+
+/// Import an archive in a single transaction.
+///
+/// Feeds already subscribed (same URL) and articles already stored (same ID
+/// or feed + GUID) are kept as they are; archived articles of such feeds are
+/// attached to the existing subscription. Behavior events are added unless
+/// an identical one is already stored, so importing twice adds nothing.
+/// Exports are recorded for existing articles too, so saved articles aren't
+/// sent to a read-later service again from the new machine.
+pub async fn import_archive<R: BufRead>(db: &Database, reader: R) -> Result<ArchiveStats> {
+    let mut stats = ArchiveStats::default();
+    // Archived feed ID -> feed ID in this database
+    let mut feed_ids: HashMap<Uuid, String> = HashMap::new();
+    // Archived article ID -> article ID in this database
+    let mut article_ids: HashMap<Uuid, String> = HashMap::new();
+    let mut imported_articles: HashSet<Uuid> = HashSet::new();
+    let mut seen_header = false;
+
+    let mut tx = db.pool().begin().await?;
+
+    for (idx, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record: ArchiveRecord = serde_json::from_str(&line).map_err(|e| {
+            Error::Other(format!("Invalid archive record on line {}: {}", idx + 1, e))
+        })?;
+
+        match record {
+            ArchiveRecord::Header { format_version, .. } => {
+                if format_version > ARCHIVE_FORMAT_VERSION {
+                    return Err(Error::Other(format!(
+                        "Archive format version {} is newer than this build supports ({})",
+                        format_version, ARCHIVE_FORMAT_VERSION
+                    )));
+                }
+                seen_header = true;
+            }
+            _ if !seen_header => {
+                return Err(Error::Other(
+                    "Not a kenseader archive (missing header line)".to_string(),
+                ));
+            }
+            ArchiveRecord::Feed(feed) => {
+                let inserted = sqlx::query(
+                    r#"
+                    INSERT INTO feeds (id, url, local_name, title, description, site_url, icon_url,
+                                       last_fetched_at, fetch_error, created_at, updated_at,
+                                       category, paused, refresh_interval_secs, auto_read_days,
+                                       pinned, position)
+                    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?,
+                            (SELECT COALESCE(MAX(position), -1) + 1 FROM feeds) + ?)
+                    ON CONFLICT(url) DO NOTHING
+                    "#,
+                )
+                .bind(feed.id.to_string())
+                .bind(&feed.url)
+                .bind(&feed.local_name)
+                .bind(&feed.title)
+                .bind(&feed.description)
+                .bind(&feed.site_url)
+                .bind(&feed.icon_url)
+                .bind(feed.last_fetched_at)
+                .bind(&feed.fetch_error)
+                .bind(feed.created_at)
+                .bind(feed.updated_at)
+                .bind(&feed.category)
+                .bind(feed.paused)
+                .bind(feed.refresh_interval_secs.map(|s| s as i64))
+                .bind(feed.auto_read_days.map(i64::from))
+                .bind(feed.pinned)
+                .bind(feed.position)
+                .execute(&mut *tx)
+                .await?
+                .rows_affected();
+                stats.feeds += inserted as u32;
+
+                let (existing_id,): (String,) = sqlx::query_as("SELECT id FROM feeds WHERE url = ?")
+                    .bind(&feed.url)
+                    .fetch_one(&mut *tx)
+                    .await?;
+                feed_ids.insert(feed.id, existing_id);
+            }
+            ArchiveRecord::Article(archived) => {
+                let ArchivedArticle { article, state_changed_at, tag_sources } = *archived;
+                let Some(feed_id) = feed_ids.get(&article.feed_id) else {
+                    continue;
+                };
+                let media = article.media.as_ref();
+                let inserted = sqlx::query(
+                    r#"
+                    INSERT OR IGNORE INTO articles (
+                        id, feed_id, guid, url, title, author, content, content_text,
+                        summary, summary_generated_at, published_at, fetched_at,
+                        is_read, read_at, is_saved, created_at, image_url, relevance_score,
+                        media_url, media_type, media_length, media_duration, media_thumbnail_url,
+                        comments_url, updated_at, state_changed_at
+                    )
+                    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                    "#,
+                )
+                .bind(article.id.to_string())
+                .bind(feed_id)
+                .bind(&article.guid)
+                .bind(&article.url)
+                .bind(&article.title)
+                .bind(&article.author)
+                .bind(&article.content)
+                .bind(&article.content_text)
+                .bind(&article.summary)
+                .bind(article.summary_generated_at)
+                .bind(article.published_at)
+                .bind(article.fetched_at)
+                .bind(article.is_read as i32)
+                .bind(article.read_at)
+                .bind(article.is_saved as i32)
+                .bind(article.created_at)
+                .bind(&article.image_url)
+                .bind(article.relevance_score)
+                .bind(media.map(|m| m.url.clone()))
+                .bind(media.and_then(|m| m.mime_type.clone()))
+                .bind(media.and_then(|m| m.length).map(|l| l as i64))
+                .bind(media.and_then(|m| m.duration_secs).map(|d| d as i64))
+                .bind(media.and_then(|m| m.thumbnail_url.clone()))
+                .bind(&article.comments_url)
+                .bind(article.updated_at)
+                .bind(state_changed_at)
+                .execute(&mut *tx)
+                .await?
+                .rows_affected();
+
+                let local_id: Option<(String,)> =
+                    sqlx::query_as("SELECT id FROM articles WHERE id = ? OR (feed_id = ? AND guid = ?)")
+                        .bind(article.id.to_string())
+                        .bind(feed_id)
+                        .bind(&article.guid)
+                        .fetch_optional(&mut *tx)
+                        .await?;
+                if let Some((local_id,)) = local_id {
+                    article_ids.insert(article.id, local_id);
+                }
+                if inserted == 0 {
+                    continue;
+                }
+
+                for tag in &article.tags {
+                    let source = tag_sources.get(tag).map_or("ai", String::as_str);
+                    sqlx::query("INSERT OR IGNORE INTO article_tags (article_id, tag, source) VALUES (?, ?, ?)")
+                        .bind(article.id.to_string())
+                        .bind(tag)
+                        .bind(source)
+                        .execute(&mut *tx)
+                        .await?;
+                }
+                imported_articles.insert(article.id);
+                stats.articles += 1;
+            }
+            ArchiveRecord::Style(style) => {
+                if !imported_articles.contains(&style.article_id) {
+                    continue;
+                }
+                sqlx::query(
+                    r#"
+                    INSERT OR IGNORE INTO article_styles (article_id, style_type, tone, length_category, computed_at)
+                    VALUES (?, ?, ?, ?, ?)
+                    "#,
+                )
+                .bind(style.article_id.to_string())
+                .bind(&style.style_type)
+                .bind(&style.tone)
+                .bind(&style.length_category)
+                .bind(style.computed_at)
+                .execute(&mut *tx)
+                .await?;
+                stats.styles += 1;
+            }
+            ArchiveRecord::Preference(mut preference) => {
+                // Feed affinities are keyed by the feed's ID
+                if preference.preference_type == "feed_affinity" {
+                    if let Some(feed_id) = parse_id(Some(preference.preference_key.clone()))
+                        .and_then(|id| feed_ids.get(&id))
+                    {
+                        preference.preference_key = feed_id.clone();
+                    }
+                }
+                sqlx::query(
+                    r#"
+                    INSERT OR REPLACE INTO user_preferences
+                    (preference_type, preference_key, weight, time_window, computed_at)
+                    VALUES (?, ?, ?, ?, ?)
+                    "#,
+                )
+                .bind(&preference.preference_type)
+                .bind(&preference.preference_key)
+                .bind(preference.weight)
+                .bind(&preference.time_window)
+                .bind(preference.computed_at)
+                .execute(&mut *tx)
+                .await?;
+                stats.preferences += 1;
+            }
+            ArchiveRecord::Event(event) => {
+                let article_id = event.article_id.and_then(|id| article_ids.get(&id));
+                let feed_id = event.feed_id.and_then(|id| feed_ids.get(&id));
+                let inserted = sqlx::query(
+                    r#"
+                    INSERT INTO behavior_events (
+                        article_id, feed_id, event_type, event_data, reading_duration_ms, scroll_depth_percent,
+                        context_time_of_day, context_day_of_week, context_network_type, created_at
+                    )
+                    SELECT ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10
+                    WHERE NOT EXISTS (
+                        SELECT 1 FROM behavior_events
+                        WHERE article_id IS ?1 AND feed_id IS ?2 AND event_type = ?3 AND created_at = ?10
+                    )
+                    "#,
+                )
+                .bind(article_id)
+                .bind(feed_id)
+                .bind(&event.event_type)
+                .bind(&event.event_data)
+                .bind(event.reading_duration_ms)
+                .bind(event.scroll_depth_percent)
+                .bind(&event.context_time_of_day)
+                .bind(event.context_day_of_week)
+                .bind(&event.context_network_type)
+                .bind(event.created_at)
+                .execute(&mut *tx)
+                .await?
+                .rows_affected();
+                stats.events += inserted as u32;
+            }
+            ArchiveRecord::Filtered(filter) => {
+                if !imported_articles.contains(&filter.article_id) {
+                    continue;
+                }
+                sqlx::query(
+                    r#"
+                    INSERT OR IGNORE INTO filtered_articles (article_id, score, threshold, filtered_at, rescued_at)
+                    VALUES (?, ?, ?, ?, ?)
+                    "#,
+                )
+                .bind(filter.article_id.to_string())
+                .bind(filter.score)
+                .bind(filter.threshold)
+                .bind(filter.filtered_at)
+                .bind(filter.rescued_at)
+                .execute(&mut *tx)
+                .await?;
+            }
+            ArchiveRecord::Alert(alert) => {
+                if !imported_articles.contains(&alert.article_id) {
+                    continue;
+                }
+                sqlx::query("INSERT OR IGNORE INTO article_alerts (article_id, keyword, alerted_at) VALUES (?, ?, ?)")
+                    .bind(alert.article_id.to_string())
+                    .bind(&alert.keyword)
+                    .bind(alert.alerted_at)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            ArchiveRecord::Cve(cve) => {
+                // Keep lookups already made here, fill in ones still pending
+                sqlx::query(
+                    r#"
+                    INSERT INTO cves (cve_id, score, severity, looked_up_at) VALUES (?, ?, ?, ?)
+                    ON CONFLICT(cve_id) DO UPDATE SET
+                        score = excluded.score, severity = excluded.severity, looked_up_at = excluded.looked_up_at
+                    WHERE cves.looked_up_at IS NULL
+                    "#,
+                )
+                .bind(&cve.cve_id)
+                .bind(cve.score)
+                .bind(&cve.severity)
+                .bind(cve.looked_up_at)
+                .execute(&mut *tx)
+                .await?;
+            }
+            ArchiveRecord::ArticleCve(mention) => {
+                if !imported_articles.contains(&mention.article_id) {
+                    continue;
+                }
+                sqlx::query("INSERT OR IGNORE INTO article_cves (article_id, cve_id) VALUES (?, ?)")
+                    .bind(mention.article_id.to_string())
+                    .bind(&mention.cve_id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            ArchiveRecord::Export(export) => {
+                let Some(article_id) = article_ids.get(&export.article_id) else {
+                    continue;
+                };
+                sqlx::query(
+                    r#"
+                    INSERT OR IGNORE INTO article_exports (article_id, destination, exported_at, error)
+                    VALUES (?, ?, ?, ?)
+                    "#,
+                )
+                .bind(article_id)
+                .bind(&export.destination)
+                .bind(export.exported_at)
+                .bind(&export.error)
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+    }
+
+    tx.commit().await?;
+    Ok(stats)
+}
+
+/// Parse an ID column that may be empty
+fn parse_id(id: Option<String>) -> Option<Uuid> {
+    id.and_then(|id| Uuid::parse_str(&id).ok())
+}
+
+fn write_record<W: Write>(writer: &mut W, record: &ArchiveRecord) -> Result<()> {
+    serde_json::to_writer(&mut *writer, record)?;
+    writer.write_all(b"\n")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feed::NewArticle;
+    use crate::storage::test_db_with_feed;
+
+    #[tokio::test]
+    async fn test_export_import_roundtrip() {
+        let (source, feed) = test_db_with_feed().await;
+        let article_repo = ArticleRepository::new(&source);
+        let article = article_repo
+            .create(&NewArticle {
+                url: Some("https://example.com/1".to_string()),
+                content: Some("<p>Body</p>".to_string()),
+                content_text: Some("Body".to_string()),
+                ..NewArticle::for_test(feed.id, "guid-1")
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        article_repo.mark_read(article.id).await.unwrap();
+        article_repo
+            .add_tags(article.id, &["rust".to_string()], "ai")
+            .await
+            .unwrap();
+        article_repo
+            .add_tags(article.id, &["later".to_string()], "user")
+            .await
+            .unwrap();
+
+        let mut archive = Vec::new();
+        let exported = export_archive(&source, &mut archive).await.unwrap();
+        assert_eq!((exported.feeds, exported.articles), (1, 1));
+
+        let target = Database::new_in_memory().await.unwrap();
+        let imported = import_archive(&target, archive.as_slice()).await.unwrap();
+        assert_eq!((imported.feeds, imported.articles), (1, 1));
+
+        let restored = ArticleRepository::new(&target)
+            .find_by_id(article.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(restored.is_read);
+        let tags: Vec<(String, String)> =
+            sqlx::query_as("SELECT tag, source FROM article_tags WHERE article_id = ? ORDER BY tag")
+                .bind(article.id.to_string())
+                .fetch_all(target.pool())
+                .await
+                .unwrap();
+        let tag = |tag: &str, source: &str| (tag.to_string(), source.to_string());
+        assert_eq!(tags, [tag("later", "user"), tag("rust", "ai")]);
+
+        // Importing again keeps existing data and adds nothing
+        let again = import_archive(&target, archive.as_slice()).await.unwrap();
+        assert_eq!((again.feeds, again.articles), (0, 0));
+    }
+
+    #[tokio::test]
+    async fn test_user_state_survives_import() {
+        use crate::profile::{BehaviorTracker, ProfileAnalyzer, TimeWindow};
+
+        let (source, feed) = test_db_with_feed().await;
+        let article_repo = ArticleRepository::new(&source);
+        let mut ids = Vec::new();
+        for i in 0..2 {
+            let article = NewArticle {
+                url: Some(format!("https://example.com/{}", i)),
+                ..NewArticle::for_test(feed.id, &format!("guid-{}", i))
+            };
+            ids.push(article_repo.create(&article).await.unwrap().unwrap().id);
+        }
+        article_repo.add_tags(ids[0], &["rust".to_string()], "ai").await.unwrap();
+        article_repo.mark_read(ids[0]).await.unwrap();
+        BehaviorTracker::new(&source).record_click(ids[0], feed.id).await.unwrap();
+        article_repo.mark_filtered(ids[1], 0.1, 0.3).await.unwrap();
+        article_repo.link_cves(ids[1], &["CVE-2024-0001".to_string()]).await.unwrap();
+        article_repo.toggle_saved(ids[0]).await.unwrap();
+        article_repo.mark_exported(ids[0], "readwise").await.unwrap();
+        ProfileAnalyzer::new(&source).compute_preferences().await.unwrap();
+
+        let mut archive = Vec::new();
+        let exported = export_archive(&source, &mut archive).await.unwrap();
+        assert_eq!(exported.events, 1);
+
+        let target = Database::new_in_memory().await.unwrap();
+        let imported = import_archive(&target, archive.as_slice()).await.unwrap();
+        assert_eq!(imported.events, 1);
+
+        // Preferences computed on the new machine come from the imported events
+        let analyzer = ProfileAnalyzer::new(&target);
+        analyzer.compute_preferences().await.unwrap();
+        assert_eq!(analyzer.get_top_tags(TimeWindow::Last30Days, 10).await.unwrap(), ["rust"]);
+
+        let target_repo = ArticleRepository::new(&target);
+        assert_eq!(target_repo.list_filtered(10).await.unwrap()[0].article.id, ids[1]);
+        assert_eq!(target_repo.list_advisories(0.0, 10).await.unwrap().len(), 1);
+        let state_changed = "SELECT COUNT(*) FROM articles WHERE state_changed_at IS NOT NULL";
+        let (changed,): (i64,) = sqlx::query_as(state_changed).fetch_one(target.pool()).await.unwrap();
+        assert_eq!(changed, 2);
+        // Saved articles already sent aren't sent again from here
+        assert!(target_repo.list_saved_unexported("readwise", 10).await.unwrap().is_empty());
+
+        // Importing again doesn't duplicate events
+        let again = import_archive(&target, archive.as_slice()).await.unwrap();
+        assert_eq!(again.events, 0);
+    }
+
+    #[tokio::test]
+    async fn test_import_rejects_missing_header() {
+        let db = Database::new_in_memory().await.unwrap();
+        let line = r#"{"type":"preference","preference_type":"tag_affinity","preference_key":"rust","weight":1.0,"time_window":"last_1_day","computed_at":"2024-01-01T00:00:00Z"}"#;
+        assert!(import_archive(&db, line.as_bytes()).await.is_err());
+    }
+}
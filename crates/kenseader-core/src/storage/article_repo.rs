@@ -1,10 +1,14 @@
+use std::collections::HashMap;
+
 use chrono::{DateTime, Duration, Utc};
 use sqlx::FromRow;
 use uuid::Uuid;
 
-use super::retry::{execute_with_retry, query_with_retry};
 use super::Database;
-use crate::feed::{Article, NewArticle};
+use crate::feed::{
+    Advisory, AdvisoryArticle, AlertedArticle, Article, ArticleAlert, ArticleListing, ArticleMedia, ArticleOutcome, FilterReason,
+    FilteredArticle, NewArticle, PageCursor, ReadStateChange,
+};
 use crate::Result;
 
 /// Repository for article CRUD operations
@@ -32,6 +36,30 @@ struct ArticleRow {
     created_at: DateTime<Utc>,
     image_url: Option<String>,
     relevance_score: Option<f64>,
+    media_url: Option<String>,
+    media_type: Option<String>,
+    media_length: Option<i64>,
+    media_duration: Option<i64>,
+    media_thumbnail_url: Option<String>,
+    comments_url: Option<String>,
+    updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(FromRow)]
+struct FilteredRow {
+    #[sqlx(flatten)]
+    article: ArticleRow,
+    filter_score: f64,
+    filter_threshold: f64,
+    filtered_at: DateTime<Utc>,
+}
+
+#[derive(FromRow)]
+struct AlertedRow {
+    #[sqlx(flatten)]
+    article: ArticleRow,
+    keyword: String,
+    alerted_at: DateTime<Utc>,
 }
 
 impl From<ArticleRow> for Article {
@@ -56,6 +84,15 @@ impl From<ArticleRow> for Article {
             image_url: row.image_url,
             relevance_score: row.relevance_score,
             tags: Vec::new(),
+            media: row.media_url.map(|url| ArticleMedia {
+                url,
+                mime_type: row.media_type,
+                length: row.media_length.map(|l| l as u64),
+                duration_secs: row.media_duration.map(|d| d as u64),
+                thumbnail_url: row.media_thumbnail_url,
+            }),
+            comments_url: row.comments_url,
+            updated_at: row.updated_at,
         }
     }
 }
@@ -66,9 +103,22 @@ impl<'a> ArticleRepository<'a> {
     }
 
     /// Create a new article (with deduplication by feed_id + guid)
+    ///
+    /// Existing articles are only rewritten when their content hash changes,
+    /// which also records `updated_at`. Unchanged articles still get their
+    /// `fetched_at` bumped once a day so retention cleanup doesn't delete
+    /// (and the next refresh re-insert) articles that are still in the feed.
     pub async fn create(&self, new_article: &NewArticle) -> Result<Option<Article>> {
         let id = Uuid::new_v4();
         let now = Utc::now();
+        let content_hash = new_article.content_hash();
+
+        // Feeds with unstable GUIDs (e.g. after a site migration) re-publish the
+        // same article under a new GUID; dedup those by canonical URL + title
+        if let Some(existing_guid) = self.find_guid_by_url(new_article).await? {
+            self.update_existing(new_article, &existing_guid, &content_hash).await?;
+            return Ok(None);
+        }
         let pool = self.db.pool().clone();
         let id_str = id.to_string();
         let feed_id_str = new_article.feed_id.to_string();
@@ -80,10 +130,12 @@ impl<'a> ArticleRepository<'a> {
         let content_text = new_article.content_text.clone();
         let published_at = new_article.published_at;
         let image_url = new_article.image_url.clone();
+        let media = new_article.media.clone();
+        let comments_url = new_article.comments_url.clone();
 
         // Try to insert, ignore if duplicate (feed_id, guid)
         // Use query_with_retry to get the result for checking rows_affected
-        let result = query_with_retry(|| {
+        let result = self.db.query_with_retry(|| {
             let pool = pool.clone();
             let id_str = id_str.clone();
             let feed_id_str = feed_id_str.clone();
@@ -94,12 +146,16 @@ impl<'a> ArticleRepository<'a> {
             let content = content.clone();
             let content_text = content_text.clone();
             let image_url = image_url.clone();
+            let media = media.clone();
+            let comments_url = comments_url.clone();
+            let content_hash = content_hash.clone();
             async move {
                 sqlx::query(
                     r#"
                     INSERT OR IGNORE INTO articles
-                    (id, feed_id, guid, url, title, author, content, content_text, published_at, fetched_at, created_at, image_url)
-                    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                    (id, feed_id, guid, url, title, author, content, content_text, published_at, fetched_at, created_at, image_url,
+                     media_url, media_type, media_length, media_duration, media_thumbnail_url, comments_url, content_hash)
+                    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
                     "#,
                 )
                 .bind(&id_str)
@@ -114,6 +170,13 @@ impl<'a> ArticleRepository<'a> {
                 .bind(now)
                 .bind(now)
                 .bind(&image_url)
+                .bind(media.as_ref().map(|m| m.url.clone()))
+                .bind(media.as_ref().and_then(|m| m.mime_type.clone()))
+                .bind(media.as_ref().and_then(|m| m.length).map(|l| l as i64))
+                .bind(media.as_ref().and_then(|m| m.duration_secs).map(|d| d as i64))
+                .bind(media.as_ref().and_then(|m| m.thumbnail_url.clone()))
+                .bind(&comments_url)
+                .bind(&content_hash)
                 .execute(&pool)
                 .await
             }
@@ -123,71 +186,186 @@ impl<'a> ArticleRepository<'a> {
         if result.rows_affected() > 0 {
             self.find_by_id(id).await
         } else {
-            // Update existing article with retry protection
-            let pool = self.db.pool().clone();
-            let url = new_article.url.clone();
-            let title = new_article.title.clone();
-            let author = new_article.author.clone();
-            let content = new_article.content.clone();
-            let content_text = new_article.content_text.clone();
-            let image_url = new_article.image_url.clone();
-            let feed_id_str = new_article.feed_id.to_string();
-            let guid = new_article.guid.clone();
-
-            execute_with_retry(|| {
-                let pool = pool.clone();
-                let url = url.clone();
-                let title = title.clone();
-                let author = author.clone();
-                let content = content.clone();
-                let content_text = content_text.clone();
-                let image_url = image_url.clone();
-                let feed_id_str = feed_id_str.clone();
-                let guid = guid.clone();
-                async move {
-                    sqlx::query(
-                        r#"
-                        UPDATE articles
-                        SET url = COALESCE(?, url),
-                            title = ?,
-                            author = COALESCE(?, author),
-                            content = COALESCE(?, content),
-                            content_text = COALESCE(?, content_text),
-                            published_at = COALESCE(?, published_at),
-                            fetched_at = ?,
-                            image_url = COALESCE(?, image_url)
-                        WHERE feed_id = ? AND guid = ?
-                        "#,
-                    )
-                    .bind(&url)
-                    .bind(&title)
-                    .bind(&author)
-                    .bind(&content)
-                    .bind(&content_text)
-                    .bind(published_at)
-                    .bind(now)
-                    .bind(&image_url)
+            // Article already exists
+            self.update_existing(new_article, &new_article.guid, &content_hash).await?;
+            Ok(None)
+        }
+    }
+
+    /// What `create` would do with an article, without writing anything
+    /// (items repeated within a feed are left to the caller)
+    pub async fn outcome(&self, new_article: &NewArticle) -> Result<ArticleOutcome> {
+        if self.find_guid_by_url(new_article).await?.is_some() {
+            return Ok(ArticleOutcome::Republished);
+        }
+        let pool = self.db.pool().clone();
+        let feed_id_str = new_article.feed_id.to_string();
+        let guid = new_article.guid.clone();
+
+        let row: Option<(Option<String>,)> = self.db.query_with_retry(|| {
+            let pool = pool.clone();
+            let feed_id_str = feed_id_str.clone();
+            let guid = guid.clone();
+            async move {
+                sqlx::query_as("SELECT content_hash FROM articles WHERE feed_id = ? AND guid = ?")
                     .bind(&feed_id_str)
                     .bind(&guid)
-                    .execute(&pool)
+                    .fetch_optional(&pool)
                     .await
-                    .map(|_| ())
-                }
-            })
-            .await?;
+            }
+        })
+        .await?;
 
-            // Article already exists
-            Ok(None)
-        }
+        Ok(match row {
+            None => ArticleOutcome::New,
+            Some((Some(hash),)) if hash == new_article.content_hash() => ArticleOutcome::Unchanged,
+            Some(_) => ArticleOutcome::Updated,
+        })
+    }
+
+    /// Find the GUID of an existing article in the same feed with the same URL
+    /// and title but a different GUID
+    async fn find_guid_by_url(&self, new_article: &NewArticle) -> Result<Option<String>> {
+        let Some(url) = new_article.url.clone() else {
+            return Ok(None);
+        };
+        let pool = self.db.pool().clone();
+        let feed_id_str = new_article.feed_id.to_string();
+        let guid = new_article.guid.clone();
+        let title = new_article.title.clone();
+
+        let row: Option<(String,)> = self.db.query_with_retry(|| {
+            let pool = pool.clone();
+            let feed_id_str = feed_id_str.clone();
+            let url = url.clone();
+            let guid = guid.clone();
+            let title = title.clone();
+            async move {
+                sqlx::query_as(
+                    r#"
+                    SELECT guid FROM articles
+                    WHERE feed_id = ? AND url = ? AND title = ? AND guid != ?
+                    LIMIT 1
+                    "#,
+                )
+                .bind(&feed_id_str)
+                .bind(&url)
+                .bind(&title)
+                .bind(&guid)
+                .fetch_optional(&pool)
+                .await
+            }
+        })
+        .await?;
+
+        Ok(row.map(|(guid,)| guid))
+    }
+
+    /// Update an existing article identified by feed_id + guid, skipping
+    /// the write when the content hash is unchanged
+    async fn update_existing(&self, new_article: &NewArticle, guid: &str, content_hash: &str) -> Result<()> {
+        let pool = self.db.pool().clone();
+        let url = new_article.url.clone();
+        let title = new_article.title.clone();
+        let author = new_article.author.clone();
+        let content = new_article.content.clone();
+        let content_text = new_article.content_text.clone();
+        let image_url = new_article.image_url.clone();
+        let media = new_article.media.clone();
+        let comments_url = new_article.comments_url.clone();
+        let feed_id_str = new_article.feed_id.to_string();
+        let guid = guid.to_string();
+        let content_hash = content_hash.to_string();
+        let published_at = new_article.published_at;
+        let now = Utc::now();
+        let stale_before = now - Duration::days(1);
+
+        self.db.execute_with_retry(|| {
+            let pool = pool.clone();
+            let url = url.clone();
+            let title = title.clone();
+            let author = author.clone();
+            let content = content.clone();
+            let content_text = content_text.clone();
+            let image_url = image_url.clone();
+            let media = media.clone();
+            let comments_url = comments_url.clone();
+            let feed_id_str = feed_id_str.clone();
+            let guid = guid.clone();
+            let content_hash = content_hash.clone();
+            async move {
+                // SET expressions see the old row, so updated_at is only
+                // recorded for real edits (not when backfilling a missing hash).
+                // A new enclosure replaces all media columns, missing fields included.
+                sqlx::query(
+                    r#"
+                    UPDATE articles
+                    SET url = COALESCE(?, url),
+                        title = ?,
+                        author = COALESCE(?, author),
+                        content = COALESCE(?, content),
+                        content_text = COALESCE(?, content_text),
+                        published_at = COALESCE(?, published_at),
+                        fetched_at = ?,
+                        image_url = COALESCE(?, image_url),
+                        media_url = CASE WHEN ? THEN ? ELSE media_url END,
+                        media_type = CASE WHEN ? THEN ? ELSE media_type END,
+                        media_length = CASE WHEN ? THEN ? ELSE media_length END,
+                        media_duration = CASE WHEN ? THEN ? ELSE media_duration END,
+                        media_thumbnail_url = CASE WHEN ? THEN ? ELSE media_thumbnail_url END,
+                        comments_url = COALESCE(?, comments_url),
+                        updated_at = CASE
+                            WHEN content_hash IS NOT NULL AND content_hash != ? THEN ?
+                            ELSE updated_at
+                        END,
+                        content_hash = ?
+                    WHERE feed_id = ? AND guid = ?
+                      AND (content_hash IS NULL OR content_hash != ? OR fetched_at < ?)
+                    "#,
+                )
+                .bind(&url)
+                .bind(&title)
+                .bind(&author)
+                .bind(&content)
+                .bind(&content_text)
+                .bind(published_at)
+                .bind(now)
+                .bind(&image_url)
+                .bind(media.is_some())
+                .bind(media.as_ref().map(|m| m.url.clone()))
+                .bind(media.is_some())
+                .bind(media.as_ref().and_then(|m| m.mime_type.clone()))
+                .bind(media.is_some())
+                .bind(media.as_ref().and_then(|m| m.length).map(|l| l as i64))
+                .bind(media.is_some())
+                .bind(media.as_ref().and_then(|m| m.duration_secs).map(|d| d as i64))
+                .bind(media.is_some())
+                .bind(media.as_ref().and_then(|m| m.thumbnail_url.clone()))
+                .bind(&comments_url)
+                .bind(&content_hash)
+                .bind(now)
+                .bind(&content_hash)
+                .bind(&feed_id_str)
+                .bind(&guid)
+                .bind(&content_hash)
+                .bind(stale_before)
+                .execute(&pool)
+                .await
+                .map(|_| ())
+            }
+        })
+        .await?;
+
+        Ok(())
     }
 
-    /// Create multiple articles, returning count of newly created
-    pub async fn create_many(&self, articles: &[NewArticle]) -> Result<u32> {
-        let mut created = 0;
+    /// Create multiple articles, returning the newly created ones
+    pub async fn create_many(&self, articles: &[NewArticle]) -> Result<Vec<Article>> {
+        let mut created = Vec::new();
 
         for article in articles {
-            if let Some(_) = self.create(article).await? {
-                created += 1;
+            if let Some(article) = self.create(article).await? {
+                created.push(article);
             }
         }
 
@@ -199,7 +377,7 @@ impl<'a> ArticleRepository<'a> {
         let pool = self.db.pool().clone();
         let id_str = id.to_string();
 
-        let row: Option<ArticleRow> = query_with_retry(|| {
+        let row: Option<ArticleRow> = self.db.query_with_retry(|| {
             let pool = pool.clone();
             let id_str = id_str.clone();
             async move {
@@ -207,7 +385,8 @@ impl<'a> ArticleRepository<'a> {
                     r#"
                     SELECT id, feed_id, guid, url, title, author, content, content_text,
                            summary, summary_generated_at, published_at, fetched_at,
-                           is_read, read_at, is_saved, created_at, image_url, relevance_score
+                           is_read, read_at, is_saved, created_at, image_url, relevance_score,
+                           media_url, media_type, media_length, media_duration, media_thumbnail_url, comments_url, updated_at
                     FROM articles
                     WHERE id = ?
                     "#,
@@ -231,215 +410,953 @@ impl<'a> ArticleRepository<'a> {
 
     /// Get articles for a feed
     pub async fn list_by_feed(&self, feed_id: Uuid, unread_only: bool) -> Result<Vec<Article>> {
-        let query = if unread_only {
+        let listing = ArticleListing { unread_only, ..Default::default() };
+        self.list_by_feed_page(feed_id, listing, None, None).await
+    }
+
+    /// Get one page of a feed's article listing, starting at `cursor` (from
+    /// the start when `None`). `limit = None` returns all of them.
+    ///
+    /// Pages continue from a row rather than an offset, so articles marked
+    /// read between two pages of unread articles don't shift the next page,
+    /// and the id breaks ties between articles published at the same time.
+    pub async fn list_by_feed_page(
+        &self,
+        feed_id: Uuid,
+        listing: ArticleListing,
+        limit: Option<u32>,
+        cursor: Option<PageCursor>,
+    ) -> Result<Vec<Article>> {
+        let (cursor_id, comparison, backwards) = match cursor {
+            None => (None, None, false),
+            Some(PageCursor::After(id)) => (Some(id), Some("<"), false),
+            Some(PageCursor::Before(id)) => (Some(id), Some(">"), true),
+            Some(PageCursor::From(id)) => (Some(id), Some("<="), false),
+        };
+        let mut conditions = listing_conditions(&listing, "a");
+        if let Some(comparison) = comparison {
+            conditions.push_str(&format!(
+                " AND ({}) {} (SELECT {} FROM articles c WHERE c.id = ?)",
+                listing_key(&listing, "a"),
+                comparison,
+                listing_key(&listing, "c"),
+            ));
+        }
+        // Pages before the cursor are read backwards from it, then reversed
+        let query = format!(
             r#"
-            SELECT id, feed_id, guid, url, title, author, content, content_text,
-                   summary, summary_generated_at, published_at, fetched_at,
-                   is_read, read_at, is_saved, created_at, image_url, relevance_score
-            FROM articles
-            WHERE feed_id = ? AND is_read = 0
-            ORDER BY published_at DESC, created_at DESC
-            "#
-        } else {
+            SELECT a.id, a.feed_id, a.guid, a.url, a.title, a.author, a.content, a.content_text,
+                   a.summary, a.summary_generated_at, a.published_at, a.fetched_at,
+                   a.is_read, a.read_at, a.is_saved, a.created_at, a.image_url, a.relevance_score,
+                   a.media_url, a.media_type, a.media_length, a.media_duration, a.media_thumbnail_url, a.comments_url, a.updated_at
+            FROM articles a
+            WHERE {}
+            ORDER BY {}
+            LIMIT ?
+            "#,
+            conditions,
+            listing_order(&listing, "a", backwards),
+        );
+
+        let pool = self.db.pool().clone();
+        let feed_id_str = feed_id.to_string();
+        let cursor_str = cursor_id.map(|id| id.to_string());
+        // SQLite treats a negative LIMIT as "no limit"
+        let limit = limit.map(i64::from).unwrap_or(-1);
+
+        let rows: Vec<ArticleRow> = self.db.query_with_retry(|| {
+            let pool = pool.clone();
+            let query = query.clone();
+            let feed_id_str = feed_id_str.clone();
+            let cursor_str = cursor_str.clone();
+            async move {
+                let mut q = sqlx::query_as(&query).bind(&feed_id_str);
+                if let Some(min_score) = listing.min_score {
+                    q = q.bind(min_score);
+                }
+                if let Some(cursor_str) = &cursor_str {
+                    q = q.bind(cursor_str);
+                }
+                q.bind(limit).fetch_all(&pool).await
+            }
+        })
+        .await?;
+
+        let mut articles: Vec<Article> = rows.into_iter().map(Article::from).collect();
+        if backwards {
+            articles.reverse();
+        }
+        Ok(articles)
+    }
+
+    /// Get how many articles a feed's listing has before the article `id`,
+    /// or `None` if the listing doesn't include it
+    pub async fn position_in_listing(
+        &self,
+        feed_id: Uuid,
+        listing: ArticleListing,
+        id: Uuid,
+    ) -> Result<Option<u32>> {
+        let query = format!(
             r#"
-            SELECT id, feed_id, guid, url, title, author, content, content_text,
-                   summary, summary_generated_at, published_at, fetched_at,
-                   is_read, read_at, is_saved, created_at, image_url, relevance_score
-            FROM articles
-            WHERE feed_id = ?
-            ORDER BY published_at DESC, created_at DESC
-            "#
-        };
+            SELECT (SELECT COUNT(*) FROM articles a WHERE {} AND ({}) > ({}))
+            FROM articles t
+            WHERE t.id = ? AND {}
+            "#,
+            listing_conditions(&listing, "a"),
+            listing_key(&listing, "a"),
+            listing_key(&listing, "t"),
+            listing_conditions(&listing, "t"),
+        );
 
         let pool = self.db.pool().clone();
         let feed_id_str = feed_id.to_string();
+        let id_str = id.to_string();
+
+        let position: Option<(i64,)> = self.db.query_with_retry(|| {
+            let pool = pool.clone();
+            let query = query.clone();
+            let feed_id_str = feed_id_str.clone();
+            let id_str = id_str.clone();
+            async move {
+                let mut q = sqlx::query_as(&query).bind(feed_id_str.clone());
+                if let Some(min_score) = listing.min_score {
+                    q = q.bind(min_score);
+                }
+                q = q.bind(id_str).bind(feed_id_str);
+                if let Some(min_score) = listing.min_score {
+                    q = q.bind(min_score);
+                }
+                q.fetch_optional(&pool).await
+            }
+        })
+        .await?;
+
+        Ok(position.map(|(count,)| count as u32))
+    }
+
+    /// Get all unread articles that have been summarized
+    pub async fn list_unread_summarized(&self) -> Result<Vec<Article>> {
+        let pool = self.db.pool().clone();
+
+        let rows: Vec<ArticleRow> = self.db.query_with_retry(|| {
+            let pool = pool.clone();
+            async move {
+                sqlx::query_as(
+                    r#"
+                    SELECT id, feed_id, guid, url, title, author, content, content_text,
+                           summary, summary_generated_at, published_at, fetched_at,
+                           is_read, read_at, is_saved, created_at, image_url, relevance_score,
+                           media_url, media_type, media_length, media_duration, media_thumbnail_url, comments_url, updated_at
+                    FROM articles
+                    WHERE is_read = 0 AND summary IS NOT NULL
+                    ORDER BY published_at DESC, created_at DESC
+                    "#,
+                )
+                .fetch_all(&pool)
+                .await
+            }
+        })
+        .await?;
+
+        Ok(rows.into_iter().map(Article::from).collect())
+    }
+
+    /// Saved articles with a URL not yet exported to (or rejected by)
+    /// `destination`, oldest first
+    pub async fn list_saved_unexported(&self, destination: &str, limit: u32) -> Result<Vec<Article>> {
+        let pool = self.db.pool().clone();
+        let destination = destination.to_string();
+
+        let rows: Vec<ArticleRow> = self.db.query_with_retry(|| {
+            let pool = pool.clone();
+            let destination = destination.clone();
+            async move {
+                sqlx::query_as(
+                    r#"
+                    SELECT id, feed_id, guid, url, title, author, content, content_text,
+                           summary, summary_generated_at, published_at, fetched_at,
+                           is_read, read_at, is_saved, created_at, image_url, relevance_score,
+                           media_url, media_type, media_length, media_duration, media_thumbnail_url, comments_url, updated_at
+                    FROM articles
+                    WHERE is_saved = 1 AND url IS NOT NULL
+                      AND id NOT IN (SELECT article_id FROM article_exports WHERE destination = ?)
+                    ORDER BY created_at ASC
+                    LIMIT ?
+                    "#,
+                )
+                .bind(&destination)
+                .bind(limit)
+                .fetch_all(&pool)
+                .await
+            }
+        })
+        .await?;
+
+        Ok(rows.into_iter().map(Article::from).collect())
+    }
+
+    /// Record that an article was exported to `destination`
+    pub async fn mark_exported(&self, id: Uuid, destination: &str) -> Result<()> {
+        let pool = self.db.pool().clone();
+        let id_str = id.to_string();
+        let destination = destination.to_string();
+
+        self.db.execute_with_retry(|| {
+            let pool = pool.clone();
+            let id_str = id_str.clone();
+            let destination = destination.clone();
+            async move {
+                sqlx::query("INSERT OR IGNORE INTO article_exports (article_id, destination) VALUES (?, ?)")
+                    .bind(&id_str)
+                    .bind(&destination)
+                    .execute(&pool)
+                    .await
+                    .map(|_| ())
+            }
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Record that `destination` rejected an article, so it isn't sent again
+    pub async fn mark_export_failed(&self, id: Uuid, destination: &str, error: &str) -> Result<()> {
+        let pool = self.db.pool().clone();
+        let id_str = id.to_string();
+        let destination = destination.to_string();
+        let error = error.to_string();
+
+        self.db.execute_with_retry(|| {
+            let pool = pool.clone();
+            let id_str = id_str.clone();
+            let destination = destination.clone();
+            let error = error.clone();
+            async move {
+                sqlx::query("INSERT OR IGNORE INTO article_exports (article_id, destination, error) VALUES (?, ?, ?)")
+                    .bind(&id_str)
+                    .bind(&destination)
+                    .bind(&error)
+                    .execute(&pool)
+                    .await
+                    .map(|_| ())
+            }
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Get all unread articles
+    pub async fn list_unread(&self, limit: u32) -> Result<Vec<Article>> {
+        let pool = self.db.pool().clone();
+
+        let rows: Vec<ArticleRow> = self.db.query_with_retry(|| {
+            let pool = pool.clone();
+            async move {
+                sqlx::query_as(
+                    r#"
+                    SELECT id, feed_id, guid, url, title, author, content, content_text,
+                           summary, summary_generated_at, published_at, fetched_at,
+                           is_read, read_at, is_saved, created_at, image_url, relevance_score,
+                           media_url, media_type, media_length, media_duration, media_thumbnail_url, comments_url, updated_at
+                    FROM articles
+                    WHERE is_read = 0
+                    ORDER BY published_at DESC, created_at DESC
+                    LIMIT ?
+                    "#,
+                )
+                .bind(limit)
+                .fetch_all(&pool)
+                .await
+            }
+        })
+        .await?;
+
+        Ok(rows.into_iter().map(Article::from).collect())
+    }
+
+    /// Get read articles across all feeds, most recently read first.
+    /// Articles marked read by relevance filtering are not included.
+    pub async fn list_recently_read(&self, limit: u32) -> Result<Vec<Article>> {
+        let pool = self.db.pool().clone();
+
+        let rows: Vec<ArticleRow> = self.db.query_with_retry(|| {
+            let pool = pool.clone();
+            async move {
+                sqlx::query_as(
+                    r#"
+                    SELECT id, feed_id, guid, url, title, author, content, content_text,
+                           summary, summary_generated_at, published_at, fetched_at,
+                           is_read, read_at, is_saved, created_at, image_url, relevance_score,
+                           media_url, media_type, media_length, media_duration, media_thumbnail_url, comments_url, updated_at
+                    FROM articles
+                    WHERE is_read = 1 AND read_at IS NOT NULL
+                      AND id NOT IN (SELECT article_id FROM filtered_articles WHERE rescued_at IS NULL)
+                    ORDER BY read_at DESC
+                    LIMIT ?
+                    "#,
+                )
+                .bind(limit)
+                .fetch_all(&pool)
+                .await
+            }
+        })
+        .await?;
+
+        Ok(rows.into_iter().map(Article::from).collect())
+    }
+
+    /// Get unread articles fetched for the first time after `since`
+    pub async fn list_unread_created_since(&self, since: DateTime<Utc>) -> Result<Vec<Article>> {
+        let pool = self.db.pool().clone();
+
+        let rows: Vec<ArticleRow> = self.db.query_with_retry(|| {
+            let pool = pool.clone();
+            async move {
+                sqlx::query_as(
+                    r#"
+                    SELECT id, feed_id, guid, url, title, author, content, content_text,
+                           summary, summary_generated_at, published_at, fetched_at,
+                           is_read, read_at, is_saved, created_at, image_url, relevance_score,
+                           media_url, media_type, media_length, media_duration, media_thumbnail_url, comments_url, updated_at
+                    FROM articles
+                    WHERE is_read = 0 AND created_at > ?
+                    ORDER BY created_at ASC
+                    "#,
+                )
+                .bind(since)
+                .fetch_all(&pool)
+                .await
+            }
+        })
+        .await?;
+
+        Ok(rows.into_iter().map(Article::from).collect())
+    }
+
+    /// Get up to `limit` articles, read or not, fetched for the first time
+    /// after `since`, newest first
+    pub async fn list_created_since(&self, since: DateTime<Utc>, limit: u32) -> Result<Vec<Article>> {
+        let pool = self.db.pool().clone();
+
+        let rows: Vec<ArticleRow> = self.db.query_with_retry(|| {
+            let pool = pool.clone();
+            async move {
+                sqlx::query_as(
+                    r#"
+                    SELECT id, feed_id, guid, url, title, author, content, content_text,
+                           summary, summary_generated_at, published_at, fetched_at,
+                           is_read, read_at, is_saved, created_at, image_url, relevance_score,
+                           media_url, media_type, media_length, media_duration, media_thumbnail_url, comments_url, updated_at
+                    FROM articles
+                    WHERE created_at > ?
+                    ORDER BY created_at DESC
+                    LIMIT ?
+                    "#,
+                )
+                .bind(since)
+                .bind(limit)
+                .fetch_all(&pool)
+                .await
+            }
+        })
+        .await?;
+
+        Ok(rows.into_iter().map(Article::from).collect())
+    }
+
+    /// Get articles that need summarization
+    /// Only returns unread articles with content_text length >= min_length and no summary
+    pub async fn list_unsummarized(&self, limit: u32, min_length: usize) -> Result<Vec<Article>> {
+        let pool = self.db.pool().clone();
+        let min_len = min_length as i64;
+
+        let rows: Vec<ArticleRow> = self.db.query_with_retry(|| {
+            let pool = pool.clone();
+            async move {
+                sqlx::query_as(
+                    r#"
+                    SELECT id, feed_id, guid, url, title, author, content, content_text,
+                           summary, summary_generated_at, published_at, fetched_at,
+                           is_read, read_at, is_saved, created_at, image_url, relevance_score,
+                           media_url, media_type, media_length, media_duration, media_thumbnail_url, comments_url, updated_at
+                    FROM articles
+                    WHERE summary IS NULL
+                      AND content_text IS NOT NULL
+                      AND LENGTH(content_text) >= ?
+                      AND is_read = 0
+                    ORDER BY created_at DESC
+                    LIMIT ?
+                    "#,
+                )
+                .bind(min_len)
+                .bind(limit)
+                .fetch_all(&pool)
+                .await
+            }
+        })
+        .await?;
+
+        Ok(rows.into_iter().map(Article::from).collect())
+    }
+
+    /// Check if articles are still unread (for batch processing validation)
+    /// Returns only the IDs of articles that are still unread
+    pub async fn filter_unread_ids(&self, ids: &[Uuid]) -> Result<Vec<Uuid>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let pool = self.db.pool().clone();
+        let id_strings: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+
+        let rows: Vec<String> = self.db.query_with_retry(|| {
+            let pool = pool.clone();
+            let id_strings = id_strings.clone();
+            async move {
+                let placeholders: Vec<String> =
+                    id_strings.iter().map(|_| "?".to_string()).collect();
+                let query = format!(
+                    "SELECT id FROM articles WHERE id IN ({}) AND is_read = 0",
+                    placeholders.join(", ")
+                );
+
+                let mut query_builder = sqlx::query_scalar::<_, String>(&query);
+                for id_str in &id_strings {
+                    query_builder = query_builder.bind(id_str);
+                }
+
+                query_builder.fetch_all(&pool).await
+            }
+        })
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|id| Uuid::parse_str(&id).ok())
+            .collect())
+    }
+
+    /// Mark an article as read
+    pub async fn mark_read(&self, id: Uuid) -> Result<()> {
+        let now = Utc::now();
+        let pool = self.db.pool().clone();
+        let id_str = id.to_string();
+
+        self.db.execute_with_retry(|| {
+            let pool = pool.clone();
+            let id_str = id_str.clone();
+            async move {
+                sqlx::query(
+                    r#"
+                    UPDATE articles
+                    SET is_read = 1, read_at = ?, state_changed_at = ?
+                    WHERE id = ?
+                    "#,
+                )
+                .bind(now)
+                .bind(now)
+                .bind(&id_str)
+                .execute(&pool)
+                .await
+                .map(|_| ())
+            }
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Mark an article read because its relevance score is below the
+    /// threshold, remembering why so it can be reviewed and rescued
+    pub async fn mark_filtered(&self, id: Uuid, score: f64, threshold: f64) -> Result<()> {
+        let now = Utc::now();
+        let pool = self.db.pool().clone();
+        let id_str = id.to_string();
+
+        self.db.execute_with_retry(|| {
+            let pool = pool.clone();
+            let id_str = id_str.clone();
+            async move {
+                let mut tx = pool.begin().await?;
+                sqlx::query(
+                    r#"
+                    UPDATE articles
+                    SET is_read = 1, read_at = ?, state_changed_at = ?
+                    WHERE id = ?
+                    "#,
+                )
+                .bind(now)
+                .bind(now)
+                .bind(&id_str)
+                .execute(&mut *tx)
+                .await?;
+                sqlx::query(
+                    r#"
+                    INSERT OR REPLACE INTO filtered_articles (article_id, score, threshold, filtered_at)
+                    VALUES (?, ?, ?, ?)
+                    "#,
+                )
+                .bind(&id_str)
+                .bind(score)
+                .bind(threshold)
+                .bind(now)
+                .execute(&mut *tx)
+                .await?;
+                tx.commit().await
+            }
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Articles still read after relevance filtering marked them read, most
+    /// recently filtered first
+    pub async fn list_filtered(&self, limit: u32) -> Result<Vec<FilteredArticle>> {
+        let pool = self.db.pool().clone();
+
+        let rows: Vec<FilteredRow> = self.db.query_with_retry(|| {
+            let pool = pool.clone();
+            async move {
+                sqlx::query_as(
+                    r#"
+                    SELECT id, feed_id, guid, url, title, author, content, content_text,
+                           summary, summary_generated_at, published_at, fetched_at,
+                           is_read, read_at, is_saved, created_at, image_url, relevance_score,
+                           media_url, media_type, media_length, media_duration, media_thumbnail_url, comments_url, updated_at,
+                           f.score AS filter_score, f.threshold AS filter_threshold, f.filtered_at
+                    FROM articles
+                    JOIN filtered_articles f ON f.article_id = articles.id
+                    WHERE is_read = 1 AND f.rescued_at IS NULL
+                    ORDER BY f.filtered_at DESC
+                    LIMIT ?
+                    "#,
+                )
+                .bind(limit)
+                .fetch_all(&pool)
+                .await
+            }
+        })
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| FilteredArticle {
+                reason: FilterReason {
+                    score: row.filter_score,
+                    threshold: row.filter_threshold,
+                    filtered_at: row.filtered_at,
+                },
+                article: Article::from(row.article),
+            })
+            .collect())
+    }
+
+    /// Mark a filtered article unread again and keep it from being filtered
+    /// again. Returns false if the article was not filtered.
+    pub async fn rescue_filtered(&self, id: Uuid) -> Result<bool> {
+        let now = Utc::now();
+        let pool = self.db.pool().clone();
+        let id_str = id.to_string();
+
+        let rescued = self.db.query_with_retry(|| {
+            let pool = pool.clone();
+            let id_str = id_str.clone();
+            async move {
+                let mut tx = pool.begin().await?;
+                let result = sqlx::query(
+                    "UPDATE filtered_articles SET rescued_at = ? WHERE article_id = ? AND rescued_at IS NULL",
+                )
+                .bind(now)
+                .bind(&id_str)
+                .execute(&mut *tx)
+                .await?;
+                if result.rows_affected() > 0 {
+                    sqlx::query(
+                        r#"
+                        UPDATE articles
+                        SET is_read = 0, read_at = NULL, state_changed_at = ?
+                        WHERE id = ?
+                        "#,
+                    )
+                    .bind(now)
+                    .bind(&id_str)
+                    .execute(&mut *tx)
+                    .await?;
+                }
+                tx.commit().await?;
+                Ok(result.rows_affected() > 0)
+            }
+        })
+        .await?;
+
+        Ok(rescued)
+    }
+
+    /// Articles rescued from relevance filtering, which are not filtered again
+    pub async fn list_rescued_ids(&self) -> Result<Vec<Uuid>> {
+        let ids: Vec<(String,)> =
+            sqlx::query_as("SELECT article_id FROM filtered_articles WHERE rescued_at IS NOT NULL")
+                .fetch_all(self.db.pool())
+                .await?;
+
+        Ok(ids.into_iter().filter_map(|(id,)| Uuid::parse_str(&id).ok()).collect())
+    }
+
+    /// Record that a new article contains a watchlist keyword
+    pub async fn record_alert(&self, id: Uuid, keyword: &str) -> Result<()> {
+        let now = Utc::now();
+        let pool = self.db.pool().clone();
+        let id_str = id.to_string();
+        let keyword = keyword.to_string();
+
+        self.db.execute_with_retry(|| {
+            let pool = pool.clone();
+            let id_str = id_str.clone();
+            let keyword = keyword.clone();
+            async move {
+                sqlx::query("INSERT OR IGNORE INTO article_alerts (article_id, keyword, alerted_at) VALUES (?, ?, ?)")
+                    .bind(&id_str)
+                    .bind(&keyword)
+                    .bind(now)
+                    .execute(&pool)
+                    .await
+                    .map(|_| ())
+            }
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Articles that raised a keyword alert, read or not, most recent alert
+    /// first
+    pub async fn list_alerted(&self, limit: u32) -> Result<Vec<AlertedArticle>> {
+        let pool = self.db.pool().clone();
+
+        let rows: Vec<AlertedRow> = self.db.query_with_retry(|| {
+            let pool = pool.clone();
+            async move {
+                sqlx::query_as(
+                    r#"
+                    SELECT id, feed_id, guid, url, title, author, content, content_text,
+                           summary, summary_generated_at, published_at, fetched_at,
+                           is_read, read_at, is_saved, created_at, image_url, relevance_score,
+                           media_url, media_type, media_length, media_duration, media_thumbnail_url, comments_url, updated_at,
+                           a.keyword, a.alerted_at
+                    FROM articles
+                    JOIN article_alerts a ON a.article_id = articles.id
+                    ORDER BY a.alerted_at DESC
+                    LIMIT ?
+                    "#,
+                )
+                .bind(limit)
+                .fetch_all(&pool)
+                .await
+            }
+        })
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| AlertedArticle {
+                alert: ArticleAlert {
+                    keyword: row.keyword,
+                    alerted_at: row.alerted_at,
+                },
+                article: Article::from(row.article),
+            })
+            .collect())
+    }
+
+    /// Link an article to the CVEs it mentions; CVEs not seen before wait
+    /// for a lookup
+    pub async fn link_cves(&self, id: Uuid, cve_ids: &[String]) -> Result<()> {
+        if cve_ids.is_empty() {
+            return Ok(());
+        }
+        let pool = self.db.pool().clone();
+        let id_str = id.to_string();
+        let cve_ids = cve_ids.to_vec();
 
-        let rows: Vec<ArticleRow> = query_with_retry(|| {
+        self.db.execute_with_retry(|| {
             let pool = pool.clone();
-            let feed_id_str = feed_id_str.clone();
-            let query = query;
+            let id_str = id_str.clone();
+            let cve_ids = cve_ids.clone();
             async move {
-                sqlx::query_as(query)
-                    .bind(&feed_id_str)
-                    .fetch_all(&pool)
-                    .await
+                let mut tx = pool.begin().await?;
+                for cve_id in &cve_ids {
+                    sqlx::query("INSERT OR IGNORE INTO cves (cve_id) VALUES (?)")
+                        .bind(cve_id)
+                        .execute(&mut *tx)
+                        .await?;
+                    sqlx::query("INSERT OR IGNORE INTO article_cves (article_id, cve_id) VALUES (?, ?)")
+                        .bind(&id_str)
+                        .bind(cve_id)
+                        .execute(&mut *tx)
+                        .await?;
+                }
+                tx.commit().await
             }
         })
         .await?;
 
-        Ok(rows.into_iter().map(Article::from).collect())
+        Ok(())
     }
 
-    /// Get all unread articles that have been summarized
-    pub async fn list_unread_summarized(&self) -> Result<Vec<Article>> {
+    /// CVEs to look up: those never looked up (first), then those still
+    /// unscored when last looked up before `retry_unscored_before`, newest
+    /// identifiers first
+    pub async fn list_pending_cves(
+        &self,
+        limit: usize,
+        retry_unscored_before: DateTime<Utc>,
+    ) -> Result<Vec<String>> {
         let pool = self.db.pool().clone();
 
-        let rows: Vec<ArticleRow> = query_with_retry(|| {
+        let rows: Vec<(String,)> = self.db.query_with_retry(|| {
             let pool = pool.clone();
             async move {
                 sqlx::query_as(
                     r#"
-                    SELECT id, feed_id, guid, url, title, author, content, content_text,
-                           summary, summary_generated_at, published_at, fetched_at,
-                           is_read, read_at, is_saved, created_at, image_url, relevance_score
-                    FROM articles
-                    WHERE is_read = 0 AND summary IS NOT NULL
-                    ORDER BY published_at DESC, created_at DESC
+                    SELECT cve_id FROM cves
+                    WHERE looked_up_at IS NULL OR (score IS NULL AND looked_up_at < ?)
+                    ORDER BY looked_up_at IS NOT NULL, cve_id DESC
+                    LIMIT ?
                     "#,
                 )
+                .bind(retry_unscored_before)
+                .bind(limit as i64)
                 .fetch_all(&pool)
                 .await
             }
         })
         .await?;
 
-        Ok(rows.into_iter().map(Article::from).collect())
+        Ok(rows.into_iter().map(|(cve_id,)| cve_id).collect())
     }
 
-    /// Get all unread articles
-    pub async fn list_unread(&self, limit: u32) -> Result<Vec<Article>> {
+    /// Store the result of a CVE lookup (no score when the NVD has none).
+    /// Returns the articles mentioning the CVE.
+    pub async fn record_cve_lookup(
+        &self,
+        cve_id: &str,
+        score: Option<f64>,
+        severity: Option<&str>,
+    ) -> Result<Vec<Uuid>> {
+        let now = Utc::now();
         let pool = self.db.pool().clone();
+        let cve_id = cve_id.to_string();
+        let severity = severity.map(str::to_string);
 
-        let rows: Vec<ArticleRow> = query_with_retry(|| {
+        let rows: Vec<(String,)> = self.db.query_with_retry(|| {
             let pool = pool.clone();
+            let cve_id = cve_id.clone();
+            let severity = severity.clone();
             async move {
-                sqlx::query_as(
-                    r#"
-                    SELECT id, feed_id, guid, url, title, author, content, content_text,
-                           summary, summary_generated_at, published_at, fetched_at,
-                           is_read, read_at, is_saved, created_at, image_url, relevance_score
-                    FROM articles
-                    WHERE is_read = 0
-                    ORDER BY published_at DESC, created_at DESC
-                    LIMIT ?
-                    "#,
-                )
-                .bind(limit)
-                .fetch_all(&pool)
-                .await
+                let mut tx = pool.begin().await?;
+                sqlx::query("UPDATE cves SET score = ?, severity = ?, looked_up_at = ? WHERE cve_id = ?")
+                    .bind(score)
+                    .bind(&severity)
+                    .bind(now)
+                    .bind(&cve_id)
+                    .execute(&mut *tx)
+                    .await?;
+                let rows = sqlx::query_as("SELECT article_id FROM article_cves WHERE cve_id = ?")
+                    .bind(&cve_id)
+                    .fetch_all(&mut *tx)
+                    .await?;
+                tx.commit().await?;
+                Ok(rows)
             }
         })
         .await?;
 
-        Ok(rows.into_iter().map(Article::from).collect())
+        Ok(rows
+            .into_iter()
+            .filter_map(|(id,)| Uuid::parse_str(&id).ok())
+            .collect())
     }
 
-    /// Get articles that need summarization
-    /// Only returns unread articles with content_text length >= min_length and no summary
-    pub async fn list_unsummarized(&self, limit: u32, min_length: usize) -> Result<Vec<Article>> {
+    /// Articles mentioning CVEs, the most severe first (articles whose CVEs
+    /// aren't scored yet last). `min_score` leaves out articles whose
+    /// highest score is lower; unscored ones only show with 0.
+    pub async fn list_advisories(&self, min_score: f64, limit: u32) -> Result<Vec<AdvisoryArticle>> {
         let pool = self.db.pool().clone();
-        let min_len = min_length as i64;
 
-        let rows: Vec<ArticleRow> = query_with_retry(|| {
+        let rows: Vec<ArticleRow> = self.db.query_with_retry(|| {
             let pool = pool.clone();
             async move {
                 sqlx::query_as(
                     r#"
                     SELECT id, feed_id, guid, url, title, author, content, content_text,
                            summary, summary_generated_at, published_at, fetched_at,
-                           is_read, read_at, is_saved, created_at, image_url, relevance_score
+                           is_read, read_at, is_saved, created_at, image_url, relevance_score,
+                           media_url, media_type, media_length, media_duration, media_thumbnail_url, comments_url, updated_at
                     FROM articles
-                    WHERE summary IS NULL
-                      AND content_text IS NOT NULL
-                      AND LENGTH(content_text) >= ?
-                      AND is_read = 0
-                    ORDER BY created_at DESC
+                    JOIN article_cves ac ON ac.article_id = articles.id
+                    LEFT JOIN cves c ON c.cve_id = ac.cve_id
+                    GROUP BY articles.id
+                    HAVING COALESCE(MAX(c.score), 0) >= ?
+                    ORDER BY MAX(c.score) IS NULL, MAX(c.score) DESC, fetched_at DESC
                     LIMIT ?
                     "#,
                 )
-                .bind(min_len)
+                .bind(min_score)
                 .bind(limit)
                 .fetch_all(&pool)
                 .await
             }
         })
         .await?;
-
-        Ok(rows.into_iter().map(Article::from).collect())
-    }
-
-    /// Check if articles are still unread (for batch processing validation)
-    /// Returns only the IDs of articles that are still unread
-    pub async fn filter_unread_ids(&self, ids: &[Uuid]) -> Result<Vec<Uuid>> {
-        if ids.is_empty() {
+        if rows.is_empty() {
             return Ok(Vec::new());
         }
 
-        let pool = self.db.pool().clone();
-        let id_strings: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
-
-        let rows: Vec<String> = query_with_retry(|| {
+        let ids: Vec<String> = rows.iter().map(|row| row.id.clone()).collect();
+        let cves: Vec<(String, String, Option<f64>, Option<String>)> = self.db.query_with_retry(|| {
             let pool = pool.clone();
-            let id_strings = id_strings.clone();
+            let ids = ids.clone();
             async move {
-                let placeholders: Vec<String> =
-                    id_strings.iter().map(|_| "?".to_string()).collect();
-                let query = format!(
-                    "SELECT id FROM articles WHERE id IN ({}) AND is_read = 0",
-                    placeholders.join(", ")
+                let placeholders = vec!["?"; ids.len()].join(", ");
+                let sql = format!(
+                    r#"
+                    SELECT ac.article_id, ac.cve_id, c.score, c.severity
+                    FROM article_cves ac
+                    LEFT JOIN cves c ON c.cve_id = ac.cve_id
+                    WHERE ac.article_id IN ({})
+                    ORDER BY c.score IS NULL, c.score DESC, ac.cve_id
+                    "#,
+                    placeholders
                 );
-
-                let mut query_builder = sqlx::query_scalar::<_, String>(&query);
-                for id_str in &id_strings {
-                    query_builder = query_builder.bind(id_str);
+                let mut query = sqlx::query_as(&sql);
+                for id in &ids {
+                    query = query.bind(id);
                 }
-
-                query_builder.fetch_all(&pool).await
+                query.fetch_all(&pool).await
             }
         })
         .await?;
 
+        let mut advisories: HashMap<String, Vec<Advisory>> = HashMap::new();
+        for (article_id, cve_id, score, severity) in cves {
+            advisories.entry(article_id).or_default().push(Advisory { cve_id, score, severity });
+        }
         Ok(rows
             .into_iter()
-            .filter_map(|id| Uuid::parse_str(&id).ok())
+            .map(|row| AdvisoryArticle {
+                advisories: advisories.remove(&row.id).unwrap_or_default(),
+                article: Article::from(row),
+            })
             .collect())
     }
 
-    /// Mark an article as read
-    pub async fn mark_read(&self, id: Uuid) -> Result<()> {
-        let now = Utc::now();
+    /// Remember the discussion page found for an article
+    pub async fn set_comments_url(&self, id: Uuid, comments_url: &str) -> Result<()> {
         let pool = self.db.pool().clone();
         let id_str = id.to_string();
+        let comments_url = comments_url.to_string();
 
-        execute_with_retry(|| {
+        self.db.execute_with_retry(|| {
             let pool = pool.clone();
             let id_str = id_str.clone();
+            let comments_url = comments_url.clone();
+            async move {
+                sqlx::query("UPDATE articles SET comments_url = ? WHERE id = ?")
+                    .bind(&comments_url)
+                    .bind(&id_str)
+                    .execute(&pool)
+                    .await
+                    .map(|_| ())
+            }
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Mark a feed's unread articles published before `before` as read,
+    /// except saved ones. They get no read time, so they stay out of the
+    /// reading history.
+    pub async fn mark_read_before(&self, feed_id: Uuid, before: DateTime<Utc>) -> Result<u32> {
+        let now = Utc::now();
+        let pool = self.db.pool().clone();
+        let feed_id_str = feed_id.to_string();
+
+        let result = self.db.query_with_retry(|| {
+            let pool = pool.clone();
+            let feed_id_str = feed_id_str.clone();
             async move {
                 sqlx::query(
                     r#"
                     UPDATE articles
-                    SET is_read = 1, read_at = ?
-                    WHERE id = ?
+                    SET is_read = 1, state_changed_at = ?
+                    WHERE feed_id = ? AND is_read = 0 AND is_saved = 0
+                      AND COALESCE(published_at, fetched_at) < ?
                     "#,
                 )
                 .bind(now)
-                .bind(&id_str)
+                .bind(&feed_id_str)
+                .bind(before)
                 .execute(&pool)
                 .await
-                .map(|_| ())
             }
         })
         .await?;
 
-        Ok(())
+        Ok(result.rows_affected() as u32)
+    }
+
+    /// Mark all of a feed's unread articles as read. Like aged-out articles
+    /// they get no read time, so catching up stays out of the reading
+    /// history. Returns the number of articles marked.
+    pub async fn mark_all_read(&self, feed_id: Uuid) -> Result<u32> {
+        let now = Utc::now();
+        let pool = self.db.pool().clone();
+        let feed_id_str = feed_id.to_string();
+
+        let result = self.db.query_with_retry(|| {
+            let pool = pool.clone();
+            let feed_id_str = feed_id_str.clone();
+            async move {
+                sqlx::query(
+                    r#"
+                    UPDATE articles
+                    SET is_read = 1, state_changed_at = ?
+                    WHERE feed_id = ? AND is_read = 0
+                    "#,
+                )
+                .bind(now)
+                .bind(&feed_id_str)
+                .execute(&pool)
+                .await
+            }
+        })
+        .await?;
+
+        Ok(result.rows_affected() as u32)
     }
 
     /// Mark an article as unread
     pub async fn mark_unread(&self, id: Uuid) -> Result<()> {
+        let now = Utc::now();
         let pool = self.db.pool().clone();
         let id_str = id.to_string();
 
-        execute_with_retry(|| {
+        self.db.execute_with_retry(|| {
             let pool = pool.clone();
             let id_str = id_str.clone();
             async move {
                 sqlx::query(
                     r#"
                     UPDATE articles
-                    SET is_read = 0, read_at = NULL
+                    SET is_read = 0, read_at = NULL, state_changed_at = ?
                     WHERE id = ?
                     "#,
                 )
+                .bind(now)
                 .bind(&id_str)
                 .execute(&pool)
                 .await
@@ -451,22 +1368,80 @@ impl<'a> ArticleRepository<'a> {
         Ok(())
     }
 
+    /// Store several read states in one transaction, returning the
+    /// articles marked read with their feed
+    pub async fn set_read_many(&self, changes: &[ReadStateChange]) -> Result<Vec<(Uuid, Uuid)>> {
+        let now = Utc::now();
+        let pool = self.db.pool().clone();
+
+        let rows: Vec<(String, String)> = self.db.query_with_retry(|| {
+            let pool = pool.clone();
+            async move {
+                let mut tx = pool.begin().await?;
+                let mut marked = Vec::new();
+                for change in changes {
+                    let id_str = change.id.to_string();
+                    if change.read {
+                        let feed_id: Option<(String,)> = sqlx::query_as(
+                            r#"
+                            UPDATE articles
+                            SET is_read = 1, read_at = ?, state_changed_at = ?
+                            WHERE id = ?
+                            RETURNING feed_id
+                            "#,
+                        )
+                        .bind(now)
+                        .bind(now)
+                        .bind(&id_str)
+                        .fetch_optional(&mut *tx)
+                        .await?;
+                        if let Some((feed_id,)) = feed_id {
+                            marked.push((id_str, feed_id));
+                        }
+                    } else {
+                        sqlx::query(
+                            r#"
+                            UPDATE articles
+                            SET is_read = 0, read_at = NULL, state_changed_at = ?
+                            WHERE id = ?
+                            "#,
+                        )
+                        .bind(now)
+                        .bind(&id_str)
+                        .execute(&mut *tx)
+                        .await?;
+                    }
+                }
+                tx.commit().await?;
+                Ok(marked)
+            }
+        })
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(id, feed_id)| Some((Uuid::parse_str(&id).ok()?, Uuid::parse_str(&feed_id).ok()?)))
+            .collect())
+    }
+
     /// Toggle article saved status
     pub async fn toggle_saved(&self, id: Uuid) -> Result<bool> {
+        let now = Utc::now();
         let pool = self.db.pool().clone();
         let id_str = id.to_string();
 
-        execute_with_retry(|| {
+        self.db.execute_with_retry(|| {
             let pool = pool.clone();
             let id_str = id_str.clone();
             async move {
                 sqlx::query(
                     r#"
                     UPDATE articles
-                    SET is_saved = 1 - is_saved
+                    SET is_saved = 1 - is_saved, state_changed_at = ?
                     WHERE id = ?
                     "#,
                 )
+                .bind(now)
                 .bind(&id_str)
                 .execute(&pool)
                 .await
@@ -476,7 +1451,7 @@ impl<'a> ArticleRepository<'a> {
         .await?;
 
         // Return the new saved status
-        let row: (i32,) = query_with_retry(|| {
+        let row: (i32,) = self.db.query_with_retry(|| {
             let pool = pool.clone();
             let id_str = id_str.clone();
             async move {
@@ -498,7 +1473,7 @@ impl<'a> ArticleRepository<'a> {
         let id_str = id.to_string();
         let summary = summary.to_string();
 
-        execute_with_retry(|| {
+        self.db.execute_with_retry(|| {
             let pool = pool.clone();
             let id_str = id_str.clone();
             let summary = summary.clone();
@@ -528,7 +1503,7 @@ impl<'a> ArticleRepository<'a> {
         let pool = self.db.pool().clone();
         let id_str = id.to_string();
 
-        execute_with_retry(|| {
+        self.db.execute_with_retry(|| {
             let pool = pool.clone();
             let id_str = id_str.clone();
             async move {
@@ -565,7 +1540,7 @@ impl<'a> ArticleRepository<'a> {
 
         // Build batch INSERT query for all tags at once
         // This reduces database round trips from N to 1
-        execute_with_retry(|| {
+        self.db.execute_with_retry(|| {
             let pool = pool.clone();
             let article_id_str = article_id_str.clone();
             let source = source.clone();
@@ -595,12 +1570,77 @@ impl<'a> ArticleRepository<'a> {
         Ok(())
     }
 
+    /// Add the same tags to many articles in one transaction. Returns the
+    /// number of tags added (tags an article already has are skipped).
+    pub async fn add_tags_bulk(&self, article_ids: &[Uuid], tags: &[String], source: &str) -> Result<u64> {
+        if article_ids.is_empty() || tags.is_empty() {
+            return Ok(0);
+        }
+
+        let now = Utc::now();
+        let pool = self.db.pool().clone();
+        let ids: Vec<String> = article_ids.iter().map(Uuid::to_string).collect();
+        let source = source.to_string();
+        let tags: Vec<String> = tags.to_vec();
+
+        let added = self.db.query_with_retry(|| {
+            let pool = pool.clone();
+            let ids = ids.clone();
+            let source = source.clone();
+            let tags = tags.clone();
+            async move {
+                // One multi-row INSERT per article, all in one transaction
+                let placeholders: Vec<String> = tags.iter().map(|_| "(?, ?, ?, ?)".to_string()).collect();
+                let query = format!(
+                    "INSERT OR IGNORE INTO article_tags (article_id, tag, source, created_at) VALUES {}",
+                    placeholders.join(", ")
+                );
+
+                let mut tx = pool.begin().await?;
+                let mut added = 0;
+                for id_str in &ids {
+                    let mut query_builder = sqlx::query(&query);
+                    for tag in &tags {
+                        query_builder = query_builder
+                            .bind(id_str)
+                            .bind(tag)
+                            .bind(&source)
+                            .bind(now);
+                    }
+                    added += query_builder.execute(&mut *tx).await?.rows_affected();
+                }
+                tx.commit().await?;
+                Ok(added)
+            }
+        })
+        .await?;
+
+        Ok(added)
+    }
+
+    /// All tags in use, most used first
+    pub async fn list_tags(&self) -> Result<Vec<String>> {
+        let pool = self.db.pool().clone();
+
+        let rows: Vec<(String,)> = self.db.query_with_retry(|| {
+            let pool = pool.clone();
+            async move {
+                sqlx::query_as("SELECT tag FROM article_tags GROUP BY tag ORDER BY COUNT(*) DESC, tag")
+                    .fetch_all(&pool)
+                    .await
+            }
+        })
+        .await?;
+
+        Ok(rows.into_iter().map(|(tag,)| tag).collect())
+    }
+
     /// Get tags for an article
     pub async fn get_tags(&self, article_id: Uuid) -> Result<Vec<String>> {
         let pool = self.db.pool().clone();
         let article_id_str = article_id.to_string();
 
-        let rows: Vec<(String,)> = query_with_retry(|| {
+        let rows: Vec<(String,)> = self.db.query_with_retry(|| {
             let pool = pool.clone();
             let article_id_str = article_id_str.clone();
             async move {
@@ -621,7 +1661,7 @@ impl<'a> ArticleRepository<'a> {
         let pool = self.db.pool().clone();
 
         // Use query_with_retry to get the result for checking rows_affected
-        let result = query_with_retry(|| {
+        let result = self.db.query_with_retry(|| {
             let pool = pool.clone();
             async move {
                 sqlx::query(
@@ -646,7 +1686,7 @@ impl<'a> ArticleRepository<'a> {
         let pool = self.db.pool().clone();
         let feed_id_str = feed_id.map(|fid| fid.to_string());
 
-        let rows: Vec<ArticleRow> = query_with_retry(|| {
+        let rows: Vec<ArticleRow> = self.db.query_with_retry(|| {
             let pool = pool.clone();
             let search_pattern = search_pattern.clone();
             let feed_id_str = feed_id_str.clone();
@@ -656,7 +1696,8 @@ impl<'a> ArticleRepository<'a> {
                         r#"
                         SELECT id, feed_id, guid, url, title, author, content, content_text,
                                summary, summary_generated_at, published_at, fetched_at,
-                               is_read, read_at, is_saved, created_at, image_url, relevance_score
+                               is_read, read_at, is_saved, created_at, image_url, relevance_score,
+                               media_url, media_type, media_length, media_duration, media_thumbnail_url, comments_url, updated_at
                         FROM articles
                         WHERE feed_id = ? AND (title LIKE ? OR content_text LIKE ?)
                         ORDER BY published_at DESC
@@ -673,7 +1714,8 @@ impl<'a> ArticleRepository<'a> {
                         r#"
                         SELECT id, feed_id, guid, url, title, author, content, content_text,
                                summary, summary_generated_at, published_at, fetched_at,
-                               is_read, read_at, is_saved, created_at, image_url, relevance_score
+                               is_read, read_at, is_saved, created_at, image_url, relevance_score,
+                               media_url, media_type, media_length, media_duration, media_thumbnail_url, comments_url, updated_at
                         FROM articles
                         WHERE title LIKE ? OR content_text LIKE ?
                         ORDER BY published_at DESC
@@ -692,3 +1734,440 @@ impl<'a> ArticleRepository<'a> {
         Ok(rows.into_iter().map(Article::from).collect())
     }
 }
+
+/// SQL conditions selecting the articles of a listing from table `t`, with
+/// placeholders for the feed id and, if set, the minimum score
+fn listing_conditions(listing: &ArticleListing, t: &str) -> String {
+    let mut conditions = format!("{t}.feed_id = ?");
+    if listing.unread_only {
+        conditions.push_str(&format!(" AND {t}.is_read = 0"));
+    }
+    if listing.min_score.is_some() {
+        conditions.push_str(&format!(
+            " AND ({t}.relevance_score IS NULL OR {t}.relevance_score >= ?)"
+        ));
+    }
+    conditions
+}
+
+/// Sort key columns of a listing, descending in list order. Unscored
+/// articles sort last, and articles without a publication time after dated ones.
+fn listing_key(listing: &ArticleListing, t: &str) -> String {
+    if listing.by_relevance {
+        format!("COALESCE({t}.relevance_score, -1.0), COALESCE({t}.published_at, ''), {t}.created_at, {t}.id")
+    } else {
+        format!("COALESCE({t}.published_at, ''), {t}.created_at, {t}.id")
+    }
+}
+
+/// ORDER BY clause of a listing, reversed when reading backwards
+fn listing_order(listing: &ArticleListing, t: &str, backwards: bool) -> String {
+    let direction = if backwards { "ASC" } else { "DESC" };
+    let mut order = String::new();
+    if listing.by_relevance {
+        order.push_str(&format!("COALESCE({t}.relevance_score, -1.0) {direction}, "));
+    }
+    order.push_str(&format!(
+        "{t}.published_at {direction}, {t}.created_at {direction}, {t}.id {direction}"
+    ));
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feed::NewFeed;
+    use crate::storage::{test_db_with_feed, FeedRepository};
+
+    fn new_article(feed_id: Uuid, content: &str) -> NewArticle {
+        NewArticle {
+            url: Some("https://example.com/1".to_string()),
+            content: Some(content.to_string()),
+            content_text: Some(content.to_string()),
+            ..NewArticle::for_test(feed_id, "guid-1")
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_only_updates_changed_content() {
+        let (db, feed) = test_db_with_feed().await;
+        let repo = ArticleRepository::new(&db);
+
+        let created = repo.create(&new_article(feed.id, "v1")).await.unwrap().unwrap();
+        assert!(created.updated_at.is_none());
+
+        // Unchanged content: no update recorded, fetched_at untouched
+        assert!(repo.create(&new_article(feed.id, "v1")).await.unwrap().is_none());
+        let same = repo.find_by_id(created.id).await.unwrap().unwrap();
+        assert!(same.updated_at.is_none());
+        assert_eq!(same.fetched_at, created.fetched_at);
+
+        // Changed content: row rewritten and marked as updated
+        repo.create(&new_article(feed.id, "v2")).await.unwrap();
+        let updated = repo.find_by_id(created.id).await.unwrap().unwrap();
+        assert_eq!(updated.content.as_deref(), Some("v2"));
+        assert!(updated.updated_at.is_some());
+        assert!(updated.is_updated());
+    }
+
+    #[tokio::test]
+    async fn test_new_enclosure_replaces_all_media_fields() {
+        let (db, feed) = test_db_with_feed().await;
+        let repo = ArticleRepository::new(&db);
+        let with_media = |url: &str, mime_type: Option<&str>, length: Option<u64>| NewArticle {
+            media: Some(ArticleMedia {
+                url: url.to_string(),
+                mime_type: mime_type.map(str::to_string),
+                length,
+                duration_secs: length.map(|_| 60),
+                thumbnail_url: None,
+            }),
+            ..new_article(feed.id, "v1")
+        };
+
+        let created = repo
+            .create(&with_media("https://example.com/1.mp3", Some("audio/mpeg"), Some(1000)))
+            .await
+            .unwrap()
+            .unwrap();
+        repo.create(&with_media("https://example.com/1.ogg", None, None)).await.unwrap();
+        let media = repo.find_by_id(created.id).await.unwrap().unwrap().media.unwrap();
+        assert_eq!(media.url, "https://example.com/1.ogg");
+        assert_eq!((media.mime_type, media.length, media.duration_secs), (None, None, None));
+
+        // An item without an enclosure keeps the stored one
+        repo.create(&new_article(feed.id, "v2")).await.unwrap();
+        let media = repo.find_by_id(created.id).await.unwrap().unwrap().media.unwrap();
+        assert_eq!(media.url, "https://example.com/1.ogg");
+    }
+
+    #[tokio::test]
+    async fn test_create_dedups_unstable_guid_by_url() {
+        let (db, feed) = test_db_with_feed().await;
+        let repo = ArticleRepository::new(&db);
+
+        assert!(repo.create(&new_article(feed.id, "v1")).await.unwrap().is_some());
+
+        // Same URL and title under a new GUID is the same article
+        let mut migrated = new_article(feed.id, "v1");
+        migrated.guid = "guid-after-migration".to_string();
+        assert!(repo.create(&migrated).await.unwrap().is_none());
+
+        // Same URL with a different title is a distinct entry
+        let mut other = new_article(feed.id, "v1");
+        other.guid = "guid-2".to_string();
+        other.title = "Another title".to_string();
+        assert!(repo.create(&other).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_outcome_predicts_create() {
+        let (db, feed) = test_db_with_feed().await;
+        let repo = ArticleRepository::new(&db);
+
+        assert_eq!(repo.outcome(&new_article(feed.id, "v1")).await.unwrap(), ArticleOutcome::New);
+        assert!(repo.list_by_feed(feed.id, false).await.unwrap().is_empty());
+
+        repo.create(&new_article(feed.id, "v1")).await.unwrap();
+        assert_eq!(repo.outcome(&new_article(feed.id, "v1")).await.unwrap(), ArticleOutcome::Unchanged);
+        assert_eq!(repo.outcome(&new_article(feed.id, "v2")).await.unwrap(), ArticleOutcome::Updated);
+
+        let mut migrated = new_article(feed.id, "v1");
+        migrated.guid = "guid-after-migration".to_string();
+        assert_eq!(repo.outcome(&migrated).await.unwrap(), ArticleOutcome::Republished);
+    }
+
+    #[tokio::test]
+    async fn test_list_created_since_includes_read_articles() {
+        let (db, feed) = test_db_with_feed().await;
+        let repo = ArticleRepository::new(&db);
+        let since = Utc::now() - Duration::hours(1);
+
+        let mut ids = Vec::new();
+        for n in 0..3 {
+            let mut article = new_article(feed.id, "text");
+            article.guid = format!("guid-{}", n);
+            article.url = Some(format!("https://example.com/{}", n));
+            ids.push(repo.create(&article).await.unwrap().unwrap().id);
+        }
+        repo.mark_read(ids[0]).await.unwrap();
+
+        assert_eq!(repo.list_created_since(since, 10).await.unwrap().len(), 3);
+        assert_eq!(repo.list_created_since(since, 2).await.unwrap().len(), 2);
+        assert!(repo.list_created_since(Utc::now(), 10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_filtered_articles_can_be_rescued() {
+        let (db, feed) = test_db_with_feed().await;
+        let repo = ArticleRepository::new(&db);
+        let article = repo.create(&new_article(feed.id, "text")).await.unwrap().unwrap();
+
+        repo.mark_filtered(article.id, 0.1, 0.3).await.unwrap();
+        let filtered = repo.list_filtered(10).await.unwrap();
+        assert_eq!(filtered.len(), 1);
+        assert!(filtered[0].article.is_read);
+        assert_eq!(filtered[0].reason.score, 0.1);
+        assert_eq!(filtered[0].reason.threshold, 0.3);
+        // Filtered articles are not in the reading history
+        assert!(repo.list_recently_read(10).await.unwrap().is_empty());
+
+        assert!(repo.rescue_filtered(article.id).await.unwrap());
+        assert!(!repo.find_by_id(article.id).await.unwrap().unwrap().is_read);
+        assert!(repo.list_filtered(10).await.unwrap().is_empty());
+        assert_eq!(repo.list_rescued_ids().await.unwrap(), [article.id]);
+        // Only filtered articles can be rescued, once
+        assert!(!repo.rescue_filtered(article.id).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_list_recently_read_orders_by_read_time() {
+        let (db, feed) = test_db_with_feed().await;
+        let repo = ArticleRepository::new(&db);
+
+        let mut ids = Vec::new();
+        for i in 0..3 {
+            let mut article = new_article(feed.id, "body");
+            article.guid = format!("guid-{}", i);
+            article.url = Some(format!("https://example.com/{}", i));
+            ids.push(repo.create(&article).await.unwrap().unwrap().id);
+        }
+        repo.mark_read(ids[2]).await.unwrap();
+        repo.mark_read(ids[0]).await.unwrap();
+
+        let history: Vec<Uuid> = repo
+            .list_recently_read(10)
+            .await
+            .unwrap()
+            .iter()
+            .map(|a| a.id)
+            .collect();
+        assert_eq!(history, vec![ids[0], ids[2]]);
+
+        repo.mark_unread(ids[0]).await.unwrap();
+        assert_eq!(repo.list_recently_read(10).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_saved_articles_export_once_per_destination() {
+        let (db, feed) = test_db_with_feed().await;
+        let repo = ArticleRepository::new(&db);
+
+        let saved = repo.create(&new_article(feed.id, "body")).await.unwrap().unwrap();
+        let mut other = new_article(feed.id, "body");
+        other.guid = "guid-2".to_string();
+        other.url = Some("https://example.com/2".to_string());
+        repo.create(&other).await.unwrap();
+        repo.toggle_saved(saved.id).await.unwrap();
+
+        let pending = repo.list_saved_unexported("readwise", 10).await.unwrap();
+        assert_eq!(pending.iter().map(|a| a.id).collect::<Vec<_>>(), [saved.id]);
+
+        repo.mark_exported(saved.id, "readwise").await.unwrap();
+        repo.mark_exported(saved.id, "readwise").await.unwrap();
+        assert!(repo.list_saved_unexported("readwise", 10).await.unwrap().is_empty());
+        assert_eq!(repo.list_saved_unexported("matter", 10).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_add_tags_bulk() {
+        let (db, feed) = test_db_with_feed().await;
+        let repo = ArticleRepository::new(&db);
+
+        let mut ids = Vec::new();
+        for i in 0..2 {
+            let mut article = new_article(feed.id, "body");
+            article.guid = format!("guid-{}", i);
+            article.url = Some(format!("https://example.com/{}", i));
+            ids.push(repo.create(&article).await.unwrap().unwrap().id);
+        }
+        repo.add_tags(ids[0], &["rust".to_string(), "misc".to_string()], "ai").await.unwrap();
+
+        // Tags the first article already has are skipped
+        let tags = vec!["rust".to_string(), "async".to_string()];
+        assert_eq!(repo.add_tags_bulk(&ids, &tags, "user").await.unwrap(), 3);
+        let mut second = repo.get_tags(ids[1]).await.unwrap();
+        second.sort();
+        assert_eq!(second, ["async", "rust"]);
+
+        assert_eq!(repo.list_tags().await.unwrap(), ["async", "rust", "misc"]);
+    }
+
+    #[tokio::test]
+    async fn test_mark_read_before() {
+        let (db, feed) = test_db_with_feed().await;
+        let repo = ArticleRepository::new(&db);
+        let now = Utc::now();
+        let mut ids = Vec::new();
+        for (i, days_ago) in [1, 10, 20].into_iter().enumerate() {
+            let article = NewArticle {
+                guid: format!("guid-{}", i),
+                url: Some(format!("https://example.com/{}", i)),
+                published_at: Some(now - Duration::days(days_ago)),
+                ..new_article(feed.id, "text")
+            };
+            ids.push(repo.create(&article).await.unwrap().unwrap().id);
+        }
+        repo.toggle_saved(ids[2]).await.unwrap();
+
+        // Only the old unsaved article is marked, without a read time
+        let marked = repo.mark_read_before(feed.id, now - Duration::days(7)).await.unwrap();
+        assert_eq!(marked, 1);
+        let aged = repo.find_by_id(ids[1]).await.unwrap().unwrap();
+        assert!(aged.is_read && aged.read_at.is_none());
+        assert!(!repo.find_by_id(ids[0]).await.unwrap().unwrap().is_read);
+        assert!(!repo.find_by_id(ids[2]).await.unwrap().unwrap().is_read);
+    }
+
+    #[tokio::test]
+    async fn test_pages_continue_after_articles_marked_read() {
+        let (db, feed) = test_db_with_feed().await;
+        let repo = ArticleRepository::new(&db);
+        let now = Utc::now();
+        // Three articles share a publication time, one has none
+        let published = [Some(5), Some(3), Some(3), Some(3), Some(1), None];
+        for (i, hours_ago) in published.into_iter().enumerate() {
+            let article = NewArticle {
+                guid: format!("guid-{}", i),
+                url: Some(format!("https://example.com/{}", i)),
+                published_at: hours_ago.map(|h| now - Duration::hours(h)),
+                ..new_article(feed.id, "text")
+            };
+            repo.create(&article).await.unwrap();
+        }
+        let all: Vec<Uuid> = repo.list_by_feed(feed.id, true).await.unwrap().iter().map(|a| a.id).collect();
+        assert_eq!(all.len(), 6);
+
+        // Reading the first page doesn't make the second skip articles
+        let unread = ArticleListing { unread_only: true, ..Default::default() };
+        let first = repo.list_by_feed_page(feed.id, unread, Some(2), None).await.unwrap();
+        for article in &first {
+            repo.mark_read(article.id).await.unwrap();
+        }
+        let mut loaded: Vec<Uuid> = first.iter().map(|a| a.id).collect();
+        while let Some(&last) = loaded.last() {
+            let page = repo
+                .list_by_feed_page(feed.id, unread, Some(2), Some(PageCursor::After(last)))
+                .await
+                .unwrap();
+            if page.is_empty() {
+                break;
+            }
+            loaded.extend(page.iter().map(|a| a.id));
+        }
+        assert_eq!(loaded, all);
+    }
+
+    #[tokio::test]
+    async fn test_relevance_listing_pages_both_ways() {
+        let (db, feed) = test_db_with_feed().await;
+        let repo = ArticleRepository::new(&db);
+        let now = Utc::now();
+        let scores = [Some(0.2), None, Some(0.9), Some(0.5), Some(0.9), Some(0.7)];
+        let mut ids = Vec::new();
+        for (i, score) in scores.into_iter().enumerate() {
+            let article = NewArticle {
+                guid: format!("guid-{}", i),
+                url: Some(format!("https://example.com/{}", i)),
+                published_at: Some(now - Duration::hours(i as i64)),
+                ..new_article(feed.id, "text")
+            };
+            let id = repo.create(&article).await.unwrap().unwrap().id;
+            if let Some(score) = score {
+                repo.update_relevance_score(id, score).await.unwrap();
+            }
+            ids.push(id);
+        }
+
+        // Most relevant first, newer first among equal scores, unscored last;
+        // low scores are left out
+        let listing = ArticleListing { by_relevance: true, min_score: Some(0.5), ..Default::default() };
+        let listed: Vec<Uuid> = repo
+            .list_by_feed_page(feed.id, listing, None, None)
+            .await
+            .unwrap()
+            .iter()
+            .map(|a| a.id)
+            .collect();
+        assert_eq!(listed, [ids[2], ids[4], ids[5], ids[3], ids[1]]);
+
+        let expected = [
+            (PageCursor::After(ids[4]), vec![ids[5], ids[3]]),
+            (PageCursor::From(ids[4]), vec![ids[4], ids[5]]),
+            (PageCursor::Before(ids[3]), vec![ids[4], ids[5]]),
+            (PageCursor::Before(ids[4]), vec![ids[2]]),
+        ];
+        for (cursor, expected) in expected {
+            let page = repo.list_by_feed_page(feed.id, listing, Some(2), Some(cursor)).await.unwrap();
+            assert_eq!(page.iter().map(|a| a.id).collect::<Vec<_>>(), expected, "{:?}", cursor);
+        }
+
+        assert_eq!(repo.position_in_listing(feed.id, listing, ids[3]).await.unwrap(), Some(3));
+        assert_eq!(repo.position_in_listing(feed.id, listing, ids[0]).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_mark_all_read() {
+        let db = Database::new_in_memory().await.unwrap();
+        let feed_repo = FeedRepository::new(&db);
+        let mut feeds = Vec::new();
+        for name in ["noisy", "quiet"] {
+            let feed = feed_repo
+                .create(&NewFeed {
+                    url: format!("https://{}.example.com/feed", name),
+                    local_name: name.to_string(),
+                })
+                .await
+                .unwrap();
+            feeds.push(feed.id);
+        }
+        let repo = ArticleRepository::new(&db);
+        let mut ids = Vec::new();
+        for (i, feed_id) in [feeds[0], feeds[0], feeds[0], feeds[1]].into_iter().enumerate() {
+            let article = NewArticle {
+                guid: format!("guid-{}", i),
+                url: Some(format!("https://example.com/{}", i)),
+                ..new_article(feed_id, "text")
+            };
+            ids.push(repo.create(&article).await.unwrap().unwrap().id);
+        }
+        repo.mark_read(ids[0]).await.unwrap();
+        repo.toggle_saved(ids[1]).await.unwrap();
+
+        // Saved articles are caught up too, other feeds are left alone
+        assert_eq!(repo.mark_all_read(feeds[0]).await.unwrap(), 2);
+        let saved = repo.find_by_id(ids[1]).await.unwrap().unwrap();
+        assert!(saved.is_read && saved.read_at.is_none());
+        assert!(repo.find_by_id(ids[2]).await.unwrap().unwrap().is_read);
+        assert!(!repo.find_by_id(ids[3]).await.unwrap().unwrap().is_read);
+        assert_eq!(repo.mark_all_read(feeds[0]).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_set_read_many() {
+        let (db, feed) = test_db_with_feed().await;
+        let repo = ArticleRepository::new(&db);
+        let mut ids = Vec::new();
+        for i in 0..2 {
+            let article = NewArticle {
+                guid: format!("guid-{}", i),
+                url: Some(format!("https://example.com/{}", i)),
+                ..new_article(feed.id, "text")
+            };
+            ids.push(repo.create(&article).await.unwrap().unwrap().id);
+        }
+        repo.mark_read(ids[1]).await.unwrap();
+
+        let changes = [
+            ReadStateChange { id: ids[0], read: true },
+            ReadStateChange { id: ids[1], read: false },
+            ReadStateChange { id: Uuid::new_v4(), read: true },
+        ];
+        // Only articles that exist and were marked read are returned
+        assert_eq!(repo.set_read_many(&changes).await.unwrap(), [(ids[0], feed.id)]);
+        let read = repo.find_by_id(ids[0]).await.unwrap().unwrap();
+        assert!(read.is_read && read.read_at.is_some());
+        let unread = repo.find_by_id(ids[1]).await.unwrap().unwrap();
+        assert!(!unread.is_read && unread.read_at.is_none());
+    }
+}
@@ -2,10 +2,13 @@ use sqlx::sqlite::{
     SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions, SqliteSynchronous,
 };
 use sqlx::{Pool, Sqlite};
+use std::future::Future;
 use std::path::Path;
 use std::str::FromStr;
 use std::time::Duration;
 
+use super::migrations::{self, Migration};
+use super::{RetryPolicy, WriterLock};
 use crate::config::AppConfig;
 use crate::Result;
 
@@ -13,11 +16,19 @@ use crate::Result;
 #[derive(Clone)]
 pub struct Database {
     pool: Pool<Sqlite>,
+    retry: RetryPolicy,
 }
 
 impl Database {
     /// Create a new database connection and run migrations
     pub async fn new(config: &AppConfig) -> Result<Self> {
+        let db = Self::open(config).await?;
+        db.migrate().await?;
+        Ok(db)
+    }
+
+    /// Open the database without running migrations
+    pub async fn open(config: &AppConfig) -> Result<Self> {
         let db_path = config.database_path();
 
         // Ensure the data directory exists
@@ -25,8 +36,9 @@ impl Database {
             std::fs::create_dir_all(parent)?;
         }
 
-        // Clean up potentially stale lock files (for cloud sync scenarios)
-        if db_path.exists() {
+        // Clean up potentially stale lock files (for cloud sync scenarios),
+        // unless a local writer such as the daemon has the database open
+        if db_path.exists() && !WriterLock::is_held_elsewhere(config)? {
             Self::cleanup_stale_locks(&db_path)?;
         }
 
@@ -34,26 +46,36 @@ impl Database {
 
         tracing::info!("Connecting to database: {}", db_path.display());
 
+        let tuning = &config.database;
+        let retry = RetryPolicy {
+            max_retries: tuning.max_retries,
+            base_delay: Duration::from_millis(tuning.retry_base_delay_ms),
+        };
+
         // Use SqliteConnectOptions to set PRAGMAs per-connection.
         // This ensures every connection in the pool has the correct settings,
         // not just the first one — critical for cloud sync (iCloud, Dropbox) scenarios.
+        // WAL lets readers proceed while the daemon writes; with it,
+        // synchronous=NORMAL only risks the last transactions on power loss.
+        // Huge sizes are capped by SQLite itself; keep the byte count in its range
+        let mmap_size = tuning.mmap_size_mb.saturating_mul(1024 * 1024).min(i64::MAX as u64);
         let options = SqliteConnectOptions::from_str(&db_url)?
             .create_if_missing(true)
             .journal_mode(SqliteJournalMode::Wal)
-            .synchronous(SqliteSynchronous::Normal)
-            .busy_timeout(Duration::from_secs(10))
-            .pragma("wal_autocheckpoint", "2000");
+            .synchronous(SqliteSynchronous::from_str(&tuning.synchronous)?)
+            .busy_timeout(Duration::from_millis(tuning.busy_timeout_ms))
+            .pragma("wal_autocheckpoint", tuning.wal_autocheckpoint.to_string())
+            .pragma("mmap_size", mmap_size.to_string())
+            .pragma("temp_store", "memory")
+            .optimize_on_close(true, 400);
 
         let pool = SqlitePoolOptions::new()
-            .max_connections(15)
+            .max_connections(tuning.max_connections.max(1))
             .acquire_timeout(Duration::from_secs(10))
             .connect_with(options)
             .await?;
 
-        let db = Self { pool };
-        db.run_migrations().await?;
-
-        Ok(db)
+        Ok(Self { pool, retry })
     }
 
     /// Create an in-memory database for testing
@@ -64,73 +86,31 @@ impl Database {
             .connect("sqlite::memory:")
             .await?;
 
-        let db = Self { pool };
-        db.run_migrations().await?;
+        let db = Self {
+            pool,
+            retry: RetryPolicy::default(),
+        };
+        db.migrate().await?;
 
         Ok(db)
     }
 
-    /// Run database migrations
-    async fn run_migrations(&self) -> Result<()> {
+    /// Apply pending schema migrations, returning the ones applied
+    pub async fn migrate(&self) -> Result<Vec<&'static Migration>> {
         tracing::info!("Running database migrations...");
+        let applied = migrations::run(&self.pool).await?;
+        tracing::info!("Database migrations completed");
+        Ok(applied)
+    }
 
-        // Create feeds table
-        sqlx::query(MIGRATION_001_FEEDS)
-            .execute(&self.pool)
-            .await?;
-
-        // Create articles table
-        sqlx::query(MIGRATION_002_ARTICLES)
-            .execute(&self.pool)
-            .await?;
-
-        // Create article tags table
-        sqlx::query(MIGRATION_003_ARTICLE_TAGS)
-            .execute(&self.pool)
-            .await?;
-
-        // Create behavior events table
-        sqlx::query(MIGRATION_004_BEHAVIOR_EVENTS)
-            .execute(&self.pool)
-            .await?;
-
-        // Create user preferences table
-        sqlx::query(MIGRATION_005_USER_PREFERENCES)
-            .execute(&self.pool)
-            .await?;
-
-        // Create article styles table
-        sqlx::query(MIGRATION_006_ARTICLE_STYLES)
-            .execute(&self.pool)
-            .await?;
-
-        // Create indexes
-        sqlx::query(MIGRATION_INDEXES)
-            .execute(&self.pool)
-            .await?;
-
-        // Add image_url column to articles (migration 007)
-        if let Err(err) = sqlx::query(MIGRATION_007_ARTICLE_IMAGE_URL)
-            .execute(&self.pool)
-            .await
-        {
-            if !is_duplicate_column_error(&err) {
-                return Err(err.into());
-            }
-        }
-
-        // Add relevance_score column to articles (migration 008)
-        if let Err(err) = sqlx::query(MIGRATION_008_ARTICLE_RELEVANCE_SCORE)
-            .execute(&self.pool)
-            .await
-        {
-            if !is_duplicate_column_error(&err) {
-                return Err(err.into());
-            }
-        }
+    /// List schema migrations not yet applied, without changing anything
+    pub async fn pending_migrations(&self) -> Result<Vec<&'static Migration>> {
+        migrations::pending(&self.pool).await
+    }
 
-        tracing::info!("Database migrations completed");
-        Ok(())
+    /// Current schema version (0 for a new database)
+    pub async fn schema_version(&self) -> Result<u32> {
+        migrations::current_version(&self.pool).await
     }
 
     /// Get the connection pool
@@ -138,6 +118,30 @@ impl Database {
         &self.pool
     }
 
+    /// Execute a write operation with exponential backoff retry for transient errors
+    ///
+    /// This is essential for cloud sync scenarios where multiple devices may
+    /// access the same database file (via iCloud, Dropbox, etc.)
+    pub async fn execute_with_retry<F, Fut>(&self, operation: F) -> std::result::Result<(), sqlx::Error>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = std::result::Result<(), sqlx::Error>>,
+    {
+        self.retry.run(operation, "write").await
+    }
+
+    /// Execute a query operation with exponential backoff retry for transient errors
+    ///
+    /// Generic over the return type T to support various query result types.
+    /// Essential for handling I/O errors during read operations in cloud sync scenarios.
+    pub async fn query_with_retry<F, Fut, T>(&self, operation: F) -> std::result::Result<T, sqlx::Error>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = std::result::Result<T, sqlx::Error>>,
+    {
+        self.retry.run(operation, "query").await
+    }
+
     /// Clean up potentially stale lock files from cloud sync scenarios
     ///
     /// When using cloud storage (iCloud, Dropbox, etc.), WAL lock files (.db-wal, .db-shm)
@@ -166,121 +170,16 @@ impl Database {
     }
 }
 
-fn is_duplicate_column_error(err: &sqlx::Error) -> bool {
-    match err {
-        sqlx::Error::Database(db_err) => {
-            let message = db_err.message().to_lowercase();
-            message.contains("duplicate column")
-        }
-        _ => false,
-    }
+/// In-memory database with one feed named "example", for tests
+#[cfg(test)]
+pub(crate) async fn test_db_with_feed() -> (Database, crate::feed::Feed) {
+    let db = Database::new_in_memory().await.unwrap();
+    let feed = super::FeedRepository::new(&db)
+        .create(&crate::feed::NewFeed {
+            url: "https://example.com/feed".to_string(),
+            local_name: "example".to_string(),
+        })
+        .await
+        .unwrap();
+    (db, feed)
 }
-
-const MIGRATION_001_FEEDS: &str = r#"
-CREATE TABLE IF NOT EXISTS feeds (
-    id TEXT PRIMARY KEY,
-    url TEXT NOT NULL UNIQUE,
-    local_name TEXT NOT NULL,
-    title TEXT,
-    description TEXT,
-    site_url TEXT,
-    icon_url TEXT,
-    last_fetched_at DATETIME,
-    fetch_error TEXT,
-    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
-    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
-)
-"#;
-
-const MIGRATION_002_ARTICLES: &str = r#"
-CREATE TABLE IF NOT EXISTS articles (
-    id TEXT PRIMARY KEY,
-    feed_id TEXT NOT NULL REFERENCES feeds(id) ON DELETE CASCADE,
-    guid TEXT NOT NULL,
-    url TEXT,
-    title TEXT NOT NULL,
-    author TEXT,
-    content TEXT,
-    content_text TEXT,
-    summary TEXT,
-    summary_generated_at DATETIME,
-    published_at DATETIME,
-    fetched_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
-    is_read INTEGER NOT NULL DEFAULT 0,
-    read_at DATETIME,
-    is_saved INTEGER NOT NULL DEFAULT 0,
-    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
-    UNIQUE(feed_id, guid)
-)
-"#;
-
-const MIGRATION_003_ARTICLE_TAGS: &str = r#"
-CREATE TABLE IF NOT EXISTS article_tags (
-    article_id TEXT NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
-    tag TEXT NOT NULL,
-    source TEXT NOT NULL DEFAULT 'ai',
-    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
-    PRIMARY KEY (article_id, tag)
-)
-"#;
-
-const MIGRATION_004_BEHAVIOR_EVENTS: &str = r#"
-CREATE TABLE IF NOT EXISTS behavior_events (
-    id INTEGER PRIMARY KEY AUTOINCREMENT,
-    article_id TEXT REFERENCES articles(id) ON DELETE SET NULL,
-    feed_id TEXT REFERENCES feeds(id) ON DELETE SET NULL,
-    event_type TEXT NOT NULL,
-    event_data TEXT,
-    reading_duration_ms INTEGER,
-    scroll_depth_percent INTEGER,
-    context_time_of_day TEXT,
-    context_day_of_week INTEGER,
-    context_network_type TEXT,
-    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
-)
-"#;
-
-const MIGRATION_005_USER_PREFERENCES: &str = r#"
-CREATE TABLE IF NOT EXISTS user_preferences (
-    id INTEGER PRIMARY KEY AUTOINCREMENT,
-    preference_type TEXT NOT NULL,
-    preference_key TEXT NOT NULL,
-    weight REAL NOT NULL DEFAULT 0.0,
-    time_window TEXT NOT NULL,
-    computed_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
-    UNIQUE(preference_type, preference_key, time_window)
-)
-"#;
-
-const MIGRATION_006_ARTICLE_STYLES: &str = r#"
-CREATE TABLE IF NOT EXISTS article_styles (
-    article_id TEXT PRIMARY KEY REFERENCES articles(id) ON DELETE CASCADE,
-    style_type TEXT,
-    tone TEXT,
-    length_category TEXT,
-    computed_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
-)
-"#;
-
-const MIGRATION_INDEXES: &str = r#"
-CREATE INDEX IF NOT EXISTS idx_feeds_local_name ON feeds(local_name);
-CREATE INDEX IF NOT EXISTS idx_articles_feed_id ON articles(feed_id);
-CREATE INDEX IF NOT EXISTS idx_articles_is_read ON articles(is_read);
-CREATE INDEX IF NOT EXISTS idx_articles_published_at ON articles(published_at DESC);
-CREATE INDEX IF NOT EXISTS idx_articles_fetched_at ON articles(fetched_at DESC);
-CREATE INDEX IF NOT EXISTS idx_article_tags_tag ON article_tags(tag);
-CREATE INDEX IF NOT EXISTS idx_behavior_events_article_id ON behavior_events(article_id);
-CREATE INDEX IF NOT EXISTS idx_behavior_events_feed_id ON behavior_events(feed_id);
-CREATE INDEX IF NOT EXISTS idx_behavior_events_type ON behavior_events(event_type);
-CREATE INDEX IF NOT EXISTS idx_behavior_events_created_at ON behavior_events(created_at DESC);
-CREATE INDEX IF NOT EXISTS idx_user_prefs_type ON user_preferences(preference_type);
-CREATE INDEX IF NOT EXISTS idx_user_prefs_window ON user_preferences(time_window)
-"#;
-
-const MIGRATION_007_ARTICLE_IMAGE_URL: &str = r#"
-ALTER TABLE articles ADD COLUMN image_url TEXT
-"#;
-
-const MIGRATION_008_ARTICLE_RELEVANCE_SCORE: &str = r#"
-ALTER TABLE articles ADD COLUMN relevance_score REAL
-"#;
@@ -2,9 +2,8 @@ use chrono::{DateTime, Utc};
 use sqlx::FromRow;
 use uuid::Uuid;
 
-use super::retry::{execute_with_retry, query_with_retry};
 use super::Database;
-use crate::feed::{Feed, NewFeed};
+use crate::feed::{Feed, FeedMetadata, FeedSettings, FeedStats, NewFeed};
 use crate::{Error, Result};
 
 /// Repository for feed CRUD operations
@@ -25,6 +24,23 @@ struct FeedRow {
     fetch_error: Option<String>,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
+    category: Option<String>,
+    paused: bool,
+    refresh_interval_secs: Option<i64>,
+    auto_read_days: Option<i64>,
+    pinned: bool,
+    position: i64,
+    moved_to: Option<String>,
+}
+
+#[derive(FromRow)]
+struct FeedStatsRow {
+    total: i64,
+    unread: Option<i64>,
+    saved: Option<i64>,
+    recent: Option<i64>,
+    average_relevance: Option<f64>,
+    last_post_at: Option<DateTime<Utc>>,
 }
 
 impl From<FeedRow> for Feed {
@@ -41,6 +57,13 @@ impl From<FeedRow> for Feed {
             fetch_error: row.fetch_error,
             created_at: row.created_at,
             updated_at: row.updated_at,
+            category: row.category,
+            paused: row.paused,
+            refresh_interval_secs: row.refresh_interval_secs.map(|s| s.max(0) as u64),
+            auto_read_days: row.auto_read_days.map(|d| d.max(0) as u32),
+            pinned: row.pinned,
+            position: row.position,
+            moved_to: row.moved_to,
             unread_count: 0,
         }
     }
@@ -60,7 +83,7 @@ impl<'a> FeedRepository<'a> {
         let url = new_feed.url.clone();
         let local_name = new_feed.local_name.clone();
 
-        execute_with_retry(|| {
+        self.db.execute_with_retry(|| {
             let pool = pool.clone();
             let id_str = id_str.clone();
             let url = url.clone();
@@ -68,8 +91,8 @@ impl<'a> FeedRepository<'a> {
             async move {
                 sqlx::query(
                     r#"
-                    INSERT INTO feeds (id, url, local_name, created_at, updated_at)
-                    VALUES (?, ?, ?, ?, ?)
+                    INSERT INTO feeds (id, url, local_name, created_at, updated_at, position)
+                    VALUES (?, ?, ?, ?, ?, (SELECT COALESCE(MAX(position), -1) + 1 FROM feeds))
                     "#,
                 )
                 .bind(&id_str)
@@ -94,14 +117,15 @@ impl<'a> FeedRepository<'a> {
         let pool = self.db.pool().clone();
         let id_str = id.to_string();
 
-        let row: Option<FeedRow> = query_with_retry(|| {
+        let row: Option<FeedRow> = self.db.query_with_retry(|| {
             let pool = pool.clone();
             let id_str = id_str.clone();
             async move {
                 sqlx::query_as(
                     r#"
                     SELECT id, url, local_name, title, description, site_url, icon_url,
-                           last_fetched_at, fetch_error, created_at, updated_at
+                           last_fetched_at, fetch_error, created_at, updated_at,
+                           category, paused, refresh_interval_secs, auto_read_days, pinned, position, moved_to
                     FROM feeds
                     WHERE id = ?
                     "#,
@@ -121,14 +145,15 @@ impl<'a> FeedRepository<'a> {
         let pool = self.db.pool().clone();
         let url = url.to_string();
 
-        let row: Option<FeedRow> = query_with_retry(|| {
+        let row: Option<FeedRow> = self.db.query_with_retry(|| {
             let pool = pool.clone();
             let url = url.clone();
             async move {
                 sqlx::query_as(
                     r#"
                     SELECT id, url, local_name, title, description, site_url, icon_url,
-                           last_fetched_at, fetch_error, created_at, updated_at
+                           last_fetched_at, fetch_error, created_at, updated_at,
+                           category, paused, refresh_interval_secs, auto_read_days, pinned, position, moved_to
                     FROM feeds
                     WHERE url = ?
                     "#,
@@ -143,19 +168,30 @@ impl<'a> FeedRepository<'a> {
         Ok(row.map(Feed::from))
     }
 
+    /// Find an existing subscription to the same feed as any of `urls`
+    /// (see `Feed::matches_url`)
+    pub async fn find_equivalent(&self, urls: &[&str]) -> Result<Option<Feed>> {
+        Ok(self
+            .list_all()
+            .await?
+            .into_iter()
+            .find(|feed| urls.iter().any(|url| feed.matches_url(url))))
+    }
+
     /// Get all feeds with unread counts
     pub async fn list_all(&self) -> Result<Vec<Feed>> {
         let pool = self.db.pool().clone();
 
-        let rows: Vec<FeedRow> = query_with_retry(|| {
+        let rows: Vec<FeedRow> = self.db.query_with_retry(|| {
             let pool = pool.clone();
             async move {
                 sqlx::query_as(
                     r#"
                     SELECT id, url, local_name, title, description, site_url, icon_url,
-                           last_fetched_at, fetch_error, created_at, updated_at
+                           last_fetched_at, fetch_error, created_at, updated_at,
+                           category, paused, refresh_interval_secs, auto_read_days, pinned, position, moved_to
                     FROM feeds
-                    ORDER BY local_name ASC
+                    ORDER BY pinned DESC, position ASC, local_name ASC
                     "#,
                 )
                 .fetch_all(&pool)
@@ -169,7 +205,7 @@ impl<'a> FeedRepository<'a> {
         // Fetch unread counts
         for feed in &mut feeds {
             let feed_id_str = feed.id.to_string();
-            let count: (i64,) = query_with_retry(|| {
+            let count: (i64,) = self.db.query_with_retry(|| {
                 let pool = pool.clone();
                 let feed_id_str = feed_id_str.clone();
                 async move {
@@ -189,37 +225,45 @@ impl<'a> FeedRepository<'a> {
         Ok(feeds)
     }
 
-    /// List feeds that need refreshing (last_fetched_at is NULL or older than threshold)
-    pub async fn list_needs_refresh(&self, min_interval_secs: u64) -> Result<Vec<Feed>> {
-        let threshold = Utc::now() - chrono::Duration::seconds(min_interval_secs as i64);
+    /// List unpaused feeds that need refreshing (never fetched, or fetched longer
+    /// ago than their own refresh interval, falling back to `default_interval_secs`)
+    pub async fn list_needs_refresh(
+        &self,
+        default_interval_secs: u64,
+        stagger_percent: u32,
+    ) -> Result<Vec<Feed>> {
+        let now = Utc::now();
         let pool = self.db.pool().clone();
 
-        let rows: Vec<FeedRow> = query_with_retry(|| {
+        let rows: Vec<FeedRow> = self.db.query_with_retry(|| {
             let pool = pool.clone();
             async move {
                 sqlx::query_as(
                     r#"
                     SELECT id, url, local_name, title, description, site_url, icon_url,
-                           last_fetched_at, fetch_error, created_at, updated_at
+                           last_fetched_at, fetch_error, created_at, updated_at,
+                           category, paused, refresh_interval_secs, auto_read_days, pinned, position, moved_to
                     FROM feeds
-                    WHERE last_fetched_at IS NULL
-                       OR last_fetched_at < ?
-                    ORDER BY local_name ASC
+                    WHERE paused = 0
+                    ORDER BY pinned DESC, position ASC, local_name ASC
                     "#,
                 )
-                .bind(threshold)
                 .fetch_all(&pool)
                 .await
             }
         })
         .await?;
 
-        let mut feeds: Vec<Feed> = rows.into_iter().map(Feed::from).collect();
+        let mut feeds: Vec<Feed> = rows
+            .into_iter()
+            .map(Feed::from)
+            .filter(|feed| feed.is_due(now, default_interval_secs, stagger_percent))
+            .collect();
 
         // Fetch unread counts
         for feed in &mut feeds {
             let feed_id_str = feed.id.to_string();
-            let count: (i64,) = query_with_retry(|| {
+            let count: (i64,) = self.db.query_with_retry(|| {
                 let pool = pool.clone();
                 let feed_id_str = feed_id_str.clone();
                 async move {
@@ -256,7 +300,7 @@ impl<'a> FeedRepository<'a> {
         let site_url = site_url.map(|s| s.to_string());
         let icon_url = icon_url.map(|s| s.to_string());
 
-        execute_with_retry(|| {
+        self.db.execute_with_retry(|| {
             let pool = pool.clone();
             let id_str = id_str.clone();
             let title = title.clone();
@@ -294,6 +338,268 @@ impl<'a> FeedRepository<'a> {
         Ok(())
     }
 
+    /// Store the details found by the metadata task, leaving the fetch time
+    /// and error to the regular refresh
+    pub async fn update_details(&self, id: Uuid, metadata: &FeedMetadata) -> Result<()> {
+        let now = Utc::now();
+        let pool = self.db.pool().clone();
+        let id_str = id.to_string();
+        let metadata = metadata.clone();
+
+        self.db.execute_with_retry(|| {
+            let pool = pool.clone();
+            let id_str = id_str.clone();
+            let metadata = metadata.clone();
+            async move {
+                sqlx::query(
+                    r#"
+                    UPDATE feeds
+                    SET title = COALESCE(?, title),
+                        description = COALESCE(?, description),
+                        site_url = COALESCE(?, site_url),
+                        icon_url = COALESCE(?, icon_url),
+                        moved_to = ?,
+                        updated_at = ?
+                    WHERE id = ?
+                    "#,
+                )
+                .bind(&metadata.title)
+                .bind(&metadata.description)
+                .bind(&metadata.site_url)
+                .bind(&metadata.icon_url)
+                .bind(&metadata.moved_to)
+                .bind(now)
+                .bind(&id_str)
+                .execute(&pool)
+                .await
+                .map(|_| ())
+            }
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Update the user-editable settings of a feed
+    pub async fn update_settings(&self, id: Uuid, settings: &FeedSettings) -> Result<bool> {
+        let now = Utc::now();
+        let pool = self.db.pool().clone();
+        let id_str = id.to_string();
+        let settings = settings.clone();
+
+        let result = self.db.query_with_retry(|| {
+            let pool = pool.clone();
+            let id_str = id_str.clone();
+            let settings = settings.clone();
+            async move {
+                sqlx::query(
+                    r#"
+                    UPDATE feeds
+                    SET moved_to = CASE WHEN url = ? THEN moved_to END,
+                        url = ?,
+                        local_name = ?,
+                        category = ?,
+                        paused = ?,
+                        refresh_interval_secs = ?,
+                        auto_read_days = ?,
+                        updated_at = ?
+                    WHERE id = ?
+                    "#,
+                )
+                .bind(&settings.url)
+                .bind(&settings.url)
+                .bind(&settings.local_name)
+                .bind(&settings.category)
+                .bind(settings.paused)
+                .bind(settings.refresh_interval_secs.map(|s| s as i64))
+                .bind(settings.auto_read_days.map(i64::from))
+                .bind(now)
+                .bind(&id_str)
+                .execute(&pool)
+                .await
+            }
+        })
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Posting rate, reading and relevance statistics of a feed, aggregated
+    /// over its stored articles
+    pub async fn stats(&self, id: Uuid) -> Result<FeedStats> {
+        let pool = self.db.pool().clone();
+        let id_str = id.to_string();
+        let since = Utc::now() - chrono::Duration::weeks(FeedStats::RECENT_WEEKS);
+
+        let row: FeedStatsRow = self.db.query_with_retry(|| {
+            let pool = pool.clone();
+            let id_str = id_str.clone();
+            async move {
+                sqlx::query_as(
+                    r#"
+                    SELECT COUNT(*) AS total,
+                           SUM(is_read = 0) AS unread,
+                           SUM(is_saved) AS saved,
+                           SUM(COALESCE(published_at, fetched_at) >= ?) AS recent,
+                           AVG(relevance_score) AS average_relevance,
+                           MAX(COALESCE(published_at, fetched_at)) AS last_post_at
+                    FROM articles
+                    WHERE feed_id = ?
+                    "#,
+                )
+                .bind(since)
+                .bind(&id_str)
+                .fetch_one(&pool)
+                .await
+            }
+        })
+        .await?;
+
+        Ok(FeedStats {
+            total: row.total as u32,
+            unread: row.unread.unwrap_or(0) as u32,
+            saved: row.saved.unwrap_or(0) as u32,
+            recent: row.recent.unwrap_or(0) as u32,
+            average_relevance: row.average_relevance,
+            last_post_at: row.last_post_at,
+        })
+    }
+
+    /// Store a manual feed order: each listed feed gets its index as position.
+    /// Feeds not listed keep their position.
+    pub async fn reorder(&self, ids: &[Uuid]) -> Result<()> {
+        let pool = self.db.pool().clone();
+        let ids: Vec<String> = ids.iter().map(Uuid::to_string).collect();
+
+        self.db.execute_with_retry(|| {
+            let pool = pool.clone();
+            let ids = ids.clone();
+            async move {
+                let mut tx = pool.begin().await?;
+                for (position, id_str) in ids.iter().enumerate() {
+                    sqlx::query("UPDATE feeds SET position = ? WHERE id = ?")
+                        .bind(position as i64)
+                        .bind(id_str)
+                        .execute(&mut *tx)
+                        .await?;
+                }
+                tx.commit().await
+            }
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Pin a feed to the top of the list, or unpin it
+    pub async fn set_pinned(&self, id: Uuid, pinned: bool) -> Result<bool> {
+        let now = Utc::now();
+        let pool = self.db.pool().clone();
+        let id_str = id.to_string();
+
+        let result = self.db.query_with_retry(|| {
+            let pool = pool.clone();
+            let id_str = id_str.clone();
+            async move {
+                sqlx::query("UPDATE feeds SET pinned = ?, updated_at = ? WHERE id = ?")
+                    .bind(pinned)
+                    .bind(now)
+                    .bind(&id_str)
+                    .execute(&pool)
+                    .await
+            }
+        })
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Put a feed in a category, or take it out of its category (None or
+    /// blank)
+    pub async fn set_category(&self, id: Uuid, category: Option<&str>) -> Result<bool> {
+        let now = Utc::now();
+        let pool = self.db.pool().clone();
+        let id_str = id.to_string();
+        let category = category.map(str::trim).filter(|c| !c.is_empty()).map(str::to_string);
+
+        let result = self.db.query_with_retry(|| {
+            let pool = pool.clone();
+            let id_str = id_str.clone();
+            let category = category.clone();
+            async move {
+                sqlx::query("UPDATE feeds SET category = ?, updated_at = ? WHERE id = ?")
+                    .bind(&category)
+                    .bind(now)
+                    .bind(&id_str)
+                    .execute(&pool)
+                    .await
+            }
+        })
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Record the URL a feed permanently redirects to (None: it no longer does)
+    pub async fn set_moved_to(&self, id: Uuid, moved_to: Option<&str>) -> Result<()> {
+        let pool = self.db.pool().clone();
+        let id_str = id.to_string();
+        let moved_to = moved_to.map(|s| s.to_string());
+
+        self.db.execute_with_retry(|| {
+            let pool = pool.clone();
+            let id_str = id_str.clone();
+            let moved_to = moved_to.clone();
+            async move {
+                sqlx::query("UPDATE feeds SET moved_to = ? WHERE id = ?")
+                    .bind(&moved_to)
+                    .bind(&id_str)
+                    .execute(&pool)
+                    .await
+                    .map(|_| ())
+            }
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Switch a feed to the URL it moved to, keeping its articles and
+    /// settings. Returns false when another subscription already has that URL.
+    pub async fn migrate_url(&self, id: Uuid, new_url: &str) -> Result<bool> {
+        let now = Utc::now();
+        let pool = self.db.pool().clone();
+        let id_str = id.to_string();
+        let new_url = new_url.to_string();
+
+        let result = self.db.query_with_retry(|| {
+            let pool = pool.clone();
+            let id_str = id_str.clone();
+            let new_url = new_url.clone();
+            async move {
+                sqlx::query(
+                    r#"
+                    UPDATE feeds
+                    SET url = ?,
+                        moved_to = NULL,
+                        updated_at = ?
+                    WHERE id = ?
+                      AND NOT EXISTS (SELECT 1 FROM feeds WHERE url = ?)
+                    "#,
+                )
+                .bind(&new_url)
+                .bind(now)
+                .bind(&id_str)
+                .bind(&new_url)
+                .execute(&pool)
+                .await
+            }
+        })
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
     /// Update feed fetch error
     pub async fn update_fetch_error(&self, id: Uuid, error: &str) -> Result<()> {
         let now = Utc::now();
@@ -301,7 +607,7 @@ impl<'a> FeedRepository<'a> {
         let id_str = id.to_string();
         let error = error.to_string();
 
-        execute_with_retry(|| {
+        self.db.execute_with_retry(|| {
             let pool = pool.clone();
             let id_str = id_str.clone();
             let error = error.clone();
@@ -332,7 +638,7 @@ impl<'a> FeedRepository<'a> {
         let pool = self.db.pool().clone();
         let id_str = id.to_string();
 
-        let result = query_with_retry(|| {
+        let result = self.db.query_with_retry(|| {
             let pool = pool.clone();
             let id_str = id_str.clone();
             async move {
@@ -351,7 +657,7 @@ impl<'a> FeedRepository<'a> {
     pub async fn count(&self) -> Result<u32> {
         let pool = self.db.pool().clone();
 
-        let count: (i64,) = query_with_retry(|| {
+        let count: (i64,) = self.db.query_with_retry(|| {
             let pool = pool.clone();
             async move {
                 sqlx::query_as("SELECT COUNT(*) FROM feeds")
@@ -364,3 +670,207 @@ impl<'a> FeedRepository<'a> {
         Ok(count.0 as u32)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_list_needs_refresh_respects_feed_settings() {
+        let db = Database::new_in_memory().await.unwrap();
+        let repo = FeedRepository::new(&db);
+        let quiet = repo
+            .create(&NewFeed {
+                url: "https://example.com/quiet".to_string(),
+                local_name: "quiet".to_string(),
+            })
+            .await
+            .unwrap();
+        let weekly = repo
+            .create(&NewFeed {
+                url: "https://example.com/weekly".to_string(),
+                local_name: "weekly".to_string(),
+            })
+            .await
+            .unwrap();
+        repo.update_metadata(weekly.id, None, None, None, None).await.unwrap();
+
+        let mut settings = quiet.settings();
+        settings.paused = true;
+        settings.category = Some("News".to_string());
+        assert!(repo.update_settings(quiet.id, &settings).await.unwrap());
+
+        let mut settings = weekly.settings();
+        settings.refresh_interval_secs = Some(7 * 24 * 3600);
+        repo.update_settings(weekly.id, &settings).await.unwrap();
+
+        // Paused and recently fetched feeds are skipped, even with a zero default interval
+        assert!(repo.list_needs_refresh(0, 0).await.unwrap().is_empty());
+
+        let quiet = repo.find_by_id(quiet.id).await.unwrap().unwrap();
+        assert!(quiet.paused);
+        assert_eq!(quiet.category.as_deref(), Some("News"));
+    }
+
+    #[tokio::test]
+    async fn test_list_all_follows_manual_order() {
+        let db = Database::new_in_memory().await.unwrap();
+        let repo = FeedRepository::new(&db);
+        let mut ids = Vec::new();
+        for name in ["b", "a", "c"] {
+            let feed = repo
+                .create(&NewFeed {
+                    url: format!("https://example.com/{}", name),
+                    local_name: name.to_string(),
+                })
+                .await
+                .unwrap();
+            ids.push(feed.id);
+        }
+        let names = |feeds: Vec<Feed>| feeds.into_iter().map(|f| f.local_name).collect::<Vec<_>>();
+
+        // New feeds are appended
+        assert_eq!(names(repo.list_all().await.unwrap()), ["b", "a", "c"]);
+
+        repo.reorder(&[ids[2], ids[0], ids[1]]).await.unwrap();
+        assert_eq!(names(repo.list_all().await.unwrap()), ["c", "b", "a"]);
+
+        assert!(repo.set_pinned(ids[1], true).await.unwrap());
+        let feeds = repo.list_all().await.unwrap();
+        assert!(feeds[0].pinned);
+        assert_eq!(names(feeds), ["a", "c", "b"]);
+    }
+
+    #[tokio::test]
+    async fn test_set_category() {
+        let db = Database::new_in_memory().await.unwrap();
+        let repo = FeedRepository::new(&db);
+        let feed = repo
+            .create(&NewFeed {
+                url: "https://example.com/feed".to_string(),
+                local_name: "feed".to_string(),
+            })
+            .await
+            .unwrap();
+
+        assert!(repo.set_category(feed.id, Some(" Tech ")).await.unwrap());
+        let category = |feed: Option<Feed>| feed.unwrap().category;
+        assert_eq!(category(repo.find_by_id(feed.id).await.unwrap()).as_deref(), Some("Tech"));
+        assert!(repo.set_category(feed.id, Some("  ")).await.unwrap());
+        assert_eq!(category(repo.find_by_id(feed.id).await.unwrap()), None);
+        assert!(!repo.set_category(Uuid::new_v4(), None).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_moved_feed_cleared_by_new_url() {
+        let db = Database::new_in_memory().await.unwrap();
+        let repo = FeedRepository::new(&db);
+        let feed = repo
+            .create(&NewFeed {
+                url: "http://example.com/feed".to_string(),
+                local_name: "feed".to_string(),
+            })
+            .await
+            .unwrap();
+        let metadata = FeedMetadata {
+            title: Some("Example".to_string()),
+            moved_to: Some("https://example.com/feed.xml".to_string()),
+            ..FeedMetadata::default()
+        };
+        repo.update_details(feed.id, &metadata).await.unwrap();
+        let feed = repo.find_by_id(feed.id).await.unwrap().unwrap();
+        assert_eq!(feed.title.as_deref(), Some("Example"));
+        assert_eq!(feed.moved_to.as_deref(), Some("https://example.com/feed.xml"));
+        // The regular refresh leaves the redirect target alone
+        assert!(feed.last_fetched_at.is_none());
+
+        // Other settings keep it, switching to the new URL clears it
+        let mut settings = feed.settings();
+        settings.category = Some("News".to_string());
+        repo.update_settings(feed.id, &settings).await.unwrap();
+        assert!(repo.find_by_id(feed.id).await.unwrap().unwrap().moved_to.is_some());
+        settings.url = metadata.moved_to.clone().unwrap();
+        repo.update_settings(feed.id, &settings).await.unwrap();
+        assert!(repo.find_by_id(feed.id).await.unwrap().unwrap().moved_to.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_migrate_url() {
+        let db = Database::new_in_memory().await.unwrap();
+        let repo = FeedRepository::new(&db);
+        let mut feeds = Vec::new();
+        for name in ["a", "b"] {
+            let feed = repo
+                .create(&NewFeed {
+                    url: format!("https://example.com/{}", name),
+                    local_name: name.to_string(),
+                })
+                .await
+                .unwrap();
+            repo.set_moved_to(feed.id, Some("https://example.org/feed")).await.unwrap();
+            feeds.push(feed);
+        }
+
+        assert!(repo.migrate_url(feeds[0].id, "https://example.org/feed").await.unwrap());
+        let migrated = repo.find_by_id(feeds[0].id).await.unwrap().unwrap();
+        assert_eq!(migrated.url, "https://example.org/feed");
+        assert_eq!(migrated.moved_to, None);
+
+        // The URL is taken now; the other feed stays flagged
+        assert!(!repo.migrate_url(feeds[1].id, "https://example.org/feed").await.unwrap());
+        let flagged = repo.find_by_id(feeds[1].id).await.unwrap().unwrap();
+        assert_eq!(flagged.url, "https://example.com/b");
+        assert!(flagged.moved_to.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_feed_stats() {
+        use crate::feed::NewArticle;
+        use crate::storage::ArticleRepository;
+
+        let db = Database::new_in_memory().await.unwrap();
+        let repo = FeedRepository::new(&db);
+        let feed = repo
+            .create(&NewFeed {
+                url: "https://example.com/feed".to_string(),
+                local_name: "feed".to_string(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(repo.stats(feed.id).await.unwrap(), FeedStats::default());
+
+        let articles = ArticleRepository::new(&db);
+        let now = Utc::now();
+        let mut ids = Vec::new();
+        for (i, days_ago) in [1, 10, 100].into_iter().enumerate() {
+            let article = articles
+                .create(&NewArticle {
+                    feed_id: feed.id,
+                    guid: format!("guid-{}", i),
+                    url: None,
+                    title: format!("Article {}", i),
+                    author: None,
+                    content: None,
+                    content_text: None,
+                    published_at: Some(now - chrono::Duration::days(days_ago)),
+                    image_url: None,
+                    media: None,
+                    comments_url: None,
+                })
+                .await
+                .unwrap()
+                .unwrap();
+            ids.push(article.id);
+        }
+        articles.mark_read(ids[2]).await.unwrap();
+        articles.toggle_saved(ids[0]).await.unwrap();
+        articles.update_relevance_score(ids[0], 0.8).await.unwrap();
+        articles.update_relevance_score(ids[1], 0.4).await.unwrap();
+
+        let stats = repo.stats(feed.id).await.unwrap();
+        assert_eq!((stats.total, stats.unread, stats.saved, stats.recent), (3, 2, 1, 2));
+        assert!((stats.average_relevance.unwrap() - 0.6).abs() < 1e-9);
+        let last_post = stats.last_post_at.unwrap();
+        assert!((last_post - (now - chrono::Duration::days(1))).num_seconds().abs() < 2);
+    }
+}
@@ -0,0 +1,139 @@
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions, TryLockError};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+use crate::config::AppConfig;
+use crate::Result;
+
+/// How often `WriterLock::acquire` retries while another process holds the lock
+const ACQUIRE_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Writer locks this process holds, by lock file, with the number of
+/// `WriterLock` values sharing each
+static HELD: Mutex<BTreeMap<PathBuf, HeldLock>> = Mutex::new(BTreeMap::new());
+
+#[derive(Debug)]
+struct HeldLock {
+    _file: File,
+    users: usize,
+}
+
+/// Advisory lock marking one local process as the database writer.
+///
+/// The daemon holds it for its whole lifetime; CLI commands take it only when
+/// no daemon is running and otherwise send their writes to the daemon. Every
+/// value taken in one process shares the same lock, which is released when
+/// the last of them is dropped or the process exits.
+#[derive(Debug)]
+pub struct WriterLock {
+    path: PathBuf,
+}
+
+impl WriterLock {
+    /// Take the writer lock without blocking, sharing it if this process
+    /// already holds it. Returns `None` if another process holds it.
+    pub fn try_acquire(config: &AppConfig) -> Result<Option<Self>> {
+        let path = config.lock_path();
+        let mut held = held();
+        if let Some(lock) = held.get_mut(&path) {
+            lock.users += 1;
+            return Ok(Some(Self { path }));
+        }
+
+        let file = Self::open(&path)?;
+        match file.try_lock() {
+            Ok(()) => {
+                held.insert(path.clone(), HeldLock { _file: file, users: 1 });
+                Ok(Some(Self { path }))
+            }
+            Err(TryLockError::WouldBlock) => Ok(None),
+            Err(TryLockError::Error(e)) => Err(e.into()),
+        }
+    }
+
+    /// Wait until the writer lock is free, then take it
+    pub async fn acquire(config: &AppConfig) -> Result<Self> {
+        // Polling keeps every lock taken in this process under `HELD`, so
+        // a concurrent `try_acquire` shares it instead of seeing it taken
+        loop {
+            if let Some(lock) = Self::try_acquire(config)? {
+                return Ok(lock);
+            }
+            tokio::time::sleep(ACQUIRE_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Check if this process holds the writer lock
+    pub fn is_held_here(config: &AppConfig) -> bool {
+        held().contains_key(&config.lock_path())
+    }
+
+    /// Check if a different process currently owns the database
+    pub fn is_held_elsewhere(config: &AppConfig) -> Result<bool> {
+        Ok(Self::try_acquire(config)?.is_none())
+    }
+
+    fn open(path: &Path) -> Result<File> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        Ok(OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(path)?)
+    }
+}
+
+impl Drop for WriterLock {
+    fn drop(&mut self) {
+        let mut held = held();
+        if let Some(lock) = held.get_mut(&self.path) {
+            lock.users -= 1;
+            if lock.users == 0 {
+                // Closing the file releases the lock
+                held.remove(&self.path);
+            }
+        }
+    }
+}
+
+/// The locks held by this process
+fn held() -> MutexGuard<'static, BTreeMap<PathBuf, HeldLock>> {
+    HELD.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_writer_lock_is_exclusive() {
+        let dir = std::env::temp_dir().join(format!("kenseader-lock-{}", uuid::Uuid::new_v4()));
+        let mut config = AppConfig::default();
+        config.general.data_dir = dir.clone();
+
+        let lock = WriterLock::try_acquire(&config).unwrap().expect("lock should be free");
+        // Another open file description conflicts, like another process would
+        let other = WriterLock::open(&config.lock_path()).unwrap();
+        assert!(other.try_lock().is_err());
+        assert!(!WriterLock::is_held_elsewhere(&config).unwrap());
+
+        // Users in this process share the lock until the last one is dropped
+        let shared = WriterLock::try_acquire(&config).unwrap().expect("lock should be shared");
+        drop(lock);
+        assert!(WriterLock::is_held_here(&config));
+        assert!(other.try_lock().is_err());
+        drop(shared);
+        assert!(!WriterLock::is_held_here(&config));
+
+        other.try_lock().unwrap();
+        assert!(WriterLock::try_acquire(&config).unwrap().is_none());
+        drop(other);
+        assert!(WriterLock::try_acquire(&config).unwrap().is_some());
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
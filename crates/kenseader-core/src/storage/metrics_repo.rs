@@ -0,0 +1,197 @@
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use super::Database;
+use crate::feed::{FetchMetric, FetchProfile};
+use crate::Result;
+
+/// Days fetch metrics are kept
+pub const METRICS_RETENTION_DAYS: i64 = 30;
+
+/// Repository for the timing of feed fetches
+pub struct FetchMetricsRepository<'a> {
+    db: &'a Database,
+}
+
+#[derive(FromRow)]
+struct FetchProfileRow {
+    feed_id: String,
+    name: String,
+    fetches: i64,
+    errors: Option<i64>,
+    avg_fetch_ms: Option<f64>,
+    avg_parse_ms: Option<f64>,
+    avg_store_ms: Option<f64>,
+    max_total_ms: Option<i64>,
+    avg_bytes: Option<f64>,
+    inserted: Option<i64>,
+}
+
+impl From<FetchProfileRow> for FetchProfile {
+    fn from(row: FetchProfileRow) -> Self {
+        FetchProfile {
+            feed_id: Uuid::parse_str(&row.feed_id).unwrap_or_default(),
+            name: row.name,
+            fetches: row.fetches as u32,
+            errors: row.errors.unwrap_or(0) as u32,
+            avg_fetch_ms: row.avg_fetch_ms.unwrap_or(0.0),
+            avg_parse_ms: row.avg_parse_ms.unwrap_or(0.0),
+            avg_store_ms: row.avg_store_ms.unwrap_or(0.0),
+            max_total_ms: row.max_total_ms.unwrap_or(0) as u64,
+            avg_bytes: row.avg_bytes.unwrap_or(0.0),
+            inserted: row.inserted.unwrap_or(0) as u32,
+        }
+    }
+}
+
+impl<'a> FetchMetricsRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        Self { db }
+    }
+
+    /// Record the timing of a fetch
+    pub async fn record(&self, metric: &FetchMetric) -> Result<()> {
+        let pool = self.db.pool().clone();
+        let feed_id = metric.feed_id.to_string();
+
+        self.db.execute_with_retry(|| {
+            let pool = pool.clone();
+            let feed_id = feed_id.clone();
+            async move {
+                sqlx::query(
+                    r#"
+                    INSERT INTO fetch_metrics
+                        (feed_id, fetched_at, fetch_ms, parse_ms, store_ms, bytes, articles, inserted, error)
+                    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+                    "#,
+                )
+                .bind(&feed_id)
+                .bind(Utc::now())
+                .bind(metric.fetch_ms as i64)
+                .bind(metric.parse_ms as i64)
+                .bind(metric.store_ms as i64)
+                .bind(metric.bytes as i64)
+                .bind(metric.articles)
+                .bind(metric.inserted)
+                .bind(&metric.error)
+                .execute(&pool)
+                .await
+                .map(|_| ())
+            }
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Fetch performance of each feed fetched since `since`, slowest on
+    /// average first
+    pub async fn profile(&self, since: DateTime<Utc>) -> Result<Vec<FetchProfile>> {
+        let pool = self.db.pool().clone();
+
+        let rows: Vec<FetchProfileRow> = self.db.query_with_retry(|| {
+            let pool = pool.clone();
+            async move {
+                sqlx::query_as(
+                    r#"
+                    SELECT m.feed_id, f.local_name AS name,
+                           COUNT(*) AS fetches,
+                           SUM(m.error IS NOT NULL) AS errors,
+                           AVG(m.fetch_ms) AS avg_fetch_ms,
+                           AVG(m.parse_ms) AS avg_parse_ms,
+                           AVG(m.store_ms) AS avg_store_ms,
+                           MAX(m.fetch_ms + m.parse_ms + m.store_ms) AS max_total_ms,
+                           AVG(m.bytes) AS avg_bytes,
+                           SUM(m.inserted) AS inserted
+                    FROM fetch_metrics m
+                    JOIN feeds f ON f.id = m.feed_id
+                    WHERE m.fetched_at >= ?
+                    GROUP BY m.feed_id
+                    ORDER BY AVG(m.fetch_ms + m.parse_ms + m.store_ms) DESC
+                    "#,
+                )
+                .bind(since)
+                .fetch_all(&pool)
+                .await
+            }
+        })
+        .await?;
+
+        Ok(rows.into_iter().map(FetchProfile::from).collect())
+    }
+
+    /// Delete metrics older than `METRICS_RETENTION_DAYS`
+    pub async fn cleanup(&self) -> Result<u64> {
+        let pool = self.db.pool().clone();
+        let cutoff = Utc::now() - chrono::Duration::days(METRICS_RETENTION_DAYS);
+
+        let deleted = self.db.query_with_retry(|| {
+            let pool = pool.clone();
+            async move {
+                sqlx::query("DELETE FROM fetch_metrics WHERE fetched_at < ?")
+                    .bind(cutoff)
+                    .execute(&pool)
+                    .await
+                    .map(|result| result.rows_affected())
+            }
+        })
+        .await?;
+
+        Ok(deleted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feed::NewFeed;
+    use crate::storage::FeedRepository;
+
+    #[tokio::test]
+    async fn test_profile_orders_slowest_first() {
+        let db = Database::new_in_memory().await.unwrap();
+        let feeds = FeedRepository::new(&db);
+        let fast = feeds
+            .create(&NewFeed {
+                url: "https://example.com/fast".to_string(),
+                local_name: "fast".to_string(),
+            })
+            .await
+            .unwrap();
+        let slow = feeds
+            .create(&NewFeed {
+                url: "https://example.com/slow".to_string(),
+                local_name: "slow".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let repo = FetchMetricsRepository::new(&db);
+        let metric = |feed_id, fetch_ms, error: Option<&str>| FetchMetric {
+            feed_id,
+            fetch_ms,
+            parse_ms: 10,
+            store_ms: 5,
+            bytes: 2048,
+            articles: 20,
+            inserted: 2,
+            error: error.map(str::to_string),
+        };
+        repo.record(&metric(fast.id, 100, None)).await.unwrap();
+        repo.record(&metric(slow.id, 3000, None)).await.unwrap();
+        repo.record(&metric(slow.id, 9000, Some("timed out"))).await.unwrap();
+
+        let profile = repo.profile(Utc::now() - chrono::Duration::hours(1)).await.unwrap();
+        assert_eq!(profile.len(), 2);
+        assert_eq!(profile[0].name, "slow");
+        assert_eq!(profile[0].fetches, 2);
+        assert_eq!(profile[0].errors, 1);
+        assert_eq!(profile[0].max_total_ms, 9015);
+        assert_eq!(profile[0].avg_total_ms(), 6015.0);
+        assert_eq!(profile[0].inserted, 4);
+        assert_eq!(profile[1].name, "fast");
+
+        assert!(repo.profile(Utc::now() + chrono::Duration::hours(1)).await.unwrap().is_empty());
+    }
+}
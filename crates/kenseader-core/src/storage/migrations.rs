@@ -0,0 +1,605 @@
+//! Versioned schema migrations
+//!
+//! Each migration runs once, in order, inside a transaction, and is recorded in
+//! the `schema_version` table. Databases created before versioning start at
+//! version 0; their migrations are idempotent (`IF NOT EXISTS`, duplicate
+//! columns ignored), so replaying them only records the versions.
+
+use sqlx::{Pool, Row, Sqlite, Transaction};
+
+use crate::feed::canonicalize_url;
+use crate::{Error, Result};
+
+/// A versioned schema change
+#[derive(Debug)]
+pub struct Migration {
+    pub version: u32,
+    pub description: &'static str,
+    statements: &'static [&'static str],
+}
+
+/// All migrations in version order. Append new ones; never edit applied ones.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "create feeds table",
+        statements: &[MIGRATION_001_FEEDS],
+    },
+    Migration {
+        version: 2,
+        description: "create articles table",
+        statements: &[MIGRATION_002_ARTICLES],
+    },
+    Migration {
+        version: 3,
+        description: "create article tags table",
+        statements: &[MIGRATION_003_ARTICLE_TAGS],
+    },
+    Migration {
+        version: 4,
+        description: "create behavior events table",
+        statements: &[MIGRATION_004_BEHAVIOR_EVENTS],
+    },
+    Migration {
+        version: 5,
+        description: "create user preferences table",
+        statements: &[MIGRATION_005_USER_PREFERENCES],
+    },
+    Migration {
+        version: 6,
+        description: "create article styles table",
+        statements: &[MIGRATION_006_ARTICLE_STYLES],
+    },
+    Migration {
+        version: 7,
+        description: "add article image url",
+        statements: &[MIGRATION_007_ARTICLE_IMAGE_URL],
+    },
+    Migration {
+        version: 8,
+        description: "add article relevance score",
+        statements: &[MIGRATION_008_ARTICLE_RELEVANCE_SCORE],
+    },
+    Migration {
+        version: 9,
+        description: "add article media attachments",
+        statements: MIGRATION_009_ARTICLE_MEDIA,
+    },
+    Migration {
+        version: 10,
+        description: "add article content hash",
+        statements: MIGRATION_010_ARTICLE_CONTENT_HASH,
+    },
+    Migration {
+        version: 11,
+        description: "create indexes",
+        statements: &[MIGRATION_011_INDEXES],
+    },
+    Migration {
+        version: 12,
+        description: "add article state change time",
+        statements: &[MIGRATION_012_ARTICLE_STATE_CHANGED_AT],
+    },
+    Migration {
+        version: 13,
+        description: "add feed settings",
+        statements: MIGRATION_013_FEED_SETTINGS,
+    },
+    Migration {
+        version: 14,
+        description: "add manual feed ordering and pinning",
+        statements: MIGRATION_014_FEED_ORDER,
+    },
+    Migration {
+        version: 15,
+        description: "index article read time",
+        statements: &[MIGRATION_015_READ_AT_INDEX],
+    },
+    Migration {
+        version: 16,
+        description: "add feed auto-read period",
+        statements: &[MIGRATION_016_FEED_AUTO_READ],
+    },
+    Migration {
+        version: 17,
+        description: "add article comments url",
+        statements: &[MIGRATION_017_ARTICLE_COMMENTS_URL],
+    },
+    Migration {
+        version: 18,
+        description: "add feed permanent redirect target",
+        statements: &[MIGRATION_018_FEED_MOVED_TO],
+    },
+    Migration {
+        version: 19,
+        description: "add article exports to read-later services",
+        statements: &[MIGRATION_019_ARTICLE_EXPORTS],
+    },
+    Migration {
+        version: 20,
+        description: "create fetch metrics table",
+        statements: MIGRATION_020_FETCH_METRICS,
+    },
+    Migration {
+        version: 21,
+        description: "create filtered articles table",
+        statements: &[MIGRATION_021_FILTERED_ARTICLES],
+    },
+    Migration {
+        version: 22,
+        description: "create article alerts table",
+        statements: &[MIGRATION_022_ARTICLE_ALERTS],
+    },
+    Migration {
+        version: 23,
+        description: "create cve tables",
+        statements: &[MIGRATION_023_CVES],
+    },
+    Migration {
+        version: 24,
+        description: "canonicalize stored article urls",
+        statements: &[],
+    },
+    Migration {
+        version: 25,
+        description: "add article export errors",
+        statements: &[MIGRATION_025_ARTICLE_EXPORT_ERROR],
+    },
+    Migration {
+        version: 26,
+        description: "create daily preference event sums",
+        statements: MIGRATION_026_PREFERENCE_DAILY_EVENTS,
+    },
+];
+
+/// Latest schema version known to this build
+pub fn latest_version() -> u32 {
+    MIGRATIONS.last().map(|m| m.version).unwrap_or(0)
+}
+
+/// Highest applied schema version (0 for new or pre-versioning databases)
+pub(super) async fn current_version(pool: &Pool<Sqlite>) -> Result<u32> {
+    let has_table: Option<(String,)> = sqlx::query_as(
+        "SELECT name FROM sqlite_master WHERE type = 'table' AND name = 'schema_version'",
+    )
+    .fetch_optional(pool)
+    .await?;
+    if has_table.is_none() {
+        return Ok(0);
+    }
+
+    let row = sqlx::query("SELECT COALESCE(MAX(version), 0) AS version FROM schema_version")
+        .fetch_one(pool)
+        .await?;
+    Ok(row.get::<i64, _>("version") as u32)
+}
+
+/// Migrations not yet applied, in order
+pub(super) async fn pending(pool: &Pool<Sqlite>) -> Result<Vec<&'static Migration>> {
+    let current = current_version(pool).await?;
+    let latest = latest_version();
+    if current > latest {
+        return Err(Error::Other(format!(
+            "Database schema version {} is newer than this build supports ({}). \
+             Please upgrade kenseader.",
+            current, latest
+        )));
+    }
+
+    Ok(MIGRATIONS.iter().filter(|m| m.version > current).collect())
+}
+
+/// Apply all pending migrations, returning the ones applied
+pub(super) async fn run(pool: &Pool<Sqlite>) -> Result<Vec<&'static Migration>> {
+    sqlx::query(SCHEMA_VERSION_TABLE).execute(pool).await?;
+
+    let pending = pending(pool).await?;
+    for migration in &pending {
+        tracing::info!(
+            "Applying migration {:03}: {}",
+            migration.version,
+            migration.description
+        );
+        apply(pool, migration).await.map_err(|e| {
+            Error::Other(format!(
+                "Migration {:03} ({}) failed: {}. The database was left at version {}.",
+                migration.version,
+                migration.description,
+                e,
+                migration.version - 1
+            ))
+        })?;
+    }
+
+    Ok(pending)
+}
+
+/// Apply one migration and record it, atomically
+async fn apply(pool: &Pool<Sqlite>, migration: &Migration) -> std::result::Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    for statement in migration.statements {
+        if let Err(err) = sqlx::query(statement).execute(&mut *tx).await {
+            // Columns may already exist in databases created before versioning
+            if !is_duplicate_column_error(&err) {
+                return Err(err);
+            }
+        }
+    }
+    rewrite_rows(&mut tx, migration.version).await?;
+
+    sqlx::query("INSERT INTO schema_version (version, description) VALUES (?, ?)")
+        .bind(migration.version as i64)
+        .bind(migration.description)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await
+}
+
+/// Data changes SQL can't express, run after a migration's statements
+async fn rewrite_rows(
+    tx: &mut Transaction<'_, Sqlite>,
+    version: u32,
+) -> std::result::Result<(), sqlx::Error> {
+    match version {
+        // Articles stored before URLs were canonicalized would never match the
+        // canonical URL of a re-published article
+        24 => {
+            let rows: Vec<(String, String)> =
+                sqlx::query_as("SELECT id, url FROM articles WHERE url IS NOT NULL")
+                    .fetch_all(&mut **tx)
+                    .await?;
+            for (id, url) in rows {
+                let canonical = canonicalize_url(&url);
+                if canonical != url {
+                    sqlx::query("UPDATE articles SET url = ? WHERE id = ?")
+                        .bind(&canonical)
+                        .bind(&id)
+                        .execute(&mut **tx)
+                        .await?;
+                }
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+fn is_duplicate_column_error(err: &sqlx::Error) -> bool {
+    match err {
+        sqlx::Error::Database(db_err) => {
+            let message = db_err.message().to_lowercase();
+            message.contains("duplicate column")
+        }
+        _ => false,
+    }
+}
+
+const SCHEMA_VERSION_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS schema_version (
+    version INTEGER PRIMARY KEY,
+    description TEXT NOT NULL,
+    applied_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+)
+"#;
+
+const MIGRATION_001_FEEDS: &str = r#"
+CREATE TABLE IF NOT EXISTS feeds (
+    id TEXT PRIMARY KEY,
+    url TEXT NOT NULL UNIQUE,
+    local_name TEXT NOT NULL,
+    title TEXT,
+    description TEXT,
+    site_url TEXT,
+    icon_url TEXT,
+    last_fetched_at DATETIME,
+    fetch_error TEXT,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+)
+"#;
+
+const MIGRATION_002_ARTICLES: &str = r#"
+CREATE TABLE IF NOT EXISTS articles (
+    id TEXT PRIMARY KEY,
+    feed_id TEXT NOT NULL REFERENCES feeds(id) ON DELETE CASCADE,
+    guid TEXT NOT NULL,
+    url TEXT,
+    title TEXT NOT NULL,
+    author TEXT,
+    content TEXT,
+    content_text TEXT,
+    summary TEXT,
+    summary_generated_at DATETIME,
+    published_at DATETIME,
+    fetched_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    is_read INTEGER NOT NULL DEFAULT 0,
+    read_at DATETIME,
+    is_saved INTEGER NOT NULL DEFAULT 0,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(feed_id, guid)
+)
+"#;
+
+const MIGRATION_003_ARTICLE_TAGS: &str = r#"
+CREATE TABLE IF NOT EXISTS article_tags (
+    article_id TEXT NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+    tag TEXT NOT NULL,
+    source TEXT NOT NULL DEFAULT 'ai',
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (article_id, tag)
+)
+"#;
+
+const MIGRATION_004_BEHAVIOR_EVENTS: &str = r#"
+CREATE TABLE IF NOT EXISTS behavior_events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    article_id TEXT REFERENCES articles(id) ON DELETE SET NULL,
+    feed_id TEXT REFERENCES feeds(id) ON DELETE SET NULL,
+    event_type TEXT NOT NULL,
+    event_data TEXT,
+    reading_duration_ms INTEGER,
+    scroll_depth_percent INTEGER,
+    context_time_of_day TEXT,
+    context_day_of_week INTEGER,
+    context_network_type TEXT,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+)
+"#;
+
+const MIGRATION_005_USER_PREFERENCES: &str = r#"
+CREATE TABLE IF NOT EXISTS user_preferences (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    preference_type TEXT NOT NULL,
+    preference_key TEXT NOT NULL,
+    weight REAL NOT NULL DEFAULT 0.0,
+    time_window TEXT NOT NULL,
+    computed_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(preference_type, preference_key, time_window)
+)
+"#;
+
+const MIGRATION_006_ARTICLE_STYLES: &str = r#"
+CREATE TABLE IF NOT EXISTS article_styles (
+    article_id TEXT PRIMARY KEY REFERENCES articles(id) ON DELETE CASCADE,
+    style_type TEXT,
+    tone TEXT,
+    length_category TEXT,
+    computed_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+)
+"#;
+
+const MIGRATION_011_INDEXES: &str = r#"
+CREATE INDEX IF NOT EXISTS idx_feeds_local_name ON feeds(local_name);
+CREATE INDEX IF NOT EXISTS idx_articles_feed_id ON articles(feed_id);
+CREATE INDEX IF NOT EXISTS idx_articles_feed_url ON articles(feed_id, url);
+CREATE INDEX IF NOT EXISTS idx_articles_is_read ON articles(is_read);
+CREATE INDEX IF NOT EXISTS idx_articles_published_at ON articles(published_at DESC);
+CREATE INDEX IF NOT EXISTS idx_articles_fetched_at ON articles(fetched_at DESC);
+CREATE INDEX IF NOT EXISTS idx_article_tags_tag ON article_tags(tag);
+CREATE INDEX IF NOT EXISTS idx_behavior_events_article_id ON behavior_events(article_id);
+CREATE INDEX IF NOT EXISTS idx_behavior_events_feed_id ON behavior_events(feed_id);
+CREATE INDEX IF NOT EXISTS idx_behavior_events_type ON behavior_events(event_type);
+CREATE INDEX IF NOT EXISTS idx_behavior_events_created_at ON behavior_events(created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_user_prefs_type ON user_preferences(preference_type);
+CREATE INDEX IF NOT EXISTS idx_user_prefs_window ON user_preferences(time_window)
+"#;
+
+const MIGRATION_007_ARTICLE_IMAGE_URL: &str = r#"
+ALTER TABLE articles ADD COLUMN image_url TEXT
+"#;
+
+const MIGRATION_008_ARTICLE_RELEVANCE_SCORE: &str = r#"
+ALTER TABLE articles ADD COLUMN relevance_score REAL
+"#;
+
+const MIGRATION_009_ARTICLE_MEDIA: &[&str] = &[
+    "ALTER TABLE articles ADD COLUMN media_url TEXT",
+    "ALTER TABLE articles ADD COLUMN media_type TEXT",
+    "ALTER TABLE articles ADD COLUMN media_length INTEGER",
+    "ALTER TABLE articles ADD COLUMN media_duration INTEGER",
+    "ALTER TABLE articles ADD COLUMN media_thumbnail_url TEXT",
+];
+
+const MIGRATION_010_ARTICLE_CONTENT_HASH: &[&str] = &[
+    "ALTER TABLE articles ADD COLUMN content_hash TEXT",
+    "ALTER TABLE articles ADD COLUMN updated_at DATETIME",
+];
+
+const MIGRATION_012_ARTICLE_STATE_CHANGED_AT: &str = r#"
+ALTER TABLE articles ADD COLUMN state_changed_at DATETIME
+"#;
+
+const MIGRATION_013_FEED_SETTINGS: &[&str] = &[
+    "ALTER TABLE feeds ADD COLUMN category TEXT",
+    "ALTER TABLE feeds ADD COLUMN paused INTEGER NOT NULL DEFAULT 0",
+    "ALTER TABLE feeds ADD COLUMN refresh_interval_secs INTEGER",
+];
+
+const MIGRATION_014_FEED_ORDER: &[&str] = &[
+    "ALTER TABLE feeds ADD COLUMN pinned INTEGER NOT NULL DEFAULT 0",
+    "ALTER TABLE feeds ADD COLUMN position INTEGER NOT NULL DEFAULT 0",
+    // Start from the previous alphabetical order
+    r#"UPDATE feeds SET position = (
+        SELECT COUNT(*) FROM feeds AS f
+        WHERE f.local_name < feeds.local_name
+           OR (f.local_name = feeds.local_name AND f.id < feeds.id)
+    )"#,
+];
+
+const MIGRATION_015_READ_AT_INDEX: &str = r#"
+CREATE INDEX IF NOT EXISTS idx_articles_read_at ON articles(read_at DESC)
+"#;
+
+const MIGRATION_016_FEED_AUTO_READ: &str = r#"
+ALTER TABLE feeds ADD COLUMN auto_read_days INTEGER
+"#;
+
+const MIGRATION_017_ARTICLE_COMMENTS_URL: &str = r#"
+ALTER TABLE articles ADD COLUMN comments_url TEXT
+"#;
+
+const MIGRATION_018_FEED_MOVED_TO: &str = r#"
+ALTER TABLE feeds ADD COLUMN moved_to TEXT
+"#;
+
+const MIGRATION_019_ARTICLE_EXPORTS: &str = r#"
+CREATE TABLE IF NOT EXISTS article_exports (
+    article_id TEXT NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+    destination TEXT NOT NULL,
+    exported_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (article_id, destination)
+)
+"#;
+
+const MIGRATION_020_FETCH_METRICS: &[&str] = &[
+    r#"
+CREATE TABLE IF NOT EXISTS fetch_metrics (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    feed_id TEXT NOT NULL REFERENCES feeds(id) ON DELETE CASCADE,
+    fetched_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    fetch_ms INTEGER NOT NULL,
+    parse_ms INTEGER NOT NULL DEFAULT 0,
+    store_ms INTEGER NOT NULL DEFAULT 0,
+    bytes INTEGER NOT NULL DEFAULT 0,
+    articles INTEGER NOT NULL DEFAULT 0,
+    inserted INTEGER NOT NULL DEFAULT 0,
+    error TEXT
+)
+"#,
+    "CREATE INDEX IF NOT EXISTS idx_fetch_metrics_fetched_at ON fetch_metrics(fetched_at)",
+];
+
+const MIGRATION_021_FILTERED_ARTICLES: &str = r#"
+CREATE TABLE IF NOT EXISTS filtered_articles (
+    article_id TEXT PRIMARY KEY REFERENCES articles(id) ON DELETE CASCADE,
+    score REAL NOT NULL,
+    threshold REAL NOT NULL,
+    filtered_at DATETIME NOT NULL,
+    rescued_at DATETIME
+)
+"#;
+
+const MIGRATION_022_ARTICLE_ALERTS: &str = r#"
+CREATE TABLE IF NOT EXISTS article_alerts (
+    article_id TEXT PRIMARY KEY REFERENCES articles(id) ON DELETE CASCADE,
+    keyword TEXT NOT NULL,
+    alerted_at DATETIME NOT NULL
+)
+"#;
+
+const MIGRATION_023_CVES: &str = r#"
+CREATE TABLE IF NOT EXISTS cves (
+    cve_id TEXT PRIMARY KEY,
+    score REAL,
+    severity TEXT,
+    looked_up_at DATETIME
+);
+CREATE TABLE IF NOT EXISTS article_cves (
+    article_id TEXT NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+    cve_id TEXT NOT NULL,
+    PRIMARY KEY (article_id, cve_id)
+);
+CREATE INDEX IF NOT EXISTS idx_article_cves_cve_id ON article_cves(cve_id);
+"#;
+
+const MIGRATION_025_ARTICLE_EXPORT_ERROR: &str = r#"
+ALTER TABLE article_exports ADD COLUMN error TEXT
+"#;
+
+const MIGRATION_026_PREFERENCE_DAILY_EVENTS: &[&str] = &[
+    r#"
+CREATE TABLE IF NOT EXISTS preference_daily_events (
+    preference_type TEXT NOT NULL,
+    preference_key TEXT NOT NULL,
+    event_type TEXT NOT NULL,
+    day TEXT NOT NULL,
+    count INTEGER NOT NULL,
+    latest_at DATETIME NOT NULL,
+    PRIMARY KEY (preference_type, preference_key, event_type, day)
+)
+"#,
+    r#"
+CREATE TABLE IF NOT EXISTS preference_aggregation (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    last_event_id INTEGER NOT NULL
+)
+"#,
+    "INSERT OR IGNORE INTO preference_aggregation (id, last_event_id) VALUES (1, 0)",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::Database;
+
+    #[test]
+    fn test_versions_strictly_increase() {
+        for pair in MIGRATIONS.windows(2) {
+            assert!(pair[0].version < pair[1].version);
+        }
+        assert_eq!(MIGRATIONS[0].version, 1);
+    }
+
+    #[tokio::test]
+    async fn test_run_is_idempotent() {
+        let db = Database::new_in_memory().await.unwrap();
+        assert_eq!(current_version(db.pool()).await.unwrap(), latest_version());
+        assert!(pending(db.pool()).await.unwrap().is_empty());
+        assert!(run(db.pool()).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_legacy_database_is_adopted() {
+        let db = Database::new_in_memory().await.unwrap();
+        // Simulate a database created before versioning
+        sqlx::query("DROP TABLE schema_version")
+            .execute(db.pool())
+            .await
+            .unwrap();
+        assert_eq!(current_version(db.pool()).await.unwrap(), 0);
+
+        let applied = run(db.pool()).await.unwrap();
+        assert_eq!(applied.len(), MIGRATIONS.len());
+        assert_eq!(current_version(db.pool()).await.unwrap(), latest_version());
+    }
+
+    #[tokio::test]
+    async fn test_newer_schema_is_rejected() {
+        let db = Database::new_in_memory().await.unwrap();
+        sqlx::query("INSERT INTO schema_version (version, description) VALUES (?, 'future')")
+            .bind(latest_version() as i64 + 1)
+            .execute(db.pool())
+            .await
+            .unwrap();
+        assert!(pending(db.pool()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_stored_article_urls_are_canonicalized() {
+        let db = Database::new_in_memory().await.unwrap();
+        sqlx::query("INSERT INTO feeds (id, url, local_name) VALUES ('f', 'https://example.com/feed', 'f')")
+            .execute(db.pool())
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO articles (id, feed_id, guid, url, title, fetched_at) \
+             VALUES ('a', 'f', 'g', 'HTTPS://Example.com/post?utm_source=rss#top', 't', CURRENT_TIMESTAMP)",
+        )
+        .execute(db.pool())
+        .await
+        .unwrap();
+        sqlx::query("DELETE FROM schema_version WHERE version >= 24")
+            .execute(db.pool())
+            .await
+            .unwrap();
+
+        run(db.pool()).await.unwrap();
+        let (url,): (String,) = sqlx::query_as("SELECT url FROM articles WHERE id = 'a'")
+            .fetch_one(db.pool())
+            .await
+            .unwrap();
+        assert_eq!(url, "https://example.com/post");
+    }
+}
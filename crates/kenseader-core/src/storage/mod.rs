@@ -1,11 +1,24 @@
+mod archive;
 mod database;
 mod feed_repo;
+mod lock;
+mod metrics_repo;
+mod migrations;
 mod article_repo;
 mod retry;
 mod style_repo;
 
+pub use archive::{
+    export_archive, import_archive, ArchiveRecord, ArchiveStats, ArchivedAlert, ArchivedArticle, ArchivedArticleCve, ArchivedCve,
+    ArchivedEvent, ArchivedFilter, ArchivedPreference, ARCHIVE_FORMAT_VERSION,
+};
 pub use database::Database;
+#[cfg(test)]
+pub(crate) use database::test_db_with_feed;
 pub use feed_repo::FeedRepository;
+pub use lock::WriterLock;
+pub use metrics_repo::{FetchMetricsRepository, METRICS_RETENTION_DAYS};
+pub use migrations::{latest_version, Migration};
 pub use article_repo::ArticleRepository;
-pub use retry::{execute_with_retry, query_with_retry, is_transient_error, MAX_RETRIES};
+pub use retry::{is_transient_error, RetryPolicy, MAX_RETRIES};
 pub use style_repo::{ArticleStyle, ArticleStyleRepository};
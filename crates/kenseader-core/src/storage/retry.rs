@@ -7,9 +7,70 @@
 use std::future::Future;
 use std::time::Duration;
 
-/// Maximum number of retry attempts for database operations
+use crate::jitter::jitter;
+
+/// Default number of retry attempts for database operations
 pub const MAX_RETRIES: u32 = 5;
 
+/// Default delay before the first retry
+const BASE_DELAY: Duration = Duration::from_millis(200);
+
+/// How transient database errors are retried
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled on every further retry
+    pub base_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: MAX_RETRIES,
+            base_delay: BASE_DELAY,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before retry `attempt` (1-based): the base delay doubled per
+    /// attempt, plus up to half of it again at random so that connections
+    /// that failed together don't retry together
+    fn backoff(&self, attempt: u32) -> Duration {
+        let delay = self.base_delay.saturating_mul(1 << attempt.saturating_sub(1).min(16));
+        delay + jitter(delay / 2)
+    }
+
+    /// Run `operation`, retrying transient errors
+    pub async fn run<F, Fut, T>(&self, operation: F, kind: &str) -> std::result::Result<T, sqlx::Error>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = std::result::Result<T, sqlx::Error>>,
+    {
+        let mut attempts = 0;
+        loop {
+            match operation().await {
+                Ok(result) => return Ok(result),
+                Err(e) if is_transient_error(&e) && attempts < self.max_retries => {
+                    attempts += 1;
+                    let delay = self.backoff(attempts);
+                    tracing::debug!(
+                        error = %e,
+                        attempt = attempts,
+                        max_retries = self.max_retries,
+                        delay_ms = delay.as_millis(),
+                        "Database transient error, retrying {} operation",
+                        kind
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
 /// Check if a SQLite error is transient and should be retried
 ///
 /// This includes:
@@ -47,84 +108,21 @@ pub fn is_transient_error(err: &sqlx::Error) -> bool {
     }
 }
 
-/// Calculate exponential backoff delay for retry attempt
-///
-/// Base delay: 200ms, doubling each attempt
-/// Delays: 200ms, 400ms, 800ms, 1600ms, 3200ms
-fn backoff_delay(attempt: u32) -> Duration {
-    Duration::from_millis(200 * 2u64.pow(attempt.saturating_sub(1)))
-}
-
-/// Execute a write operation with exponential backoff retry for transient errors
-///
-/// This is essential for cloud sync scenarios where multiple devices may
-/// access the same database file (via iCloud, Dropbox, etc.)
-pub async fn execute_with_retry<F, Fut>(operation: F) -> std::result::Result<(), sqlx::Error>
-where
-    F: Fn() -> Fut,
-    Fut: Future<Output = std::result::Result<(), sqlx::Error>>,
-{
-    let mut attempts = 0;
-    loop {
-        match operation().await {
-            Ok(_) => return Ok(()),
-            Err(e) if is_transient_error(&e) && attempts < MAX_RETRIES => {
-                attempts += 1;
-                let delay = backoff_delay(attempts);
-                tracing::debug!(
-                    error = %e,
-                    attempt = attempts,
-                    max_retries = MAX_RETRIES,
-                    delay_ms = delay.as_millis(),
-                    "Database transient error, retrying write operation"
-                );
-                tokio::time::sleep(delay).await;
-            }
-            Err(e) => return Err(e),
-        }
-    }
-}
-
-/// Execute a query operation with exponential backoff retry for transient errors
-///
-/// Generic over the return type T to support various query result types.
-/// Essential for handling I/O errors during read operations in cloud sync scenarios.
-pub async fn query_with_retry<F, Fut, T>(operation: F) -> std::result::Result<T, sqlx::Error>
-where
-    F: Fn() -> Fut,
-    Fut: Future<Output = std::result::Result<T, sqlx::Error>>,
-{
-    let mut attempts = 0;
-    loop {
-        match operation().await {
-            Ok(result) => return Ok(result),
-            Err(e) if is_transient_error(&e) && attempts < MAX_RETRIES => {
-                attempts += 1;
-                let delay = backoff_delay(attempts);
-                tracing::debug!(
-                    error = %e,
-                    attempt = attempts,
-                    max_retries = MAX_RETRIES,
-                    delay_ms = delay.as_millis(),
-                    "Database transient error, retrying query operation"
-                );
-                tokio::time::sleep(delay).await;
-            }
-            Err(e) => return Err(e),
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_backoff_delay() {
-        assert_eq!(backoff_delay(1), Duration::from_millis(200));
-        assert_eq!(backoff_delay(2), Duration::from_millis(400));
-        assert_eq!(backoff_delay(3), Duration::from_millis(800));
-        assert_eq!(backoff_delay(4), Duration::from_millis(1600));
-        assert_eq!(backoff_delay(5), Duration::from_millis(3200));
+        let policy = RetryPolicy::default();
+        for (attempt, base_ms) in [(1, 200), (2, 400), (3, 800), (4, 1600), (5, 3200)] {
+            let delay = policy.backoff(attempt).as_millis();
+            assert!(
+                (base_ms..=base_ms * 3 / 2).contains(&delay),
+                "attempt {}: {}ms",
+                attempt,
+                delay
+            );
+        }
     }
 }
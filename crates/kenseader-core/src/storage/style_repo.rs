@@ -1,15 +1,15 @@
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use sqlx::Row;
 use uuid::Uuid;
 
-use super::retry::{execute_with_retry, query_with_retry};
 use super::Database;
 use crate::ai::ArticleStyleResult;
 use crate::feed::Article;
 use crate::Result;
 
 /// Stored article style classification
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ArticleStyle {
     pub article_id: Uuid,
     pub style_type: Option<String>,
@@ -36,7 +36,7 @@ impl<'a> ArticleStyleRepository<'a> {
         let tone = style.tone.clone();
         let length_category = style.length_category.clone();
 
-        execute_with_retry(|| {
+        self.db.execute_with_retry(|| {
             let pool = pool.clone();
             let article_id_str = article_id_str.clone();
             let style_type = style_type.clone();
@@ -73,7 +73,7 @@ impl<'a> ArticleStyleRepository<'a> {
         let pool = self.db.pool().clone();
         let article_id_str = article_id.to_string();
 
-        let row = query_with_retry(|| {
+        let row = self.db.query_with_retry(|| {
             let pool = pool.clone();
             let article_id_str = article_id_str.clone();
             async move {
@@ -111,7 +111,7 @@ impl<'a> ArticleStyleRepository<'a> {
     pub async fn list_unclassified(&self, limit: i64) -> Result<Vec<Article>> {
         let pool = self.db.pool().clone();
 
-        let rows = query_with_retry(|| {
+        let rows = self.db.query_with_retry(|| {
             let pool = pool.clone();
             async move {
                 sqlx::query(
@@ -161,6 +161,9 @@ impl<'a> ArticleStyleRepository<'a> {
                     image_url: row.get("image_url"),
                     relevance_score: row.get("relevance_score"),
                     tags: Vec::new(),
+                    media: None,
+                    comments_url: None,
+                    updated_at: None,
                 }
             })
             .collect();
@@ -172,7 +175,7 @@ impl<'a> ArticleStyleRepository<'a> {
     pub async fn count_classified(&self) -> Result<i64> {
         let pool = self.db.pool().clone();
 
-        let row = query_with_retry(|| {
+        let row = self.db.query_with_retry(|| {
             let pool = pool.clone();
             async move {
                 sqlx::query("SELECT COUNT(*) as count FROM article_styles")
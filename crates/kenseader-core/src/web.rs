@@ -0,0 +1,267 @@
+//! Read-only web interface
+//!
+//! The daemon can serve the unread articles, each feed's articles and the
+//! article text as plain HTML pages, to skim on a phone on the same network.
+//! Nothing is changed from the web: articles opened there stay unread.
+//! `/calendar.ics` serves the calendar of upcoming digests.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::Local;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::watch;
+use tracing::{debug, info, warn};
+use url::Url;
+use uuid::Uuid;
+
+use crate::calendar::{self, DEFAULT_DAYS};
+use crate::config::AppConfig;
+use crate::feed::{Article, Feed};
+use crate::http::{accept_failed, escape, read_request_head, respond};
+use crate::storage::{ArticleRepository, Database, FeedRepository};
+use crate::Result;
+
+const STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:46em;margin:0 auto;padding:0 1em;line-height:1.5}\
+a{color:#2a6ab8;text-decoration:none}nav{margin:1em 0;font-size:.9em}nav a{margin-right:.8em;white-space:nowrap}\
+ul{list-style:none;padding:0}li{margin:.7em 0}li.read a{color:#888}small{display:block;color:#777}\
+.summary{background:#f3f3f3;padding:.6em 1em;border-radius:4px}";
+
+/// Pages of the web interface
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Route {
+    /// Unread articles of all feeds
+    Unread,
+    /// Newest articles of a feed
+    Feed(Uuid),
+    Article(Uuid),
+    /// ICS calendar of upcoming digests
+    Calendar,
+    NotFound,
+}
+
+impl Route {
+    pub fn parse(path: &str) -> Self {
+        let path = path.split(['?', '#']).next().unwrap_or("");
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        match segments.as_slice() {
+            [] => Route::Unread,
+            ["calendar.ics"] => Route::Calendar,
+            ["feed", id] => id.parse().map_or(Route::NotFound, Route::Feed),
+            ["article", id] => id.parse().map_or(Route::NotFound, Route::Article),
+            _ => Route::NotFound,
+        }
+    }
+}
+
+fn page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\">\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\
+         <title>{} - kenseader</title><style>{}</style></head>\n<body>\n{}</body></html>\n",
+        escape(title),
+        STYLE,
+        body
+    )
+}
+
+fn date(article: &Article) -> String {
+    article
+        .published_at
+        .unwrap_or(article.created_at)
+        .with_timezone(&Local)
+        .format("%Y-%m-%d %H:%M")
+        .to_string()
+}
+
+/// Page listing articles, with links to every feed
+pub fn render_list(heading: &str, feeds: &[Feed], articles: &[Article]) -> String {
+    let names: HashMap<_, _> = feeds.iter().map(|f| (f.id, f.local_name.as_str())).collect();
+    let mut body = String::from("<nav><a href=\"/\">Unread</a>");
+    for feed in feeds {
+        body.push_str(&format!("<a href=\"/feed/{}\">{}", feed.id, escape(&feed.local_name)));
+        if feed.unread_count > 0 {
+            body.push_str(&format!(" ({})", feed.unread_count));
+        }
+        body.push_str("</a>");
+    }
+    body.push_str(&format!("</nav>\n<h1>{}</h1>\n<ul>\n", escape(heading)));
+    for article in articles {
+        body.push_str(&format!(
+            "<li{}><a href=\"/article/{}\">{}</a><small>{} · {}</small></li>\n",
+            if article.is_read { " class=\"read\"" } else { "" },
+            article.id,
+            escape(&article.title),
+            escape(names.get(&article.feed_id).copied().unwrap_or("")),
+            date(article),
+        ));
+    }
+    if articles.is_empty() {
+        body.push_str("<li>No articles</li>\n");
+    }
+    body.push_str("</ul>\n");
+    page(heading, &body)
+}
+
+fn is_web_url(url: &str) -> bool {
+    Url::parse(url).is_ok_and(|url| matches!(url.scheme(), "http" | "https"))
+}
+
+/// Page showing an article's summary and text
+pub fn render_article(article: &Article, feed: Option<&Feed>) -> String {
+    let mut body = String::from("<nav><a href=\"/\">Unread</a>");
+    if let Some(feed) = feed {
+        body.push_str(&format!("<a href=\"/feed/{}\">{}</a>", feed.id, escape(&feed.local_name)));
+    }
+    body.push_str(&format!("</nav>\n<h1>{}</h1>\n<p><small>", escape(&article.title)));
+    if let Some(ref author) = article.author {
+        body.push_str(&format!("{} · ", escape(author)));
+    }
+    body.push_str(&date(article));
+    // Feeds can supply any URL: only web pages are linked, never scripts
+    if let Some(url) = article.url.as_deref().filter(|url| is_web_url(url)) {
+        body.push_str(&format!(" · <a href=\"{}\">Original</a>", escape(url)));
+    }
+    body.push_str("</small></p>\n");
+    if let Some(ref summary) = article.summary {
+        body.push_str(&format!("<p class=\"summary\">{}</p>\n", escape(summary)));
+    }
+    let text = article.content_text.as_deref().unwrap_or("");
+    for paragraph in text.split("\n\n").map(str::trim).filter(|p| !p.is_empty()) {
+        body.push_str(&format!("<p>{}</p>\n", escape(paragraph).replace('\n', "<br>")));
+    }
+    page(&article.title, &body)
+}
+
+/// Render the page of a route (None when there is no such page)
+async fn render(db: &Database, page_size: u32, route: Route) -> Result<Option<String>> {
+    let feeds = FeedRepository::new(db).list_all().await?;
+    let articles = ArticleRepository::new(db);
+    Ok(match route {
+        Route::Unread => Some(render_list("Unread", &feeds, &articles.list_unread(page_size).await?)),
+        Route::Feed(id) => match feeds.iter().find(|f| f.id == id) {
+            Some(feed) => {
+                let list = articles.list_by_feed_page(id, Default::default(), Some(page_size), None).await?;
+                Some(render_list(&feed.local_name, &feeds, &list))
+            }
+            None => None,
+        },
+        Route::Article(id) => articles
+            .find_by_id(id)
+            .await?
+            .map(|article| render_article(&article, feeds.iter().find(|f| f.id == article.feed_id))),
+        Route::Calendar | Route::NotFound => None,
+    })
+}
+
+async fn handle_request(mut stream: TcpStream, db: &Database, config: &AppConfig) -> std::io::Result<()> {
+    let Some(head) = read_request_head(&mut stream).await? else {
+        return Ok(());
+    };
+    let mut request = head.lines().next().unwrap_or("").split_whitespace();
+    let (method, path) = (request.next().unwrap_or(""), request.next().unwrap_or("/"));
+    if method != "GET" && method != "HEAD" {
+        return respond(&mut stream, "405 Method Not Allowed", "text/plain", &[], b"").await;
+    }
+
+    let body_wanted = method == "GET";
+
+    let route = Route::parse(path);
+    if route == Route::Calendar {
+        let ics = calendar::digest_calendar(config, DEFAULT_DAYS);
+        let body = if body_wanted { ics.as_bytes() } else { b"" };
+        return respond(&mut stream, "200 OK", "text/calendar; charset=utf-8", &[], body).await;
+    }
+
+    match render(db, config.web.page_size.max(1), route).await {
+        Ok(Some(html)) => {
+            let body = if body_wanted { html.as_bytes() } else { b"" };
+            respond(&mut stream, "200 OK", "text/html; charset=utf-8", &[], body).await
+        }
+        Ok(None) => respond(&mut stream, "404 Not Found", "text/plain", &[], b"Not found").await,
+        Err(e) => {
+            warn!("Web page {} failed: {}", path, e);
+            respond(&mut stream, "500 Internal Server Error", "text/plain", &[], b"Internal error").await
+        }
+    }
+}
+
+/// Serve the web interface until shutdown
+pub async fn serve(db: Arc<Database>, config: Arc<AppConfig>, mut shutdown: watch::Receiver<bool>) -> Result<()> {
+    let address = &config.web.address;
+    let listener = TcpListener::bind(address).await?;
+    info!("Serving the web interface at http://{}/", address);
+    loop {
+        tokio::select! {
+            result = shutdown.changed() => {
+                if result.is_err() || *shutdown.borrow() {
+                    return Ok(());
+                }
+            }
+            accepted = listener.accept() => {
+                let stream = match accepted {
+                    Ok((stream, _)) => stream,
+                    Err(e) => {
+                        accept_failed("Web interface", e).await;
+                        continue;
+                    }
+                };
+                let (db, config) = (db.clone(), config.clone());
+                tokio::spawn(async move {
+                    if let Err(e) = handle_request(stream, &db, &config).await {
+                        debug!("Web request failed: {}", e);
+                    }
+                });
+            }
+        }
+    }
+}
+
+/// Start the web interface when `web.enabled` is set
+pub fn spawn_server(db: Arc<Database>, config: Arc<AppConfig>, shutdown: watch::Receiver<bool>) {
+    if !config.web.enabled {
+        return;
+    }
+    tokio::spawn(async move {
+        if let Err(e) = serve(db, config, shutdown).await {
+            warn!("Web interface failed: {}", e);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route_parse() {
+        let id = Uuid::new_v4();
+        assert_eq!(Route::parse("/"), Route::Unread);
+        assert_eq!(Route::parse("/?utm=x"), Route::Unread);
+        assert_eq!(Route::parse(&format!("/feed/{}", id)), Route::Feed(id));
+        assert_eq!(Route::parse(&format!("/article/{}/", id)), Route::Article(id));
+        assert_eq!(Route::parse("/article/nope"), Route::NotFound);
+        assert_eq!(Route::parse("/calendar.ics"), Route::Calendar);
+        assert_eq!(Route::parse("/favicon.ico"), Route::NotFound);
+    }
+
+    #[test]
+    fn test_render_article_escapes_text() {
+        let mut article = Article {
+            url: Some("https://example.com/?a=1&b=2".to_string()),
+            content: Some("<p>ignored</p>".to_string()),
+            content_text: Some("First line\nsecond <b>line</b>\n\n\nNext paragraph".to_string()),
+            ..Article::for_test("<script>alert(1)</script>")
+        };
+        let html = render_article(&article, None);
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("<h1>&lt;script&gt;alert(1)&lt;/script&gt;</h1>"));
+        assert!(html.contains("href=\"https://example.com/?a=1&amp;b=2\""));
+        assert!(html.contains("<p>First line<br>second &lt;b&gt;line&lt;/b&gt;</p>\n<p>Next paragraph</p>"));
+
+        article.url = Some("javascript:alert(document.cookie)".to_string());
+        let html = render_article(&article, None);
+        assert!(!html.contains("javascript:"));
+        assert!(!html.contains("Original"));
+    }
+}
@@ -1,16 +1,35 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use kenseader_core::feed::{Article, Feed};
-use kenseader_core::ipc::DaemonClient;
+use kenseader_core::feed::{Advisory, Article, ArticleAlert, ArticleListing, Comment, Feed, FeedStats, FilterReason};
+use kenseader_core::ipc::{DaemonClient, StatusResponse};
+use kenseader_core::profile::{ProfileInsights, TimeWindow};
+use kenseader_core::storage::ArticleStyle;
 use kenseader_core::AppConfig;
 use uuid::Uuid;
 
+use crate::external::ForegroundCommand;
+use crate::feed_manager::{FeedManager, SubscribePrompt};
+use crate::feed_tree::{self, FeedRow};
+use crate::image_download::ImageDownloader;
 use crate::image_renderer::ImageRenderer;
+use crate::keymap::KeyBinding;
+use crate::macros::MacroRecorder;
+use crate::network::MeteredCheck;
+use crate::read_state::ReadStateBuffer;
+use crate::reading::ReadingTimer;
 use crate::rich_content::{ArticleImageCache, ContentElement, FocusableItem, PreloadCache, ResizedImageCache, RichContent};
 use crate::scroll::ScrollAnimator;
+use crate::settings::SettingsScreen;
+use crate::status_format;
+use crate::tagging::TagPrompt;
 use crate::theme::Theme;
+use crate::toast::{Severity, Toasts};
+use crate::toc::{self, TocEntry};
+use crate::triage::Triage;
+use crate::tts::Speaker;
 
 /// Rich content state for the current article
 pub struct RichArticleState {
@@ -20,16 +39,25 @@ pub struct RichArticleState {
     pub image_cache: ArticleImageCache,
     /// Pre-resized image cache for halfblock rendering (avoids resize on every frame)
     pub resized_cache: ResizedImageCache,
-    /// Pre-computed line height for each element
+    /// Line height of each element, as last rendered (estimated by
+    /// `calculate_heights` until the article is first rendered)
     pub element_heights: Vec<u16>,
-    /// Total content height in lines
+    /// Height of the article view in lines, from the title to the hints
     pub total_height: u16,
-    /// Current viewport height
+    /// Height of the article pane in lines
     pub viewport_height: u16,
     /// Image height in terminal rows
     pub image_height: u16,
+    /// Blank lines rendered for each paragraph break
+    pub paragraph_spacing: u16,
     /// Index of currently focused item in focusable_items (images + links)
     pub focused_item: Option<usize>,
+    /// Line of the article view each element starts at, recorded while
+    /// rendering (the title and header come before the first element)
+    pub element_offsets: Vec<u16>,
+    /// Focusable item of the footnote reference last followed, to jump
+    /// back to from the footnote
+    pub footnote_return: Option<usize>,
 }
 
 impl RichArticleState {
@@ -48,7 +76,10 @@ impl RichArticleState {
             total_height: 0,
             viewport_height: 0,
             image_height: Self::DEFAULT_IMAGE_HEIGHT,
+            paragraph_spacing: 1,
             focused_item: None,
+            element_offsets: Vec::new(),
+            footnote_return: None,
         }
     }
 
@@ -64,25 +95,38 @@ impl RichArticleState {
             total_height: 0,
             viewport_height: 0,
             image_height: Self::DEFAULT_IMAGE_HEIGHT,
+            paragraph_spacing: 1,
             focused_item: None,
+            element_offsets: Vec::new(),
+            footnote_return: None,
         }
     }
 
-    /// Calculate heights for all elements given a width
+    /// Estimate the heights of all elements given a width, before the
+    /// article is rendered
     pub fn calculate_heights(&mut self, width: u16) {
         self.element_heights.clear();
         self.total_height = 0;
 
         for element in &self.content.elements {
             let height = match element {
-                ContentElement::Text(text) => Self::text_height(text, width),
+                ContentElement::Text(text, _) => Self::text_height(text, width),
                 ContentElement::Heading(_, text) => Self::text_height(text, width) + 1,
-                ContentElement::Image { .. } => self.image_height,
+                ContentElement::Image { .. } => {
+                    let caption = element.image_caption().map_or(0, |c| Self::text_height(c, width));
+                    self.image_height + caption
+                }
                 ContentElement::Quote(text) => Self::text_height(text, width.saturating_sub(2)),
                 ContentElement::Code(text) => text.lines().count() as u16 + 2,
+                ContentElement::Math(text) => Self::text_height(text, width.saturating_sub(4)) + 1,
                 ContentElement::ListItem(text) => Self::text_height(text, width.saturating_sub(2)),
+                ContentElement::DefinitionTerm(text) => Self::text_height(text, width),
+                ContentElement::Definition(text) => Self::text_height(text, width.saturating_sub(4)),
+                ContentElement::Footnote { label, text } => {
+                    Self::text_height(text, width.saturating_sub(label.len() as u16 + 3))
+                }
                 ContentElement::Separator => 1,
-                ContentElement::EmptyLine => 1,
+                ContentElement::EmptyLine => self.paragraph_spacing,
             };
 
             self.element_heights.push(height);
@@ -109,7 +153,8 @@ impl RichArticleState {
 
     /// Get image URLs that need loading in the visible range
     pub fn get_urls_needing_load(&self, scroll: u16, viewport_height: u16) -> Vec<String> {
-        let mut current_y = 0u16;
+        // The title and header lines come before the elements
+        let mut current_y = self.element_offsets.first().copied().unwrap_or(0);
         let mut urls = Vec::new();
 
         for (idx, element) in self.content.elements.iter().enumerate() {
@@ -140,6 +185,8 @@ impl RichArticleState {
         self.element_heights.clear();
         self.total_height = 0;
         self.focused_item = None;
+        self.element_offsets.clear();
+        self.footnote_return = None;
     }
 
     /// Get the currently focused item
@@ -172,6 +219,44 @@ impl RichArticleState {
     pub fn is_link_focused(&self) -> bool {
         matches!(self.get_focused_item(), Some(FocusableItem::Link { .. }))
     }
+
+    /// Move the focus from a footnote reference to its footnote, or from a
+    /// footnote back to the reference it was reached from (its first
+    /// reference otherwise). Returns the label, whether the footnote was
+    /// reached, and the element to scroll to; None without a footnote focused.
+    pub fn follow_footnote(&mut self) -> Option<(String, bool, usize)> {
+        let current = self.focused_item?;
+        let items = &self.content.focusable_items;
+        let (target, to_footnote) = match items.get(current)? {
+            FocusableItem::FootnoteRef { label, .. } => {
+                let target = items.iter().position(
+                    |item| matches!(item, FocusableItem::Footnote { label: l, .. } if l == label),
+                )?;
+                (target, true)
+            }
+            FocusableItem::Footnote { label, .. } => {
+                let is_ref = |index: usize| {
+                    matches!(items.get(index), Some(FocusableItem::FootnoteRef { label: l, .. }) if l == label)
+                };
+                let target = self
+                    .footnote_return
+                    .filter(|&index| is_ref(index))
+                    .or_else(|| (0..items.len()).find(|&index| is_ref(index)))?;
+                (target, false)
+            }
+            _ => return None,
+        };
+        let (label, element_index) = match &items[target] {
+            FocusableItem::FootnoteRef { label, element_index, .. }
+            | FocusableItem::Footnote { label, element_index } => (label.clone(), *element_index),
+            _ => return None,
+        };
+        if to_footnote {
+            self.footnote_return = Some(current);
+        }
+        self.focused_item = Some(target);
+        Some((label, to_footnote, element_index))
+    }
 }
 
 /// Current focus panel in the UI
@@ -191,6 +276,19 @@ pub enum ViewMode {
     UnreadOnly,
 }
 
+/// Virtual feed listed instead of the selected feed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VirtualFeed {
+    /// Recently read articles of all feeds
+    History,
+    /// Articles marked read by relevance filtering, to review and rescue
+    Filtered,
+    /// Articles that raised a keyword alert
+    Alerts,
+    /// Articles mentioning CVEs, most severe first
+    Advisories,
+}
+
 /// Application mode
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Mode {
@@ -204,26 +302,128 @@ pub enum Mode {
     DeleteConfirm(Uuid),
     /// Batch delete confirmation (multiple feeds)
     BatchDeleteConfirm,
-    /// Help overlay
-    Help,
+    /// Confirmation of marking all of a feed's articles read
+    MarkAllReadConfirm(Uuid),
+    /// Help overlay listing the key bindings, scrolled by the given
+    /// number of lines
+    Help(u16),
     /// Fullscreen image viewer mode (image index)
     ImageViewer(usize),
+    /// Share menu for the current article
+    Share,
+    /// QR code of the current article URL
+    QrCode,
+    /// History of status messages
+    Messages,
+    /// Feed management screen
+    FeedManager,
+    /// Subscribe prompt (URL + name)
+    Subscribe,
+    /// Typing a filter for the subscriptions list
+    FeedFilter,
+    /// Summary-first triage of the article list
+    Triage,
+    /// Typing tags for the selected articles
+    Tag,
+    /// Settings screen
+    Settings,
+    /// Table of contents of the article (selected heading)
+    Toc(usize),
+    /// Interest profile learned from reading, in a time window
+    Profile(TimeWindow),
+}
+
+/// Command waiting for a register name as its next key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegisterCommand {
+    /// `M{a-z}`: record a macro
+    RecordMacro,
+    /// `@{a-z}`: replay a macro
+    PlayMacro,
+    /// `m{a-z}`: set a mark
+    SetMark,
+    /// `'{a-z}`: jump to a mark
+    JumpToMark,
+}
+
+/// Mark holding the position before the last jump (`''`)
+pub const PREVIOUS_MARK: char = '\'';
+
+/// Feed/article position saved with `m{a-z}`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mark {
+    pub feed_id: Uuid,
+    /// Article, unless the mark was set in the feed list
+    pub article_id: Option<Uuid>,
+    pub focus: Focus,
+}
+
+/// Discussion of an article, shown in a collapsible section after its content
+#[derive(Debug, Clone, Default)]
+pub struct Discussion {
+    pub article_id: Uuid,
+    /// Discussion page (None when none was found)
+    pub url: Option<String>,
+    /// Top-level comments
+    pub comments: Vec<Comment>,
+    /// Comments are being fetched
+    pub loading: bool,
+    pub error: Option<String>,
+    /// Section collapsed to its heading
+    pub collapsed: bool,
+    /// Lines the section took when last rendered
+    pub height: u16,
+}
+
+/// Daemon state shown by the status bar's `{daemon}`, `{next_refresh}`
+/// and `{ai_queue}` placeholders, polled while one of them is used
+#[derive(Debug, Clone, Default)]
+pub struct DaemonStatus {
+    /// When the daemon was last asked (None: not yet)
+    pub polled_at: Option<Instant>,
+    /// Whether the daemon answered the last poll
+    pub reachable: bool,
+    /// When the next scheduled refresh runs
+    pub next_refresh_at: Option<Instant>,
+    /// AI requests queued or running
+    pub ai_queue: Option<usize>,
+}
+
+impl DaemonStatus {
+    /// Record the answer to a `status` request (None: no answer)
+    pub fn update(&mut self, status: Option<StatusResponse>) {
+        let now = Instant::now();
+        self.polled_at = Some(now);
+        self.reachable = status.is_some();
+        self.next_refresh_at = status
+            .as_ref()
+            .and_then(|s| s.next_refresh_secs)
+            .map(|secs| now + Duration::from_secs(secs));
+        self.ai_queue = status
+            .and_then(|s| s.ai_queue)
+            .map(|queue| queue.iter().map(|q| q.queued + q.running).sum());
+    }
 }
 
 /// Application state
 pub struct App {
-    /// Daemon client for IPC communication (None in read-mode)
-    pub client: Option<Arc<DaemonClient>>,
+    /// Client for all data access (in-process against the database in read-mode)
+    pub client: Arc<DaemonClient>,
     /// Application configuration
     pub config: Arc<AppConfig>,
     /// List of feeds
     pub feeds: Vec<Feed>,
     /// Currently selected feed index
     pub selected_feed: usize,
-    /// Articles for the selected feed
+    /// Loaded window of the selected feed's articles (pages of
+    /// `ARTICLE_PAGE_SIZE`, at most `ARTICLE_WINDOW_SIZE` at once)
     pub articles: Vec<Article>,
     /// Currently selected article index
     pub selected_article: usize,
+    /// Number of the feed's listed articles before the loaded window
+    pub articles_offset: usize,
+    /// Whether the loaded window reaches the end of the feed's articles
+    pub articles_exhausted: bool,
     /// Current focus panel
     pub focus: Focus,
     /// Current view mode
@@ -240,12 +440,46 @@ pub struct App {
     pub current_match: usize,
     /// Whether the app should quit
     pub should_quit: bool,
-    /// Status message
-    pub status_message: Option<String>,
-    /// Pending key for multi-key sequences (e.g., 'gg')
-    pub pending_key: Option<char>,
+    /// Stacked status messages and their history
+    pub toasts: Toasts,
+    /// Pending prefix key of a two-key sequence (e.g. 'g' of 'gg')
+    pub pending_key: Option<KeyBinding>,
+    /// When the pending prefix key was pressed
+    pub pending_key_at: Option<Instant>,
+    /// Command waiting for its register key (e.g. the 'a' of '@a')
+    pub pending_register: Option<RegisterCommand>,
+    /// Count typed before a command (e.g. the 5 of '5@a')
+    pub pending_count: Option<u32>,
+    /// Recorded keyboard macros
+    pub macros: MacroRecorder,
+    /// Positions saved with `m{a-z}` for this session
+    pub marks: HashMap<char, Mark>,
     /// Rich content state for current article (replaces image_cache)
     pub rich_state: Option<RichArticleState>,
+    /// Articles whose images were loaded on request (`load_images`)
+    pub images_requested: HashSet<Uuid>,
+    /// Whether NetworkManager reports the connection as metered
+    pub metered: MeteredCheck,
+    /// Metered mode switched by hand (`toggle_metered`), overriding the
+    /// config and the connection
+    pub metered_override: Option<bool>,
+    /// Reads an article aloud
+    pub speaker: Speaker,
+    /// Verdicts of the current triage session
+    pub triage: Triage,
+    /// AI header of the article detail collapsed to one line
+    pub ai_header_collapsed: bool,
+    /// Show only the summary and AI metadata of articles
+    pub summary_only: bool,
+    /// Style classification of the article in the detail panel, with the
+    /// article it was loaded for
+    pub article_style: Option<(Uuid, Option<ArticleStyle>)>,
+    /// Statistics of the selected feed, with the feed they were loaded for
+    pub feed_stats: Option<(Uuid, Option<FeedStats>)>,
+    /// Interest profile shown in `Mode::Profile`
+    pub profile: Option<ProfileInsights>,
+    /// Discussion loaded for an article with `<leader>c`
+    pub discussion: Option<Discussion>,
     /// Reading history stack - stores (feed_id, article_id) tuples
     /// Using IDs instead of indices to ensure correct navigation in unread-only mode
     pub read_history: Vec<(Uuid, Uuid)>,
@@ -261,6 +495,13 @@ pub struct App {
     pub visual_start_feed: Option<usize>,
     /// Whether a refresh operation is in progress
     pub is_refreshing: bool,
+    /// Feeds fetched and feeds due in the running refresh, as reported by the daemon
+    pub refresh_progress: Option<(u32, u32)>,
+    /// Whether the daemon's events arrive, so a refresh runs in the daemon
+    /// and its progress and result come back as events
+    pub daemon_events: bool,
+    /// Daemon state for the status bar
+    pub daemon_status: DaemonStatus,
     /// Image renderer for high-resolution image display
     pub image_renderer: ImageRenderer,
     /// Terminal viewport height for adaptive scroll calculations
@@ -269,32 +510,98 @@ pub struct App {
     pub spinner_frame: usize,
     /// Global preload cache for prefetching images before entering article detail
     pub preload_cache: PreloadCache,
-    /// Read-mode: TUI reads directly from data_dir without daemon
+    /// Downloads article images
+    pub image_downloader: ImageDownloader,
+    /// Read-mode: TUI reads directly from data_dir without daemon (explicitly,
+    /// or as the fallback when no daemon is running)
     /// Disables refresh, feed add/delete; allows read status toggle with retry
     pub read_mode: bool,
     /// Current color theme
     pub theme: Theme,
     /// Smooth scroll animator for article detail view
     pub scroll_animator: ScrollAnimator,
+    /// Command to run with the TUI suspended, picked up by the main loop
+    pub foreground_command: Option<ForegroundCommand>,
+    /// Feed management screen state (shown in `Mode::FeedManager`)
+    pub feed_manager: FeedManager,
+    /// Subscribe prompt state (shown in `Mode::Subscribe`)
+    pub subscribe_prompt: SubscribePrompt,
+    /// Tag prompt for the selected articles
+    pub tag_prompt: TagPrompt,
+    /// Settings screen state (shown in `Mode::Settings`)
+    pub settings: SettingsScreen,
+    /// Read-state changes shown in the lists but not yet stored
+    pub read_state: ReadStateBuffer,
+    /// Time spent reading the article open in the detail view
+    pub reading: ReadingTimer,
+    /// Filter narrowing the subscriptions list (empty = no filter)
+    pub feed_filter: String,
+    /// Categories collapsed in the subscriptions list
+    pub collapsed_categories: HashSet<String>,
+    /// Category whose header the subscriptions cursor is on, if any
+    pub category_cursor: Option<String>,
+    /// Virtual feed the article list shows instead of the selected feed
+    pub virtual_feed: Option<VirtualFeed>,
+    /// Why the articles of the Filtered view were filtered
+    pub filter_reasons: HashMap<Uuid, FilterReason>,
+    /// Keyword alerts raised by the articles of the Alerts view
+    pub article_alerts: HashMap<Uuid, ArticleAlert>,
+    /// CVEs mentioned by the articles of the Advisories view, most severe
+    /// first
+    pub article_advisories: HashMap<Uuid, Vec<Advisory>>,
+    /// Whether the article list is sorted by relevance score
+    pub sort_by_relevance: bool,
+    /// Whether articles scored below `relevance_threshold` are hidden
+    pub hide_low_relevance: bool,
+    /// Score below which articles are hidden (starts at `ai.relevance_threshold`)
+    pub relevance_threshold: f64,
 }
 
+/// Number of articles fetched per page for the article list
+pub const ARTICLE_PAGE_SIZE: u32 = 500;
+
+/// Most articles kept loaded; pages far from the cursor are dropped and
+/// fetched again when scrolled back to
+pub const ARTICLE_WINDOW_SIZE: usize = 3 * ARTICLE_PAGE_SIZE as usize;
+
+/// Number of recently read articles listed in the History view
+pub const HISTORY_VIEW_SIZE: u32 = 200;
+
+/// Number of filtered articles listed in the Filtered view
+pub const FILTERED_VIEW_SIZE: u32 = 200;
+
+/// Number of alerted articles listed in the Alerts view
+pub const ALERTS_VIEW_SIZE: u32 = 200;
+
+/// Number of articles mentioning CVEs listed in the Advisories view
+pub const ADVISORIES_VIEW_SIZE: u32 = 200;
+
+/// Amount the relevance threshold changes per key press
+pub const RELEVANCE_THRESHOLD_STEP: f64 = 0.05;
+
+/// Fetch the next (or previous) page once the cursor gets this close to the
+/// end (or start) of the loaded articles
+const ARTICLE_PREFETCH_MARGIN: usize = 100;
+
+/// Seconds between daemon status polls for the status bar
+const STATUS_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Status bar placeholders that need the daemon's status
+const DAEMON_PLACEHOLDERS: &[&str] = &["daemon", "next_refresh", "ai_queue"];
+
 /// Spinner animation frames (braille pattern)
 pub const SPINNER_FRAMES: &[char] = &['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
 
 impl App {
-    /// Create a new App with daemon client (normal mode)
-    pub fn new(client: Arc<DaemonClient>, config: Arc<AppConfig>, theme: Theme) -> Self {
-        Self::new_with_mode(Some(client), config, false, theme)
-    }
-
-    /// Create a new App in read-mode (direct database access, no daemon)
-    pub fn new_read_mode(config: Arc<AppConfig>, theme: Theme) -> Self {
-        Self::new_with_mode(None, config, true, theme)
-    }
-
-    /// Internal constructor with mode selection
-    fn new_with_mode(client: Option<Arc<DaemonClient>>, config: Arc<AppConfig>, read_mode: bool, theme: Theme) -> Self {
-        let scroll_animator = ScrollAnimator::new(config.ui.scroll.clone());
+    /// Create a new App
+    pub fn new(client: Arc<DaemonClient>, config: Arc<AppConfig>, read_mode: bool, theme: Theme) -> Self {
+        let scroll_animator = ScrollAnimator::new(config.ui.effective_scroll());
+        let relevance_threshold = config.ai.relevance_threshold.clamp(0.0, 1.0);
+        let toasts = Toasts::new(Duration::from_secs(config.ui.toast_timeout_secs));
+        let speaker = Speaker::new(config.ui.tts_command.as_deref());
+        let reading = ReadingTimer::new(config.ui.idle_timeout_secs);
+        let ai_header_collapsed = config.ui.collapse_ai_header;
+        let image_downloader = ImageDownloader::new(&config.images);
         Self {
             client,
             config,
@@ -302,6 +609,8 @@ impl App {
             selected_feed: 0,
             articles: Vec::new(),
             selected_article: 0,
+            articles_offset: 0,
+            articles_exhausted: true,
             focus: Focus::Subscriptions,
             view_mode: ViewMode::All,
             mode: Mode::Normal,
@@ -310,9 +619,22 @@ impl App {
             search_matches: Vec::new(),
             current_match: 0,
             should_quit: false,
-            status_message: None,
+            toasts,
             pending_key: None,
+            pending_key_at: None,
+            pending_register: None,
+            pending_count: None,
+            macros: MacroRecorder::default(),
+            marks: HashMap::new(),
             rich_state: None,
+            speaker,
+            triage: Triage::default(),
+            ai_header_collapsed,
+            summary_only: false,
+            article_style: None,
+            feed_stats: None,
+            profile: None,
+            discussion: None,
             read_history: Vec::new(),
             history_position: 0,
             selected_articles: HashSet::new(),
@@ -320,16 +642,98 @@ impl App {
             visual_start_article: None,
             visual_start_feed: None,
             is_refreshing: false,
+            refresh_progress: None,
+            daemon_events: false,
+            daemon_status: DaemonStatus::default(),
+            images_requested: HashSet::new(),
+            metered: MeteredCheck::default(),
+            metered_override: None,
             image_renderer: ImageRenderer::new(),
             viewport_height: 24, // Default, will be updated on first render
             spinner_frame: 0,
             preload_cache: PreloadCache::new(None), // Initialized without disk cache, will be set later
+            image_downloader,
             read_mode,
             theme,
             scroll_animator,
+            foreground_command: None,
+            feed_manager: FeedManager::default(),
+            subscribe_prompt: SubscribePrompt::default(),
+            tag_prompt: TagPrompt::default(),
+            settings: SettingsScreen::default(),
+            read_state: ReadStateBuffer::new(),
+            reading,
+            feed_filter: String::new(),
+            collapsed_categories: HashSet::new(),
+            category_cursor: None,
+            virtual_feed: None,
+            filter_reasons: HashMap::new(),
+            article_alerts: HashMap::new(),
+            article_advisories: HashMap::new(),
+            sort_by_relevance: false,
+            hide_low_relevance: false,
+            relevance_threshold,
+        }
+    }
+
+    /// Check if the next page of articles should be fetched
+    pub fn needs_next_articles(&self) -> bool {
+        !self.articles_exhausted
+            && self.selected_article + ARTICLE_PREFETCH_MARGIN >= self.articles.len()
+    }
+
+    /// Check if the page before the loaded articles should be fetched again
+    pub fn needs_previous_articles(&self) -> bool {
+        self.articles_offset > 0 && self.selected_article < ARTICLE_PREFETCH_MARGIN
+    }
+
+    /// Add the next page after the loaded articles, dropping articles from
+    /// the front beyond `ARTICLE_WINDOW_SIZE`
+    pub fn append_articles(&mut self, page: Vec<Article>, exhausted: bool) {
+        self.articles.extend(page);
+        self.articles_exhausted = exhausted;
+        let excess = self
+            .articles
+            .len()
+            .saturating_sub(ARTICLE_WINDOW_SIZE)
+            .min(self.selected_article);
+        if excess > 0 {
+            self.articles.drain(..excess);
+            self.articles_offset += excess;
+            self.remap_article_indices(|i| i.checked_sub(excess));
         }
     }
 
+    /// Add the previous page before the loaded articles, dropping articles
+    /// from the end beyond `ARTICLE_WINDOW_SIZE`. `at_start` tells if the
+    /// page is the first of the feed.
+    pub fn prepend_articles(&mut self, page: Vec<Article>, at_start: bool) {
+        let added = page.len();
+        self.articles.splice(0..0, page);
+        // Unread-only listings shrink as articles are read, so the offset
+        // is only an estimate until the start is reached
+        self.articles_offset = if at_start { 0 } else { self.articles_offset.saturating_sub(added) };
+        self.remap_article_indices(|i| Some(i + added));
+        if self.articles.len() > ARTICLE_WINDOW_SIZE {
+            self.articles.truncate(ARTICLE_WINDOW_SIZE);
+            self.articles_exhausted = false;
+            self.remap_article_indices(|i| (i < ARTICLE_WINDOW_SIZE).then_some(i));
+        }
+    }
+
+    /// Move the cursor, selection and search matches along with their
+    /// articles after the loaded window changed, forgetting dropped ones
+    fn remap_article_indices(&mut self, map: impl Fn(usize) -> Option<usize>) {
+        self.selected_article = map(self.selected_article).unwrap_or(0);
+        self.selected_articles = self.selected_articles.iter().filter_map(|&i| map(i)).collect();
+        self.visual_start_article = self.visual_start_article.map(|i| map(i).unwrap_or(0));
+        let current = self.search_matches.get(self.current_match).and_then(|&i| map(i));
+        self.search_matches = self.search_matches.iter().filter_map(|&i| map(i)).collect();
+        self.current_match = current
+            .and_then(|current| self.search_matches.iter().position(|&i| i == current))
+            .unwrap_or(0);
+    }
+
     /// Initialize preload cache with data directory for disk cache support
     pub fn init_preload_cache(&mut self, data_dir: Option<&PathBuf>) {
         self.preload_cache = PreloadCache::new(data_dir);
@@ -361,11 +765,53 @@ impl App {
 
     /// Tick the spinner animation (call on each tick event)
     pub fn tick_spinner(&mut self) {
-        if self.is_refreshing {
+        if self.is_refreshing && !self.config.ui.reduces_motion() {
             self.spinner_frame = (self.spinner_frame + 1) % SPINNER_FRAMES.len();
         }
     }
 
+    /// Whether the daemon status should be polled for the status bar
+    pub fn daemon_status_due(&self) -> bool {
+        let ui = &self.config.ui;
+        !self.read_mode
+            && (status_format::uses_any(&ui.status_left, DAEMON_PLACEHOLDERS)
+                || status_format::uses_any(&ui.status_right, DAEMON_PLACEHOLDERS)
+                || status_format::uses_any(&ui.terminal_title, DAEMON_PLACEHOLDERS))
+            && self
+                .daemon_status
+                .polled_at
+                .is_none_or(|at| at.elapsed() >= STATUS_POLL_INTERVAL)
+    }
+
+    /// Whether metered mode is on: switched by hand, else set in the config
+    /// or the connection is metered
+    pub fn is_metered(&self) -> bool {
+        self.metered_override
+            .unwrap_or(self.config.metered.enabled || self.metered.metered)
+    }
+
+    /// Image downloads allowed at once (None for no limit)
+    pub fn max_image_downloads(&self) -> Option<usize> {
+        self.is_metered()
+            .then(|| self.config.metered.max_concurrent_downloads.max(1))
+    }
+
+    /// Whether article images load without being asked for: image preview
+    /// is on, and metered mode is off (unless images are allowed there)
+    pub fn images_load_automatically(&self) -> bool {
+        let ui = &self.config.ui;
+        ui.image_preview && !ui.screen_reader && (ui.images_on_metered || !self.is_metered())
+    }
+
+    /// Whether the current article's images are loaded and shown
+    pub fn images_enabled(&self) -> bool {
+        !self.config.ui.screen_reader
+            && (self.images_load_automatically()
+                || self
+                    .current_article()
+                    .is_some_and(|article| self.images_requested.contains(&article.id)))
+    }
+
     /// Get the current spinner character
     pub fn current_spinner(&self) -> char {
         SPINNER_FRAMES[self.spinner_frame]
@@ -381,19 +827,124 @@ impl App {
         self.feeds.get_mut(self.selected_feed)
     }
 
-    /// Get feeds to display based on view mode
+    /// Get feeds to display based on view mode and the feed filter
     /// In UnreadOnly mode, feeds with errors are always shown (highlighted in red)
+    /// and paused feeds are hidden
     pub fn visible_feeds(&self) -> Vec<&Feed> {
-        match self.view_mode {
-            ViewMode::All => self.feeds.iter().collect(),
-            ViewMode::UnreadOnly => self
-                .feeds
-                .iter()
-                .filter(|f| f.unread_count > 0 || f.has_error())
-                .collect(),
+        self.visible_feed_indices().into_iter().map(|i| &self.feeds[i]).collect()
+    }
+
+    /// Indices of the visible feeds
+    pub fn visible_feed_indices(&self) -> Vec<usize> {
+        self.feeds
+            .iter()
+            .enumerate()
+            .filter(|(_, f)| match self.view_mode {
+                ViewMode::All => true,
+                ViewMode::UnreadOnly => !f.paused && (f.unread_count > 0 || f.has_error()),
+            })
+            .filter(|(_, f)| feed_matches_filter(f, &self.feed_filter))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Rows of the subscriptions list: the visible feeds grouped by
+    /// category. While filtering, no category is collapsed so every match
+    /// shows.
+    pub fn feed_rows(&self) -> Vec<FeedRow> {
+        let expanded = HashSet::new();
+        let collapsed = if self.feed_filter.is_empty() {
+            &self.collapsed_categories
+        } else {
+            &expanded
+        };
+        feed_tree::feed_rows(&self.feeds, &self.visible_feed_indices(), collapsed)
+    }
+
+    /// Row of `rows` the subscriptions cursor is on
+    pub fn feed_cursor_row(&self, rows: &[FeedRow]) -> Option<usize> {
+        feed_tree::cursor_row(rows, self.selected_feed, self.category_cursor.as_deref())
+    }
+
+    /// Put the subscriptions cursor on a row. On a category header, the
+    /// selected feed moves to the category's first feed unless it is in the
+    /// category already.
+    pub fn select_feed_row(&mut self, row: &FeedRow) {
+        match row {
+            FeedRow::Category { name, feeds, .. } => {
+                if !feeds.contains(&self.selected_feed) {
+                    if let Some(&first) = feeds.first() {
+                        self.selected_feed = first;
+                    }
+                }
+                self.category_cursor = Some(name.clone());
+            }
+            FeedRow::Feed { index, .. } => {
+                self.selected_feed = *index;
+                self.category_cursor = None;
+            }
+        }
+    }
+
+    /// Move the subscriptions cursor `delta` rows
+    pub fn move_feed_cursor(&mut self, delta: isize) {
+        let rows = self.feed_rows();
+        if rows.is_empty() {
+            return;
+        }
+        let current = self.feed_cursor_row(&rows).unwrap_or(0);
+        let target = current.saturating_add_signed(delta).min(rows.len() - 1);
+        self.select_feed_row(&rows[target]);
+    }
+
+    /// Move the subscriptions cursor up the feed tree: from a feed in a
+    /// category to the category's header, from an expanded header to
+    /// collapsing it. Returns false when there is nothing further up.
+    pub fn ascend_feed_tree(&mut self) -> bool {
+        let rows = self.feed_rows();
+        let Some(row) = self.feed_cursor_row(&rows) else {
+            return false;
+        };
+        let Some(header) = feed_tree::header_row(&rows, row) else {
+            return false;
+        };
+        match &rows[header] {
+            FeedRow::Category { collapsed: false, name, .. } if header == row => {
+                self.collapsed_categories.insert(name.clone());
+                true
+            }
+            FeedRow::Category { .. } if header != row => {
+                self.select_feed_row(&rows[header]);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Expand the category whose header the subscriptions cursor is on.
+    /// Returns false when the cursor isn't on a collapsed header.
+    pub fn expand_cursor_category(&mut self) -> bool {
+        let rows = self.feed_rows();
+        match self.feed_cursor_row(&rows).map(|row| &rows[row]) {
+            Some(FeedRow::Category { name, collapsed: true, .. }) => {
+                self.collapsed_categories.remove(name);
+                self.category_cursor = Some(name.clone());
+                true
+            }
+            _ => false,
         }
     }
 
+    /// Collapse or expand the category whose header the subscriptions
+    /// cursor is on. Returns false when the cursor is on a feed.
+    pub fn toggle_cursor_category(&mut self) -> bool {
+        let rows = self.feed_rows();
+        let on_header = self
+            .feed_cursor_row(&rows)
+            .is_some_and(|row| matches!(rows[row], FeedRow::Category { .. }));
+        on_header && (self.expand_cursor_category() || self.ascend_feed_tree())
+    }
+
     /// Get the actual feed index from visible index
     pub fn visible_to_actual_feed_index(&self, visible_idx: usize) -> Option<usize> {
         let visible_feeds = self.visible_feeds();
@@ -408,11 +959,40 @@ impl App {
         self.visible_feeds().iter().position(|f| f.id == feed.id)
     }
 
+    /// Feed of the article at `index` (the selected feed, except in a
+    /// virtual feed)
+    pub fn article_feed_mut(&mut self, index: usize) -> Option<&mut Feed> {
+        let feed_id = self.articles.get(index)?.feed_id;
+        self.feeds.iter_mut().find(|f| f.id == feed_id)
+    }
+
+    /// Feed of the current article, falling back to the selected feed
+    pub fn current_article_feed(&self) -> Option<&Feed> {
+        self.current_article()
+            .and_then(|a| self.feeds.iter().find(|f| f.id == a.feed_id))
+            .or_else(|| self.current_feed())
+    }
+
+    /// Browser command for the current feed: its per-feed override, or
+    /// `ui.browser_command` (None means the system default browser)
+    pub fn browser_command(&self) -> Option<&str> {
+        self.current_article_feed()
+            .and_then(|feed| self.config.ui.feed_browser_commands.get(&feed.local_name))
+            .or(self.config.ui.browser_command.as_ref())
+            .map(String::as_str)
+    }
+
     /// Get the currently selected article
     pub fn current_article(&self) -> Option<&Article> {
         self.articles.get(self.selected_article)
     }
 
+    /// Discussion loaded for the current article
+    pub fn current_discussion(&self) -> Option<&Discussion> {
+        let article_id = self.current_article()?.id;
+        self.discussion.as_ref().filter(|d| d.article_id == article_id)
+    }
+
     /// Get the currently selected article mutably
     pub fn current_article_mut(&mut self) -> Option<&mut Article> {
         self.articles.get_mut(self.selected_article)
@@ -574,7 +1154,11 @@ impl App {
     /// Jump to the beginning
     pub fn jump_to_top(&mut self) {
         match self.focus {
-            Focus::Subscriptions => self.selected_feed = 0,
+            Focus::Subscriptions => {
+                if let Some(idx) = self.visible_to_actual_feed_index(0) {
+                    self.selected_feed = idx;
+                }
+            }
             Focus::ArticleList => self.selected_article = 0,
             Focus::ArticleDetail => self.detail_scroll = 0,
         }
@@ -584,7 +1168,10 @@ impl App {
     pub fn jump_to_bottom(&mut self) {
         match self.focus {
             Focus::Subscriptions => {
-                self.selected_feed = self.feeds.len().saturating_sub(1);
+                let last = self.visible_feeds().len().saturating_sub(1);
+                if let Some(idx) = self.visible_to_actual_feed_index(last) {
+                    self.selected_feed = idx;
+                }
             }
             Focus::ArticleList => {
                 self.selected_article = self.articles.len().saturating_sub(1);
@@ -603,24 +1190,72 @@ impl App {
         };
     }
 
-    /// Set a status message
+    /// Which of the selected feed's articles the list shows, and in what order
+    pub fn article_listing(&self) -> ArticleListing {
+        ArticleListing {
+            unread_only: matches!(self.view_mode, ViewMode::UnreadOnly),
+            by_relevance: self.sort_by_relevance,
+            min_score: self.hide_low_relevance.then_some(self.relevance_threshold),
+        }
+    }
+
+    /// Raise or lower the relevance threshold by `steps` increments
+    pub fn adjust_relevance_threshold(&mut self, steps: i32) {
+        let threshold = self.relevance_threshold + steps as f64 * RELEVANCE_THRESHOLD_STEP;
+        // Round away float drift so the threshold stays on the step grid
+        self.relevance_threshold = ((threshold / RELEVANCE_THRESHOLD_STEP).round()
+            * RELEVANCE_THRESHOLD_STEP)
+            .clamp(0.0, 1.0);
+    }
+
+    /// Show an informational toast
     pub fn set_status(&mut self, message: impl Into<String>) {
-        self.status_message = Some(message.into());
+        self.toasts.push(message, Severity::Info);
     }
 
-    /// Clear the status message
+    /// Show a warning toast
+    pub fn set_warning(&mut self, message: impl Into<String>) {
+        self.toasts.push(message, Severity::Warn);
+    }
+
+    /// Show an error toast
+    pub fn set_error(&mut self, message: impl Into<String>) {
+        self.toasts.push(message, Severity::Error);
+    }
+
+    /// Dismiss the toasts on screen
     pub fn clear_status(&mut self) {
-        self.status_message = None;
+        self.toasts.dismiss();
     }
 
     /// Check if we're in a mode that accepts text input
     pub fn is_input_mode(&self) -> bool {
-        matches!(self.mode, Mode::SearchForward(_) | Mode::SearchBackward(_))
+        matches!(
+            self.mode,
+            Mode::SearchForward(_) | Mode::SearchBackward(_) | Mode::FeedFilter
+        )
+    }
+
+    /// Start a two-key sequence
+    pub fn set_pending_key(&mut self, prefix: KeyBinding) {
+        self.pending_key = Some(prefix);
+        self.pending_key_at = Some(Instant::now());
     }
 
     /// Clear the pending key
     pub fn clear_pending_key(&mut self) {
         self.pending_key = None;
+        self.pending_key_at = None;
+    }
+
+    /// Prefix key whose continuations should be shown, once it has been
+    /// pending for `delay`
+    pub fn which_key_prefix(&self, delay: Duration) -> Option<KeyBinding> {
+        let pending_since = self.pending_key_at?;
+        if !matches!(self.mode, Mode::Normal) || pending_since.elapsed() < delay {
+            return None;
+        }
+        self.pending_key
     }
 
     /// Navigate to next search match
@@ -647,6 +1282,19 @@ impl App {
         }
     }
 
+    /// Whether a search query is kept after typing it, for `n`/`N` and the
+    /// highlighted titles
+    pub fn has_active_search(&self) -> bool {
+        !self.search_query.is_empty() && !matches!(self.mode, Mode::SearchForward(_) | Mode::SearchBackward(_))
+    }
+
+    /// Forget the search query and its matches
+    pub fn clear_search(&mut self) {
+        self.search_query.clear();
+        self.search_matches.clear();
+        self.current_match = 0;
+    }
+
     /// Execute search and find matches
     pub fn execute_search(&mut self) {
         self.search_matches.clear();
@@ -677,13 +1325,9 @@ impl App {
 
     /// Record current position to history using IDs (not indices)
     pub fn push_history(&mut self) {
-        // Get current feed and article IDs
-        let feed_id = match self.feeds.get(self.selected_feed) {
-            Some(feed) => feed.id,
-            None => return,
-        };
-        let article_id = match self.articles.get(self.selected_article) {
-            Some(article) => article.id,
+        // Get current article and its feed IDs
+        let (feed_id, article_id) = match self.articles.get(self.selected_article) {
+            Some(article) => (article.feed_id, article.id),
             None => return,
         };
 
@@ -721,6 +1365,32 @@ impl App {
         }
     }
 
+    /// Current position as a mark (None without a feed)
+    pub fn current_mark(&self) -> Option<Mark> {
+        let article = match self.focus {
+            Focus::Subscriptions => None,
+            Focus::ArticleList | Focus::ArticleDetail => self.current_article(),
+        };
+        let feed_id = article.map(|a| a.feed_id).or(self.current_feed().map(|f| f.id))?;
+        let article_id = article.map(|a| a.id);
+        Some(Mark {
+            feed_id,
+            article_id,
+            focus: if article_id.is_some() { self.focus } else { Focus::Subscriptions },
+        })
+    }
+
+    /// Save the current position under `register`
+    pub fn set_mark(&mut self, register: char) -> bool {
+        match self.current_mark() {
+            Some(mark) => {
+                self.marks.insert(register, mark);
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Find feed index by ID
     pub fn find_feed_index(&self, feed_id: Uuid) -> Option<usize> {
         self.feeds.iter().position(|f| f.id == feed_id)
@@ -822,15 +1492,42 @@ impl App {
         self.scroll_animator.is_animating()
     }
 
-    /// Get maximum scroll value for article detail
+    /// Get maximum scroll value for article detail, where the last line of the article (and its discussion) is at the bottom
+    /// of the pane
     pub fn max_detail_scroll(&self) -> u16 {
         if let Some(ref rich_state) = self.rich_state {
-            rich_state.total_height.saturating_sub(self.viewport_height / 2)
+            let discussion = self.current_discussion().map_or(0, |d| d.height);
+            (rich_state.total_height + discussion).saturating_sub(rich_state.viewport_height)
         } else {
             0
         }
     }
 
+    /// Article being read: the one open in the detail view
+    pub fn reading_article(&self) -> Option<Uuid> {
+        (self.focus == Focus::ArticleDetail)
+            .then(|| self.current_article().map(|a| a.id))
+            .flatten()
+    }
+
+    /// How far the article is scrolled, in percent
+    pub fn detail_scroll_depth(&self) -> u8 {
+        match self.max_detail_scroll() {
+            0 => 100,
+            max => (u32::from(self.detail_scroll.min(max)) * 100 / u32::from(max)) as u8,
+        }
+    }
+
+    /// Lines a page scroll of the article moves: the pane's height once it
+    /// was rendered, else the terminal's
+    fn detail_page_height(&self) -> u16 {
+        self.rich_state
+            .as_ref()
+            .map(|state| state.viewport_height)
+            .filter(|height| *height > 0)
+            .unwrap_or(self.viewport_height)
+    }
+
     /// Check if scroll animation is currently active
     pub fn is_scroll_animating(&self) -> bool {
         self.scroll_animator.is_animating()
@@ -841,6 +1538,21 @@ impl App {
         self.scroll_animator.needs_update()
     }
 
+    /// Whether images of the current or nearby articles are downloading
+    pub fn image_loads_pending(&self) -> bool {
+        self.preload_cache.has_loading()
+            || self.rich_state.as_ref().is_some_and(|state| state.image_cache.has_loading())
+    }
+
+    /// Whether nothing on screen changes by itself (no refresh spinner,
+    /// notifications, pending key or reading aloud), so ticks can be slow
+    pub fn is_idle(&self) -> bool {
+        !self.is_refreshing
+            && self.toasts.is_empty()
+            && self.pending_key.is_none()
+            && !self.speaker.is_active()
+    }
+
     /// Scroll article detail down by one line (smooth)
     pub fn scroll_detail_down(&mut self) {
         let max_scroll = self.max_detail_scroll();
@@ -856,25 +1568,25 @@ impl App {
     /// Scroll article detail down by half page (smooth)
     pub fn scroll_detail_half_page_down(&mut self) {
         let max_scroll = self.max_detail_scroll();
-        self.scroll_animator.scroll_half_page_down(self.viewport_height, max_scroll);
+        self.scroll_animator.scroll_half_page_down(self.detail_page_height(), max_scroll);
     }
 
     /// Scroll article detail up by half page (smooth)
     pub fn scroll_detail_half_page_up(&mut self) {
         let max_scroll = self.max_detail_scroll();
-        self.scroll_animator.scroll_half_page_up(self.viewport_height, max_scroll);
+        self.scroll_animator.scroll_half_page_up(self.detail_page_height(), max_scroll);
     }
 
     /// Scroll article detail down by full page (smooth)
     pub fn scroll_detail_full_page_down(&mut self) {
         let max_scroll = self.max_detail_scroll();
-        self.scroll_animator.scroll_full_page_down(self.viewport_height, max_scroll);
+        self.scroll_animator.scroll_full_page_down(self.detail_page_height(), max_scroll);
     }
 
     /// Scroll article detail up by full page (smooth)
     pub fn scroll_detail_full_page_up(&mut self) {
         let max_scroll = self.max_detail_scroll();
-        self.scroll_animator.scroll_full_page_up(self.viewport_height, max_scroll);
+        self.scroll_animator.scroll_full_page_up(self.detail_page_height(), max_scroll);
     }
 
     /// Jump to top of article detail (instant)
@@ -890,9 +1602,244 @@ impl App {
         self.detail_scroll = max_scroll;
     }
 
+    /// Jump to the line an article element starts at (instant), keeping a
+    /// line above it in view
+    pub fn scroll_detail_to_element(&mut self, element_index: usize) {
+        let Some(offset) = self
+            .rich_state
+            .as_ref()
+            .and_then(|state| state.element_offsets.get(element_index).copied())
+        else {
+            return;
+        };
+        let scroll = offset.saturating_sub(1).min(self.max_detail_scroll());
+        self.scroll_animator.set_scroll(scroll);
+        self.detail_scroll = scroll;
+    }
+
+    /// Headings of the current article
+    pub fn table_of_contents(&self) -> Vec<TocEntry> {
+        self.rich_state
+            .as_ref()
+            .map(|state| toc::table_of_contents(&state.content))
+            .unwrap_or_default()
+    }
+
+    /// Line each element of the current article starts at
+    fn detail_element_starts(&self) -> Vec<u16> {
+        self.rich_state
+            .as_ref()
+            .map(|state| {
+                let header = state.element_offsets.first().copied().unwrap_or(0);
+                toc::element_starts(header, &state.element_heights)
+            })
+            .unwrap_or_default()
+    }
+
+    /// Open the table of contents at the heading being read. False when the
+    /// article has no headings.
+    pub fn open_toc(&mut self) -> bool {
+        let entries = self.table_of_contents();
+        if entries.is_empty() {
+            return false;
+        }
+        let starts = self.detail_element_starts();
+        let current = toc::current_entry(&entries, &starts, self.detail_scroll).unwrap_or(0);
+        self.mode = Mode::Toc(current);
+        true
+    }
+
+    /// Scroll article detail to the start of the next (`forward`) or
+    /// previous element (smooth). False when there is none.
+    pub fn scroll_detail_to_adjacent_element(&mut self, forward: bool) -> bool {
+        let Some(ref rich_state) = self.rich_state else {
+            return false;
+        };
+        let starts = self.detail_element_starts();
+        // Keep going from where a running animation is headed
+        let scroll = self.scroll_animator.target_scroll();
+        let target = if forward {
+            toc::next_element_scroll(&rich_state.content, &starts, scroll)
+        } else {
+            toc::prev_element_scroll(&rich_state.content, &starts, scroll)
+        };
+        let max_scroll = self.max_detail_scroll();
+        match target {
+            // Elements after the bottom of the article can't come to the top
+            Some(_) if forward && scroll >= max_scroll => false,
+            Some(target) => {
+                self.scroll_animator.scroll_to(target, max_scroll);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Follow the focused footnote reference to its footnote, or the focused
+    /// footnote back to its reference. False without a footnote focused.
+    pub fn follow_footnote(&mut self) -> bool {
+        let Some((label, to_footnote, element_index)) =
+            self.rich_state.as_mut().and_then(|state| state.follow_footnote())
+        else {
+            return false;
+        };
+        self.scroll_detail_to_element(element_index);
+        if to_footnote {
+            self.set_status(format!("Footnote {} (Enter to go back)", label));
+        } else {
+            self.set_status(format!("Back to reference [{}]", label));
+        }
+        true
+    }
+
     /// Reset scroll animator when switching articles
     pub fn reset_detail_scroll(&mut self) {
         self.scroll_animator.reset();
         self.detail_scroll = 0;
     }
 }
+
+/// Whether a feed's name, title or category contains `filter` (case-insensitive)
+pub fn feed_matches_filter(feed: &Feed, filter: &str) -> bool {
+    let filter = filter.trim().to_lowercase();
+    filter.is_empty()
+        || [Some(&feed.local_name), feed.title.as_ref(), feed.category.as_ref()]
+            .into_iter()
+            .flatten()
+            .any(|field| field.to_lowercase().contains(&filter))
+}
+
+/// Byte ranges of `text` containing `query`, ignoring case. The ranges are
+/// of `text` itself, so they stay on character boundaries even where
+/// lowercasing changes a character's length.
+pub fn search_match_ranges(text: &str, query: &str) -> Vec<std::ops::Range<usize>> {
+    let query = query.to_lowercase();
+    if query.is_empty() {
+        return Vec::new();
+    }
+    // Lowercased text, with the offset in `text` each of its bytes came from
+    let mut lower = String::with_capacity(text.len());
+    let mut origin = Vec::with_capacity(text.len());
+    for (offset, c) in text.char_indices() {
+        for lc in c.to_lowercase() {
+            origin.extend(std::iter::repeat_n(offset, lc.len_utf8()));
+            lower.push(lc);
+        }
+    }
+    let mut ranges: Vec<std::ops::Range<usize>> = Vec::new();
+    for (lower_start, matched) in lower.match_indices(&query) {
+        let start = origin[lower_start];
+        // Up to the end of the character the match ends in
+        let last = origin[lower_start + matched.len() - 1];
+        let end = last + text[last..].chars().next().map_or(0, char::len_utf8);
+        if ranges.last().is_none_or(|prev| prev.end <= start) {
+            ranges.push(start..end);
+        }
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_match_ranges() {
+        assert_eq!(search_match_ranges("Rust and RUST", "rust"), [0..4, 9..13]);
+        assert!(search_match_ranges("Rust", "").is_empty());
+        assert!(search_match_ranges("Rust", "go").is_empty());
+        // "İ" lowercases to two characters; ranges stay on `text`'s boundaries
+        let text = "İstanbul İstanbul";
+        assert_eq!(search_match_ranges(text, "stan"), [2..6, 12..16]);
+        assert_eq!(search_match_ranges(text, "i"), [0..2, 10..12]);
+        assert_eq!(&text[search_match_ranges(text, "İST")[0].clone()], "İst");
+    }
+
+    #[test]
+    fn test_feed_matches_filter() {
+        let now = chrono::Utc::now();
+        let feed = Feed {
+            id: Uuid::new_v4(),
+            url: "https://example.com/feed".to_string(),
+            local_name: "hn".to_string(),
+            title: Some("Hacker News".to_string()),
+            description: None,
+            site_url: None,
+            icon_url: None,
+            last_fetched_at: None,
+            fetch_error: None,
+            created_at: now,
+            updated_at: now,
+            category: Some("Tech".to_string()),
+            paused: false,
+            refresh_interval_secs: None,
+            auto_read_days: None,
+            pinned: false,
+            position: 0,
+            moved_to: None,
+            unread_count: 0,
+        };
+
+        assert!(feed_matches_filter(&feed, ""));
+        assert!(feed_matches_filter(&feed, "HN"));
+        assert!(feed_matches_filter(&feed, "news"));
+        assert!(feed_matches_filter(&feed, " tech "));
+        assert!(!feed_matches_filter(&feed, "example"));
+    }
+
+    #[test]
+    fn test_article_window_stays_bounded() {
+        let now = chrono::Utc::now();
+        let article = |i: usize| Article {
+            id: Uuid::new_v4(),
+            feed_id: Uuid::nil(),
+            guid: i.to_string(),
+            url: None,
+            title: i.to_string(),
+            author: None,
+            content: None,
+            content_text: None,
+            summary: None,
+            summary_generated_at: None,
+            published_at: None,
+            fetched_at: now,
+            is_read: false,
+            read_at: None,
+            is_saved: false,
+            created_at: now,
+            image_url: None,
+            relevance_score: None,
+            tags: Vec::new(),
+            media: None,
+            comments_url: None,
+            updated_at: None,
+        };
+        let page = |start: usize| (start..start + ARTICLE_PAGE_SIZE as usize).map(article).collect::<Vec<_>>();
+        let client = Arc::new(DaemonClient::new(PathBuf::from("/nonexistent")));
+        let mut app = App::new(client, Arc::new(AppConfig::default()), true, Theme::default());
+
+        // Scrolling down drops pages from the front, keeping the cursor on its article
+        app.articles = page(0);
+        for start in (1..4).map(|p| p * ARTICLE_PAGE_SIZE as usize) {
+            app.selected_article = app.articles.len() - 1;
+            app.search_matches = vec![app.selected_article];
+            app.append_articles(page(start), false);
+        }
+        assert_eq!(app.articles.len(), ARTICLE_WINDOW_SIZE);
+        assert_eq!(app.articles_offset, ARTICLE_PAGE_SIZE as usize);
+        assert_eq!(app.current_article().unwrap().title, "1499");
+        assert_eq!(app.search_matches, [app.selected_article]);
+        assert!(!app.needs_previous_articles());
+
+        // Scrolling back up fetches the dropped page again and drops the last one
+        app.selected_article = 0;
+        assert!(app.needs_previous_articles());
+        app.prepend_articles(page(0), true);
+        assert_eq!(app.articles.len(), ARTICLE_WINDOW_SIZE);
+        assert_eq!(app.articles_offset, 0);
+        assert!(!app.articles_exhausted);
+        assert_eq!(app.current_article().unwrap().title, "500");
+        assert_eq!(app.articles.last().unwrap().title, "1499");
+        assert_eq!(app.articles[app.search_matches[0]].title, "1499");
+    }
+}
@@ -0,0 +1,184 @@
+//! Light/dark theme switching, following the OS appearance or a time of day
+
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use chrono::{Local, NaiveTime};
+use kenseader_core::config::{ThemeConfig, ThemeMode};
+use kenseader_core::scheduler::QuietHours;
+
+use crate::theme::{ColorDepth, Theme};
+use crate::themes::load_theme;
+
+/// How often the appearance is checked again
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Appearance {
+    Light,
+    Dark,
+}
+
+impl Appearance {
+    /// Appearance of the OS: the macOS interface style, or the
+    /// freedesktop portal's color-scheme on Linux. None when unknown.
+    pub fn detect() -> Option<Self> {
+        if cfg!(target_os = "macos") {
+            // The key only exists in dark mode
+            let output = quiet_command("defaults", &["read", "-g", "AppleInterfaceStyle"])?;
+            let dark = output.status.success() && String::from_utf8_lossy(&output.stdout).contains("Dark");
+            return Some(if dark { Appearance::Dark } else { Appearance::Light });
+        }
+        portal_color_scheme()
+    }
+
+    /// Appearance by time of day: light inside `light_hours`
+    pub fn at(time: NaiveTime, light_hours: &str) -> Self {
+        match QuietHours::parse(light_hours, 0) {
+            Some(window) if window.contains(time) => Appearance::Light,
+            _ => Appearance::Dark,
+        }
+    }
+}
+
+/// Run a command without a terminal, returning its output if it started
+pub(crate) fn quiet_command(program: &str, args: &[&str]) -> Option<std::process::Output> {
+    Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+}
+
+/// `org.freedesktop.appearance color-scheme` of the settings portal, read
+/// with gdbus or busctl (1 = prefer dark, 2 = prefer light, 0 = no preference)
+fn portal_color_scheme() -> Option<Appearance> {
+    let output = quiet_command(
+        "gdbus",
+        &[
+            "call",
+            "--session",
+            "--dest",
+            "org.freedesktop.portal.Desktop",
+            "--object-path",
+            "/org/freedesktop/portal/desktop",
+            "--method",
+            "org.freedesktop.portal.Settings.Read",
+            "org.freedesktop.appearance",
+            "color-scheme",
+        ],
+    )
+    .filter(|output| output.status.success())
+    .or_else(|| {
+        quiet_command(
+            "busctl",
+            &[
+                "--user",
+                "call",
+                "org.freedesktop.portal.Desktop",
+                "/org/freedesktop/portal/desktop",
+                "org.freedesktop.portal.Settings",
+                "Read",
+                "ss",
+                "org.freedesktop.appearance",
+                "color-scheme",
+            ],
+        )
+        .filter(|output| output.status.success())
+    })?;
+    parse_color_scheme(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// The color-scheme value is the last number of the reply, e.g.
+/// `(<<uint32 1>>,)` from gdbus or `v v u 1` from busctl
+fn parse_color_scheme(reply: &str) -> Option<Appearance> {
+    let value = reply
+        .split(|c: char| !c.is_ascii_digit())
+        .rfind(|part| !part.is_empty())?;
+    match value {
+        "1" => Some(Appearance::Dark),
+        "2" => Some(Appearance::Light),
+        _ => None,
+    }
+}
+
+/// Picks the theme of the configured mode, in the colors the terminal
+/// supports, and reports when it changes
+pub struct ThemeSwitcher {
+    config: ThemeConfig,
+    depth: ColorDepth,
+    current: Option<Appearance>,
+    checked_at: Instant,
+}
+
+impl ThemeSwitcher {
+    pub fn new(config: &ThemeConfig, depth: ColorDepth) -> Self {
+        Self {
+            config: config.clone(),
+            depth,
+            current: None,
+            checked_at: Instant::now(),
+        }
+    }
+
+    /// The theme to start with
+    pub fn initial_theme(&mut self) -> Theme {
+        self.current = self.appearance();
+        self.checked_at = Instant::now();
+        self.theme()
+    }
+
+    /// The new theme if the appearance changed since the last check
+    pub fn poll(&mut self) -> Option<Theme> {
+        if self.config.mode == ThemeMode::Fixed || self.checked_at.elapsed() < CHECK_INTERVAL {
+            return None;
+        }
+        self.checked_at = Instant::now();
+        let appearance = self.appearance();
+        if appearance == self.current {
+            return None;
+        }
+        self.current = appearance;
+        Some(self.theme())
+    }
+
+    fn appearance(&self) -> Option<Appearance> {
+        let by_time = || Appearance::at(Local::now().time(), &self.config.light_hours);
+        match self.config.mode {
+            ThemeMode::Fixed => None,
+            ThemeMode::Auto => Some(Appearance::detect().unwrap_or_else(by_time)),
+            ThemeMode::Schedule => Some(by_time()),
+        }
+    }
+
+    fn theme(&self) -> Theme {
+        let name = match self.current {
+            None => &self.config.name,
+            Some(Appearance::Light) => &self.config.light,
+            Some(Appearance::Dark) => &self.config.dark,
+        };
+        load_theme(&ThemeConfig {
+            name: name.clone(),
+            ..self.config.clone()
+        })
+        .with_depth(self.depth)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_appearance_detection() {
+        assert_eq!(parse_color_scheme("(<<uint32 1>>,)\n"), Some(Appearance::Dark));
+        assert_eq!(parse_color_scheme("v v u 2\n"), Some(Appearance::Light));
+        assert_eq!(parse_color_scheme("(<<uint32 0>>,)"), None);
+
+        let at = |h, m| NaiveTime::from_hms_opt(h, m, 0).unwrap();
+        assert_eq!(Appearance::at(at(12, 0), "07:00-19:00"), Appearance::Light);
+        assert_eq!(Appearance::at(at(19, 0), "07:00-19:00"), Appearance::Dark);
+        assert_eq!(Appearance::at(at(12, 0), "invalid"), Appearance::Dark);
+    }
+}
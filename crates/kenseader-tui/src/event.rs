@@ -4,11 +4,16 @@ use std::time::Duration;
 use anyhow::Result;
 use crossterm::event::{self, Event, KeyEvent, KeyEventKind};
 use image::DynamicImage;
+use kenseader_core::feed::FetchedPage;
+use kenseader_core::ipc::{ArticleArchivedResponse, ArticleDiscussionResponse};
+use uuid::Uuid;
 
 /// Event handler for terminal events
 pub struct EventHandler {
-    /// Default tick rate (used when idle)
+    /// Default tick rate
     tick_rate: Duration,
+    /// Slow tick rate (used when idle)
+    idle_tick_rate: Duration,
     /// Fast tick rate (used during animations)
     animation_tick_rate: Duration,
 }
@@ -40,6 +45,50 @@ pub enum RefreshResult {
     Failure {
         error: String,
     },
+    /// Subscribed to a feed from the subscribe prompt
+    Subscribed {
+        feed_id: Uuid,
+        name: String,
+        new_count: u32,
+    },
+    /// Subscribing failed (no feed found, ...)
+    SubscribeFailure {
+        error: String,
+    },
+    /// The feed to subscribe to is already subscribed as `name`
+    SubscribeDuplicate {
+        feed_id: Uuid,
+        name: String,
+    },
+    /// An existing subscription was renamed from the subscribe prompt
+    /// instead of subscribing to its feed twice
+    SubscriptionRenamed {
+        feed_id: Uuid,
+        old_name: String,
+        name: String,
+    },
+    /// The media player exited; on success the article counts as watched
+    Played {
+        article_id: Uuid,
+        error: Option<String>,
+    },
+    /// Discussion of an article looked up (and its comments fetched);
+    /// `open` when it was requested to be opened in the browser
+    Discussion {
+        article_id: Uuid,
+        open: bool,
+        result: std::result::Result<ArticleDiscussionResponse, String>,
+    },
+    /// Wayback Machine snapshot of an article looked up, to be opened
+    Archived {
+        article_id: Uuid,
+        result: std::result::Result<ArticleArchivedResponse, String>,
+    },
+    /// Web page of an article fetched, to replace its feed content
+    PageLoaded {
+        article_id: Uuid,
+        result: std::result::Result<FetchedPage, String>,
+    },
 }
 
 impl EventHandler {
@@ -47,6 +96,7 @@ impl EventHandler {
     pub fn new(tick_rate_ms: u64) -> Self {
         Self {
             tick_rate: Duration::from_millis(tick_rate_ms),
+            idle_tick_rate: Duration::from_millis(tick_rate_ms),
             animation_tick_rate: Duration::from_millis(16), // ~60 FPS for animations
         }
     }
@@ -56,15 +106,27 @@ impl EventHandler {
         let animation_tick_ms = if animation_fps == 0 { 16 } else { 1000 / animation_fps as u64 };
         Self {
             tick_rate: Duration::from_millis(tick_rate_ms),
+            idle_tick_rate: Duration::from_millis(tick_rate_ms),
             animation_tick_rate: Duration::from_millis(animation_tick_ms),
         }
     }
 
+    /// Set the tick rate used when idle (never faster than the default rate)
+    pub fn with_idle_tick_rate(mut self, idle_tick_rate_ms: u64) -> Self {
+        self.idle_tick_rate = Duration::from_millis(idle_tick_rate_ms).max(self.tick_rate);
+        self
+    }
+
     /// Poll for the next event with default tick rate
     pub fn next(&self) -> Result<Option<AppEvent>> {
         self.next_with_rate(self.tick_rate)
     }
 
+    /// Poll for the next event with idle tick rate (lower CPU use)
+    pub fn next_idle(&self) -> Result<Option<AppEvent>> {
+        self.next_with_rate(self.idle_tick_rate)
+    }
+
     /// Poll for the next event with animation tick rate (higher FPS)
     pub fn next_animation(&self) -> Result<Option<AppEvent>> {
         self.next_with_rate(self.animation_tick_rate)
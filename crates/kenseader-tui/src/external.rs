@@ -0,0 +1,100 @@
+use std::io;
+use std::process::{Command, Stdio};
+
+use kenseader_core::feed::Article;
+use kenseader_core::shell;
+
+/// A shell command to run with the TUI suspended (terminal browsers, pagers)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForegroundCommand {
+    pub command: String,
+    /// Text piped to the command's stdin
+    pub stdin: Option<String>,
+}
+
+/// Fill `{url}` and `{title}` placeholders with shell-quoted values.
+/// If the template has no `{url}`, the URL is appended as the last argument.
+pub fn expand_command(template: &str, url: &str, title: &str) -> String {
+    let command = shell::expand(template, &[("url", url), ("title", title)]);
+    if shell::has_placeholder(template, "url") {
+        command
+    } else {
+        format!("{} {}", command, shell::quote(url))
+    }
+}
+
+/// Pager for article text: `ui.pager_command`, then `$PAGER`, then `less -R`
+pub fn pager_command(configured: Option<&str>) -> String {
+    configured
+        .map(str::to_string)
+        .or_else(|| std::env::var("PAGER").ok().filter(|p| !p.trim().is_empty()))
+        .unwrap_or_else(|| "less -R".to_string())
+}
+
+/// Plain text (Markdown-style) rendering of an article for external viewers
+pub fn article_text(article: &Article) -> String {
+    let mut text = format!("# {}\n\n", article.title);
+    if let Some(ref author) = article.author {
+        text.push_str(&format!("By {}\n", author));
+    }
+    if let Some(ref url) = article.url {
+        text.push_str(&format!("<{}>\n", url));
+    }
+    if let Some(ref summary) = article.summary {
+        text.push_str(&format!("\n> {}\n", summary.replace('\n', "\n> ")));
+    }
+    let body = article
+        .content_text
+        .as_deref()
+        .or(article.content.as_deref())
+        .unwrap_or("");
+    text.push_str(&format!("\n{}\n", body.trim()));
+    text
+}
+
+/// Start a shell command detached from the terminal, without waiting for it
+pub fn spawn_background(command: &str) -> io::Result<()> {
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    // Reap the process so it doesn't linger as a zombie
+    std::thread::spawn(move || {
+        let _ = child.wait();
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pager_command_prefers_config() {
+        assert_eq!(pager_command(Some("bat -l md")), "bat -l md");
+    }
+
+    #[test]
+    fn test_expand_command_placeholders() {
+        assert_eq!(
+            expand_command("firefox --new-tab {url}", "https://a.com/?q=1&b=2", "T"),
+            "firefox --new-tab 'https://a.com/?q=1&b=2'"
+        );
+        assert_eq!(
+            expand_command("w3m", "https://a.com", "T"),
+            "w3m 'https://a.com'"
+        );
+        assert_eq!(
+            expand_command("notify-send {title} {url}", "u", "It's here"),
+            r"notify-send 'It'\''s here' 'u'"
+        );
+        // A feed-supplied URL can't unquote the title
+        assert_eq!(
+            expand_command("echo {url} {title}", "http://evil/{title}", "$(echo INJECTED >&2)"),
+            "echo 'http://evil/{title}' '$(echo INJECTED >&2)'"
+        );
+    }
+}
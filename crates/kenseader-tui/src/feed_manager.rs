@@ -0,0 +1,490 @@
+use kenseader_core::feed::{Feed, FeedSettings};
+use uuid::Uuid;
+
+/// Longest refresh interval a feed can be given (a year)
+const MAX_REFRESH_INTERVAL_MINUTES: u64 = 365 * 24 * 60;
+
+/// Action inside the feed management screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedManagerAction {
+    MoveUp,
+    MoveDown,
+    Add,
+    Edit,
+    TogglePause,
+    Delete,
+    Close,
+    // Form editing
+    NextField,
+    PrevField,
+    Input(char),
+    Backspace,
+    CursorLeft,
+    CursorRight,
+    Save,
+    CancelForm,
+    // Delete confirmation
+    ConfirmDelete,
+    CancelDelete,
+}
+
+/// State of the feed management screen
+#[derive(Debug, Clone, Default)]
+pub struct FeedManager {
+    /// Selected row (index into `App::feeds`)
+    pub selected: usize,
+    /// Add/edit form, shown on top of the list
+    pub form: Option<FeedForm>,
+    /// Whether the delete confirmation is shown for the selected feed
+    pub confirm_delete: bool,
+}
+
+impl FeedManager {
+    pub fn new(selected: usize) -> Self {
+        Self {
+            selected,
+            ..Self::default()
+        }
+    }
+
+    pub fn move_down(&mut self, feed_count: usize) {
+        if self.selected + 1 < feed_count {
+            self.selected += 1;
+        }
+    }
+
+    pub fn move_up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Keep the selection in range after feeds were added or removed
+    pub fn clamp(&mut self, feed_count: usize) {
+        self.selected = self.selected.min(feed_count.saturating_sub(1));
+    }
+}
+
+/// Single-line text input with a cursor
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TextField {
+    pub value: String,
+    /// Cursor position in characters
+    pub cursor: usize,
+}
+
+impl TextField {
+    pub fn new(value: impl Into<String>) -> Self {
+        let value = value.into();
+        let cursor = value.chars().count();
+        Self { value, cursor }
+    }
+
+    fn byte_index(&self, char_index: usize) -> usize {
+        self.value
+            .char_indices()
+            .nth(char_index)
+            .map(|(i, _)| i)
+            .unwrap_or(self.value.len())
+    }
+
+    pub fn insert(&mut self, c: char) {
+        let idx = self.byte_index(self.cursor);
+        self.value.insert(idx, c);
+        self.cursor += 1;
+    }
+
+    /// Delete the character before the cursor
+    pub fn backspace(&mut self) {
+        if self.cursor > 0 {
+            self.cursor -= 1;
+            let idx = self.byte_index(self.cursor);
+            self.value.remove(idx);
+        }
+    }
+
+    pub fn move_left(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    pub fn move_right(&mut self) {
+        self.cursor = (self.cursor + 1).min(self.value.chars().count());
+    }
+}
+
+/// Fields of the feed form, in tab order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormField {
+    Url,
+    Name,
+    Category,
+    Interval,
+    AutoRead,
+    Paused,
+}
+
+impl FormField {
+    pub const ALL: [FormField; 6] = [
+        FormField::Url,
+        FormField::Name,
+        FormField::Category,
+        FormField::Interval,
+        FormField::AutoRead,
+        FormField::Paused,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            FormField::Url => "URL",
+            FormField::Name => "Name",
+            FormField::Category => "Category",
+            FormField::Interval => "Refresh every (min)",
+            FormField::AutoRead => "Auto-read after days",
+            FormField::Paused => "Paused",
+        }
+    }
+}
+
+/// Add/edit form for a feed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeedForm {
+    /// Feed being edited (None when adding a new feed)
+    pub feed_id: Option<Uuid>,
+    pub url: TextField,
+    pub name: TextField,
+    pub category: TextField,
+    /// Refresh interval in minutes (empty = global default)
+    pub interval: TextField,
+    /// Days before unread articles are marked read (empty = global
+    /// default, 0 = never)
+    pub auto_read: TextField,
+    pub paused: bool,
+    pub focused: FormField,
+    /// Validation or save error shown in the form
+    pub error: Option<String>,
+}
+
+impl FeedForm {
+    /// Empty form for a new subscription
+    pub fn new() -> Self {
+        Self {
+            feed_id: None,
+            url: TextField::default(),
+            name: TextField::default(),
+            category: TextField::default(),
+            interval: TextField::default(),
+            auto_read: TextField::default(),
+            paused: false,
+            focused: FormField::Url,
+            error: None,
+        }
+    }
+
+    /// Form pre-filled with a feed's settings
+    pub fn edit(feed: &Feed) -> Self {
+        Self {
+            feed_id: Some(feed.id),
+            url: TextField::new(&feed.url),
+            name: TextField::new(&feed.local_name),
+            category: TextField::new(feed.category.clone().unwrap_or_default()),
+            interval: TextField::new(
+                feed.refresh_interval_secs
+                    .map(|secs| (secs / 60).to_string())
+                    .unwrap_or_default(),
+            ),
+            auto_read: TextField::new(
+                feed.auto_read_days.map(|days| days.to_string()).unwrap_or_default(),
+            ),
+            paused: feed.paused,
+            focused: FormField::Name,
+            error: None,
+        }
+    }
+
+    pub fn is_new(&self) -> bool {
+        self.feed_id.is_none()
+    }
+
+    /// Text input of a field (None for the paused checkbox)
+    pub fn text_field(&self, field: FormField) -> Option<&TextField> {
+        match field {
+            FormField::Url => Some(&self.url),
+            FormField::Name => Some(&self.name),
+            FormField::Category => Some(&self.category),
+            FormField::Interval => Some(&self.interval),
+            FormField::AutoRead => Some(&self.auto_read),
+            FormField::Paused => None,
+        }
+    }
+
+    fn focused_text_mut(&mut self) -> Option<&mut TextField> {
+        match self.focused {
+            FormField::Url => Some(&mut self.url),
+            FormField::Name => Some(&mut self.name),
+            FormField::Category => Some(&mut self.category),
+            FormField::Interval => Some(&mut self.interval),
+            FormField::AutoRead => Some(&mut self.auto_read),
+            FormField::Paused => None,
+        }
+    }
+
+    pub fn next_field(&mut self) {
+        let idx = FormField::ALL.iter().position(|f| *f == self.focused).unwrap_or(0);
+        self.focused = FormField::ALL[(idx + 1) % FormField::ALL.len()];
+    }
+
+    pub fn prev_field(&mut self) {
+        let idx = FormField::ALL.iter().position(|f| *f == self.focused).unwrap_or(0);
+        self.focused = FormField::ALL[(idx + FormField::ALL.len() - 1) % FormField::ALL.len()];
+    }
+
+    /// Type a character; space toggles the paused checkbox
+    pub fn input(&mut self, c: char) {
+        match self.focused_text_mut() {
+            Some(field) => field.insert(c),
+            None if c == ' ' => self.paused = !self.paused,
+            None => {}
+        }
+        self.error = None;
+    }
+
+    pub fn backspace(&mut self) {
+        if let Some(field) = self.focused_text_mut() {
+            field.backspace();
+        }
+        self.error = None;
+    }
+
+    pub fn cursor_left(&mut self) {
+        if let Some(field) = self.focused_text_mut() {
+            field.move_left();
+        }
+    }
+
+    pub fn cursor_right(&mut self) {
+        if let Some(field) = self.focused_text_mut() {
+            field.move_right();
+        }
+    }
+
+    /// Validate the form and build the settings to save
+    pub fn to_settings(&self) -> Result<FeedSettings, String> {
+        let url = self.url.value.trim();
+        if url.is_empty() {
+            return Err("URL is required".to_string());
+        }
+        if url::Url::parse(url).is_err() {
+            return Err(format!("Invalid URL: {}", url));
+        }
+        let name = self.name.value.trim();
+        if name.is_empty() {
+            return Err("Name is required".to_string());
+        }
+        let category = self.category.value.trim();
+        let interval = self.interval.value.trim();
+        let refresh_interval_secs = if interval.is_empty() {
+            None
+        } else {
+            match interval.parse::<u64>() {
+                Ok(minutes) if (1..=MAX_REFRESH_INTERVAL_MINUTES).contains(&minutes) => Some(minutes * 60),
+                _ => {
+                    return Err(format!(
+                        "Refresh interval must be between 1 and {} minutes (a year)",
+                        MAX_REFRESH_INTERVAL_MINUTES
+                    ))
+                }
+            }
+        };
+        let auto_read = self.auto_read.value.trim();
+        let auto_read_days = if auto_read.is_empty() {
+            None
+        } else {
+            match auto_read.parse::<u32>() {
+                Ok(days) => Some(days),
+                _ => return Err("Auto-read must be a number of days (0 = never)".to_string()),
+            }
+        };
+
+        Ok(FeedSettings {
+            url: url.to_string(),
+            local_name: name.to_string(),
+            category: (!category.is_empty()).then(|| category.to_string()),
+            paused: self.paused,
+            refresh_interval_secs,
+            auto_read_days,
+        })
+    }
+}
+
+impl Default for FeedForm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// URL + name prompt for subscribing from the main view
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SubscribePrompt {
+    pub url: TextField,
+    /// Optional; defaults to the feed title
+    pub name: TextField,
+    /// Whether the name field has focus (otherwise the URL field)
+    pub name_focused: bool,
+    /// Waiting for the daemon to validate the feed and subscribe
+    pub pending: bool,
+    pub error: Option<String>,
+    /// Existing subscription (ID and name) to the entered feed; saving again
+    /// renames it to the entered name instead of subscribing twice
+    pub duplicate: Option<(Uuid, String)>,
+}
+
+impl SubscribePrompt {
+    fn focused_mut(&mut self) -> &mut TextField {
+        if self.name_focused {
+            &mut self.name
+        } else {
+            &mut self.url
+        }
+    }
+
+    pub fn toggle_field(&mut self) {
+        self.name_focused = !self.name_focused;
+    }
+
+    pub fn input(&mut self, c: char) {
+        self.focused_mut().insert(c);
+        self.error = None;
+        self.duplicate = None;
+    }
+
+    pub fn backspace(&mut self) {
+        self.focused_mut().backspace();
+        self.error = None;
+        self.duplicate = None;
+    }
+
+    pub fn cursor_left(&mut self) {
+        self.focused_mut().move_left();
+    }
+
+    pub fn cursor_right(&mut self) {
+        self.focused_mut().move_right();
+    }
+}
+
+/// Feed IDs after swapping the feed at `from` with the one at `to`, or None
+/// if that would move a feed across the pinned/unpinned boundary
+pub fn swapped_feed_order(feeds: &[Feed], from: usize, to: usize) -> Option<Vec<Uuid>> {
+    let (a, b) = (feeds.get(from)?, feeds.get(to)?);
+    if a.pinned != b.pinned {
+        return None;
+    }
+    let mut ids: Vec<Uuid> = feeds.iter().map(|f| f.id).collect();
+    ids.swap(from, to);
+    Some(ids)
+}
+
+/// Name for a new subscription: the feed title, or the host of its URL
+pub fn default_feed_name(title: Option<&str>, url: &str) -> String {
+    title
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(str::to_string)
+        .or_else(|| {
+            url::Url::parse(url)
+                .ok()
+                .and_then(|u| u.host_str().map(|h| h.trim_start_matches("www.").to_string()))
+        })
+        .unwrap_or_else(|| url.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_field_editing() {
+        let mut field = TextField::new("héllo");
+        field.move_left();
+        field.move_left();
+        field.backspace();
+        field.insert('L');
+        assert_eq!(field.value, "héLlo");
+        assert_eq!(field.cursor, 3);
+    }
+
+    #[test]
+    fn test_swapped_feed_order_keeps_pinned_on_top() {
+        let now = chrono::Utc::now();
+        let mut feeds: Vec<Feed> = (0..3)
+            .map(|i| Feed {
+                id: Uuid::new_v4(),
+                url: format!("https://example.com/{}", i),
+                local_name: i.to_string(),
+                title: None,
+                description: None,
+                site_url: None,
+                icon_url: None,
+                last_fetched_at: None,
+                fetch_error: None,
+                created_at: now,
+                updated_at: now,
+                category: None,
+                paused: false,
+                refresh_interval_secs: None,
+                auto_read_days: None,
+                pinned: i == 0,
+                position: i,
+                moved_to: None,
+                unread_count: 0,
+            })
+            .collect();
+        let ids: Vec<Uuid> = feeds.iter().map(|f| f.id).collect();
+
+        assert_eq!(swapped_feed_order(&feeds, 2, 1), Some(vec![ids[0], ids[2], ids[1]]));
+        assert_eq!(swapped_feed_order(&feeds, 1, 0), None);
+        assert_eq!(swapped_feed_order(&feeds, 2, 3), None);
+
+        feeds[1].pinned = true;
+        assert!(swapped_feed_order(&feeds, 1, 0).is_some());
+    }
+
+    #[test]
+    fn test_default_feed_name() {
+        assert_eq!(default_feed_name(Some(" Blog "), "https://a.com/feed"), "Blog");
+        assert_eq!(default_feed_name(None, "https://www.example.com/rss"), "example.com");
+    }
+
+    #[test]
+    fn test_form_to_settings() {
+        let mut form = FeedForm::new();
+        for c in "https://example.com/feed".chars() {
+            form.input(c);
+        }
+        assert_eq!(form.to_settings().unwrap_err(), "Name is required");
+
+        form.next_field();
+        form.input('x');
+        form.next_field();
+        form.next_field();
+        form.input('9');
+        form.input('0');
+        form.next_field();
+        form.input('0');
+        form.next_field();
+        form.input(' ');
+
+        let settings = form.to_settings().unwrap();
+        assert_eq!(settings.local_name, "x");
+        assert_eq!(settings.category, None);
+        assert_eq!(settings.refresh_interval_secs, Some(5400));
+        assert_eq!(settings.auto_read_days, Some(0));
+        assert!(settings.paused);
+
+        // Intervals that would overflow are refused
+        form.prev_field();
+        form.prev_field();
+        for c in "99999999999999999".chars() {
+            form.input(c);
+        }
+        assert!(form.to_settings().unwrap_err().starts_with("Refresh interval"));
+    }
+}
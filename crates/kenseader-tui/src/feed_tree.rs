@@ -0,0 +1,174 @@
+//! Subscriptions grouped by category
+//!
+//! Feeds with a category are listed under a header for it, placed where the
+//! category's first feed is; a collapsed category shows only its header.
+//! Feeds without a category keep their place, so a list without categories
+//! looks as it always did.
+
+use std::collections::HashSet;
+
+use kenseader_core::feed::Feed;
+
+/// A row of the subscriptions list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FeedRow {
+    /// Header of a category
+    Category {
+        name: String,
+        collapsed: bool,
+        /// Unread articles of the category's listed feeds
+        unread: u32,
+        /// Indices of the category's listed feeds, in order
+        feeds: Vec<usize>,
+    },
+    /// A feed, by index in the feed list
+    Feed {
+        index: usize,
+        /// Whether the feed is listed under a category header
+        nested: bool,
+    },
+}
+
+/// Category of a feed, if it has a non-blank one
+pub fn feed_category(feed: &Feed) -> Option<&str> {
+    feed.category.as_deref().map(str::trim).filter(|c| !c.is_empty())
+}
+
+/// Rows listing the `visible` feeds, grouped by category
+pub fn feed_rows(feeds: &[Feed], visible: &[usize], collapsed: &HashSet<String>) -> Vec<FeedRow> {
+    let mut rows = Vec::new();
+    let mut listed: HashSet<&str> = HashSet::new();
+    for &index in visible {
+        let Some(name) = feed_category(&feeds[index]) else {
+            rows.push(FeedRow::Feed { index, nested: false });
+            continue;
+        };
+        if !listed.insert(name) {
+            continue;
+        }
+        let members: Vec<usize> = visible
+            .iter()
+            .copied()
+            .filter(|&i| feed_category(&feeds[i]) == Some(name))
+            .collect();
+        let is_collapsed = collapsed.contains(name);
+        rows.push(FeedRow::Category {
+            name: name.to_string(),
+            collapsed: is_collapsed,
+            unread: members.iter().map(|&i| feeds[i].unread_count).sum(),
+            feeds: members.clone(),
+        });
+        if !is_collapsed {
+            rows.extend(members.into_iter().map(|index| FeedRow::Feed { index, nested: true }));
+        }
+    }
+    rows
+}
+
+/// Row of the cursor. It is on a category header when the selected feed is
+/// in the category and either the cursor was put on the header
+/// (`on_category`) or the category is collapsed; otherwise on the selected
+/// feed.
+pub fn cursor_row(rows: &[FeedRow], selected: usize, on_category: Option<&str>) -> Option<usize> {
+    rows.iter().position(|row| match row {
+        FeedRow::Category {
+            name,
+            collapsed,
+            feeds,
+            ..
+        } => feeds.contains(&selected) && (*collapsed || on_category == Some(name.as_str())),
+        FeedRow::Feed { index, .. } => *index == selected,
+    })
+}
+
+/// Header row of the category the row at `row` is in: itself when it is a
+/// header
+pub fn header_row(rows: &[FeedRow], row: usize) -> Option<usize> {
+    match rows.get(row)? {
+        FeedRow::Category { .. } => Some(row),
+        FeedRow::Feed { nested: false, .. } => None,
+        FeedRow::Feed { nested: true, .. } => rows[..row]
+            .iter()
+            .rposition(|r| matches!(r, FeedRow::Category { .. })),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn feed(name: &str, category: Option<&str>, unread_count: u32) -> Feed {
+        let now = Utc::now();
+        Feed {
+            id: Uuid::new_v4(),
+            url: format!("https://example.com/{}", name),
+            local_name: name.to_string(),
+            title: None,
+            description: None,
+            site_url: None,
+            icon_url: None,
+            last_fetched_at: None,
+            fetch_error: None,
+            created_at: now,
+            updated_at: now,
+            category: category.map(str::to_string),
+            paused: false,
+            refresh_interval_secs: None,
+            auto_read_days: None,
+            pinned: false,
+            position: 0,
+            moved_to: None,
+            unread_count,
+        }
+    }
+
+    fn names(feeds: &[Feed], rows: &[FeedRow]) -> Vec<String> {
+        rows.iter()
+            .map(|row| match row {
+                FeedRow::Category { name, unread, .. } => format!("[{}] {}", name, unread),
+                FeedRow::Feed { index, nested } => {
+                    format!("{}{}", if *nested { "  " } else { "" }, feeds[*index].local_name)
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_feeds_are_grouped_under_their_category() {
+        let feeds = vec![
+            feed("a", None, 1),
+            feed("b", Some("Tech"), 2),
+            feed("c", Some("News"), 0),
+            feed("d", Some(" Tech "), 3),
+            feed("e", Some(""), 0),
+        ];
+        let visible: Vec<usize> = (0..feeds.len()).collect();
+        let rows = feed_rows(&feeds, &visible, &HashSet::new());
+        assert_eq!(
+            names(&feeds, &rows),
+            ["a", "[Tech] 5", "  b", "  d", "[News] 0", "  c", "e"]
+        );
+
+        // Cursor on the feed, or on the header when put there
+        assert_eq!(cursor_row(&rows, 3, None), Some(3));
+        assert_eq!(cursor_row(&rows, 3, Some("Tech")), Some(1));
+        assert_eq!(cursor_row(&rows, 2, Some("Tech")), Some(5));
+        assert_eq!(header_row(&rows, 3), Some(1));
+        assert_eq!(header_row(&rows, 0), None);
+
+        // A collapsed category hides its feeds and holds the cursor
+        let collapsed = HashSet::from(["Tech".to_string()]);
+        let rows = feed_rows(&feeds, &[0, 1, 2, 3], &collapsed);
+        assert_eq!(names(&feeds, &rows), ["a", "[Tech] 5", "[News] 0", "  c"]);
+        assert_eq!(cursor_row(&rows, 3, None), Some(1));
+    }
+
+    #[test]
+    fn test_list_without_categories_is_flat() {
+        let feeds = vec![feed("a", None, 0), feed("b", None, 0)];
+        let rows = feed_rows(&feeds, &[1], &HashSet::new());
+        assert_eq!(rows, [FeedRow::Feed { index: 1, nested: false }]);
+    }
+}
@@ -0,0 +1,298 @@
+//! Downloading article images
+//!
+//! All image loads share one HTTP client. Each request carries a Referer
+//! (the image's own site unless configured for the host), an image Accept
+//! header, the configured headers and the host's cookies: the configured
+//! ones plus those the host set on earlier responses.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use image::DynamicImage;
+use kenseader_core::config::{ImageDownloadConfig, ImageHostConfig};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, ACCEPT, COOKIE, REFERER, SET_COOKIE};
+use url::Url;
+
+use crate::rich_content::decode_image_bytes_async;
+
+/// Largest image downloaded
+const MAX_IMAGE_BYTES: usize = 5 * 1024 * 1024;
+
+const IMAGE_ACCEPT: &str = "image/avif,image/webp,image/png,image/jpeg,image/gif,image/*;q=0.8";
+
+/// Cookies set by each host, by name
+type CookieJar = HashMap<String, Vec<(String, String)>>;
+
+/// Image downloader shared by all image loads; cheap to clone
+#[derive(Clone)]
+pub struct ImageDownloader {
+    client: reqwest::Client,
+    config: Arc<ImageDownloadConfig>,
+    cookies: Arc<Mutex<CookieJar>>,
+}
+
+impl ImageDownloader {
+    pub fn new(config: &ImageDownloadConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .user_agent(&config.user_agent)
+            .timeout(Duration::from_secs(config.timeout_secs.max(1)))
+            .redirect(reqwest::redirect::Policy::limited(10))
+            .build()
+            .unwrap_or_default();
+        Self {
+            client,
+            config: Arc::new(config.clone()),
+            cookies: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Download an image and decode it
+    pub async fn download(&self, url: &str) -> Result<(Vec<u8>, DynamicImage), String> {
+        let bytes = self.download_bytes(url).await?;
+        // Use async decoding to avoid blocking the main thread
+        let image = decode_image_bytes_async(bytes.clone()).await?;
+        Ok((bytes, image))
+    }
+
+    async fn download_bytes(&self, url: &str) -> Result<Vec<u8>, String> {
+        let parsed = parse_http_url(url)?;
+        let mut response = self
+            .client
+            .get(parsed.clone())
+            .headers(self.request_headers(&parsed))
+            .send()
+            .await
+            .map_err(|e| format!("Request failed: {}", e))?;
+
+        self.remember_cookies(response.url(), response.headers());
+        if !response.status().is_success() {
+            return Err(format!("HTTP {}", response.status()));
+        }
+        if let Some(len) = response.content_length() {
+            if len as usize > MAX_IMAGE_BYTES {
+                return Err(format!("Image too large ({}B)", len));
+            }
+        }
+
+        // Read in chunks so a missing or wrong Content-Length can't make us
+        // download more than the limit
+        let mut bytes = Vec::new();
+        while let Some(chunk) = response.chunk().await.map_err(|e| format!("Read error: {}", e))? {
+            bytes.extend_from_slice(&chunk);
+            if bytes.len() > MAX_IMAGE_BYTES {
+                return Err(format!("Image too large (over {}B)", MAX_IMAGE_BYTES));
+            }
+        }
+        if bytes.is_empty() {
+            return Err("Empty response".to_string());
+        }
+        Ok(bytes)
+    }
+
+    /// Headers of a request for `url`
+    fn request_headers(&self, url: &Url) -> HeaderMap {
+        let host = url.host_str().unwrap_or_default();
+        let host_config = host_config(host, &self.config.hosts);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(ACCEPT, HeaderValue::from_static(IMAGE_ACCEPT));
+        let referer = match host_config.and_then(|c| c.referer.clone()) {
+            Some(referer) => referer,
+            None => site_referer(url),
+        };
+        if !referer.is_empty() {
+            insert_header(&mut headers, REFERER.as_str(), &referer);
+        }
+
+        let cookie = cookie_header(
+            host_config.and_then(|c| c.cookie.as_deref()),
+            self.cookies.lock().unwrap().get(host).map(Vec::as_slice).unwrap_or_default(),
+        );
+        if !cookie.is_empty() {
+            insert_header(&mut headers, COOKIE.as_str(), &cookie);
+        }
+
+        let host_headers = host_config.map(|c| &c.headers).into_iter().flatten();
+        for (name, value) in self.config.headers.iter().chain(host_headers) {
+            insert_header(&mut headers, name, value);
+        }
+        headers
+    }
+
+    /// Keep the cookies a host set, for its later images
+    fn remember_cookies(&self, url: &Url, headers: &HeaderMap) {
+        let Some(host) = url.host_str() else {
+            return;
+        };
+        let set_cookies: Vec<&str> = headers.get_all(SET_COOKIE).iter().filter_map(|v| v.to_str().ok()).collect();
+        if set_cookies.is_empty() {
+            return;
+        }
+        let mut jar = self.cookies.lock().unwrap();
+        let cookies = jar.entry(host.to_string()).or_default();
+        for set_cookie in set_cookies {
+            apply_set_cookie(cookies, set_cookie);
+        }
+    }
+}
+
+fn parse_http_url(url: &str) -> Result<Url, String> {
+    let parsed = Url::parse(url).map_err(|e| format!("Invalid URL: {}", e))?;
+    match parsed.scheme() {
+        "http" | "https" => Ok(parsed),
+        scheme => Err(format!("Unsupported URL scheme: {}", scheme)),
+    }
+}
+
+/// Referer naming the image's own site ("https://example.com/")
+fn site_referer(url: &Url) -> String {
+    match url.host_str() {
+        Some(_) => format!("{}/", url.origin().ascii_serialization()),
+        None => String::new(),
+    }
+}
+
+/// Settings of `host`: the entry for the host itself or the most specific
+/// domain it belongs to
+fn host_config<'a>(host: &str, hosts: &'a HashMap<String, ImageHostConfig>) -> Option<&'a ImageHostConfig> {
+    hosts
+        .iter()
+        .filter(|(domain, _)| {
+            let domain = domain.trim_start_matches('.');
+            host == domain || host.strip_suffix(domain).is_some_and(|sub| sub.ends_with('.'))
+        })
+        .max_by_key(|(domain, _)| domain.len())
+        .map(|(_, config)| config)
+}
+
+/// Set a header, skipping names or values that aren't valid
+fn insert_header(headers: &mut HeaderMap, name: &str, value: &str) {
+    match (HeaderName::from_bytes(name.as_bytes()), HeaderValue::from_str(value)) {
+        (Ok(name), Ok(value)) => {
+            headers.insert(name, value);
+        }
+        _ => tracing::warn!("Skipping invalid image request header {:?}", name),
+    }
+}
+
+/// Store a Set-Cookie header's cookie, or drop it when the host cleared it
+fn apply_set_cookie(cookies: &mut Vec<(String, String)>, set_cookie: &str) {
+    let pair = set_cookie.split(';').next().unwrap_or_default();
+    let Some((name, value)) = pair.split_once('=') else {
+        return;
+    };
+    let (name, value) = (name.trim(), value.trim());
+    if name.is_empty() {
+        return;
+    }
+    cookies.retain(|(existing, _)| existing != name);
+    let expired = set_cookie
+        .split(';')
+        .skip(1)
+        .any(|attr| attr.trim().eq_ignore_ascii_case("max-age=0"));
+    if !value.is_empty() && !expired {
+        cookies.push((name.to_string(), value.to_string()));
+    }
+}
+
+/// Cookie header value: the configured cookies, with ones the host set
+/// replacing those of the same name
+fn cookie_header(configured: Option<&str>, set: &[(String, String)]) -> String {
+    let mut pairs: Vec<String> = configured
+        .unwrap_or_default()
+        .split(';')
+        .map(str::trim)
+        .filter(|pair| {
+            !pair.is_empty()
+                && !set.iter().any(|(name, _)| pair.split('=').next().map(str::trim) == Some(name.as_str()))
+        })
+        .map(str::to_string)
+        .collect();
+    pairs.extend(set.iter().map(|(name, value)| format!("{}={}", name, value)));
+    pairs.join("; ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn downloader(hosts: &[(&str, ImageHostConfig)]) -> ImageDownloader {
+        let config = ImageDownloadConfig {
+            headers: HashMap::from([("X-Client".to_string(), "kenseader".to_string())]),
+            hosts: hosts.iter().map(|(host, c)| (host.to_string(), c.clone())).collect(),
+            ..ImageDownloadConfig::default()
+        };
+        ImageDownloader::new(&config)
+    }
+
+    #[test]
+    fn test_referer_defaults_to_image_site() {
+        let url = Url::parse("https://img.example.com:8443/a/b.png?x=1").unwrap();
+        let headers = downloader(&[]).request_headers(&url);
+        assert_eq!(headers[REFERER], "https://img.example.com:8443/");
+        assert_eq!(headers[ACCEPT], IMAGE_ACCEPT);
+        assert_eq!(headers["x-client"], "kenseader");
+        assert!(headers.get(COOKIE).is_none());
+    }
+
+    #[test]
+    fn test_host_settings_apply_to_subdomains() {
+        let blog = ImageHostConfig {
+            referer: Some("https://blog.example.com/post".to_string()),
+            cookie: Some("session=abc; theme=dark".to_string()),
+            headers: HashMap::from([("X-Client".to_string(), "reader".to_string())]),
+        };
+        let hidden = ImageHostConfig {
+            referer: Some(String::new()),
+            ..ImageHostConfig::default()
+        };
+        let downloader = downloader(&[("example.com", blog), ("cdn.example.com", hidden)]);
+
+        let headers = downloader.request_headers(&Url::parse("https://img.example.com/a.png").unwrap());
+        assert_eq!(headers[REFERER], "https://blog.example.com/post");
+        assert_eq!(headers[COOKIE], "session=abc; theme=dark");
+        assert_eq!(headers["x-client"], "reader");
+
+        // The more specific host wins; an empty referer sends none
+        let headers = downloader.request_headers(&Url::parse("https://cdn.example.com/a.png").unwrap());
+        assert!(headers.get(REFERER).is_none());
+        assert!(headers.get(COOKIE).is_none());
+
+        let headers = downloader.request_headers(&Url::parse("https://notexample.com/a.png").unwrap());
+        assert_eq!(headers[REFERER], "https://notexample.com/");
+    }
+
+    #[test]
+    fn test_cookies_set_by_host_are_sent_back() {
+        let downloader = downloader(&[(
+            "example.com",
+            ImageHostConfig {
+                cookie: Some("session=abc; theme=dark".to_string()),
+                ..ImageHostConfig::default()
+            },
+        )]);
+        let url = Url::parse("https://example.com/a.png").unwrap();
+        let mut response = HeaderMap::new();
+        response.append(SET_COOKIE, HeaderValue::from_static("theme=light; Path=/; HttpOnly"));
+        response.append(SET_COOKIE, HeaderValue::from_static("cdn=1; Secure"));
+        downloader.remember_cookies(&url, &response);
+        assert_eq!(downloader.request_headers(&url)[COOKIE], "session=abc; theme=light; cdn=1");
+
+        let mut response = HeaderMap::new();
+        response.append(SET_COOKIE, HeaderValue::from_static("cdn=; Max-Age=0"));
+        downloader.remember_cookies(&url, &response);
+        assert_eq!(downloader.request_headers(&url)[COOKIE], "session=abc; theme=light");
+
+        // Cookies stay with the host that set them
+        let other = Url::parse("https://other.org/a.png").unwrap();
+        assert!(downloader.request_headers(&other).get(COOKIE).is_none());
+    }
+
+    #[test]
+    fn test_parse_http_url_rejects_other_schemes() {
+        assert!(parse_http_url("https://example.com/a.png").is_ok());
+        assert!(parse_http_url("file:///etc/passwd").is_err());
+        assert!(parse_http_url("not a url").is_err());
+    }
+}
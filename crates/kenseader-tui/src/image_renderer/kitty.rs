@@ -7,6 +7,7 @@ use image::{DynamicImage, GenericImageView};
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::io::{self, Write};
+use std::ops::Range;
 
 /// Information about a displayed image
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -21,6 +22,8 @@ pub struct DisplayedImage {
     pub width: u16,
     /// Height in terminal cells
     pub height: u16,
+    /// Rows of the image's slot that are on screen (the rest is scrolled off)
+    pub visible: Range<u16>,
 }
 
 /// Result of encoding an image with aspect ratio preserved
@@ -31,16 +34,34 @@ struct EncodedImage {
     cols: u16,
     /// Actual height in cells (after preserving aspect ratio)
     rows: u16,
+    /// Height of the encoded image in pixels
+    pixel_height: u32,
+}
+
+/// An image whose data the terminal holds, ready to be placed
+struct TransmittedImage {
+    /// Kitty image ID
+    id: u32,
+    /// Width in cells (after preserving aspect ratio)
+    cols: u16,
+    /// Height in cells (after preserving aspect ratio)
+    rows: u16,
+    /// Height in pixels, for cropping rows scrolled off screen
+    pixel_height: u32,
 }
 
 /// Kitty graphics protocol renderer with state tracking
+///
+/// Images are transmitted once per quantized size and then only placed:
+/// scrolling moves or crops the placement (via the source rectangle) instead
+/// of re-encoding and re-sending the image.
 pub struct KittyRenderer {
     /// Counter for unique image IDs
     next_id: u32,
     /// Currently displayed images: url -> (kitty_id, display_info)
     displayed: HashMap<String, (u32, DisplayedImage)>,
-    /// Cached encoded images: (url, quantized_cols, quantized_rows) -> EncodedImage
-    encoded_cache: HashMap<(String, u16, u16), EncodedImage>,
+    /// Images held by the terminal: (url, quantized_cols, quantized_rows) -> image
+    transmitted: HashMap<(String, u16, u16), TransmittedImage>,
     /// Flag indicating if display state has changed
     dirty: bool,
     /// Cell dimensions (width, height) in pixels
@@ -55,7 +76,7 @@ impl KittyRenderer {
         Self {
             next_id: 1,
             displayed: HashMap::new(),
-            encoded_cache: HashMap::new(),
+            transmitted: HashMap::new(),
             dirty: false,
             // Default cell size for Kitty (can be detected via escape sequence)
             cell_size: (8, 16),
@@ -71,9 +92,9 @@ impl KittyRenderer {
         (q_cols.max(DIMENSION_BUCKET_SIZE), q_rows.max(DIMENSION_BUCKET_SIZE))
     }
 
-    /// Clear all images from the terminal
+    /// Clear all images from the terminal, freeing their data
     pub fn clear_all(&mut self) -> io::Result<()> {
-        if self.displayed.is_empty() {
+        if self.displayed.is_empty() && self.transmitted.is_empty() {
             return Ok(());
         }
 
@@ -84,7 +105,7 @@ impl KittyRenderer {
         stdout.flush()?;
 
         self.displayed.clear();
-        self.encoded_cache.clear();
+        self.transmitted.clear();
         self.dirty = false;
 
         Ok(())
@@ -92,8 +113,9 @@ impl KittyRenderer {
 
     /// Clear a specific image by ID
     pub fn clear_image(&mut self, id: u32) -> io::Result<()> {
-        // Delete specific image: a=d (delete), d=I (by ID), i=<id>
-        let cmd = format!("\x1b_Ga=d,d=I,i={}\x1b\\", id);
+        // Delete the placements of an image but keep its data, so it can be
+        // placed again without re-sending: a=d (delete), d=i (by ID), i=<id>
+        let cmd = format!("\x1b_Ga=d,d=i,i={},q=2\x1b\\", id);
         let mut stdout = io::stdout();
         stdout.write_all(cmd.as_bytes())?;
         stdout.flush()
@@ -101,9 +123,18 @@ impl KittyRenderer {
 
     /// Check if an image needs to be updated at the given position
     pub fn needs_update(&self, url: &str, x: u16, y: u16, width: u16, height: u16) -> bool {
+        self.needs_placement(url, x, y, width, height, &(0..height))
+    }
+
+    /// Check if an image's placement differs from the given position and crop
+    fn needs_placement(&self, url: &str, x: u16, y: u16, width: u16, height: u16, visible: &Range<u16>) -> bool {
         match self.displayed.get(url) {
             Some((_, info)) => {
-                info.x != x || info.y != y || info.width != width || info.height != height
+                info.x != x
+                    || info.y != y
+                    || info.width != width
+                    || info.height != height
+                    || info.visible != *visible
             }
             None => true,
         }
@@ -152,41 +183,71 @@ impl KittyRenderer {
         max_cols: u16,
         max_rows: u16,
     ) -> io::Result<u32> {
+        self.display_clipped(url, img, x, y, (max_cols, max_rows), 0..max_rows)
+    }
+
+    /// Display the `visible` rows of an image slot of `size` cells, with the
+    /// first visible row at `y`. The image is sized for the whole slot, so
+    /// scrolling only moves and crops its placement; it is encoded and sent
+    /// again only when the quantized slot size changes.
+    pub fn display_clipped(
+        &mut self,
+        url: &str,
+        img: &DynamicImage,
+        x: u16,
+        y: u16,
+        size: (u16, u16),
+        visible: Range<u16>,
+    ) -> io::Result<u32> {
+        let (max_cols, max_rows) = size;
         // Check if we need to update
-        if !self.needs_update(url, x, y, max_cols, max_rows) {
+        if !self.needs_placement(url, x, y, max_cols, max_rows, &visible) {
             // Image already displayed at correct position
             return Ok(self.displayed.get(url).map(|(id, _)| *id).unwrap_or(0));
         }
 
-        // If image exists but position changed, delete old one first
-        if let Some((old_id, _)) = self.displayed.remove(url) {
-            self.clear_image(old_id)?;
-        }
-
-        // Get or create encoded image with aspect ratio preserved
+        // Get or transmit the image with aspect ratio preserved
         // Use quantized dimensions for cache key to improve hit rate during scroll
         let (q_cols, q_rows) = Self::quantize_dimensions(max_cols, max_rows);
         let cache_key = (url.to_string(), q_cols, q_rows);
-        let encoded = if let Some(cached) = self.encoded_cache.get(&cache_key) {
-            cached
-        } else {
+        if !self.transmitted.contains_key(&cache_key) {
             // Use quantized dimensions for encoding to match cache key
-            let enc = self.encode_png_preserve_aspect(img, q_cols, q_rows)?;
-            self.encoded_cache.insert(cache_key.clone(), enc);
-            self.encoded_cache.get(&cache_key).unwrap()
-        };
-
-        // Calculate centered position
-        let actual_cols = encoded.cols;
-        let actual_rows = encoded.rows;
-        let x_offset = (max_cols.saturating_sub(actual_cols)) / 2;
-        let centered_x = x + x_offset;
+            let encoded = self.encode_png_preserve_aspect(img, q_cols, q_rows)?;
+            let id = self.next_id;
+            self.next_id += 1;
+            self.transmit_image(id, &encoded.data)?;
+            self.transmitted.insert(
+                cache_key.clone(),
+                TransmittedImage {
+                    id,
+                    cols: encoded.cols,
+                    rows: encoded.rows,
+                    pixel_height: encoded.pixel_height,
+                },
+            );
+        }
+        let image = &self.transmitted[&cache_key];
+        let (id, pixel_height) = (image.id, image.pixel_height);
+        let cols = image.cols.min(max_cols);
+        let rows = image.rows.min(max_rows);
 
-        // Display the image
-        let id = self.next_id;
-        self.next_id += 1;
+        // A different size of the same image may still be placed
+        if let Some((old_id, _)) = self.displayed.remove(url) {
+            if old_id != id {
+                self.clear_image(old_id)?;
+            }
+        }
 
-        self.send_image_at_position(id, &encoded.data, centered_x, y, actual_cols, actual_rows)?;
+        // The image may be shorter than its slot; only its own rows are cropped
+        let shown = visible.start.min(rows)..visible.end.min(rows);
+        if shown.is_empty() {
+            self.clear_image(id)?;
+        } else {
+            // Calculate centered position
+            let x_offset = (max_cols.saturating_sub(cols)) / 2;
+            let (source_y, source_height) = source_rows(rows, pixel_height, &shown);
+            self.place_image(id, x + x_offset, y, cols, shown.len() as u16, source_y, source_height)?;
+        }
 
         // Track the displayed image (use max dimensions for cache key matching)
         self.displayed.insert(
@@ -199,6 +260,7 @@ impl KittyRenderer {
                     y,
                     width: max_cols,
                     height: max_rows,
+                    visible,
                 },
             ),
         );
@@ -258,30 +320,16 @@ impl KittyRenderer {
             data: png_data,
             cols: actual_cols.min(max_cols),
             rows: actual_rows.min(max_rows),
+            pixel_height: to_encode.height(),
         })
     }
 
-    /// Send image data at a specific position
-    fn send_image_at_position(
-        &self,
-        id: u32,
-        data: &[u8],
-        x: u16,
-        y: u16,
-        cols: u16,
-        rows: u16,
-    ) -> io::Result<()> {
+    /// Send image data without displaying it
+    fn transmit_image(&self, id: u32, data: &[u8]) -> io::Result<()> {
         use base64::{engine::general_purpose::STANDARD, Engine};
 
         let mut stdout = io::stdout();
 
-        // Save cursor position
-        stdout.write_all(b"\x1b[s")?;
-
-        // Move to position (1-indexed)
-        let pos_cmd = format!("\x1b[{};{}H", y + 1, x + 1);
-        stdout.write_all(pos_cmd.as_bytes())?;
-
         // Send image in chunks
         let chunk_size = 4096;
         let total_chunks = (data.len() + chunk_size - 1) / chunk_size;
@@ -294,17 +342,14 @@ impl KittyRenderer {
 
             if is_first {
                 // First chunk: include all parameters
-                // a=T: transmit and display
+                // a=t: transmit only (placed separately)
                 // f=100: PNG format
                 // t=d: direct transmission
                 // i=<id>: image ID
-                // c=<cols>: cell columns (actual size, not max)
-                // r=<rows>: cell rows (actual size, not max)
                 // q=2: suppress all responses (quiet mode)
-                // C=1: do not move cursor
                 let cmd = format!(
-                    "\x1b_Ga=T,f=100,t=d,i={},c={},r={},q=2,C=1,m={};{}\x1b\\",
-                    id, cols, rows, more, b64_chunk
+                    "\x1b_Ga=t,f=100,t=d,i={},q=2,m={};{}\x1b\\",
+                    id, more, b64_chunk
                 );
                 stdout.write_all(cmd.as_bytes())?;
             } else {
@@ -314,6 +359,40 @@ impl KittyRenderer {
             }
         }
 
+        stdout.flush()
+    }
+
+    /// Place a transmitted image at a position, showing `source_height`
+    /// pixel rows from `source_y` scaled to `cols` x `rows` cells. Placing it
+    /// again moves the placement rather than adding one.
+    #[allow(clippy::too_many_arguments)]
+    fn place_image(
+        &self,
+        id: u32,
+        x: u16,
+        y: u16,
+        cols: u16,
+        rows: u16,
+        source_y: u32,
+        source_height: u32,
+    ) -> io::Result<()> {
+        let mut stdout = io::stdout();
+
+        // Save cursor position
+        stdout.write_all(b"\x1b[s")?;
+
+        // Move to position (1-indexed)
+        let pos_cmd = format!("\x1b[{};{}H", y + 1, x + 1);
+        stdout.write_all(pos_cmd.as_bytes())?;
+
+        // a=p: place, p=1: the image's only placement, y/h: source rows,
+        // c/r: cells to fill, C=1: do not move cursor
+        let cmd = format!(
+            "\x1b_Ga=p,i={},p=1,y={},h={},c={},r={},q=2,C=1\x1b\\",
+            id, source_y, source_height, cols, rows
+        );
+        stdout.write_all(cmd.as_bytes())?;
+
         // Restore cursor position
         stdout.write_all(b"\x1b[u")?;
         stdout.flush()
@@ -405,3 +484,27 @@ impl Default for KittyRenderer {
         Self::new()
     }
 }
+
+/// Pixel rows of an image `rows` cells tall that show the `shown` cell rows:
+/// the offset of the first one and how many
+fn source_rows(rows: u16, pixel_height: u32, shown: &Range<u16>) -> (u32, u32) {
+    let rows = rows.max(1) as u32;
+    let top = shown.start as u32 * pixel_height / rows;
+    let bottom = shown.end as u32 * pixel_height / rows;
+    (top, (bottom - top).max(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_rows_crop_scrolled_off_rows() {
+        // 10 rows of 160 pixels
+        assert_eq!(source_rows(10, 160, &(0..10)), (0, 160));
+        assert_eq!(source_rows(10, 160, &(3..10)), (48, 112));
+        assert_eq!(source_rows(10, 160, &(0..4)), (0, 64));
+        // Rows that don't split evenly still cover the image
+        assert_eq!(source_rows(3, 100, &(2..3)), (66, 34));
+    }
+}
@@ -1,7 +1,13 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
-use crate::app::{App, Focus, Mode};
+use crate::app::{App, Focus, Mode, RegisterCommand, PREVIOUS_MARK};
+use crate::feed_manager::{FeedManagerAction, FormField};
 use crate::keymap::{KeyBinding, Keymap};
+use crate::macros::{MacroRecorder, LAST_MACRO};
+use crate::settings::SettingsAction;
+use crate::share::ShareTarget;
+use crate::toc::TocAction;
+use crate::triage::{TriageAction, Verdict};
 
 /// Input action that can be performed
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -17,23 +23,54 @@ pub enum Action {
     ScrollPageUp,
     JumpToTop,
     JumpToBottom,
-    PendingG,  // First 'g' press, waiting for second 'g'
+    PendingKey(KeyBinding), // Prefix key pressed, waiting for the second key
     Select,
     OpenInBrowser,
     Delete,
     ToggleSaved,
+    MoreLikeThis,     // <leader>+: recommend more articles like this one
+    LessLikeThis,     // <leader>-: recommend fewer articles like this one
+    ShowProfile,      // <leader>p: show the learned interest profile
+    RescueArticle,    // <leader>u: mark a filtered article unread and ask for more like it
+    NextProfileWindow, // Tab in the profile: show the next time window
     Refresh,
     StartSearchForward,
     StartSearchBackward,
+    StartFeedFilter,  // Type to filter the subscriptions list
     NextMatch,
     PrevMatch,
     ToggleUnreadOnly,
     ToggleRead,       // Toggle article read/unread status
+    MarkAllRead,      // 'A': mark all unread articles of the feed read (with confirmation)
+    Share,            // Open the share menu
+    ShareTo(ShareTarget), // Pick a share menu option
+    ShowQrCode,       // Show the article URL as a QR code
+    OpenInPager,      // Pipe the article text into an external pager
+    OpenFeedManager,  // Open the feed management screen
+    FeedManager(FeedManagerAction), // Action inside the feed management screen
+    AddFeed,          // Open the subscribe prompt
+    TogglePause,      // Pause/resume the selected feed
+    MoveFeedUp,       // Move the selected feed up in the list
+    MoveFeedDown,     // Move the selected feed down in the list
+    TogglePin,        // Pin/unpin the selected feed to the top
+    SubscribePrompt(FeedManagerAction), // Edit or submit the subscribe prompt
     HistoryBack,      // Navigate to previous article in history
     HistoryForward,   // Navigate to next article in history
+    ShowHistory,      // Toggle the History view of recently read articles
+    ShowFiltered,     // Toggle the Filtered view of articles filtered by relevance
+    ShowAlerts,       // Toggle the Alerts view of articles matching watchlist keywords
+    ShowAdvisories,   // Toggle the Advisories view of articles mentioning CVEs
+    ShowMessages,     // Show the history of status messages
+    ShowHelp,         // "?": show the key bindings in effect
+    ScrollHelp(i16),  // Scroll the help overlay by a number of lines
+    ToggleRelevanceSort,     // Sort the article list by relevance score
+    ToggleRelevanceFilter,   // Hide articles below the relevance threshold
+    RaiseRelevanceThreshold, // Raise the relevance threshold (takes a count)
+    LowerRelevanceThreshold, // Lower the relevance threshold (takes a count)
     ToggleSelect,     // Space: toggle selection and move to next
     VisualMode,       // 'v': enter/exit visual selection mode
     ClearSelection,   // Esc: clear selection
+    ClearSearch,      // Esc: clear the active search
     // Image navigation and viewing
     OpenImage,        // 'o': open image in external viewer
     ViewImage,        // Enter: enter fullscreen image viewer
@@ -43,6 +80,48 @@ pub enum Action {
     // Article navigation (ArticleDetail only, respects UnreadOnly mode)
     NextArticle,      // Ctrl+J: Switch to next article
     PrevArticle,      // Ctrl+K: Switch to previous article
+    // Macros
+    RecordMacro,      // 'M': start recording (waits for a register) or stop
+    PlayMacro,        // '@': replay a macro (waits for a register)
+    StartRecording(char), // Register picked after RecordMacro
+    ReplayMacro(char),    // Register picked after PlayMacro ('@' = last macro)
+    CountDigit(u32),  // Digit of a count prefix
+    // Marks
+    SetMark,          // 'm': set a mark (waits for a register)
+    JumpToMark,       // "'": jump to a mark (waits for a register)
+    PlaceMark(char),  // Register picked after SetMark
+    GoToMark(char),   // Register picked after JumpToMark ("'" = before the last jump)
+    // Read aloud
+    Speak,            // <leader>t: start reading the article aloud, or pause/resume
+    SpeakNext,        // <leader>]: skip to the next sentence
+    SpeakPrev,        // <leader>[: back to the previous sentence
+    SpeakStop,        // <leader>x: stop reading aloud
+    Play,             // 'w': watch/listen in the media player, marking the article read
+    // AI header
+    ToggleAiHeader,   // <leader>i: collapse/expand the summary and AI metadata
+    ToggleSummaryOnly, // <leader>s: read only the summary, hiding the article body
+    StartTriage,      // 'T': summary-first triage of the article list
+    TagArticles,      // 't': add tags to the selected (or current) articles
+    // Discussion
+    OpenDiscussion,   // 'c': open the discussion (HN, Lobsters, Reddit) in the browser
+    ToggleComments,   // <leader>c: show/collapse the top comments below the article
+    // Wayback Machine
+    OpenArchived,     // "ga": open the latest Wayback Machine snapshot
+    LoadPage,         // "gp": load the web page (archived copy when gone) into the detail
+    // GitHub releases
+    OpenCompare,      // "gd": open the diff between a release and the previous one
+    TagPrompt(FeedManagerAction), // Edit, complete or submit the tag prompt
+    OpenSettings,     // "gs": open the settings screen
+    Settings(SettingsAction), // Action inside the settings screen
+    SettingsPrompt(FeedManagerAction), // Edit or submit the value of a setting
+    LoadImages,       // "gi": load the article's images when they aren't loaded automatically
+    ToggleMetered,    // "gM": switch metered mode
+    Triage(TriageAction), // Move or give a verdict in the triage view
+    // Article outline (ArticleDetail)
+    ShowToc,          // <leader>o: table of contents of the article's headings
+    Toc(TocAction),   // Move or jump in the table of contents
+    NextElement,      // '}': scroll to the start of the next element
+    PrevElement,      // '{': scroll to the start of the previous element
     ExitMode,
     Confirm,
     Cancel,
@@ -51,6 +130,20 @@ pub enum Action {
     None,
 }
 
+impl Action {
+    /// Actions that control macros themselves; their keys are never recorded
+    pub fn is_macro_control(&self) -> bool {
+        matches!(
+            self,
+            Action::RecordMacro
+                | Action::PlayMacro
+                | Action::StartRecording(_)
+                | Action::ReplayMacro(_)
+                | Action::CountDigit(_)
+        )
+    }
+}
+
 /// Handle a key event and return the corresponding action
 /// Uses the provided keymap for dynamic key binding lookup
 pub fn handle_key_event(key: KeyEvent, app: &App, keymap: &Keymap) -> Action {
@@ -61,33 +154,90 @@ pub fn handle_key_event(key: KeyEvent, app: &App, keymap: &Keymap) -> Action {
 
     // Handle special modes
     match &app.mode {
-        Mode::DeleteConfirm(_) | Mode::BatchDeleteConfirm => return handle_confirm_mode(key),
-        Mode::Help => {
-            // Any key exits help
-            return Action::ExitMode;
+        Mode::DeleteConfirm(_) | Mode::BatchDeleteConfirm | Mode::MarkAllReadConfirm(_) => {
+            return handle_confirm_mode(key)
         }
+        Mode::Help(_) => return handle_help_mode(key, app, keymap),
         Mode::ImageViewer(_) => return handle_image_viewer_mode(key, keymap),
+        Mode::Share => return handle_share_mode(key),
+        Mode::QrCode | Mode::Messages => return Action::Cancel,
+        Mode::Profile(_) => {
+            return match key.code {
+                KeyCode::Tab => Action::NextProfileWindow,
+                _ => Action::Cancel,
+            };
+        }
+        Mode::FeedManager => return handle_feed_manager_mode(key, app, keymap),
+        Mode::Settings => return handle_settings_mode(key, app, keymap),
+        Mode::Triage => return handle_triage_mode(key, keymap),
+        Mode::Toc(_) => return handle_toc_mode(key, keymap),
+        Mode::Tag => {
+            return match form_key_action(key) {
+                Some(action) => Action::TagPrompt(action),
+                None => Action::None,
+            };
+        }
+        Mode::Subscribe => {
+            return match form_key_action(key) {
+                // Only cancelling is possible while the daemon is checking the feed
+                Some(action) if app.subscribe_prompt.pending
+                    && action != FeedManagerAction::CancelForm => Action::None,
+                Some(action) => Action::SubscribePrompt(action),
+                None => Action::None,
+            };
+        }
         _ => {}
     }
 
+    // Register name after 'M', '@', 'm' or "'"; any other key cancels the command
+    if let Some(command) = app.pending_register {
+        return match (command, key.code) {
+            (RegisterCommand::RecordMacro, KeyCode::Char(c)) if MacroRecorder::is_register(c) => {
+                Action::StartRecording(c)
+            }
+            (RegisterCommand::PlayMacro, KeyCode::Char(c))
+                if MacroRecorder::is_register(c) || c == LAST_MACRO =>
+            {
+                Action::ReplayMacro(c)
+            }
+            (RegisterCommand::SetMark, KeyCode::Char(c)) if c.is_ascii_lowercase() => {
+                Action::PlaceMark(c)
+            }
+            (RegisterCommand::JumpToMark, KeyCode::Char(c))
+                if c.is_ascii_lowercase() || c == PREVIOUS_MARK =>
+            {
+                Action::GoToMark(c)
+            }
+            _ => Action::None,
+        };
+    }
+
     // Create key binding from event
     let binding = KeyBinding::new(key.code, key.modifiers);
 
-    // Handle "gg" sequence for jump-to-top
-    if keymap.has_pending_g() {
-        if binding.code == KeyCode::Char('g') && binding.modifiers == KeyModifiers::NONE {
-            if app.pending_key == Some('g') {
-                // Second 'g' press - complete the sequence
-                if let Some(action) = keymap.get_pending_g_action() {
-                    return action.clone();
-                }
-            } else {
-                // First 'g' press - start pending sequence
-                return Action::PendingG;
-            }
+    // Count prefix for unbound digits ('0' only continues a count)
+    if let KeyCode::Char(c @ '0'..='9') = key.code {
+        if key.modifiers == KeyModifiers::NONE
+            && app.pending_key.is_none()
+            && keymap.get(&binding).is_none()
+            && !keymap.is_prefix(&binding)
+            && (c != '0' || app.pending_count.is_some())
+        {
+            return Action::CountDigit(c as u32 - '0' as u32);
         }
     }
 
+    // Complete a two-key sequence ("gg", "<leader>r"). Keys that don't
+    // continue the sequence cancel it and act on their own.
+    if let Some(prefix) = app.pending_key {
+        if let Some(action) = keymap.get_sequence(&prefix, &binding) {
+            return apply_context_overrides(action.clone(), app, &binding);
+        }
+    }
+    if keymap.is_prefix(&binding) {
+        return Action::PendingKey(binding);
+    }
+
     // Lookup action from keymap
     if let Some(action) = keymap.get(&binding) {
         // Apply context-specific overrides
@@ -134,6 +284,19 @@ fn apply_context_overrides(action: Action, app: &App, binding: &KeyBinding) -> A
                 Action::None
             }
         }
+        // Share, QR code and pager only in ArticleList or ArticleDetail
+        Action::Share
+        | Action::ShowQrCode
+        | Action::OpenInPager
+        | Action::MoreLikeThis
+        | Action::LessLikeThis
+        | Action::RescueArticle => {
+            if app.focus == Focus::ArticleDetail || app.focus == Focus::ArticleList {
+                action
+            } else {
+                Action::None
+            }
+        }
         // OpenImage only in ArticleDetail
         Action::OpenImage => {
             if app.focus == Focus::ArticleDetail {
@@ -142,14 +305,26 @@ fn apply_context_overrides(action: Action, app: &App, binding: &KeyBinding) -> A
                 Action::None
             }
         }
-        // NextImage/PrevImage only in ArticleDetail
-        Action::NextImage | Action::PrevImage => {
+        // NextImage/PrevImage and the article outline only in ArticleDetail
+        Action::NextImage
+        | Action::PrevImage
+        | Action::ShowToc
+        | Action::NextElement
+        | Action::PrevElement => {
             if app.focus == Focus::ArticleDetail {
                 action
             } else {
                 Action::None
             }
         }
+        // Feed pausing and ordering only in Subscriptions
+        Action::TogglePause | Action::MoveFeedUp | Action::MoveFeedDown | Action::TogglePin => {
+            if app.focus == Focus::Subscriptions {
+                action
+            } else {
+                Action::None
+            }
+        }
         // ToggleRead becomes Delete in Subscriptions, or when feeds are selected
         Action::ToggleRead => {
             if !app.selected_feeds.is_empty() {
@@ -161,7 +336,7 @@ fn apply_context_overrides(action: Action, app: &App, binding: &KeyBinding) -> A
                 }
             }
         }
-        // Escape: clear selection if any, otherwise exit mode
+        // Escape: clear selection if any, then the search, otherwise exit mode
         Action::ExitMode => {
             if binding.code == KeyCode::Esc && binding.modifiers == KeyModifiers::NONE {
                 if app.is_visual_mode()
@@ -169,6 +344,8 @@ fn apply_context_overrides(action: Action, app: &App, binding: &KeyBinding) -> A
                     || !app.selected_feeds.is_empty()
                 {
                     Action::ClearSelection
+                } else if app.has_active_search() {
+                    Action::ClearSearch
                 } else {
                     Action::ExitMode
                 }
@@ -200,6 +377,168 @@ fn handle_confirm_mode(key: KeyEvent) -> Action {
     }
 }
 
+/// Handle key events in the share menu
+fn handle_share_mode(key: KeyEvent) -> Action {
+    match key.code {
+        KeyCode::Char(c) => match ShareTarget::from_key(c) {
+            Some(target) => Action::ShareTo(target),
+            None => Action::None,
+        },
+        KeyCode::Esc => Action::Cancel,
+        _ => Action::None,
+    }
+}
+
+/// Handle keys in the triage view: the configured up/down keys or the
+/// arrows move, single keys give verdicts
+fn handle_triage_mode(key: KeyEvent, keymap: &Keymap) -> Action {
+    let action = match key.code {
+        KeyCode::Char(' ') => TriageAction::Decide(Verdict::Keep),
+        KeyCode::Char('s') => TriageAction::Decide(Verdict::Save),
+        KeyCode::Char('d') => TriageAction::Decide(Verdict::Dismiss),
+        KeyCode::Char('u') => TriageAction::Undo,
+        KeyCode::Enter => TriageAction::Open,
+        KeyCode::Esc | KeyCode::Char('q') => TriageAction::Close,
+        KeyCode::Down => TriageAction::MoveDown,
+        KeyCode::Up => TriageAction::MoveUp,
+        _ => match keymap.get(&KeyBinding::new(key.code, key.modifiers)) {
+            Some(Action::MoveDown) => TriageAction::MoveDown,
+            Some(Action::MoveUp) => TriageAction::MoveUp,
+            _ => return Action::None,
+        },
+    };
+    Action::Triage(action)
+}
+
+/// Handle keys in the table of contents: the configured up/down keys or
+/// the arrows move, Enter jumps to the heading
+fn handle_toc_mode(key: KeyEvent, keymap: &Keymap) -> Action {
+    let action = match key.code {
+        KeyCode::Enter => TocAction::Jump,
+        KeyCode::Esc | KeyCode::Char('q') => TocAction::Close,
+        KeyCode::Down => TocAction::MoveDown,
+        KeyCode::Up => TocAction::MoveUp,
+        _ => match keymap.get(&KeyBinding::new(key.code, key.modifiers)) {
+            Some(Action::MoveDown) => TocAction::MoveDown,
+            Some(Action::MoveUp) => TocAction::MoveUp,
+            Some(Action::Select | Action::FocusRight) => TocAction::Jump,
+            _ => return Action::None,
+        },
+    };
+    Action::Toc(action)
+}
+
+/// Movement and scroll keys scroll the help overlay; any other key closes it
+fn handle_help_mode(key: KeyEvent, app: &App, keymap: &Keymap) -> Action {
+    let half_page = (app.viewport_height / 2).max(1) as i16;
+    let lines = match key.code {
+        KeyCode::Down => 1,
+        KeyCode::Up => -1,
+        KeyCode::PageDown => half_page * 2,
+        KeyCode::PageUp => -half_page * 2,
+        KeyCode::Home => i16::MIN,
+        KeyCode::End => i16::MAX,
+        _ => match keymap.get(&KeyBinding::new(key.code, key.modifiers)) {
+            Some(Action::MoveDown) => 1,
+            Some(Action::MoveUp) => -1,
+            Some(Action::ScrollHalfPageDown) => half_page,
+            Some(Action::ScrollHalfPageUp) => -half_page,
+            Some(Action::ScrollPageDown) => half_page * 2,
+            Some(Action::ScrollPageUp) => -half_page * 2,
+            Some(Action::JumpToBottom) => i16::MAX,
+            _ => return Action::Cancel,
+        },
+    };
+    Action::ScrollHelp(lines)
+}
+
+/// Map a key to a form editing action (shared by the feed form and the
+/// subscribe prompt)
+fn form_key_action(key: KeyEvent) -> Option<FeedManagerAction> {
+    let action = match (key.code, key.modifiers) {
+        (KeyCode::Esc, _) => FeedManagerAction::CancelForm,
+        (KeyCode::Enter, _) => FeedManagerAction::Save,
+        (KeyCode::Tab, _) | (KeyCode::Down, _) => FeedManagerAction::NextField,
+        (KeyCode::BackTab, _) | (KeyCode::Up, _) => FeedManagerAction::PrevField,
+        (KeyCode::Left, _) => FeedManagerAction::CursorLeft,
+        (KeyCode::Right, _) => FeedManagerAction::CursorRight,
+        (KeyCode::Backspace, _) => FeedManagerAction::Backspace,
+        (KeyCode::Char(c), KeyModifiers::NONE | KeyModifiers::SHIFT) => FeedManagerAction::Input(c),
+        _ => return None,
+    };
+    Some(action)
+}
+
+/// Handle key events in the feed management screen
+fn handle_feed_manager_mode(key: KeyEvent, app: &App, keymap: &Keymap) -> Action {
+    let manager = &app.feed_manager;
+
+    if let Some(ref form) = manager.form {
+        let Some(action) = form_key_action(key) else {
+            return Action::None;
+        };
+        // The checkbox only reacts to space
+        if form.focused == FormField::Paused && matches!(action, FeedManagerAction::Input(c) if c != ' ') {
+            return Action::None;
+        }
+        return Action::FeedManager(action);
+    }
+
+    if manager.confirm_delete {
+        return match handle_confirm_mode(key) {
+            Action::Confirm => Action::FeedManager(FeedManagerAction::ConfirmDelete),
+            Action::Cancel => Action::FeedManager(FeedManagerAction::CancelDelete),
+            _ => Action::None,
+        };
+    }
+
+    let action = match key.code {
+        KeyCode::Esc => FeedManagerAction::Close,
+        KeyCode::Char('a') => FeedManagerAction::Add,
+        KeyCode::Char('e') | KeyCode::Enter => FeedManagerAction::Edit,
+        KeyCode::Char('p') => FeedManagerAction::TogglePause,
+        KeyCode::Char('d') => FeedManagerAction::Delete,
+        KeyCode::Up => FeedManagerAction::MoveUp,
+        KeyCode::Down => FeedManagerAction::MoveDown,
+        _ => match keymap.get(&KeyBinding::new(key.code, key.modifiers)) {
+            Some(Action::MoveUp) => FeedManagerAction::MoveUp,
+            Some(Action::MoveDown) => FeedManagerAction::MoveDown,
+            Some(Action::Quit) | Some(Action::OpenFeedManager) => FeedManagerAction::Close,
+            _ => return Action::None,
+        },
+    };
+    Action::FeedManager(action)
+}
+
+/// Handle key events in the settings screen
+fn handle_settings_mode(key: KeyEvent, app: &App, keymap: &Keymap) -> Action {
+    if app.settings.editing.is_some() {
+        return match form_key_action(key) {
+            // Up/Down leave the text input alone
+            Some(FeedManagerAction::NextField | FeedManagerAction::PrevField) | None => Action::None,
+            Some(action) => Action::SettingsPrompt(action),
+        };
+    }
+
+    let action = match key.code {
+        KeyCode::Esc => SettingsAction::Close,
+        KeyCode::Enter | KeyCode::Char(' ') => SettingsAction::Change,
+        KeyCode::Right => SettingsAction::Change,
+        KeyCode::Left => SettingsAction::ChangeBack,
+        KeyCode::Up => SettingsAction::MoveUp,
+        KeyCode::Down => SettingsAction::MoveDown,
+        _ => match keymap.get(&KeyBinding::new(key.code, key.modifiers)) {
+            Some(Action::MoveUp) => SettingsAction::MoveUp,
+            Some(Action::MoveDown) => SettingsAction::MoveDown,
+            Some(Action::FocusRight) => SettingsAction::Change,
+            Some(Action::FocusLeft) => SettingsAction::ChangeBack,
+            Some(Action::Quit) => SettingsAction::Close,
+            _ => return Action::None,
+        },
+    };
+    Action::Settings(action)
+}
+
 /// Handle key events in fullscreen image viewer mode
 fn handle_image_viewer_mode(key: KeyEvent, keymap: &Keymap) -> Action {
     let binding = KeyBinding::new(key.code, key.modifiers);
@@ -29,15 +29,38 @@ impl KeyBinding {
     pub fn shift(code: KeyCode) -> Self {
         Self::new(code, KeyModifiers::SHIFT)
     }
+
+    /// Vim-style notation of the key ("g", "G", "<C-r>", "<Space>")
+    pub fn label(&self) -> String {
+        let name = match self.code {
+            KeyCode::Char(' ') => "Space".to_string(),
+            // Uppercase letters already imply Shift
+            KeyCode::Char(c) if !self.modifiers.contains(KeyModifiers::CONTROL) => {
+                return c.to_string();
+            }
+            KeyCode::Char(c) => c.to_string(),
+            KeyCode::Enter => "CR".to_string(),
+            KeyCode::BackTab => return "<S-Tab>".to_string(),
+            KeyCode::Backspace => "BS".to_string(),
+            KeyCode::F(n) => format!("F{}", n),
+            other => format!("{:?}", other),
+        };
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            format!("<C-{}>", name)
+        } else if self.modifiers.contains(KeyModifiers::SHIFT) {
+            format!("<S-{}>", name)
+        } else {
+            format!("<{}>", name)
+        }
+    }
 }
 
 /// Runtime keymap for efficient key-to-action lookup
 pub struct Keymap {
     /// Primary key bindings
     bindings: HashMap<KeyBinding, Action>,
-    /// Special handling for multi-key sequences (e.g., "gg")
-    /// Stores the first key and the action it triggers
-    pending_g_action: Option<Action>,
+    /// Two-key sequences (e.g. "gg", "<leader>r"): prefix key -> (second key, action)
+    sequences: HashMap<KeyBinding, Vec<(KeyBinding, Action)>>,
 }
 
 impl Default for Keymap {
@@ -50,27 +73,37 @@ impl Keymap {
     /// Create a keymap from configuration
     pub fn from_config(config: &KeymapConfig) -> Self {
         let mut bindings = HashMap::new();
-        let mut pending_g_action = None;
+        let mut sequences: HashMap<KeyBinding, Vec<(KeyBinding, Action)>> = HashMap::new();
+        let leader = parse_key_binding(&config.leader).unwrap_or_else(|| {
+            warn!("Invalid leader key: '{}', using '\\'", config.leader);
+            KeyBinding::simple(KeyCode::Char('\\'))
+        });
 
         // Helper to add binding with conflict detection
         let mut add_binding = |key_str: &str, action: Action| {
-            // Handle special "gg" sequence
-            if key_str == "gg" {
-                pending_g_action = Some(action);
-                return;
-            }
-
-            if let Some(binding) = parse_key_binding(key_str) {
-                if let Some(existing) = bindings.get(&binding) {
-                    warn!(
-                        "Key conflict: '{}' already bound to {:?}, ignoring binding to {:?}",
-                        key_str, existing, action
-                    );
-                } else {
-                    bindings.insert(binding, action);
+            match parse_key_sequence(key_str, leader).as_deref() {
+                Some([binding]) => {
+                    if let Some(existing) = bindings.get(binding) {
+                        warn!(
+                            "Key conflict: '{}' already bound to {:?}, ignoring binding to {:?}",
+                            key_str, existing, action
+                        );
+                    } else {
+                        bindings.insert(*binding, action);
+                    }
                 }
-            } else {
-                warn!("Invalid key binding: '{}', using default", key_str);
+                Some([prefix, second]) => {
+                    let continuations = sequences.entry(*prefix).or_default();
+                    if let Some((_, existing)) = continuations.iter().find(|(k, _)| k == second) {
+                        warn!(
+                            "Key conflict: '{}' already bound to {:?}, ignoring binding to {:?}",
+                            key_str, existing, action
+                        );
+                    } else {
+                        continuations.push((*second, action));
+                    }
+                }
+                _ => warn!("Invalid key binding: '{}', using default", key_str),
             }
         };
 
@@ -91,21 +124,70 @@ impl Keymap {
         add_binding(&config.select, Action::Select);
         add_binding(&config.open_browser, Action::OpenInBrowser);
         add_binding(&config.toggle_saved, Action::ToggleSaved);
+        add_binding(&config.more_like_this, Action::MoreLikeThis);
+        add_binding(&config.less_like_this, Action::LessLikeThis);
+        add_binding(&config.show_profile, Action::ShowProfile);
+        add_binding(&config.rescue_article, Action::RescueArticle);
         add_binding(&config.refresh, Action::Refresh);
         add_binding(&config.toggle_read, Action::ToggleRead);
+        add_binding(&config.mark_all_read, Action::MarkAllRead);
+        add_binding(&config.share, Action::Share);
+        add_binding(&config.show_qr, Action::ShowQrCode);
+        add_binding(&config.pager, Action::OpenInPager);
+        add_binding(&config.manage_feeds, Action::OpenFeedManager);
+        add_binding(&config.add_feed, Action::AddFeed);
+        add_binding(&config.toggle_pause, Action::TogglePause);
+        add_binding(&config.move_feed_up, Action::MoveFeedUp);
+        add_binding(&config.move_feed_down, Action::MoveFeedDown);
+        add_binding(&config.toggle_pin, Action::TogglePin);
         add_binding(&config.search_forward, Action::StartSearchForward);
         add_binding(&config.search_backward, Action::StartSearchBackward);
+        add_binding(&config.filter_feeds, Action::StartFeedFilter);
         add_binding(&config.next_match, Action::NextMatch);
         add_binding(&config.prev_match, Action::PrevMatch);
         add_binding(&config.toggle_unread_only, Action::ToggleUnreadOnly);
         add_binding(&config.history_back, Action::HistoryBack);
         add_binding(&config.history_forward, Action::HistoryForward);
+        add_binding(&config.show_history, Action::ShowHistory);
+        add_binding(&config.show_filtered, Action::ShowFiltered);
+        add_binding(&config.show_alerts, Action::ShowAlerts);
+        add_binding(&config.show_advisories, Action::ShowAdvisories);
+        add_binding(&config.show_messages, Action::ShowMessages);
+        add_binding(&config.help, Action::ShowHelp);
+        add_binding(&config.sort_by_relevance, Action::ToggleRelevanceSort);
+        add_binding(&config.hide_low_relevance, Action::ToggleRelevanceFilter);
+        add_binding(&config.raise_relevance_threshold, Action::RaiseRelevanceThreshold);
+        add_binding(&config.lower_relevance_threshold, Action::LowerRelevanceThreshold);
         add_binding(&config.toggle_select, Action::ToggleSelect);
         add_binding(&config.visual_mode, Action::VisualMode);
         add_binding(&config.open_item, Action::OpenImage);
         add_binding(&config.view_image, Action::ViewImage);
         add_binding(&config.next_item, Action::NextImage);
         add_binding(&config.prev_item, Action::PrevImage);
+        add_binding(&config.record_macro, Action::RecordMacro);
+        add_binding(&config.play_macro, Action::PlayMacro);
+        add_binding(&config.set_mark, Action::SetMark);
+        add_binding(&config.jump_to_mark, Action::JumpToMark);
+        add_binding(&config.speak, Action::Speak);
+        add_binding(&config.speak_next, Action::SpeakNext);
+        add_binding(&config.speak_prev, Action::SpeakPrev);
+        add_binding(&config.speak_stop, Action::SpeakStop);
+        add_binding(&config.play, Action::Play);
+        add_binding(&config.toggle_ai_header, Action::ToggleAiHeader);
+        add_binding(&config.toggle_summary_only, Action::ToggleSummaryOnly);
+        add_binding(&config.triage, Action::StartTriage);
+        add_binding(&config.tag, Action::TagArticles);
+        add_binding(&config.open_discussion, Action::OpenDiscussion);
+        add_binding(&config.toggle_comments, Action::ToggleComments);
+        add_binding(&config.open_archived, Action::OpenArchived);
+        add_binding(&config.load_page, Action::LoadPage);
+        add_binding(&config.open_compare, Action::OpenCompare);
+        add_binding(&config.settings, Action::OpenSettings);
+        add_binding(&config.load_images, Action::LoadImages);
+        add_binding(&config.toggle_metered, Action::ToggleMetered);
+        add_binding(&config.toc, Action::ShowToc);
+        add_binding(&config.next_element, Action::NextElement);
+        add_binding(&config.prev_element, Action::PrevElement);
 
         // Add hardcoded bindings that shouldn't be configurable
         // Ctrl+C always quits
@@ -118,10 +200,7 @@ impl Keymap {
         // Escape for exiting modes/clearing selection
         bindings.insert(KeyBinding::simple(KeyCode::Esc), Action::ExitMode);
 
-        Self {
-            bindings,
-            pending_g_action,
-        }
+        Self { bindings, sequences }
     }
 
     /// Get action for a key binding
@@ -131,20 +210,113 @@ impl Keymap {
 
     /// Check if "gg" sequence is configured
     pub fn has_pending_g(&self) -> bool {
-        self.pending_g_action.is_some()
+        self.get_pending_g_action().is_some()
     }
 
     /// Get the action for completed "gg" sequence
     pub fn get_pending_g_action(&self) -> Option<&Action> {
-        self.pending_g_action.as_ref()
+        let g = KeyBinding::simple(KeyCode::Char('g'));
+        self.get_sequence(&g, &g)
     }
 
     /// Check if a single 'g' press should start a pending sequence
     pub fn is_g_prefix(&self, binding: &KeyBinding) -> bool {
-        self.pending_g_action.is_some()
-            && binding.code == KeyCode::Char('g')
+        binding.code == KeyCode::Char('g')
             && binding.modifiers == KeyModifiers::NONE
+            && self.is_prefix(binding)
+    }
+
+    /// Check if a key starts a two-key sequence
+    pub fn is_prefix(&self, binding: &KeyBinding) -> bool {
+        self.sequences.contains_key(binding)
+    }
+
+    /// Get the action of a completed two-key sequence
+    pub fn get_sequence(&self, prefix: &KeyBinding, binding: &KeyBinding) -> Option<&Action> {
+        self.sequences
+            .get(prefix)?
+            .iter()
+            .find(|(key, _)| key == binding)
+            .map(|(_, action)| action)
+    }
+
+    /// Keys that can follow a prefix, with their actions, sorted by key
+    pub fn continuations(&self, prefix: &KeyBinding) -> Vec<(KeyBinding, Action)> {
+        let mut continuations = self.sequences.get(prefix).cloned().unwrap_or_default();
+        continuations.sort_by_key(|(key, _)| key.label());
+        continuations
+    }
+
+    /// Every action that has a key, with its keys (plain keys first),
+    /// sorted by action name; the help overlay lists these
+    pub fn help_entries(&self) -> Vec<(String, Vec<String>)> {
+        let single = self.bindings.iter().map(|(key, action)| (action, key.label()));
+        let sequences = self.sequences.iter().flat_map(|(prefix, continuations)| {
+            continuations
+                .iter()
+                .map(move |(key, action)| (action, format!("{}{}", prefix.label(), key.label())))
+        });
+        let mut keys: Vec<(String, bool, String)> = single
+            .chain(sequences)
+            .map(|(action, key)| (action_label(action), key.starts_with('<'), key))
+            .collect();
+        keys.sort();
+
+        let mut entries: Vec<(String, Vec<String>)> = Vec::new();
+        for (action, _, key) in keys {
+            match entries.last_mut() {
+                Some((last, keys)) if *last == action => keys.push(key),
+                _ => entries.push((action, vec![key])),
+            }
+        }
+        entries
+    }
+}
+
+/// Human-readable name of an action, for hints ("JumpToTop" -> "Jump to top")
+pub fn action_label(action: &Action) -> String {
+    let name = format!("{:?}", action);
+    let mut label = String::with_capacity(name.len() + 4);
+    for (i, c) in name.chars().enumerate() {
+        if c == '(' {
+            break;
+        }
+        if i > 0 && c.is_ascii_uppercase() {
+            label.push(' ');
+            label.push(c.to_ascii_lowercase());
+        } else {
+            label.push(c);
+        }
     }
+    label
+}
+
+/// Parse a binding of one or two keys ("j", "gg", "<C-w>j", "<leader>r");
+/// `<leader>` stands for the configured leader key
+pub fn parse_key_sequence(s: &str, leader: KeyBinding) -> Option<Vec<KeyBinding>> {
+    let s = s.trim();
+    let mut keys = Vec::new();
+    let mut rest = s;
+
+    while let Some(c) = rest.chars().next() {
+        if c == '<' {
+            if let Some(end) = rest.find('>').filter(|&end| end > 1) {
+                let inner = &rest[1..end];
+                let key = if inner.eq_ignore_ascii_case("leader") {
+                    leader
+                } else {
+                    parse_special_key(inner)?
+                };
+                keys.push(key);
+                rest = &rest[end + 1..];
+                continue;
+            }
+        }
+        keys.push(parse_key_binding(&c.to_string())?);
+        rest = &rest[c.len_utf8()..];
+    }
+
+    (!keys.is_empty() && keys.len() <= 2).then_some(keys)
 }
 
 /// Parse Vim-style key notation into KeyBinding
@@ -338,4 +510,78 @@ mod tests {
         assert!(keymap.has_pending_g());
         assert_eq!(keymap.get_pending_g_action(), Some(&Action::JumpToTop));
     }
+
+    #[test]
+    fn test_sequences_and_leader() {
+        let config = KeymapConfig {
+            leader: "<Space>".to_string(),
+            refresh: "<leader>r".to_string(),
+            share: "gs".to_string(),
+            toggle_select: "v".to_string(),
+            visual_mode: "V".to_string(),
+            ..KeymapConfig::default()
+        };
+        let keymap = Keymap::from_config(&config);
+        let g = KeyBinding::simple(KeyCode::Char('g'));
+        let space = KeyBinding::simple(KeyCode::Char(' '));
+
+        assert!(keymap.is_prefix(&space));
+        assert_eq!(
+            keymap.get_sequence(&space, &KeyBinding::simple(KeyCode::Char('r'))),
+            Some(&Action::Refresh)
+        );
+        let continuations: Vec<_> = keymap
+            .continuations(&g)
+            .into_iter()
+            .map(|(key, action)| (key.label(), action))
+            .collect();
+        assert_eq!(
+            continuations,
+            vec![
+                ("/".to_string(), Action::StartSearchBackward),
+                ("A".to_string(), Action::ShowAlerts),
+                ("C".to_string(), Action::ShowAdvisories),
+                ("M".to_string(), Action::ToggleMetered),
+                ("a".to_string(), Action::OpenArchived),
+                ("d".to_string(), Action::OpenCompare),
+                ("f".to_string(), Action::ShowFiltered),
+                ("g".to_string(), Action::JumpToTop),
+                ("i".to_string(), Action::LoadImages),
+                ("m".to_string(), Action::ShowMessages),
+                ("p".to_string(), Action::LoadPage),
+                ("s".to_string(), Action::Share)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_help_lists_configured_keys() {
+        let config = KeymapConfig {
+            leader: "<Space>".to_string(),
+            quit: "Q".to_string(),
+            show_profile: "<leader>p".to_string(),
+            ..KeymapConfig::default()
+        };
+        let entries = Keymap::from_config(&config).help_entries();
+        let keys = |action: &str| {
+            entries
+                .iter()
+                .find(|(label, _)| label == action)
+                .map(|(_, keys)| keys.clone())
+        };
+        assert_eq!(keys("Quit"), Some(vec!["Q".to_string(), "<C-c>".to_string()]));
+        assert_eq!(keys("Show profile"), Some(vec!["<Space>p".to_string()]));
+        assert_eq!(keys("Show help"), Some(vec!["?".to_string()]));
+        assert_eq!(keys("Move down"), Some(vec!["j".to_string(), "<Down>".to_string()]));
+        assert!(entries.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn test_key_labels() {
+        assert_eq!(KeyBinding::shift(KeyCode::Char('G')).label(), "G");
+        assert_eq!(KeyBinding::ctrl(KeyCode::Char('r')).label(), "<C-r>");
+        assert_eq!(KeyBinding::simple(KeyCode::Char(' ')).label(), "<Space>");
+        assert_eq!(KeyBinding::simple(KeyCode::Enter).label(), "<CR>");
+        assert_eq!(action_label(&Action::JumpToTop), "Jump to top");
+    }
 }
@@ -1,17 +1,38 @@
 pub mod app;
+pub mod appearance;
 pub mod event;
+pub mod external;
+pub mod feed_manager;
+pub mod feed_tree;
+pub mod image_download;
 pub mod image_renderer;
 pub mod input;
 pub mod keymap;
+pub mod macros;
+pub mod math;
+pub mod network;
+pub mod qr;
+pub mod read_state;
+pub mod reading;
 pub mod rich_content;
 pub mod scroll;
+pub mod settings;
+pub mod share;
+pub mod status_format;
+pub mod tagging;
 pub mod theme;
 pub mod themes;
+pub mod time_format;
+pub mod toast;
+pub mod toc;
+pub mod triage;
+pub mod tts;
 pub mod widgets;
 
 pub use app::{App, RichArticleState};
 pub use image_renderer::{ImageRenderer, RenderBackend};
 pub use keymap::{KeyBinding, Keymap};
 pub use rich_content::{ArticleImageCache, ContentElement, ImageState, RichContent};
-pub use theme::Theme;
+pub use theme::{ColorDepth, Theme};
 pub use themes::load_theme;
+pub use toast::{Severity, Toast, Toasts};
@@ -0,0 +1,127 @@
+use std::collections::{HashMap, VecDeque};
+
+use crossterm::event::KeyEvent;
+
+/// Register that `@@` replays: the last macro played
+pub const LAST_MACRO: char = '@';
+
+/// Keyboard macros recorded into registers `a`-`z`.
+///
+/// Keys are recorded rather than the actions they produced, so a replayed
+/// macro is resolved against the current focus and mode like typed input.
+#[derive(Debug, Clone, Default)]
+pub struct MacroRecorder {
+    registers: HashMap<char, Vec<KeyEvent>>,
+    /// Register being recorded and the keys so far
+    recording: Option<(char, Vec<KeyEvent>)>,
+    /// Register played last (for `@@`)
+    last_played: Option<char>,
+    /// Keys of a replay not yet processed by the main loop
+    replay: VecDeque<KeyEvent>,
+}
+
+impl MacroRecorder {
+    /// Whether `c` names a macro register
+    pub fn is_register(c: char) -> bool {
+        c.is_ascii_lowercase()
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    /// Register being recorded, if any
+    pub fn recording_register(&self) -> Option<char> {
+        self.recording.as_ref().map(|(register, _)| *register)
+    }
+
+    /// Start recording into `register`, discarding its previous contents
+    /// when the recording is stopped
+    pub fn start(&mut self, register: char) {
+        self.recording = Some((register, Vec::new()));
+    }
+
+    /// Stop recording and store the macro. Returns the register and the
+    /// number of keys recorded.
+    pub fn stop(&mut self) -> Option<(char, usize)> {
+        let (register, keys) = self.recording.take()?;
+        let len = keys.len();
+        self.registers.insert(register, keys);
+        Some((register, len))
+    }
+
+    /// Append a key to the macro being recorded
+    pub fn record(&mut self, key: KeyEvent) {
+        if let Some((_, ref mut keys)) = self.recording {
+            keys.push(key);
+        }
+    }
+
+    /// Queue the keys of `register` (or the last macro for `@`) `count` times.
+    /// Returns the register played, or None if it is empty.
+    pub fn play(&mut self, register: char, count: usize) -> Option<char> {
+        let register = if register == LAST_MACRO {
+            self.last_played?
+        } else {
+            register
+        };
+        let keys = self.registers.get(&register).filter(|keys| !keys.is_empty())?;
+        for _ in 0..count.max(1) {
+            self.replay.extend(keys.iter().copied());
+        }
+        self.last_played = Some(register);
+        Some(register)
+    }
+
+    /// Next key of the running replay
+    pub fn next_replay_key(&mut self) -> Option<KeyEvent> {
+        self.replay.pop_front()
+    }
+
+    /// Abort the running replay
+    pub fn cancel_replay(&mut self) {
+        self.replay.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    fn key(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)
+    }
+
+    fn drain(recorder: &mut MacroRecorder) -> String {
+        std::iter::from_fn(|| recorder.next_replay_key())
+            .map(|k| match k.code {
+                KeyCode::Char(c) => c,
+                _ => '?',
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_record_and_replay_with_count() {
+        let mut recorder = MacroRecorder::default();
+        assert_eq!(recorder.play('a', 1), None);
+
+        recorder.start('a');
+        assert_eq!(recorder.recording_register(), Some('a'));
+        recorder.record(key('s'));
+        recorder.record(key('j'));
+        assert_eq!(recorder.stop(), Some(('a', 2)));
+        assert!(!recorder.is_recording());
+
+        // Keys pressed while not recording are ignored
+        recorder.record(key('x'));
+
+        assert_eq!(recorder.play('a', 3), Some('a'));
+        assert_eq!(drain(&mut recorder), "sjsjsj");
+
+        assert_eq!(recorder.play(LAST_MACRO, 0), Some('a'));
+        recorder.cancel_replay();
+        assert_eq!(recorder.next_replay_key(), None);
+    }
+}
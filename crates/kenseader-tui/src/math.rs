@@ -0,0 +1,471 @@
+//! Readable plain-text approximations of LaTeX and MathML formulas, since
+//! a terminal can't typeset them
+
+/// TeX commands written as a single symbol
+const SYMBOLS: &[(&str, &str)] = &[
+    ("alpha", "α"), ("beta", "β"), ("gamma", "γ"), ("delta", "δ"), ("epsilon", "ϵ"),
+    ("varepsilon", "ε"), ("zeta", "ζ"), ("eta", "η"), ("theta", "θ"), ("vartheta", "ϑ"),
+    ("iota", "ι"), ("kappa", "κ"), ("lambda", "λ"), ("mu", "μ"), ("nu", "ν"), ("xi", "ξ"),
+    ("pi", "π"), ("varpi", "ϖ"), ("rho", "ρ"), ("varrho", "ϱ"), ("sigma", "σ"),
+    ("varsigma", "ς"), ("tau", "τ"), ("upsilon", "υ"), ("phi", "ϕ"), ("varphi", "φ"),
+    ("chi", "χ"), ("psi", "ψ"), ("omega", "ω"), ("Gamma", "Γ"), ("Delta", "Δ"),
+    ("Theta", "Θ"), ("Lambda", "Λ"), ("Xi", "Ξ"), ("Pi", "Π"), ("Sigma", "Σ"),
+    ("Upsilon", "Υ"), ("Phi", "Φ"), ("Psi", "Ψ"), ("Omega", "Ω"),
+    ("sum", "∑"), ("prod", "∏"), ("coprod", "∐"), ("int", "∫"), ("iint", "∬"),
+    ("iiint", "∭"), ("oint", "∮"), ("partial", "∂"), ("nabla", "∇"), ("infty", "∞"),
+    ("pm", "±"), ("mp", "∓"), ("times", "×"), ("div", "÷"), ("cdot", "·"), ("cdotp", "·"),
+    ("cdots", "⋯"), ("ldots", "…"), ("dots", "…"), ("vdots", "⋮"), ("ddots", "⋱"),
+    ("leq", "≤"), ("le", "≤"), ("geq", "≥"), ("ge", "≥"), ("neq", "≠"), ("ne", "≠"),
+    ("approx", "≈"), ("equiv", "≡"), ("sim", "∼"), ("simeq", "≃"), ("cong", "≅"),
+    ("propto", "∝"), ("ll", "≪"), ("gg", "≫"), ("in", "∈"), ("notin", "∉"), ("ni", "∋"),
+    ("subset", "⊂"), ("subseteq", "⊆"), ("supset", "⊃"), ("supseteq", "⊇"), ("cup", "∪"),
+    ("cap", "∩"), ("setminus", "∖"), ("emptyset", "∅"), ("varnothing", "∅"),
+    ("forall", "∀"), ("exists", "∃"), ("neg", "¬"), ("lnot", "¬"), ("land", "∧"),
+    ("wedge", "∧"), ("lor", "∨"), ("vee", "∨"), ("oplus", "⊕"), ("otimes", "⊗"),
+    ("to", "→"), ("rightarrow", "→"), ("leftarrow", "←"), ("gets", "←"),
+    ("leftrightarrow", "↔"), ("Rightarrow", "⇒"), ("Leftarrow", "⇐"),
+    ("Leftrightarrow", "⇔"), ("iff", "⇔"), ("implies", "⟹"), ("mapsto", "↦"),
+    ("uparrow", "↑"), ("downarrow", "↓"), ("langle", "⟨"), ("rangle", "⟩"),
+    ("lfloor", "⌊"), ("rfloor", "⌋"), ("lceil", "⌈"), ("rceil", "⌉"), ("circ", "∘"),
+    ("bullet", "•"), ("star", "⋆"), ("ast", "∗"), ("perp", "⊥"), ("parallel", "∥"),
+    ("mid", "|"), ("vert", "|"), ("Vert", "‖"), ("angle", "∠"), ("triangle", "△"),
+    ("square", "□"), ("prime", "′"), ("ell", "ℓ"), ("hbar", "ℏ"), ("Re", "ℜ"),
+    ("Im", "ℑ"), ("aleph", "ℵ"), ("degree", "°"),
+];
+
+/// Functions written upright as their name
+const FUNCTIONS: &[&str] = &[
+    "sin", "cos", "tan", "cot", "sec", "csc", "arcsin", "arccos", "arctan", "sinh", "cosh",
+    "tanh", "log", "ln", "lg", "exp", "lim", "liminf", "limsup", "max", "min", "sup", "inf",
+    "det", "dim", "ker", "deg", "arg", "gcd", "Pr", "mod", "bmod",
+];
+
+/// Commands that only change the size, style or spacing of what follows
+const IGNORED: &[&str] = &[
+    "left", "right", "big", "Big", "bigg", "Bigg", "bigl", "bigr", "Bigl", "Bigr", "biggl",
+    "biggr", "displaystyle", "textstyle", "scriptstyle", "limits", "nolimits", "middle",
+];
+
+/// Commands whose argument is kept as it is
+const STYLES: &[&str] = &[
+    "mathrm", "mathit", "mathbf", "mathsf", "mathtt", "mathcal", "mathscr", "mathfrak",
+    "boldsymbol", "bm", "text", "textrm", "textbf", "textit", "mbox", "operatorname",
+];
+
+/// Accents, added as combining characters after the argument
+const ACCENTS: &[(&str, char)] = &[
+    ("hat", '\u{302}'), ("widehat", '\u{302}'), ("bar", '\u{304}'), ("overline", '\u{305}'),
+    ("vec", '\u{20d7}'), ("tilde", '\u{303}'), ("widetilde", '\u{303}'), ("dot", '\u{307}'),
+    ("ddot", '\u{308}'),
+];
+
+const SUPERSCRIPTS: &[(char, char)] = &[
+    ('0', '⁰'), ('1', '¹'), ('2', '²'), ('3', '³'), ('4', '⁴'), ('5', '⁵'), ('6', '⁶'),
+    ('7', '⁷'), ('8', '⁸'), ('9', '⁹'), ('+', '⁺'), ('-', '⁻'), ('=', '⁼'), ('(', '⁽'),
+    (')', '⁾'), ('a', 'ᵃ'), ('b', 'ᵇ'), ('c', 'ᶜ'), ('d', 'ᵈ'), ('e', 'ᵉ'), ('f', 'ᶠ'),
+    ('g', 'ᵍ'), ('h', 'ʰ'), ('i', 'ⁱ'), ('j', 'ʲ'), ('k', 'ᵏ'), ('l', 'ˡ'), ('m', 'ᵐ'),
+    ('n', 'ⁿ'), ('o', 'ᵒ'), ('p', 'ᵖ'), ('r', 'ʳ'), ('s', 'ˢ'), ('t', 'ᵗ'), ('u', 'ᵘ'),
+    ('v', 'ᵛ'), ('w', 'ʷ'), ('x', 'ˣ'), ('y', 'ʸ'), ('z', 'ᶻ'), ('T', 'ᵀ'), ('′', '′'),
+    ('*', '*'),
+];
+
+const SUBSCRIPTS: &[(char, char)] = &[
+    ('0', '₀'), ('1', '₁'), ('2', '₂'), ('3', '₃'), ('4', '₄'), ('5', '₅'), ('6', '₆'),
+    ('7', '₇'), ('8', '₈'), ('9', '₉'), ('+', '₊'), ('-', '₋'), ('=', '₌'), ('(', '₍'),
+    (')', '₎'), ('a', 'ₐ'), ('e', 'ₑ'), ('h', 'ₕ'), ('i', 'ᵢ'), ('j', 'ⱼ'), ('k', 'ₖ'),
+    ('l', 'ₗ'), ('m', 'ₘ'), ('n', 'ₙ'), ('o', 'ₒ'), ('p', 'ₚ'), ('r', 'ᵣ'), ('s', 'ₛ'),
+    ('t', 'ₜ'), ('u', 'ᵤ'), ('v', 'ᵥ'), ('x', 'ₓ'),
+];
+
+/// Approximate a TeX formula in plain text: Greek letters and operators
+/// become their symbols, exponents and indices superscript or subscript
+/// characters where they exist, fractions `a/b`
+pub fn tex_to_unicode(tex: &str) -> String {
+    let chars: Vec<char> = tex.chars().collect();
+    let mut pos = 0;
+    let text = TexParser { chars: &chars, pos: &mut pos }.group(false);
+    collapse_spaces(&text)
+}
+
+struct TexParser<'a> {
+    chars: &'a [char],
+    pos: &'a mut usize,
+}
+
+impl TexParser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(*self.pos).copied()
+    }
+
+    /// Convert up to the end, or up to the closing brace with `braced`
+    fn group(&mut self, braced: bool) -> String {
+        let mut out = String::new();
+        while let Some(c) = self.peek() {
+            if c == '}' && braced {
+                *self.pos += 1;
+                break;
+            }
+            out.push_str(&self.token());
+        }
+        out
+    }
+
+    /// One argument: a braced group, a command or a single character
+    fn argument(&mut self) -> String {
+        while self.peek().is_some_and(char::is_whitespace) {
+            *self.pos += 1;
+        }
+        self.token()
+    }
+
+    /// Optional `[...]` argument
+    fn optional(&mut self) -> Option<String> {
+        if self.peek() != Some('[') {
+            return None;
+        }
+        *self.pos += 1;
+        let mut out = String::new();
+        while let Some(c) = self.peek() {
+            if c == ']' {
+                *self.pos += 1;
+                break;
+            }
+            out.push_str(&self.token());
+        }
+        Some(out)
+    }
+
+    fn token(&mut self) -> String {
+        let Some(c) = self.peek() else {
+            return String::new();
+        };
+        *self.pos += 1;
+        match c {
+            '{' => self.group(true),
+            '}' => String::new(),
+            '^' => superscript(&self.argument()),
+            '_' => subscript(&self.argument()),
+            '\'' => "′".to_string(),
+            '~' => " ".to_string(),
+            '&' => "  ".to_string(),
+            '\\' => self.command(),
+            c => c.to_string(),
+        }
+    }
+
+    fn command(&mut self) -> String {
+        let start = *self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_alphabetic()) {
+            *self.pos += 1;
+        }
+        if *self.pos == start {
+            // Single-character commands: spacing, line breaks, escapes
+            let Some(c) = self.peek() else {
+                return String::new();
+            };
+            *self.pos += 1;
+            return match c {
+                ',' | ':' | ';' | ' ' => " ".to_string(),
+                '!' => String::new(),
+                '\\' => "\n".to_string(),
+                '|' => "‖".to_string(),
+                c => c.to_string(),
+            };
+        }
+        let name: String = self.chars[start..*self.pos].iter().collect();
+
+        if let Some((_, symbol)) = SYMBOLS.iter().find(|(command, _)| *command == name) {
+            return symbol.to_string();
+        }
+        if FUNCTIONS.contains(&name.as_str()) {
+            return format!("{} ", name);
+        }
+        if IGNORED.contains(&name.as_str()) {
+            // `\left.` and `\right.` stand for no delimiter
+            if self.peek() == Some('.') {
+                *self.pos += 1;
+            }
+            return String::new();
+        }
+        if STYLES.contains(&name.as_str()) {
+            return self.argument();
+        }
+        if let Some((_, accent)) = ACCENTS.iter().find(|(command, _)| *command == name) {
+            let mut argument = self.argument();
+            argument.push(*accent);
+            return argument;
+        }
+        match name.as_str() {
+            "frac" | "dfrac" | "tfrac" | "cfrac" => {
+                let numerator = self.argument();
+                let denominator = self.argument();
+                format!("{}/{}", parenthesize(&numerator), parenthesize(&denominator))
+            }
+            "binom" | "dbinom" | "tbinom" => {
+                let n = self.argument();
+                let k = self.argument();
+                format!("C({}, {})", n.trim(), k.trim())
+            }
+            "sqrt" => {
+                let index = self.optional();
+                let radicand = self.argument();
+                let index = index.map(|index| superscript(&index)).unwrap_or_default();
+                format!("{}√{}", index, parenthesize(&radicand))
+            }
+            "mathbb" => self.argument().chars().map(double_struck).collect(),
+            "quad" | "qquad" => "  ".to_string(),
+            // Environment names and labels aren't shown
+            "begin" | "end" | "label" => {
+                self.argument();
+                String::new()
+            }
+            "tag" => format!("  ({})", self.argument()),
+            _ => name,
+        }
+    }
+}
+
+/// Blackboard bold letters for the common number sets
+fn double_struck(c: char) -> char {
+    match c {
+        'N' => 'ℕ',
+        'Z' => 'ℤ',
+        'Q' => 'ℚ',
+        'R' => 'ℝ',
+        'C' => 'ℂ',
+        'P' => 'ℙ',
+        'H' => 'ℍ',
+        c => c,
+    }
+}
+
+/// `text` in superscript characters, or `^(text)` when some have none
+pub fn superscript(text: &str) -> String {
+    scripted(text, SUPERSCRIPTS, '^')
+}
+
+/// `text` in subscript characters, or `_(text)` when some have none
+pub fn subscript(text: &str) -> String {
+    scripted(text, SUBSCRIPTS, '_')
+}
+
+fn scripted(text: &str, table: &[(char, char)], marker: char) -> String {
+    let text = text.trim();
+    let mapped: Option<String> = text
+        .chars()
+        .map(|c| table.iter().find(|(plain, _)| *plain == c).map(|(_, script)| *script))
+        .collect();
+    match mapped {
+        Some(mapped) => mapped,
+        None if text.chars().count() == 1 => format!("{}{}", marker, text),
+        None => format!("{}({})", marker, text),
+    }
+}
+
+/// Parentheses around a fraction part or root longer than one term
+fn parenthesize(text: &str) -> String {
+    let text = text.trim();
+    if text.chars().all(|c| c.is_alphanumeric() || c == '.') || text.chars().count() == 1 {
+        text.to_string()
+    } else {
+        format!("({})", text)
+    }
+}
+
+/// Runs of spaces become one, on each line
+fn collapse_spaces(text: &str) -> String {
+    text.lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// MathML element, with the text of token elements (`<mi>`, `<mo>`...)
+struct Node {
+    name: String,
+    attributes: String,
+    children: Vec<Node>,
+    text: String,
+}
+
+/// Approximate a MathML `<math>` element in plain text. The TeX source in
+/// an `application/x-tex` annotation (added by KaTeX and MathJax) is used
+/// when there is one.
+pub fn mathml_to_text(mathml: &str) -> String {
+    let root = parse_mathml(mathml);
+    if let Some(tex) = find_tex_annotation(&root) {
+        return tex_to_unicode(&tex);
+    }
+    collapse_spaces(&render_node(&root))
+}
+
+fn find_tex_annotation(node: &Node) -> Option<String> {
+    if node.name == "annotation" && node.attributes.to_lowercase().contains("x-tex") {
+        return Some(node.text.clone()).filter(|tex| !tex.trim().is_empty());
+    }
+    node.children.iter().find_map(find_tex_annotation)
+}
+
+/// Parse MathML into a tree, leniently: unclosed elements end with their parent
+fn parse_mathml(mathml: &str) -> Node {
+    let mut stack = vec![Node {
+        name: String::new(),
+        attributes: String::new(),
+        children: Vec::new(),
+        text: String::new(),
+    }];
+    let mut rest = mathml;
+    while !rest.is_empty() {
+        let Some(open) = rest.find('<') else {
+            push_text(&mut stack, rest);
+            break;
+        };
+        push_text(&mut stack, &rest[..open]);
+        let Some(close) = rest[open..].find('>') else {
+            break;
+        };
+        let tag = &rest[open + 1..open + close];
+        rest = &rest[open + close + 1..];
+
+        if let Some(name) = tag.strip_prefix('/') {
+            let name = local_name(name);
+            if let Some(depth) = stack.iter().rposition(|node| node.name == name).filter(|&d| d > 0) {
+                while stack.len() > depth {
+                    let node = stack.pop().unwrap();
+                    stack.last_mut().unwrap().children.push(node);
+                }
+            }
+            continue;
+        }
+        if tag.starts_with('!') || tag.starts_with('?') {
+            continue;
+        }
+        let self_closing = tag.ends_with('/');
+        let tag = tag.trim_end_matches('/');
+        let (name, attributes) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+        let node = Node {
+            name: local_name(name),
+            attributes: attributes.to_string(),
+            children: Vec::new(),
+            text: String::new(),
+        };
+        if self_closing {
+            stack.last_mut().unwrap().children.push(node);
+        } else {
+            stack.push(node);
+        }
+    }
+    while stack.len() > 1 {
+        let node = stack.pop().unwrap();
+        stack.last_mut().unwrap().children.push(node);
+    }
+    stack.pop().unwrap()
+}
+
+/// Element name without a namespace prefix (`m:mi`), lowercased
+fn local_name(name: &str) -> String {
+    let name = name.trim();
+    name.rsplit(':').next().unwrap_or(name).to_lowercase()
+}
+
+fn push_text(stack: &mut [Node], text: &str) {
+    if let Some(node) = stack.last_mut() {
+        node.text.push_str(&decode_entities(text));
+    }
+}
+
+/// Entities common in MathML; anything else is left as written
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&#x2212;", "−")
+        .replace("&minus;", "−")
+        .replace("&amp;", "&")
+}
+
+/// Operators written with a space on each side
+const SPACED_OPERATORS: &[&str] = &[
+    "=", "+", "−", "-", "<", ">", "≤", "≥", "≠", "≈", "≡", "→", "⇒", "⇔", "×", "±", "∈", "∉",
+    "⊂", "⊆", "∪", "∩",
+];
+
+fn render_node(node: &Node) -> String {
+    let child = |index: usize| node.children.get(index).map(render_node).unwrap_or_default();
+    let children = || node.children.iter().map(render_node).collect::<String>();
+    match node.name.as_str() {
+        "mi" | "mn" | "mtext" | "ms" => node.text.trim().to_string(),
+        "mo" => {
+            let operator = node.text.trim();
+            if SPACED_OPERATORS.contains(&operator) {
+                format!(" {} ", operator)
+            } else if operator == "," {
+                ", ".to_string()
+            } else {
+                operator.to_string()
+            }
+        }
+        "mspace" => " ".to_string(),
+        "msup" | "mover" => format!("{}{}", child(0), superscript(&child(1))),
+        "msub" | "munder" => format!("{}{}", child(0), subscript(&child(1))),
+        "msubsup" | "munderover" => {
+            format!("{}{}{}", child(0), subscript(&child(1)), superscript(&child(2)))
+        }
+        "mfrac" => format!("{}/{}", parenthesize(&child(0)), parenthesize(&child(1))),
+        "msqrt" => format!("√{}", parenthesize(&children())),
+        "mroot" => format!("{}√{}", superscript(&child(1)), parenthesize(&child(0))),
+        "mfenced" => {
+            let attribute = |name: &str, default: &str| {
+                attribute_value(&node.attributes, name).unwrap_or_else(|| default.to_string())
+            };
+            let separator = attribute("separators", ",");
+            let parts: Vec<String> = node.children.iter().map(render_node).collect();
+            let separator = format!("{} ", separator.trim());
+            format!("{}{}{}", attribute("open", "("), parts.join(&separator), attribute("close", ")"))
+        }
+        "mtable" => node.children.iter().map(render_node).collect::<Vec<_>>().join("\n"),
+        "mtr" | "mlabeledtr" => node.children.iter().map(render_node).collect::<Vec<_>>().join("  "),
+        "annotation" | "annotation-xml" | "mphantom" | "none" | "mprescripts" => String::new(),
+        _ => children(),
+    }
+}
+
+fn attribute_value(attributes: &str, name: &str) -> Option<String> {
+    for quote in ['"', '\''] {
+        let pattern = format!("{}={}", name, quote);
+        if let Some(start) = attributes.find(&pattern) {
+            let value = &attributes[start + pattern.len()..];
+            return value.find(quote).map(|end| value[..end].to_string());
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tex_to_unicode() {
+        assert_eq!(tex_to_unicode(r"e^{i\pi} + 1 = 0"), "e^(iπ) + 1 = 0");
+        assert_eq!(tex_to_unicode(r"\sum_{i=1}^{n} x_i^2"), "∑ᵢ₌₁ⁿ xᵢ²");
+        assert_eq!(tex_to_unicode(r"\frac{a+b}{2} \leq \sqrt{ab}"), "(a+b)/2 ≤ √ab");
+        assert_eq!(tex_to_unicode(r"\left( \mathbb{R}^n \right) \to \mathbf{x}_{\alpha}"), "( ℝⁿ ) → x_α");
+        assert_eq!(tex_to_unicode(r"\sin \theta \cdot f'(x)"), "sin θ · f′(x)");
+    }
+
+    #[test]
+    fn test_mathml_to_text() {
+        let mathml = r#"<math><mrow><msup><mi>x</mi><mn>2</mn></msup><mo>+</mo>
+            <mfrac><mn>1</mn><mi>y</mi></mfrac></mrow></math>"#;
+        assert_eq!(mathml_to_text(mathml), "x² + 1/y");
+
+        // The TeX annotation wins over the presentation markup
+        let katex = r#"<math><semantics><mrow><mi>a</mi></mrow>
+            <annotation encoding="application/x-tex">\alpha_0</annotation></semantics></math>"#;
+        assert_eq!(mathml_to_text(katex), "α₀");
+    }
+}
@@ -0,0 +1,92 @@
+//! Metered connection detection, to hold back image downloads
+
+use std::time::{Duration, Instant};
+
+use crate::appearance::quiet_command;
+
+/// How often the connection is checked again
+const CHECK_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Whether NetworkManager reports the connection as metered (e.g. a phone
+/// hotspot), read with gdbus or busctl. False when unknown.
+pub fn is_metered() -> bool {
+    if !cfg!(target_os = "linux") {
+        return false;
+    }
+    let output = quiet_command(
+        "gdbus",
+        &[
+            "call",
+            "--system",
+            "--dest",
+            "org.freedesktop.NetworkManager",
+            "--object-path",
+            "/org/freedesktop/NetworkManager",
+            "--method",
+            "org.freedesktop.DBus.Properties.Get",
+            "org.freedesktop.NetworkManager",
+            "Metered",
+        ],
+    )
+    .filter(|output| output.status.success())
+    .or_else(|| {
+        quiet_command(
+            "busctl",
+            &[
+                "--system",
+                "get-property",
+                "org.freedesktop.NetworkManager",
+                "/org/freedesktop/NetworkManager",
+                "org.freedesktop.NetworkManager",
+                "Metered",
+            ],
+        )
+        .filter(|output| output.status.success())
+    });
+    output.is_some_and(|output| parse_metered(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// NetworkManager's metered state is the last number of the reply, e.g.
+/// `(<uint32 1>,)` from gdbus or `u 1` from busctl: 1 = yes, 3 = guessed yes
+fn parse_metered(reply: &str) -> bool {
+    let value = reply
+        .split(|c: char| !c.is_ascii_digit())
+        .rfind(|part| !part.is_empty());
+    matches!(value, Some("1" | "3"))
+}
+
+/// Metered state of the connection, checked again every few minutes
+#[derive(Debug, Default)]
+pub struct MeteredCheck {
+    pub metered: bool,
+    checked_at: Option<Instant>,
+}
+
+impl MeteredCheck {
+    /// Check the connection if the last check is old enough; returns
+    /// whether the metered state changed
+    pub fn poll(&mut self) -> bool {
+        if self.checked_at.is_some_and(|at| at.elapsed() < CHECK_INTERVAL) {
+            return false;
+        }
+        self.checked_at = Some(Instant::now());
+        let metered = is_metered();
+        let changed = metered != self.metered;
+        self.metered = metered;
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_metered() {
+        assert!(parse_metered("(<uint32 1>,)\n"));
+        assert!(parse_metered("u 3\n"));
+        assert!(!parse_metered("u 4\n"));
+        assert!(!parse_metered("(<uint32 0>,)"));
+        assert!(!parse_metered(""));
+    }
+}
@@ -0,0 +1,540 @@
+//! Minimal QR code encoder for showing article URLs in the terminal
+//!
+//! Byte mode, error correction level L, versions 1-10 (up to 271 bytes),
+//! which covers practically every article URL.
+
+/// Highest supported version (57x57 modules)
+const MAX_VERSION: usize = 10;
+
+/// Error correction codewords per block at level L, indexed by version
+const ECC_PER_BLOCK: [usize; MAX_VERSION + 1] = [0, 7, 10, 15, 20, 26, 18, 20, 24, 30, 18];
+
+/// Number of error correction blocks at level L, indexed by version
+const NUM_BLOCKS: [usize; MAX_VERSION + 1] = [0, 1, 1, 1, 1, 1, 2, 2, 2, 2, 4];
+
+/// Format information bits for level L
+const FORMAT_BITS_L: u32 = 1;
+
+/// An encoded QR code
+#[derive(Debug, Clone)]
+pub struct QrCode {
+    size: usize,
+    modules: Vec<bool>,
+}
+
+impl QrCode {
+    /// Encode bytes, choosing the smallest version that fits.
+    /// Returns `None` if the data is too long.
+    pub fn encode(data: &[u8]) -> Option<Self> {
+        let version = (1..=MAX_VERSION).find(|&v| data.len() <= data_capacity(v))?;
+        let codewords = add_error_correction(&encode_data(data, version), version);
+
+        let size = version * 4 + 17;
+        let mut qr = Builder {
+            size,
+            modules: vec![false; size * size],
+            function: vec![false; size * size],
+        };
+        qr.draw_function_patterns(version);
+        qr.draw_codewords(&codewords);
+
+        // Pick the mask with the lowest penalty
+        let mut best: Option<(u32, Vec<bool>)> = None;
+        for mask in 0..8 {
+            let mut candidate = qr.clone();
+            candidate.apply_mask(mask);
+            candidate.draw_format_bits(mask);
+            let penalty = candidate.penalty();
+            if best.as_ref().is_none_or(|(p, _)| penalty < *p) {
+                best = Some((penalty, candidate.modules));
+            }
+        }
+
+        Some(Self {
+            size,
+            modules: best.map(|(_, modules)| modules)?,
+        })
+    }
+
+    /// Width and height in modules
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Whether the module at column `x`, row `y` is dark
+    pub fn is_dark(&self, x: usize, y: usize) -> bool {
+        self.modules[y * self.size + x]
+    }
+
+    /// Render as text using half blocks (two module rows per line),
+    /// surrounded by a `quiet` module wide light border. Meant to be drawn
+    /// with a dark foreground on a light background.
+    pub fn to_half_block_lines(&self, quiet: usize) -> Vec<String> {
+        let total = self.size + quiet * 2;
+        let dark = |x: usize, y: usize| {
+            x >= quiet
+                && y >= quiet
+                && x < quiet + self.size
+                && y < quiet + self.size
+                && self.is_dark(x - quiet, y - quiet)
+        };
+
+        (0..total)
+            .step_by(2)
+            .map(|y| {
+                (0..total)
+                    .map(|x| match (dark(x, y), dark(x, y + 1)) {
+                        (true, true) => '█',
+                        (true, false) => '▀',
+                        (false, true) => '▄',
+                        (false, false) => ' ',
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Modules available for data and error correction in a version
+fn raw_data_modules(version: usize) -> usize {
+    let mut result = (16 * version + 128) * version + 64;
+    if version >= 2 {
+        let num_align = version / 7 + 2;
+        result -= (25 * num_align - 10) * num_align - 55;
+        if version >= 7 {
+            result -= 36;
+        }
+    }
+    result
+}
+
+/// Data codewords in a version at level L
+fn data_codewords(version: usize) -> usize {
+    raw_data_modules(version) / 8 - ECC_PER_BLOCK[version] * NUM_BLOCKS[version]
+}
+
+/// Bytes that fit in a version (mode indicator and length take 12 or 20 bits)
+fn data_capacity(version: usize) -> usize {
+    let header_bits = 4 + char_count_bits(version);
+    (data_codewords(version) * 8 - header_bits) / 8
+}
+
+fn char_count_bits(version: usize) -> usize {
+    if version <= 9 {
+        8
+    } else {
+        16
+    }
+}
+
+/// Byte mode segment, terminator and padding
+fn encode_data(data: &[u8], version: usize) -> Vec<u8> {
+    let mut bits = BitBuffer::default();
+    bits.push(0b0100, 4);
+    bits.push(data.len() as u32, char_count_bits(version));
+    for &byte in data {
+        bits.push(byte as u32, 8);
+    }
+
+    let capacity = data_codewords(version) * 8;
+    bits.push(0, (capacity - bits.len).min(4));
+    bits.push(0, (8 - bits.len % 8) % 8);
+    for pad in [0xEC, 0x11].iter().cycle() {
+        if bits.len >= capacity {
+            break;
+        }
+        bits.push(*pad, 8);
+    }
+    bits.bytes
+}
+
+/// Split data into blocks, append Reed-Solomon codewords and interleave
+fn add_error_correction(data: &[u8], version: usize) -> Vec<u8> {
+    let num_blocks = NUM_BLOCKS[version];
+    let ecc_len = ECC_PER_BLOCK[version];
+    let raw_codewords = raw_data_modules(version) / 8;
+    let num_short_blocks = num_blocks - raw_codewords % num_blocks;
+    let short_block_len = raw_codewords / num_blocks;
+    let divisor = rs_divisor(ecc_len);
+
+    let mut blocks = Vec::with_capacity(num_blocks);
+    let mut offset = 0;
+    for i in 0..num_blocks {
+        let data_len = short_block_len - ecc_len + usize::from(i >= num_short_blocks);
+        let block_data = &data[offset..offset + data_len];
+        offset += data_len;
+        blocks.push((block_data.to_vec(), rs_remainder(block_data, &divisor)));
+    }
+
+    let mut result = Vec::with_capacity(raw_codewords);
+    let max_data_len = short_block_len - ecc_len + 1;
+    for i in 0..max_data_len {
+        for (block_data, _) in &blocks {
+            if let Some(&byte) = block_data.get(i) {
+                result.push(byte);
+            }
+        }
+    }
+    for i in 0..ecc_len {
+        for (_, ecc) in &blocks {
+            result.push(ecc[i]);
+        }
+    }
+    result
+}
+
+/// Multiply in GF(2^8) with the QR polynomial 0x11D
+fn gf_multiply(x: u8, y: u8) -> u8 {
+    let mut z: u16 = 0;
+    for i in (0..8).rev() {
+        z = (z << 1) ^ ((z >> 7) * 0x11D);
+        z ^= ((y as u16 >> i) & 1) * x as u16;
+    }
+    z as u8
+}
+
+/// Generator polynomial coefficients (highest degree first, leading 1 omitted)
+fn rs_divisor(degree: usize) -> Vec<u8> {
+    let mut result = vec![0u8; degree];
+    result[degree - 1] = 1;
+    let mut root = 1u8;
+    for _ in 0..degree {
+        for j in 0..degree {
+            result[j] = gf_multiply(result[j], root);
+            if j + 1 < degree {
+                result[j] ^= result[j + 1];
+            }
+        }
+        root = gf_multiply(root, 0x02);
+    }
+    result
+}
+
+fn rs_remainder(data: &[u8], divisor: &[u8]) -> Vec<u8> {
+    let mut result = vec![0u8; divisor.len()];
+    for &byte in data {
+        let factor = byte ^ result.remove(0);
+        result.push(0);
+        for (r, &d) in result.iter_mut().zip(divisor) {
+            *r ^= gf_multiply(d, factor);
+        }
+    }
+    result
+}
+
+#[derive(Default)]
+struct BitBuffer {
+    bytes: Vec<u8>,
+    len: usize,
+}
+
+impl BitBuffer {
+    fn push(&mut self, value: u32, count: usize) {
+        for i in (0..count).rev() {
+            if self.len.is_multiple_of(8) {
+                self.bytes.push(0);
+            }
+            if (value >> i) & 1 != 0 {
+                *self.bytes.last_mut().unwrap() |= 0x80 >> (self.len % 8);
+            }
+            self.len += 1;
+        }
+    }
+}
+
+/// Module grid under construction
+#[derive(Clone)]
+struct Builder {
+    size: usize,
+    modules: Vec<bool>,
+    /// Modules reserved for function patterns (never masked)
+    function: Vec<bool>,
+}
+
+impl Builder {
+    fn get(&self, x: usize, y: usize) -> bool {
+        self.modules[y * self.size + x]
+    }
+
+    fn set_function(&mut self, x: usize, y: usize, dark: bool) {
+        self.modules[y * self.size + x] = dark;
+        self.function[y * self.size + x] = true;
+    }
+
+    fn draw_function_patterns(&mut self, version: usize) {
+        let size = self.size;
+
+        // Timing patterns
+        for i in 0..size {
+            self.set_function(6, i, i % 2 == 0);
+            self.set_function(i, 6, i % 2 == 0);
+        }
+
+        // Finder patterns with separators
+        self.draw_finder(3, 3);
+        self.draw_finder(size - 4, 3);
+        self.draw_finder(3, size - 4);
+
+        // Alignment patterns, except where they would overlap finders
+        let positions = alignment_positions(version);
+        let n = positions.len();
+        for (i, &x) in positions.iter().enumerate() {
+            for (j, &y) in positions.iter().enumerate() {
+                let on_finder = (i == 0 && (j == 0 || j == n - 1)) || (i == n - 1 && j == 0);
+                if !on_finder {
+                    self.draw_alignment(x, y);
+                }
+            }
+        }
+
+        // Reserve format areas (real bits are drawn after masking)
+        self.draw_format_bits(0);
+
+        // Version information
+        if version >= 7 {
+            let mut rem = version as u32;
+            for _ in 0..12 {
+                rem = (rem << 1) ^ ((rem >> 11) * 0x1F25);
+            }
+            let bits = ((version as u32) << 12) | rem;
+            for i in 0..18 {
+                let dark = (bits >> i) & 1 != 0;
+                let a = size - 11 + i % 3;
+                let b = i / 3;
+                self.set_function(a, b, dark);
+                self.set_function(b, a, dark);
+            }
+        }
+    }
+
+    fn draw_finder(&mut self, cx: usize, cy: usize) {
+        for dy in -4i32..=4 {
+            for dx in -4i32..=4 {
+                let x = cx as i32 + dx;
+                let y = cy as i32 + dy;
+                if x < 0 || y < 0 || x >= self.size as i32 || y >= self.size as i32 {
+                    continue;
+                }
+                let dist = dx.abs().max(dy.abs());
+                self.set_function(x as usize, y as usize, dist != 2 && dist != 4);
+            }
+        }
+    }
+
+    fn draw_alignment(&mut self, cx: usize, cy: usize) {
+        for dy in -2i32..=2 {
+            for dx in -2i32..=2 {
+                let dark = dx.abs().max(dy.abs()) != 1;
+                self.set_function((cx as i32 + dx) as usize, (cy as i32 + dy) as usize, dark);
+            }
+        }
+    }
+
+    fn draw_format_bits(&mut self, mask: u32) {
+        let data = (FORMAT_BITS_L << 3) | mask;
+        let mut rem = data;
+        for _ in 0..10 {
+            rem = (rem << 1) ^ ((rem >> 9) * 0x537);
+        }
+        let bits = ((data << 10) | rem) ^ 0x5412;
+        let bit = |i: usize| (bits >> i) & 1 != 0;
+        let size = self.size;
+
+        // First copy, around the top-left finder
+        for i in 0..=5 {
+            self.set_function(8, i, bit(i));
+        }
+        self.set_function(8, 7, bit(6));
+        self.set_function(8, 8, bit(7));
+        self.set_function(7, 8, bit(8));
+        for i in 9..15 {
+            self.set_function(14 - i, 8, bit(i));
+        }
+
+        // Second copy, split between the other two finders
+        for i in 0..8 {
+            self.set_function(size - 1 - i, 8, bit(i));
+        }
+        for i in 8..15 {
+            self.set_function(8, size - 15 + i, bit(i));
+        }
+        // Always-dark module
+        self.set_function(8, size - 8, true);
+    }
+
+    /// Place codewords in the zigzag order, skipping function modules
+    fn draw_codewords(&mut self, data: &[u8]) {
+        let size = self.size;
+        let total_bits = data.len() * 8;
+        let mut i = 0;
+        let mut right = size as i32 - 1;
+        while right >= 1 {
+            if right == 6 {
+                right = 5;
+            }
+            for vert in 0..size {
+                for j in 0..2 {
+                    let x = (right - j) as usize;
+                    let upward = (right + 1) & 2 == 0;
+                    let y = if upward { size - 1 - vert } else { vert };
+                    if !self.function[y * size + x] && i < total_bits {
+                        self.modules[y * size + x] = (data[i >> 3] >> (7 - (i & 7))) & 1 != 0;
+                        i += 1;
+                    }
+                }
+            }
+            right -= 2;
+        }
+    }
+
+    fn apply_mask(&mut self, mask: u32) {
+        for y in 0..self.size {
+            for x in 0..self.size {
+                let invert = match mask {
+                    0 => (x + y) % 2 == 0,
+                    1 => y % 2 == 0,
+                    2 => x % 3 == 0,
+                    3 => (x + y) % 3 == 0,
+                    4 => (x / 3 + y / 2) % 2 == 0,
+                    5 => x * y % 2 + x * y % 3 == 0,
+                    6 => (x * y % 2 + x * y % 3) % 2 == 0,
+                    _ => ((x + y) % 2 + x * y % 3) % 2 == 0,
+                };
+                let idx = y * self.size + x;
+                if invert && !self.function[idx] {
+                    self.modules[idx] = !self.modules[idx];
+                }
+            }
+        }
+    }
+
+    /// Penalty score from the four standard mask evaluation rules
+    fn penalty(&self) -> u32 {
+        let size = self.size;
+        let mut result = 0u32;
+
+        // Rule 1 (runs) and rule 3 (finder-like patterns), rows and columns
+        for transposed in [false, true] {
+            for a in 0..size {
+                let line: Vec<bool> = (0..size)
+                    .map(|b| if transposed { self.get(a, b) } else { self.get(b, a) })
+                    .collect();
+
+                let mut run = 1;
+                for b in 1..=size {
+                    if b < size && line[b] == line[b - 1] {
+                        run += 1;
+                    } else {
+                        if run >= 5 {
+                            result += 3 + (run - 5);
+                        }
+                        run = 1;
+                    }
+                }
+
+                const PATTERN: [bool; 7] = [true, false, true, true, true, false, true];
+                for start in 0..size.saturating_sub(6) {
+                    if line[start..start + 7] != PATTERN {
+                        continue;
+                    }
+                    let light = |from: i32, to: i32| {
+                        (from..to).all(|k| k < 0 || k >= size as i32 || !line[k as usize])
+                    };
+                    let s = start as i32;
+                    if light(s - 4, s) || light(s + 7, s + 11) {
+                        result += 40;
+                    }
+                }
+            }
+        }
+
+        // Rule 2: 2x2 blocks of one color
+        for y in 0..size - 1 {
+            for x in 0..size - 1 {
+                let c = self.get(x, y);
+                if c == self.get(x + 1, y) && c == self.get(x, y + 1) && c == self.get(x + 1, y + 1) {
+                    result += 3;
+                }
+            }
+        }
+
+        // Rule 4: balance of dark and light modules
+        let total = (size * size) as i64;
+        let dark = self.modules.iter().filter(|&&d| d).count() as i64;
+        let k = ((dark * 20 - total * 10).abs() + total - 1) / total - 1;
+        result += (k * 10) as u32;
+
+        result
+    }
+}
+
+/// Centers of alignment patterns along each axis
+fn alignment_positions(version: usize) -> Vec<usize> {
+    if version == 1 {
+        return Vec::new();
+    }
+    let num_align = version / 7 + 2;
+    let step = (version * 4 + num_align * 2 + 1) / (num_align * 2 - 2) * 2;
+    let size = version * 4 + 17;
+    let mut result = vec![6];
+    for i in (0..num_align - 1).rev() {
+        result.push(size - 7 - i * step);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reed_solomon_reference_vector() {
+        // "HELLO WORLD" at 1-M (ISO/IEC 18004 worked example)
+        let data = [32, 91, 11, 120, 209, 114, 220, 77, 67, 64, 236, 17, 236, 17, 236, 17];
+        let ecc = rs_remainder(&data, &rs_divisor(10));
+        assert_eq!(ecc, vec![196, 35, 39, 119, 235, 215, 231, 226, 93, 23]);
+    }
+
+    #[test]
+    fn test_capacity_and_version_selection() {
+        assert_eq!(data_capacity(1), 17);
+        assert_eq!(data_capacity(10), 271);
+        assert_eq!(QrCode::encode(b"https://example.com").unwrap().size(), 25);
+        assert!(QrCode::encode(&[b'a'; 272]).is_none());
+    }
+
+    #[test]
+    fn test_alignment_positions() {
+        assert_eq!(alignment_positions(2), vec![6, 18]);
+        assert_eq!(alignment_positions(7), vec![6, 22, 38]);
+        assert_eq!(alignment_positions(10), vec![6, 28, 50]);
+    }
+
+    #[test]
+    fn test_format_and_finder_modules() {
+        let qr = QrCode::encode(b"hi").unwrap();
+        // Finder pattern corners and the always-dark module
+        assert!(qr.is_dark(0, 0) && qr.is_dark(6, 6) && !qr.is_dark(7, 7));
+        assert!(qr.is_dark(8, qr.size() - 8));
+
+        // Both copies of the format information agree
+        let first: Vec<bool> = (0..=5)
+            .map(|i| qr.is_dark(8, i))
+            .chain([qr.is_dark(8, 7), qr.is_dark(8, 8), qr.is_dark(7, 8)])
+            .chain((9..15).map(|i| qr.is_dark(14 - i, 8)))
+            .collect();
+        let second: Vec<bool> = (0..8)
+            .map(|i| qr.is_dark(qr.size() - 1 - i, 8))
+            .chain((8..15).map(|i| qr.is_dark(8, qr.size() - 15 + i)))
+            .collect();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_half_block_rendering() {
+        let qr = QrCode::encode(b"hi").unwrap();
+        let lines = qr.to_half_block_lines(2);
+        assert_eq!(lines.len(), (qr.size() + 4).div_ceil(2));
+        assert!(lines.iter().all(|l| l.chars().count() == qr.size() + 4));
+    }
+}
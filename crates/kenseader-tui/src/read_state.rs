@@ -0,0 +1,123 @@
+//! Write-behind buffer for read-state changes
+//!
+//! Marking articles read during fast triage only updates the list; the
+//! changes are sent to the daemon in one batch every few seconds, before the
+//! lists are reloaded from it and when the TUI exits.
+
+use std::time::{Duration, Instant};
+
+use kenseader_core::feed::ReadStateChange;
+use uuid::Uuid;
+
+/// Longest time a change waits before it is stored
+pub const FLUSH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Number of pending changes that are stored without waiting
+const MAX_PENDING: usize = 50;
+
+/// Read-state changes not yet stored by the daemon
+#[derive(Debug, Clone)]
+pub struct ReadStateBuffer {
+    /// Latest change per article, in the order they were first made
+    pending: Vec<ReadStateChange>,
+    /// When the oldest pending change was made
+    since: Option<Instant>,
+}
+
+impl Default for ReadStateBuffer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReadStateBuffer {
+    pub fn new() -> Self {
+        Self {
+            pending: Vec::new(),
+            since: None,
+        }
+    }
+
+    /// Queue an article's new read state, replacing an earlier pending one
+    pub fn record(&mut self, id: Uuid, read: bool) {
+        match self.pending.iter_mut().find(|change| change.id == id) {
+            Some(change) => change.read = read,
+            None => self.pending.push(ReadStateChange { id, read }),
+        }
+        self.since.get_or_insert_with(Instant::now);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Whether the pending changes should be stored now
+    pub fn is_due(&self) -> bool {
+        self.pending.len() >= MAX_PENDING || self.since.is_some_and(|since| since.elapsed() >= FLUSH_INTERVAL)
+    }
+
+    /// Take the pending changes to store them
+    pub fn take(&mut self) -> Vec<ReadStateChange> {
+        self.since = None;
+        std::mem::take(&mut self.pending)
+    }
+
+    /// Queue changes again after storing them failed, unless the article
+    /// changed again since
+    pub fn restore(&mut self, changes: Vec<ReadStateChange>) {
+        let newer = std::mem::take(&mut self.pending);
+        self.pending = changes
+            .into_iter()
+            .filter(|change| !newer.iter().any(|n| n.id == change.id))
+            .collect();
+        self.pending.extend(newer);
+        if !self.pending.is_empty() {
+            self.since.get_or_insert_with(Instant::now);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_keeps_latest_state() {
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut buffer = ReadStateBuffer::new();
+        assert!(!buffer.is_due());
+        buffer.record(a, true);
+        buffer.record(b, true);
+        buffer.record(a, false);
+        assert_eq!(buffer.len(), 2);
+        assert!(!buffer.is_due());
+
+        let changes = buffer.take();
+        assert_eq!(
+            changes,
+            [ReadStateChange { id: a, read: false }, ReadStateChange { id: b, read: true }]
+        );
+        assert!(buffer.is_empty());
+
+        // A failed batch is queued again, behind nothing newer for `a`
+        buffer.record(a, true);
+        buffer.restore(changes);
+        assert_eq!(
+            buffer.take(),
+            [ReadStateChange { id: b, read: true }, ReadStateChange { id: a, read: true }]
+        );
+    }
+
+    #[test]
+    fn test_many_changes_are_due_at_once() {
+        let mut buffer = ReadStateBuffer::new();
+        for _ in 0..MAX_PENDING {
+            buffer.record(Uuid::new_v4(), true);
+        }
+        assert!(buffer.is_due());
+    }
+}
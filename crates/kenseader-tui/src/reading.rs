@@ -0,0 +1,143 @@
+//! Time spent reading the article open in the detail view
+//!
+//! Reading time only runs while there is input: once no key was pressed for
+//! the idle timeout, the clock stops until the next key, so an article left
+//! open while away isn't taken for one read at length.
+
+use std::time::{Duration, Instant};
+
+use uuid::Uuid;
+
+/// Shortest reading worth recording; less is skimming past the article
+pub const MIN_READING: Duration = Duration::from_secs(5);
+
+/// Reading of an article that ended
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FinishedReading {
+    pub id: Uuid,
+    pub duration: Duration,
+    /// Deepest scroll reached, in percent
+    pub scroll_depth: u8,
+}
+
+#[derive(Debug, Clone)]
+struct Reading {
+    id: Uuid,
+    active: Duration,
+    /// Time up to which `active` is counted
+    counted_to: Instant,
+    scroll_depth: u8,
+}
+
+/// Tracks the reading of the open article
+#[derive(Debug, Clone)]
+pub struct ReadingTimer {
+    /// No input for this long stops the clock; None never stops it
+    idle_after: Option<Duration>,
+    last_input: Instant,
+    current: Option<Reading>,
+}
+
+impl ReadingTimer {
+    pub fn new(idle_timeout_secs: u64) -> Self {
+        Self {
+            idle_after: (idle_timeout_secs > 0).then(|| Duration::from_secs(idle_timeout_secs)),
+            last_input: Instant::now(),
+            current: None,
+        }
+    }
+
+    /// Note a key press (or other sign of the user being there)
+    pub fn input(&mut self, now: Instant) {
+        self.count(now);
+        self.last_input = now;
+    }
+
+    /// Follow the article being read, if any, and how far it is scrolled.
+    /// Returns the reading of the previous article when it changes.
+    pub fn track(&mut self, article: Option<Uuid>, scroll_depth: u8, now: Instant) -> Option<FinishedReading> {
+        self.count(now);
+        if self.current.as_ref().map(|r| r.id) != article {
+            let finished = self.finish(now);
+            self.current = article.map(|id| Reading {
+                id,
+                active: Duration::ZERO,
+                counted_to: now,
+                scroll_depth,
+            });
+            return finished;
+        }
+        if let Some(reading) = self.current.as_mut() {
+            reading.scroll_depth = reading.scroll_depth.max(scroll_depth);
+        }
+        None
+    }
+
+    /// End the current reading, returning it when it is long enough to record
+    pub fn finish(&mut self, now: Instant) -> Option<FinishedReading> {
+        self.count(now);
+        self.current
+            .take()
+            .filter(|reading| reading.active >= MIN_READING)
+            .map(|reading| FinishedReading {
+                id: reading.id,
+                duration: reading.active,
+                scroll_depth: reading.scroll_depth,
+            })
+    }
+
+    /// Add the time since it was last counted, up to when the user went idle
+    fn count(&mut self, now: Instant) {
+        let Some(reading) = self.current.as_mut() else {
+            return;
+        };
+        let until = match self.idle_after {
+            Some(idle_after) => now.min(self.last_input + idle_after),
+            None => now,
+        };
+        reading.active += until.saturating_duration_since(reading.counted_to);
+        reading.counted_to = reading.counted_to.max(now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_idle_time_is_not_counted() {
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let (article, next) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut timer = ReadingTimer::new(60);
+        timer.input(start);
+        assert!(timer.track(Some(article), 0, start).is_none());
+
+        // Read for 30s, then away for an hour
+        timer.input(at(30));
+        timer.track(Some(article), 40, at(30));
+        timer.track(Some(article), 40, at(2000));
+
+        // Back: only the time up to the idle timeout and after returning counts
+        timer.input(at(3630));
+        timer.track(Some(article), 100, at(3640));
+        let finished = timer.track(Some(next), 0, at(3650)).unwrap();
+        assert_eq!(finished.id, article);
+        assert_eq!(finished.duration, Duration::from_secs(30 + 60 + 20));
+        assert_eq!(finished.scroll_depth, 100);
+
+        // A glance at an article isn't recorded
+        assert!(timer.track(None, 0, at(3652)).is_none());
+    }
+
+    #[test]
+    fn test_zero_timeout_counts_all_time() {
+        let start = Instant::now();
+        let article = Uuid::new_v4();
+        let mut timer = ReadingTimer::new(0);
+        timer.track(Some(article), 10, start);
+        let finished = timer.finish(start + Duration::from_secs(3600)).unwrap();
+        assert_eq!(finished.duration, Duration::from_secs(3600));
+        assert_eq!(finished.scroll_depth, 10);
+    }
+}
@@ -1,23 +1,30 @@
 use std::collections::HashMap;
+use std::ops::Range;
 use std::path::PathBuf;
-use std::sync::{Arc, OnceLock};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::Instant;
 
 use regex::Regex;
 
 use image::{DynamicImage, RgbaImage};
+
+use crate::math::{mathml_to_text, tex_to_unicode};
 use ratatui_image::picker::{Picker, ProtocolType};
 use ratatui_image::protocol::StatefulProtocol;
 
-const MAX_IMAGE_BYTES: usize = 5 * 1024 * 1024;
+/// Protocols kept per image, one for each area size it was shown at
+const MAX_PROTOCOLS_PER_IMAGE: usize = 4;
 
-/// Get the global image picker instance with automatic protocol detection
-pub fn get_image_picker() -> &'static Picker {
-    static PICKER: OnceLock<Picker> = OnceLock::new();
+/// The global image picker, shared by all images
+///
+/// The terminal is queried once, on first use, rather than mid-render for
+/// every image. Creating a protocol needs the picker mutably, hence the lock.
+fn image_picker() -> &'static Mutex<Picker> {
+    static PICKER: OnceLock<Mutex<Picker>> = OnceLock::new();
     PICKER.get_or_init(|| {
         // Try to query terminal capabilities for best protocol
         // Falls back to halfblocks if query fails
-        Picker::from_query_stdio().unwrap_or_else(|_| Picker::from_fontsize((8, 16)))
+        Mutex::new(Picker::from_query_stdio().unwrap_or_else(|_| Picker::from_fontsize((8, 16))))
     })
 }
 
@@ -25,7 +32,7 @@ pub fn get_image_picker() -> &'static Picker {
 pub fn supports_graphics_protocol() -> bool {
     static SUPPORTS_GRAPHICS: OnceLock<bool> = OnceLock::new();
     *SUPPORTS_GRAPHICS.get_or_init(|| {
-        let picker = get_image_picker();
+        let picker = image_picker().lock().unwrap();
         !matches!(picker.protocol_type(), ProtocolType::Halfblocks)
     })
 }
@@ -34,47 +41,50 @@ pub fn supports_graphics_protocol() -> bool {
 pub struct CachedImageData {
     /// The raw image data (wrapped in Arc to avoid expensive deep clones)
     pub image: Arc<DynamicImage>,
-    /// The stateful protocol for StatefulImage rendering
-    pub protocol: Option<StatefulProtocol>,
+    /// Stateful protocols for StatefulImage rendering, keyed by the area
+    /// (columns, rows) they were made for, oldest first
+    protocols: Vec<((u16, u16), StatefulProtocol)>,
     /// Disk cache path for external viewer fallback
     pub cache_path: Option<PathBuf>,
 }
 
 impl CachedImageData {
     pub fn new(image: DynamicImage, cache_path: Option<PathBuf>) -> Self {
-        Self {
-            image: Arc::new(image),
-            protocol: None,
-            cache_path,
-        }
+        Self::new_arc(Arc::new(image), cache_path)
     }
 
     /// Create from an already-Arc'd image (avoids double-wrapping)
     pub fn new_arc(image: Arc<DynamicImage>, cache_path: Option<PathBuf>) -> Self {
         Self {
             image,
-            protocol: None,
+            protocols: Vec::new(),
             cache_path,
         }
     }
 
-    /// Initialize the StatefulProtocol using a new picker
-    /// This should be called after the image is loaded
-    pub fn init_protocol(&mut self) {
-        if self.protocol.is_none() {
-            // Create a new picker for this protocol (new_resize_protocol requires &mut self)
-            let mut picker = Picker::from_query_stdio()
-                .unwrap_or_else(|_| Picker::from_fontsize((8, 16)));
-            // Note: new_resize_protocol needs owned DynamicImage, but this is only called
-            // for protocol initialization which is relatively rare
-            self.protocol = Some(picker.new_resize_protocol((*self.image).clone()));
-        }
+    /// Get the protocol for rendering in an area of `width` x `height`
+    /// cells, creating it with the shared picker the first time
+    pub fn protocol_for(&mut self, width: u16, height: u16) -> &mut StatefulProtocol {
+        let area = (width, height);
+        let idx = match self.protocols.iter().position(|(size, _)| *size == area) {
+            Some(idx) => idx,
+            None => {
+                if self.protocols.len() >= MAX_PROTOCOLS_PER_IMAGE {
+                    self.protocols.remove(0);
+                }
+                // new_resize_protocol needs an owned DynamicImage; this only
+                // happens the first time the image is shown at this size
+                let protocol = image_picker().lock().unwrap().new_resize_protocol((*self.image).clone());
+                self.protocols.push((area, protocol));
+                self.protocols.len() - 1
+            }
+        };
+        &mut self.protocols[idx].1
     }
 
-    /// Get or initialize the protocol for rendering
-    pub fn get_protocol(&mut self) -> &mut StatefulProtocol {
-        self.init_protocol();
-        self.protocol.as_mut().unwrap()
+    /// Drop the protocols, which no longer fit after the terminal resized
+    pub fn invalidate_protocols(&mut self) {
+        self.protocols.clear();
     }
 }
 
@@ -177,24 +187,68 @@ impl ImageDiskCache {
 /// Represents a content element in the article
 #[derive(Clone, Debug)]
 pub enum ContentElement {
-    /// Plain text paragraph
-    Text(String),
+    /// Plain text paragraph, with its inline emphasis
+    Text(String, Vec<InlineStyle>),
     /// Heading with level (1-6) and text
     Heading(u8, String),
-    /// Image with URL
-    Image { url: String, alt: Option<String> },
+    /// Image with URL, alt text and the caption of its `<figure>`
+    Image {
+        url: String,
+        alt: Option<String>,
+        caption: Option<String>,
+    },
     /// Horizontal rule / separator
     Separator,
     /// Block quote
     Quote(String),
     /// Code block
     Code(String),
+    /// Displayed formula, as a plain-text approximation
+    Math(String),
     /// List item
     ListItem(String),
+    /// Term of a definition list
+    DefinitionTerm(String),
+    /// Definition of the term above it
+    Definition(String),
+    /// Footnote, listed at the end of the article under the number its
+    /// references (`[1]`) show
+    Footnote { label: String, text: String },
     /// Empty line
     EmptyLine,
 }
 
+impl ContentElement {
+    /// Text shown under an image: its caption, or the alt text without one
+    pub fn image_caption(&self) -> Option<&str> {
+        match self {
+            ContentElement::Image { alt, caption, .. } => caption.as_deref().or(alt.as_deref()),
+            _ => None,
+        }
+    }
+}
+
+/// Kind of inline emphasis in a paragraph
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Inline {
+    /// `<strong>`, `<b>`
+    Bold,
+    /// `<em>`, `<i>`
+    Italic,
+    /// `<code>` inside a paragraph
+    Code,
+    /// Text of an `<a>` link
+    Link,
+}
+
+/// Inline emphasis over a range of a paragraph, counted in non-whitespace
+/// characters (like spoken sentences) so it survives trimming and wrapping
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InlineStyle {
+    pub kind: Inline,
+    pub range: Range<usize>,
+}
+
 /// A span of text, optionally a hyperlink
 #[derive(Clone, Debug)]
 pub struct TextSpan {
@@ -213,6 +267,16 @@ pub enum FocusableItem {
         text: String,
         element_index: usize,
     },
+    /// A footnote reference like `[1]`, with the range of its characters
+    /// in the element text (counting non-whitespace characters, like
+    /// spoken sentences)
+    FootnoteRef {
+        label: String,
+        element_index: usize,
+        range: Range<usize>,
+    },
+    /// A footnote at the end of the article
+    Footnote { label: String, element_index: usize },
 }
 
 /// Parsed rich content ready for rendering
@@ -256,11 +320,26 @@ impl RichContent {
         let mut elements = Vec::new();
         let mut image_urls = Vec::new();
 
-        // Remove script and style tags first
-        let cleaned = remove_tags(html, &["script", "style", "noscript"]);
+        // Remove script and style tags first, keeping MathJax's TeX scripts
+        let cleaned = math_scripts_to_mathml(html);
+        let cleaned = remove_tags(&cleaned, &["script", "style", "noscript"]);
+
+        // Footnotes are moved to the end, their references become `[1]`
+        let (cleaned, footnotes) = split_footnotes(&cleaned);
 
         // Parse the HTML content
         parse_html_content(&cleaned, &mut elements, &mut image_urls);
+        if !footnotes.is_empty() {
+            elements.push(ContentElement::Separator);
+            elements.extend(
+                footnotes
+                    .into_iter()
+                    .map(|(label, text)| ContentElement::Footnote { label, text }),
+            );
+        }
+
+        // Raw TeX left for MathJax to typeset in the browser
+        let elements = expand_tex(elements);
 
         // Clean up consecutive empty lines
         let elements = collapse_empty_lines(elements);
@@ -283,10 +362,11 @@ impl RichContent {
                 if line.trim().is_empty() {
                     ContentElement::EmptyLine
                 } else {
-                    ContentElement::Text(line.to_string())
+                    ContentElement::Text(line.to_string(), Vec::new())
                 }
             })
             .collect();
+        let elements = expand_tex(elements);
 
         let image_urls = Vec::new();
         // Build focusable items (only bare URLs in plain text, no images)
@@ -326,6 +406,7 @@ fn remove_tags(html: &str, tags: &[&str]) -> String {
 fn parse_html_content(html: &str, elements: &mut Vec<ContentElement>, image_urls: &mut Vec<String>) {
     let mut remaining = html;
     let mut current_text = String::new();
+    let mut styles = InlineStyles::default();
 
     while !remaining.is_empty() {
         if let Some(tag_start) = remaining.find('<') {
@@ -333,6 +414,9 @@ fn parse_html_content(html: &str, elements: &mut Vec<ContentElement>, image_urls
             let text_before = &remaining[..tag_start];
             if !text_before.trim().is_empty() {
                 current_text.push_str(&decode_html_entities(text_before));
+            } else if !text_before.is_empty() && !current_text.is_empty() && !current_text.ends_with(char::is_whitespace) {
+                // Keep the space between inline elements (`<b>a</b> <i>b</i>`)
+                current_text.push(' ');
             }
 
             remaining = &remaining[tag_start..];
@@ -354,32 +438,25 @@ fn parse_html_content(html: &str, elements: &mut Vec<ContentElement>, image_urls
                 match tag_name.as_str() {
                     "img" => {
                         // Flush current text
-                        if !current_text.trim().is_empty() {
-                            elements.push(ContentElement::Text(current_text.trim().to_string()));
-                            current_text.clear();
-                        }
+                        flush_text(&mut current_text, &mut styles, elements);
 
                         // Extract src and alt
                         if let Some(src) = extract_attr(tag_content, "src") {
                             if !src.is_empty() && !image_urls.contains(&src) {
                                 image_urls.push(src.clone());
                             }
-                            let alt = extract_attr(tag_content, "alt");
-                            elements.push(ContentElement::Image { url: src, alt });
+                            let alt = extract_attr(tag_content, "alt")
+                                .map(|alt| decode_html_entities(&alt).trim().to_string())
+                                .filter(|alt| !alt.is_empty());
+                            elements.push(ContentElement::Image { url: src, alt, caption: None });
                         }
                     }
                     "br" => {
-                        if !current_text.trim().is_empty() {
-                            elements.push(ContentElement::Text(current_text.trim().to_string()));
-                            current_text.clear();
-                        }
+                        flush_text(&mut current_text, &mut styles, elements);
                         elements.push(ContentElement::EmptyLine);
                     }
                     "p" | "div" => {
-                        if !current_text.trim().is_empty() {
-                            elements.push(ContentElement::Text(current_text.trim().to_string()));
-                            current_text.clear();
-                        }
+                        flush_text(&mut current_text, &mut styles, elements);
                         // Check for closing tag with content
                         if !full_tag.ends_with("/>") {
                             let close_tag = format!("</{}>", tag_name);
@@ -399,10 +476,7 @@ fn parse_html_content(html: &str, elements: &mut Vec<ContentElement>, image_urls
                         }
                     }
                     "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
-                        if !current_text.trim().is_empty() {
-                            elements.push(ContentElement::Text(current_text.trim().to_string()));
-                            current_text.clear();
-                        }
+                        flush_text(&mut current_text, &mut styles, elements);
                         let level = tag_name.chars().nth(1).and_then(|c| c.to_digit(10)).unwrap_or(1) as u8;
                         let close_tag = format!("</{}>", tag_name);
                         if let Some(close_pos) = remaining.to_lowercase().find(&close_tag) {
@@ -420,17 +494,11 @@ fn parse_html_content(html: &str, elements: &mut Vec<ContentElement>, image_urls
                         }
                     }
                     "hr" => {
-                        if !current_text.trim().is_empty() {
-                            elements.push(ContentElement::Text(current_text.trim().to_string()));
-                            current_text.clear();
-                        }
+                        flush_text(&mut current_text, &mut styles, elements);
                         elements.push(ContentElement::Separator);
                     }
                     "blockquote" => {
-                        if !current_text.trim().is_empty() {
-                            elements.push(ContentElement::Text(current_text.trim().to_string()));
-                            current_text.clear();
-                        }
+                        flush_text(&mut current_text, &mut styles, elements);
                         let close_tag = "</blockquote>";
                         if let Some(close_pos) = remaining.to_lowercase().find(close_tag) {
                             let inner = &remaining[..close_pos];
@@ -442,11 +510,19 @@ fn parse_html_content(html: &str, elements: &mut Vec<ContentElement>, image_urls
                             }
                         }
                     }
+                    "strong" | "b" | "em" | "i" | "code" if !tag_content.starts_with('/') && inline_code(tag_name.as_str(), remaining) => {
+                        let kind = match tag_name.as_str() {
+                            "strong" | "b" => Inline::Bold,
+                            "em" | "i" => Inline::Italic,
+                            _ => Inline::Code,
+                        };
+                        styles.open(kind, &current_text);
+                    }
+                    "strong" | "b" => styles.close(Inline::Bold, &current_text),
+                    "em" | "i" => styles.close(Inline::Italic, &current_text),
+                    "code" if tag_content.starts_with('/') => styles.close(Inline::Code, &current_text),
                     "pre" | "code" => {
-                        if !current_text.trim().is_empty() {
-                            elements.push(ContentElement::Text(current_text.trim().to_string()));
-                            current_text.clear();
-                        }
+                        flush_text(&mut current_text, &mut styles, elements);
                         let close_tag = format!("</{}>", tag_name);
                         if let Some(close_pos) = remaining.to_lowercase().find(&close_tag) {
                             let inner = &remaining[..close_pos];
@@ -459,10 +535,7 @@ fn parse_html_content(html: &str, elements: &mut Vec<ContentElement>, image_urls
                         }
                     }
                     "li" => {
-                        if !current_text.trim().is_empty() {
-                            elements.push(ContentElement::Text(current_text.trim().to_string()));
-                            current_text.clear();
-                        }
+                        flush_text(&mut current_text, &mut styles, elements);
                         let close_tag = "</li>";
                         if let Some(close_pos) = remaining.to_lowercase().find(close_tag) {
                             let inner = &remaining[..close_pos];
@@ -474,6 +547,46 @@ fn parse_html_content(html: &str, elements: &mut Vec<ContentElement>, image_urls
                             }
                         }
                     }
+                    "dt" | "dd" => {
+                        flush_text(&mut current_text, &mut styles, elements);
+                        let close_tag = format!("</{}>", tag_name);
+                        if let Some(close_pos) = remaining.to_lowercase().find(&close_tag) {
+                            let inner = &remaining[..close_pos];
+                            let skip = close_pos + close_tag.len();
+                            remaining = remaining.get(skip..).unwrap_or("");
+                            let text = strip_html_tags(inner);
+                            let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+                            if !text.is_empty() {
+                                elements.push(if tag_name == "dt" {
+                                    ContentElement::DefinitionTerm(text)
+                                } else {
+                                    ContentElement::Definition(text)
+                                });
+                            }
+                        }
+                    }
+                    "math" => {
+                        // MathML formula: displayed ones become their own element
+                        let close_tag = "</math>";
+                        if let Some(close_pos) = remaining.to_lowercase().find(close_tag) {
+                            let text = mathml_to_text(&remaining[..close_pos]);
+                            let skip = close_pos + close_tag.len();
+                            remaining = remaining.get(skip..).unwrap_or("");
+                            let display = extract_attr(tag_content, "display").is_some_and(|d| d == "block");
+                            if display {
+                                flush_text(&mut current_text, &mut styles, elements);
+                                if !text.is_empty() {
+                                    elements.push(ContentElement::Math(text));
+                                }
+                            } else {
+                                current_text.push_str(&text);
+                            }
+                        }
+                    }
+                    "span" if extract_attr(tag_content, "class").is_some_and(|c| c.contains("katex-html")) => {
+                        // KaTeX's typeset copy of a formula, already read from its MathML
+                        remaining = skip_element(remaining, "span");
+                    }
                     "a" => {
                         // Handle anchor/link elements - extract href and link text
                         if let Some(href) = extract_attr(tag_content, "href") {
@@ -489,7 +602,10 @@ fn parse_html_content(html: &str, elements: &mut Vec<ContentElement>, image_urls
                                     if !link_text.is_empty() {
                                         // If link text is different from URL, show both
                                         if link_text != href && !link_text.contains(&href) {
-                                            current_text.push_str(&format!("{} ({})", link_text, href));
+                                            styles.open(Inline::Link, &current_text);
+                                            current_text.push_str(&link_text);
+                                            styles.close(Inline::Link, &current_text);
+                                            current_text.push_str(&format!(" ({})", href));
                                         } else {
                                             // Link text is the URL or contains it
                                             current_text.push_str(&link_text);
@@ -507,17 +623,27 @@ fn parse_html_content(html: &str, elements: &mut Vec<ContentElement>, image_urls
                     }
                     "figure" => {
                         // Handle figure element (common in modern articles)
-                        if !current_text.trim().is_empty() {
-                            elements.push(ContentElement::Text(current_text.trim().to_string()));
-                            current_text.clear();
-                        }
+                        flush_text(&mut current_text, &mut styles, elements);
                         let close_tag = "</figure>";
                         if let Some(close_pos) = remaining.to_lowercase().find(close_tag) {
                             let inner = &remaining[..close_pos];
                             let skip = close_pos + close_tag.len();
                             remaining = remaining.get(skip..).unwrap_or("");
-                            // Recursively parse to find images inside
-                            parse_html_content(inner, elements, image_urls);
+                            // Recursively parse to find images inside, then
+                            // attach the caption to the figure's last image
+                            let (inner, caption) = split_figcaption(inner);
+                            let start = elements.len();
+                            parse_html_content(&inner, elements, image_urls);
+                            if let Some(caption) = caption {
+                                let image = elements[start..].iter_mut().rev().find_map(|e| match e {
+                                    ContentElement::Image { caption, .. } => Some(caption),
+                                    _ => None,
+                                });
+                                match image {
+                                    Some(slot) => *slot = Some(caption),
+                                    None => elements.push(ContentElement::Text(caption, Vec::new())),
+                                }
+                            }
                         }
                     }
                     _ => {
@@ -539,9 +665,275 @@ fn parse_html_content(html: &str, elements: &mut Vec<ContentElement>, image_urls
     }
 
     // Flush any remaining text
-    if !current_text.trim().is_empty() {
-        elements.push(ContentElement::Text(current_text.trim().to_string()));
+    flush_text(&mut current_text, &mut styles, elements);
+}
+
+/// Number of non-whitespace characters, the unit of inline style ranges
+fn visible_len(text: &str) -> usize {
+    text.chars().filter(|c| !c.is_whitespace()).count()
+}
+
+/// Inline styles of the paragraph being parsed
+#[derive(Default)]
+struct InlineStyles {
+    /// Styles that ended
+    closed: Vec<InlineStyle>,
+    /// Styles begun but not ended yet, with where they start
+    open: Vec<(Inline, usize)>,
+}
+
+impl InlineStyles {
+    /// Start a style at the end of `text`
+    fn open(&mut self, kind: Inline, text: &str) {
+        self.open.push((kind, visible_len(text)));
+    }
+
+    /// End the last style of this kind at the end of `text`
+    fn close(&mut self, kind: Inline, text: &str) {
+        if let Some(pos) = self.open.iter().rposition(|(open, _)| *open == kind) {
+            let (kind, start) = self.open.remove(pos);
+            let end = visible_len(text);
+            if start < end {
+                self.closed.push(InlineStyle { kind, range: start..end });
+            }
+        }
+    }
+
+    /// Styles of the paragraph ending with `text`. Styles still open end
+    /// with it and go on in the next paragraph.
+    fn take(&mut self, text: &str) -> Vec<InlineStyle> {
+        let end = visible_len(text);
+        let mut styles = std::mem::take(&mut self.closed);
+        for (kind, start) in &mut self.open {
+            if *start < end {
+                styles.push(InlineStyle { kind: *kind, range: *start..end });
+            }
+            *start = 0;
+        }
+        styles.sort_by_key(|style| style.range.start);
+        styles
+    }
+}
+
+/// End the paragraph being parsed
+fn flush_text(text: &mut String, styles: &mut InlineStyles, elements: &mut Vec<ContentElement>) {
+    let inline = styles.take(text);
+    if !text.trim().is_empty() {
+        elements.push(ContentElement::Text(text.trim().to_string(), inline));
+    }
+    text.clear();
+}
+
+/// Whether a `<code>` tag, whose content starts `remaining`, is inline
+/// code rather than a block (blocks span several lines). Other tags are
+/// always inline.
+fn inline_code(tag_name: &str, remaining: &str) -> bool {
+    if tag_name != "code" {
+        return true;
+    }
+    let end = remaining.to_ascii_lowercase().find("</code>").unwrap_or(remaining.len());
+    !remaining[..end].contains('\n')
+}
+
+/// Remove the `<figcaption>` from the content of a `<figure>`, returning
+/// the rest and the caption text
+fn split_figcaption(inner: &str) -> (String, Option<String>) {
+    let lower = inner.to_ascii_lowercase();
+    let Some(start) = lower.find("<figcaption") else {
+        return (inner.to_string(), None);
+    };
+    let end = lower[start..]
+        .find("</figcaption>")
+        .map_or(inner.len(), |pos| start + pos + "</figcaption>".len());
+    let caption = strip_html_tags(&inner[start..end]);
+    let caption = caption.split_whitespace().collect::<Vec<_>>().join(" ");
+    let rest = format!("{}{}", &inner[..start], &inner[end..]);
+    (rest, Some(caption).filter(|c| !c.is_empty()))
+}
+
+/// Turn MathJax's `<script type="math/tex">` formulas into MathML with a
+/// TeX annotation, so they survive the removal of scripts
+fn math_scripts_to_mathml(html: &str) -> String {
+    static SCRIPT: OnceLock<Regex> = OnceLock::new();
+    let script = SCRIPT.get_or_init(|| {
+        Regex::new(r#"(?is)<script\b[^>]*type=["']math/tex(;\s*mode=display)?["'][^>]*>(.*?)</script>"#).unwrap()
+    });
+    script
+        .replace_all(html, |captures: &regex::Captures| {
+            let display = if captures.get(1).is_some() { r#" display="block""# } else { "" };
+            let tex = captures[2].replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+            format!(r#"<math{}><annotation encoding="application/x-tex">{}</annotation></math>"#, display, tex)
+        })
+        .into_owned()
+}
+
+/// The rest of the HTML after the element whose opening tag was just
+/// read, counting nested elements of the same name
+fn skip_element<'a>(remaining: &'a str, tag: &str) -> &'a str {
+    let lower = remaining.to_ascii_lowercase();
+    let (open, close) = (format!("<{}", tag), format!("</{}>", tag));
+    let mut depth = 1;
+    let mut pos = 0;
+    while let Some(next_close) = lower[pos..].find(&close).map(|p| pos + p) {
+        // Opening tags before this closing tag nest deeper
+        depth += lower[pos..next_close]
+            .match_indices(&open)
+            .filter(|(i, _)| {
+                let after = lower.as_bytes().get(pos + i + open.len());
+                after.is_some_and(|b| b.is_ascii_whitespace() || *b == b'>')
+            })
+            .count();
+        depth -= 1;
+        pos = next_close + close.len();
+        if depth == 0 {
+            return &remaining[pos..];
+        }
+    }
+    ""
+}
+
+/// Replace TeX written between `\(...\)` with its plain-text approximation,
+/// and split `$$...$$` and `\[...\]` out of paragraphs as displayed formulas
+fn expand_tex(elements: Vec<ContentElement>) -> Vec<ContentElement> {
+    static DISPLAY: OnceLock<Regex> = OnceLock::new();
+    static INLINE: OnceLock<Regex> = OnceLock::new();
+    let display = DISPLAY.get_or_init(|| Regex::new(r"(?s)\$\$(.+?)\$\$|\\\[(.+?)\\\]").unwrap());
+    let inline = INLINE.get_or_init(|| Regex::new(r"(?s)\\\((.+?)\\\)").unwrap());
+
+    let mut result = Vec::with_capacity(elements.len());
+    for element in elements {
+        let ContentElement::Text(text, mut styles) = element else {
+            result.push(element);
+            continue;
+        };
+
+        // Inline formulas change the length of the text after them
+        let mut replaced = String::with_capacity(text.len());
+        let mut last = 0;
+        for captures in inline.captures_iter(&text) {
+            let found = captures.get(0).unwrap();
+            replaced.push_str(&text[last..found.start()]);
+            let formula = tex_to_unicode(&captures[1]);
+            let at = visible_len(&replaced);
+            let (old, new) = (visible_len(found.as_str()), visible_len(&formula));
+            let shift = |pos: usize| match pos {
+                pos if pos <= at => pos,
+                pos if pos >= at + old => pos - old + new,
+                _ => at + new,
+            };
+            for style in &mut styles {
+                style.range = shift(style.range.start)..shift(style.range.end);
+            }
+            replaced.push_str(&formula);
+            last = found.end();
+        }
+        replaced.push_str(&text[last..]);
+
+        let mut last = 0;
+        for captures in display.captures_iter(&replaced) {
+            let found = captures.get(0).unwrap();
+            push_text_part(&mut result, &replaced, last..found.start(), &styles);
+            let tex = captures.get(1).or(captures.get(2)).map_or("", |m| m.as_str());
+            result.push(ContentElement::Math(tex_to_unicode(tex)));
+            last = found.end();
+        }
+        push_text_part(&mut result, &replaced, last..replaced.len(), &styles);
     }
+    result
+}
+
+/// Add the part of a paragraph in `bytes` as a paragraph of its own, with
+/// the inline styles over it
+fn push_text_part(result: &mut Vec<ContentElement>, text: &str, bytes: Range<usize>, styles: &[InlineStyle]) {
+    let part = text[bytes.clone()].trim();
+    if part.is_empty() {
+        return;
+    }
+    let start = visible_len(&text[..bytes.start]);
+    let end = start + visible_len(part);
+    let styles = styles
+        .iter()
+        .filter(|style| style.range.start < end && style.range.end > start)
+        .map(|style| InlineStyle {
+            kind: style.kind,
+            range: style.range.start.max(start) - start..style.range.end.min(end) - start,
+        })
+        .collect();
+    result.push(ContentElement::Text(part.to_string(), styles));
+}
+
+/// Remove the footnotes section (`<section class="footnotes">`,
+/// `<div class="footnotes">`...) and number its footnotes, replacing the
+/// links to them with `[1]`, `[2]`... Returns the rest of the HTML and
+/// the numbered footnote texts.
+fn split_footnotes(html: &str) -> (String, Vec<(String, String)>) {
+    static CONTAINER: OnceLock<Regex> = OnceLock::new();
+    static ITEM: OnceLock<Regex> = OnceLock::new();
+    static BACKLINK: OnceLock<Regex> = OnceLock::new();
+    let container = CONTAINER.get_or_init(|| {
+        Regex::new(r#"(?i)<(section|div|aside|ol)\b[^>]*(?:class=["'][^"']*\bfootnotes\b|role=["']doc-endnotes)"#)
+            .unwrap()
+    });
+    let item = ITEM.get_or_init(|| Regex::new(r"(?is)<li\b([^>]*)>(.*?)</li>").unwrap());
+    let backlink = BACKLINK
+        .get_or_init(|| Regex::new(r#"(?is)<a\b[^>]*href=["']#[^"']*["'][^>]*>.*?</a>"#).unwrap());
+
+    let Some(found) = container.captures(html) else {
+        return (html.to_string(), Vec::new());
+    };
+    let start = found.get(0).map_or(0, |m| m.start());
+    let tag = found[1].to_ascii_lowercase();
+    // Lowercasing ASCII only keeps byte offsets valid in `html`
+    let lower = html.to_ascii_lowercase();
+    let list_end = lower[start..]
+        .find("</ol>")
+        .map_or(html.len(), |pos| start + pos + "</ol>".len());
+    let end = if tag == "ol" {
+        list_end
+    } else {
+        let close_tag = format!("</{}>", tag);
+        lower[list_end..]
+            .find(&close_tag)
+            .map_or(list_end, |pos| list_end + pos + close_tag.len())
+    };
+
+    let mut rest = format!("{}{}", &html[..start], &html[end..]);
+    let mut footnotes = Vec::new();
+    for captures in item.captures_iter(&html[start..end]) {
+        let label = (footnotes.len() + 1).to_string();
+        let text = strip_html_tags(&backlink.replace_all(&captures[2], ""));
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        let text = text.trim_end_matches(['↩', '\u{fe0e}', ' ']).to_string();
+        if let Some(id) = extract_attr(&captures[1], "id").filter(|id| !id.is_empty()) {
+            let reference = Regex::new(&format!(
+                r#"(?is)<a\b[^>]*href=["']#{}["'][^>]*>.*?</a>"#,
+                regex::escape(&id)
+            ))
+            .unwrap();
+            rest = reference
+                .replace_all(&rest, format!("[{}]", label).as_str())
+                .into_owned();
+        }
+        footnotes.push((label, text));
+    }
+    (rest, footnotes)
+}
+
+/// Footnote references (`[1]`) in `text` to the given footnotes: byte
+/// offset, range in non-whitespace characters and label of each
+fn footnote_refs(text: &str, labels: &[&str]) -> Vec<(usize, Range<usize>, String)> {
+    static REFERENCE: OnceLock<Regex> = OnceLock::new();
+    let reference = REFERENCE.get_or_init(|| Regex::new(r"\[(\d+)\]").unwrap());
+    reference
+        .captures_iter(text)
+        .filter(|captures| labels.contains(&&captures[1]))
+        .map(|captures| {
+            let found = captures.get(0).unwrap();
+            let start = text[..found.start()].chars().filter(|c| !c.is_whitespace()).count();
+            let len = found.as_str().chars().count();
+            (found.start(), start..start + len, captures[1].to_string())
+        })
+        .collect()
 }
 
 /// Extract an attribute value from an HTML tag
@@ -650,41 +1042,60 @@ pub fn parse_text_with_urls(text: &str) -> Vec<TextSpan> {
 fn build_focusable_items(elements: &[ContentElement], _image_urls: &[String]) -> Vec<FocusableItem> {
     let mut items = Vec::new();
     let mut image_index = 0;
+    let labels: Vec<&str> = elements
+        .iter()
+        .filter_map(|element| match element {
+            ContentElement::Footnote { label, .. } => Some(label.as_str()),
+            _ => None,
+        })
+        .collect();
 
     for (elem_idx, element) in elements.iter().enumerate() {
-        match element {
+        let text = match element {
             ContentElement::Image { .. } => {
                 items.push(FocusableItem::Image { url_index: image_index });
                 image_index += 1;
+                continue;
             }
-            ContentElement::Text(text) => {
-                // Check for bare URLs in plain text
-                let spans = parse_text_with_urls(text);
-                for span in &spans {
-                    if let Some(ref url) = span.link_url {
-                        items.push(FocusableItem::Link {
-                            url: url.clone(),
-                            text: span.text.clone(),
-                            element_index: elem_idx,
-                        });
-                    }
-                }
+            ContentElement::Footnote { label, text } => {
+                items.push(FocusableItem::Footnote {
+                    label: label.clone(),
+                    element_index: elem_idx,
+                });
+                text
             }
-            ContentElement::Quote(text) | ContentElement::ListItem(text) => {
-                // Also check quotes and list items for bare URLs
-                let spans = parse_text_with_urls(text);
-                for span in &spans {
-                    if let Some(ref url) = span.link_url {
-                        items.push(FocusableItem::Link {
-                            url: url.clone(),
-                            text: span.text.clone(),
-                            element_index: elem_idx,
-                        });
-                    }
-                }
+            // Check paragraphs, quotes, list items and definitions for bare
+            // URLs and footnote references
+            ContentElement::Text(text, _)
+            | ContentElement::Quote(text)
+            | ContentElement::ListItem(text)
+            | ContentElement::Definition(text) => text,
+            _ => continue,
+        };
+
+        // Links and references, in the order they appear in the text
+        let mut found: Vec<(usize, FocusableItem)> = Vec::new();
+        let mut offset = 0;
+        for span in parse_text_with_urls(text) {
+            if let Some(url) = span.link_url {
+                found.push((
+                    offset,
+                    FocusableItem::Link {
+                        url,
+                        text: span.text.clone(),
+                        element_index: elem_idx,
+                    },
+                ));
             }
-            _ => {}
+            offset += span.text.len();
+        }
+        if !matches!(element, ContentElement::Footnote { .. }) {
+            found.extend(footnote_refs(text, &labels).into_iter().map(|(start, range, label)| {
+                (start, FocusableItem::FootnoteRef { label, element_index: elem_idx, range })
+            }));
         }
+        found.sort_by_key(|(start, _)| *start);
+        items.extend(found.into_iter().map(|(_, item)| item));
     }
 
     items
@@ -751,6 +1162,25 @@ impl ArticleImageCache {
         matches!(self.images.get(url), Some(ImageState::Loading))
     }
 
+    /// Check if any image is still loading
+    pub fn has_loading(&self) -> bool {
+        self.images.values().any(|state| matches!(state, ImageState::Loading))
+    }
+
+    /// Number of images still loading
+    pub fn loading_count(&self) -> usize {
+        self.images.values().filter(|state| matches!(state, ImageState::Loading)).count()
+    }
+
+    /// Drop the images' protocols after the terminal resized
+    pub fn invalidate_protocols(&mut self) {
+        for state in self.images.values_mut() {
+            if let ImageState::Loaded(data) = state {
+                data.invalidate_protocols();
+            }
+        }
+    }
+
     /// Get a loaded image
     pub fn get(&self, url: &str) -> Option<&CachedImageData> {
         match self.images.get(url) {
@@ -984,97 +1414,6 @@ pub async fn decode_image_bytes_async(bytes: Vec<u8>) -> Result<DynamicImage, St
         .map_err(|e| format!("Task failed: {}", e))?
 }
 
-/// Download image and decode it
-pub async fn download_image(url: &str) -> Result<(Vec<u8>, DynamicImage), String> {
-    parse_http_url(url)?;
-    let bytes = download_image_bytes(url).await?;
-    // Use async decoding to avoid blocking the main thread
-    let image = decode_image_bytes_async(bytes.clone()).await?;
-    Ok((bytes, image))
-}
-
-/// Download image bytes using curl (most compatible)
-async fn download_image_bytes(url: &str) -> Result<Vec<u8>, String> {
-    use std::process::Command;
-
-    let parsed = parse_http_url(url)?;
-    let referer = build_referer(&parsed);
-
-    let output = tokio::task::spawn_blocking({
-        let url = url.to_string();
-        let referer = referer.clone();
-        move || {
-            let max_bytes = MAX_IMAGE_BYTES.to_string();
-            Command::new("curl")
-                .args([
-                    "-sL",
-                    "--max-time", "15",
-                    "--max-filesize", &max_bytes,
-                    "-A", "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36",
-                    "-H", &format!("Referer: {}", referer),
-                    "-H", "Accept: image/png,image/jpeg,image/gif,image/*;q=0.8",
-                    &url,
-                ])
-                .output()
-        }
-    })
-    .await
-    .map_err(|e| format!("Task failed: {}", e))? // Added missing closing parenthesis
-    .map_err(|e| format!("Curl failed: {}", e))?;
-
-    if output.status.success() && !output.stdout.is_empty() {
-        if output.stdout.len() > MAX_IMAGE_BYTES {
-            return Err(format!("Image too large ({}B)", output.stdout.len()));
-        }
-        Ok(output.stdout)
-    } else {
-        // Fallback to reqwest
-        download_with_reqwest(url).await
-    }
-}
-
-/// Fallback download using reqwest
-async fn download_with_reqwest(url: &str) -> Result<Vec<u8>, String> {
-    let parsed = parse_http_url(url)?;
-    let referer = build_referer(&parsed);
-
-    let client = reqwest::Client::builder()
-        .user_agent("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36")
-        .timeout(std::time::Duration::from_secs(30))
-        .redirect(reqwest::redirect::Policy::limited(10))
-        .build()
-        .map_err(|e| format!("Client error: {}", e))?;
-
-    let response = client
-        .get(url)
-        .header("Accept", "image/png,image/jpeg,image/gif,image/*;q=0.8")
-        .header("Referer", &referer)
-        .send()
-        .await
-        .map_err(|e| format!("Request failed: {}", e))?;
-
-    if !response.status().is_success() {
-        return Err(format!("HTTP {}", response.status()));
-    }
-
-    if let Some(len) = response.content_length() {
-        if len as usize > MAX_IMAGE_BYTES {
-            return Err(format!("Image too large ({}B)", len));
-        }
-    }
-
-    let bytes = response
-        .bytes()
-        .await
-        .map_err(|e| format!("Read error: {}", e))?;
-
-    if bytes.len() > MAX_IMAGE_BYTES {
-        return Err(format!("Image too large ({}B)", bytes.len()));
-    }
-
-    Ok(bytes.to_vec())
-}
-
 /// Decode image bytes with format detection
 fn decode_image_bytes(bytes: &[u8]) -> Result<DynamicImage, String> {
     if bytes.is_empty() {
@@ -1109,24 +1448,6 @@ fn decode_image_bytes(bytes: &[u8]) -> Result<DynamicImage, String> {
     Err(format!("Unknown format ({}B)", bytes.len()))
 }
 
-fn parse_http_url(url: &str) -> Result<url::Url, String> {
-    let parsed = url::Url::parse(url).map_err(|e| format!("Invalid URL: {}", e))?;
-    match parsed.scheme() {
-        "http" | "https" => Ok(parsed),
-        scheme => Err(format!("Unsupported URL scheme: {}", scheme)),
-    }
-}
-
-fn build_referer(parsed: &url::Url) -> String {
-    match parsed.host_str() {
-        Some(host) => match parsed.port() {
-            Some(port) => format!("{}://{}:{}", parsed.scheme(), host, port),
-            None => format!("{}://{}", parsed.scheme(), host),
-        },
-        None => String::new(),
-    }
-}
-
 /// Global preload cache for prefetching images before entering article detail
 /// Shares the same ImageState enum as ArticleImageCache
 pub struct PreloadCache {
@@ -1155,6 +1476,20 @@ impl PreloadCache {
         matches!(self.images.get(url), Some(ImageState::Loading))
     }
 
+    /// Check if any image is still loading
+    pub fn has_loading(&self) -> bool {
+        self.images.values().any(|state| matches!(state, ImageState::Loading))
+    }
+
+    /// Drop the images' protocols after the terminal resized
+    pub fn invalidate_protocols(&mut self) {
+        for state in self.images.values_mut() {
+            if let ImageState::Loaded(data) = state {
+                data.invalidate_protocols();
+            }
+        }
+    }
+
     /// Get a loaded image
     pub fn get(&self, url: &str) -> Option<&CachedImageData> {
         match self.images.get(url) {
@@ -1239,6 +1574,32 @@ mod tests {
         assert_eq!(content.image_urls.len(), 3);
     }
 
+    #[test]
+    fn test_image_alt_and_figcaption() {
+        let html = r#"<figure><img src="a.jpg" alt="A &amp; B"><figcaption>The <em>first</em>
+            figure</figcaption></figure><img src="b.jpg" alt=" "><figure><figcaption>Alone</figcaption></figure>"#;
+        let content = RichContent::from_html(html);
+
+        let images: Vec<_> = content.elements.iter()
+            .filter(|e| matches!(e, ContentElement::Image { .. }))
+            .collect();
+        assert!(matches!(images[0], ContentElement::Image { alt: Some(alt), .. } if alt == "A & B"));
+        assert_eq!(images[0].image_caption(), Some("The first figure"));
+        // Blank alt text is ignored
+        assert_eq!(images[1].image_caption(), None);
+        // A caption without an image stays as text
+        assert!(content.elements.iter().any(|e| matches!(e, ContentElement::Text(t, _) if t == "Alone")));
+    }
+
+    #[test]
+    fn test_figcaption_after_non_ascii_text() {
+        // Lowercasing these changes their length in bytes
+        let inner = "\u{212A}İ<img src=\"a.jpg\"><figcaption>Café İ</figcaption>\u{212A}";
+        let (rest, caption) = split_figcaption(inner);
+        assert_eq!(rest, "\u{212A}İ<img src=\"a.jpg\">\u{212A}");
+        assert_eq!(caption.as_deref(), Some("Café İ"));
+    }
+
     #[test]
     fn test_extract_image_urls() {
         let html = r#"<p>Some text</p><img src="cover.jpg"><div><IMG SRC="nested.png"></div><img src='single.gif'>"#;
@@ -1259,4 +1620,86 @@ mod tests {
         assert_eq!(urls[0], "same.jpg");
         assert_eq!(urls[1], "different.png");
     }
+
+    #[test]
+    fn test_footnotes_are_numbered_and_moved_to_the_end() {
+        let html = r##"<p>Claim<sup id="fnref:a"><a href="#fn:a" class="footnote-ref">a</a></sup> and more.</p>
+<div class="footnotes" role="doc-endnotes"><hr><ol>
+<li id="fn:a"><p>See <em>the paper</em>. <a href="#fnref:a" class="footnote-backref">&#8617;</a></p></li>
+</ol></div><p>After</p>"##;
+        let content = RichContent::from_html(html);
+
+        assert!(matches!(&content.elements[0], ContentElement::Text(t, _) if t == "Claim[1] and more."));
+        assert!(matches!(&content.elements[2], ContentElement::Text(t, _) if t == "After"));
+        assert!(matches!(
+            content.elements.last(),
+            Some(ContentElement::Footnote { label, text }) if label == "1" && text == "See the paper."
+        ));
+        let footnote_index = content.elements.len() - 1;
+        match &content.focusable_items[..] {
+            [FocusableItem::FootnoteRef { label, element_index: 0, range }, FocusableItem::Footnote { element_index, .. }] => {
+                assert_eq!((label.as_str(), range.clone()), ("1", 5..8));
+                assert_eq!(*element_index, footnote_index);
+            }
+            items => panic!("unexpected focusable items: {:?}", items),
+        }
+    }
+
+    #[test]
+    fn test_math_is_kept_as_text() {
+        let html = r#"<p>Euler: <math><msup><mi>e</mi><mi>x</mi></msup></math> and</p>
+<script type="math/tex; mode=display">\sum_{n} a_n</script>
+<span class="katex"><span class="katex-mathml"><math><semantics><mi>y</mi>
+<annotation encoding="application/x-tex">\alpha</annotation></semantics></math></span>
+<span class="katex-html" aria-hidden="true"><span class="base"><span>α</span></span></span></span>
+<p>Raw $$x^2$$ and \(\beta\).</p>"#;
+        let content = RichContent::from_html(html);
+        let texts: Vec<String> = content
+            .elements
+            .iter()
+            .filter_map(|e| match e {
+                ContentElement::Text(t, _) => Some(format!("text:{}", t)),
+                ContentElement::Math(m) => Some(format!("math:{}", m)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            texts,
+            ["text:Euler: eˣ and", "math:∑ₙ aₙ", "text:α", "text:Raw", "math:x²", "text:and β."]
+        );
+    }
+
+    #[test]
+    fn test_inline_formatting() {
+        let html = r#"<p>A <strong>bold</strong> <em>and <b>nested</b></em> <code>x()</code>, <a href="https://e.com">site</a> \(\pi\) <i>end</i></p>
+<code>fn main() {
+}</code>"#;
+        let content = RichContent::from_html(html);
+
+        let ContentElement::Text(text, styles) = &content.elements[0] else {
+            panic!("expected a paragraph, got {:?}", content.elements[0]);
+        };
+        assert_eq!(text, "A bold and nested x(), site (https://e.com) π end");
+        let style = |kind, range| InlineStyle { kind, range };
+        assert_eq!(
+            styles,
+            &[
+                style(Inline::Bold, 1..5),
+                style(Inline::Italic, 5..14),
+                style(Inline::Bold, 8..14),
+                style(Inline::Code, 14..17),
+                style(Inline::Link, 18..22),
+                style(Inline::Italic, 38..41),
+            ]
+        );
+        // Code spanning several lines stays a block
+        assert!(matches!(&content.elements[2], ContentElement::Code(code) if code.starts_with("fn main")));
+    }
+
+    #[test]
+    fn test_definition_list() {
+        let content = RichContent::from_html("<dl><dt>RSS</dt><dd>Really  Simple\nSyndication</dd></dl>");
+        assert!(matches!(&content.elements[0], ContentElement::DefinitionTerm(t) if t == "RSS"));
+        assert!(matches!(&content.elements[1], ContentElement::Definition(t) if t == "Really Simple Syndication"));
+    }
 }
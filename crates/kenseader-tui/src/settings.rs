@@ -0,0 +1,282 @@
+use kenseader_core::config::AppConfig;
+
+use crate::feed_manager::TextField;
+use crate::themes::available_themes;
+
+/// AI providers offered in the settings screen
+const AI_PROVIDERS: [&str; 7] = [
+    "claude_cli",
+    "gemini_cli",
+    "codex_cli",
+    "openai",
+    "azure_openai",
+    "gemini_api",
+    "claude_api",
+];
+
+/// Action inside the settings screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsAction {
+    MoveUp,
+    MoveDown,
+    /// Toggle, pick the next choice or start editing the selected setting
+    Change,
+    /// Pick the previous choice
+    ChangeBack,
+    Close,
+}
+
+/// Common settings editable from the TUI, in display order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Setting {
+    Theme,
+    ImagePreview,
+    RelativeDates,
+    ShowScrollbar,
+    RefreshInterval,
+    ArticleRetention,
+    AutoReadDays,
+    AiEnabled,
+    AiProvider,
+    SummaryLanguage,
+}
+
+/// Value of a setting
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SettingValue {
+    Bool(bool),
+    Number(u64),
+    Text(String),
+}
+
+impl SettingValue {
+    pub fn to_toml(&self) -> toml::Value {
+        match self {
+            SettingValue::Bool(value) => toml::Value::Boolean(*value),
+            SettingValue::Number(value) => toml::Value::Integer(i64::try_from(*value).unwrap_or(i64::MAX)),
+            SettingValue::Text(value) => toml::Value::String(value.clone()),
+        }
+    }
+}
+
+impl std::fmt::Display for SettingValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SettingValue::Bool(value) => f.write_str(if *value { "[x]" } else { "[ ]" }),
+            SettingValue::Number(value) => write!(f, "{}", value),
+            SettingValue::Text(value) => f.write_str(value),
+        }
+    }
+}
+
+impl Setting {
+    pub const ALL: [Setting; 10] = [
+        Setting::Theme,
+        Setting::ImagePreview,
+        Setting::RelativeDates,
+        Setting::ShowScrollbar,
+        Setting::RefreshInterval,
+        Setting::ArticleRetention,
+        Setting::AutoReadDays,
+        Setting::AiEnabled,
+        Setting::AiProvider,
+        Setting::SummaryLanguage,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Setting::Theme => "Theme",
+            Setting::ImagePreview => "Image preview",
+            Setting::RelativeDates => "Relative dates",
+            Setting::ShowScrollbar => "Scrollbars",
+            Setting::RefreshInterval => "Refresh every (sec)",
+            Setting::ArticleRetention => "Keep articles (days)",
+            Setting::AutoReadDays => "Auto-read after days",
+            Setting::AiEnabled => "AI summaries",
+            Setting::AiProvider => "AI provider",
+            Setting::SummaryLanguage => "Summary language",
+        }
+    }
+
+    /// Dotted path of the setting in the config file
+    pub fn key(self) -> &'static str {
+        match self {
+            Setting::Theme => "ui.theme.name",
+            Setting::ImagePreview => "ui.image_preview",
+            Setting::RelativeDates => "ui.relative_dates",
+            Setting::ShowScrollbar => "ui.show_scrollbar",
+            Setting::RefreshInterval => "sync.refresh_interval_secs",
+            Setting::ArticleRetention => "general.article_retention_days",
+            Setting::AutoReadDays => "general.auto_read_days",
+            Setting::AiEnabled => "ai.enabled",
+            Setting::AiProvider => "ai.provider",
+            Setting::SummaryLanguage => "ai.summary_language",
+        }
+    }
+
+    /// Whether the TUI applies a change right away; the others are used by
+    /// the daemon, which reads them when it starts
+    pub fn is_live(self) -> bool {
+        matches!(
+            self,
+            Setting::Theme | Setting::ImagePreview | Setting::RelativeDates | Setting::ShowScrollbar
+        )
+    }
+
+    /// Values to pick from (None for free input)
+    pub fn choices(self) -> Option<Vec<&'static str>> {
+        match self {
+            Setting::Theme => Some(available_themes()),
+            Setting::AiProvider => Some(AI_PROVIDERS.to_vec()),
+            _ => None,
+        }
+    }
+
+    pub fn value(self, config: &AppConfig) -> SettingValue {
+        match self {
+            Setting::Theme => SettingValue::Text(config.ui.theme.name.clone()),
+            Setting::ImagePreview => SettingValue::Bool(config.ui.image_preview),
+            Setting::RelativeDates => SettingValue::Bool(config.ui.relative_dates),
+            Setting::ShowScrollbar => SettingValue::Bool(config.ui.show_scrollbar),
+            Setting::RefreshInterval => SettingValue::Number(config.sync.refresh_interval_secs),
+            Setting::ArticleRetention => SettingValue::Number(config.general.article_retention_days.into()),
+            Setting::AutoReadDays => SettingValue::Number(config.general.auto_read_days.into()),
+            Setting::AiEnabled => SettingValue::Bool(config.ai.enabled),
+            Setting::AiProvider => SettingValue::Text(config.ai.provider.clone()),
+            Setting::SummaryLanguage => SettingValue::Text(config.ai.summary_language.clone()),
+        }
+    }
+
+    /// Store a value in the loaded configuration
+    pub fn apply(self, config: &mut AppConfig, value: &SettingValue) {
+        match (self, value) {
+            (Setting::Theme, SettingValue::Text(name)) => config.ui.theme.name = name.clone(),
+            (Setting::ImagePreview, SettingValue::Bool(on)) => config.ui.image_preview = *on,
+            (Setting::RelativeDates, SettingValue::Bool(on)) => config.ui.relative_dates = *on,
+            (Setting::ShowScrollbar, SettingValue::Bool(on)) => config.ui.show_scrollbar = *on,
+            (Setting::RefreshInterval, SettingValue::Number(secs)) => config.sync.refresh_interval_secs = *secs,
+            (Setting::ArticleRetention, SettingValue::Number(days)) => {
+                config.general.article_retention_days = u32::try_from(*days).unwrap_or(u32::MAX)
+            }
+            (Setting::AutoReadDays, SettingValue::Number(days)) => {
+                config.general.auto_read_days = u32::try_from(*days).unwrap_or(u32::MAX)
+            }
+            (Setting::AiEnabled, SettingValue::Bool(on)) => config.ai.enabled = *on,
+            (Setting::AiProvider, SettingValue::Text(provider)) => config.ai.provider = provider.clone(),
+            (Setting::SummaryLanguage, SettingValue::Text(language)) => config.ai.summary_language = language.clone(),
+            _ => {}
+        }
+    }
+
+    /// Value after Space/Enter (`forward`) or going back: toggled, or the
+    /// next/previous choice. None for settings typed in.
+    pub fn cycle(self, value: &SettingValue, forward: bool) -> Option<SettingValue> {
+        if let SettingValue::Bool(on) = value {
+            return Some(SettingValue::Bool(!on));
+        }
+        let choices = self.choices()?;
+        let current = match value {
+            SettingValue::Text(current) => choices.iter().position(|c| c == current),
+            _ => None,
+        };
+        let idx = match (current, forward) {
+            (None, _) => 0,
+            (Some(idx), true) => (idx + 1) % choices.len(),
+            (Some(idx), false) => (idx + choices.len() - 1) % choices.len(),
+        };
+        Some(SettingValue::Text(choices[idx].to_string()))
+    }
+
+    /// Validate typed text
+    pub fn parse(self, text: &str) -> Result<SettingValue, String> {
+        let text = text.trim();
+        match self {
+            Setting::RefreshInterval => match text.parse::<u64>() {
+                Ok(secs) if secs > 0 => Ok(SettingValue::Number(secs)),
+                _ => Err("Refresh interval must be a positive number of seconds".to_string()),
+            },
+            Setting::ArticleRetention => match text.parse::<u32>() {
+                Ok(days) if days > 0 => Ok(SettingValue::Number(days.into())),
+                _ => Err("Article retention must be a positive number of days".to_string()),
+            },
+            Setting::AutoReadDays => text
+                .parse::<u32>()
+                .map(|days| SettingValue::Number(days.into()))
+                .map_err(|_| "Auto-read must be a number of days (0 = never)".to_string()),
+            _ if text.is_empty() => Err(format!("{} can't be empty", self.label())),
+            _ => Ok(SettingValue::Text(text.to_string())),
+        }
+    }
+}
+
+/// State of the settings screen
+#[derive(Debug, Clone, Default)]
+pub struct SettingsScreen {
+    /// Selected row (index into `Setting::ALL`)
+    pub selected: usize,
+    /// Text input of the setting being typed in
+    pub editing: Option<TextField>,
+    /// Validation or save error shown below the list
+    pub error: Option<String>,
+}
+
+impl SettingsScreen {
+    pub fn selected_setting(&self) -> Setting {
+        Setting::ALL[self.selected.min(Setting::ALL.len() - 1)]
+    }
+
+    pub fn move_down(&mut self) {
+        if self.selected + 1 < Setting::ALL.len() {
+            self.selected += 1;
+        }
+        self.error = None;
+    }
+
+    pub fn move_up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+        self.error = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cycle_setting_values() {
+        assert_eq!(
+            Setting::ImagePreview.cycle(&SettingValue::Bool(true), true),
+            Some(SettingValue::Bool(false))
+        );
+        let claude = SettingValue::Text("claude_cli".to_string());
+        assert_eq!(
+            Setting::AiProvider.cycle(&claude, true),
+            Some(SettingValue::Text("gemini_cli".to_string()))
+        );
+        assert_eq!(
+            Setting::AiProvider.cycle(&claude, false),
+            Some(SettingValue::Text("claude_api".to_string()))
+        );
+        assert_eq!(Setting::SummaryLanguage.cycle(&claude, true), None);
+    }
+
+    #[test]
+    fn test_parse_and_apply() {
+        assert!(Setting::RefreshInterval.parse("0").is_err());
+        assert!(Setting::SummaryLanguage.parse("  ").is_err());
+
+        let mut config = AppConfig::default();
+        let value = Setting::AutoReadDays.parse(" 7 ").unwrap();
+        Setting::AutoReadDays.apply(&mut config, &value);
+        assert_eq!(config.general.auto_read_days, 7);
+        assert_eq!(Setting::AutoReadDays.value(&config), value);
+        assert_eq!(value.to_toml(), toml::Value::Integer(7));
+
+        for setting in Setting::ALL {
+            let value = setting.value(&config);
+            let mut applied = AppConfig::default();
+            setting.apply(&mut applied, &value);
+            assert_eq!(setting.value(&applied), value, "{}", setting.key());
+        }
+    }
+}
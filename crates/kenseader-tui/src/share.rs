@@ -0,0 +1,120 @@
+use std::io::{self, Write};
+use std::process::Stdio;
+
+use base64::Engine;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+/// Share menu option
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShareTarget {
+    /// Copy the article URL
+    CopyUrl,
+    /// Copy `[title](url)`
+    CopyMarkdown,
+    /// Pipe title and URL to `ui.share_command`
+    SendCommand,
+    /// Copy the archive.org snapshot link
+    ArchiveLink,
+    /// Show the URL as a QR code
+    QrCode,
+}
+
+impl ShareTarget {
+    /// All options in menu order, with their key and label
+    pub const ALL: [(char, ShareTarget, &'static str); 5] = [
+        ('u', ShareTarget::CopyUrl, "Copy URL"),
+        ('m', ShareTarget::CopyMarkdown, "Copy as Markdown link"),
+        ('c', ShareTarget::SendCommand, "Send via share command"),
+        ('a', ShareTarget::ArchiveLink, "Copy archive.org link"),
+        ('q', ShareTarget::QrCode, "Show QR code"),
+    ];
+
+    /// Look up the option bound to a menu key
+    pub fn from_key(key: char) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .find(|(k, _, _)| *k == key)
+            .map(|(_, target, _)| *target)
+    }
+}
+
+/// Markdown link for an article, escaping brackets in the title
+pub fn markdown_link(title: &str, url: &str) -> String {
+    let title = title.replace('\\', "\\\\").replace('[', "\\[").replace(']', "\\]");
+    format!("[{}]({})", title, url)
+}
+
+/// Wayback Machine link that shows the latest snapshot of `url`
+pub fn archive_url(url: &str) -> String {
+    format!("https://web.archive.org/web/{}", url)
+}
+
+/// OSC 52 escape sequence that sets the system clipboard
+pub fn osc52_sequence(text: &str) -> String {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    format!("\x1b]52;c;{}\x07", encoded)
+}
+
+/// Copy text to the clipboard of the terminal (works over SSH)
+pub fn copy_to_clipboard(text: &str) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    stdout.write_all(osc52_sequence(text).as_bytes())?;
+    stdout.flush()
+}
+
+/// Run the configured share command in the background with the article
+/// title and URL on stdin and in the environment
+pub fn send_via_command(command: &str, title: &str, url: &str) -> io::Result<()> {
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .env("KENSEADER_TITLE", title)
+        .env("KENSEADER_URL", url)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    let input = format!("{}\n{}\n", title, url);
+    let mut stdin = child.stdin.take();
+    tokio::spawn(async move {
+        if let Some(ref mut stdin) = stdin {
+            let _ = stdin.write_all(input.as_bytes()).await;
+        }
+        drop(stdin);
+        match child.wait().await {
+            Ok(status) if !status.success() => {
+                tracing::warn!("Share command exited with {}", status);
+            }
+            Err(e) => tracing::warn!("Share command failed: {}", e),
+            _ => {}
+        }
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_link_escapes_brackets() {
+        assert_eq!(
+            markdown_link("Rust [1.80] released", "https://example.com"),
+            "[Rust \\[1.80\\] released](https://example.com)"
+        );
+    }
+
+    #[test]
+    fn test_osc52_sequence() {
+        assert_eq!(osc52_sequence("hi"), "\x1b]52;c;aGk=\x07");
+    }
+
+    #[test]
+    fn test_share_target_keys() {
+        assert_eq!(ShareTarget::from_key('m'), Some(ShareTarget::CopyMarkdown));
+        assert_eq!(ShareTarget::from_key('x'), None);
+    }
+}
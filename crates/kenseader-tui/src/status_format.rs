@@ -0,0 +1,87 @@
+use std::time::Duration;
+
+/// Expand the `{name}` and `{name:arg}` placeholders of a status bar format
+/// with `value`. Placeholders it doesn't know are kept as written; `{{` and
+/// `}}` are literal braces.
+pub fn expand(format: &str, mut value: impl FnMut(&str, Option<&str>) -> Option<String>) -> String {
+    let mut out = String::with_capacity(format.len());
+    let mut rest = format;
+    while let Some(pos) = rest.find(['{', '}']) {
+        out.push_str(&rest[..pos]);
+        let tail = &rest[pos..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            out.push_str(&tail[..1]);
+            rest = &tail[2..];
+            continue;
+        }
+        match tail.starts_with('{').then(|| tail.find('}')).flatten() {
+            Some(end) => {
+                let placeholder = &tail[1..end];
+                let (name, arg) = match placeholder.split_once(':') {
+                    Some((name, arg)) => (name, Some(arg)),
+                    None => (placeholder, None),
+                };
+                match value(name, arg) {
+                    Some(v) => out.push_str(&v),
+                    None => out.push_str(&tail[..=end]),
+                }
+                rest = &tail[end + 1..];
+            }
+            None => {
+                out.push_str(&tail[..1]);
+                rest = &tail[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Whether `format` uses any of the placeholders `names`
+pub fn uses_any(format: &str, names: &[&str]) -> bool {
+    let mut used = false;
+    expand(format, |name, _| {
+        used |= names.contains(&name);
+        None
+    });
+    used
+}
+
+/// Short countdown like "45s", "12m" or "1h05m"
+pub fn format_countdown(left: Duration) -> String {
+    let secs = left.as_secs();
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m", secs / 60),
+        _ => format!("{}h{:02}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_placeholders() {
+        let value = |name: &str, arg: Option<&str>| match (name, arg) {
+            ("feed", None) => Some("Rust Blog".to_string()),
+            ("unread", None) => Some("12".to_string()),
+            ("clock", Some(format)) => Some(format.to_string()),
+            _ => None,
+        };
+        assert_eq!(expand(" {feed} ({unread}) ", value), " Rust Blog (12) ");
+        assert_eq!(expand("{clock:%H:%M}", value), "%H:%M");
+        // Unknown placeholders, literal and unbalanced braces
+        assert_eq!(expand("{nope} {{x}} {feed", value), "{nope} {x} {feed");
+
+        assert!(uses_any("{mode} {ai_queue}", &["ai_queue", "daemon"]));
+        assert!(!uses_any("{mode} {{ai_queue}}", &["ai_queue"]));
+    }
+
+    #[test]
+    fn test_format_countdown() {
+        assert_eq!(format_countdown(Duration::from_secs(45)), "45s");
+        assert_eq!(format_countdown(Duration::from_secs(12 * 60 + 5)), "12m");
+        assert_eq!(format_countdown(Duration::from_secs(3900)), "1h05m");
+    }
+}
@@ -0,0 +1,155 @@
+//! Prompt for adding tags to the selected articles, with completion of
+//! tags already in use
+
+use uuid::Uuid;
+
+use crate::feed_manager::TextField;
+
+/// Suggestions shown under the prompt
+pub const MAX_SUGGESTIONS: usize = 6;
+
+#[derive(Debug, Clone, Default)]
+pub struct TagPrompt {
+    /// Comma-separated tags
+    pub input: TextField,
+    /// Articles the tags are added to
+    pub targets: Vec<Uuid>,
+    /// Tags in use, most used first
+    pub known: Vec<String>,
+    /// Typed prefix and index of the suggestion picked with Tab
+    completion: Option<(String, usize)>,
+    pub error: Option<String>,
+}
+
+impl TagPrompt {
+    pub fn new(targets: Vec<Uuid>, known: Vec<String>) -> Self {
+        Self {
+            targets,
+            known,
+            ..Self::default()
+        }
+    }
+
+    /// Byte offset where the tag being typed starts (after the last comma)
+    fn fragment_start(&self) -> usize {
+        self.input.value.rfind(',').map_or(0, |i| i + 1)
+    }
+
+    /// The tag being typed
+    fn fragment(&self) -> &str {
+        self.input.value[self.fragment_start()..].trim_start()
+    }
+
+    /// Tags in use that start with `prefix` and aren't entered before the
+    /// one being typed
+    fn matching(&self, prefix: &str) -> Vec<&str> {
+        let prefix = prefix.to_lowercase();
+        let entered = parse_tags(&self.input.value[..self.fragment_start()]);
+        self.known
+            .iter()
+            .filter(|tag| tag.to_lowercase().starts_with(&prefix))
+            .filter(|tag| !entered.iter().any(|e| e.eq_ignore_ascii_case(tag)))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Completions for the tag being typed
+    pub fn suggestions(&self) -> Vec<&str> {
+        match &self.completion {
+            Some((prefix, _)) => self.matching(prefix),
+            None => self.matching(self.fragment()),
+        }
+    }
+
+    /// Replace the tag being typed with the next (or previous) completion
+    pub fn complete(&mut self, forward: bool) {
+        let (prefix, index) = match self.completion.take() {
+            Some((prefix, index)) => {
+                let count = self.matching(&prefix).len().max(1);
+                let index = if forward { (index + 1) % count } else { (index + count - 1) % count };
+                (prefix, index)
+            }
+            None => (self.fragment().to_string(), 0),
+        };
+        let Some(tag) = self.matching(&prefix).get(index).map(|tag| tag.to_string()) else {
+            return;
+        };
+        let value = &self.input.value;
+        let start = self.fragment_start();
+        let separator = if start > 0 { " " } else { "" };
+        self.input = TextField::new(format!("{}{}{}", &value[..start], separator, tag));
+        self.completion = Some((prefix, index));
+    }
+
+    pub fn input(&mut self, c: char) {
+        self.completion = None;
+        self.input.insert(c);
+        self.error = None;
+    }
+
+    pub fn backspace(&mut self) {
+        self.completion = None;
+        self.input.backspace();
+    }
+
+    pub fn cursor_left(&mut self) {
+        self.completion = None;
+        self.input.move_left();
+    }
+
+    pub fn cursor_right(&mut self) {
+        self.completion = None;
+        self.input.move_right();
+    }
+
+    /// The typed tags
+    pub fn tags(&self) -> Vec<String> {
+        parse_tags(&self.input.value)
+    }
+}
+
+/// Comma-separated tags, trimmed, without empty or repeated ones
+fn parse_tags(value: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in value.split(',').map(str::trim) {
+        if !tag.is_empty() && !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prompt(value: &str) -> TagPrompt {
+        let known = ["rust", "rss", "release", "go"].map(String::from).to_vec();
+        let mut prompt = TagPrompt::new(vec![Uuid::new_v4()], known);
+        prompt.input = TextField::new(value);
+        prompt
+    }
+
+    #[test]
+    fn test_tags_are_trimmed_and_deduplicated() {
+        assert_eq!(prompt(" rust, ,Go , RUST,go").tags(), ["rust", "Go"]);
+        assert!(prompt(" , ").tags().is_empty());
+    }
+
+    #[test]
+    fn test_tab_cycles_through_completions() {
+        let mut prompt = prompt("go, r");
+        assert_eq!(prompt.suggestions(), ["rust", "rss", "release"]);
+
+        prompt.complete(true);
+        assert_eq!(prompt.input.value, "go, rust");
+        prompt.complete(true);
+        assert_eq!(prompt.input.value, "go, rss");
+        prompt.complete(false);
+        assert_eq!(prompt.input.value, "go, rust");
+
+        // Typing ends the completion; entered tags aren't suggested again
+        prompt.input(',');
+        assert_eq!(prompt.suggestions(), ["rss", "release"]);
+    }
+}
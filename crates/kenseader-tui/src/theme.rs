@@ -1,3 +1,4 @@
+use kenseader_core::config::ColorMode;
 use ratatui::style::Color;
 
 /// Runtime theme with configurable colors
@@ -68,3 +69,264 @@ impl Default for Theme {
     }
 }
 
+
+/// Colors the terminal can display
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorDepth {
+    TrueColor,
+    Ansi256,
+    Ansi16,
+}
+
+impl ColorDepth {
+    /// The configured depth, or the one detected from the environment
+    pub fn from_mode(mode: ColorMode) -> Self {
+        match mode {
+            ColorMode::Auto => Self::detect(
+                std::env::var("COLORTERM").ok().as_deref(),
+                std::env::var("TERM").ok().as_deref(),
+            ),
+            ColorMode::TrueColor => ColorDepth::TrueColor,
+            ColorMode::Ansi256 => ColorDepth::Ansi256,
+            ColorMode::Ansi16 => ColorDepth::Ansi16,
+        }
+    }
+
+    /// Depth from `$COLORTERM` and `$TERM`. Terminals don't announce
+    /// truecolor reliably (COLORTERM is often lost over ssh), so a few
+    /// terminal names known to support it count too.
+    fn detect(colorterm: Option<&str>, term: Option<&str>) -> Self {
+        if matches!(colorterm, Some("truecolor" | "24bit")) {
+            return ColorDepth::TrueColor;
+        }
+        let Some(term) = term.filter(|t| !t.is_empty()) else {
+            // No TERM (e.g. Windows Terminal): assume a modern terminal
+            return ColorDepth::TrueColor;
+        };
+        const TRUECOLOR_TERMS: &[&str] = &["-direct", "kitty", "ghostty", "alacritty", "wezterm", "foot"];
+        if TRUECOLOR_TERMS.iter().any(|name| term.contains(name)) {
+            ColorDepth::TrueColor
+        } else if term.contains("256color") {
+            ColorDepth::Ansi256
+        } else {
+            ColorDepth::Ansi16
+        }
+    }
+
+    /// `color` as the nearest color of this depth
+    pub fn fit(self, color: Color) -> Color {
+        match (self, color) {
+            (ColorDepth::Ansi256, Color::Rgb(r, g, b)) => Color::Indexed(nearest_256(r, g, b)),
+            (ColorDepth::Ansi16, Color::Rgb(r, g, b)) => nearest_16(r, g, b),
+            _ => color,
+        }
+    }
+}
+
+impl Theme {
+    /// The theme with every color mapped to what the terminal can display
+    pub fn with_depth(mut self, depth: ColorDepth) -> Self {
+        match depth {
+            ColorDepth::TrueColor => return self,
+            ColorDepth::Ansi16 => return self.to_ansi16(),
+            ColorDepth::Ansi256 => {}
+        }
+        for color in [
+            &mut self.bg0, &mut self.bg1, &mut self.bg2, &mut self.bg3,
+            &mut self.fg0, &mut self.fg1, &mut self.grey0, &mut self.grey1, &mut self.grey2,
+            &mut self.red, &mut self.orange, &mut self.yellow, &mut self.green,
+            &mut self.aqua, &mut self.blue, &mut self.purple,
+            &mut self.selection, &mut self.unread, &mut self.read, &mut self.error,
+            &mut self.success, &mut self.warning, &mut self.info, &mut self.accent,
+        ] {
+            *color = depth.fit(*color);
+        }
+        self
+    }
+
+    /// Map the theme to the 16 ANSI colors by role: the terminal's default
+    /// background and foreground for the base colors, and the ANSI color of
+    /// each palette hue. Semantic colors take the role they were copied
+    /// from, or the nearest hue.
+    fn to_ansi16(&self) -> Self {
+        let dark = match self.bg0 {
+            Color::Rgb(r, g, b) => u16::from(r) + u16::from(g) + u16::from(b) < 3 * 128,
+            _ => true,
+        };
+        let (bg1, bg2, grey0, grey1, grey2) = if dark {
+            (Color::Black, Color::DarkGray, Color::DarkGray, Color::DarkGray, Color::Gray)
+        } else {
+            (Color::White, Color::Gray, Color::Gray, Color::DarkGray, Color::DarkGray)
+        };
+        let roles = [
+            (self.bg0, Color::Reset),
+            (self.bg1, bg1),
+            (self.bg2, bg2),
+            (self.bg3, bg2),
+            (self.fg0, Color::Reset),
+            (self.fg1, Color::Reset),
+            (self.grey0, grey0),
+            (self.grey1, grey1),
+            (self.grey2, grey2),
+            (self.red, Color::Red),
+            (self.orange, Color::LightRed),
+            (self.yellow, Color::Yellow),
+            (self.green, Color::Green),
+            (self.aqua, Color::Cyan),
+            (self.blue, Color::Blue),
+            (self.purple, Color::Magenta),
+        ];
+        let map = |color: Color| {
+            roles
+                .iter()
+                .find(|(original, _)| *original == color)
+                .map(|(_, ansi)| *ansi)
+                .unwrap_or_else(|| ColorDepth::Ansi16.fit(color))
+        };
+        Self {
+            bg0: map(self.bg0),
+            bg1: map(self.bg1),
+            bg2: map(self.bg2),
+            bg3: map(self.bg3),
+            fg0: map(self.fg0),
+            fg1: map(self.fg1),
+            grey0: map(self.grey0),
+            grey1: map(self.grey1),
+            grey2: map(self.grey2),
+            red: map(self.red),
+            orange: map(self.orange),
+            yellow: map(self.yellow),
+            green: map(self.green),
+            aqua: map(self.aqua),
+            blue: map(self.blue),
+            purple: map(self.purple),
+            selection: map(self.selection),
+            unread: map(self.unread),
+            read: map(self.read),
+            error: map(self.error),
+            success: map(self.success),
+            warning: map(self.warning),
+            info: map(self.info),
+            accent: map(self.accent),
+        }
+    }
+}
+
+/// Levels of each channel in the xterm 6x6x6 color cube (indices 16-231)
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let d = |x: u8, y: u8| (i32::from(x) - i32::from(y)).unsigned_abs();
+    d(a.0, b.0).pow(2) + d(a.1, b.1).pow(2) + d(a.2, b.2).pow(2)
+}
+
+/// Nearest xterm-256 color: the closest color cube entry or grey ramp step
+fn nearest_256(r: u8, g: u8, b: u8) -> u8 {
+    let level = |v: u8| {
+        (0..6)
+            .min_by_key(|&i| (i32::from(CUBE_LEVELS[i]) - i32::from(v)).unsigned_abs())
+            .unwrap_or(0)
+    };
+    let (ri, gi, bi) = (level(r), level(g), level(b));
+    let cube = (CUBE_LEVELS[ri], CUBE_LEVELS[gi], CUBE_LEVELS[bi]);
+    let cube_index = 16 + 36 * ri + 6 * gi + bi;
+
+    // Grey ramp 232-255: 8, 18, ..., 238
+    let average = (u32::from(r) + u32::from(g) + u32::from(b)) / 3;
+    let step = (average.saturating_sub(3) / 10).min(23) as u8;
+    let grey = 8 + 10 * step;
+
+    if distance((grey, grey, grey), (r, g, b)) < distance(cube, (r, g, b)) {
+        232 + step
+    } else {
+        cube_index as u8
+    }
+}
+
+/// Nearest of the 16 ANSI colors. Their actual values depend on the
+/// terminal's palette, so the match is by hue and lightness rather than
+/// by distance to the xterm defaults (which turns muted theme colors grey).
+fn nearest_16(r: u8, g: u8, b: u8) -> Color {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let chroma = max - min;
+    let lightness = (u16::from(max) + u16::from(min)) / 2;
+
+    // Greys, and backgrounds/text too dark or light to show a hue
+    if chroma < 32 || !(64..=216).contains(&lightness) {
+        return match lightness {
+            0..=47 => Color::Black,
+            48..=111 => Color::DarkGray,
+            112..=199 => Color::Gray,
+            _ => Color::White,
+        };
+    }
+
+    let (r, g, b, c) = (f32::from(r), f32::from(g), f32::from(b), f32::from(chroma));
+    let hue = if max == min {
+        0.0
+    } else if r >= g && r >= b {
+        (60.0 * ((g - b) / c)).rem_euclid(360.0)
+    } else if g >= b {
+        60.0 * ((b - r) / c + 2.0)
+    } else {
+        60.0 * ((r - g) / c + 4.0)
+    };
+    let bright = lightness > 150;
+    let pick = |normal: Color, light: Color| if bright { light } else { normal };
+    match hue as u16 {
+        20..=64 => pick(Color::Yellow, Color::LightYellow),
+        65..=164 => pick(Color::Green, Color::LightGreen),
+        165..=199 => pick(Color::Cyan, Color::LightCyan),
+        200..=259 => pick(Color::Blue, Color::LightBlue),
+        260..=344 => pick(Color::Magenta, Color::LightMagenta),
+        _ => pick(Color::Red, Color::LightRed),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_depth_detection() {
+        assert_eq!(ColorDepth::detect(Some("truecolor"), Some("xterm")), ColorDepth::TrueColor);
+        assert_eq!(ColorDepth::detect(None, Some("xterm-kitty")), ColorDepth::TrueColor);
+        assert_eq!(ColorDepth::detect(None, Some("screen-256color")), ColorDepth::Ansi256);
+        assert_eq!(ColorDepth::detect(None, Some("xterm")), ColorDepth::Ansi16);
+        assert_eq!(ColorDepth::detect(None, None), ColorDepth::TrueColor);
+    }
+
+    #[test]
+    fn test_fit_colors() {
+        let fit256 = |r, g, b| ColorDepth::Ansi256.fit(Color::Rgb(r, g, b));
+        assert_eq!(fit256(255, 0, 0), Color::Indexed(196));
+        assert_eq!(fit256(0x28, 0x28, 0x28), Color::Indexed(235));
+        assert_eq!(fit256(0x87, 0xaf, 0xd7), Color::Indexed(110));
+
+        // Gruvbox dark: background, red, green, aqua, purple
+        let fit16 = |r, g, b| ColorDepth::Ansi16.fit(Color::Rgb(r, g, b));
+        assert_eq!(fit16(0x28, 0x28, 0x28), Color::Black);
+        assert_eq!(fit16(0xea, 0x69, 0x62), Color::LightRed);
+        assert_eq!(fit16(0xa9, 0xb6, 0x65), Color::Green);
+        assert_eq!(fit16(0x89, 0xb4, 0x82), Color::LightGreen);
+        assert_eq!(fit16(0xd3, 0x86, 0x9b), Color::LightMagenta);
+        // Gruvbox light background
+        assert_eq!(fit16(0xfb, 0xf1, 0xc7), Color::White);
+
+        assert_eq!(ColorDepth::Ansi16.fit(Color::Reset), Color::Reset);
+        assert_eq!(ColorDepth::TrueColor.fit(Color::Rgb(1, 2, 3)), Color::Rgb(1, 2, 3));
+    }
+
+    #[test]
+    fn test_theme_to_ansi16() {
+        let theme = Theme::default().with_depth(ColorDepth::Ansi16);
+        assert_eq!(theme.bg0, Color::Reset);
+        assert_eq!(theme.fg0, Color::Reset);
+        assert_eq!(theme.aqua, Color::Cyan);
+        // Semantic colors follow the role they were copied from
+        assert_eq!(theme.selection, Color::DarkGray);
+        assert_eq!(theme.unread, Color::Yellow);
+        assert_eq!(theme.accent, Color::Cyan);
+    }
+}
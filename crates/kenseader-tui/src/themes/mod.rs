@@ -247,6 +247,7 @@ mod tests {
                 unread: Some("#ff0000".to_string()),
                 ..Default::default()
             },
+            ..Default::default()
         };
         let theme = load_theme(&config);
         assert!(matches!(theme.unread, Color::Rgb(255, 0, 0)));
@@ -0,0 +1,77 @@
+use chrono::{DateTime, Local, Utc};
+use kenseader_core::config::UiConfig;
+
+/// Format a stored UTC timestamp for display according to the UI config
+pub fn format_timestamp(timestamp: &DateTime<Utc>, config: &UiConfig) -> String {
+    if config.relative_dates {
+        format_relative(timestamp, &Utc::now())
+    } else {
+        format_absolute(timestamp, &config.date_format)
+    }
+}
+
+/// Format a timestamp in local time with a strftime-style format string
+pub fn format_absolute(timestamp: &DateTime<Utc>, format: &str) -> String {
+    use std::fmt::Write;
+
+    let local = timestamp.with_timezone(&Local);
+    let mut out = String::new();
+    // An invalid format string makes Display fail; fall back to the default format
+    if write!(out, "{}", local.format(format)).is_err() {
+        out.clear();
+        let _ = write!(out, "{}", local.format("%Y-%m-%d %H:%M"));
+    }
+    out
+}
+
+/// Format a timestamp relative to `now`, e.g. "3h ago"
+pub fn format_relative(timestamp: &DateTime<Utc>, now: &DateTime<Utc>) -> String {
+    let secs = now.signed_duration_since(*timestamp).num_seconds();
+
+    // Future timestamps (clock skew between feed and local machine)
+    if secs < 60 {
+        return "just now".to_string();
+    }
+
+    let minutes = secs / 60;
+    let hours = minutes / 60;
+    let days = hours / 24;
+
+    if minutes < 60 {
+        format!("{}m ago", minutes)
+    } else if hours < 24 {
+        format!("{}h ago", hours)
+    } else if days < 30 {
+        format!("{}d ago", days)
+    } else if days < 365 {
+        format!("{}mo ago", days / 30)
+    } else {
+        format!("{}y ago", days / 365)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_format_relative() {
+        let now = Utc::now();
+        assert_eq!(format_relative(&(now - Duration::seconds(10)), &now), "just now");
+        assert_eq!(format_relative(&(now + Duration::minutes(5)), &now), "just now");
+        assert_eq!(format_relative(&(now - Duration::minutes(5)), &now), "5m ago");
+        assert_eq!(format_relative(&(now - Duration::hours(3)), &now), "3h ago");
+        assert_eq!(format_relative(&(now - Duration::days(2)), &now), "2d ago");
+        assert_eq!(format_relative(&(now - Duration::days(65)), &now), "2mo ago");
+        assert_eq!(format_relative(&(now - Duration::days(800)), &now), "2y ago");
+    }
+
+    #[test]
+    fn test_format_absolute_invalid_format_falls_back() {
+        let ts = Utc::now();
+        let expected = ts.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string();
+        assert_eq!(format_absolute(&ts, "%Q"), expected);
+        assert_eq!(format_absolute(&ts, "%Y-%m-%d %H:%M"), expected);
+    }
+}
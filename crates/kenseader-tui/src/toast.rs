@@ -0,0 +1,157 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local};
+
+/// Messages kept for the message history
+const HISTORY_LEN: usize = 100;
+
+/// Toasts shown at once; older ones are dismissed early
+const MAX_VISIBLE: usize = 3;
+
+/// Severity of a toast, which sets its color and how long it stays
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Info,
+    Warn,
+    Error,
+}
+
+impl Severity {
+    pub fn label(self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warn => "warn",
+            Severity::Error => "error",
+        }
+    }
+
+    /// Warnings and errors stay up longer than the configured timeout
+    fn ttl(self, timeout: Duration) -> Duration {
+        match self {
+            Severity::Info => timeout,
+            Severity::Warn => timeout * 2,
+            Severity::Error => timeout * 3,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub message: String,
+    pub severity: Severity,
+    pub created: Instant,
+    /// Wall-clock time, shown in the history
+    pub time: DateTime<Local>,
+}
+
+/// Stacked, auto-expiring messages plus a history of everything shown
+#[derive(Debug, Clone)]
+pub struct Toasts {
+    /// Toasts on screen, oldest first
+    active: VecDeque<Toast>,
+    /// Every toast pushed, oldest first
+    history: VecDeque<Toast>,
+    timeout: Duration,
+}
+
+impl Toasts {
+    pub fn new(timeout: Duration) -> Self {
+        Self { active: VecDeque::new(), history: VecDeque::new(), timeout }
+    }
+
+    pub fn push(&mut self, message: impl Into<String>, severity: Severity) {
+        let toast = Toast {
+            message: message.into(),
+            severity,
+            created: Instant::now(),
+            time: Local::now(),
+        };
+        // Repeating the newest message just restarts its timer
+        if let Some(last) = self.active.back_mut() {
+            if last.message == toast.message && last.severity == toast.severity {
+                last.created = toast.created;
+                return;
+            }
+        }
+        if self.history.len() == HISTORY_LEN {
+            self.history.pop_front();
+        }
+        self.history.push_back(toast.clone());
+        self.active.push_back(toast);
+        if self.active.len() > MAX_VISIBLE {
+            self.active.pop_front();
+        }
+    }
+
+    /// Drop toasts that have been shown long enough. Returns whether any expired.
+    pub fn expire(&mut self) -> bool {
+        let now = Instant::now();
+        let before = self.active.len();
+        let timeout = self.timeout;
+        self.active
+            .retain(|toast| now.duration_since(toast.created) < toast.severity.ttl(timeout));
+        self.active.len() != before
+    }
+
+    /// Toasts on screen, oldest first
+    pub fn visible(&self) -> impl Iterator<Item = &Toast> {
+        self.active.iter()
+    }
+
+    /// Newest toast on screen
+    pub fn latest(&self) -> Option<&Toast> {
+        self.active.back()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.active.is_empty()
+    }
+
+    /// Dismiss the toasts on screen (they stay in the history)
+    pub fn dismiss(&mut self) {
+        self.active.clear();
+    }
+
+    /// Past messages, newest first
+    pub fn history(&self) -> impl Iterator<Item = &Toast> {
+        self.history.iter().rev()
+    }
+}
+
+impl Default for Toasts {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(4))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toasts_stack_and_expire() {
+        let mut toasts = Toasts::new(Duration::from_millis(50));
+        toasts.push("one", Severity::Info);
+        toasts.push("one", Severity::Info);
+        toasts.push("failed", Severity::Error);
+        for i in 0..3 {
+            toasts.push(format!("info {}", i), Severity::Info);
+        }
+        // Repeats are merged; only the newest few stay on screen
+        assert_eq!(toasts.history().count(), 5);
+        let visible: Vec<&str> = toasts.visible().map(|t| t.message.as_str()).collect();
+        assert_eq!(visible, ["info 0", "info 1", "info 2"]);
+
+        toasts.push("failed again", Severity::Error);
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(toasts.expire());
+        // Errors outlive infos
+        assert_eq!(toasts.latest().unwrap().message, "failed again");
+        assert_eq!(toasts.visible().count(), 1);
+
+        toasts.dismiss();
+        assert!(toasts.is_empty());
+        assert_eq!(toasts.history().next().unwrap().message, "failed again");
+    }
+}
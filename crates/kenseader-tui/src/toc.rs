@@ -0,0 +1,138 @@
+//! Table of contents of the article and scrolling by element
+//!
+//! Both go by the line each element starts at, which follows from the
+//! element heights of the last render.
+
+use crate::rich_content::{ContentElement, RichContent};
+
+/// A heading listed in the table of contents
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TocEntry {
+    /// Index of the heading in the article's elements
+    pub element_index: usize,
+    pub level: u8,
+    pub title: String,
+}
+
+/// Action inside the table of contents
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TocAction {
+    MoveDown,
+    MoveUp,
+    /// Scroll the article to the selected heading
+    Jump,
+    Close,
+}
+
+/// Headings of the article, in order
+pub fn table_of_contents(content: &RichContent) -> Vec<TocEntry> {
+    content
+        .elements
+        .iter()
+        .enumerate()
+        .filter_map(|(element_index, element)| match element {
+            ContentElement::Heading(level, text) => Some(TocEntry {
+                element_index,
+                level: *level,
+                title: text.split_whitespace().collect::<Vec<_>>().join(" "),
+            }),
+            _ => None,
+        })
+        .filter(|entry| !entry.title.is_empty())
+        .collect()
+}
+
+/// Line each element starts at, after `header` lines of title and metadata
+pub fn element_starts(header: u16, heights: &[u16]) -> Vec<u16> {
+    heights
+        .iter()
+        .scan(header, |start, height| {
+            let element_start = *start;
+            *start = start.saturating_add(*height);
+            Some(element_start)
+        })
+        .collect()
+}
+
+/// Scroll showing an element that starts at `start` at the top, with a line
+/// above it in view
+pub fn scroll_to_start(start: u16) -> u16 {
+    start.saturating_sub(1)
+}
+
+/// Elements worth stopping at: everything but paragraph spacing
+fn stops<'a>(content: &'a RichContent, starts: &'a [u16]) -> impl Iterator<Item = u16> + 'a {
+    content
+        .elements
+        .iter()
+        .zip(starts)
+        .filter(|(element, _)| !matches!(element, ContentElement::EmptyLine))
+        .map(|(_, start)| scroll_to_start(*start))
+}
+
+/// Scroll to the first element starting below `scroll`
+pub fn next_element_scroll(content: &RichContent, starts: &[u16], scroll: u16) -> Option<u16> {
+    stops(content, starts).find(|stop| *stop > scroll)
+}
+
+/// Scroll to the last element starting above `scroll`
+pub fn prev_element_scroll(content: &RichContent, starts: &[u16], scroll: u16) -> Option<u16> {
+    stops(content, starts).take_while(|stop| *stop < scroll).last()
+}
+
+/// Entry of the heading the view at `scroll` is in: the last one starting
+/// at or above the top line
+pub fn current_entry(entries: &[TocEntry], starts: &[u16], scroll: u16) -> Option<usize> {
+    entries
+        .iter()
+        .rposition(|entry| starts.get(entry.element_index).is_some_and(|start| scroll_to_start(*start) <= scroll))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn article() -> RichContent {
+        RichContent::from_html(
+            "<h1>Intro</h1><p>One</p><p>Two</p><h2>Deeper\n  part</h2><p>Three</p><h2> </h2>",
+        )
+    }
+
+    #[test]
+    fn test_table_of_contents_lists_headings() {
+        let content = article();
+        let toc = table_of_contents(&content);
+        let titles: Vec<_> = toc.iter().map(|e| (e.level, e.title.as_str())).collect();
+        assert_eq!(titles, [(1, "Intro"), (2, "Deeper part")]);
+        assert!(matches!(content.elements[toc[1].element_index], ContentElement::Heading(2, _)));
+    }
+
+    #[test]
+    fn test_scrolling_snaps_to_elements() {
+        let content = article();
+        let heights: Vec<u16> = content
+            .elements
+            .iter()
+            .map(|e| if matches!(e, ContentElement::EmptyLine) { 1 } else { 3 })
+            .collect();
+        let starts = element_starts(4, &heights);
+        assert_eq!(starts[0], 4);
+        assert_eq!(starts[1], 4 + heights[0]);
+
+        // From the top, the first stop is the first heading
+        assert_eq!(next_element_scroll(&content, &starts, 0), Some(3));
+        let second = next_element_scroll(&content, &starts, 3).unwrap();
+        assert_eq!(second, scroll_to_start(starts[1]));
+        assert_eq!(prev_element_scroll(&content, &starts, second), Some(3));
+        assert_eq!(prev_element_scroll(&content, &starts, 3), None);
+        // Part way into an element goes back to its start
+        assert_eq!(prev_element_scroll(&content, &starts, second + 1), Some(second));
+        let last = *starts.last().unwrap();
+        assert_eq!(next_element_scroll(&content, &starts, last), None);
+
+        let toc = table_of_contents(&content);
+        assert_eq!(current_entry(&toc, &starts, 0), None);
+        assert_eq!(current_entry(&toc, &starts, second), Some(0));
+        assert_eq!(current_entry(&toc, &starts, last), Some(1));
+    }
+}
@@ -0,0 +1,125 @@
+//! Summary-first triage: going through the article list one verdict at a time
+
+use uuid::Uuid;
+
+/// What to do with an article
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    /// Leave it unread to read later
+    Keep,
+    /// Save it and mark it read
+    Save,
+    /// Mark it read without reading
+    Dismiss,
+}
+
+impl Verdict {
+    pub fn label(self) -> &'static str {
+        match self {
+            Verdict::Keep => "kept",
+            Verdict::Save => "saved",
+            Verdict::Dismiss => "dismissed",
+        }
+    }
+}
+
+/// Action inside the triage view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriageAction {
+    MoveDown,
+    MoveUp,
+    Decide(Verdict),
+    /// Take back the last verdict
+    Undo,
+    /// Leave triage and read the selected article
+    Open,
+    Close,
+}
+
+/// A verdict with the article state it changed, so it can be undone
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Decision {
+    pub article_id: Uuid,
+    pub verdict: Verdict,
+    pub was_read: bool,
+    pub was_saved: bool,
+}
+
+/// Verdicts given in the current triage session, in order
+#[derive(Debug, Default)]
+pub struct Triage {
+    decisions: Vec<Decision>,
+}
+
+impl Triage {
+    /// Forget the verdicts of the previous session
+    pub fn start(&mut self) {
+        self.decisions.clear();
+    }
+
+    /// Record a verdict. A new verdict for the same article replaces the
+    /// earlier one, keeping the state from before either; the recorded
+    /// decision is returned.
+    pub fn record(&mut self, mut decision: Decision) -> Decision {
+        if let Some(pos) = self.decisions.iter().position(|d| d.article_id == decision.article_id) {
+            let earlier = self.decisions.remove(pos);
+            decision.was_read = earlier.was_read;
+            decision.was_saved = earlier.was_saved;
+        }
+        self.decisions.push(decision);
+        decision
+    }
+
+    /// Remove and return the last verdict
+    pub fn undo(&mut self) -> Option<Decision> {
+        self.decisions.pop()
+    }
+
+    pub fn verdict(&self, article_id: Uuid) -> Option<Verdict> {
+        self.decisions
+            .iter()
+            .find(|d| d.article_id == article_id)
+            .map(|d| d.verdict)
+    }
+
+    /// Number of articles kept, saved and dismissed
+    pub fn counts(&self) -> (usize, usize, usize) {
+        let count = |verdict| self.decisions.iter().filter(|d| d.verdict == verdict).count();
+        (count(Verdict::Keep), count(Verdict::Save), count(Verdict::Dismiss))
+    }
+
+    /// Summary like "3 kept, 1 saved, 12 dismissed"
+    pub fn summary(&self) -> String {
+        let (kept, saved, dismissed) = self.counts();
+        format!("{} kept, {} saved, {} dismissed", kept, saved, dismissed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_triage_verdicts_and_undo() {
+        let decision = |article_id, verdict| Decision {
+            article_id,
+            verdict,
+            was_read: false,
+            was_saved: false,
+        };
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut triage = Triage::default();
+        triage.record(decision(a, Verdict::Keep));
+        triage.record(decision(b, Verdict::Dismiss));
+        // Changing a verdict replaces it, but undoing restores the original state
+        let recorded = triage.record(Decision { was_read: true, ..decision(a, Verdict::Save) });
+        assert!(!recorded.was_read);
+        assert_eq!(triage.counts(), (0, 1, 1));
+        assert_eq!(triage.verdict(a), Some(Verdict::Save));
+
+        let undone = triage.undo().unwrap();
+        assert_eq!((undone.article_id, undone.was_read), (a, false));
+        assert_eq!(triage.verdict(a), None);
+        assert_eq!(triage.summary(), "0 kept, 0 saved, 1 dismissed");
+    }
+}
@@ -0,0 +1,310 @@
+//! Reading articles aloud with an external text-to-speech command
+
+use std::io::{self, Write};
+use std::ops::Range;
+use std::process::{Child, Command, Stdio};
+
+use uuid::Uuid;
+
+use crate::rich_content::ContentElement;
+
+/// One sentence of the article, spoken by one run of the TTS command
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sentence {
+    /// Content element the sentence is in; None for the title
+    pub element: Option<usize>,
+    /// Position of the sentence in the element's text, counted in
+    /// non-whitespace characters so it can be found again after wrapping
+    pub range: Range<usize>,
+    pub text: String,
+}
+
+/// Split text into sentences, each with its non-whitespace character range
+pub fn split_sentences(text: &str) -> Vec<(Range<usize>, String)> {
+    let mut sentences = Vec::new();
+    let mut current = String::new();
+    let mut start = 0;
+    let mut count = 0;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c.is_whitespace() {
+            if !current.is_empty() && !current.ends_with(' ') {
+                current.push(' ');
+            }
+            continue;
+        }
+        current.push(c);
+        count += 1;
+
+        let ends = match c {
+            // CJK full stops need no space after them
+            '。' | '！' | '？' => true,
+            '.' | '!' | '?' | '…' => chars
+                .peek()
+                .is_none_or(|&next| next.is_whitespace() || is_closing(next)),
+            _ => false,
+        };
+        if ends {
+            while let Some(&next) = chars.peek() {
+                if !is_closing(next) {
+                    break;
+                }
+                current.push(next);
+                count += 1;
+                chars.next();
+            }
+            sentences.push((start..count, current.trim_end().to_string()));
+            current.clear();
+            start = count;
+        }
+    }
+    if !current.trim().is_empty() {
+        sentences.push((start..count, current.trim_end().to_string()));
+    }
+    sentences
+}
+
+/// Closing quotes and brackets, kept with the sentence they end
+fn is_closing(c: char) -> bool {
+    matches!(c, '"' | '\'' | ')' | ']' | '”' | '’' | '」' | '）')
+}
+
+/// Sentences of an article in reading order: the title, then the text of
+/// paragraphs, headings, quotes, list items, definitions and footnotes
+/// (code blocks and formulas are skipped)
+pub fn article_sentences(title: &str, elements: &[ContentElement]) -> Vec<Sentence> {
+    let mut sentences: Vec<Sentence> = split_sentences(title)
+        .into_iter()
+        .map(|(range, text)| Sentence { element: None, range, text })
+        .collect();
+    for (index, element) in elements.iter().enumerate() {
+        let text = match element {
+            ContentElement::Text(text, _)
+            | ContentElement::Heading(_, text)
+            | ContentElement::Quote(text)
+            | ContentElement::ListItem(text)
+            | ContentElement::DefinitionTerm(text)
+            | ContentElement::Definition(text)
+            | ContentElement::Footnote { text, .. } => text,
+            _ => continue,
+        };
+        sentences.extend(
+            split_sentences(text)
+                .into_iter()
+                .map(|(range, text)| Sentence { element: Some(index), range, text }),
+        );
+    }
+    sentences
+}
+
+/// TTS command to use: `ui.tts_command`, then `say` on macOS, then
+/// `espeak-ng` or `espeak` if installed
+pub fn tts_command(configured: Option<&str>) -> Option<String> {
+    if let Some(command) = configured.filter(|c| !c.trim().is_empty()) {
+        return Some(command.to_string());
+    }
+    if cfg!(target_os = "macos") {
+        return Some("say".to_string());
+    }
+    ["espeak-ng", "espeak"]
+        .into_iter()
+        .find(|program| on_path(program))
+        .map(str::to_string)
+}
+
+fn on_path(program: &str) -> bool {
+    std::env::var_os("PATH")
+        .is_some_and(|path| std::env::split_paths(&path).any(|dir| dir.join(program).is_file()))
+}
+
+/// Reads one article aloud, a sentence at a time, so it can pause, skip and
+/// show which sentence is being spoken
+pub struct Speaker {
+    command: Option<String>,
+    article_id: Option<Uuid>,
+    sentences: Vec<Sentence>,
+    current: usize,
+    child: Option<Child>,
+    paused: bool,
+}
+
+impl Speaker {
+    pub fn new(configured: Option<&str>) -> Self {
+        Self {
+            command: tts_command(configured),
+            article_id: None,
+            sentences: Vec::new(),
+            current: 0,
+            child: None,
+            paused: false,
+        }
+    }
+
+    /// Whether an article is being read (possibly paused)
+    pub fn is_active(&self) -> bool {
+        self.article_id.is_some()
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Article being read
+    pub fn article_id(&self) -> Option<Uuid> {
+        self.article_id
+    }
+
+    /// Sentence being spoken, for highlighting
+    pub fn current(&self) -> Option<&Sentence> {
+        self.article_id.and(self.sentences.get(self.current))
+    }
+
+    /// Current sentence number (from 1) and the number of sentences
+    pub fn progress(&self) -> (usize, usize) {
+        (self.current + 1, self.sentences.len())
+    }
+
+    /// Start reading an article from its first sentence
+    pub fn start(&mut self, article_id: Uuid, sentences: Vec<Sentence>) -> io::Result<()> {
+        if self.command.is_none() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "no TTS command found; set ui.tts_command or install espeak-ng",
+            ));
+        }
+        self.stop();
+        if sentences.is_empty() {
+            return Ok(());
+        }
+        self.article_id = Some(article_id);
+        self.sentences = sentences;
+        self.speak_current()
+    }
+
+    /// Pause or resume. Resuming repeats the interrupted sentence.
+    pub fn toggle_pause(&mut self) -> io::Result<()> {
+        if self.paused {
+            self.speak_current()
+        } else {
+            self.kill();
+            self.paused = true;
+            Ok(())
+        }
+    }
+
+    /// Move `delta` sentences forward or back and speak from there
+    pub fn skip(&mut self, delta: isize) -> io::Result<()> {
+        if !self.is_active() {
+            return Ok(());
+        }
+        let last = self.sentences.len().saturating_sub(1);
+        self.current = self.current.saturating_add_signed(delta).min(last);
+        self.speak_current()
+    }
+
+    pub fn stop(&mut self) {
+        self.kill();
+        self.article_id = None;
+        self.sentences.clear();
+        self.current = 0;
+        self.paused = false;
+    }
+
+    /// Move on to the next sentence once the current one has been spoken.
+    /// Returns whether the spoken sentence changed (or reading finished).
+    pub fn poll(&mut self) -> io::Result<bool> {
+        let Some(child) = self.child.as_mut() else {
+            return Ok(false);
+        };
+        if child.try_wait()?.is_none() {
+            return Ok(false);
+        }
+        self.child = None;
+        if self.current + 1 < self.sentences.len() {
+            self.current += 1;
+            self.speak_current()?;
+        } else {
+            self.stop();
+        }
+        Ok(true)
+    }
+
+    fn speak_current(&mut self) -> io::Result<()> {
+        self.kill();
+        self.paused = false;
+        let (Some(command), Some(sentence)) = (&self.command, self.sentences.get(self.current)) else {
+            return Ok(());
+        };
+
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c")
+            .arg(command)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        // Own process group, so pausing also stops the rest of a pipeline
+        // like `piper | aplay`
+        #[cfg(unix)]
+        std::os::unix::process::CommandExt::process_group(&mut cmd, 0);
+        let mut child = cmd.spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            // Dropping stdin closes it, which ends the input
+            let _ = writeln!(stdin, "{}", sentence.text);
+        }
+        self.child = Some(child);
+        Ok(())
+    }
+
+    fn kill(&mut self) {
+        let Some(mut child) = self.child.take() else {
+            return;
+        };
+        #[cfg(unix)]
+        let _ = Command::new("kill")
+            .args(["-TERM", "--", &format!("-{}", child.id())])
+            .stderr(Stdio::null())
+            .status();
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+}
+
+impl Drop for Speaker {
+    fn drop(&mut self) {
+        self.kill();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_sentences() {
+        let sentences = split_sentences("Hello  world. Is it v1.2?\nYes!\"  Trailing words");
+        let texts: Vec<&str> = sentences.iter().map(|(_, text)| text.as_str()).collect();
+        assert_eq!(texts, ["Hello world.", "Is it v1.2?", "Yes!\"", "Trailing words"]);
+        let ranges: Vec<Range<usize>> = sentences.into_iter().map(|(range, _)| range).collect();
+        assert_eq!(ranges, [0..11, 11..20, 20..25, 25..38]);
+
+        let texts: Vec<String> = split_sentences("今天很好。明天呢？").into_iter().map(|(_, t)| t).collect();
+        assert_eq!(texts, ["今天很好。", "明天呢？"]);
+    }
+
+    #[test]
+    fn test_article_sentences_skip_code() {
+        let elements = vec![
+            ContentElement::Text("One. Two.".to_string(), Vec::new()),
+            ContentElement::Code("let x = 1;".to_string()),
+            ContentElement::ListItem("Three".to_string()),
+        ];
+        let sentences = article_sentences("Title", &elements);
+        let spoken: Vec<(Option<usize>, &str)> =
+            sentences.iter().map(|s| (s.element, s.text.as_str())).collect();
+        assert_eq!(
+            spoken,
+            [(None, "Title"), (Some(0), "One."), (Some(0), "Two."), (Some(2), "Three")]
+        );
+    }
+}
@@ -1,6 +1,7 @@
+use std::ops::Range;
 use std::sync::Arc;
 
-use image::{DynamicImage, GenericImageView};
+use image::DynamicImage;
 use ratatui::{
     layout::Rect,
     style::{Color, Modifier, Style},
@@ -8,12 +9,19 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
+use ratatui_image::StatefulImage;
 use unicode_width::UnicodeWidthStr;
 
-use crate::app::{App, Focus, RichArticleState};
+use kenseader_core::config::UiConfig;
+use kenseader_core::feed::{Article, ArticleMedia};
+use kenseader_core::storage::ArticleStyle;
+
+use crate::app::{App, Discussion, Focus, RichArticleState};
 use crate::image_renderer::RenderBackend;
-use crate::rich_content::{ContentElement, ImageState, parse_text_with_urls, ResizedImageCache, TextSpan};
+use crate::rich_content::{ContentElement, FocusableItem, ImageState, Inline, parse_text_with_urls, ResizedImageCache, TextSpan};
 use crate::theme::Theme;
+use crate::time_format::format_timestamp;
+use crate::tts::Sentence;
 
 /// Information about an image to render
 struct ImageRenderInfo {
@@ -27,6 +35,28 @@ struct ImageRenderInfo {
     image_index: usize,
 }
 
+/// An image in the viewport, for Kitty rendering
+struct VisibleImage {
+    url: String,
+    image: Arc<DynamicImage>,
+    x: u16,
+    /// Screen row of the first visible row
+    y: u16,
+    width: u16,
+    /// Height of the image's whole slot in the content
+    height: u16,
+    /// Rows of the slot that are on screen
+    visible: Range<u16>,
+}
+
+/// How to show the summary and AI metadata above the article
+#[derive(Clone, Copy)]
+struct AiHeader<'s> {
+    style: Option<&'s ArticleStyle>,
+    collapsed: bool,
+    summary_only: bool,
+}
+
 pub struct ArticleDetailWidget;
 
 impl ArticleDetailWidget {
@@ -40,49 +70,61 @@ impl ArticleDetailWidget {
             Style::default().fg(theme.grey0)
         };
 
-        let block = Block::default()
-            .title(" Article ")
+        let mut block = Block::default()
+            .title(super::pane_title(" Article ".to_string(), is_focused, app))
             .borders(Borders::ALL)
             .border_style(border_style)
             .style(Style::default().bg(theme.bg0));
-
         let inner_area = block.inner(area);
-        frame.render_widget(block, area);
+        // Reader mode: the text column, centered when narrower than the pane
+        let content_area = reader_area(inner_area, app.config.ui.reader.max_width);
 
         // Get UI config options
-        let show_author = app.config.ui.show_author;
-        let show_timestamps = app.config.ui.show_timestamps;
-
-        // Check which backend to use for images
-        let backend = app.image_renderer.backend();
-        // Use external rendering for Ueberzug and Kitty (both need image_infos populated)
-        let use_overlay = matches!(backend, RenderBackend::Ueberzug | RenderBackend::Kitty);
+        let ui_config = &app.config.ui;
+        // Key loading the images, when they are held back for this article
+        let load_key = (!app.images_enabled() && !ui_config.screen_reader)
+            .then(|| app.config.keymap.load_images.clone());
 
-        // Track image positions for protocol rendering
+        // Track the slots of loaded images, placed over the text afterwards
         let mut image_infos: Vec<ImageRenderInfo> = Vec::new();
 
-        let content = if let Some(article) = app.current_article().cloned() {
+        let mut content = if let Some(article) = app.current_article().cloned() {
+            // Sentence being read aloud, if it is of this article
+            let spoken = app
+                .speaker
+                .current()
+                .filter(|_| app.speaker.article_id() == Some(article.id))
+                .cloned();
+            let style = app
+                .article_style
+                .as_ref()
+                .filter(|(id, _)| *id == article.id)
+                .and_then(|(_, style)| style.as_ref());
+            let ai_header = AiHeader {
+                style,
+                collapsed: app.ai_header_collapsed,
+                summary_only: app.summary_only,
+            };
             // Build content with rich rendering if available
             if let Some(ref mut rich_state) = app.rich_state {
-                // Recalculate heights if needed
-                if rich_state.element_heights.is_empty()
-                    || rich_state.viewport_height != inner_area.height
-                {
-                    rich_state.viewport_height = inner_area.height;
-                    rich_state.calculate_heights(inner_area.width.saturating_sub(2));
+                rich_state.viewport_height = inner_area.height;
+                // Estimate the layout until the article has been rendered
+                if rich_state.element_heights.is_empty() {
+                    rich_state.calculate_heights(content_area.width.saturating_sub(2));
                 }
                 Self::render_rich_content(
                     &article,
                     rich_state,
-                    inner_area.width.saturating_sub(2),
-                    show_author,
-                    show_timestamps,
-                    use_overlay,
+                    content_area.width.saturating_sub(2),
+                    ui_config,
+                    load_key.as_deref(),
                     &mut image_infos,
+                    spoken.as_ref(),
+                    ai_header,
                     theme,
                 )
             } else {
-                Self::render_plain_content(&article, show_author, show_timestamps, theme)
+                Self::render_plain_content(&article, ui_config, ai_header, theme)
             }
         } else {
             Text::from(Line::from(Span::styled(
@@ -91,30 +133,65 @@ impl ArticleDetailWidget {
             )))
         };
 
+        // Discussion section after the article, counted in the scroll range
+        let discussion = app
+            .current_discussion()
+            .map(|d| render_discussion(d, content_area.width.saturating_sub(2) as usize, &app.theme));
+        if let (Some(lines), Some(state)) = (discussion, app.discussion.as_mut()) {
+            state.height = lines.len() as u16;
+            content.lines.extend(lines);
+        }
+
+        // Clamp the scroll against the height just laid out, e.g. after a
+        // jump to the bottom or when the pane grew
+        let max_scroll = app.max_detail_scroll();
+        if app.detail_scroll > max_scroll {
+            app.detail_scroll = max_scroll;
+            app.scroll_animator.set_scroll(max_scroll);
+        }
+
+        // How far into the article the view is
+        let show_position = app.config.ui.show_scrollbar && max_scroll > 0 && app.current_article().is_some();
+        if show_position {
+            let position = super::scroll_position(app.detail_scroll, max_scroll);
+            block = block.title_bottom(Line::from(format!(" {} ", position)).right_aligned());
+        }
+        frame.render_widget(block, area);
+
         // Don't use Paragraph's wrap - we handle wrapping manually to ensure
         // accurate line counting for image positioning
         let paragraph = Paragraph::new(content)
             .scroll((app.detail_scroll, 0));
 
-        frame.render_widget(paragraph, inner_area);
+        frame.render_widget(paragraph, content_area);
+        if show_position {
+            super::render_scrollbar(
+                frame,
+                area,
+                max_scroll as usize,
+                inner_area.height as usize,
+                app.detail_scroll as usize,
+                &app.theme,
+            );
+        }
 
-        // Render images using the appropriate backend
+        // Place the images in their slots with the renderer's backend
+        let backend = app.image_renderer.backend();
         if !image_infos.is_empty() {
             match backend {
                 RenderBackend::Ueberzug => {
                     // Render using Üeberzug++ overlay
-                    Self::render_ueberzug_images(inner_area, app, &image_infos);
+                    Self::render_ueberzug_images(content_area, app, &image_infos);
                 }
                 RenderBackend::Kitty => {
                     // Render using Kitty graphics protocol
-                    Self::render_kitty_images(frame, inner_area, app, &image_infos);
+                    Self::render_kitty_images(frame, content_area, app, &image_infos);
                 }
                 RenderBackend::ITerm2 | RenderBackend::Sixel => {
-                    // Render using halfblocks for native protocols (as fallback)
-                    Self::render_protocol_images(frame, inner_area, app, &image_infos);
+                    Self::render_protocol_images(frame, content_area, app, &image_infos, true);
                 }
                 RenderBackend::Halfblocks => {
-                    // Halfblocks are rendered inline via the Paragraph
+                    Self::render_protocol_images(frame, content_area, app, &image_infos, false);
                 }
             }
         } else {
@@ -138,9 +215,10 @@ impl ArticleDetailWidget {
             return;
         };
 
-        // Collect visible images and their render info
+        // Collect visible images and their render info, with the rows of
+        // each image's slot that are on screen
         // Uses Arc<DynamicImage> to avoid expensive deep cloning
-        let mut visible_images: Vec<(String, Arc<DynamicImage>, u16, u16, u16, u16)> = Vec::new();
+        let mut visible_images: Vec<VisibleImage> = Vec::new();
 
         for img_info in images {
             // Calculate if image is visible in viewport
@@ -180,30 +258,32 @@ impl ArticleDetailWidget {
                 continue;
             }
 
-            visible_images.push((
-                img_info.url.clone(),
+            visible_images.push(VisibleImage {
+                url: img_info.url.clone(),
                 image,
-                area.x,
-                render_y,
-                area.width,
-                render_height,
-            ));
+                x: area.x,
+                y: render_y,
+                width: area.width,
+                height: img_info.height,
+                visible: visible_top..visible_top + render_height,
+            });
         }
 
         // Collect active URLs for cleanup
-        let active_urls: Vec<String> = visible_images.iter().map(|(url, _, _, _, _, _)| url.clone()).collect();
+        let active_urls: Vec<String> = visible_images.iter().map(|v| v.url.clone()).collect();
 
-        // Render each visible image using state-aware API
+        // Render each visible image using state-aware API; scrolling only
+        // moves and crops the placement of an already sent image
         // Collect failed images for fallback rendering (uses Arc for efficiency)
         let mut failed_images: Vec<(String, Arc<DynamicImage>, u16, u16, u16, u16)> = Vec::new();
 
         if let Some(ref mut kitty) = app.image_renderer.kitty_renderer() {
-            for (url, image, x, y, width, height) in visible_images {
-                // Dereference Arc to get &DynamicImage for kitty API
-                if let Err(e) = kitty.display_or_update(&url, &*image, x, y, width, height) {
+            for VisibleImage { url, image, x, y, width, height, visible } in visible_images {
+                let render_height = visible.len() as u16;
+                if let Err(e) = kitty.display_clipped(&url, &image, x, y, (width, height), visible) {
                     tracing::error!("Failed to display image via Kitty: {}", e);
                     // Collect for fallback rendering after releasing kitty borrow
-                    failed_images.push((url, image, x, y, width, height));
+                    failed_images.push((url, image, x, y, width, render_height));
                 }
             }
 
@@ -306,12 +386,15 @@ impl ArticleDetailWidget {
         // Note: This is a simplified approach; a more robust implementation would track all active identifiers
     }
 
-    /// Render images using halfblock characters with proper positioning
+    /// Render images in their slots: with the terminal's graphics protocol
+    /// (`native`, iTerm2 or Sixel) while the whole image is on screen, else
+    /// with halfblock characters
     fn render_protocol_images(
         frame: &mut Frame,
         area: Rect,
         app: &mut App,
         images: &[ImageRenderInfo],
+        native: bool,
     ) {
         let scroll = app.detail_scroll;
         let Some(ref mut rich_state) = app.rich_state else {
@@ -327,6 +410,7 @@ impl ArticleDetailWidget {
             render_area: Rect,
             is_focused: bool,
             image_area: Rect,
+            fully_visible: bool,
         }
         let mut render_items: Vec<RenderItem> = Vec::new();
 
@@ -393,6 +477,7 @@ impl ArticleDetailWidget {
                     render_area,
                     is_focused,
                     image_area,
+                    fully_visible: visible_top == 0 && render_height == img_info.height,
                 });
             }
         }
@@ -407,6 +492,16 @@ impl ArticleDetailWidget {
                 frame.render_widget(border, item.image_area);
             }
 
+            // A protocol image can't be cut, so it only shows once the
+            // whole slot is on screen
+            if native && item.fully_visible {
+                if let Some(cached) = rich_state.image_cache.get_mut(&item.url) {
+                    let protocol = cached.protocol_for(item.render_area.width, item.render_area.height);
+                    frame.render_stateful_widget(StatefulImage::new(None), item.render_area, protocol);
+                    continue;
+                }
+            }
+
             // Use halfblock rendering with resize cache
             // Dereference Arc to get &DynamicImage
             Self::render_halfblocks_at_position(
@@ -488,15 +583,17 @@ impl ArticleDetailWidget {
         article: &kenseader_core::feed::Article,
         rich_state: &mut RichArticleState,
         width: u16,
-        show_author: bool,
-        show_timestamps: bool,
-        use_overlay: bool,
+        ui_config: &UiConfig,
+        load_key: Option<&str>,
         image_infos: &mut Vec<ImageRenderInfo>,
+        spoken: Option<&Sentence>,
+        ai_header: AiHeader,
         theme: &Theme,
     ) -> Text<'a> {
         let mut lines: Vec<Line<'a>> = Vec::new();
         let mut current_y: u16 = 0;
         let mut image_index: usize = 0;
+        let spoken_style = Style::default().bg(theme.selection);
 
         // Title
         lines.push(Line::from(Span::styled(
@@ -505,13 +602,16 @@ impl ArticleDetailWidget {
                 .fg(theme.fg1)
                 .add_modifier(Modifier::BOLD),
         )));
+        if let Some(sentence) = spoken.filter(|s| s.element.is_none()) {
+            highlight_range(&mut lines, &sentence.range, 0, spoken_style);
+        }
         current_y += 1;
         lines.push(Line::from(""));
         current_y += 1;
 
         // Metadata (controlled by show_author and show_timestamps config)
         let mut meta_spans = Vec::new();
-        if show_author {
+        if ui_config.show_author {
             if let Some(author) = &article.author {
                 meta_spans.push(Span::styled(
                     format!("By {} ", author),
@@ -519,14 +619,25 @@ impl ArticleDetailWidget {
                 ));
             }
         }
-        if show_timestamps {
+        if ui_config.show_timestamps {
             if let Some(date) = &article.published_at {
                 let separator = if meta_spans.is_empty() { "" } else { "| " };
                 meta_spans.push(Span::styled(
-                    format!("{}{}", separator, date.format("%Y-%m-%d %H:%M")),
+                    format!("{}{}", separator, format_timestamp(date, ui_config)),
                     Style::default().fg(theme.grey1),
                 ));
             }
+            if let Some(updated) = &article.updated_at {
+                let separator = match (meta_spans.is_empty(), article.published_at.is_some()) {
+                    (true, _) => "",
+                    (false, true) => " | ",
+                    (false, false) => "| ",
+                };
+                meta_spans.push(Span::styled(
+                    format!("{}Updated {}", separator, format_timestamp(updated, ui_config)),
+                    Style::default().fg(theme.blue),
+                ));
+            }
         }
         if !meta_spans.is_empty() {
             lines.push(Line::from(meta_spans));
@@ -535,25 +646,84 @@ impl ArticleDetailWidget {
             current_y += 1;
         }
 
-        // Summary (if available) - render with box border
-        if let Some(summary) = &article.summary {
-            let summary_lines = render_summary_box(summary, width as usize, theme);
-            let summary_height = summary_lines.len() as u16;
-            lines.extend(summary_lines);
-            current_y += summary_height;
+        // Media attachment (podcast/video)
+        if let Some(ref media) = article.media {
+            lines.push(Line::from(Span::styled(
+                format_media_info(media),
+                Style::default().fg(theme.aqua),
+            )));
+            current_y += 1;
             lines.push(Line::from(""));
             current_y += 1;
         }
 
+        // Summary, tags, relevance and style
+        let header_lines = render_ai_header(article, ai_header, width as usize, theme);
+        current_y += header_lines.len() as u16;
+        lines.extend(header_lines);
+
         // Render each content element with proper text wrapping
         let wrap_width = width as usize;
 
         // Get focused link info for highlighting
         let focused_link_url = rich_state.focused_link_url().map(|s| s.to_string());
+        let focused_item = rich_state.focused_item;
+        let footnote_refs: Vec<(usize, usize, Range<usize>)> = rich_state
+            .content
+            .focusable_items
+            .iter()
+            .enumerate()
+            .filter_map(|(index, item)| match item {
+                FocusableItem::FootnoteRef { element_index, range, .. } => {
+                    Some((index, *element_index, range.clone()))
+                }
+                _ => None,
+            })
+            .collect();
+        let focused_footnote = match rich_state.get_focused_item() {
+            Some(FocusableItem::Footnote { element_index, .. }) => Some(*element_index),
+            _ => None,
+        };
+        rich_state.element_offsets.clear();
 
-        for (_elem_idx, element) in rich_state.content.elements.clone().into_iter().enumerate() {
+        // Summary-only reading skips the body
+        let elements = if shows_summary_only(article, ai_header) {
+            Vec::new()
+        } else {
+            rich_state.content.elements.clone()
+        };
+        for (elem_idx, element) in elements.into_iter().enumerate() {
+            let element_start = lines.len();
+            rich_state.element_offsets.push(element_start as u16);
+            // Quote bars, list bullets and footnote numbers are not part of
+            // the spoken text
+            let prefix_spans = match element {
+                ContentElement::Quote(_)
+                | ContentElement::ListItem(_)
+                | ContentElement::Definition(_)
+                | ContentElement::Footnote { .. } => 1,
+                ContentElement::Heading(..) | ContentElement::DefinitionTerm(_) if ui_config.screen_reader => 1,
+                _ => 0,
+            };
+            // Screen readers get elements named in words, and no images
+            if ui_config.screen_reader && !matches!(element, ContentElement::Text(..) | ContentElement::EmptyLine) {
+                let is_image_focused = matches!(element, ContentElement::Image { .. })
+                    && rich_state.focused_image_index() == Some(image_index);
+                if matches!(element, ContentElement::Image { .. }) {
+                    image_index += 1;
+                }
+                let is_focused = is_image_focused || focused_footnote == Some(elem_idx);
+                let element_lines = render_linear_element(&element, wrap_width, is_focused, theme);
+                current_y += element_lines.len() as u16;
+                lines.extend(element_lines);
+                highlight_footnote_refs(&mut lines[element_start..], &footnote_refs, elem_idx, focused_item, prefix_spans, theme);
+                if let Some(sentence) = spoken.filter(|s| s.element == Some(elem_idx)) {
+                    highlight_range(&mut lines[element_start..], &sentence.range, prefix_spans, spoken_style);
+                }
+                continue;
+            }
             match element {
-                ContentElement::Text(text) => {
+                ContentElement::Text(text, inline) => {
                     // Parse text for URLs and render with link styling
                     let spans = parse_text_with_urls(&text);
                     let has_urls = spans.iter().any(|s| s.link_url.is_some());
@@ -573,7 +743,14 @@ impl ArticleDetailWidget {
                     } else {
                         // No URLs, render as plain text
                         let wrapped = wrap_text_unicode(&text, wrap_width);
-                        for line in wrapped {
+                        let last = wrapped.len().saturating_sub(1);
+                        for (i, line) in wrapped.into_iter().enumerate() {
+                            // The last line of a justified paragraph stays ragged
+                            let line = if ui_config.reader.justify && i < last {
+                                justify_line(&line, wrap_width)
+                            } else {
+                                line
+                            };
                             lines.push(Line::from(Span::styled(
                                 line,
                                 Style::default().fg(theme.fg0),
@@ -581,6 +758,10 @@ impl ArticleDetailWidget {
                             current_y += 1;
                         }
                     }
+                    // Bold, italic, code and link text inside the paragraph
+                    for style in &inline {
+                        highlight_range(&mut lines[element_start..], &style.range, 0, inline_style(style.kind, theme));
+                    }
                 }
                 ContentElement::Heading(level, text) => {
                     let style = match level {
@@ -603,14 +784,20 @@ impl ArticleDetailWidget {
                     lines.push(Line::from(""));
                     current_y += 1;
                 }
-                ContentElement::Image { ref url, ref alt } => {
+                ContentElement::Image { ref url, ref alt, ref caption } => {
                     let image_height = rich_state.image_height;
 
-                    // Check if image is loaded and we're using overlay (Üeberzug/Kitty)
                     let is_loaded = rich_state.image_cache.is_ready(url);
 
-                    if use_overlay && is_loaded {
-                        // Record image position for overlay rendering
+                    // Placeholders already show the alt text
+                    let caption = if is_loaded { element.image_caption() } else { caption.as_deref() };
+                    let caption_lines = caption
+                        .map(|caption| render_caption(caption, wrap_width, theme))
+                        .unwrap_or_default();
+
+                    if is_loaded {
+                        // Reserve the image's slot; the backend draws the
+                        // image over it once the text is rendered
                         image_infos.push(ImageRenderInfo {
                             url: url.clone(),
                             content_y: current_y,
@@ -618,31 +805,45 @@ impl ArticleDetailWidget {
                             image_index,
                         });
 
-                        // Add placeholder empty lines (will be overlaid by image)
                         for _ in 0..image_height {
                             lines.push(Line::from(""));
                         }
                         current_y += image_height;
 
+                        current_y += caption_lines.len() as u16;
+                        lines.extend(caption_lines);
+
                         // Add empty line after image
                         lines.push(Line::from(""));
                         current_y += 1;
                     } else {
-                        // Fallback: render using half-block characters or show status
-                        // Get focused index before borrowing image_cache mutably
+                        // Show the image's status until it is loaded
                         let is_image_focused = rich_state.focused_image_index() == Some(image_index);
-                        let image_lines = Self::render_image_element(
-                            url,
-                            alt.as_deref(),
-                            &mut rich_state.image_cache,
-                            width as u32,
-                            image_height as u32,
-                            is_image_focused,
-                            theme,
-                        );
+                        let image_lines = match load_key {
+                            Some(key) if rich_state.image_cache.get(url).is_none() => {
+                                render_image_placeholder(url, alt.as_deref(), key, is_image_focused, theme)
+                            }
+                            _ => render_image_status(
+                                url,
+                                alt.as_deref(),
+                                rich_state.image_cache.images.get(url),
+                                is_image_focused,
+                                theme,
+                            ),
+                        };
                         let line_count = image_lines.len() as u16;
                         lines.extend(image_lines);
                         current_y += line_count;
+                        if !caption_lines.is_empty() {
+                            // The placeholder's blank line goes after the caption
+                            if lines.last().is_some_and(|line| line.width() == 0) {
+                                lines.pop();
+                                current_y -= 1;
+                            }
+                            current_y += caption_lines.len() as u16 + 1;
+                            lines.extend(caption_lines);
+                            lines.push(Line::from(""));
+                        }
                     }
                     image_index += 1;
                 }
@@ -708,6 +909,62 @@ impl ArticleDetailWidget {
                         current_y += 1;
                     }
                 }
+                ContentElement::Math(text) => {
+                    // Displayed formulas are indented, one line per row of the formula
+                    let style = Style::default().fg(theme.purple);
+                    for line in wrap_text_unicode(&text, wrap_width.saturating_sub(4)) {
+                        lines.push(Line::from(vec![Span::raw("    "), Span::styled(line, style)]));
+                        current_y += 1;
+                    }
+                    lines.push(Line::from(""));
+                    current_y += 1;
+                }
+                ContentElement::DefinitionTerm(text) => {
+                    let style = Style::default().fg(theme.fg1).add_modifier(Modifier::BOLD);
+                    for line in wrap_text_unicode(&text, wrap_width) {
+                        lines.push(Line::from(Span::styled(line, style)));
+                        current_y += 1;
+                    }
+                }
+                ContentElement::Definition(text) => {
+                    // Definitions are indented under their term
+                    let wrapped = wrap_text_unicode(&text, wrap_width.saturating_sub(4));
+                    for line in wrapped {
+                        lines.push(Line::from(vec![
+                            Span::raw("    "),
+                            Span::styled(line, Style::default().fg(theme.fg0)),
+                        ]));
+                        current_y += 1;
+                    }
+                }
+                ContentElement::Footnote { label, text } => {
+                    // "[1] " before the first line, continuation lines indented
+                    let number = format!("[{}] ", label);
+                    let number_style = if focused_footnote == Some(elem_idx) {
+                        Style::default()
+                            .fg(theme.blue)
+                            .bg(theme.yellow)
+                            .add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default().fg(theme.aqua)
+                    };
+                    let rendered_lines = render_text_with_links(
+                        &parse_text_with_urls(&text),
+                        wrap_width.saturating_sub(number.width()),
+                        focused_link_url.as_deref(),
+                        theme,
+                    );
+                    for (i, mut line) in rendered_lines.into_iter().enumerate() {
+                        let prefix = if i == 0 {
+                            Span::styled(number.clone(), number_style)
+                        } else {
+                            Span::raw(" ".repeat(number.width()))
+                        };
+                        line.spans.insert(0, prefix);
+                        lines.push(line);
+                        current_y += 1;
+                    }
+                }
                 ContentElement::Separator => {
                     lines.push(Line::from(Span::styled(
                         "─".repeat(40.min(width as usize)),
@@ -716,30 +973,41 @@ impl ArticleDetailWidget {
                     current_y += 1;
                 }
                 ContentElement::EmptyLine => {
-                    lines.push(Line::from(""));
-                    current_y += 1;
+                    for _ in 0..ui_config.reader.paragraph_spacing {
+                        lines.push(Line::from(""));
+                        current_y += 1;
+                    }
                 }
             }
+            highlight_footnote_refs(&mut lines[element_start..], &footnote_refs, elem_idx, focused_item, prefix_spans, theme);
+            if let Some(sentence) = spoken.filter(|s| s.element == Some(elem_idx)) {
+                highlight_range(&mut lines[element_start..], &sentence.range, prefix_spans, spoken_style);
+            }
         }
 
-        // Tags
-        if !article.tags.is_empty() {
-            lines.push(Line::from(""));
-            let tags_str = article.tags.join(" | ");
-            lines.push(Line::from(Span::styled(
-                format!("Tags: {}", tags_str),
-                Style::default().fg(theme.purple),
-            )));
+        // Keep the heights just rendered, so image loading and scrolling use
+        // the real layout rather than the estimate
+        if !rich_state.element_offsets.is_empty() {
+            let ends = rich_state.element_offsets.iter().skip(1).copied().chain([lines.len() as u16]);
+            rich_state.element_heights = rich_state
+                .element_offsets
+                .iter()
+                .zip(ends)
+                .map(|(start, end)| end - start)
+                .collect();
         }
 
         // URL hint and image navigation hint
-        if article.url.is_some() || !rich_state.content.image_urls.is_empty() {
+        let has_images = !rich_state.content.image_urls.is_empty() && !shows_summary_only(article, ai_header);
+        if article.url.is_some() || has_images {
             lines.push(Line::from(""));
             let mut hints = Vec::new();
             if article.url.is_some() {
                 hints.push("'b' open in browser");
             }
-            if !rich_state.content.image_urls.is_empty() {
+            let load_hint = load_key.map(|key| format!("'{}' load images", key));
+            if has_images {
+                hints.extend(load_hint.as_deref());
                 hints.push("Tab/Shift+Tab navigate images");
                 hints.push("'o' open image");
                 hints.push("Enter fullscreen");
@@ -749,90 +1017,16 @@ impl ArticleDetailWidget {
                 Style::default().fg(theme.grey1),
             )));
         }
+        rich_state.total_height = lines.len() as u16;
 
         Text::from(lines)
     }
 
-    /// Render a single image element (fallback halfblock rendering or status)
-    fn render_image_element<'a>(
-        url: &str,
-        alt: Option<&str>,
-        image_cache: &mut crate::rich_content::ArticleImageCache,
-        width: u32,
-        height: u32,
-        is_focused: bool,
-        theme: &Theme,
-    ) -> Vec<Line<'a>> {
-        let focus_style = if is_focused {
-            Style::default().fg(theme.yellow)
-        } else {
-            Style::default().fg(theme.grey1)
-        };
-
-        match image_cache.images.get_mut(url) {
-            Some(ImageState::Loaded(cached)) => {
-                // Render the image using half-block characters
-                let mut lines = Self::render_image_to_lines(&cached.image, width, height);
-                // Add focus indicator if focused
-                if is_focused {
-                    lines.insert(
-                        0,
-                        Line::from(Span::styled(
-                            format!("┌{:─^width$}┐", " Image (focused) ", width = width as usize - 2),
-                            Style::default().fg(theme.yellow),
-                        )),
-                    );
-                }
-                lines
-            }
-            Some(ImageState::Loading) => {
-                let prefix = if is_focused { "▶ " } else { "" };
-                vec![
-                    Line::from(Span::styled(
-                        format!("{}[Loading image: {}]", prefix, truncate_url(url, 40)),
-                        focus_style,
-                    )),
-                    Line::from(""),
-                ]
-            }
-            Some(ImageState::Failed(err)) => {
-                let prefix = if is_focused { "▶ " } else { "" };
-                let display = if let Some(alt_text) = alt {
-                    format!("{}[{}]", prefix, alt_text)
-                } else {
-                    format!("{}[Image failed: {}]", prefix, err)
-                };
-                vec![
-                    Line::from(Span::styled(
-                        display,
-                        if is_focused {
-                            Style::default().fg(theme.yellow)
-                        } else {
-                            Style::default().fg(theme.red)
-                        },
-                    )),
-                    Line::from(""),
-                ]
-            }
-            None => {
-                // Image not yet queued for loading
-                let prefix = if is_focused { "▶ " } else { "" };
-                vec![
-                    Line::from(Span::styled(
-                        format!("{}[Image: {}]", prefix, truncate_url(url, 50)),
-                        focus_style,
-                    )),
-                    Line::from(""),
-                ]
-            }
-        }
-    }
-
     /// Fallback: render plain text content (when RichArticleState is not available)
     fn render_plain_content<'a>(
         article: &kenseader_core::feed::Article,
-        show_author: bool,
-        show_timestamps: bool,
+        ui_config: &UiConfig,
+        ai_header: AiHeader,
         theme: &Theme,
     ) -> Text<'a> {
         let mut lines = Vec::new();
@@ -848,7 +1042,7 @@ impl ArticleDetailWidget {
 
         // Metadata (controlled by show_author and show_timestamps config)
         let mut meta_spans = Vec::new();
-        if show_author {
+        if ui_config.show_author {
             if let Some(author) = &article.author {
                 meta_spans.push(Span::styled(
                     format!("By {} ", author),
@@ -856,29 +1050,45 @@ impl ArticleDetailWidget {
                 ));
             }
         }
-        if show_timestamps {
+        if ui_config.show_timestamps {
             if let Some(date) = &article.published_at {
                 let separator = if meta_spans.is_empty() { "" } else { "| " };
                 meta_spans.push(Span::styled(
-                    format!("{}{}", separator, date.format("%Y-%m-%d %H:%M")),
+                    format!("{}{}", separator, format_timestamp(date, ui_config)),
                     Style::default().fg(theme.grey1),
                 ));
             }
+            if let Some(updated) = &article.updated_at {
+                let separator = match (meta_spans.is_empty(), article.published_at.is_some()) {
+                    (true, _) => "",
+                    (false, true) => " | ",
+                    (false, false) => "| ",
+                };
+                meta_spans.push(Span::styled(
+                    format!("{}Updated {}", separator, format_timestamp(updated, ui_config)),
+                    Style::default().fg(theme.blue),
+                ));
+            }
         }
         if !meta_spans.is_empty() {
             lines.push(Line::from(meta_spans));
             lines.push(Line::from(""));
         }
 
-        // Summary (if available) - render with box border
-        if let Some(summary) = &article.summary {
-            let summary_lines = render_summary_box(summary, 70, theme); // Fixed width for plain content
-            lines.extend(summary_lines);
+        // Media attachment (podcast/video)
+        if let Some(ref media) = article.media {
+            lines.push(Line::from(Span::styled(
+                format_media_info(media),
+                Style::default().fg(theme.aqua),
+            )));
             lines.push(Line::from(""));
         }
 
+        // Summary, tags, relevance and style
+        lines.extend(render_ai_header(article, ai_header, 70, theme)); // Fixed width for plain content
+
         // Content
-        if let Some(content_text) = &article.content_text {
+        if let Some(content_text) = article.content_text.as_ref().filter(|_| !shows_summary_only(article, ai_header)) {
             for line in content_text.lines() {
                 lines.push(Line::from(Span::styled(
                     line.to_string(),
@@ -887,16 +1097,6 @@ impl ArticleDetailWidget {
             }
         }
 
-        // Tags
-        if !article.tags.is_empty() {
-            lines.push(Line::from(""));
-            let tags_str = article.tags.join(" | ");
-            lines.push(Line::from(Span::styled(
-                format!("Tags: {}", tags_str),
-                Style::default().fg(theme.purple),
-            )));
-        }
-
         // URL hint
         if article.url.is_some() {
             lines.push(Line::from(""));
@@ -908,77 +1108,242 @@ impl ArticleDetailWidget {
 
         Text::from(lines)
     }
+}
 
-    /// Render an image to terminal lines using half-block characters
-    /// Uses ▀ (upper half block) with fg=top pixel, bg=bottom pixel
-    /// This works in any terminal with true color support
-    fn render_image_to_lines<'a>(
-        img: &DynamicImage,
-        target_width: u32,
-        target_height: u32,
-    ) -> Vec<Line<'a>> {
-        // Each character cell represents 2 vertical pixels
-        let char_height = target_height * 2;
-
-        // Calculate aspect-ratio preserving dimensions
-        let (img_width, img_height) = img.dimensions();
-        let scale_w = target_width as f32 / img_width as f32;
-        let scale_h = char_height as f32 / img_height as f32;
-        let scale = scale_w.min(scale_h);
-
-        let new_width = ((img_width as f32 * scale) as u32).max(1);
-        let new_height = ((img_height as f32 * scale) as u32).max(1);
-
-        // Resize image using fast nearest-neighbor for performance
-        let resized = img.resize_exact(
-            new_width,
-            new_height,
-            image::imageops::FilterType::Nearest,
-        );
-        let rgba = resized.to_rgba8();
-
-        // Center the image horizontally
-        let x_offset = (target_width.saturating_sub(new_width)) / 2;
-        let padding = " ".repeat(x_offset as usize);
-
-        let mut lines = Vec::with_capacity((new_height / 2 + 1) as usize);
-
-        // Process 2 rows at a time (top pixel = fg, bottom pixel = bg)
-        for y in (0..new_height).step_by(2) {
-            let mut spans: Vec<Span<'a>> = Vec::with_capacity(new_width as usize + 1);
-
-            // Add left padding for centering
-            if x_offset > 0 {
-                spans.push(Span::raw(padding.clone()));
-            }
+/// Format a media attachment as a one-line description
+/// e.g. "♪ Audio · audio/mpeg · 1:02:03 · 12.3 MB"
+fn format_media_info(media: &ArticleMedia) -> String {
+    let kind = if media.is_video() {
+        "▶ Video"
+    } else if media.is_audio() {
+        "♪ Audio"
+    } else {
+        "📎 Attachment"
+    };
 
-            for x in 0..new_width {
-                let top_pixel = rgba.get_pixel(x, y);
-                let bottom_pixel = if y + 1 < new_height {
-                    rgba.get_pixel(x, y + 1)
-                } else {
-                    top_pixel // Use top pixel if no bottom pixel
-                };
+    let mut parts = vec![kind.to_string()];
+    if let Some(ref mime) = media.mime_type {
+        parts.push(mime.clone());
+    }
+    if let Some(secs) = media.duration_secs {
+        let (h, m, s) = (secs / 3600, (secs % 3600) / 60, secs % 60);
+        parts.push(if h > 0 {
+            format!("{}:{:02}:{:02}", h, m, s)
+        } else {
+            format!("{}:{:02}", m, s)
+        });
+    }
+    if let Some(bytes) = media.length {
+        parts.push(format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0)));
+    }
 
-                let top_color = Color::Rgb(top_pixel[0], top_pixel[1], top_pixel[2]);
-                let bottom_color = Color::Rgb(bottom_pixel[0], bottom_pixel[1], bottom_pixel[2]);
+    parts.join(" · ")
+}
 
-                // Use upper half block: ▀
-                // Foreground = top pixel, Background = bottom pixel
-                spans.push(Span::styled(
-                    "▀",
-                    Style::default().fg(top_color).bg(bottom_color),
-                ));
-            }
+/// Centered column of at most `max_width` columns (plus the right margin
+/// kept by the text wrapping) within `area`; 0 keeps the whole area
+fn reader_area(area: Rect, max_width: u16) -> Rect {
+    let width = max_width.saturating_add(2);
+    if max_width == 0 || area.width <= width {
+        return area;
+    }
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        width,
+        ..area
+    }
+}
 
-            lines.push(Line::from(spans));
+/// Spread the words of a wrapped line to fill `width` columns, adding the
+/// extra spaces to the leftmost gaps first
+fn justify_line(line: &str, width: usize) -> String {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let text_width: usize = words.iter().map(|word| word.width()).sum();
+    let gaps = words.len().saturating_sub(1);
+    if gaps == 0 || text_width + gaps >= width {
+        return line.to_string();
+    }
+    let spaces = width - text_width;
+    let mut justified = String::with_capacity(line.len() + spaces);
+    for (i, word) in words.iter().enumerate() {
+        if i > 0 {
+            let gap = spaces / gaps + usize::from(i - 1 < spaces % gaps);
+            justified.push_str(&" ".repeat(gap));
         }
+        justified.push_str(word);
+    }
+    justified
+}
 
-        // Add empty line after image
-        lines.push(Line::from(""));
+/// Screen-reader rendering of a non-text element: the kind of element is
+/// named in a prefix instead of shown by colors, bars or bullets
+fn render_linear_element<'a>(
+    element: &ContentElement,
+    max_width: usize,
+    focused: bool,
+    theme: &Theme,
+) -> Vec<Line<'a>> {
+    let style = Style::default().fg(theme.fg0);
+    // Continuation lines keep an indent span so every line has the prefix span
+    let prefixed = |label: String, text: &str| -> Vec<Line<'a>> {
+        let indent = " ".repeat(label.width());
+        wrap_text_unicode(text, max_width.saturating_sub(label.width()))
+            .into_iter()
+            .enumerate()
+            .map(|(i, line)| {
+                let prefix = if i == 0 { label.clone() } else { indent.clone() };
+                Line::from(vec![Span::styled(prefix, style), Span::styled(line, style)])
+            })
+            .collect()
+    };
+    let mut lines = match element {
+        ContentElement::Heading(level, text) => prefixed(format!("Heading {}: ", level), text),
+        ContentElement::Quote(text) => prefixed("Quote: ".to_string(), text),
+        ContentElement::ListItem(text) => return prefixed("Item: ".to_string(), text),
+        ContentElement::Math(text) => prefixed("Formula: ".to_string(), text),
+        ContentElement::DefinitionTerm(text) => return prefixed("Term: ".to_string(), text),
+        ContentElement::Definition(text) => return prefixed("Definition: ".to_string(), text),
+        ContentElement::Footnote { label, text } => {
+            let kind = if focused { "Focused footnote" } else { "Footnote" };
+            return prefixed(format!("{} {}: ", kind, label), text);
+        }
+        ContentElement::Code(text) => {
+            let mut lines = vec![Line::from(Span::styled("Code:", style))];
+            lines.extend(text.lines().map(|line| Line::from(Span::styled(line.to_string(), style))));
+            lines.push(Line::from(Span::styled("End of code", style)));
+            lines
+        }
+        ContentElement::Separator => return vec![Line::from(Span::styled("Separator", style))],
+        ContentElement::Image { alt, caption, .. } => {
+            let label = if focused { "Focused image: " } else { "Image: " };
+            let description = match (alt, caption) {
+                (Some(alt), Some(caption)) if alt != caption => format!("{}. Caption: {}", alt, caption),
+                (_, Some(text)) | (Some(text), None) => text.clone(),
+                (None, None) => "no description".to_string(),
+            };
+            prefixed(label.to_string(), &description)
+        }
+        ContentElement::Text(..) | ContentElement::EmptyLine => Vec::new(),
+    };
+    lines.push(Line::from(""));
+    lines
+}
+
+/// Status of an image that isn't loaded (yet): loading, failed or queued
+fn render_image_status<'a>(
+    url: &str,
+    alt: Option<&str>,
+    state: Option<&ImageState>,
+    is_focused: bool,
+    theme: &Theme,
+) -> Vec<Line<'a>> {
+    let prefix = if is_focused { "▶ " } else { "" };
+    let (display, style) = match state {
+        Some(ImageState::Loading) => (
+            format!("{}[Loading image: {}]", prefix, alt.unwrap_or(&truncate_url(url, 40))),
+            Style::default().fg(theme.grey1),
+        ),
+        Some(ImageState::Failed(err)) => (
+            match alt {
+                Some(alt_text) => format!("{}[{}]", prefix, alt_text),
+                None => format!("{}[Image failed: {}]", prefix, err),
+            },
+            Style::default().fg(theme.red),
+        ),
+        // Not yet queued for loading (loaded images are drawn in their slot)
+        Some(ImageState::Loaded(_)) | None => (
+            format!("{}[Image: {}]", prefix, alt.unwrap_or(&truncate_url(url, 50))),
+            Style::default().fg(theme.grey1),
+        ),
+    };
+    let style = if is_focused { Style::default().fg(theme.yellow) } else { style };
+    vec![Line::from(Span::styled(display, style)), Line::from("")]
+}
+
+/// Image that is only downloaded on request, e.g. with image preview off
+fn render_image_placeholder<'a>(url: &str, alt: Option<&str>, key: &str, focused: bool, theme: &Theme) -> Vec<Line<'a>> {
+    let prefix = if focused { "▶ " } else { "" };
+    let style = Style::default().fg(if focused { theme.yellow } else { theme.grey1 });
+    vec![
+        Line::from(Span::styled(
+            format!("{}[image: {} — press {} to load]", prefix, alt.unwrap_or(&truncate_url(url, 40)), key),
+            style,
+        )),
+        Line::from(""),
+    ]
+}
+
+/// Caption or alt text under an image, wrapped to the content width
+/// Heading of the discussion section, then its top comments unless collapsed
+fn render_discussion<'a>(discussion: &Discussion, max_width: usize, theme: &Theme) -> Vec<Line<'a>> {
+    let grey = Style::default().fg(theme.grey1);
+    // The collapsed heading tells how much is hidden
+    let count = match discussion.comments.len() {
+        0 => String::new(),
+        _ if !discussion.collapsed => String::new(),
+        1 => " (1 comment)".to_string(),
+        n => format!(" ({} comments)", n),
+    };
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled("─".repeat(40.min(max_width)), Style::default().fg(theme.grey0))),
+        Line::from(vec![
+            Span::styled(
+                format!("Discussion{}", count),
+                Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(if discussion.collapsed { " ▸" } else { " ▾" }, grey),
+        ]),
+    ];
+    if discussion.collapsed {
+        return lines;
+    }
+    if let Some(url) = &discussion.url {
+        lines.push(Line::from(Span::styled(truncate_url(url, max_width), grey)));
+    }
+    lines.push(Line::from(""));
+
+    let status = if discussion.loading {
+        Some(("Loading comments…".to_string(), grey))
+    } else if let Some(error) = &discussion.error {
+        Some((format!("Comments unavailable: {}", error), Style::default().fg(theme.error)))
+    } else if discussion.url.is_none() {
+        Some(("No discussion found".to_string(), grey))
+    } else if discussion.comments.is_empty() {
+        Some(("No comments to show here; open the discussion with c".to_string(), grey))
+    } else {
+        None
+    };
+    if let Some((message, style)) = status {
+        for line in wrap_text_unicode(&message, max_width) {
+            lines.push(Line::from(Span::styled(line, style)));
+        }
+        return lines;
+    }
 
-        lines
+    for comment in &discussion.comments {
+        let mut heading = vec![Span::styled(
+            comment.author.clone(),
+            Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+        )];
+        if let Some(score) = comment.score {
+            heading.push(Span::styled(format!(" · {} points", score), grey));
+        }
+        lines.push(Line::from(heading));
+        for line in wrap_text_unicode(&comment.text, max_width.saturating_sub(2)) {
+            lines.push(Line::from(Span::styled(format!("  {}", line), Style::default().fg(theme.fg0))));
+        }
+        lines.push(Line::from(""));
     }
+    lines
+}
+
+fn render_caption<'a>(caption: &str, max_width: usize, theme: &Theme) -> Vec<Line<'a>> {
+    let style = Style::default().fg(theme.grey1).add_modifier(Modifier::ITALIC);
+    wrap_text_unicode(caption, max_width)
+        .into_iter()
+        .map(|line| Line::from(Span::styled(line, style)))
+        .collect()
 }
 
 /// Truncate URL for display (UTF-8 safe)
@@ -991,8 +1356,77 @@ fn truncate_url(url: &str, max_len: usize) -> String {
     }
 }
 
-/// Render summary text in a styled box with proper unicode width handling
-fn render_summary_box<'a>(summary: &str, max_width: usize, theme: &Theme) -> Vec<Line<'a>> {
+/// Whether the body is hidden for summary-only reading. Articles without a
+/// summary are shown in full.
+fn shows_summary_only(article: &Article, ai_header: AiHeader) -> bool {
+    ai_header.summary_only && article.summary.is_some()
+}
+
+/// Relevance, style and tags of an article, each with its color
+fn ai_metadata(article: &Article, style: Option<&ArticleStyle>, theme: &Theme) -> Vec<(String, Color)> {
+    let mut metadata = Vec::new();
+    if let Some(score) = article.relevance_score {
+        metadata.push((format!("Relevance {:.2}", score), theme.yellow));
+    }
+    if let Some(style) = style {
+        let parts: Vec<&str> = [&style.style_type, &style.tone, &style.length_category]
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .collect();
+        if !parts.is_empty() {
+            metadata.push((format!("Style: {}", parts.join(", ")), theme.blue));
+        }
+    }
+    if !article.tags.is_empty() {
+        metadata.push((format!("Tags: {}", article.tags.join(" | ")), theme.purple));
+    }
+    metadata
+}
+
+/// The summary and AI metadata: a box, or one line when collapsed.
+/// Nothing when the article has neither.
+fn render_ai_header<'a>(article: &Article, ai_header: AiHeader, max_width: usize, theme: &Theme) -> Vec<Line<'a>> {
+    let metadata = ai_metadata(article, ai_header.style, theme);
+    if article.summary.is_none() && metadata.is_empty() {
+        return Vec::new();
+    }
+
+    let mut lines = if ai_header.collapsed && !shows_summary_only(article, ai_header) {
+        let mut spans = vec![Span::styled(
+            "▸ AI Summary",
+            Style::default().fg(theme.yellow).add_modifier(Modifier::BOLD),
+        )];
+        if article.summary.is_none() {
+            spans.push(Span::styled(" (none yet)", Style::default().fg(theme.grey1)));
+        }
+        for (text, color) in metadata {
+            spans.push(Span::styled(" · ", Style::default().fg(theme.grey1)));
+            spans.push(Span::styled(text, Style::default().fg(color)));
+        }
+        vec![Line::from(spans)]
+    } else {
+        let mut rows = match &article.summary {
+            Some(summary) => vec![(summary.clone(), theme.fg0)],
+            None => vec![("Not summarized yet".to_string(), theme.grey1)],
+        };
+        rows.extend(metadata);
+        render_summary_box(&rows, max_width, theme)
+    };
+    lines.push(Line::from(""));
+    if ai_header.summary_only && article.summary.is_none() {
+        lines.push(Line::from(Span::styled(
+            "No summary yet, showing the full article",
+            Style::default().fg(theme.grey1),
+        )));
+        lines.push(Line::from(""));
+    }
+    lines
+}
+
+/// Render summary rows (text and color, each wrapped on its own) in a
+/// styled box with proper unicode width handling
+fn render_summary_box<'a>(rows: &[(String, Color)], max_width: usize, theme: &Theme) -> Vec<Line<'a>> {
     let border_color = theme.aqua;
     let title = " AI Summary ";
 
@@ -1017,16 +1451,17 @@ fn render_summary_box<'a>(summary: &str, max_width: usize, theme: &Theme) -> Vec
     ]));
 
     // Wrap and render summary content
-    let wrapped_lines = wrap_text_unicode(summary, inner_width);
-    for content in wrapped_lines {
-        let content_width = content.width();
-        let padding = inner_width.saturating_sub(content_width);
-        lines.push(Line::from(vec![
-            Span::styled("│ ", Style::default().fg(border_color)),
-            Span::styled(content, Style::default().fg(theme.fg0)),
-            Span::styled(" ".repeat(padding), Style::default().fg(theme.fg0)),
-            Span::styled(" │", Style::default().fg(border_color)),
-        ]));
+    for (text, color) in rows {
+        for content in wrap_text_unicode(text, inner_width) {
+            let content_width = content.width();
+            let padding = inner_width.saturating_sub(content_width);
+            lines.push(Line::from(vec![
+                Span::styled("│ ", Style::default().fg(border_color)),
+                Span::styled(content, Style::default().fg(*color)),
+                Span::styled(" ".repeat(padding), Style::default().fg(theme.fg0)),
+                Span::styled(" │", Style::default().fg(border_color)),
+            ]));
+        }
     }
 
     // Bottom border: ╰────────────────╯
@@ -1040,7 +1475,7 @@ fn render_summary_box<'a>(summary: &str, max_width: usize, theme: &Theme) -> Vec
 }
 
 /// Wrap text respecting unicode character widths (CJK = 2 columns)
-fn wrap_text_unicode(text: &str, max_width: usize) -> Vec<String> {
+pub(super) fn wrap_text_unicode(text: &str, max_width: usize) -> Vec<String> {
     let mut result = Vec::new();
 
     for paragraph in text.lines() {
@@ -1081,6 +1516,79 @@ fn wrap_text_unicode(text: &str, max_width: usize) -> Vec<String> {
     result
 }
 
+/// Patch `style` onto the characters of `range` in rendered lines. The range
+/// counts non-whitespace characters (see `tts::Sentence`), so it holds however
+/// the text was wrapped; the first `skip_spans` spans of each line (quote
+/// bars, bullets) are not counted.
+fn highlight_range(lines: &mut [Line<'_>], range: &Range<usize>, skip_spans: usize, style: Style) {
+    let mut count = 0;
+    for line in lines {
+        let spans = std::mem::take(&mut line.spans);
+        let mut highlighted = Vec::with_capacity(spans.len() + 2);
+        for (i, span) in spans.into_iter().enumerate() {
+            if i < skip_spans {
+                highlighted.push(span);
+                continue;
+            }
+            let mut segment = String::new();
+            let mut segment_inside = false;
+            for c in span.content.chars() {
+                // Spaces are highlighted only between highlighted characters
+                let inside = if c.is_whitespace() {
+                    count > range.start && count < range.end
+                } else {
+                    count += 1;
+                    range.contains(&(count - 1))
+                };
+                if inside != segment_inside && !segment.is_empty() {
+                    let segment_style = if segment_inside { span.style.patch(style) } else { span.style };
+                    highlighted.push(Span::styled(std::mem::take(&mut segment), segment_style));
+                }
+                segment_inside = inside;
+                segment.push(c);
+            }
+            if !segment.is_empty() {
+                let segment_style = if segment_inside { span.style.patch(style) } else { span.style };
+                highlighted.push(Span::styled(segment, segment_style));
+            }
+        }
+        line.spans = highlighted;
+    }
+}
+
+/// Style of inline emphasis in a paragraph
+fn inline_style(kind: Inline, theme: &Theme) -> Style {
+    match kind {
+        Inline::Bold => Style::default().add_modifier(Modifier::BOLD),
+        Inline::Italic => Style::default().add_modifier(Modifier::ITALIC),
+        Inline::Code => Style::default().fg(theme.green).bg(theme.bg1),
+        Inline::Link => Style::default().fg(theme.blue),
+    }
+}
+
+/// Color the footnote references (focusable item, element, character
+/// range) of an element's lines, the focused one like a focused link
+fn highlight_footnote_refs(
+    lines: &mut [Line<'_>],
+    refs: &[(usize, usize, Range<usize>)],
+    element_index: usize,
+    focused_item: Option<usize>,
+    skip_spans: usize,
+    theme: &Theme,
+) {
+    for (index, _, range) in refs.iter().filter(|(_, element, _)| *element == element_index) {
+        let style = if focused_item == Some(*index) {
+            Style::default()
+                .fg(theme.blue)
+                .bg(theme.yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.aqua)
+        };
+        highlight_range(lines, range, skip_spans, style);
+    }
+}
+
 /// Render text with URL highlighting and optional focus highlighting
 fn render_text_with_links<'a>(
     spans: &[TextSpan],
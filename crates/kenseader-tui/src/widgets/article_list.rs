@@ -6,7 +6,11 @@ use ratatui::{
     Frame,
 };
 
-use crate::app::{App, Focus, ViewMode};
+use kenseader_core::feed::Advisory;
+
+use crate::app::{search_match_ranges, App, Focus, ViewMode, VirtualFeed};
+use crate::theme::Theme;
+use crate::time_format::format_timestamp;
 
 pub struct ArticleListWidget;
 
@@ -26,40 +30,96 @@ impl ArticleListWidget {
             ViewMode::UnreadOnly => " [Unread]",
         };
 
-        let title = format!(" Articles{} ", mode_indicator);
+        let title = match app.virtual_feed {
+            Some(VirtualFeed::History) => " History (recently read) ".to_string(),
+            Some(VirtualFeed::Filtered) => " Filtered (below the relevance threshold) ".to_string(),
+            Some(VirtualFeed::Alerts) => " Alerts (keyword watchlist) ".to_string(),
+            Some(VirtualFeed::Advisories) => " Advisories (most severe first) ".to_string(),
+            None => format!(" Articles{} ", mode_indicator),
+        };
 
-        let block = Block::default()
-            .title(title)
+        let mut block = Block::default()
+            .title(super::pane_title(title, is_focused, app))
             .borders(Borders::ALL)
             .border_style(border_style)
             .style(Style::default().bg(theme.bg0));
+        // Position in a list longer than the pane, counting the articles
+        // before the loaded window; more pages may follow
+        let visible_rows = block.inner(area).height as usize;
+        let show_position = app.config.ui.show_scrollbar
+            && (app.articles.len() > visible_rows || app.articles_offset > 0);
+        if show_position {
+            let more = if app.articles_exhausted { "" } else { "+" };
+            let position = format!(
+                " {}/{}{} ",
+                app.articles_offset + app.selected_article + 1,
+                app.articles_offset + app.articles.len(),
+                more
+            );
+            block = block.title_bottom(Line::from(position).right_aligned());
+        }
 
         // Check if we're searching
         let search_query = if !app.search_query.is_empty() {
-            Some(app.search_query.to_lowercase())
+            Some(app.search_query.as_str())
         } else {
             None
         };
 
+        let now = chrono::Utc::now();
+        let dim_after_days = app.config.ui.dim_unread_after_days;
+        // State in words rather than colors and symbols
+        let screen_reader = app.config.ui.screen_reader;
+
+        // Only build items for the visible window so huge feeds render in
+        // constant time; keep the cursor on the last row when scrolling down,
+        // matching List's own scrolling behavior
+        let offset = app.selected_article.saturating_sub(visible_rows.saturating_sub(1));
+
         let items: Vec<ListItem> = app
             .articles
             .iter()
             .enumerate()
+            .skip(offset)
+            .take(visible_rows)
             .map(|(i, article)| {
                 // Selection marker (yazi-like)
                 let is_selected = app.selected_articles.contains(&i);
-                let select_marker = if is_selected { "✓" } else { " " };
+                let select_marker = match (is_selected, screen_reader) {
+                    (true, true) => "marked ",
+                    (true, false) => "✓",
+                    (false, true) => "",
+                    (false, false) => " ",
+                };
 
                 // Search match marker
                 let is_search_match = app.search_matches.contains(&i);
-                let match_marker = if is_search_match { "*" } else { " " };
+                let match_marker = match (is_search_match, screen_reader) {
+                    (true, true) => "match ",
+                    (true, false) => "*",
+                    (false, true) => "",
+                    (false, false) => " ",
+                };
 
-                let read_marker = if article.is_read { " " } else { "●" };
-                let saved_marker = if article.is_saved { "★" } else { " " };
+                // Unread but old enough to be unlikely to be read
+                let aging = article.is_aging(dim_after_days, now);
+                let read_marker = match (article.is_read, screen_reader) {
+                    (false, true) if aging => "old unread ",
+                    (false, true) => "unread ",
+                    (false, false) => "●",
+                    (true, true) => "",
+                    (true, false) => " ",
+                };
+                let saved_marker = match (article.is_saved, screen_reader) {
+                    (true, true) => "saved ",
+                    (true, false) => "★",
+                    (false, true) => "",
+                    (false, false) => " ",
+                };
 
                 let title = &article.title;
 
-                // Style priority: selected > cursor > search_match > unread > read
+                // Style priority: selected > cursor > search_match > aging > unread > read
                 let base_style = if is_selected {
                     Style::default()
                         .fg(theme.fg0)
@@ -73,6 +133,8 @@ impl ArticleListWidget {
                     Style::default()
                         .fg(theme.fg0)
                         .bg(theme.bg3)
+                } else if aging {
+                    Style::default().fg(theme.grey2)
                 } else if !article.is_read {
                     Style::default().fg(theme.unread)
                 } else {
@@ -84,11 +146,11 @@ impl ArticleListWidget {
                 } else {
                     Style::default().fg(theme.grey1)
                 };
-                let marker_style = Style::default().fg(theme.yellow);
+                let marker_style = Style::default().fg(if aging { theme.grey1 } else { theme.yellow });
                 let saved_style = Style::default().fg(theme.orange);
 
                 // Build title spans with search highlighting
-                let title_spans = if let Some(ref query) = search_query {
+                let title_spans = if let Some(query) = search_query {
                     Self::highlight_matches(title, query, base_style, theme.bg0, theme.yellow)
                 } else {
                     vec![Span::styled(title.clone(), base_style)]
@@ -105,9 +167,78 @@ impl ArticleListWidget {
                     Span::styled(match_marker, match_style),
                     Span::styled(read_marker, marker_style),
                     Span::styled(saved_marker, saved_style),
-                    Span::raw(" "),
                 ];
+                if !screen_reader {
+                    spans.push(Span::raw(" "));
+                }
+                // Podcast/video attachment marker
+                if let Some(ref media) = article.media {
+                    let media_marker = match (media.is_video(), screen_reader) {
+                        (true, true) => "video ",
+                        (true, false) => "▶ ",
+                        (false, true) => "audio ",
+                        (false, false) => "♪ ",
+                    };
+                    spans.push(Span::styled(media_marker, Style::default().fg(theme.aqua)));
+                }
+                // Virtual feeds mix feeds, so name each article's feed
+                if app.virtual_feed.is_some() {
+                    if let Some(feed) = app.feeds.iter().find(|f| f.id == article.feed_id) {
+                        spans.push(Span::styled(
+                            format!("[{}] ", feed.local_name),
+                            Style::default().fg(theme.blue),
+                        ));
+                    }
+                }
                 spans.extend(title_spans);
+                // Content changed upstream since it was read
+                if article.is_updated() {
+                    spans.push(Span::styled(" [updated]", Style::default().fg(theme.blue)));
+                }
+                // Why it was filtered or alerted, in those views
+                match app.virtual_feed {
+                    Some(VirtualFeed::Filtered) => {
+                        if let Some(reason) = app.filter_reasons.get(&article.id) {
+                            spans.push(Span::styled(
+                                format!(" [score {:.2} < {:.2}]", reason.score, reason.threshold),
+                                Style::default().fg(theme.yellow),
+                            ));
+                        }
+                    }
+                    Some(VirtualFeed::Alerts) => {
+                        if let Some(alert) = app.article_alerts.get(&article.id) {
+                            spans.push(Span::styled(
+                                format!(" [alert: {}]", alert.keyword),
+                                Style::default().fg(theme.error),
+                            ));
+                        }
+                    }
+                    Some(VirtualFeed::Advisories) => {
+                        if let Some(advisories) = app.article_advisories.get(&article.id) {
+                            spans.push(advisory_span(advisories, theme));
+                        }
+                    }
+                    _ => {}
+                }
+                if app.config.ui.show_timestamps {
+                    // When the article was read, filtered or alerted, in virtual feeds
+                    let date = match app.virtual_feed {
+                        Some(VirtualFeed::History) => article.read_at.as_ref(),
+                        Some(VirtualFeed::Filtered) => {
+                            app.filter_reasons.get(&article.id).map(|reason| &reason.filtered_at)
+                        }
+                        Some(VirtualFeed::Alerts) => {
+                            app.article_alerts.get(&article.id).map(|alert| &alert.alerted_at)
+                        }
+                        Some(VirtualFeed::Advisories) | None => article.published_at.as_ref(),
+                    };
+                    if let Some(date) = date {
+                        spans.push(Span::styled(
+                            format!("  {}", format_timestamp(date, &app.config.ui)),
+                            Style::default().fg(theme.grey1),
+                        ));
+                    }
+                }
 
                 ListItem::new(Line::from(spans))
             })
@@ -119,11 +250,22 @@ impl ArticleListWidget {
                 Style::default()
                     .bg(theme.selection),
             );
+        let list = if screen_reader { list.highlight_symbol("> ") } else { list };
 
         let mut state = ListState::default();
-        state.select(Some(app.selected_article));
+        state.select(Some(app.selected_article - offset));
 
         frame.render_stateful_widget(list, area, &mut state);
+        if show_position {
+            super::render_scrollbar(
+                frame,
+                area,
+                app.articles.len() - 1,
+                visible_rows,
+                app.selected_article,
+                theme,
+            );
+        }
     }
 
     /// Highlight matching parts of a string with a different color
@@ -134,46 +276,49 @@ impl ArticleListWidget {
         highlight_fg: Color,
         highlight_bg: Color,
     ) -> Vec<Span<'a>> {
+        let highlight_style = base_style.fg(highlight_fg).bg(highlight_bg);
         let mut spans = Vec::new();
-        let text_lower = text.to_lowercase();
         let mut last_end = 0;
 
-        // Find all occurrences of the query in the text
-        for (start, _) in text_lower.match_indices(query) {
+        for range in search_match_ranges(text, query) {
             // Add non-matching part before this match
-            if start > last_end {
-                spans.push(Span::styled(
-                    text[last_end..start].to_string(),
-                    base_style,
-                ));
+            if range.start > last_end {
+                spans.push(Span::styled(text[last_end..range.start].to_string(), base_style));
             }
-
-            // Add the matching part with highlight style
-            let end = start + query.len();
-            let highlight_style = base_style
-                .fg(highlight_fg)
-                .bg(highlight_bg);
-            spans.push(Span::styled(
-                text[start..end].to_string(),
-                highlight_style,
-            ));
-
-            last_end = end;
+            last_end = range.end;
+            spans.push(Span::styled(text[range].to_string(), highlight_style));
         }
 
-        // Add remaining non-matching part
-        if last_end < text.len() {
-            spans.push(Span::styled(
-                text[last_end..].to_string(),
-                base_style,
-            ));
-        }
-
-        // If no matches found, return the whole text with base style
-        if spans.is_empty() {
-            spans.push(Span::styled(text.to_string(), base_style));
+        // Add remaining non-matching part (or the whole text without matches)
+        if last_end < text.len() || spans.is_empty() {
+            spans.push(Span::styled(text[last_end..].to_string(), base_style));
         }
 
         spans
     }
 }
+
+/// The most severe CVE of an article, colored by severity, with how many
+/// others it mentions
+fn advisory_span(advisories: &[Advisory], theme: &Theme) -> Span<'static> {
+    let Some(top) = advisories.first() else {
+        return Span::raw("");
+    };
+    let others = match advisories.len() - 1 {
+        0 => String::new(),
+        n => format!(" +{}", n),
+    };
+    let (text, color) = match (top.score, top.severity.as_deref()) {
+        (Some(score), Some(severity)) => {
+            let color = match severity {
+                "CRITICAL" => theme.error,
+                "HIGH" => theme.orange,
+                "MEDIUM" => theme.yellow,
+                _ => theme.grey1,
+            };
+            (format!(" [{} {:.1} {}{}]", top.cve_id, score, severity.to_lowercase(), others), color)
+        }
+        _ => (format!(" [{} unscored{}]", top.cve_id, others), theme.grey1),
+    };
+    Span::styled(text, Style::default().fg(color))
+}
@@ -0,0 +1,318 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Position, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState},
+    Frame,
+};
+
+use super::popup::{centered_rect, truncate_str, PopupWidget};
+use crate::app::App;
+use crate::feed_manager::{FeedForm, FormField, SubscribePrompt, TextField};
+use crate::theme::Theme;
+
+/// Width of the form labels column
+const LABEL_WIDTH: u16 = 21;
+
+pub struct FeedManagerWidget;
+
+impl FeedManagerWidget {
+    /// Render the feed management screen over the main layout
+    pub fn render(frame: &mut Frame, app: &App) {
+        let theme = &app.theme;
+        let manager = &app.feed_manager;
+        let area = frame.area();
+        let popup_area = centered_rect(
+            area.width.saturating_sub(4),
+            area.height.saturating_sub(2),
+            area,
+        );
+
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .title(format!(" Manage Feeds ({}) ", app.feeds.len()))
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent))
+            .style(Style::default().bg(theme.bg0));
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1)])
+            .split(inner);
+
+        let rows: Vec<Row> = app
+            .feeds
+            .iter()
+            .map(|feed| {
+                let (status, status_style) = if feed.paused {
+                    ("paused".to_string(), Style::default().fg(theme.yellow))
+                } else if feed.has_error() {
+                    ("error".to_string(), Style::default().fg(theme.error))
+                } else {
+                    ("active".to_string(), Style::default().fg(theme.green))
+                };
+                let interval = feed
+                    .refresh_interval_secs
+                    .map(|secs| format!("{}m", secs / 60))
+                    .unwrap_or_else(|| "default".to_string());
+                Row::new(vec![
+                    Cell::from(feed.local_name.clone()).style(Style::default().fg(theme.fg0)),
+                    Cell::from(feed.category.clone().unwrap_or_default())
+                        .style(Style::default().fg(theme.aqua)),
+                    Cell::from(status).style(status_style),
+                    Cell::from(interval).style(Style::default().fg(theme.grey2)),
+                    Cell::from(feed.url.clone()).style(Style::default().fg(theme.grey1)),
+                ])
+            })
+            .collect();
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Percentage(22),
+                Constraint::Percentage(14),
+                Constraint::Length(7),
+                Constraint::Length(8),
+                Constraint::Min(10),
+            ],
+        )
+        .header(
+            Row::new(vec!["Name", "Category", "Status", "Refresh", "URL"])
+                .style(Style::default().fg(theme.grey1).add_modifier(Modifier::BOLD)),
+        )
+        .row_highlight_style(
+            Style::default()
+                .bg(theme.selection)
+                .add_modifier(Modifier::BOLD),
+        );
+
+        let mut state = TableState::default();
+        if !app.feeds.is_empty() {
+            state.select(Some(manager.selected));
+        }
+        frame.render_stateful_widget(table, chunks[0], &mut state);
+
+        let hint = Line::from(vec![
+            key_span("a", theme),
+            Span::styled(" add  ", Style::default().fg(theme.grey1)),
+            key_span("e", theme),
+            Span::styled(" edit  ", Style::default().fg(theme.grey1)),
+            key_span("p", theme),
+            Span::styled(" pause/resume  ", Style::default().fg(theme.grey1)),
+            key_span("d", theme),
+            Span::styled(" delete  ", Style::default().fg(theme.grey1)),
+            key_span("Esc", theme),
+            Span::styled(" close", Style::default().fg(theme.grey1)),
+        ]);
+        frame.render_widget(Paragraph::new(hint).alignment(Alignment::Center), chunks[1]);
+
+        if let Some(ref form) = manager.form {
+            Self::render_form(frame, form, theme);
+        } else if manager.confirm_delete {
+            if let Some(feed) = app.feeds.get(manager.selected) {
+                PopupWidget::render_delete_confirm(frame, &feed.local_name, theme);
+            }
+        }
+    }
+
+    /// Render the subscribe prompt (URL + optional name)
+    pub fn render_subscribe_prompt(frame: &mut Frame, prompt: &SubscribePrompt, theme: &Theme) {
+        let area = frame.area();
+        let popup_width = 72u16.min(area.width.saturating_sub(4));
+        let popup_height = 7u16.min(area.height.saturating_sub(2));
+        let popup_area = centered_rect(popup_width, popup_height, area);
+
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .title(" Subscribe ")
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent))
+            .style(Style::default().bg(theme.bg1));
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let editable = !prompt.pending;
+        let (url_line, url_cursor) = field_line(
+            "Feed or site URL",
+            &prompt.url,
+            editable && !prompt.name_focused,
+            inner,
+            0,
+            theme,
+        );
+        let (name_line, name_cursor) = field_line(
+            "Name (optional)",
+            &prompt.name,
+            editable && prompt.name_focused,
+            inner,
+            1,
+            theme,
+        );
+
+        let status = if prompt.pending {
+            Line::from(Span::styled(
+                "Looking for a feed...",
+                Style::default().fg(theme.yellow),
+            ))
+        } else if let Some((_, ref existing)) = prompt.duplicate {
+            let message = format!(
+                "Already subscribed as '{}' - Enter renames it to '{}'",
+                existing,
+                prompt.name.value.trim()
+            );
+            Line::from(Span::styled(
+                truncate_str(&message, inner.width as usize),
+                Style::default().fg(theme.yellow),
+            ))
+        } else if let Some(ref error) = prompt.error {
+            Line::from(Span::styled(
+                truncate_str(error, inner.width as usize),
+                Style::default().fg(theme.error),
+            ))
+        } else {
+            Line::from(Span::styled(
+                "Site URLs are searched for a feed link",
+                Style::default().fg(theme.grey0),
+            ))
+        };
+        let hint = Line::from(vec![
+            key_span("Tab", theme),
+            Span::styled(" switch field  ", Style::default().fg(theme.grey1)),
+            key_span("Enter", theme),
+            Span::styled(
+                if prompt.duplicate.is_some() { " rename  " } else { " subscribe  " },
+                Style::default().fg(theme.grey1),
+            ),
+            key_span("Esc", theme),
+            Span::styled(" cancel", Style::default().fg(theme.grey1)),
+        ]);
+
+        frame.render_widget(
+            Paragraph::new(vec![url_line, name_line, Line::from(""), status, hint]),
+            inner,
+        );
+        if let Some(position) = url_cursor.or(name_cursor) {
+            frame.set_cursor_position(position);
+        }
+    }
+
+    /// Render the add/edit form
+    fn render_form(frame: &mut Frame, form: &FeedForm, theme: &Theme) {
+        let area = frame.area();
+        let popup_width = 72u16.min(area.width.saturating_sub(4));
+        let popup_height = (FormField::ALL.len() as u16 + 6).min(area.height.saturating_sub(2));
+        let popup_area = centered_rect(popup_width, popup_height, area);
+
+        frame.render_widget(Clear, popup_area);
+
+        let title = if form.is_new() { " Add Feed " } else { " Edit Feed " };
+        let block = Block::default()
+            .title(title)
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent))
+            .style(Style::default().bg(theme.bg1));
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let mut lines = Vec::new();
+        let mut cursor = None;
+
+        for (row, field) in FormField::ALL.iter().enumerate() {
+            let focused = form.focused == *field;
+            let line = match form.text_field(*field) {
+                Some(text) => {
+                    let (line, position) =
+                        field_line(field.label(), text, focused, inner, row as u16, theme);
+                    cursor = cursor.or(position);
+                    line
+                }
+                None => Line::from(vec![
+                    label_span(field.label(), focused, theme),
+                    Span::styled(
+                        if form.paused { "[x]" } else { "[ ]" },
+                        Style::default().fg(theme.fg0).add_modifier(if focused {
+                            Modifier::BOLD | Modifier::REVERSED
+                        } else {
+                            Modifier::empty()
+                        }),
+                    ),
+                ]),
+            };
+            lines.push(line);
+        }
+
+        lines.push(Line::from(""));
+        lines.push(match form.error {
+            Some(ref error) => Line::from(Span::styled(
+                truncate_str(error, inner.width as usize),
+                Style::default().fg(theme.error),
+            )),
+            None => Line::from(Span::styled(
+                "Empty refresh and auto-read use the global defaults",
+                Style::default().fg(theme.grey0),
+            )),
+        });
+        lines.push(Line::from(vec![
+            key_span("Tab", theme),
+            Span::styled(" next field  ", Style::default().fg(theme.grey1)),
+            key_span("Space", theme),
+            Span::styled(" toggle  ", Style::default().fg(theme.grey1)),
+            key_span("Enter", theme),
+            Span::styled(" save  ", Style::default().fg(theme.grey1)),
+            key_span("Esc", theme),
+            Span::styled(" cancel", Style::default().fg(theme.grey1)),
+        ]));
+
+        frame.render_widget(Paragraph::new(lines), inner);
+        if let Some(position) = cursor {
+            frame.set_cursor_position(position);
+        }
+    }
+}
+
+/// Right-aligned form label
+pub(super) fn label_span(label: &str, focused: bool, theme: &Theme) -> Span<'static> {
+    let style = if focused {
+        Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(theme.grey2)
+    };
+    Span::styled(format!("{:>width$} ", label, width = LABEL_WIDTH as usize), style)
+}
+
+/// Labeled text input on `row` of `area`, with the cursor position if focused.
+/// Long values scroll horizontally so the cursor stays visible.
+pub(super) fn field_line(
+    label: &str,
+    text: &TextField,
+    focused: bool,
+    area: Rect,
+    row: u16,
+    theme: &Theme,
+) -> (Line<'static>, Option<Position>) {
+    let value_width = area.width.saturating_sub(LABEL_WIDTH + 1) as usize;
+    let skip = text.cursor.saturating_sub(value_width.saturating_sub(1));
+    let cursor = focused.then(|| {
+        Position::new(
+            area.x + LABEL_WIDTH + 1 + (text.cursor - skip) as u16,
+            area.y + row,
+        )
+    });
+    let visible: String = text.value.chars().skip(skip).take(value_width).collect();
+    let line = Line::from(vec![
+        label_span(label, focused, theme),
+        Span::styled(visible, Style::default().fg(theme.fg0).bg(theme.bg2)),
+    ]);
+    (line, cursor)
+}
+
+pub(super) fn key_span<'a>(key: &'a str, theme: &Theme) -> Span<'a> {
+    Span::styled(key, Style::default().fg(theme.yellow).add_modifier(Modifier::BOLD))
+}
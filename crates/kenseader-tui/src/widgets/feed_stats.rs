@@ -0,0 +1,141 @@
+use chrono::{DateTime, Utc};
+use kenseader_core::feed::FeedStats;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::App;
+use crate::image_renderer::RenderBackend;
+use crate::theme::Theme;
+use crate::time_format::format_relative;
+
+/// Width of the metric names
+const LABEL_WIDTH: usize = 14;
+
+pub struct FeedStatsWidget;
+
+impl FeedStatsWidget {
+    /// Render the statistics of the selected feed in place of the article
+    /// detail, to help decide which subscriptions to keep
+    pub fn render(frame: &mut Frame, area: Rect, app: &mut App) {
+        // Overlay images of the last article would stay on screen otherwise
+        let backend = app.image_renderer.backend();
+        if matches!(backend, RenderBackend::Ueberzug | RenderBackend::Kitty) {
+            app.image_renderer.clear_all();
+        }
+
+        let theme = &app.theme;
+        let block = Block::default()
+            .title(" Feed ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.grey0))
+            .style(Style::default().bg(theme.bg0));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let Some(feed) = app.current_feed() else {
+            let empty = Line::from(Span::styled("No feed selected", Style::default().fg(theme.grey1)));
+            frame.render_widget(Paragraph::new(empty), inner);
+            return;
+        };
+        let now = Utc::now();
+
+        let mut lines = vec![
+            Line::from(Span::styled(
+                feed.title.clone().unwrap_or_else(|| feed.local_name.clone()),
+                Style::default().fg(theme.fg0).add_modifier(Modifier::BOLD),
+            )),
+            Line::from(Span::styled(feed.url.clone(), Style::default().fg(theme.grey1))),
+            Line::default(),
+        ];
+        if let Some(category) = &feed.category {
+            lines.push(row("Category", category.clone(), theme.fg0, theme));
+        }
+        if feed.paused {
+            lines.push(row("Status", "paused".to_string(), theme.warning, theme));
+        }
+        if let Some(error) = &feed.fetch_error {
+            lines.push(row("Last error", error.clone(), theme.error, theme));
+        }
+        lines.push(row("Last fetched", ago(feed.last_fetched_at, now), theme.fg0, theme));
+        lines.push(Line::default());
+
+        match app.feed_stats.as_ref().filter(|(id, _)| *id == feed.id) {
+            Some((_, Some(stats))) => {
+                let reasons = stats.prune_reasons(feed.created_at, &app.config.prune, now);
+                lines.extend(stats_lines(stats, &reasons, now, theme));
+            }
+            Some((_, None)) => lines.push(Line::from(Span::styled(
+                "Statistics unavailable",
+                Style::default().fg(theme.grey1),
+            ))),
+            None => lines.push(Line::from(Span::styled(
+                "Loading statistics…",
+                Style::default().fg(theme.grey1),
+            ))),
+        }
+
+        frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
+    }
+}
+
+/// Metrics of the feed, then the reasons it may be worth pruning
+fn stats_lines(
+    stats: &FeedStats,
+    reasons: &[String],
+    now: DateTime<Utc>,
+    theme: &Theme,
+) -> Vec<Line<'static>> {
+    let read = stats
+        .read_ratio()
+        .map(|ratio| format!("{:.0}%", ratio * 100.0))
+        .unwrap_or_else(|| "-".to_string());
+    let relevance = stats
+        .average_relevance
+        .map(|score| format!("{:.0}% average", score * 100.0))
+        .unwrap_or_else(|| "not scored".to_string());
+
+    let mut lines = vec![
+        row(
+            "Articles",
+            format!("{} stored, {} unread, {} saved", stats.total, stats.unread, stats.saved),
+            theme.fg0,
+            theme,
+        ),
+        row(
+            "Per week",
+            format!("{:.1} (last {} weeks)", stats.per_week(), FeedStats::RECENT_WEEKS),
+            theme.fg0,
+            theme,
+        ),
+        row("Read", read, theme.fg0, theme),
+        row("Relevance", relevance, theme.fg0, theme),
+        row("Last post", ago(stats.last_post_at, now), theme.fg0, theme),
+    ];
+
+    if !reasons.is_empty() {
+        lines.push(Line::default());
+        lines.push(Line::from(Span::styled(
+            format!("Consider unsubscribing: {}", reasons.join(", ")),
+            Style::default().fg(theme.warning),
+        )));
+    }
+    lines
+}
+
+fn row(name: &str, value: String, color: Color, theme: &Theme) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(format!("{:<LABEL_WIDTH$}", name), Style::default().fg(theme.grey1)),
+        Span::styled(value, Style::default().fg(color)),
+    ])
+}
+
+fn ago(timestamp: Option<DateTime<Utc>>, now: DateTime<Utc>) -> String {
+    timestamp
+        .map(|at| format_relative(&at, &now))
+        .unwrap_or_else(|| "never".to_string())
+}
@@ -0,0 +1,79 @@
+use ratatui::{
+    layout::Alignment,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::popup::{centered_rect, truncate_str};
+use crate::app::{App, Mode};
+
+/// Widest key list shown before it is cut
+const MAX_KEYS_WIDTH: usize = 28;
+
+pub struct HelpWidget;
+
+impl HelpWidget {
+    /// Render the key bindings in effect, one action per line with its keys
+    /// (from `Keymap::help_entries`). Clamps the scroll offset of
+    /// `Mode::Help` to the end of the list.
+    pub fn render(frame: &mut Frame, app: &mut App, entries: &[(String, Vec<String>)]) {
+        let Mode::Help(scroll) = app.mode else {
+            return;
+        };
+        let theme = &app.theme;
+        let area = frame.area();
+        let width = (area.width * 7 / 10).max(50).min(area.width);
+        let height = (area.height * 8 / 10).max(10).min(area.height);
+        let popup_area = centered_rect(width, height, area);
+        frame.render_widget(Clear, popup_area);
+
+        let keys: Vec<String> = entries.iter().map(|(_, keys)| keys.join(", ")).collect();
+        let keys_width = keys
+            .iter()
+            .map(|k| k.chars().count())
+            .max()
+            .unwrap_or(0)
+            .min(MAX_KEYS_WIDTH);
+
+        // Border, blank line and hint take 4 rows
+        let rows = popup_area.height.saturating_sub(4);
+        let max_scroll = (entries.len() as u16).saturating_sub(rows);
+        let scroll = scroll.min(max_scroll);
+
+        let block = Block::default()
+            .title(format!(" Key bindings ({}) ", super::scroll_position(scroll, max_scroll)))
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent))
+            .style(Style::default().bg(theme.bg1));
+
+        let mut lines: Vec<Line> = entries
+            .iter()
+            .zip(&keys)
+            .skip(scroll as usize)
+            .take(rows as usize)
+            .map(|((action, _), keys)| {
+                Line::from(vec![
+                    Span::styled(
+                        format!(" {:<keys_width$} ", truncate_str(keys, keys_width)),
+                        Style::default().fg(theme.yellow).add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(action.clone(), Style::default().fg(theme.fg0)),
+                ])
+            })
+            .collect();
+        lines.push(Line::default());
+        lines.push(Line::from(Span::styled(
+            " Movement and scroll keys scroll · any other key closes",
+            Style::default().fg(theme.grey1),
+        )));
+
+        frame.render_widget(Paragraph::new(lines).block(block), popup_area);
+        if max_scroll > 0 {
+            super::render_scrollbar(frame, popup_area, max_scroll as usize, rows as usize, scroll as usize, theme);
+        }
+        app.mode = Mode::Help(scroll);
+    }
+}
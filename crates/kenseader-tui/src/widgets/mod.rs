@@ -1,13 +1,78 @@
 mod article_detail;
 mod article_list;
+mod feed_manager;
+mod feed_stats;
+mod help;
 mod image_viewer;
 mod popup;
+mod profile;
+mod settings;
 mod status_bar;
 mod subscriptions;
+mod triage;
 
 pub use article_detail::ArticleDetailWidget;
 pub use article_list::ArticleListWidget;
+pub use feed_manager::FeedManagerWidget;
+pub use feed_stats::FeedStatsWidget;
+pub use help::HelpWidget;
 pub use image_viewer::ImageViewerWidget;
-pub use popup::PopupWidget;
+pub use popup::{truncate_str, PopupWidget};
+pub use profile::ProfileWidget;
+pub use settings::SettingsWidget;
 pub use status_bar::StatusBarWidget;
 pub use subscriptions::SubscriptionsWidget;
+pub use triage::TriageWidget;
+
+use ratatui::{
+    layout::{Margin, Rect},
+    style::Style,
+    widgets::{Scrollbar, ScrollbarOrientation, ScrollbarState},
+    Frame,
+};
+
+use crate::app::App;
+use crate::theme::Theme;
+
+/// Title of a main pane; screen-reader mode names the focused pane, which
+/// is otherwise only shown by the border color
+fn pane_title(title: String, is_focused: bool, app: &App) -> String {
+    if is_focused && app.config.ui.screen_reader {
+        format!("{}(focused) ", title)
+    } else {
+        title
+    }
+}
+
+/// Scrollbar on the right border of a pane whose content doesn't fit.
+/// `position` goes from 0 to `max_position` (the scroll offset, or the
+/// selected row of a list); `viewport` sizes the thumb.
+fn render_scrollbar(
+    frame: &mut Frame,
+    area: Rect,
+    max_position: usize,
+    viewport: usize,
+    position: usize,
+    theme: &Theme,
+) {
+    let mut state = ScrollbarState::new(max_position + 1)
+        .viewport_content_length(viewport)
+        .position(position);
+    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+        .begin_symbol(None)
+        .end_symbol(None)
+        .track_style(Style::default().fg(theme.grey0))
+        .thumb_style(Style::default().fg(theme.grey2));
+    frame.render_stateful_widget(scrollbar, area.inner(Margin { vertical: 1, horizontal: 0 }), &mut state);
+}
+
+/// Vim-style position in scrolled content: "Top", "Bot" or a percentage
+fn scroll_position(scroll: u16, max_scroll: u16) -> String {
+    if scroll == 0 {
+        "Top".to_string()
+    } else if scroll >= max_scroll {
+        "Bot".to_string()
+    } else {
+        format!("{}%", scroll as u32 * 100 / max_scroll as u32)
+    }
+}
@@ -1,12 +1,18 @@
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Modifier, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
     Frame,
 };
 
+use super::feed_manager::{field_line, key_span};
+use crate::qr::QrCode;
+use crate::share::ShareTarget;
+use crate::tagging::{TagPrompt, MAX_SUGGESTIONS};
 use crate::theme::Theme;
+use crate::toast::{Severity, Toasts};
+use crate::toc::TocEntry;
 
 pub struct PopupWidget;
 
@@ -99,17 +105,373 @@ impl PopupWidget {
         let message = format!("Delete {} selected feeds?", count);
         Self::render_confirm(frame, "Confirm Batch Delete", &message, theme);
     }
+
+    /// Render the confirmation of marking all of a feed's articles read
+    pub fn render_mark_all_read_confirm(frame: &mut Frame, feed_name: &str, unread: u32, theme: &Theme) {
+        let message = format!("Mark {} unread in \"{}\" read?", unread, truncate_str(feed_name, 24));
+        Self::render_confirm(frame, "Mark All Read", &message, theme);
+    }
+
+    /// Render the tag prompt, with completions of the tag being typed
+    pub fn render_tag_prompt(frame: &mut Frame, prompt: &TagPrompt, theme: &Theme) {
+        let area = frame.area();
+        let popup_width = 72u16.min(area.width.saturating_sub(4));
+        let popup_height = 7u16.min(area.height.saturating_sub(2));
+        let popup_area = centered_rect(popup_width, popup_height, area);
+
+        frame.render_widget(Clear, popup_area);
+
+        let count = prompt.targets.len();
+        let title = if count == 1 {
+            " Tag article ".to_string()
+        } else {
+            format!(" Tag {} articles ", count)
+        };
+        let block = Block::default()
+            .title(title)
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent))
+            .style(Style::default().bg(theme.bg1));
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let (input_line, cursor) = field_line("Tags, comma-separated", &prompt.input, true, inner, 0, theme);
+
+        let suggestions = prompt.suggestions();
+        let status = if let Some(ref error) = prompt.error {
+            Line::from(Span::styled(
+                truncate_str(error, inner.width as usize),
+                Style::default().fg(theme.error),
+            ))
+        } else if suggestions.is_empty() {
+            Line::from(Span::styled("New tag", Style::default().fg(theme.grey0)))
+        } else {
+            let shown = suggestions.iter().take(MAX_SUGGESTIONS).copied().collect::<Vec<_>>().join("  ");
+            Line::from(Span::styled(
+                truncate_str(&shown, inner.width as usize),
+                Style::default().fg(theme.purple),
+            ))
+        };
+        let hint = Line::from(vec![
+            key_span("Tab", theme),
+            Span::styled(" complete  ", Style::default().fg(theme.grey1)),
+            key_span("Enter", theme),
+            Span::styled(" apply  ", Style::default().fg(theme.grey1)),
+            key_span("Esc", theme),
+            Span::styled(" cancel", Style::default().fg(theme.grey1)),
+        ]);
+
+        frame.render_widget(
+            Paragraph::new(vec![input_line, Line::from(""), status, Line::from(""), hint]),
+            inner,
+        );
+        if let Some(position) = cursor {
+            frame.set_cursor_position(position);
+        }
+    }
+
+    /// Render the share menu for an article
+    pub fn render_share_menu(frame: &mut Frame, title: &str, has_command: bool, theme: &Theme) {
+        let area = frame.area();
+        let popup_width = 44u16.min(area.width.saturating_sub(4));
+        let popup_height = (ShareTarget::ALL.len() as u16 + 5).min(area.height.saturating_sub(2));
+        let popup_area = centered_rect(popup_width, popup_height, area);
+
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .title(" Share ")
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent))
+            .style(Style::default().bg(theme.bg1));
+
+        let mut lines = vec![
+            Line::from(Span::styled(
+                truncate_str(title, popup_width.saturating_sub(4) as usize),
+                Style::default().fg(theme.fg0).add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+        ];
+        for (key, target, label) in ShareTarget::ALL {
+            let enabled = target != ShareTarget::SendCommand || has_command;
+            let label_style = if enabled {
+                Style::default().fg(theme.fg0)
+            } else {
+                Style::default().fg(theme.grey0)
+            };
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  {} ", key),
+                    Style::default().fg(theme.yellow).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(label, label_style),
+            ]));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "Esc to close",
+            Style::default().fg(theme.grey1),
+        )));
+
+        frame.render_widget(Paragraph::new(lines).block(block), popup_area);
+    }
+
+    /// Render the table of contents of the article, headings indented by
+    /// level, keeping the selected one in view
+    pub fn render_toc(frame: &mut Frame, entries: &[TocEntry], selected: usize, theme: &Theme) {
+        let area = frame.area();
+        let popup_width = (area.width * 6 / 10).max(40).min(area.width);
+        let popup_height = (entries.len() as u16 + 4).clamp(5, area.height.saturating_sub(2).max(5));
+        let popup_area = centered_rect(popup_width, popup_height.min(area.height), area);
+
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .title(" Contents ")
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent))
+            .style(Style::default().bg(theme.bg1));
+
+        let rows = popup_area.height.saturating_sub(4) as usize;
+        let first = (selected + 1).saturating_sub(rows.max(1));
+        let min_level = entries.iter().map(|e| e.level).min().unwrap_or(1);
+        let mut lines: Vec<Line> = entries
+            .iter()
+            .enumerate()
+            .skip(first)
+            .take(rows)
+            .map(|(i, entry)| {
+                let indent = "  ".repeat(entry.level.saturating_sub(min_level) as usize);
+                let text = format!(" {}{}", indent, entry.title);
+                let style = if i == selected {
+                    Style::default().fg(theme.bg0).bg(theme.accent).add_modifier(Modifier::BOLD)
+                } else if entry.level == min_level {
+                    Style::default().fg(theme.fg1)
+                } else {
+                    Style::default().fg(theme.fg0)
+                };
+                Line::from(Span::styled(truncate_str(&text, popup_width.saturating_sub(3) as usize), style))
+            })
+            .collect();
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            " Enter to jump, Esc to close",
+            Style::default().fg(theme.grey1),
+        )));
+
+        frame.render_widget(Paragraph::new(lines).block(block), popup_area);
+    }
+
+    /// Render a QR code of a URL, drawn dark-on-light so phones can scan it
+    /// on any theme
+    pub fn render_qr_code(frame: &mut Frame, url: &str, theme: &Theme) {
+        let Some(qr) = QrCode::encode(url.as_bytes()) else {
+            return;
+        };
+        let area = frame.area();
+        let rows = qr.to_half_block_lines(2);
+        let qr_width = rows.first().map(|r| r.chars().count()).unwrap_or(0) as u16;
+
+        let popup_width = (qr_width + 2).max(30);
+        let popup_height = rows.len() as u16 + 4;
+        if popup_width > area.width || popup_height > area.height {
+            Self::render_confirm(frame, "QR Code", "Terminal too small for the QR code", theme);
+            return;
+        }
+        let popup_area = centered_rect(popup_width, popup_height, area);
+
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .title(" QR Code ")
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent))
+            .style(Style::default().bg(theme.bg1));
+
+        let qr_style = Style::default().fg(Color::Black).bg(Color::White);
+        let mut lines: Vec<Line> = rows
+            .into_iter()
+            .map(|row| Line::from(Span::styled(row, qr_style)))
+            .collect();
+        lines.push(Line::from(Span::styled(
+            truncate_str(url, popup_width.saturating_sub(2) as usize),
+            Style::default().fg(theme.grey1),
+        )));
+        lines.push(Line::from(Span::styled(
+            "Press any key to close",
+            Style::default().fg(theme.grey1),
+        )));
+
+        frame.render_widget(
+            Paragraph::new(lines).alignment(Alignment::Center).block(block),
+            popup_area,
+        );
+    }
+
+    /// Render the keys that can follow a pending prefix key, in the bottom
+    /// right corner above the status bar
+    pub fn render_which_key(
+        frame: &mut Frame,
+        status_area: Rect,
+        prefix: &str,
+        entries: &[(String, String)],
+        theme: &Theme,
+    ) {
+        let area = frame.area();
+        let key_width = entries.iter().map(|(k, _)| k.chars().count()).max().unwrap_or(1);
+        let label_width = entries.iter().map(|(_, l)| l.chars().count()).max().unwrap_or(0);
+        let popup_width = ((key_width + label_width + 7) as u16)
+            .max(prefix.chars().count() as u16 + 6)
+            .min(area.width);
+        let popup_height = (entries.len() as u16 + 2).min(status_area.y.saturating_sub(area.y));
+        if popup_height < 3 {
+            return;
+        }
+        let popup_area = Rect::new(
+            area.x + area.width - popup_width,
+            status_area.y - popup_height,
+            popup_width,
+            popup_height,
+        );
+
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .title(format!(" {} ", prefix))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent))
+            .style(Style::default().bg(theme.bg1));
+
+        let lines: Vec<Line> = entries
+            .iter()
+            .map(|(key, label)| {
+                Line::from(vec![
+                    Span::styled(
+                        format!(" {:>width$} ", key, width = key_width),
+                        Style::default().fg(theme.yellow).add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled("→ ", Style::default().fg(theme.grey1)),
+                    Span::styled(label.clone(), Style::default().fg(theme.fg0)),
+                ])
+            })
+            .collect();
+
+        frame.render_widget(Paragraph::new(lines).block(block), popup_area);
+    }
+    /// Render the toasts on screen stacked in the bottom right corner above
+    /// the status bar, newest at the bottom
+    pub fn render_toasts(frame: &mut Frame, status_area: Rect, toasts: &Toasts, theme: &Theme) {
+        let area = frame.area();
+        let max_width = (area.width / 2).max(20).min(area.width) as usize;
+        let lines: Vec<Line> = toasts
+            .visible()
+            .map(|toast| {
+                let color = severity_color(toast.severity, theme);
+                Line::from(vec![
+                    Span::styled("▌ ", Style::default().fg(color)),
+                    Span::styled(
+                        truncate_str(&toast.message, max_width.saturating_sub(3).max(4)),
+                        Style::default().fg(if toast.severity == Severity::Info { theme.fg0 } else { color }),
+                    ),
+                    Span::raw(" "),
+                ])
+            })
+            .collect();
+        let available = status_area.y.saturating_sub(area.y) as usize;
+        let skip = lines.len().saturating_sub(available);
+        let lines = &lines[skip..];
+        if lines.is_empty() {
+            return;
+        }
+
+        let width = lines.iter().map(Line::width).max().unwrap_or(0) as u16;
+        let height = lines.len() as u16;
+        let toast_area = Rect::new(
+            area.x + area.width - width,
+            status_area.y - height,
+            width,
+            height,
+        );
+        frame.render_widget(Clear, toast_area);
+        frame.render_widget(
+            Paragraph::new(lines.to_vec()).style(Style::default().bg(theme.bg1)),
+            toast_area,
+        );
+    }
+
+    /// Render the history of status messages, newest first
+    pub fn render_messages(frame: &mut Frame, toasts: &Toasts, theme: &Theme) {
+        let area = frame.area();
+        let popup_width = (area.width * 7 / 10).max(40).min(area.width);
+        let popup_height = (toasts.history().count() as u16 + 4).clamp(5, area.height.saturating_sub(2).max(5));
+        let popup_area = centered_rect(popup_width, popup_height.min(area.height), area);
+
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .title(" Messages ")
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent))
+            .style(Style::default().bg(theme.bg1));
+
+        let message_width = popup_width.saturating_sub(18) as usize;
+        let rows = popup_area.height.saturating_sub(4) as usize;
+        let mut lines: Vec<Line> = toasts
+            .history()
+            .take(rows)
+            .map(|toast| {
+                Line::from(vec![
+                    Span::styled(
+                        format!(" {} ", toast.time.format("%H:%M:%S")),
+                        Style::default().fg(theme.grey1),
+                    ),
+                    Span::styled(
+                        format!("{:<5} ", toast.severity.label()),
+                        Style::default().fg(severity_color(toast.severity, theme)),
+                    ),
+                    Span::styled(
+                        truncate_str(&toast.message, message_width.max(4)),
+                        Style::default().fg(theme.fg0),
+                    ),
+                ])
+            })
+            .collect();
+        if lines.is_empty() {
+            lines.push(Line::from(Span::styled(" No messages", Style::default().fg(theme.grey1))));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            " Press any key to close",
+            Style::default().fg(theme.grey1),
+        )));
+
+        frame.render_widget(Paragraph::new(lines).block(block), popup_area);
+    }
 }
 
+fn severity_color(severity: Severity, theme: &Theme) -> Color {
+    match severity {
+        Severity::Info => theme.info,
+        Severity::Warn => theme.warning,
+        Severity::Error => theme.error,
+    }
+}
+
+
 /// Helper function to create a centered rect
-fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+pub(super) fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
     let x = area.x + (area.width.saturating_sub(width)) / 2;
     let y = area.y + (area.height.saturating_sub(height)) / 2;
     Rect::new(x, y, width, height)
 }
 
 /// Truncate a string to max length with ellipsis
-fn truncate_str(s: &str, max_len: usize) -> String {
+pub fn truncate_str(s: &str, max_len: usize) -> String {
     if s.chars().count() <= max_len {
         s.to_string()
     } else {
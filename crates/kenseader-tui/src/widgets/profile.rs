@@ -0,0 +1,149 @@
+use kenseader_core::profile::{PreferenceInsight, ProfileInsights, TimeWindow};
+use ratatui::{
+    layout::Alignment,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::popup::{centered_rect, truncate_str};
+use crate::app::App;
+use crate::theme::Theme;
+use crate::time_format::format_relative;
+
+/// Width of the preference names
+const LABEL_WIDTH: usize = 22;
+
+/// Width of the bars
+const BAR_WIDTH: usize = 24;
+
+pub struct ProfileWidget;
+
+impl ProfileWidget {
+    /// Render the interest profile learned from reading, so what drives the
+    /// relevance scores can be inspected
+    pub fn render(frame: &mut Frame, app: &App, window: TimeWindow) {
+        let theme = &app.theme;
+        let area = frame.area();
+        let width = (area.width * 8 / 10).max(50).min(area.width);
+        let height = (area.height * 8 / 10).max(12).min(area.height);
+        let popup_area = centered_rect(width, height, area);
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .title(format!(" Interest profile: {} ", window.label()))
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent))
+            .style(Style::default().bg(theme.bg1));
+
+        // Bars say nothing to a screen reader; the weights do
+        let bars = !app.config.ui.screen_reader;
+        let mut lines = match &app.profile {
+            None => vec![Line::from(Span::styled(" Loading…", Style::default().fg(theme.grey1)))],
+            Some(profile) if profile.is_empty() => vec![
+                Line::from(Span::styled(
+                    format!(" Nothing learned about your interests in the {} yet.", window.label()),
+                    Style::default().fg(theme.fg0),
+                )),
+                Line::from(Span::styled(
+                    " Preferences are computed from your reading whenever articles are scored.",
+                    Style::default().fg(theme.grey1),
+                )),
+            ],
+            Some(profile) => profile_lines(profile, bars, theme),
+        };
+
+        lines.push(Line::default());
+        lines.push(Line::from(Span::styled(
+            " Tab: next time window · any other key closes",
+            Style::default().fg(theme.grey1),
+        )));
+
+        frame.render_widget(Paragraph::new(lines).block(block), popup_area);
+    }
+}
+
+/// Sections of the profile, each preference with a bar relative to the
+/// heaviest of its section
+fn profile_lines(profile: &ProfileInsights, bars: bool, theme: &Theme) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    if let Some(at) = profile.computed_at {
+        lines.push(Line::from(Span::styled(
+            format!(" Computed {}", format_relative(&at, &chrono::Utc::now())),
+            Style::default().fg(theme.grey1),
+        )));
+    }
+
+    let sections = [
+        ("Top tags", &profile.tags, theme.accent),
+        ("Tags asked for less of", &profile.avoided_tags, theme.error),
+        ("Top feeds", &profile.feeds, theme.green),
+        ("Article styles", &profile.styles, theme.purple),
+    ];
+    for (title, prefs, color) in sections {
+        if prefs.is_empty() {
+            continue;
+        }
+        lines.push(Line::default());
+        lines.push(section_title(title, theme));
+        let max = prefs.iter().map(|p| p.weight.abs()).fold(0.0, f64::max);
+        lines.extend(
+            prefs
+                .iter()
+                .map(|pref| preference_line(pref, max, format!("{:.1}", pref.weight), bars, color, theme)),
+        );
+    }
+
+    if !profile.times.is_empty() {
+        lines.push(Line::default());
+        lines.push(section_title("Reading by time of day", theme));
+        let total: f64 = profile.times.iter().map(|t| t.weight).sum();
+        let max = profile.times.iter().map(|t| t.weight).fold(0.0, f64::max);
+        lines.extend(profile.times.iter().map(|time| {
+            let share = format!("{:.0}%", time.weight / total * 100.0);
+            preference_line(time, max, share, bars, theme.yellow, theme)
+        }));
+    }
+    lines
+}
+
+fn section_title(title: &str, theme: &Theme) -> Line<'static> {
+    Line::from(Span::styled(
+        format!(" {}", title),
+        Style::default().fg(theme.fg0).add_modifier(Modifier::BOLD),
+    ))
+}
+
+fn preference_line(
+    pref: &PreferenceInsight,
+    max: f64,
+    value: String,
+    bars: bool,
+    color: Color,
+    theme: &Theme,
+) -> Line<'static> {
+    let mut spans = vec![Span::styled(
+        format!("   {:<LABEL_WIDTH$} ", truncate_str(&pref.label, LABEL_WIDTH)),
+        Style::default().fg(theme.fg0),
+    )];
+    if bars {
+        spans.push(Span::styled(
+            format!("{:<BAR_WIDTH$} ", bar(pref.weight.abs(), max)),
+            Style::default().fg(color),
+        ));
+    }
+    spans.push(Span::styled(value, Style::default().fg(theme.grey1)));
+    Line::from(spans)
+}
+
+/// Bar scaled so that `max` fills `BAR_WIDTH`, at least one block for any
+/// positive value
+fn bar(value: f64, max: f64) -> String {
+    if value <= 0.0 || max <= 0.0 {
+        return String::new();
+    }
+    let blocks = ((value / max) * BAR_WIDTH as f64).round() as usize;
+    "█".repeat(blocks.clamp(1, BAR_WIDTH))
+}
@@ -0,0 +1,110 @@
+use ratatui::{
+    layout::Alignment,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::feed_manager::{field_line, key_span, label_span};
+use super::popup::{centered_rect, truncate_str};
+use crate::app::App;
+use crate::settings::{Setting, SettingValue};
+
+pub struct SettingsWidget;
+
+impl SettingsWidget {
+    /// Render the settings screen over the main layout
+    pub fn render(frame: &mut Frame, app: &App) {
+        let theme = &app.theme;
+        let screen = &app.settings;
+        let area = frame.area();
+        let popup_width = 72u16.min(area.width.saturating_sub(4));
+        let popup_height = (Setting::ALL.len() as u16 + 5).min(area.height.saturating_sub(2));
+        let popup_area = centered_rect(popup_width, popup_height, area);
+
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .title(" Settings ")
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent))
+            .style(Style::default().bg(theme.bg1));
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let mut lines = Vec::new();
+        let mut cursor = None;
+
+        for (row, setting) in Setting::ALL.iter().enumerate() {
+            let focused = row == screen.selected;
+            // Settings read by the daemon are marked; they apply when it restarts
+            let label = if setting.is_live() {
+                setting.label().to_string()
+            } else {
+                format!("{}*", setting.label())
+            };
+            let line = match screen.editing {
+                Some(ref text) if focused => {
+                    let (line, position) = field_line(&label, text, true, inner, row as u16, theme);
+                    cursor = position;
+                    line
+                }
+                _ => {
+                    let value = setting.value(&app.config);
+                    let shown = match value {
+                        SettingValue::Text(ref text) if setting.choices().is_some() => format!("< {} >", text),
+                        ref value => value.to_string(),
+                    };
+                    Line::from(vec![
+                        label_span(&label, focused, theme),
+                        Span::styled(
+                            shown,
+                            Style::default().fg(theme.fg0).add_modifier(if focused {
+                                Modifier::BOLD | Modifier::REVERSED
+                            } else {
+                                Modifier::empty()
+                            }),
+                        ),
+                    ])
+                }
+            };
+            lines.push(line);
+        }
+
+        lines.push(Line::from(""));
+        lines.push(match screen.error {
+            Some(ref error) => Line::from(Span::styled(
+                truncate_str(error, inner.width as usize),
+                Style::default().fg(theme.error),
+            )),
+            None => Line::from(Span::styled(
+                "Saved to config.toml on change; * applies when the daemon restarts",
+                Style::default().fg(theme.grey0),
+            )),
+        });
+        lines.push(if screen.editing.is_some() {
+            Line::from(vec![
+                key_span("Enter", theme),
+                Span::styled(" save  ", Style::default().fg(theme.grey1)),
+                key_span("Esc", theme),
+                Span::styled(" cancel", Style::default().fg(theme.grey1)),
+            ])
+        } else {
+            Line::from(vec![
+                key_span("Enter", theme),
+                Span::styled(" change  ", Style::default().fg(theme.grey1)),
+                key_span("h/l", theme),
+                Span::styled(" previous/next  ", Style::default().fg(theme.grey1)),
+                key_span("Esc", theme),
+                Span::styled(" close", Style::default().fg(theme.grey1)),
+            ])
+        });
+
+        frame.render_widget(Paragraph::new(lines), inner);
+        if let Some(position) = cursor {
+            frame.set_cursor_position(position);
+        }
+    }
+}
@@ -1,3 +1,6 @@
+use std::time::Instant;
+
+use chrono::Utc;
 use ratatui::{
     layout::Rect,
     style::Style,
@@ -7,6 +10,8 @@ use ratatui::{
 };
 
 use crate::app::{App, Focus, Mode, ViewMode};
+use crate::status_format;
+use crate::time_format;
 
 pub struct StatusBarWidget;
 
@@ -27,8 +32,18 @@ impl StatusBarWidget {
         let read_mode_prefix = if app.read_mode { "[READ] " } else { "" };
 
         let mode_str: String = if app.is_refreshing {
-            // Show animated spinner with SYNCING text
-            format!("{}{} SYNCING", read_mode_prefix, app.current_spinner())
+            // Show animated spinner with SYNCING text and the feeds fetched so far
+            let spinner = if app.config.ui.reduces_motion() {
+                String::new()
+            } else {
+                format!("{} ", app.current_spinner())
+            };
+            match app.refresh_progress {
+                Some((done, total)) if total > 0 => {
+                    format!("{}{}SYNCING {}/{}", read_mode_prefix, spinner, done, total)
+                }
+                _ => format!("{}{}SYNCING", read_mode_prefix, spinner),
+            }
         } else {
             let base_mode = match &app.mode {
                 Mode::Normal => {
@@ -45,11 +60,27 @@ impl StatusBarWidget {
                 Mode::SearchBackward(_) => "SEARCH".to_string(),
                 Mode::DeleteConfirm(_) => "CONFIRM".to_string(),
                 Mode::BatchDeleteConfirm => "CONFIRM".to_string(),
-                Mode::Help => "HELP".to_string(),
+                Mode::MarkAllReadConfirm(_) => "CONFIRM".to_string(),
+                Mode::Help(_) => "HELP".to_string(),
                 Mode::ImageViewer(_) => "IMAGE".to_string(),
+                Mode::Share => "SHARE".to_string(),
+                Mode::QrCode => "QR".to_string(),
+                Mode::Messages => "MESSAGES".to_string(),
+                Mode::FeedManager => "FEEDS".to_string(),
+                Mode::Subscribe => "SUBSCRIBE".to_string(),
+                Mode::FeedFilter => "FILTER".to_string(),
+                Mode::Triage => "TRIAGE".to_string(),
+                Mode::Tag => "TAG".to_string(),
+                Mode::Settings => "SETTINGS".to_string(),
+                Mode::Toc(_) => "CONTENTS".to_string(),
+                Mode::Profile(_) => "PROFILE".to_string(),
             };
             format!("{}{}", read_mode_prefix, base_mode)
         };
+        let mode_str = match app.macros.recording_register() {
+            Some(register) => format!("{} REC @{}", mode_str, register),
+            None => mode_str,
+        };
 
         // Selection info
         let selection_info = if selection_count > 0 {
@@ -64,10 +95,25 @@ impl StatusBarWidget {
             Focus::ArticleDetail => "Detail",
         };
 
+        // Relevance sorting and the threshold articles are hidden below
+        let relevance_info = match (app.sort_by_relevance, app.hide_low_relevance) {
+            (false, false) => String::new(),
+            (true, false) => " | By relevance".to_string(),
+            (false, true) => format!(" | Relevance ≥ {:.2}", app.relevance_threshold),
+            (true, true) => format!(" | By relevance ≥ {:.2}", app.relevance_threshold),
+        };
+
         let feed_count = app.feeds.len();
         let article_count = app.articles.len();
 
-        let status_text = if is_search_mode {
+        let status_text = if app.mode == Mode::FeedFilter {
+            format!(
+                " filter: {}_ ({} of {} feeds)",
+                app.feed_filter,
+                app.visible_feeds().len(),
+                feed_count
+            )
+        } else if is_search_mode {
             // Show search prompt with cursor and match count
             let search_char = match &app.mode {
                 Mode::SearchForward(_) => "/",
@@ -85,18 +131,21 @@ impl StatusBarWidget {
                 String::new()
             };
             format!(" {}{}_{}", search_char, app.search_query, match_info)
-        } else if let Some(msg) = &app.status_message {
-            msg.clone()
         } else {
-            format!(
-                " {} | {} | Feeds: {} | Articles: {}{}",
-                mode_str, focus_str, feed_count, article_count, selection_info
-            )
+            status_format::expand(&app.config.ui.status_left, |name, arg| match name {
+                "mode" => Some(mode_str.clone()),
+                "focus" => Some(focus_str.to_string()),
+                "feeds" => Some(feed_count.to_string()),
+                "articles" => Some(article_count.to_string()),
+                "relevance" => Some(relevance_info.clone()),
+                "selection" => Some(selection_info.clone()),
+                _ => placeholder(app, name, arg),
+            })
         };
 
-        let help_hint = " q:quit h/l:panels j/k:move /:search ?:help ";
+        let help_hint = status_format::expand(&app.config.ui.status_right, |name, arg| placeholder(app, name, arg));
         let padding_len = area.width.saturating_sub(
-            status_text.len() as u16 + help_hint.len() as u16,
+            status_text.chars().count() as u16 + help_hint.chars().count() as u16,
         ) as usize;
 
         let line = Line::from(vec![
@@ -125,4 +174,61 @@ impl StatusBarWidget {
         frame.render_widget(paragraph, area);
     }
 
+    /// Terminal window title from `ui.terminal_title`, or None when it is
+    /// empty and the title is left alone
+    pub fn terminal_title(app: &App) -> Option<String> {
+        let format = &app.config.ui.terminal_title;
+        if format.is_empty() {
+            return None;
+        }
+        let title = status_format::expand(format, |name, arg| placeholder(app, name, arg));
+        Some(if app.read_mode {
+            format!("{} (Read Mode)", title)
+        } else {
+            title
+        })
+    }
+}
+
+/// Values of the placeholders available on both sides of the status bar
+fn placeholder(app: &App, name: &str, arg: Option<&str>) -> Option<String> {
+    let value = match name {
+        "feed" => app.current_feed().map(|f| f.local_name.clone()).unwrap_or_default(),
+        "feed_unread" => app.current_feed().map(|f| f.unread_count).unwrap_or(0).to_string(),
+        "unread" => app.feeds.iter().map(|f| f.unread_count).sum::<u32>().to_string(),
+        "daemon" => {
+            if app.read_mode {
+                "read-only".to_string()
+            } else if app.client.is_local() {
+                "embedded".to_string()
+            } else if app.daemon_status.polled_at.is_some() && !app.daemon_status.reachable {
+                "disconnected".to_string()
+            } else {
+                "daemon".to_string()
+            }
+        }
+        "next_refresh" => match app.daemon_status.next_refresh_at {
+            Some(at) => status_format::format_countdown(at.saturating_duration_since(Instant::now())),
+            None => "-".to_string(),
+        },
+        "ai_queue" => app
+            .daemon_status
+            .ai_queue
+            .map(|depth| depth.to_string())
+            .unwrap_or_else(|| "-".to_string()),
+        "speech" if app.speaker.is_active() => {
+            let (sentence, total) = app.speaker.progress();
+            let state = if app.speaker.is_paused() { "Paused" } else { "Reading" };
+            format!(" | {} {}/{}", state, sentence, total)
+        }
+        "speech" => String::new(),
+        "search" if app.has_active_search() => match app.search_matches.len() {
+            0 => format!(" | /{} (no matches)", app.search_query),
+            count => format!(" | /{} ({}/{})", app.search_query, app.current_match + 1, count),
+        },
+        "search" => String::new(),
+        "clock" => time_format::format_absolute(&Utc::now(), arg.unwrap_or("%H:%M")),
+        _ => return None,
+    };
+    Some(value)
 }
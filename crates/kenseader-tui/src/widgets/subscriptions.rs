@@ -7,6 +7,7 @@ use ratatui::{
 };
 
 use crate::app::{App, Focus};
+use crate::feed_tree::FeedRow;
 
 pub struct SubscriptionsWidget;
 
@@ -21,30 +22,70 @@ impl SubscriptionsWidget {
             Style::default().fg(theme.grey0)
         };
 
-        // Use visible feeds based on view mode
-        let visible_feeds = app.visible_feeds();
+        // Visible feeds based on view mode, grouped by category
+        let rows = app.feed_rows();
 
+        let title = if app.feed_filter.is_empty() {
+            " Subscriptions ".to_string()
+        } else {
+            format!(" Subscriptions [{}] ", app.feed_filter)
+        };
         let block = Block::default()
-            .title(" Subscriptions ")
+            .title(super::pane_title(title, is_focused, app))
             .borders(Borders::ALL)
             .border_style(border_style)
             .style(Style::default().bg(theme.bg0));
 
-        // Calculate selected index in visible feeds
-        let selected_visible_idx = app.actual_to_visible_feed_index(app.selected_feed);
+        let cursor_row = app.feed_cursor_row(&rows);
+        // State in words rather than colors and symbols
+        let screen_reader = app.config.ui.screen_reader;
 
-        let items: Vec<ListItem> = visible_feeds
+        let items: Vec<ListItem> = rows
             .iter()
             .enumerate()
-            .map(|(i, feed)| {
-                // Get actual feed index to check selection
-                let actual_idx = app.visible_to_actual_feed_index(i);
-                let is_marked = actual_idx
-                    .map(|idx| app.selected_feeds.contains(&idx))
-                    .unwrap_or(false);
+            .map(|(i, row)| {
+                let is_cursor = cursor_row == Some(i);
+                let (index, nested) = match row {
+                    FeedRow::Feed { index, nested } => (*index, *nested),
+                    FeedRow::Category {
+                        name,
+                        collapsed,
+                        unread,
+                        ..
+                    } => {
+                        let marker = match (*collapsed, screen_reader) {
+                            (true, true) => "collapsed ",
+                            (false, true) => "expanded ",
+                            (true, false) => "▸ ",
+                            (false, false) => "▾ ",
+                        };
+                        let style = if is_cursor && is_focused {
+                            Style::default().fg(theme.fg0).bg(theme.selection)
+                        } else {
+                            Style::default().fg(theme.fg1)
+                        };
+                        let unread = if *unread > 0 {
+                            format!(" ({})", unread)
+                        } else {
+                            String::new()
+                        };
+                        return ListItem::new(Line::from(vec![
+                            Span::styled(marker, Style::default().fg(theme.grey1)),
+                            Span::styled(name.clone(), style.add_modifier(Modifier::BOLD)),
+                            Span::styled(unread, Style::default().fg(theme.yellow)),
+                        ]));
+                    }
+                };
+                let feed = &app.feeds[index];
+                let is_marked = app.selected_feeds.contains(&index);
 
                 // Selection marker (yazi-like)
-                let select_marker = if is_marked { "✓" } else { " " };
+                let select_marker = match (is_marked, screen_reader) {
+                    (true, true) => "marked ",
+                    (true, false) => "✓",
+                    (false, true) => "",
+                    (false, false) => " ",
+                };
 
                 let unread = if feed.unread_count > 0 {
                     format!(" ({})", feed.unread_count)
@@ -53,7 +94,7 @@ impl SubscriptionsWidget {
                 };
 
                 let name = &feed.local_name;
-                let is_cursor = selected_visible_idx == Some(i);
+                let indent = if nested { "  " } else { "" };
 
                 // Determine style based on feed state
                 // Priority: marked > cursor > error > unread > read
@@ -70,6 +111,8 @@ impl SubscriptionsWidget {
                 } else if feed.has_error() {
                     // Feeds with fetch errors are shown in red
                     Style::default().fg(theme.error)
+                } else if feed.paused {
+                    Style::default().fg(theme.grey0)
                 } else if feed.unread_count > 0 {
                     Style::default().fg(theme.unread)
                 } else {
@@ -83,12 +126,29 @@ impl SubscriptionsWidget {
                 };
 
                 // Add error indicator for feeds with errors
-                let error_indicator = if feed.has_error() { " !" } else { "" };
+                let error_indicator = match (feed.has_error(), screen_reader) {
+                    (true, true) => " (error)",
+                    (true, false) => " !",
+                    (false, _) => "",
+                };
+                let paused_indicator = match (feed.paused, screen_reader) {
+                    (true, true) => " (paused)",
+                    (true, false) => " ‖",
+                    (false, _) => "",
+                };
+                let pin_marker = match (feed.pinned, screen_reader) {
+                    (true, true) => "pinned ",
+                    (true, false) => "▴",
+                    (false, _) => "",
+                };
 
                 let line = Line::from(vec![
+                    Span::raw(indent),
                     Span::styled(select_marker, select_style),
+                    Span::styled(pin_marker, Style::default().fg(theme.accent)),
                     Span::styled(name.clone(), style),
                     Span::styled(error_indicator, Style::default().fg(theme.error)),
+                    Span::styled(paused_indicator, Style::default().fg(theme.grey1)),
                     Span::styled(unread, Style::default().fg(theme.yellow)),
                 ]);
 
@@ -103,9 +163,10 @@ impl SubscriptionsWidget {
                     .bg(theme.selection)
                     .add_modifier(Modifier::BOLD),
             );
+        let list = if screen_reader { list.highlight_symbol("> ") } else { list };
 
         let mut state = ListState::default();
-        state.select(selected_visible_idx);
+        state.select(cursor_row);
 
         frame.render_stateful_widget(list, area, &mut state);
     }
@@ -0,0 +1,146 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use super::article_detail::wrap_text_unicode;
+use super::popup::{centered_rect, truncate_str};
+use crate::app::App;
+use crate::triage::Verdict;
+
+/// Lines of summary shown under each title
+const SNIPPET_LINES: usize = 2;
+/// Title, snippet and a blank line
+const ROW_HEIGHT: usize = SNIPPET_LINES + 2;
+
+pub struct TriageWidget;
+
+impl TriageWidget {
+    /// Render the triage view over the main layout: titles with their AI
+    /// summary, and the verdict given to each
+    pub fn render(frame: &mut Frame, app: &App) {
+        let theme = &app.theme;
+        let area = frame.area();
+        let popup_area = centered_rect(
+            area.width.saturating_sub(4),
+            area.height.saturating_sub(2),
+            area,
+        );
+
+        frame.render_widget(Clear, popup_area);
+
+        let total = app.articles.len();
+        let position = if total == 0 { 0 } else { app.selected_article + 1 };
+        let block = Block::default()
+            .title(format!(" Triage {}/{} · {} ", position, total, app.triage.summary()))
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent))
+            .style(Style::default().bg(theme.bg0));
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1)])
+            .split(inner);
+
+        // Only build the rows that fit, keeping the cursor on the last one
+        // when scrolling down
+        let visible_rows = (chunks[0].height as usize / ROW_HEIGHT).max(1);
+        let offset = app.selected_article.saturating_sub(visible_rows - 1);
+        let snippet_width = (chunks[0].width as usize).saturating_sub(4).max(10);
+
+        let items: Vec<ListItem> = app
+            .articles
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(visible_rows)
+            .map(|(i, article)| {
+                let verdict = app.triage.verdict(article.id);
+                let title_style = if article.is_read {
+                    Style::default().fg(theme.read)
+                } else {
+                    Style::default().fg(theme.unread).add_modifier(Modifier::BOLD)
+                };
+
+                let mut title = vec![
+                    Span::styled(if article.is_read { "  " } else { "● " }, Style::default().fg(theme.yellow)),
+                    Span::styled(if article.is_saved { "★ " } else { "  " }, Style::default().fg(theme.orange)),
+                ];
+                if let Some(verdict) = verdict {
+                    let color = match verdict {
+                        Verdict::Keep => theme.green,
+                        Verdict::Save => theme.orange,
+                        Verdict::Dismiss => theme.grey1,
+                    };
+                    title.push(Span::styled(format!("[{}] ", verdict.label()), Style::default().fg(color)));
+                }
+                if let Some(feed) = app.feeds.iter().find(|f| f.id == article.feed_id) {
+                    title.push(Span::styled(
+                        format!("{} · ", feed.local_name),
+                        Style::default().fg(theme.blue),
+                    ));
+                }
+                title.push(Span::styled(article.title.clone(), title_style));
+                if let Some(score) = article.relevance_score {
+                    title.push(Span::styled(format!("  {:.2}", score), Style::default().fg(theme.yellow)));
+                }
+
+                // The AI summary, or the start of the article until it is summarized
+                let (snippet, snippet_style) = match &article.summary {
+                    Some(summary) => (summary.clone(), Style::default().fg(theme.fg0)),
+                    None => (
+                        article.content_preview(snippet_width * SNIPPET_LINES),
+                        Style::default().fg(theme.grey1).add_modifier(Modifier::ITALIC),
+                    ),
+                };
+                let mut wrapped = wrap_text_unicode(&snippet, snippet_width);
+                if wrapped.len() > SNIPPET_LINES {
+                    wrapped.truncate(SNIPPET_LINES);
+                    let last = &mut wrapped[SNIPPET_LINES - 1];
+                    *last = format!("{}...", truncate_str(last, snippet_width.saturating_sub(3)));
+                }
+
+                let mut lines = vec![Line::from(title)];
+                lines.extend(
+                    wrapped
+                        .into_iter()
+                        .map(|line| Line::from(Span::styled(format!("    {}", line), snippet_style))),
+                );
+                while lines.len() < ROW_HEIGHT {
+                    lines.push(Line::from(""));
+                }
+                let item = ListItem::new(lines);
+                if verdict == Some(Verdict::Dismiss) && i != app.selected_article {
+                    item.style(Style::default().add_modifier(Modifier::DIM))
+                } else {
+                    item
+                }
+            })
+            .collect();
+
+        if items.is_empty() {
+            frame.render_widget(
+                Paragraph::new("No articles to triage").style(Style::default().fg(theme.grey1)),
+                chunks[0],
+            );
+        } else {
+            let list = List::new(items).highlight_style(Style::default().bg(theme.selection));
+            let mut state = ListState::default();
+            state.select(Some(app.selected_article - offset));
+            frame.render_stateful_widget(list, chunks[0], &mut state);
+        }
+
+        let hints = Paragraph::new(Line::from(Span::styled(
+            " j/k move  Space keep  s save  d dismiss  u undo  Enter read  Esc done ",
+            Style::default().fg(theme.grey1),
+        )))
+        .alignment(Alignment::Center);
+        frame.render_widget(hints, chunks[1]);
+    }
+}